@@ -0,0 +1,12 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Grammar-aware DDLs to the SQL parser, reaching deeper than random strings.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use springql_core_fuzz::{assert_no_parser_bug, Ddl};
+
+fuzz_target!(|ddl: Ddl| {
+    assert_no_parser_bug(&ddl.to_string());
+});
@@ -0,0 +1,17 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Arbitrary `OPTIONS (...)` to each type of options.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use springql_core::api::fuzzing::parse_options;
+use springql_core_fuzz::OptionKey;
+
+fuzz_target!(|key_values: Vec<(OptionKey, String)>| {
+    let key_values = key_values
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value))
+        .collect::<Vec<_>>();
+    parse_options(&key_values);
+});
@@ -0,0 +1,412 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Grammar-aware generator of DDLs for fuzz targets.
+//!
+//! Random strings rarely get past `CREATE`. Generated DDLs follow the grammar (`springql.pest`) except for
+//! names, constants and option values, which are arbitrary to hit both valid and malformed inputs.
+
+use std::fmt;
+
+use arbitrary::Arbitrary;
+use springql_core::api::fuzzing::parse_sql;
+
+/// Parses `sql` and panics if the error is from a bug of the parser, not from the input.
+///
+/// Panics inside the parser also abort a fuzz target.
+pub fn assert_no_parser_bug(sql: &str) {
+    if let Err(e) = parse_sql(sql) {
+        let message = format!("{:?}", e);
+        assert!(!message.contains("parser bug"), "{}\nSQL: {}", message, sql);
+    }
+}
+
+#[derive(Arbitrary, Debug)]
+pub enum Ddl {
+    CreateSourceStream(StreamDefinition),
+    CreateStream(StreamDefinition),
+    CreateSinkStream(StreamDefinition),
+    CreatePump(Pump),
+    CreateSourceReader {
+        name: Name,
+        stream: Name,
+        reader_type: Name,
+        options: Vec<OptionSpecification>,
+    },
+    CreateSinkWriter {
+        name: Name,
+        stream: Name,
+        filter: Option<Expr>,
+        writer_type: Name,
+        options: Vec<OptionSpecification>,
+    },
+}
+
+impl fmt::Display for Ddl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Ddl::CreateSourceStream(stream) => write!(f, "CREATE SOURCE STREAM {}", stream),
+            Ddl::CreateStream(stream) => write!(f, "CREATE STREAM {}", stream),
+            Ddl::CreateSinkStream(stream) => write!(f, "CREATE SINK STREAM {}", stream),
+            Ddl::CreatePump(pump) => write!(f, "{}", pump),
+            Ddl::CreateSourceReader {
+                name,
+                stream,
+                reader_type,
+                options,
+            } => {
+                write!(
+                    f,
+                    "CREATE SOURCE READER {} FOR {} TYPE {}",
+                    name, stream, reader_type
+                )?;
+                write_options(f, options)
+            }
+            Ddl::CreateSinkWriter {
+                name,
+                stream,
+                filter,
+                writer_type,
+                options,
+            } => {
+                write!(f, "CREATE SINK WRITER {} FOR {}", name, stream)?;
+                if let Some(filter) = filter {
+                    write!(f, " FILTER WHERE {}", filter)?;
+                }
+                write!(f, " TYPE {}", writer_type)?;
+                write_options(f, options)
+            }
+        }?;
+        write!(f, ";")
+    }
+}
+
+#[derive(Arbitrary, Debug)]
+pub struct StreamDefinition {
+    name: Name,
+    first_column: ColumnDefinition,
+    columns: Vec<ColumnDefinition>,
+}
+
+impl fmt::Display for StreamDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}", self.name, self.first_column)?;
+        for column in &self.columns {
+            write!(f, ", {}", column)?;
+        }
+        write!(f, ")")
+    }
+}
+
+#[derive(Arbitrary, Debug)]
+pub struct ColumnDefinition {
+    name: Name,
+    data_type: DataType,
+    not_null: bool,
+    rowtime: bool,
+}
+
+impl fmt::Display for ColumnDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.name, self.data_type)?;
+        if self.not_null {
+            write!(f, " NOT NULL")?;
+        }
+        if self.rowtime {
+            write!(f, " ROWTIME")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Arbitrary, Debug)]
+pub enum DataType {
+    SmallInt,
+    Integer,
+    BigInt,
+    UnsignedInteger,
+    Float,
+    Boolean,
+    Text,
+    Blob,
+    Timestamp,
+}
+
+impl fmt::Display for DataType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            DataType::SmallInt => "SMALLINT",
+            DataType::Integer => "INTEGER",
+            DataType::BigInt => "BIGINT",
+            DataType::UnsignedInteger => "UNSIGNED INTEGER",
+            DataType::Float => "FLOAT",
+            DataType::Boolean => "BOOLEAN",
+            DataType::Text => "TEXT",
+            DataType::Blob => "BLOB",
+            DataType::Timestamp => "TIMESTAMP",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Arbitrary, Debug)]
+pub struct Pump {
+    name: Name,
+    downstream: Name,
+    first_column: Name,
+    columns: Vec<Name>,
+    first_field: Expr,
+    fields: Vec<Expr>,
+    upstream: Name,
+    condition: Option<Expr>,
+    window: Option<Window>,
+}
+
+impl fmt::Display for Pump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "CREATE PUMP {} AS INSERT INTO {} ({}",
+            self.name, self.downstream, self.first_column
+        )?;
+        for column in &self.columns {
+            write!(f, ", {}", column)?;
+        }
+        write!(f, ") SELECT STREAM {}", self.first_field)?;
+        for field in &self.fields {
+            write!(f, ", {}", field)?;
+        }
+        write!(f, " FROM {}", self.upstream)?;
+        if let Some(condition) = &self.condition {
+            write!(f, " WHERE {}", condition)?;
+        }
+        if let Some(window) = &self.window {
+            write!(f, " {}", window)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Arbitrary, Debug)]
+pub enum Window {
+    Fixed {
+        length: u16,
+        allowed_delay: u16,
+    },
+    Sliding {
+        length: u16,
+        period: u16,
+        allowed_delay: u16,
+    },
+}
+
+impl fmt::Display for Window {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Window::Fixed {
+                length,
+                allowed_delay,
+            } => write!(
+                f,
+                "FIXED WINDOW DURATION_SECS({}), DURATION_SECS({})",
+                length, allowed_delay
+            ),
+            Window::Sliding {
+                length,
+                period,
+                allowed_delay,
+            } => write!(
+                f,
+                "SLIDING WINDOW DURATION_SECS({}), DURATION_SECS({}), DURATION_SECS({})",
+                length, period, allowed_delay
+            ),
+        }
+    }
+}
+
+/// Value expression. Depth is bounded by the input size of the fuzzer.
+#[derive(Arbitrary, Debug)]
+pub enum Expr {
+    Null,
+    Integer(i64),
+    Float(f32),
+    Boolean(bool),
+    Text(String),
+    Column { correlation: Name, column: Name },
+    Minus(Box<Expr>),
+    Binary(Box<Expr>, BinaryOperator, Box<Expr>),
+    Function(FunctionName, Box<Expr>, Vec<Expr>),
+    Paren(Box<Expr>),
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Null => write!(f, "NULL"),
+            Expr::Integer(i) => write!(f, "{}", i),
+            Expr::Float(v) => write!(f, "{:?}", v),
+            Expr::Boolean(b) => write!(f, "{}", b),
+            Expr::Text(s) => write!(f, "'{}'", s),
+            Expr::Column {
+                correlation,
+                column,
+            } => write!(f, "{}.{}", correlation, column),
+            // spaced not to be `--` (line comment) with a negative constant
+            Expr::Minus(expr) => write!(f, "- {}", expr),
+            Expr::Binary(left, op, right) => write!(f, "{} {} {}", left, op, right),
+            Expr::Function(name, first_arg, args) => {
+                write!(f, "{}({}", name, first_arg)?;
+                for arg in args {
+                    write!(f, ", {}", arg)?;
+                }
+                write!(f, ")")
+            }
+            Expr::Paren(expr) => write!(f, "({})", expr),
+        }
+    }
+}
+
+#[derive(Arbitrary, Debug)]
+pub enum BinaryOperator {
+    Equal,
+    Add,
+    Mul,
+    And,
+    Like,
+    IsDistinctFrom,
+    IsNotDistinctFrom,
+}
+
+impl fmt::Display for BinaryOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            BinaryOperator::Equal => "=",
+            BinaryOperator::Add => "+",
+            BinaryOperator::Mul => "*",
+            BinaryOperator::And => "AND",
+            BinaryOperator::Like => "LIKE",
+            BinaryOperator::IsDistinctFrom => "IS DISTINCT FROM",
+            BinaryOperator::IsNotDistinctFrom => "IS NOT DISTINCT FROM",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Arbitrary, Debug)]
+pub enum FunctionName {
+    Coalesce,
+    DurationMillis,
+    DurationSecs,
+    FloorTime,
+    Floor,
+    NullIf,
+    RegexpMatch,
+}
+
+impl fmt::Display for FunctionName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            FunctionName::Coalesce => "COALESCE",
+            FunctionName::DurationMillis => "DURATION_MILLIS",
+            FunctionName::DurationSecs => "DURATION_SECS",
+            FunctionName::FloorTime => "FLOOR_TIME",
+            FunctionName::Floor => "FLOOR",
+            FunctionName::NullIf => "NULLIF",
+            FunctionName::RegexpMatch => "REGEXP_MATCH",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Arbitrary, Debug)]
+pub struct OptionSpecification {
+    key: OptionKey,
+    value: String,
+}
+
+impl fmt::Display for OptionSpecification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} '{}'", self.key, self.value)
+    }
+}
+
+/// Mostly keys known to option parsers.
+#[derive(Arbitrary, Debug)]
+pub enum OptionKey {
+    Known(KnownOptionKey),
+    Other(Name),
+}
+
+impl fmt::Display for OptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OptionKey::Known(key) => write!(f, "{}", key.0),
+            OptionKey::Other(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct KnownOptionKey(&'static str);
+
+impl<'a> Arbitrary<'a> for KnownOptionKey {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.choose(KNOWN_OPTION_KEYS).map(|&key| Self(key))
+    }
+}
+
+const KNOWN_OPTION_KEYS: &[&str] = &[
+    "CSV_COLUMNS",
+    "CSV_DELIMITER",
+    "CSV_HEADER",
+    "FORMAT",
+    "JSON_LAYOUT",
+    "JSON_RECORDS_PATH",
+    "NAME",
+    "PORT",
+    "PROTOCOL",
+    "REMOTE_HOST",
+    "REMOTE_PORT",
+    "TOPIC",
+    "URL",
+];
+
+/// Identifier, or arbitrary string to be rejected.
+#[derive(Debug)]
+pub struct Name(String);
+
+impl<'a> Arbitrary<'a> for Name {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        if u.ratio(1, 8)? {
+            return String::arbitrary(u).map(Self);
+        }
+        let len = u.int_in_range(1..=8)?;
+        let name = (0..len)
+            .map(|i| {
+                let chars: &[u8] = if i == 0 {
+                    b"abcdefghijklmnopqrstuvwxyz_"
+                } else {
+                    b"abcdefghijklmnopqrstuvwxyz_0123456789"
+                };
+                u.choose(chars).map(|&c| c as char)
+            })
+            .collect::<arbitrary::Result<String>>()?;
+        Ok(Self(name))
+    }
+}
+
+impl fmt::Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+fn write_options(f: &mut fmt::Formatter<'_>, options: &[OptionSpecification]) -> fmt::Result {
+    if let Some((first, rest)) = options.split_first() {
+        write!(f, " OPTIONS ({}", first)?;
+        for option in rest {
+            write!(f, ", {}", option)?;
+        }
+        write!(f, ")")?;
+    }
+    Ok(())
+}
@@ -0,0 +1,234 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Instrumentation to validate memory accounting by `MemSize`.
+//!
+//! With `alloc-instrumentation` feature, `InstrumentedAllocator` tags each allocation with the site (`AllocSite`) of the current thread
+//! and keeps live bytes per site. Performance monitor worker compares them with the bytes accounted in queues and windows and reports drift.
+//!
+//! Without the feature, `AllocScope` is a no-op and no live bytes are available.
+
+#[cfg(feature = "alloc-instrumentation")]
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    sync::atomic::{AtomicBool, AtomicI64, Ordering},
+};
+
+/// Drift [%] over which the performance monitor warns.
+pub const ALLOC_DRIFT_WARN_PERCENT: f32 = 30.0;
+
+/// Accounted bytes under which drift is not warned (allocator and per-row overheads dominate).
+const ALLOC_DRIFT_MIN_ACCOUNTED_BYTES: u64 = 1_000_000;
+
+/// Where an allocation is attributed to.
+///
+/// Allocations are attributed by where they are made, not where the memory ends up.
+/// Rows are allocated while tasks run, then moved into queues; window states are allocated while windows dispatch rows.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum AllocSite {
+    /// Outside of task execution.
+    Other = 0,
+    /// Task execution (rows put into queues).
+    Queue = 1,
+    /// Window operations (rows and aggregation states in panes).
+    Window = 2,
+}
+
+impl AllocSite {
+    #[cfg(feature = "alloc-instrumentation")]
+    const ALL: [AllocSite; 3] = [AllocSite::Other, AllocSite::Queue, AllocSite::Window];
+
+    #[cfg(feature = "alloc-instrumentation")]
+    fn from_tag(tag: u8) -> Self {
+        Self::ALL[tag as usize]
+    }
+}
+
+/// Attributes allocations made by the current thread to a site until dropped.
+#[derive(Debug)]
+pub struct AllocScope {
+    #[cfg(feature = "alloc-instrumentation")]
+    prev: AllocSite,
+}
+
+impl AllocScope {
+    #[cfg(feature = "alloc-instrumentation")]
+    pub fn enter(site: AllocSite) -> Self {
+        let prev = CURRENT_SITE
+            .try_with(|current| current.replace(site))
+            .unwrap_or(AllocSite::Other);
+        Self { prev }
+    }
+
+    #[cfg(not(feature = "alloc-instrumentation"))]
+    pub fn enter(_site: AllocSite) -> Self {
+        Self {}
+    }
+}
+
+#[cfg(feature = "alloc-instrumentation")]
+impl Drop for AllocScope {
+    fn drop(&mut self) {
+        let _ = CURRENT_SITE.try_with(|current| current.set(self.prev));
+    }
+}
+
+/// Difference between live bytes tracked by `InstrumentedAllocator` and bytes accounted by `MemSize`.
+#[derive(Clone, Eq, PartialEq, Debug, new)]
+pub struct AllocDrift {
+    pub tracked_queue_bytes: u64,
+    pub tracked_window_bytes: u64,
+    pub accounted_bytes: u64,
+}
+
+impl AllocDrift {
+    /// # Returns
+    ///
+    /// `None` if `InstrumentedAllocator` is not the global allocator.
+    #[cfg(feature = "alloc-instrumentation")]
+    pub fn measure(accounted_bytes: u64) -> Option<Self> {
+        INSTALLED.load(Ordering::Relaxed).then(|| {
+            Self::new(
+                live_bytes(AllocSite::Queue),
+                live_bytes(AllocSite::Window),
+                accounted_bytes,
+            )
+        })
+    }
+
+    /// # Returns
+    ///
+    /// `None` always (`alloc-instrumentation` feature is disabled).
+    #[cfg(not(feature = "alloc-instrumentation"))]
+    pub fn measure(_accounted_bytes: u64) -> Option<Self> {
+        None
+    }
+
+    pub fn tracked_bytes(&self) -> u64 {
+        self.tracked_queue_bytes + self.tracked_window_bytes
+    }
+
+    /// (tracked - accounted) / tracked [%]
+    pub fn drift_percent(&self) -> f32 {
+        let tracked = self.tracked_bytes();
+        if tracked == 0 {
+            0.0
+        } else {
+            (tracked as f32 - self.accounted_bytes as f32) / tracked as f32 * 100.0
+        }
+    }
+
+    pub fn should_warn(&self) -> bool {
+        self.accounted_bytes.max(self.tracked_bytes()) >= ALLOC_DRIFT_MIN_ACCOUNTED_BYTES
+            && self.drift_percent().abs() > ALLOC_DRIFT_WARN_PERCENT
+    }
+}
+
+#[cfg(feature = "alloc-instrumentation")]
+thread_local! {
+    static CURRENT_SITE: Cell<AllocSite> = Cell::new(AllocSite::Other);
+}
+
+#[cfg(feature = "alloc-instrumentation")]
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+#[cfg(feature = "alloc-instrumentation")]
+static LIVE_BYTES: [AtomicI64; 3] = [AtomicI64::new(0), AtomicI64::new(0), AtomicI64::new(0)];
+
+#[cfg(feature = "alloc-instrumentation")]
+fn live_bytes(site: AllocSite) -> u64 {
+    LIVE_BYTES[site as usize].load(Ordering::Relaxed).max(0) as u64
+}
+
+/// Global allocator to validate memory accounting (`alloc-instrumentation` feature).
+///
+/// Each allocation has a small header to remember its site, so this allocator is for diagnostics, not for production.
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOC: springql::InstrumentedAllocator = springql::InstrumentedAllocator;
+/// ```
+#[cfg(feature = "alloc-instrumentation")]
+#[derive(Debug)]
+pub struct InstrumentedAllocator;
+
+#[cfg(feature = "alloc-instrumentation")]
+impl InstrumentedAllocator {
+    /// Header is as large as the alignment so that the returned pointer keeps it.
+    fn padded(layout: Layout) -> Option<(Layout, usize)> {
+        let header = layout.align().max(1);
+        let size = layout.size().checked_add(header)?;
+        Layout::from_size_align(size, layout.align())
+            .ok()
+            .map(|padded| (padded, header))
+    }
+}
+
+#[cfg(feature = "alloc-instrumentation")]
+unsafe impl GlobalAlloc for InstrumentedAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+
+        let (padded, header) = match Self::padded(layout) {
+            Some(p) => p,
+            None => return std::ptr::null_mut(),
+        };
+        let base = System.alloc(padded);
+        if base.is_null() {
+            return base;
+        }
+
+        let site = CURRENT_SITE
+            .try_with(|current| current.get())
+            .unwrap_or(AllocSite::Other);
+        LIVE_BYTES[site as usize].fetch_add(layout.size() as i64, Ordering::Relaxed);
+
+        let ptr = base.add(header);
+        *ptr.sub(1) = site as u8;
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let (padded, header) = Self::padded(layout).expect("allocated with the same layout");
+
+        let site = AllocSite::from_tag(*ptr.sub(1));
+        LIVE_BYTES[site as usize].fetch_sub(layout.size() as i64, Ordering::Relaxed);
+
+        System.dealloc(ptr.sub(header), padded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drift_percent() {
+        let drift = AllocDrift::new(600, 400, 800);
+        assert_eq!(drift.tracked_bytes(), 1000);
+        assert!((drift.drift_percent() - 20.0).abs() < f32::EPSILON);
+
+        assert!(!AllocDrift::new(600, 400, 100).should_warn()); // too small to judge
+        assert!(AllocDrift::new(6_000_000, 4_000_000, 1_000_000).should_warn());
+        assert!(!AllocDrift::new(6_000_000, 4_000_000, 9_000_000).should_warn());
+    }
+
+    #[cfg(feature = "alloc-instrumentation")]
+    #[test]
+    fn test_instrumented_allocator() {
+        let allocator = InstrumentedAllocator;
+        let layout = Layout::from_size_align(1024, 16).unwrap();
+
+        let before = live_bytes(AllocSite::Window);
+        let ptr = {
+            let _scope = AllocScope::enter(AllocSite::Window);
+            unsafe { allocator.alloc(layout) }
+        };
+        assert_eq!(ptr as usize % 16, 0);
+        assert_eq!(live_bytes(AllocSite::Window), before + 1024);
+
+        // dealloc outside of the scope is still attributed to the window
+        unsafe { allocator.dealloc(ptr, layout) };
+        assert_eq!(live_bytes(AllocSite::Window), before);
+    }
+}
@@ -1,19 +1,29 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 pub mod error;
+/// Entry points for fuzz targets.
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
+/// proptest strategies for property-based tests.
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 
 mod spring_config;
 mod spring_pipeline;
 mod spring_sink_row;
 mod spring_source_row;
+mod spring_stream_tap;
+mod spring_task_hook;
 
 pub use crate::{
     api::{
-        error::{Result, SpringError},
+        error::{Result, SpringError, SpringErrorClass},
         spring_config::*,
         spring_pipeline::SpringPipeline,
-        spring_sink_row::SpringSinkRow,
+        spring_sink_row::{SpringColumn, SpringSinkRow},
         spring_source_row::{SpringSourceRow, SpringSourceRowBuilder},
+        spring_stream_tap::SpringStreamTap,
+        spring_task_hook::{SpringTaskExecution, SpringTaskHook, SpringTaskInfo, SpringTaskKind},
         SpringConfig,
     },
     stream_engine::{
@@ -21,3 +31,6 @@ pub use crate::{
         SpringValue,
     },
 };
+
+#[cfg(feature = "alloc-instrumentation")]
+pub use crate::alloc_instrumentation::InstrumentedAllocator;
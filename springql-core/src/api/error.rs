@@ -42,6 +42,14 @@ pub enum SpringError {
     #[error("invalid config")]
     InvalidConfig { source: anyhow::Error },
 
+    #[error("invalid config value (path `{path}`, value `{value}`)")]
+    InvalidConfigValue {
+        /// TOML path (e.g. `memory.upper_limit_bytes`)
+        path: String,
+        value: String,
+        source: anyhow::Error,
+    },
+
     #[error("invalid option (key `{key:?}`, value `{value:?}`)")]
     InvalidOption {
         key: String,
@@ -61,7 +69,10 @@ pub enum SpringError {
     #[error("SQL error")]
     Sql(anyhow::Error),
 
-    /// Occurs only when a value is fetched from a SpringSinkRow.
+    #[error("statement `{statement}` is not allowed by `permission.role`")]
+    PermissionDenied { statement: String },
+
+    /// Occurs when a value is fetched from a SpringSinkRow, or when a sink writer requires a value in the column (e.g. `BLOB_BODY_COLUMN`).
     #[error("unexpectedly got NULL")]
     Null {
         /// Column index
@@ -71,3 +82,116 @@ pub enum SpringError {
     #[error("Time conversion error {0}")]
     Time(TimeError),
 }
+
+/// How a worker should treat a `SpringError` it got.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum SpringErrorClass {
+    /// Caused by a foreign system or by no input having arrived yet. Retrying the same operation later may succeed.
+    Recoverable,
+
+    /// Caused by a row, a value, or an option given by users, or by a resource not available in this build.
+    /// The same input fails again but other inputs are not affected.
+    Invalid,
+
+    /// An invariant inside SpringQL-core is violated (e.g. a thread panicked while holding a lock).
+    /// Results after this error are not reliable, so the next `SpringPipeline` method call returns it.
+    Fatal,
+}
+
+impl SpringError {
+    /// Stable identifier of the variant (e.g. `SQL`, `INVALID_FORMAT`) for structured logs and metrics.
+    pub fn code(&self) -> &'static str {
+        match self {
+            SpringError::ForeignIo { .. } => "FOREIGN_IO",
+            SpringError::ForeignSourceTimeout { .. } => "FOREIGN_SOURCE_TIMEOUT",
+            SpringError::InputTimeout { .. } => "INPUT_TIMEOUT",
+            SpringError::SpringQlCoreIo(_) => "SPRINGQL_CORE_IO",
+            SpringError::ThreadPoisoned(_) => "THREAD_POISONED",
+            SpringError::InvalidConfig { .. } => "INVALID_CONFIG",
+            SpringError::InvalidConfigValue { .. } => "INVALID_CONFIG_VALUE",
+            SpringError::InvalidOption { .. } => "INVALID_OPTION",
+            SpringError::InvalidFormat { .. } => "INVALID_FORMAT",
+            SpringError::Unavailable { .. } => "UNAVAILABLE",
+            SpringError::Sql(_) => "SQL",
+            SpringError::PermissionDenied { .. } => "PERMISSION_DENIED",
+            SpringError::Null { .. } => "NULL",
+            SpringError::Time(_) => "TIME",
+        }
+    }
+
+    /// Classification of this error, which the autonomous executor uses to decide how to handle it.
+    pub fn class(&self) -> SpringErrorClass {
+        match self {
+            SpringError::ForeignIo { .. }
+            | SpringError::ForeignSourceTimeout { .. }
+            | SpringError::InputTimeout { .. } => SpringErrorClass::Recoverable,
+
+            SpringError::InvalidConfig { .. }
+            | SpringError::InvalidConfigValue { .. }
+            | SpringError::InvalidOption { .. }
+            | SpringError::InvalidFormat { .. }
+            | SpringError::Unavailable { .. }
+            | SpringError::Sql(_)
+            | SpringError::PermissionDenied { .. }
+            | SpringError::Null { .. }
+            | SpringError::Time(_) => SpringErrorClass::Invalid,
+
+            SpringError::SpringQlCoreIo(_) | SpringError::ThreadPoisoned(_) => {
+                SpringErrorClass::Fatal
+            }
+        }
+    }
+
+    /// Whether retrying the operation later may succeed.
+    pub fn is_recoverable(&self) -> bool {
+        self.class() == SpringErrorClass::Recoverable
+    }
+
+    /// Whether a foreign system (e.g. the remote endpoint of a sink writer) is unreachable or does not respond.
+    ///
+    /// Unlike `is_recoverable()`, `InputTimeout` (no input from a stream yet) is not included.
+    pub fn is_foreign_unreachable(&self) -> bool {
+        matches!(
+            self,
+            SpringError::ForeignIo { .. } | SpringError::ForeignSourceTimeout { .. }
+        )
+    }
+
+    /// Whether an invariant inside SpringQL-core is violated.
+    pub fn is_fatal(&self) -> bool {
+        self.class() == SpringErrorClass::Fatal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+
+    use super::*;
+
+    #[test]
+    fn test_class() {
+        let foreign_io = SpringError::ForeignIo {
+            foreign_info: ForeignInfo::GenericTcp("127.0.0.1:1".parse().unwrap()),
+            source: anyhow!("connection refused"),
+        };
+        assert!(foreign_io.is_recoverable());
+        assert!(foreign_io.is_foreign_unreachable());
+        assert!(!foreign_io.is_fatal());
+
+        let input_timeout = SpringError::InputTimeout {
+            task_name: "pu_a".to_string(),
+            source: anyhow!("no input"),
+        };
+        assert!(input_timeout.is_recoverable());
+        assert!(!input_timeout.is_foreign_unreachable());
+
+        let sql = SpringError::Sql(anyhow!("invalid regular expression"));
+        assert_eq!(sql.class(), SpringErrorClass::Invalid);
+        assert!(!sql.is_recoverable());
+        assert!(!sql.is_fatal());
+
+        let poisoned = SpringError::ThreadPoisoned(anyhow!("poisoned"));
+        assert!(poisoned.is_fatal());
+    }
+}
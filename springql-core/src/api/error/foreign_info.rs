@@ -5,32 +5,90 @@
 use std::{
     fmt::{Debug, Display},
     net::SocketAddr,
+    path::PathBuf,
 };
 
-use crate::pipeline::QueueName;
+use crate::pipeline::{QueueName, StreamName};
 
 /// Foreign system information for error reporting.
+#[derive(Clone)]
 pub enum ForeignInfo {
     /// Generic TCP connection.
     GenericTcp(SocketAddr),
 
+    /// UDP socket (local address)
+    GenericUdp(SocketAddr),
+
     /// HTTP client
     Http(SocketAddr),
 
+    /// HTTP endpoint (URL)
+    HttpEndpoint(String),
+
     /// Socket CAN interface
     SocketCAN(String),
 
     /// In memory queue
     InMemoryQueue(QueueName),
+
+    /// Local file
+    File(PathBuf),
+
+    /// Local command
+    Command(PathBuf),
+
+    /// MQTT broker (host:port)
+    Mqtt(String),
+
+    /// OPC UA server (endpoint URL)
+    OpcUa(String),
+
+    /// ROS2 topic (over DDS)
+    Ros2(String),
+
+    /// Redis server (host:port or UNIX socket path)
+    Redis(String),
+
+    /// NATS servers (URLs)
+    Nats(String),
+
+    /// Kafka brokers (host:port)
+    Kafka(String),
+
+    /// ZeroMQ endpoints
+    ZeroMq(String),
+
+    /// S3-compatible object storage (endpoint URL and bucket)
+    ObjectStorage(String),
+
+    /// UNIX domain socket
+    UnixSocket(PathBuf),
+
+    /// System stream fed by the stream engine itself
+    SystemStream(StreamName),
 }
 
 impl Display for ForeignInfo {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let detail = match self {
             ForeignInfo::GenericTcp(addr) => format!("TCP connection to {:?}", addr),
+            ForeignInfo::GenericUdp(addr) => format!("UDP socket at {:?}", addr),
             ForeignInfo::Http(addr) => format!("HTTP connection to {:?}", addr),
+            ForeignInfo::HttpEndpoint(url) => format!("HTTP endpoint {}", url),
             ForeignInfo::SocketCAN(interface) => format!("Socket CAN interface {}", interface),
             ForeignInfo::InMemoryQueue(queue_name) => format!("In-memory queue {}", queue_name),
+            ForeignInfo::File(path) => format!("File {:?}", path),
+            ForeignInfo::Command(path) => format!("Command {:?}", path),
+            ForeignInfo::Mqtt(broker) => format!("MQTT broker {}", broker),
+            ForeignInfo::OpcUa(endpoint_url) => format!("OPC UA server {}", endpoint_url),
+            ForeignInfo::Ros2(topic) => format!("ROS2 topic {}", topic),
+            ForeignInfo::Redis(addr) => format!("Redis server {}", addr),
+            ForeignInfo::Nats(url) => format!("NATS server {}", url),
+            ForeignInfo::Kafka(brokers) => format!("Kafka brokers {}", brokers),
+            ForeignInfo::ZeroMq(endpoint) => format!("ZeroMQ endpoint {}", endpoint),
+            ForeignInfo::ObjectStorage(bucket) => format!("Object storage {}", bucket),
+            ForeignInfo::UnixSocket(path) => format!("UNIX domain socket {:?}", path),
+            ForeignInfo::SystemStream(stream_name) => format!("System stream {}", stream_name),
         };
 
         write!(f, "[foreign info.] {}", detail)
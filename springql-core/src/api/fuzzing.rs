@@ -0,0 +1,121 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Entry points for fuzz targets (`springql-core/fuzz/`).
+//!
+//! Malformed inputs must be rejected by `SpringError`; a panic here is a bug to fix.
+
+use crate::{api::error::Result, pipeline::*, sql_processor::SqlParser};
+
+/// Parses a SQL statement.
+///
+/// # Failure
+///
+/// - `SpringError::Sql` when:
+///   - `sql` is invalid.
+pub fn parse_sql(sql: &str) -> Result<()> {
+    SqlParser::default().parse(sql).map(|_| ())
+}
+
+/// Parses `OPTIONS (...)` of a CREATE statement into each type of options.
+/// Results are discarded since most option types reject most key-value pairs.
+pub fn parse_options(key_values: &[(String, String)]) {
+    let options = key_values
+        .iter()
+        .fold(OptionsBuilder::default(), |builder, (key, value)| {
+            builder.add(key, value)
+        })
+        .build();
+
+    let _ = CommandOptions::try_from(&options);
+    let _ = FileSinkOptions::try_from(&options);
+    let _ = FileSourceOptions::try_from(&options);
+    let _ = InMemoryQueueOptions::try_from(&options);
+    let _ = InfluxDbOptions::try_from(&options);
+    let _ = JsonShapeOptions::try_from(&options);
+    let _ = ModbusOptions::try_from(&options);
+    let _ = NetClientOptions::try_from(&options);
+    let _ = NetHttpOptions::try_from(&options);
+    let _ = NetServerOptions::try_from(&options);
+    let _ = SinkFormatOptions::try_from(&options);
+    let _ = SourceFormatOptions::try_from(&options);
+    let _ = SourceMetadataOptions::try_from(&options);
+    let _ = SpringqlLinkSinkOptions::try_from(&options);
+    let _ = SpringqlLinkSourceOptions::try_from(&options);
+    let _ = UdpSourceOptions::try_from(&options);
+    let _ = UnixSocketOptions::try_from(&options);
+
+    #[cfg(feature = "avro")]
+    let _ = AvroOptions::try_from(&options);
+    #[cfg(feature = "source-can")]
+    let _ = CANOptions::try_from(&options);
+    #[cfg(feature = "sink-cloud-iot")]
+    {
+        let _ = AwsIotCoreOptions::try_from(&options);
+        let _ = AzureIotHubOptions::try_from(&options);
+    }
+    #[cfg(feature = "sink-gpio")]
+    let _ = GpioOptions::try_from(&options);
+    #[cfg(feature = "sink-http")]
+    let _ = Http1ClientOptions::try_from(&options);
+    #[cfg(feature = "source-kafka")]
+    let _ = KafkaSourceOptions::try_from(&options);
+    #[cfg(feature = "mqtt")]
+    {
+        let _ = MqttSinkOptions::try_from(&options);
+        let _ = MqttSourceOptions::try_from(&options);
+    }
+    #[cfg(feature = "nats-messaging")]
+    {
+        let _ = NatsSinkOptions::try_from(&options);
+        let _ = NatsSourceOptions::try_from(&options);
+    }
+    #[cfg(feature = "source-opc-ua")]
+    let _ = OpcUaOptions::try_from(&options);
+    #[cfg(feature = "sink-parquet")]
+    let _ = ParquetOptions::try_from(&options);
+    #[cfg(feature = "protobuf")]
+    let _ = ProtobufOptions::try_from(&options);
+    #[cfg(feature = "redis-stream")]
+    {
+        let _ = RedisStreamSinkOptions::try_from(&options);
+        let _ = RedisStreamSourceOptions::try_from(&options);
+    }
+    #[cfg(feature = "source-ros2")]
+    let _ = Ros2Options::try_from(&options);
+    #[cfg(feature = "sink-s3")]
+    let _ = S3Options::try_from(&options);
+    #[cfg(feature = "zeromq")]
+    {
+        let _ = ZeroMqSinkOptions::try_from(&options);
+        let _ = ZeroMqSourceOptions::try_from(&options);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sql_rejects_malformed() {
+        for sql in [
+            "",
+            "CREATE",
+            "CREATE SOURCE STREAM s (",
+            "CREATE PUMP p AS INSERT INTO s (c) SELECT STREAM FLOOR(t.c) FROM t;",
+            "SELECT STREAM ((((((((((1",
+        ] {
+            assert!(parse_sql(sql).is_err(), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_parse_options_does_not_panic() {
+        parse_options(&[]);
+        parse_options(&[
+            ("FORMAT".to_string(), "CSV".to_string()),
+            ("CSV_DELIMITER".to_string(), "".to_string()),
+            ("PORT".to_string(), "99999999".to_string()),
+            ("REMOTE_HOST".to_string(), "\u{0}".to_string()),
+        ]);
+    }
+}
@@ -1,8 +1,16 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use serde::Deserialize;
+use std::{collections::BTreeMap, fmt::Display, net::SocketAddr};
 
-use crate::api::error::{Result, SpringError};
+use anyhow::{anyhow, Context};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::Options,
+    sql_processor::TypeChecking,
+    stream_engine::command::Command,
+};
 
 /// Default configuration.
 ///
@@ -22,8 +30,30 @@ n_source_worker_threads = 1
 # Small number will improve the initial row's E2E latency but increase the CPU usage.
 sleep_msec_no_row = 100
 
+# Whether source worker threads take over pumps reading directly from source streams, once the pumps are observed to be light
+# (average execution time up to `adaptive_placement_max_task_usec`). Generic worker threads do not execute such pumps meanwhile.
+# Rows are processed right after read, without waiting in queues for generic worker threads, which may reduce latency on small topologies.
+# Placement follows performance metrics, and all pumps are back on generic worker threads just after the pipeline is altered.
+adaptive_placement = false
+adaptive_placement_max_task_usec = 1000
+
+# Whether each (weakly) connected subgraph of the pipeline is dedicated to a generic worker thread and a source worker thread,
+# with worker threads pinned to CPU cores (generic workers first, then source workers).
+# Workers never execute tasks of other subgraphs, so that each queue has only 1 writer thread and 1 reader thread.
+# Suits small, fixed topologies where synchronization between workers dominates. `adaptive_placement` is ignored when enabled.
+thread_per_core = false
+
+# Whether a worker executes a scheduled pump or sink task repeatedly as a micro-batch, instead of once, before moving on to the next task.
+# Batch size is tuned per task: doubled (up to `micro_batch_max_size`) while input queues of the task have more rows than the batch size,
+# and halved when a batch takes longer than `micro_batch_latency_target_usec`, which delays rows of other tasks.
+# Batch sizes are reported as `batch_size` of task metrics.
+micro_batch = false
+micro_batch_max_size = 256
+micro_batch_latency_target_usec = 1000
+
 [memory]
 # How much memory is allowed to be used in SpringQL streaming runtime.
+# Rows in queues and windows are counted by their in-memory footprint (fixed-size slots and heap-allocated values).
 upper_limit_bytes = 10_000_000
 
 # Percentage over `upper_limit_bytes` to transit from Moderate state to Severe.
@@ -43,6 +73,28 @@ memory_state_transition_interval_msec = 10
 # Interval for PerformanceMonitorWorker to publish ReportMetricsSummary event.
 performance_metrics_summary_report_interval_msec = 10
 
+# Name of an in-memory queue to receive a summary row for each queue purged in Critical state, so that downstream knows there is a gap in the data.
+# Read it by an IN_MEMORY_QUEUE source reader for a source stream with columns:
+#   purged_at TIMESTAMP, queue_name TEXT, n_rows BIGINT, min_rowtime TIMESTAMP, max_rowtime TIMESTAMP
+# Not reported if unset.
+# purge_report_queue = "q_purge_report"
+
+# Quotas of namespaces. A stream named `<namespace>.<stream>` (e.g. `tenant_a.s1`) belongs to the namespace.
+# When rows in queues of a namespace's streams exceed `memory_upper_limit_bytes`, they are purged without affecting other namespaces.
+# When tasks of a namespace run longer than `cpu_time_upper_limit_percent` of wall-clock time (100 for a core),
+# schedulers deprioritize (or pause, by `on_quota_exceeded = "pause"`) them until their excess is paid back.
+# [namespaces.tenant_a]
+# memory_upper_limit_bytes = 1_000_000
+# cpu_time_upper_limit_percent = 20
+# on_quota_exceeded = "deprioritize"
+
+# Quotas of pumps. `memory_upper_limit_bytes` limits rows a pump has put into its downstream queues.
+# Over either quota, schedulers deprioritize (or pause) the pump.
+# [pumps.experimental_pump]
+# memory_upper_limit_bytes = 100_000
+# cpu_time_upper_limit_percent = 10
+# on_quota_exceeded = "pause"
+
 [web_console]
 # Whether to enable POST API request to web console.
 enable_report_post = false
@@ -54,6 +106,8 @@ port = 8050
 
 timeout_msec = 3_000
 
+# `[source_reader]` and `[sink_writer]` values can be overridden per source reader / sink writer
+# by its OPTIONS with upper-case keys (e.g. `NET_READ_TIMEOUT_MSEC '500'`).
 [source_reader]
 net_connect_timeout_msec = 1_000
 net_read_timeout_msec = 100
@@ -66,17 +120,114 @@ net_write_timeout_msec = 100
 
 http_connect_timeout_msec = 1_000
 http_timeout_msec = 100
+
+# Retries of a failed HTTP request (connection error or 5xx response) with exponential backoff from `http_retry_backoff_msec`.
+http_retries = 0
+http_retry_backoff_msec = 100
+
+# After this number of consecutive failed HTTP requests (after retries), the circuit breaker opens and
+# rows are dropped without requests for `http_circuit_breaker_open_msec`. 0 disables the circuit breaker.
+http_circuit_breaker_failures = 0
+http_circuit_breaker_open_msec = 10_000
+
+[system_stream]
+# Rows buffered in each system stream (e.g. `spring._events`) until pumps read them. Rows are dropped while full.
+capacity = 1_000
+
+# Interval to put task metrics into `spring._task_metrics` system stream.
+task_metrics_interval_msec = 1_000
+
+[row_error]
+# A row failing in a pump (e.g. a value not convertible in an expression) is skipped and counted in `row_errors` of task metrics.
+# Up to `log_burst` errors of the same error code in a pump are logged in each `log_interval_msec`,
+# and the number of errors not logged is reported with the next log.
+log_burst = 5
+log_interval_msec = 10_000
+
+[management]
+# Listener accepting DDL and introspection commands at runtime (one command per line, a JSON line per response).
+# Disabled unless `uds_path` and/or `tcp_addr` is set.
+# uds_path = "/run/springql/management.sock"
+# tcp_addr = "0.0.0.0:7290"
+
+# Clients must send `AUTH <token>` first if set. Required for `tcp_addr`.
+# auth_token = "change-me"
+
+[permission]
+# Statements allowed by `SpringPipeline::command()` and the management listener. DDLs in `pipeline.ddl` are not restricted.
+# - "admin": all statements.
+# - "operator": CREATE PUMP, SWITCH PUMP, DROP PUMP, ALTER PUMP, ALTER STREAM, and the statements allowed for "observer". Streams, source readers, and sink writers cannot be added or dropped.
+# - "observer": INFER SCHEMA, SHOW HISTORY, SHOW WARNINGS, SHOW STREAMS, SHOW PUMPS, SHOW SOURCE READERS, SHOW SINK WRITERS, and EXPLAIN.
+# - "custom": statements listed in `allowed_statements` (e.g. ["CREATE PUMP", "SWITCH PUMP"]).
+role = "admin"
+# allowed_statements = []
+
+[recovery]
+# Write-ahead log of statements altering the pipeline, to reconstruct the pipeline after a crash or a restart.
+# If it has any statement, `SpringPipeline::new()` replays them instead of executing `pipeline.ddl`.
+# Remove the file to set up from `pipeline.ddl` again. Disabled unless set.
+# ddl_wal_path = "/var/lib/springql/ddl.wal"
+
+# When appended statements are flushed to storage (fsync).
+# - "always": before each statement returns. No applied statement is lost.
+# - "interval": on a statement at least `ddl_wal_fsync_interval_msec` after the last fsync. Statements after the last fsync may be lost.
+# - "never": left to OS.
+ddl_wal_fsync = "always"
+ddl_wal_fsync_interval_msec = 1_000
+
+[chaos]
+# Fault injection to validate recovery handling against realistic failures. Requires `chaos` feature.
+# Probabilities are in parts per million (1_000_000 = always). 0 disables each fault.
+
+# Probability to delay a task execution by `task_delay_msec`.
+task_delay_ppm = 0
+task_delay_msec = 100
+
+# Probability to drop a row put into a row queue. Rows put into window queues are not dropped.
+row_drop_ppm = 0
+
+# Probability to fail a read from a source reader (the row stays in the foreign source).
+source_read_failure_ppm = 0
+
+# Probability to kill a task worker thread after it executes a task series.
+# A killed worker executes no more tasks until the pipeline is dropped, so that other workers have to take over.
+worker_kill_ppm = 0
+
+[pipeline]
+# DDLs executed in order by `SpringPipeline::new()`, so that a pipeline can be set up without application code.
+# Example: ddl = ["CREATE SOURCE STREAM ...", "CREATE SINK STREAM ...", "CREATE PUMP ..."]
+ddl = []
+
+# How types in pumps are checked. Overridden by `CREATE PUMP pump_name STRICT AS ...` or `CREATE PUMP pump_name LENIENT AS ...`.
+# - "strict": CREATE PUMP fails if expressions or inserted values may mismatch the types (or NOT NULL) of stream columns.
+# - "lenient": values are converted into column types at runtime. Rows failing conversion are routed to `spring._row_errors`.
+type_checking = "lenient"
 "#;
 
 /// Top-level config.
 #[allow(missing_docs)]
-#[derive(Clone, Eq, PartialEq, Debug, Deserialize)]
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
 pub struct SpringConfig {
     pub worker: SpringWorkerConfig,
     pub memory: SpringMemoryConfig,
     pub web_console: SpringWebConsoleConfig,
     pub source_reader: SpringSourceReaderConfig,
     pub sink_writer: SpringSinkWriterConfig,
+    pub system_stream: SpringSystemStreamConfig,
+    pub row_error: SpringRowErrorConfig,
+    /// All keys are optional, and the `[management]` table itself may be omitted.
+    #[serde(default)]
+    pub management: SpringManagementConfig,
+    pub permission: SpringPermissionConfig,
+    pub recovery: SpringRecoveryConfig,
+    pub chaos: SpringChaosConfig,
+    pub pipeline: SpringPipelineConfig,
+    /// Namespace names to their configs. Namespaces without config have no quota.
+    #[serde(default)]
+    pub namespaces: BTreeMap<String, SpringNamespaceConfig>,
+    /// Pump names to their configs. Pumps without config have no quota.
+    #[serde(default)]
+    pub pumps: BTreeMap<String, SpringPumpConfig>,
 }
 
 impl Default for SpringConfig {
@@ -92,6 +243,7 @@ impl SpringConfig {
     ///   - `overwrite_config_toml` includes invalid key and/or value.
     /// - [SpringError::InvalidFormat](crate::api::error::SpringError::InvalidFormat) when:
     ///   - `overwrite_config_toml` is not valid as TOML.
+    /// - Raises Err from [SpringConfig::validate()](crate::api::SpringConfig::validate) as-is if values violate constraints.
     pub fn new(overwrite_config_toml: &str) -> Result<Self> {
         let default_conf = config::Config::builder()
             .add_source(config::File::from_str(
@@ -113,8 +265,11 @@ impl SpringConfig {
                 source: e.into(),
             })?;
 
-        c.try_deserialize()
-            .map_err(|e| SpringError::InvalidConfig { source: e.into() })
+        let config: Self = c
+            .try_deserialize()
+            .map_err(|e| SpringError::InvalidConfig { source: e.into() })?;
+        config.validate()?;
+        Ok(config)
     }
 
     /// Configuration by TOML format string.
@@ -132,20 +287,346 @@ impl SpringConfig {
     pub fn from_toml(overwrite_config_toml: &str) -> Result<SpringConfig> {
         SpringConfig::new(overwrite_config_toml)
     }
+
+    /// Checks constraints across fields.
+    ///
+    /// Configs created by [SpringConfig::new()](crate::api::SpringConfig::new) are already validated.
+    /// Call this if you modify fields directly.
+    ///
+    /// # Failures
+    ///
+    /// - [SpringError::InvalidConfigValue](crate::api::error::SpringError::InvalidConfigValue) when:
+    ///   - `worker.n_generic_worker_threads`, `worker.n_source_worker_threads` or `worker.micro_batch_max_size` is 0.
+    ///   - `memory.upper_limit_bytes` is less than 100.
+    ///   - `memory.*_percent` do not satisfy `100 > severe_to_critical > critical_to_severe > moderate_to_severe > severe_to_moderate`.
+    ///   - An interval is 0.
+    ///   - `management.tcp_addr` is not a socket address or set without `management.auth_token`.
+    ///   - `management.uds_path` is set on non-Unix platforms.
+    ///   - `permission.role` is unknown, or `permission.allowed_statements` is set for other than "custom" role.
+    ///   - `permission.allowed_statements` includes an unknown statement.
+    ///   - `recovery.ddl_wal_fsync` is unknown.
+    ///   - `pipeline.type_checking` is unknown.
+    ///   - `namespaces.<namespace>` is `spring` (reserved for system streams).
+    ///   - A quota in `namespaces.<namespace>` or `pumps.<pump>` is 0, or `on_quota_exceeded` is unknown.
+    ///   - A probability in `chaos` exceeds 1_000_000, or is set without `chaos` feature.
+    pub fn validate(&self) -> Result<()> {
+        let worker = &self.worker;
+        ensure(
+            worker.n_generic_worker_threads > 0,
+            "worker.n_generic_worker_threads",
+            worker.n_generic_worker_threads,
+            "must be at least 1",
+        )?;
+        ensure(
+            worker.n_source_worker_threads > 0,
+            "worker.n_source_worker_threads",
+            worker.n_source_worker_threads,
+            "must be at least 1",
+        )?;
+        ensure(
+            worker.micro_batch_max_size > 0,
+            "worker.micro_batch_max_size",
+            worker.micro_batch_max_size,
+            "must be at least 1",
+        )?;
+
+        let memory = &self.memory;
+        ensure(
+            memory.upper_limit_bytes >= 100,
+            "memory.upper_limit_bytes",
+            memory.upper_limit_bytes,
+            "must be at least 100 to apply percentages",
+        )?;
+        ensure(
+            memory.severe_to_critical_percent < 100,
+            "memory.severe_to_critical_percent",
+            memory.severe_to_critical_percent,
+            "must be less than 100",
+        )?;
+        ensure(
+            memory.critical_to_severe_percent < memory.severe_to_critical_percent,
+            "memory.critical_to_severe_percent",
+            memory.critical_to_severe_percent,
+            "must be less than memory.severe_to_critical_percent",
+        )?;
+        ensure(
+            memory.moderate_to_severe_percent < memory.critical_to_severe_percent,
+            "memory.moderate_to_severe_percent",
+            memory.moderate_to_severe_percent,
+            "must be less than memory.critical_to_severe_percent",
+        )?;
+        ensure(
+            memory.severe_to_moderate_percent < memory.moderate_to_severe_percent,
+            "memory.severe_to_moderate_percent",
+            memory.severe_to_moderate_percent,
+            "must be less than memory.moderate_to_severe_percent",
+        )?;
+
+        [
+            (
+                "memory.memory_state_transition_interval_msec",
+                memory.memory_state_transition_interval_msec,
+            ),
+            (
+                "memory.performance_metrics_summary_report_interval_msec",
+                memory.performance_metrics_summary_report_interval_msec,
+            ),
+            (
+                "web_console.report_interval_msec",
+                self.web_console.report_interval_msec,
+            ),
+            (
+                "system_stream.task_metrics_interval_msec",
+                self.system_stream.task_metrics_interval_msec,
+            ),
+            (
+                "row_error.log_interval_msec",
+                self.row_error.log_interval_msec,
+            ),
+            (
+                "recovery.ddl_wal_fsync_interval_msec",
+                self.recovery.ddl_wal_fsync_interval_msec,
+            ),
+        ]
+        .iter()
+        .try_for_each(|(path, interval)| {
+            ensure(*interval > 0, path, interval, "must be positive")
+        })?;
+
+        let management = &self.management;
+        if let Some(tcp_addr) = &management.tcp_addr {
+            ensure(
+                tcp_addr.parse::<SocketAddr>().is_ok(),
+                "management.tcp_addr",
+                tcp_addr,
+                "must be `<IP address>:<port>`",
+            )?;
+            ensure(
+                management.auth_token.is_some(),
+                "management.tcp_addr",
+                tcp_addr,
+                "requires management.auth_token",
+            )?;
+        }
+        if let Some(uds_path) = &management.uds_path {
+            ensure(
+                cfg!(unix),
+                "management.uds_path",
+                uds_path,
+                "is only available on Unix",
+            )?;
+        }
+
+        ensure(
+            ["always", "interval", "never"].contains(&self.recovery.ddl_wal_fsync.as_str()),
+            "recovery.ddl_wal_fsync",
+            &self.recovery.ddl_wal_fsync,
+            "must be one of \"always\", \"interval\", or \"never\"",
+        )?;
+        ensure(
+            ["strict", "lenient"].contains(&self.pipeline.type_checking.as_str()),
+            "pipeline.type_checking",
+            &self.pipeline.type_checking,
+            "must be one of \"strict\" or \"lenient\"",
+        )?;
+
+        self.namespaces
+            .iter()
+            .try_for_each(|(namespace, namespace_config)| {
+                ensure(
+                    namespace != "spring",
+                    "namespaces",
+                    namespace,
+                    "must not have `spring`, reserved for system streams",
+                )?;
+                validate_quota(
+                    &format!("namespaces.{}", namespace),
+                    namespace_config.memory_upper_limit_bytes,
+                    namespace_config.cpu_time_upper_limit_percent,
+                    namespace_config.on_quota_exceeded.as_deref(),
+                )
+            })?;
+        self.pumps.iter().try_for_each(|(pump, pump_config)| {
+            validate_quota(
+                &format!("pumps.{}", pump),
+                pump_config.memory_upper_limit_bytes,
+                pump_config.cpu_time_upper_limit_percent,
+                pump_config.on_quota_exceeded.as_deref(),
+            )
+        })?;
+
+        let chaos = &self.chaos;
+        [
+            ("chaos.task_delay_ppm", chaos.task_delay_ppm),
+            ("chaos.row_drop_ppm", chaos.row_drop_ppm),
+            (
+                "chaos.source_read_failure_ppm",
+                chaos.source_read_failure_ppm,
+            ),
+            ("chaos.worker_kill_ppm", chaos.worker_kill_ppm),
+        ]
+        .iter()
+        .try_for_each(|(path, ppm)| {
+            ensure(*ppm <= 1_000_000, path, ppm, "must be at most 1_000_000")?;
+            ensure(
+                *ppm == 0 || cfg!(feature = "chaos"),
+                path,
+                ppm,
+                "requires `chaos` feature",
+            )
+        })?;
+
+        let permission = &self.permission;
+        ensure(
+            ["admin", "operator", "observer", "custom"].contains(&permission.role.as_str()),
+            "permission.role",
+            &permission.role,
+            "must be one of \"admin\", \"operator\", \"observer\", or \"custom\"",
+        )?;
+        ensure(
+            (permission.role == "custom") == permission.allowed_statements.is_some(),
+            "permission.allowed_statements",
+            format!("{:?}", permission.allowed_statements),
+            "must be set if and only if `permission.role` is \"custom\"",
+        )?;
+        permission
+            .allowed_statements
+            .iter()
+            .flatten()
+            .try_for_each(|statement| {
+                ensure(
+                    Command::STATEMENTS.contains(&statement.as_str()),
+                    "permission.allowed_statements",
+                    statement,
+                    "includes an unknown statement",
+                )
+            })
+    }
+
+    /// Logs the effective config with defaults filled in. Values overwriting defaults are marked.
+    ///
+    /// Also warns if worker threads exceed available cores.
+    pub(crate) fn log_effective(&self) {
+        let default_values = Self::default().toml_path_values();
+
+        let lines = self
+            .toml_path_values()
+            .into_iter()
+            .map(|(path, value)| {
+                if path == "management.auth_token" {
+                    (path, r#""***""#.to_string())
+                } else {
+                    (path, value)
+                }
+            })
+            .map(|(path, value)| match default_values.get(&path) {
+                Some(default_value) if default_value == &value => format!("  {} = {}", path, value),
+                Some(default_value) => {
+                    format!("  {} = {} (default: {})", path, value, default_value)
+                }
+                None => format!("  {} = {} (default: unset)", path, value),
+            })
+            .collect::<Vec<_>>();
+
+        log::info!("[SpringConfig] effective config:\n{}", lines.join("\n"));
+
+        let n_workers = self.worker.n_generic_worker_threads as usize
+            + self.worker.n_source_worker_threads as usize;
+        let n_cpus = num_cpus::get();
+        if n_workers > n_cpus {
+            log::warn!(
+                "[SpringConfig] {} worker threads (worker.n_generic_worker_threads + worker.n_source_worker_threads) exceed {} available cores",
+                n_workers,
+                n_cpus
+            );
+        }
+    }
+
+    /// TOML paths (e.g. `memory.upper_limit_bytes`) and their values. Unset optional values are omitted.
+    fn toml_path_values(&self) -> BTreeMap<String, String> {
+        let value = serde_json::to_value(self).expect("config is serializable");
+
+        let mut path_values = BTreeMap::new();
+        if let serde_json::Value::Object(sections) = value {
+            for (section, keys) in sections {
+                if let serde_json::Value::Object(keys) = keys {
+                    for (key, v) in keys.into_iter().filter(|(_, v)| !v.is_null()) {
+                        path_values.insert(format!("{}.{}", section, key), v.to_string());
+                    }
+                }
+            }
+        }
+        path_values
+    }
+}
+
+fn ensure<V: Display>(cond: bool, path: &str, value: V, reason: &str) -> Result<()> {
+    if cond {
+        Ok(())
+    } else {
+        Err(SpringError::InvalidConfigValue {
+            path: path.to_string(),
+            value: value.to_string(),
+            source: anyhow!("`{}` {}", path, reason),
+        })
+    }
+}
+
+/// Validates quotas in `[namespaces.<namespace>]` or `[pumps.<pump>]` (`section`).
+fn validate_quota(
+    section: &str,
+    memory_upper_limit_bytes: Option<u64>,
+    cpu_time_upper_limit_percent: Option<u32>,
+    on_quota_exceeded: Option<&str>,
+) -> Result<()> {
+    if let Some(bytes) = memory_upper_limit_bytes {
+        ensure(
+            bytes > 0,
+            &format!("{}.memory_upper_limit_bytes", section),
+            bytes,
+            "must be positive",
+        )?;
+    }
+    if let Some(percent) = cpu_time_upper_limit_percent {
+        ensure(
+            percent > 0,
+            &format!("{}.cpu_time_upper_limit_percent", section),
+            percent,
+            "must be positive",
+        )?;
+    }
+    if let Some(action) = on_quota_exceeded {
+        ensure(
+            ["deprioritize", "pause"].contains(&action),
+            &format!("{}.on_quota_exceeded", section),
+            action,
+            "must be one of \"deprioritize\" or \"pause\"",
+        )?;
+    }
+    Ok(())
 }
 
 /// Config related to worker threads.
 #[allow(missing_docs)]
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Deserialize)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
 pub struct SpringWorkerConfig {
     pub n_generic_worker_threads: u16,
     pub n_source_worker_threads: u16,
     pub sleep_msec_no_row: u64,
+
+    pub adaptive_placement: bool,
+    pub adaptive_placement_max_task_usec: u32,
+
+    pub thread_per_core: bool,
+
+    pub micro_batch: bool,
+    pub micro_batch_max_size: u64,
+    pub micro_batch_latency_target_usec: u32,
 }
 
 /// Config related to memory management.
 #[allow(missing_docs)]
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Deserialize)]
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
 pub struct SpringMemoryConfig {
     pub upper_limit_bytes: u64,
 
@@ -157,11 +638,13 @@ pub struct SpringMemoryConfig {
 
     pub memory_state_transition_interval_msec: u32,
     pub performance_metrics_summary_report_interval_msec: u32,
+
+    pub purge_report_queue: Option<String>,
 }
 
 /// Config related to web console.
 #[allow(missing_docs)]
-#[derive(Clone, Eq, PartialEq, Debug, Deserialize)]
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
 pub struct SpringWebConsoleConfig {
     pub enable_report_post: bool,
 
@@ -175,7 +658,7 @@ pub struct SpringWebConsoleConfig {
 
 /// Config related to source reader
 #[allow(missing_docs)]
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Deserialize)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
 pub struct SpringSourceReaderConfig {
     pub net_connect_timeout_msec: u32,
     pub net_read_timeout_msec: u32,
@@ -183,13 +666,491 @@ pub struct SpringSourceReaderConfig {
     pub can_read_timeout_msec: u32,
 }
 
+impl SpringSourceReaderConfig {
+    /// Values overridden by `options` of a source reader (e.g. `NET_READ_TIMEOUT_MSEC`).
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::InvalidOption` when:
+    ///   - an overriding value is not an unsigned integer.
+    pub(crate) fn overridden_by(&self, options: &Options) -> Result<Self> {
+        let mut config = *self;
+        override_by_option(
+            &mut config.net_connect_timeout_msec,
+            options,
+            "NET_CONNECT_TIMEOUT_MSEC",
+        )?;
+        override_by_option(
+            &mut config.net_read_timeout_msec,
+            options,
+            "NET_READ_TIMEOUT_MSEC",
+        )?;
+        override_by_option(
+            &mut config.can_read_timeout_msec,
+            options,
+            "CAN_READ_TIMEOUT_MSEC",
+        )?;
+        Ok(config)
+    }
+}
+
 /// Config related to sink writer.
 #[allow(missing_docs)]
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Deserialize)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
 pub struct SpringSinkWriterConfig {
     pub net_connect_timeout_msec: u32,
     pub net_write_timeout_msec: u32,
 
     pub http_timeout_msec: u32,
     pub http_connect_timeout_msec: u32,
+
+    pub http_retries: u32,
+    pub http_retry_backoff_msec: u32,
+
+    pub http_circuit_breaker_failures: u32,
+    pub http_circuit_breaker_open_msec: u32,
+}
+
+impl SpringSinkWriterConfig {
+    /// Values overridden by `options` of a sink writer (e.g. `HTTP_TIMEOUT_MSEC`).
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::InvalidOption` when:
+    ///   - an overriding value is not an unsigned integer.
+    pub(crate) fn overridden_by(&self, options: &Options) -> Result<Self> {
+        let mut config = *self;
+        override_by_option(
+            &mut config.net_connect_timeout_msec,
+            options,
+            "NET_CONNECT_TIMEOUT_MSEC",
+        )?;
+        override_by_option(
+            &mut config.net_write_timeout_msec,
+            options,
+            "NET_WRITE_TIMEOUT_MSEC",
+        )?;
+        override_by_option(&mut config.http_timeout_msec, options, "HTTP_TIMEOUT_MSEC")?;
+        override_by_option(
+            &mut config.http_connect_timeout_msec,
+            options,
+            "HTTP_CONNECT_TIMEOUT_MSEC",
+        )?;
+        override_by_option(&mut config.http_retries, options, "HTTP_RETRIES")?;
+        override_by_option(
+            &mut config.http_retry_backoff_msec,
+            options,
+            "HTTP_RETRY_BACKOFF_MSEC",
+        )?;
+        override_by_option(
+            &mut config.http_circuit_breaker_failures,
+            options,
+            "HTTP_CIRCUIT_BREAKER_FAILURES",
+        )?;
+        override_by_option(
+            &mut config.http_circuit_breaker_open_msec,
+            options,
+            "HTTP_CIRCUIT_BREAKER_OPEN_MSEC",
+        )?;
+        Ok(config)
+    }
+}
+
+fn override_by_option(value: &mut u32, options: &Options, key: &str) -> Result<()> {
+    if let Some(v) = options.get_optional(key, |s| s.parse().context("not an unsigned integer"))? {
+        *value = v;
+    }
+    Ok(())
+}
+
+/// Config related to system streams.
+#[allow(missing_docs)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SpringSystemStreamConfig {
+    pub capacity: u32,
+    pub task_metrics_interval_msec: u32,
+}
+
+/// Config related to errors in processing rows.
+#[allow(missing_docs)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SpringRowErrorConfig {
+    pub log_burst: u32,
+    pub log_interval_msec: u32,
+}
+
+/// Config related to the management listener.
+#[allow(missing_docs)]
+#[derive(Clone, Eq, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct SpringManagementConfig {
+    pub uds_path: Option<String>,
+    pub tcp_addr: Option<String>,
+    pub auth_token: Option<String>,
+}
+
+impl SpringManagementConfig {
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.uds_path.is_some() || self.tcp_addr.is_some()
+    }
+}
+
+/// Config related to statements allowed at runtime.
+#[allow(missing_docs)]
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SpringPermissionConfig {
+    pub role: String,
+    pub allowed_statements: Option<Vec<String>>,
+}
+
+impl SpringPermissionConfig {
+    /// `statement` is one of `Command::STATEMENTS`.
+    pub(crate) fn allows(&self, statement: &str) -> bool {
+        match self.role.as_str() {
+            "admin" => true,
+            "operator" => matches!(
+                statement,
+                "CREATE PUMP"
+                    | "SWITCH PUMP"
+                    | "DROP PUMP"
+                    | "ALTER PUMP"
+                    | "ALTER STREAM"
+                    | "INFER SCHEMA"
+                    | "SHOW HISTORY"
+                    | "SHOW WARNINGS"
+                    | "SHOW STREAMS"
+                    | "SHOW PUMPS"
+                    | "SHOW SOURCE READERS"
+                    | "SHOW SINK WRITERS"
+                    | "EXPLAIN"
+            ),
+            "observer" => matches!(
+                statement,
+                "INFER SCHEMA"
+                    | "SHOW HISTORY"
+                    | "SHOW WARNINGS"
+                    | "SHOW STREAMS"
+                    | "SHOW PUMPS"
+                    | "SHOW SOURCE READERS"
+                    | "SHOW SINK WRITERS"
+                    | "EXPLAIN"
+            ),
+            _ => self
+                .allowed_statements
+                .iter()
+                .flatten()
+                .any(|allowed| allowed == statement),
+        }
+    }
+}
+
+/// Config related to recovery of a pipeline.
+#[allow(missing_docs)]
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SpringRecoveryConfig {
+    pub ddl_wal_path: Option<String>,
+    pub ddl_wal_fsync: String,
+    pub ddl_wal_fsync_interval_msec: u32,
+}
+
+/// Config of a namespace (`[namespaces.<namespace>]`).
+#[allow(missing_docs)]
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SpringNamespaceConfig {
+    pub memory_upper_limit_bytes: Option<u64>,
+    pub cpu_time_upper_limit_percent: Option<u32>,
+    pub on_quota_exceeded: Option<String>,
+}
+
+/// Config of a pump (`[pumps.<pump>]`).
+#[allow(missing_docs)]
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SpringPumpConfig {
+    pub memory_upper_limit_bytes: Option<u64>,
+    pub cpu_time_upper_limit_percent: Option<u32>,
+    pub on_quota_exceeded: Option<String>,
+}
+
+/// Config related to fault injection (`chaos` feature). Probabilities are in parts per million.
+#[allow(missing_docs)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SpringChaosConfig {
+    pub task_delay_ppm: u32,
+    pub task_delay_msec: u32,
+    pub row_drop_ppm: u32,
+    pub source_read_failure_ppm: u32,
+    pub worker_kill_ppm: u32,
+}
+
+/// Config related to pipeline setup.
+#[allow(missing_docs)]
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SpringPipelineConfig {
+    pub ddl: Vec<String>,
+    pub type_checking: String,
+}
+
+impl SpringPipelineConfig {
+    pub(crate) fn type_checking(&self) -> TypeChecking {
+        match self.type_checking.as_str() {
+            "strict" => TypeChecking::Strict,
+            _ => TypeChecking::Lenient,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::OptionsBuilder;
+
+    #[test]
+    fn test_validate_thresholds_ordering() {
+        let err = SpringConfig::new(
+            "
+            [memory]
+            critical_to_severe_percent = 50
+            ",
+        )
+        .unwrap_err();
+        match err {
+            SpringError::InvalidConfigValue { path, value, .. } => {
+                assert_eq!(path, "memory.moderate_to_severe_percent");
+                assert_eq!(value, "60");
+            }
+            _ => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn test_validate_modified_fields() {
+        let mut config = SpringConfig::default();
+        assert!(config.validate().is_ok());
+
+        config.worker.n_generic_worker_threads = 0;
+        assert!(matches!(
+            config.validate(),
+            Err(SpringError::InvalidConfigValue { path, .. }) if path == "worker.n_generic_worker_threads"
+        ));
+
+        let mut config = SpringConfig::default();
+        config.worker.micro_batch_max_size = 0;
+        assert!(matches!(
+            config.validate(),
+            Err(SpringError::InvalidConfigValue { path, .. }) if path == "worker.micro_batch_max_size"
+        ));
+    }
+
+    #[test]
+    fn test_validate_management() {
+        assert!(!SpringConfig::default().management.is_enabled());
+
+        let err = SpringConfig::new(
+            r#"
+            [management]
+            tcp_addr = "127.0.0.1:7290"
+            "#,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            SpringError::InvalidConfigValue { path, .. } if path == "management.tcp_addr"
+        ));
+
+        let config = SpringConfig::new(
+            r#"
+            [management]
+            tcp_addr = "127.0.0.1:7290"
+            auth_token = "secret"
+            "#,
+        )
+        .unwrap();
+        assert!(config.management.is_enabled());
+    }
+
+    #[test]
+    fn test_permission() {
+        assert!(SpringConfig::default()
+            .permission
+            .allows("CREATE SOURCE READER"));
+
+        let config = SpringConfig::new(
+            r#"
+            [permission]
+            role = "operator"
+            "#,
+        )
+        .unwrap();
+        assert!(config.permission.allows("CREATE PUMP"));
+        assert!(config.permission.allows("DROP PUMP"));
+        assert!(config.permission.allows("ALTER PUMP"));
+        assert!(config.permission.allows("SHOW STREAMS"));
+        assert!(config.permission.allows("EXPLAIN"));
+        assert!(!config.permission.allows("CREATE SOURCE READER"));
+        assert!(!config.permission.allows("DROP STREAM"));
+
+        let config = SpringConfig::new(
+            r#"
+            [permission]
+            role = "custom"
+            allowed_statements = ["SWITCH PUMP"]
+            "#,
+        )
+        .unwrap();
+        assert!(config.permission.allows("SWITCH PUMP"));
+        assert!(!config.permission.allows("CREATE PUMP"));
+
+        for invalid in [
+            r#"role = "root""#,
+            r#"role = "custom""#,
+            r#"allowed_statements = ["CREATE PUMP"]"#,
+            "role = \"custom\"\nallowed_statements = [\"DROP TABLE\"]",
+        ] {
+            let err = SpringConfig::new(&format!("[permission]\n{}", invalid)).unwrap_err();
+            assert!(matches!(err, SpringError::InvalidConfigValue { .. }));
+        }
+    }
+
+    #[test]
+    fn test_overridden_by_options() {
+        let options = OptionsBuilder::default()
+            .add("PROTOCOL", "TCP")
+            .add("NET_READ_TIMEOUT_MSEC", "500")
+            .build();
+        let config = SpringSourceReaderConfig::fx_default()
+            .overridden_by(&options)
+            .unwrap();
+        assert_eq!(config.net_read_timeout_msec, 500);
+        assert_eq!(
+            config.net_connect_timeout_msec,
+            SpringSourceReaderConfig::fx_default().net_connect_timeout_msec
+        );
+
+        let options = OptionsBuilder::default()
+            .add("HTTP_TIMEOUT_MSEC", "1s")
+            .build();
+        assert!(matches!(
+            SpringSinkWriterConfig::fx_default().overridden_by(&options),
+            Err(SpringError::InvalidOption { key, .. }) if key == "HTTP_TIMEOUT_MSEC"
+        ));
+    }
+
+    #[test]
+    fn test_validate_namespaces() {
+        let config = SpringConfig::new(
+            "
+            [namespaces.tenant_a]
+            memory_upper_limit_bytes = 1_000
+            ",
+        )
+        .unwrap();
+        assert_eq!(
+            config.namespaces["tenant_a"].memory_upper_limit_bytes,
+            Some(1_000)
+        );
+        assert_eq!(
+            config.namespaces["tenant_a"].cpu_time_upper_limit_percent,
+            None
+        );
+        assert!(SpringConfig::default().namespaces.is_empty());
+
+        let err = SpringConfig::new(
+            "
+            [namespaces.spring]
+            memory_upper_limit_bytes = 1_000
+            ",
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            SpringError::InvalidConfigValue { path, .. } if path == "namespaces"
+        ));
+    }
+
+    #[test]
+    fn test_validate_quotas() {
+        let config = SpringConfig::new(
+            r#"
+            [namespaces.tenant_a]
+            cpu_time_upper_limit_percent = 20
+
+            [pumps.experimental_pump]
+            memory_upper_limit_bytes = 1_000
+            on_quota_exceeded = "pause"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.namespaces["tenant_a"].cpu_time_upper_limit_percent,
+            Some(20)
+        );
+        assert_eq!(
+            config.pumps["experimental_pump"].on_quota_exceeded,
+            Some("pause".to_string())
+        );
+
+        let err = SpringConfig::new(
+            "
+            [pumps.experimental_pump]
+            cpu_time_upper_limit_percent = 0
+            ",
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            SpringError::InvalidConfigValue { path, .. } if path == "pumps.experimental_pump.cpu_time_upper_limit_percent"
+        ));
+
+        let err = SpringConfig::new(
+            r#"
+            [namespaces.tenant_a]
+            on_quota_exceeded = "drop"
+            "#,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            SpringError::InvalidConfigValue { path, .. } if path == "namespaces.tenant_a.on_quota_exceeded"
+        ));
+    }
+
+    #[test]
+    fn test_validate_chaos() {
+        let err = SpringConfig::new(
+            "
+            [chaos]
+            row_drop_ppm = 1_000_001
+            ",
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            SpringError::InvalidConfigValue { path, .. } if path == "chaos.row_drop_ppm"
+        ));
+
+        let config = SpringConfig::new(
+            "
+            [chaos]
+            worker_kill_ppm = 10
+            ",
+        );
+        assert_eq!(config.is_ok(), cfg!(feature = "chaos"));
+    }
+
+    #[test]
+    fn test_toml_path_values() {
+        let config = SpringConfig::new(
+            r#"
+            [memory]
+            purge_report_queue = "q"
+            "#,
+        )
+        .unwrap();
+        let path_values = config.toml_path_values();
+
+        assert_eq!(path_values["memory.upper_limit_bytes"], "10000000");
+        assert_eq!(path_values["memory.purge_report_queue"], r#""q""#);
+        assert!(!SpringConfig::default()
+            .toml_path_values()
+            .contains_key("memory.purge_report_queue"));
+    }
 }
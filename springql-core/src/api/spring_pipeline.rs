@@ -1,33 +1,181 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use std::{sync::Arc, time::Duration};
+
+use anyhow::anyhow;
+
 use crate::{
-    api::{error::Result, spring_source_row::SpringSourceRow, SpringConfig, SpringSinkRow},
+    api::{
+        error::{Result, SpringError},
+        spring_source_row::SpringSourceRow,
+        SpringColumn, SpringConfig, SpringSinkRow, SpringStreamTap, SpringTaskHook,
+    },
     connection::Connection,
+    stream_engine::{autonomous_executor::SchemalessRow, PipelineSnapshot},
 };
 
 /// Pipeline.
+///
+/// When a worker thread gets a fatal error ([SpringError::ThreadPoisoned](crate::api::error::SpringError::ThreadPoisoned)
+/// or [SpringError::SpringQlCoreIo](crate::api::error::SpringError::SpringQlCoreIo)), the next method call returns it,
+/// since results after it are not reliable. Blocking [SpringPipeline::pop()](crate::api::SpringPipeline::pop) also returns it while waiting.
 #[derive(Debug)]
 pub struct SpringPipeline(Connection);
 
 impl SpringPipeline {
     /// Creates and open an in-process stream pipeline.
+    ///
+    /// DDLs in `config.pipeline.ddl` are executed in order, regardless of `config.permission.role`.
+    /// If `config.recovery.ddl_wal_path` has any statement, they are replayed instead of `config.pipeline.ddl`.
+    ///
+    /// # Failure
+    ///
+    /// - Raises Err from [SpringConfig::validate()](crate::api::SpringConfig::validate) as-is if `config` is invalid.
+    /// - [SpringError::ForeignIo](crate::api::error::SpringError::ForeignIo) when:
+    ///   - failed to listen on `config.management.uds_path` or `config.management.tcp_addr`.
+    ///   - failed to open `config.recovery.ddl_wal_path`.
+    /// - [SpringError::InvalidFormat](crate::api::error::SpringError::InvalidFormat) when:
+    ///   - `config.recovery.ddl_wal_path` has a broken record other than the last one.
+    /// - Raises Err from [SpringPipeline::command()](crate::api::SpringPipeline::command) as-is if a DDL in `config.pipeline.ddl`
+    ///   or `config.recovery.ddl_wal_path` fails.
     pub fn new(config: &SpringConfig) -> Result<Self> {
-        let conn = Connection::new(config);
-        Ok(Self(conn))
+        config.validate()?;
+        config.log_effective();
+
+        let conn = Connection::new(config)?;
+        let pipeline = Self(conn);
+
+        if !pipeline.0.is_recovered() {
+            config
+                .pipeline
+                .ddl
+                .iter()
+                .try_for_each(|ddl| pipeline.0.command_unrestricted(ddl).map(|_| ()))?;
+        }
+
+        Ok(pipeline)
+    }
+
+    /// Creates an in-process stream pipeline from an archive made by [SpringPipeline::snapshot()](crate::api::SpringPipeline::snapshot),
+    /// possibly in another process or on another device.
+    ///
+    /// Statements in the archive are executed in order instead of `config.pipeline.ddl`, and then rows in queues and
+    /// in-progress aggregations over windows are restored.
+    /// Statements are also appended to `config.recovery.ddl_wal_path` if set.
+    ///
+    /// # Failure
+    ///
+    /// - [SpringError::InvalidFormat](crate::api::error::SpringError::InvalidFormat) when:
+    ///   - `archive` is not made by `SpringPipeline::snapshot()` or made by an incompatible version.
+    /// - [SpringError::InvalidConfig](crate::api::error::SpringError::InvalidConfig) when:
+    ///   - `config.recovery.ddl_wal_path` already has statements.
+    /// - [SpringError::Sql](crate::api::error::SpringError::Sql) when:
+    ///   - rows or window states in `archive` do not match the pipeline made from its statements.
+    /// - Raises Err from [SpringPipeline::new()](crate::api::SpringPipeline::new) as-is.
+    pub fn restore(config: &SpringConfig, archive: &[u8]) -> Result<Self> {
+        config.validate()?;
+        config.log_effective();
+
+        let snapshot = PipelineSnapshot::decode(archive)?;
+        Connection::restore(config, snapshot).map(Self)
+    }
+
+    /// Takes a snapshot of the pipeline into a portable archive to be restored by
+    /// [SpringPipeline::restore()](crate::api::SpringPipeline::restore), e.g. for replacing hardware.
+    ///
+    /// The archive has statements which altered the pipeline (including `config.pipeline.ddl`), rows in queues,
+    /// and in-progress aggregations over windows.
+    /// Workers are paused while the snapshot is taken, so that it is consistent.
+    ///
+    /// Not included: rows in sources and sinks outside of the pipeline (e.g. in-memory queues of `IN_MEMORY_QUEUE` sink writers),
+    /// and rows waiting in windows of joins, which restart empty.
+    ///
+    /// # Failure
+    ///
+    /// - [SpringError::InvalidFormat](crate::api::error::SpringError::InvalidFormat) when:
+    ///   - failed to encode the snapshot.
+    pub fn snapshot(&self) -> Result<Vec<u8>> {
+        self.0.snapshot()?.encode()
     }
 
     /// Execute commands (DDL).
     ///
+    /// `sql` may contain `-- line comments` and `/* block comments */`.
+    ///
     /// # Failure
     ///
     /// - [SpringError::Sql](crate::api::error::SpringError::Sql) when:
     ///   - Invalid SQL syntax.
     ///   - Refers to undefined objects (streams, pumps, etc)
+    ///   - `DROP STREAM` for a stream still read or written by a pump, a source reader, or a sink writer.
     ///   - Other semantic errors.
     /// - [SpringError::InvalidOption](crate::api::error::SpringError::Sql) when:
     ///   - `OPTIONS` in `CREATE` statement includes invalid key or value.
+    /// - [SpringError::PermissionDenied](crate::api::error::SpringError::PermissionDenied) when:
+    ///   - the statement is not allowed by `permission.role` in the config.
+    /// - [SpringError::ForeignIo](crate::api::error::SpringError::ForeignIo) when:
+    ///   - failed to append the statement to `recovery.ddl_wal_path` in the config. The pipeline is not altered.
     pub fn command<S: AsRef<str>>(&self, sql: S) -> Result<()> {
-        self.0.command(sql.as_ref())
+        self.0.command(sql.as_ref()).map(|_| ())
+    }
+
+    /// Execute a command which returns a text result.
+    ///
+    /// Currently supported commands:
+    ///
+    /// - `INFER SCHEMA [FOR stream] FROM SOURCE READER TYPE ... OPTIONS (...) SAMPLE n`: connects to a source, samples up to `n` rows,
+    ///   and returns a suggested `CREATE SOURCE STREAM` statement. The pipeline keeps running while sampling.
+    /// - `INFER SCHEMA [FOR stream] FROM SOURCE READER source_reader_name SAMPLE n`: the same as above with the type and options of a source reader in the pipeline.
+    ///   The suggested stream is named after the source reader's stream unless `FOR stream` is given.
+    /// - `SHOW HISTORY`: returns statements which altered the pipeline so far (including `config.pipeline.ddl`),
+    ///   a line per statement with the UTC time applied and the resulting pipeline version. Only the latest 1,000 statements are kept.
+    /// - `SHOW WARNINGS`: returns streams not read by any pump or sink writer, pumps whose downstreams do not reach any sink writer,
+    ///   and columns not used downstream, a line per warning. Empty if no warning.
+    /// - `SHOW STREAMS`, `SHOW PUMPS`, `SHOW SOURCE READERS`, and `SHOW SINK WRITERS`: returns rows of
+    ///   [SpringPipeline::command_query()](crate::api::SpringPipeline::command_query) as JSON objects, a line per row.
+    /// - `EXPLAIN CREATE PUMP ...` and `EXPLAIN SELECT STREAM ...`: returns the query plan of the pump (or `SELECT STREAM`) as a JSON object:
+    ///   `upper_ops` (projection and window aggregation) over `lower_ops` (join of upstreams and WHERE selection).
+    ///   The pump is not created.
+    ///
+    /// # Failure
+    ///
+    /// - [SpringError::Sql](crate::api::error::SpringError::Sql) when:
+    ///   - Invalid SQL syntax.
+    ///   - `sql` is a command which does not return a text (e.g. `CREATE STREAM`).
+    ///   - `INFER SCHEMA` names a source reader which does not exist.
+    /// - [SpringError::ForeignSourceTimeout](crate::api::error::SpringError::ForeignSourceTimeout) when:
+    ///   - `INFER SCHEMA` does not get any row from the source.
+    /// - [SpringError::InvalidFormat](crate::api::error::SpringError::InvalidFormat) when:
+    ///   - `INFER SCHEMA` gets rows which cannot be typed (e.g. a column has both BOOLEAN and INTEGER values).
+    pub fn query<S: AsRef<str>>(&self, sql: S) -> Result<String> {
+        let sql = sql.as_ref();
+        self.0
+            .command(sql)?
+            .ok_or_else(|| SpringError::Sql(anyhow!("command does not return any text: {}", sql)))
+    }
+
+    /// Execute a command which returns rows.
+    ///
+    /// Currently supported commands (rows are sorted by `name`):
+    ///
+    /// - `SHOW STREAMS`: a row per stream with `name` and `columns` (comma-separated column names) TEXT columns.
+    /// - `SHOW PUMPS`: a row per pump with `name`, `upstreams`, and `downstreams` (comma-separated stream names) TEXT columns,
+    ///   and `stopped` BOOLEAN column (`ALTER PUMP p STOP`).
+    /// - `SHOW SOURCE READERS` and `SHOW SINK WRITERS`: a row per source reader / sink writer with `name`,
+    ///   `type` (e.g. `IN_MEMORY_QUEUE`), and `stream` TEXT columns.
+    ///
+    /// Column values are got by [SpringSinkRow::get_not_null_by_column_name()](crate::api::SpringSinkRow::get_not_null_by_column_name).
+    ///
+    /// # Failure
+    ///
+    /// - [SpringError::Sql](crate::api::error::SpringError::Sql) when:
+    ///   - Invalid SQL syntax.
+    ///   - `sql` is a command which does not return rows (e.g. `SHOW HISTORY`).
+    /// - [SpringError::PermissionDenied](crate::api::error::SpringError::PermissionDenied) when:
+    ///   - the statement is not allowed by `permission.role` in the config.
+    pub fn command_query<S: AsRef<str>>(&self, sql: S) -> Result<Vec<SpringSinkRow>> {
+        let rows = self.0.command_query(sql.as_ref())?;
+        Ok(rows.into_iter().map(SpringSinkRow::new).collect())
     }
 
     /// Pop a row from an in memory queue. This is a blocking function.
@@ -41,7 +189,8 @@ impl SpringPipeline {
     /// - [SpringError::Unavailable](crate::api::error::SpringError::Unavailable) when:
     ///   - queue named `queue` does not exist.
     pub fn pop(&self, queue: &str) -> Result<SpringSinkRow> {
-        self.0.pop(queue).map(SpringSinkRow::new)
+        let row = self.0.pop(queue)?;
+        self.in_memory_queue_row(queue, row)
     }
 
     /// Pop a row from an in memory queue. This is a non-blocking function.
@@ -57,12 +206,41 @@ impl SpringPipeline {
     ///   - queue named `queue` does not exist.
     pub fn pop_non_blocking(&self, queue: &str) -> Result<Option<SpringSinkRow>> {
         self.0
-            .pop_non_blocking(queue)
-            .map(|opt_row| opt_row.map(SpringSinkRow::new))
+            .pop_non_blocking(queue)?
+            .map(|row| self.in_memory_queue_row(queue, row))
+            .transpose()
+    }
+
+    /// Starts receiving copies of rows traversing a stream (including internal streams) for debugging.
+    ///
+    /// The tap keeps up to `capacity` rows and drops new rows while it is full.
+    /// It stops receiving rows after `ttl`, or when it is dropped.
+    ///
+    /// # Failure
+    ///
+    /// - [SpringError::Sql](crate::api::error::SpringError::Sql) when:
+    ///   - stream named `stream` does not exist.
+    pub fn tap(&self, stream: &str, capacity: usize, ttl: Duration) -> Result<SpringStreamTap> {
+        self.0.tap(stream, capacity, ttl).map(SpringStreamTap::new)
+    }
+
+    /// Registers a hook invoked before and after each task execution on worker threads.
+    ///
+    /// Hooks stay registered while the pipeline lives, including after the pipeline is altered.
+    ///
+    /// # Failure
+    ///
+    /// - [SpringError::SpringQlCoreIo](crate::api::error::SpringError::SpringQlCoreIo) when:
+    ///   - another thread sharing the stream engine got panic.
+    pub fn add_task_hook<H: SpringTaskHook>(&self, hook: H) -> Result<()> {
+        self.0.add_task_hook(Arc::new(hook))
     }
 
     /// Push a row into an in memory queue. This is a non-blocking function.
     ///
+    /// The queue is created by an `IN_MEMORY_QUEUE` source reader with `NAME` option, so that the host application
+    /// feeds rows into its source stream without opening sockets.
+    ///
     /// # Failure
     ///
     /// - [SpringError::Unavailable](crate::api::error::SpringError::Unavailable) when:
@@ -70,4 +248,13 @@ impl SpringPipeline {
     pub fn push(&self, queue: &str, row: SpringSourceRow) -> Result<()> {
         self.0.push(queue, row.into_schemaless_row()?)
     }
+
+    /// Row popped from an in-memory queue, with the schema of the sink stream written into the queue.
+    fn in_memory_queue_row(&self, queue: &str, row: SchemalessRow) -> Result<SpringSinkRow> {
+        let schema = self
+            .0
+            .in_memory_queue_columns(queue)?
+            .map(|columns| columns.iter().map(SpringColumn::from).collect());
+        Ok(SpringSinkRow::new(row).with_schema(schema))
+    }
 }
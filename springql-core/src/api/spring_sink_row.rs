@@ -1,20 +1,60 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use std::fmt::{self, Display};
+
+use anyhow::anyhow;
+
 use crate::{
     api::{
         error::{Result, SpringError},
         spring_source_row::SpringSourceRow,
     },
-    stream_engine::{autonomous_executor::SchemalessRow, SpringValue, SqlValue},
+    pipeline::{ColumnDataType, ColumnName},
+    stream_engine::{autonomous_executor::SchemalessRow, NnSqlValue, SpringValue, SqlValue},
 };
 
+/// Column in the schema of a [SpringSinkRow].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct SpringColumn {
+    /// Column name.
+    pub name: String,
+    /// SQL type name as written in DDLs (e.g. `INTEGER`, `TIMESTAMP`).
+    pub sql_type: String,
+    /// `false` for `NOT NULL` columns.
+    pub nullable: bool,
+}
+
+impl From<&ColumnDataType> for SpringColumn {
+    fn from(column: &ColumnDataType) -> Self {
+        Self {
+            name: column.column_name().to_string(),
+            sql_type: column.sql_type().to_string(),
+            nullable: column.nullable(),
+        }
+    }
+}
+
 /// Row object from an in memory sink queue.
 #[derive(Debug)]
-pub struct SpringSinkRow(SchemalessRow);
+pub struct SpringSinkRow {
+    row: SchemalessRow,
+    schema: Option<Vec<SpringColumn>>,
+}
 
 impl SpringSinkRow {
     pub(crate) fn new(row: SchemalessRow) -> Self {
-        SpringSinkRow(row)
+        SpringSinkRow { row, schema: None }
+    }
+
+    pub(crate) fn with_schema(self, schema: Option<Vec<SpringColumn>>) -> Self {
+        Self { schema, ..self }
+    }
+
+    /// Columns of the row in order, as defined in the sink stream (and `PROJECT` clause of the sink writer).
+    ///
+    /// `None` for rows not from a sink writer's in-memory queue (e.g. rows from a stream tap or `command_query()`).
+    pub fn schema(&self) -> Option<&[SpringColumn]> {
+        self.schema.as_deref()
     }
 
     /// Get a i-th column value from the row.
@@ -23,23 +63,259 @@ impl SpringSinkRow {
     ///
     /// - [SpringError::Sql](crate::api::error::SpringError::Sql) when:
     ///   - Column index out of range
+    ///   - Column value cannot be typed as `T` (e.g. `T = bool` for an INTEGER column)
     /// - [SpringError::Null](crate::api::error::SpringError::Null) when:
     ///   - Column value is NULL
     pub fn get_not_null_by_index<T>(&self, i_col: usize) -> Result<T>
     where
         T: SpringValue,
     {
-        let sql_value = self.0.get_by_index(i_col)?;
+        self.get_nn_sql_value(i_col)?.unpack()
+    }
+
+    /// Get a i-th column value from the row. `None` if the value is NULL.
+    ///
+    /// # Failure
+    ///
+    /// - [SpringError::Sql](crate::api::error::SpringError::Sql) when:
+    ///   - Column index out of range
+    ///   - Column value cannot be typed as `T` (e.g. `T = bool` for an INTEGER column)
+    pub fn get_by_index<T>(&self, i_col: usize) -> Result<Option<T>>
+    where
+        T: SpringValue,
+    {
+        match self.row.get_by_index(i_col)? {
+            SqlValue::Null => Ok(None),
+            SqlValue::NotNull(nn_sql_value) => nn_sql_value.unpack().map(Some),
+        }
+    }
+
+    /// Get a column value from the row by the column name in the sink stream.
+    ///
+    /// # Failure
+    ///
+    /// - [SpringError::Sql](crate::api::error::SpringError::Sql) when:
+    ///   - Column name is not found
+    ///   - Column value cannot be typed as `T` (e.g. `T = bool` for an INTEGER column)
+    /// - [SpringError::Null](crate::api::error::SpringError::Null) when:
+    ///   - Column value is NULL
+    pub fn get_not_null_by_column_name<T>(&self, column_name: &str) -> Result<T>
+    where
+        T: SpringValue,
+    {
+        self.get_not_null_by_index(self.column_index(column_name)?)
+    }
+
+    /// Get a column value from the row by the column name in the sink stream. `None` if the value is NULL.
+    ///
+    /// # Failure
+    ///
+    /// - [SpringError::Sql](crate::api::error::SpringError::Sql) when:
+    ///   - Column name is not found
+    ///   - Column value cannot be typed as `T` (e.g. `T = bool` for an INTEGER column)
+    pub fn get_by_column_name<T>(&self, column_name: &str) -> Result<Option<T>>
+    where
+        T: SpringValue,
+    {
+        self.get_by_index(self.column_index(column_name)?)
+    }
+
+    /// Number of columns in the row.
+    pub fn n_columns(&self) -> usize {
+        self.row.len()
+    }
 
-        match sql_value {
+    /// Copy a i-th TEXT (UTF-8 bytes) or BLOB column value into `buf` without heap allocation.
+    ///
+    /// Only the first `buf.len()` bytes are copied if the value is longer than `buf`.
+    /// Callers with fixed-capacity buffers (e.g. C binding) can detect truncation by comparing the return value with `buf.len()`.
+    ///
+    /// # Returns
+    ///
+    /// Length of the whole value in bytes.
+    ///
+    /// # Failure
+    ///
+    /// - [SpringError::Sql](crate::api::error::SpringError::Sql) when:
+    ///   - Column index out of range
+    ///   - Column value is neither TEXT nor BLOB
+    /// - [SpringError::Null](crate::api::error::SpringError::Null) when:
+    ///   - Column value is NULL
+    pub fn get_not_null_bytes_into(&self, i_col: usize, buf: &mut [u8]) -> Result<usize> {
+        let bytes = match self.get_nn_sql_value(i_col)? {
+            NnSqlValue::Text(s) => s.as_bytes(),
+            NnSqlValue::Blob(v) => v.as_slice(),
+            nn_sql_value => {
+                return Err(SpringError::Sql(anyhow!(
+                    "column {} is neither TEXT nor BLOB: {}",
+                    i_col,
+                    nn_sql_value
+                )))
+            }
+        };
+
+        let n_copy = bytes.len().min(buf.len());
+        buf[..n_copy].copy_from_slice(&bytes[..n_copy]);
+        Ok(bytes.len())
+    }
+
+    /// JSON object with column names as keys.
+    ///
+    /// NULL columns are output as `null`, and BLOB columns as arrays of bytes.
+    /// Rows without BLOB columns are in the format [SpringSourceRow::from_json()](crate::api::SpringSourceRow::from_json) accepts,
+    /// while it does not accept arrays.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - a column has a value without JSON representation (DURATION).
+    pub fn to_json(&self) -> Result<String> {
+        let object = self
+            .row
+            .iter()
+            .map(|(column_name, value)| {
+                Ok((
+                    column_name.to_string(),
+                    serde_json::Value::try_from(value.clone())?,
+                ))
+            })
+            .collect::<Result<serde_json::Map<_, _>>>()?;
+        Ok(serde_json::Value::Object(object).to_string())
+    }
+
+    fn column_index(&self, column_name: &str) -> Result<usize> {
+        self.row
+            .column_index(&ColumnName::new(column_name.to_string()))
+    }
+
+    fn get_nn_sql_value(&self, i_col: usize) -> Result<&NnSqlValue> {
+        match self.row.get_by_index(i_col)? {
             SqlValue::Null => Err(SpringError::Null { i_col }),
-            SqlValue::NotNull(nn_sql_value) => nn_sql_value.unpack(),
+            SqlValue::NotNull(nn_sql_value) => Ok(nn_sql_value),
+        }
+    }
+}
+
+/// Column names and values in order, like `(ts: 2022-01-01 13:00:00.000000000, ticker: "ORCL", amount: NULL)`.
+impl Display for SpringSinkRow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(")?;
+        for (i, (column_name, value)) in self.row.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}: {}", column_name, value)?;
         }
+        write!(f, ")")
     }
 }
 
 impl From<SpringSinkRow> for SpringSourceRow {
     fn from(sink_row: SpringSinkRow) -> Self {
-        SpringSourceRow::new(sink_row.0)
+        SpringSourceRow::new(sink_row.row)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::api::SpringSourceRowBuilder;
+
+    fn sink_row() -> SpringSinkRow {
+        let source_row = SpringSourceRowBuilder::with_capacity(3)
+            .add_column("text", "Tokyo".to_string())
+            .unwrap()
+            .add_column("blob", b"\x00\x01".to_vec())
+            .unwrap()
+            .add_column("int", 1i32)
+            .unwrap()
+            .build();
+        SpringSinkRow::new(source_row.into_schemaless_row().unwrap())
+    }
+
+    #[test]
+    fn test_get_by_column_name() {
+        let mut row = sink_row().row;
+        row.insert(ColumnName::new("null".to_string()), SqlValue::Null)
+            .unwrap();
+        let row = SpringSinkRow::new(row);
+
+        assert_eq!(
+            row.get_not_null_by_column_name::<String>("text").unwrap(),
+            "Tokyo"
+        );
+        assert_eq!(row.get_by_column_name::<i64>("int").unwrap(), Some(1));
+        assert_eq!(row.get_by_column_name::<i32>("null").unwrap(), None);
+        assert_eq!(row.get_by_index::<i32>(3).unwrap(), None);
+
+        assert!(matches!(
+            row.get_not_null_by_column_name::<i32>("null"),
+            Err(SpringError::Null { i_col: 3 })
+        ));
+        assert!(matches!(
+            row.get_by_column_name::<bool>("int"),
+            Err(SpringError::Sql(_))
+        ));
+        assert!(matches!(
+            row.get_by_column_name::<i32>("404"),
+            Err(SpringError::Sql(_))
+        ));
+    }
+
+    #[test]
+    fn test_display_and_to_json() {
+        let mut row = sink_row().row;
+        row.insert(ColumnName::new("null".to_string()), SqlValue::Null)
+            .unwrap();
+        let row = SpringSinkRow::new(row);
+
+        assert_eq!(
+            row.to_string(),
+            r#"(text: "Tokyo", blob: [0, 1], int: 1, null: NULL)"#
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&row.to_json().unwrap()).unwrap();
+        assert_eq!(json["text"], "Tokyo");
+        assert_eq!(json["blob"], serde_json::json!([0, 1]));
+        assert_eq!(json["int"], 1);
+        assert!(json["null"].is_null());
+    }
+
+    #[test]
+    fn test_get_not_null_bytes_into() {
+        let row = sink_row();
+        assert_eq!(row.n_columns(), 3);
+
+        let mut buf = [0u8; 8];
+        assert_eq!(row.get_not_null_bytes_into(0, &mut buf).unwrap(), 5);
+        assert_eq!(&buf[..5], b"Tokyo");
+
+        assert_eq!(row.get_not_null_bytes_into(1, &mut buf).unwrap(), 2);
+        assert_eq!(&buf[..2], b"\x00\x01");
+    }
+
+    #[test]
+    fn test_get_not_null_bytes_into_truncated() {
+        let row = sink_row();
+
+        let mut buf = [0u8; 3];
+        assert_eq!(row.get_not_null_bytes_into(0, &mut buf).unwrap(), 5);
+        assert_eq!(&buf, b"Tok");
+    }
+
+    #[test]
+    fn test_get_not_null_bytes_into_error() {
+        let row = sink_row();
+
+        let mut buf = [0u8; 8];
+        assert!(matches!(
+            row.get_not_null_bytes_into(2, &mut buf),
+            Err(SpringError::Sql(_))
+        ));
+        assert!(matches!(
+            row.get_not_null_bytes_into(3, &mut buf),
+            Err(SpringError::Sql(_))
+        ));
     }
 }
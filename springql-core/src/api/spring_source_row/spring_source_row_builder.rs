@@ -1,7 +1,11 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use std::str;
+
+use anyhow::Context;
+
 use crate::{
-    api::{error::Result, SpringSourceRow},
+    api::{error::Result, SpringError, SpringSourceRow},
     pipeline::ColumnName,
     stream_engine::{autonomous_executor::SchemalessRow, SpringValue},
 };
@@ -11,6 +15,11 @@ use crate::{
 pub struct SpringSourceRowBuilder(SchemalessRow);
 
 impl SpringSourceRowBuilder {
+    /// Builder which can add `n_columns` columns without reallocation.
+    pub fn with_capacity(n_columns: usize) -> Self {
+        Self(SchemalessRow::with_capacity(n_columns))
+    }
+
     /// Add a column to the source row.
     ///
     /// # Failure
@@ -27,6 +36,27 @@ impl SpringSourceRowBuilder {
         Ok(self)
     }
 
+    /// Add a TEXT column from UTF-8 bytes (e.g. a fixed-capacity buffer from C binding).
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidFormat` when:
+    ///   - `utf8` is not a valid UTF-8 sequence.
+    /// - `SpringError::Sql` when:
+    ///   - `column_name` is already inserted.
+    pub fn add_text_column_from_utf8<S>(self, column_name: S, utf8: &[u8]) -> Result<Self>
+    where
+        S: Into<String>,
+    {
+        let text = str::from_utf8(utf8)
+            .context("TEXT column value must be UTF-8")
+            .map_err(|e| SpringError::InvalidFormat {
+                s: String::from_utf8_lossy(utf8).to_string(),
+                source: e,
+            })?;
+        self.add_column(column_name, text.to_string())
+    }
+
     /// Create a final source row.
     pub fn build(self) -> SpringSourceRow {
         SpringSourceRow::new(self.0)
@@ -49,6 +79,21 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_add_text_column_from_utf8() {
+        let builder = SpringSourceRowBuilder::with_capacity(1);
+        let _ = builder
+            .add_text_column_from_utf8("a", "🚔".as_bytes())
+            .unwrap()
+            .build();
+
+        let builder = SpringSourceRowBuilder::with_capacity(1);
+        assert!(matches!(
+            builder.add_text_column_from_utf8("a", &[0xff, 0xfe]),
+            Err(SpringError::InvalidFormat { .. })
+        ));
+    }
+
     #[test]
     fn test_build() {
         let builder = SpringSourceRowBuilder::default();
@@ -0,0 +1,30 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::sync::Arc;
+
+use crate::{api::SpringSinkRow, stream_engine::autonomous_executor::StreamTap};
+
+/// Temporary queue receiving copies of rows traversing a stream. Created by [SpringPipeline::tap()](crate::api::SpringPipeline::tap).
+#[derive(Debug)]
+pub struct SpringStreamTap(Arc<StreamTap>);
+
+impl SpringStreamTap {
+    pub(crate) fn new(tap: Arc<StreamTap>) -> Self {
+        Self(tap)
+    }
+
+    /// Pop a row from the tap. This is a non-blocking function.
+    ///
+    /// # Returns
+    ///
+    /// - `Some` when at least a row is in the tap.
+    /// - `None` when no row is in the tap.
+    pub fn pop_non_blocking(&self) -> Option<SpringSinkRow> {
+        self.0.pop_non_blocking().map(SpringSinkRow::new)
+    }
+
+    /// Whether the tap has stopped receiving rows because its TTL has passed.
+    pub fn is_expired(&self) -> bool {
+        self.0.is_expired()
+    }
+}
@@ -0,0 +1,50 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::time::Duration;
+
+/// Kind of a task.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum SpringTaskKind {
+    /// Reads rows by a source reader.
+    Source,
+    /// Processes rows by a pump.
+    Pump,
+    /// Writes rows by a sink writer.
+    Sink,
+}
+
+/// Task executed by a worker thread.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct SpringTaskInfo {
+    /// Kind of the task.
+    pub kind: SpringTaskKind,
+    /// Name of the source reader, pump or sink writer.
+    pub name: String,
+    /// ID of the worker thread executing the task. Source workers and generic workers are numbered separately.
+    pub worker_id: u16,
+}
+
+/// Outcome of a task execution.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct SpringTaskExecution {
+    /// Rows the task processed (read from its source or collected from its input queues).
+    pub processed_rows: u64,
+    /// Rows the task put into downstream queues.
+    pub rows_put: u64,
+    /// Wall-clock time of the execution.
+    pub elapsed: Duration,
+    /// Error message if the execution failed. The worker continues to execute tasks after errors.
+    pub error: Option<String>,
+}
+
+/// Middleware invoked before and after each task execution. Registered by [SpringPipeline::add_task_hook()](crate::api::SpringPipeline::add_task_hook).
+///
+/// Hooks run on worker threads in the order they are registered, so they delay task executions by their own execution time.
+/// Useful for custom accounting and tracing, or for chaos testing (e.g. sleeping in `before_task()`).
+pub trait SpringTaskHook: Send + Sync + 'static {
+    /// Called before a task is executed.
+    fn before_task(&self, _task: &SpringTaskInfo) {}
+
+    /// Called after a task is executed.
+    fn after_task(&self, _task: &SpringTaskInfo, _execution: &SpringTaskExecution) {}
+}
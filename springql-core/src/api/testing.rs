@@ -0,0 +1,122 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! [proptest](https://docs.rs/proptest) strategies to test pipelines, especially with windows, by randomized rows.
+//!
+//! ```text
+//! proptest! {
+//!     #[test]
+//!     fn test_my_window(rows in trade_source_rows(1..100, Duration::from_secs(1))) {
+//!         // push rows into a pipeline with `ALLOWED DELAY` of 1 sec and check its properties
+//!     }
+//! }
+//! ```
+//!
+//! Pipelines can also be tested by golden files. See [golden](self::golden) module for the format of cases.
+
+pub mod golden;
+
+use std::{ops::Range, time::Duration};
+
+use proptest::{collection::SizeRange, prelude::*};
+
+use crate::{api::SpringSourceRow, stream_engine::time::SpringTimestamp};
+
+/// Range of generated timestamps: `[2020-01-01, 2030-01-01)`.
+const TIMESTAMP_NANOS_RANGE: Range<i128> = 1_577_836_800_000_000_000..1_893_456_000_000_000_000;
+
+/// Max interval between rowtimes (before disorder).
+const MAX_ROWTIME_STEP_NANOS: i128 = 1_000_000_000;
+
+/// Tickers of `trade_source_rows()`. Few to make groups of multiple rows.
+const TICKERS: &[&str] = &["GOOGL", "ORCL", "IBM"];
+
+/// A timestamp in `[2020-01-01, 2030-01-01)`.
+pub fn spring_timestamp() -> impl Strategy<Value = SpringTimestamp> {
+    TIMESTAMP_NANOS_RANGE.prop_map(|nanos| {
+        SpringTimestamp::from_unix_timestamp_nanos(nanos).expect("in the range of timestamp")
+    })
+}
+
+/// Rowtimes mostly increasing by up to 1 second, but each of them may be behind the max rowtime so far by up to `max_disorder`.
+///
+/// Windows with `allowed_delay >= max_disorder` get no late rows.
+pub fn rowtimes(
+    len: impl Into<SizeRange>,
+    max_disorder: Duration,
+) -> impl Strategy<Value = Vec<SpringTimestamp>> {
+    let max_disorder_nanos = max_disorder.as_nanos() as i128;
+    (
+        spring_timestamp(),
+        prop::collection::vec((0..=MAX_ROWTIME_STEP_NANOS, 0..=max_disorder_nanos), len),
+    )
+        .prop_map(|(start, steps)| {
+            let mut base = start.unix_timestamp_nanos();
+            steps
+                .into_iter()
+                .map(|(step, disorder)| {
+                    // rowtime <= base and max(rowtime so far) <= base
+                    base += step;
+                    SpringTimestamp::from_unix_timestamp_nanos(base - disorder)
+                        .expect("in the range of timestamp")
+                })
+                .collect()
+        })
+}
+
+/// Rows of the stream below, in the order of `rowtimes()`.
+///
+/// ```sql
+/// CREATE SOURCE STREAM trade (
+///   ts TIMESTAMP NOT NULL ROWTIME,
+///   ticker TEXT NOT NULL,
+///   amount INTEGER NOT NULL
+/// );
+/// ```
+pub fn trade_source_rows(
+    len: impl Into<SizeRange>,
+    max_disorder: Duration,
+) -> impl Strategy<Value = Vec<SpringSourceRow>> {
+    rowtimes(len, max_disorder).prop_flat_map(|rowtimes| {
+        let n = rowtimes.len();
+        (
+            Just(rowtimes),
+            prop::collection::vec((prop::sample::select(TICKERS), 0..10_000_i32), n),
+        )
+            .prop_map(|(rowtimes, values)| {
+                rowtimes
+                    .into_iter()
+                    .zip(values)
+                    .map(|(ts, (ticker, amount))| {
+                        let json = serde_json::json!({
+                            "ts": ts.to_string(),
+                            "ticker": ticker,
+                            "amount": amount,
+                        });
+                        SpringSourceRow::from_json(&json.to_string()).expect("valid JSON")
+                    })
+                    .collect()
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn test_rowtimes_disorder(
+            rowtimes in rowtimes(0..50, Duration::from_millis(300))
+        ) {
+            let mut max_rowtime = None;
+            for rowtime in rowtimes {
+                let max = *max_rowtime.get_or_insert(rowtime);
+                if rowtime < max {
+                    let disorder = (max - rowtime).to_std().unwrap();
+                    prop_assert!(disorder <= Duration::from_millis(300));
+                }
+                max_rowtime = Some(max.max(rowtime));
+            }
+        }
+    }
+}
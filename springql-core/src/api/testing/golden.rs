@@ -0,0 +1,382 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Golden-file tests of pipelines.
+//!
+//! A case is a directory with:
+//!
+//! - `pipeline.sql`: statements separated by `;`, with `IN_MEMORY_QUEUE` source readers and sink writers.
+//! - `input.jsonl`: rows pushed in order, e.g. `{"queue": "q_trade", "row": {"ts": "2020-01-01 00:00:00.000000000", ...}}`.
+//! - `expected.jsonl`: rows expected in sink queues, in the same shape as `input.jsonl`.
+//!
+//! Time is virtual: source streams must have `ROWTIME` columns so that windows are driven only by the event time in
+//! `input.jsonl`, and wall-clock timeouts (`MIN WATERMARK IDLE TIMEOUT` of joins) must not be used.
+//! Windows open at the end of input are not closed; add a row far ahead to close them.
+//! Each input row is processed through the pipeline before the next one is pushed (see `Connection::run_until_idle()`),
+//! so a case does not wait for its output and gets the same output in every run.
+//! Rows are compared per queue regardless of their order. Queues without expected rows are not checked.
+//!
+//! In-memory queues are shared in a process, so queue names must be unique among cases (e.g. prefixed by the case name).
+//! Run with `SPRINGQL_BLESS=1` to write the actual output into `expected.jsonl`.
+
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::{
+        error::{foreign_info::ForeignInfo, Result, SpringError},
+        SpringConfig, SpringSourceRow,
+    },
+    connection::Connection,
+    stream_engine::autonomous_executor::JsonObject,
+};
+
+/// Set to write the actual output into `expected.jsonl` instead of comparing.
+const BLESS_ENV: &str = "SPRINGQL_BLESS";
+
+/// A line of `input.jsonl` or `expected.jsonl`.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct GoldenRecord {
+    /// In-memory queue name.
+    pub queue: String,
+    /// Row as a JSON object.
+    pub row: serde_json::Value,
+}
+
+/// A golden test case loaded from a directory.
+#[derive(Clone, PartialEq, Debug)]
+pub struct GoldenCase {
+    dir: PathBuf,
+    statements: Vec<String>,
+    input: Vec<GoldenRecord>,
+    expected: Vec<GoldenRecord>,
+}
+
+impl GoldenCase {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to read a file of the case.
+    /// - `SpringError::InvalidFormat` when:
+    ///   - a line of `input.jsonl` or `expected.jsonl` is not a record.
+    pub fn load<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        let expected_path = dir.join("expected.jsonl");
+        Ok(Self {
+            statements: split_statements(&read(&dir.join("pipeline.sql"))?),
+            input: parse_records(&read(&dir.join("input.jsonl"))?)?,
+            // a new case to bless may not have it yet
+            expected: if expected_path.exists() {
+                parse_records(&read(&expected_path)?)?
+            } else {
+                Vec::new()
+            },
+            dir,
+        })
+    }
+
+    /// Case name (directory name).
+    pub fn name(&self) -> String {
+        self.dir
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+
+    /// Runs the pipeline with the input and collects rows from queues in `expected.jsonl`.
+    ///
+    /// # Failure
+    ///
+    /// - Raises Err from statements in `pipeline.sql` as-is.
+    /// - Raises Err from pushing and processing rows in `input.jsonl` as-is.
+    pub fn run(&self) -> Result<Vec<GoldenRecord>> {
+        self.run_with_queues(&self.expected_queues())
+    }
+
+    /// Runs the case and compares its output with `expected.jsonl`, or writes the output into `expected.jsonl`
+    /// when `SPRINGQL_BLESS` is set.
+    ///
+    /// # Panics
+    ///
+    /// When the case fails or its output differs from `expected.jsonl`.
+    pub fn assert(&self) {
+        if std::env::var_os(BLESS_ENV).is_some() {
+            self.bless()
+                .unwrap_or_else(|e| panic!("{}: {:?}", self.name(), e));
+            return;
+        }
+
+        let actual = self
+            .run()
+            .unwrap_or_else(|e| panic!("{}: {:?}", self.name(), e));
+        if let Some(diff) = diff(&self.expected, &actual) {
+            panic!(
+                "{}: output differs from expected.jsonl (run with {}=1 to update)\n{}",
+                self.name(),
+                BLESS_ENV,
+                diff
+            );
+        }
+    }
+
+    /// Writes the output into `expected.jsonl`. Rows are collected from all queues in `pipeline.sql` that
+    /// `input.jsonl` does not push into.
+    fn bless(&self) -> Result<()> {
+        let input_queues = self
+            .input
+            .iter()
+            .map(|record| record.queue.as_str())
+            .collect::<BTreeSet<_>>();
+        let queues = self
+            .statements
+            .iter()
+            .filter_map(|statement| in_memory_queue_name(statement))
+            .filter(|queue| !input_queues.contains(queue.as_str()))
+            .collect::<BTreeSet<_>>();
+
+        let actual = self.run_with_queues(&queues)?;
+        let jsonl = actual
+            .iter()
+            .map(|record| serde_json::to_string(record).expect("records are serializable") + "\n")
+            .collect::<String>();
+
+        let path = self.dir.join("expected.jsonl");
+        fs::write(&path, jsonl)
+            .context("failed to write expected.jsonl")
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::File(path),
+            })
+    }
+
+    fn expected_queues(&self) -> BTreeSet<String> {
+        self.expected
+            .iter()
+            .map(|record| record.queue.clone())
+            .collect()
+    }
+
+    fn run_with_queues(&self, queues: &BTreeSet<String>) -> Result<Vec<GoldenRecord>> {
+        let conn = Connection::new(&SpringConfig::default())?;
+        for statement in &self.statements {
+            conn.command(statement)?;
+        }
+        for record in &self.input {
+            conn.push(
+                &record.queue,
+                SpringSourceRow::from_json(&record.row.to_string())?.into_schemaless_row()?,
+            )?;
+            conn.run_until_idle()?;
+        }
+
+        let mut actual = Vec::new();
+        for queue in queues {
+            while let Some(row) = conn.pop_non_blocking(queue)? {
+                actual.push(GoldenRecord {
+                    queue: queue.clone(),
+                    row: serde_json::Value::from(JsonObject::try_from(row)?),
+                });
+            }
+        }
+        Ok(actual)
+    }
+}
+
+/// Runs all cases in subdirectories of `dir` (in the order of their names) and reports all failing cases.
+///
+/// # Panics
+///
+/// When a case fails or `dir` cannot be read.
+pub fn assert_golden_dir<P: AsRef<Path>>(dir: P) {
+    let dir = dir.as_ref();
+    let mut case_dirs = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("failed to read {}: {:?}", dir.display(), e))
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_dir())
+        .collect::<Vec<_>>();
+    case_dirs.sort();
+
+    let failures = case_dirs
+        .iter()
+        .filter_map(|case_dir| {
+            let result = std::panic::catch_unwind(|| {
+                GoldenCase::load(case_dir)
+                    .unwrap_or_else(|e| panic!("{}: {:?}", case_dir.display(), e))
+                    .assert()
+            });
+            result.err().map(|panic| {
+                panic
+                    .downcast_ref::<String>()
+                    .cloned()
+                    .unwrap_or_else(|| format!("{}: panicked", case_dir.display()))
+            })
+        })
+        .collect::<Vec<_>>();
+
+    assert!(
+        failures.is_empty(),
+        "{} of {} golden cases failed:\n\n{}",
+        failures.len(),
+        case_dirs.len(),
+        failures.join("\n\n")
+    );
+}
+
+/// Lines of rows missing in or unexpected by `actual`, or None if they are the same regardless of order.
+fn diff(expected: &[GoldenRecord], actual: &[GoldenRecord]) -> Option<String> {
+    let mut unexpected = actual.to_vec();
+    let mut missing = Vec::new();
+    for record in expected {
+        match unexpected.iter().position(|actual| actual == record) {
+            Some(i) => {
+                let _ = unexpected.remove(i);
+            }
+            None => missing.push(record),
+        }
+    }
+
+    if missing.is_empty() && unexpected.is_empty() {
+        return None;
+    }
+    let lines =
+        missing
+            .into_iter()
+            .map(|record| format!("- {}", serde_json::to_string(record).expect("serializable")))
+            .chain(unexpected.iter().map(|record| {
+                format!("+ {}", serde_json::to_string(record).expect("serializable"))
+            }))
+            .collect::<Vec<_>>();
+    Some(lines.join("\n"))
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path)
+        .context("failed to read golden file")
+        .map_err(|e| SpringError::ForeignIo {
+            source: e,
+            foreign_info: ForeignInfo::File(path.to_path_buf()),
+        })
+}
+
+/// Blank lines are ignored.
+fn parse_records(jsonl: &str) -> Result<Vec<GoldenRecord>> {
+    jsonl
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line)
+                .context(r#"not a record: {"queue": "...", "row": {...}}"#)
+                .map_err(|e| SpringError::InvalidFormat {
+                    s: line.to_string(),
+                    source: e,
+                })
+        })
+        .collect()
+}
+
+/// Splits statements by `;` outside of quotes and `--` comments.
+fn split_statements(sql: &str) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut statement = String::new();
+    let mut quote = None;
+    let mut chars = sql.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '-') if chars.peek() == Some(&'-') => {
+                // skip the comment
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+                statement.push('\n');
+                continue;
+            }
+            (None, ';') => {
+                statements.push(statement.trim().to_string());
+                statement.clear();
+                continue;
+            }
+            (None, _) => {}
+        }
+        statement.push(c);
+    }
+    statements.push(statement.trim().to_string());
+
+    statements.retain(|statement| !statement.is_empty());
+    statements
+}
+
+/// `NAME` option of an `IN_MEMORY_QUEUE` source reader or sink writer.
+fn in_memory_queue_name(statement: &str) -> Option<String> {
+    let upper = statement.to_uppercase();
+    if !upper.contains("IN_MEMORY_QUEUE") {
+        return None;
+    }
+    let options_at = upper.find("OPTIONS")?;
+    let name_at = options_at + upper[options_at..].find("NAME")?;
+    let rest = &statement[name_at + "NAME".len()..];
+    let start = rest.find('\'')? + 1;
+    let len = rest[start..].find('\'')?;
+    Some(rest[start..start + len].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_statements() {
+        let sql = "
+            -- comment with ; inside
+            CREATE SOURCE STREAM s (c TEXT NOT NULL);
+            CREATE SINK WRITER w FOR s TYPE IN_MEMORY_QUEUE OPTIONS (NAME 'q;1');
+        ";
+        let statements = split_statements(sql);
+        assert_eq!(
+            statements,
+            vec![
+                "CREATE SOURCE STREAM s (c TEXT NOT NULL)",
+                "CREATE SINK WRITER w FOR s TYPE IN_MEMORY_QUEUE OPTIONS (NAME 'q;1')"
+            ]
+        );
+        assert_eq!(in_memory_queue_name(&statements[0]), None);
+        assert_eq!(
+            in_memory_queue_name(&statements[1]),
+            Some("q;1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_diff() {
+        let record = |queue: &str, v: i32| GoldenRecord {
+            queue: queue.to_string(),
+            row: serde_json::json!({ "v": v }),
+        };
+
+        assert_eq!(
+            diff(
+                &[record("q", 1), record("q", 2)],
+                &[record("q", 2), record("q", 1)]
+            ),
+            None
+        );
+        assert_eq!(
+            diff(
+                &[record("q", 1), record("q", 1)],
+                &[record("q", 1), record("r", 1)]
+            )
+            .unwrap(),
+            "- {\"queue\":\"q\",\"row\":{\"v\":1}}\n+ {\"queue\":\"r\",\"row\":{\"v\":1}}"
+        );
+    }
+}
@@ -1,12 +1,26 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use std::{sync::Once, thread, time::Duration};
+use std::{
+    sync::{Arc, MutexGuard, Once},
+    thread,
+    time::Duration,
+};
+
+use anyhow::anyhow;
 
 use crate::{
-    api::{error::Result, SpringConfig},
-    pipeline::QueueName,
+    api::{
+        error::{Result, SpringError},
+        SpringConfig, SpringPermissionConfig, SpringTaskHook,
+    },
+    management::ManagementListener,
+    pipeline::{ColumnDataType, QueueName, StreamName},
     sql_processor::SqlProcessor,
-    stream_engine::{autonomous_executor::SchemalessRow, command::Command, EngineMutex},
+    stream_engine::{
+        autonomous_executor::{JsonObject, SchemalessRow, StreamTap},
+        command::Command,
+        EngineMutex, PipelineSnapshot, StreamEngine,
+    },
 };
 
 fn setup_logger() {
@@ -29,37 +43,208 @@ fn setup_logger() {
 pub struct Connection {
     engine: EngineMutex,
     sql_processor: SqlProcessor,
+    permission: SpringPermissionConfig,
+    /// Whether the pipeline is reconstructed from statements in the DDL WAL or a snapshot.
+    recovered: bool,
+
+    /// Stops on drop.
+    _management_listener: Option<ManagementListener>,
 }
 
 impl Connection {
-    pub fn new(config: &SpringConfig) -> Self {
+    /// Replays statements in the DDL WAL (`recovery.ddl_wal_path`) if any.
+    ///
+    /// # Failure
+    ///
+    /// - Raises Err from `StreamEngine::open_ddl_wal()` as-is.
+    /// - Raises Err from `Connection::command()` as-is if a statement in the DDL WAL fails.
+    /// - Raises Err from `ManagementListener::start()` as-is.
+    pub fn new(config: &SpringConfig) -> Result<Self> {
+        Self::start(config, None)
+    }
+
+    /// Replays statements in `snapshot` and then puts rows and window states in it into the pipeline.
+    /// The statements are appended to the DDL WAL if `recovery.ddl_wal_path` is set.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidConfig` when:
+    ///   - the DDL WAL already has statements.
+    /// - Raises Err from `Connection::new()` as-is.
+    /// - Raises Err from `StreamEngine::restore_state()` as-is.
+    pub fn restore(config: &SpringConfig, snapshot: PipelineSnapshot) -> Result<Self> {
+        Self::start(config, Some(snapshot))
+    }
+
+    fn start(config: &SpringConfig, snapshot: Option<PipelineSnapshot>) -> Result<Self> {
         setup_logger();
 
         let engine = EngineMutex::new(config);
-        let sql_processor = SqlProcessor::default();
+        let sql_processor = SqlProcessor::new(config.pipeline.type_checking());
+
+        let mut recovered = false;
+        if let Some((ddl_wal, statements)) = StreamEngine::open_ddl_wal(config)? {
+            if snapshot.is_some() && !statements.is_empty() {
+                return Err(SpringError::InvalidConfig {
+                    source: anyhow!(
+                        "cannot restore a snapshot while recovery.ddl_wal_path has statements to replay"
+                    ),
+                });
+            }
 
-        Self {
+            log::info!(
+                "[Connection] Replaying {} statements in DDL WAL",
+                statements.len()
+            );
+            for statement in &statements {
+                Self::execute(engine.get()?, &sql_processor, None, statement)?;
+            }
+            recovered = !statements.is_empty();
+            engine.get()?.attach_ddl_wal(ddl_wal);
+        }
+
+        if let Some(snapshot) = snapshot {
+            log::info!(
+                "[Connection] Restoring a snapshot with {} statements",
+                snapshot.statements().len()
+            );
+            for statement in snapshot.statements() {
+                Self::execute(engine.get()?, &sql_processor, None, statement)?;
+            }
+            engine.get()?.restore_state(snapshot)?;
+            recovered = true;
+        }
+
+        let management_listener = ManagementListener::start(
+            &config.management,
+            &config.permission,
+            config.pipeline.type_checking(),
+            engine.clone(),
+        )?;
+
+        Ok(Self {
             engine,
             sql_processor,
+            permission: config.permission.clone(),
+            recovered,
+            _management_listener: management_listener,
+        })
+    }
+
+    /// # Returns
+    ///
+    /// - `Ok(Some)` for commands returning a text (e.g. `INFER SCHEMA`, `SHOW HISTORY`, `SHOW WARNINGS`).
+    ///   Commands returning rows (e.g. `SHOW STREAMS`) return a JSON object per row, a line per object.
+    /// - `Ok(None)` for commands altering pipeline.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::PermissionDenied` when:
+    ///   - the statement is not allowed by `permission.role`.
+    pub fn command(&self, sql: &str) -> Result<Option<String>> {
+        Self::execute(
+            self.lock_engine()?,
+            &self.sql_processor,
+            Some(&self.permission),
+            sql,
+        )
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::PermissionDenied` when:
+    ///   - the statement is not allowed by `permission.role`.
+    /// - `SpringError::Sql` when:
+    ///   - the command does not return rows.
+    pub fn command_query(&self, sql: &str) -> Result<Vec<SchemalessRow>> {
+        let engine = self.lock_engine()?;
+        match Self::compile(&engine, &self.sql_processor, Some(&self.permission), sql)? {
+            Command::ShowObjects(c) => Ok(engine.show_objects(c)),
+            _ => Err(SpringError::Sql(anyhow!(
+                "command does not return any row: {}",
+                sql
+            ))),
         }
     }
 
-    pub fn command(&self, sql: &str) -> Result<()> {
-        let mut engine = self.engine.get()?;
+    /// Whether the pipeline is reconstructed from statements in the DDL WAL or a snapshot, in which case DDLs in the config are not executed again.
+    pub fn is_recovered(&self) -> bool {
+        self.recovered
+    }
 
-        let command = self.sql_processor.compile(sql, engine.current_pipeline())?;
+    /// `command()` without restriction by `permission.role`, for DDLs in the config.
+    pub fn command_unrestricted(&self, sql: &str) -> Result<Option<String>> {
+        Self::execute(self.lock_engine()?, &self.sql_processor, None, sql)
+    }
+
+    /// `command()` for an engine shared with the management listener.
+    ///
+    /// `INFER SCHEMA` releases `engine` before sampling, not to block the pipeline while waiting for the source.
+    pub(crate) fn execute(
+        mut engine: MutexGuard<'_, StreamEngine>,
+        sql_processor: &SqlProcessor,
+        permission: Option<&SpringPermissionConfig>,
+        sql: &str,
+    ) -> Result<Option<String>> {
+        match Self::compile(&engine, sql_processor, permission, sql)? {
+            Command::AlterPipeline(c) => engine.alter_pipeline(c, sql).map(|_| None),
+            Command::InferSchema(c) => {
+                let source_reader_config = *engine.source_reader_config();
+                drop(engine);
+                StreamEngine::infer_schema(c, &source_reader_config).map(Some)
+            }
+            Command::ShowHistory => Ok(Some(engine.show_history())),
+            Command::ShowWarnings => Ok(Some(engine.show_warnings())),
+            Command::ShowObjects(c) => Ok(Some(
+                engine
+                    .show_objects(c)
+                    .into_iter()
+                    .map(|row| JsonObject::try_from(row).map(|json| json.to_string()))
+                    .collect::<Result<Vec<_>>>()?
+                    .join("\n"),
+            )),
+            Command::Explain(query_plan) => Ok(Some(
+                serde_json::to_string_pretty(&query_plan.explain())
+                    .expect("JSON value is always serializable"),
+            )),
+        }
+    }
 
-        match command {
-            Command::AlterPipeline(c) => engine.alter_pipeline(c),
+    /// # Failure
+    ///
+    /// - `SpringError::PermissionDenied` when:
+    ///   - the statement is not allowed by `permission`.
+    fn compile(
+        engine: &StreamEngine,
+        sql_processor: &SqlProcessor,
+        permission: Option<&SpringPermissionConfig>,
+        sql: &str,
+    ) -> Result<Command> {
+        let command = sql_processor.compile(sql, engine.current_pipeline())?;
+        if let Some(permission) = permission {
+            if !permission.allows(command.statement()) {
+                return Err(SpringError::PermissionDenied {
+                    statement: command.statement().to_string(),
+                });
+            }
         }
+        Ok(command)
+    }
+
+    pub fn snapshot(&self) -> Result<PipelineSnapshot> {
+        let engine = self.lock_engine()?;
+        Ok(engine.snapshot())
     }
 
     pub fn pop(&self, queue: &str) -> Result<SchemalessRow> {
         const SLEEP_MSECS: u64 = 10;
 
-        let mut engine = self.engine.get()?;
+        let mut engine = self.lock_engine()?;
 
         loop {
+            if let Some(e) = engine.take_fatal_error() {
+                return Err(e);
+            }
             if let Some(sink_row) =
                 engine.pop_in_memory_queue_non_blocking(QueueName::new(queue.to_string()))?
             {
@@ -71,14 +256,56 @@ impl Connection {
     }
 
     pub fn pop_non_blocking(&self, queue: &str) -> Result<Option<SchemalessRow>> {
-        let mut engine = self.engine.get()?;
+        let mut engine = self.lock_engine()?;
         let sink_row =
             engine.pop_in_memory_queue_non_blocking(QueueName::new(queue.to_string()))?;
         Ok(sink_row)
     }
 
+    /// See `StreamEngine::in_memory_queue_columns()`.
+    pub fn in_memory_queue_columns(&self, queue: &str) -> Result<Option<Vec<ColumnDataType>>> {
+        let engine = self.lock_engine()?;
+        Ok(engine.in_memory_queue_columns(&QueueName::new(queue.to_string())))
+    }
+
+    pub fn tap(&self, stream: &str, capacity: usize, ttl: Duration) -> Result<Arc<StreamTap>> {
+        let engine = self.lock_engine()?;
+        engine.tap_stream(StreamName::new(stream.to_string()), capacity, ttl)
+    }
+
+    pub fn add_task_hook(&self, hook: Arc<dyn SpringTaskHook>) -> Result<()> {
+        let engine = self.lock_engine()?;
+        engine.add_task_hook(hook);
+        Ok(())
+    }
+
+    /// Processes rows pushed so far through the pipeline in the calling thread. See `StreamEngine::run_until_idle()`.
+    ///
+    /// # Failure
+    ///
+    /// - Raises a fatal error got while processing the rows as-is.
+    pub fn run_until_idle(&self) -> Result<()> {
+        let engine = self.lock_engine()?;
+        engine.run_until_idle();
+        engine.take_fatal_error().map_or(Ok(()), Err)
+    }
+
     pub fn push(&self, queue: &str, row: SchemalessRow) -> Result<()> {
-        let mut engine = self.engine.get()?;
+        let mut engine = self.lock_engine()?;
         engine.push_in_memory_queue(QueueName::new(queue.to_string()), row)
     }
+
+    /// Locks the engine for an API call.
+    ///
+    /// # Failure
+    ///
+    /// - Raises Err from `EngineMutex::get()` as-is.
+    /// - Raises a fatal error got by workers since the last API call as-is (see `StreamEngine::take_fatal_error()`).
+    fn lock_engine(&self) -> Result<MutexGuard<'_, StreamEngine>> {
+        let engine = self.engine.get()?;
+        match engine.take_fatal_error() {
+            Some(e) => Err(e),
+            None => Ok(engine),
+        }
+    }
 }
@@ -10,7 +10,7 @@ use anyhow::anyhow;
 use crate::{
     api::error::{Result, SpringError},
     expression::{AggrExpr, ValueExpr},
-    pipeline::{AggrAlias, ValueAlias},
+    pipeline::{AggrAlias, ColumnReference, ValueAlias},
     sql_processor::SelectFieldSyntax,
     stream_engine::{SqlValue, Tuple},
 };
@@ -111,6 +111,16 @@ impl ExprResolver {
             })
     }
 
+    /// # Panics
+    ///
+    /// -  `label` is not found
+    pub fn resolve_value_expr(&self, label: ValueExprLabel) -> ValueExpr {
+        self.value_expressions
+            .get(&label)
+            .cloned()
+            .unwrap_or_else(|| panic!("label {:?} not found", label))
+    }
+
     /// # Panics
     ///
     /// -  `label` is not found
@@ -138,6 +148,19 @@ impl ExprResolver {
         label
     }
 
+    /// Column references in all the registered value expressions and aggregated expressions.
+    pub fn column_references(&self) -> Vec<&ColumnReference> {
+        self.value_expressions
+            .values()
+            .chain(
+                self.aggr_expressions
+                    .values()
+                    .map(|aggr_expr| &aggr_expr.aggregated),
+            )
+            .flat_map(|value_expr| value_expr.column_references())
+            .collect()
+    }
+
     /// label -> (internal) value expression + tuple (for ColumnReference) -> SqlValue.
     ///
     /// # Panics
@@ -5,16 +5,19 @@
 mod boolean_expression;
 mod function_call;
 mod operator;
+mod string_matching;
 
 pub use boolean_expression::{BinaryExpr, ComparisonFunction, LogicalFunction, NumericalFunction};
 pub use function_call::FunctionCall;
 pub use operator::{BinaryOperator, UnaryOperator};
+pub use string_matching::validate_regexp;
 
 use anyhow::anyhow;
+use springql_expr::logic;
 
 use crate::{
     api::error::{Result, SpringError},
-    pipeline::{AggregateFunctionParameter, ColumnReference},
+    pipeline::{AggregateFunctionParameter, ColumnReference, WindowMetadata},
     stream_engine::{
         time::{SpringDuration, SpringEventDuration},
         NnSqlValue, SqlCompareResult, SqlValue, Tuple,
@@ -35,6 +38,9 @@ pub enum ValueExpr {
     FunctionCall(FunctionCall<Self>),
 
     ColumnReference(ColumnReference),
+
+    /// Only available in select_list of aggregation over window.
+    WindowMetadata(WindowMetadata),
 }
 impl ValueExprType for ValueExpr {}
 
@@ -48,6 +54,11 @@ impl ValueExpr {
                 Ok(ValueExprPh2::Constant(value))
             }
 
+            Self::WindowMetadata(window_metadata) => Err(SpringError::Sql(anyhow!(
+                "{:?} is only available in select list of aggregation over window",
+                window_metadata
+            ))),
+
             Self::FunctionCall(function_call) => match function_call {
                 FunctionCall::DurationMillis { duration_millis } => {
                     let duration_millis_ph2 = duration_millis.resolve_colref(tuple)?;
@@ -69,6 +80,31 @@ impl ValueExpr {
                         resolution: Box::new(resolution_ph2),
                     }))
                 }
+                FunctionCall::RegexpMatch { target, pattern } => {
+                    let target_ph2 = target.resolve_colref(tuple)?;
+                    let pattern_ph2 = pattern.resolve_colref(tuple)?;
+                    Ok(ValueExprPh2::FunctionCall(FunctionCall::RegexpMatch {
+                        target: Box::new(target_ph2),
+                        pattern: Box::new(pattern_ph2),
+                    }))
+                }
+                FunctionCall::Coalesce { values } => {
+                    let values_ph2 = values
+                        .into_iter()
+                        .map(|expr| expr.resolve_colref(tuple))
+                        .collect::<Result<Vec<_>>>()?;
+                    Ok(ValueExprPh2::FunctionCall(FunctionCall::Coalesce {
+                        values: values_ph2,
+                    }))
+                }
+                FunctionCall::NullIf { value, compared } => {
+                    let value_ph2 = value.resolve_colref(tuple)?;
+                    let compared_ph2 = compared.resolve_colref(tuple)?;
+                    Ok(ValueExprPh2::FunctionCall(FunctionCall::NullIf {
+                        value: Box::new(value_ph2),
+                        compared: Box::new(compared_ph2),
+                    }))
+                }
             },
             Self::UnaryOperator(op, expr_ph1) => {
                 let expr_ph2 = expr_ph1.resolve_colref(tuple)?;
@@ -101,6 +137,82 @@ impl ValueExpr {
                                 ),
                             ))
                         }
+                        ComparisonFunction::LikeVariant { left, right } => {
+                            let left_ph2 = left.resolve_colref(tuple)?;
+                            let right_ph2 = right.resolve_colref(tuple)?;
+                            Ok(ValueExprPh2::BinaryExpr(
+                                BinaryExpr::ComparisonFunctionVariant(
+                                    ComparisonFunction::LikeVariant {
+                                        left: Box::new(left_ph2),
+                                        right: Box::new(right_ph2),
+                                    },
+                                ),
+                            ))
+                        }
+                        ComparisonFunction::IsDistinctFromVariant { left, right } => {
+                            let left_ph2 = left.resolve_colref(tuple)?;
+                            let right_ph2 = right.resolve_colref(tuple)?;
+                            Ok(ValueExprPh2::BinaryExpr(
+                                BinaryExpr::ComparisonFunctionVariant(
+                                    ComparisonFunction::IsDistinctFromVariant {
+                                        left: Box::new(left_ph2),
+                                        right: Box::new(right_ph2),
+                                    },
+                                ),
+                            ))
+                        }
+                        ComparisonFunction::IsNotDistinctFromVariant { left, right } => {
+                            let left_ph2 = left.resolve_colref(tuple)?;
+                            let right_ph2 = right.resolve_colref(tuple)?;
+                            Ok(ValueExprPh2::BinaryExpr(
+                                BinaryExpr::ComparisonFunctionVariant(
+                                    ComparisonFunction::IsNotDistinctFromVariant {
+                                        left: Box::new(left_ph2),
+                                        right: Box::new(right_ph2),
+                                    },
+                                ),
+                            ))
+                        }
+                        ComparisonFunction::InVariant {
+                            target,
+                            list,
+                            negated,
+                        } => {
+                            let target_ph2 = target.resolve_colref(tuple)?;
+                            let list_ph2 = list
+                                .into_iter()
+                                .map(|expr| expr.resolve_colref(tuple))
+                                .collect::<Result<Vec<_>>>()?;
+                            Ok(ValueExprPh2::BinaryExpr(
+                                BinaryExpr::ComparisonFunctionVariant(
+                                    ComparisonFunction::InVariant {
+                                        target: Box::new(target_ph2),
+                                        list: list_ph2,
+                                        negated,
+                                    },
+                                ),
+                            ))
+                        }
+                        ComparisonFunction::BetweenVariant {
+                            target,
+                            low,
+                            high,
+                            negated,
+                        } => {
+                            let target_ph2 = target.resolve_colref(tuple)?;
+                            let low_ph2 = low.resolve_colref(tuple)?;
+                            let high_ph2 = high.resolve_colref(tuple)?;
+                            Ok(ValueExprPh2::BinaryExpr(
+                                BinaryExpr::ComparisonFunctionVariant(
+                                    ComparisonFunction::BetweenVariant {
+                                        target: Box::new(target_ph2),
+                                        low: Box::new(low_ph2),
+                                        high: Box::new(high_ph2),
+                                        negated,
+                                    },
+                                ),
+                            ))
+                        }
                     }
                 }
                 BinaryExpr::NumericalFunctionVariant(numerical_function) => {
@@ -134,6 +246,64 @@ impl ValueExpr {
             },
         }
     }
+
+    /// Column references in this expression (including sub-expressions), in appearance order.
+    pub fn column_references(&self) -> Vec<&ColumnReference> {
+        match self {
+            Self::ColumnReference(colref) => vec![colref],
+            _ => self
+                .operands()
+                .into_iter()
+                .flat_map(|operand| operand.column_references())
+                .collect(),
+        }
+    }
+
+    fn operands(&self) -> Vec<&Self> {
+        match self {
+            Self::Constant(_) | Self::ColumnReference(_) | Self::WindowMetadata(_) => vec![],
+            Self::UnaryOperator(_, child) => vec![child.as_ref()],
+            Self::BinaryExpr(binary_expr) => match binary_expr {
+                BinaryExpr::LogicalFunctionVariant(LogicalFunction::AndVariant { left, right })
+                | BinaryExpr::NumericalFunctionVariant(NumericalFunction::AddVariant {
+                    left,
+                    right,
+                })
+                | BinaryExpr::NumericalFunctionVariant(NumericalFunction::MulVariant {
+                    left,
+                    right,
+                }) => vec![left.as_ref(), right.as_ref()],
+                BinaryExpr::ComparisonFunctionVariant(comparison_function) => {
+                    match comparison_function {
+                        ComparisonFunction::EqualVariant { left, right }
+                        | ComparisonFunction::LikeVariant { left, right }
+                        | ComparisonFunction::IsDistinctFromVariant { left, right }
+                        | ComparisonFunction::IsNotDistinctFromVariant { left, right } => {
+                            vec![left.as_ref(), right.as_ref()]
+                        }
+                        ComparisonFunction::InVariant { target, list, .. } => {
+                            std::iter::once(target.as_ref()).chain(list).collect()
+                        }
+                        ComparisonFunction::BetweenVariant {
+                            target, low, high, ..
+                        } => vec![target.as_ref(), low.as_ref(), high.as_ref()],
+                    }
+                }
+            },
+            Self::FunctionCall(function_call) => match function_call {
+                FunctionCall::DurationMillis { duration_millis } => vec![duration_millis.as_ref()],
+                FunctionCall::DurationSecs { duration_secs } => vec![duration_secs.as_ref()],
+                FunctionCall::FloorTime { target, resolution } => {
+                    vec![target.as_ref(), resolution.as_ref()]
+                }
+                FunctionCall::RegexpMatch { target, pattern } => {
+                    vec![target.as_ref(), pattern.as_ref()]
+                }
+                FunctionCall::Coalesce { values } => values.iter().collect(),
+                FunctionCall::NullIf { value, compared } => vec![value.as_ref(), compared.as_ref()],
+            },
+        }
+    }
 }
 
 /// Value Expression (phase2).
@@ -169,22 +339,46 @@ impl ValueExprPh2 {
                             let right_sql_value = right.eval()?;
                             left_sql_value
                                 .sql_compare(&right_sql_value)
-                                .map(|sql_compare_result| {
-                                    SqlValue::NotNull(NnSqlValue::Boolean(matches!(
-                                        sql_compare_result,
-                                        SqlCompareResult::Eq
-                                    )))
+                                .map(|sql_compare_result| match sql_compare_result {
+                                    SqlCompareResult::Null => SqlValue::Null,
+                                    SqlCompareResult::Eq => Self::sql_bool(Some(true)),
+                                    SqlCompareResult::LessThan | SqlCompareResult::GreaterThan => {
+                                        Self::sql_bool(Some(false))
+                                    }
                                 })
                         }
+                        ComparisonFunction::IsDistinctFromVariant { left, right } => {
+                            let distinct = Self::eval_distinct(*left, *right)?;
+                            Ok(Self::sql_bool(Some(distinct)))
+                        }
+                        ComparisonFunction::IsNotDistinctFromVariant { left, right } => {
+                            let distinct = Self::eval_distinct(*left, *right)?;
+                            Ok(Self::sql_bool(Some(!distinct)))
+                        }
+                        ComparisonFunction::LikeVariant { left, right } => {
+                            Self::eval_text_match("LIKE", *left, *right, |s, pattern| {
+                                Ok(string_matching::like(s, pattern))
+                            })
+                        }
+                        ComparisonFunction::InVariant {
+                            target,
+                            list,
+                            negated,
+                        } => Self::eval_in(*target, list, negated),
+                        ComparisonFunction::BetweenVariant {
+                            target,
+                            low,
+                            high,
+                            negated,
+                        } => Self::eval_between(*target, *low, *high, negated),
                     }
                 }
                 BinaryExpr::LogicalFunctionVariant(logical_function) => match logical_function {
                     LogicalFunction::AndVariant { left, right } => {
-                        let left_sql_value = left.eval()?;
-                        let right_sql_value = right.eval()?;
+                        let left_b = Self::to_sql_bool(left.eval()?)?;
+                        let right_b = Self::to_sql_bool(right.eval()?)?;
 
-                        let b = left_sql_value.to_bool()? && right_sql_value.to_bool()?;
-                        Ok(SqlValue::NotNull(NnSqlValue::Boolean(b)))
+                        Ok(Self::sql_bool(logic::and(left_b, right_b)))
                     }
                 },
                 BinaryExpr::NumericalFunctionVariant(numerical_function) => {
@@ -194,6 +388,60 @@ impl ValueExprPh2 {
             Self::FunctionCall(function_call) => Self::eval_function_call(function_call),
         }
     }
+
+    /// TRUE if target equals to any of the list. Otherwise, NULL if target or any of the list is NULL, FALSE if not.
+    fn eval_in(target: Self, list: Vec<Self>, negated: bool) -> Result<SqlValue> {
+        let target_value = target.eval()?;
+
+        let found = logic::in_list(
+            list.into_iter()
+                .map(|expr| target_value.sql_compare(&expr.eval()?)),
+        )?;
+        Ok(Self::sql_bool(found.map(|b| b != negated)))
+    }
+
+    /// `low <= target AND target <= high` in three-valued logic.
+    fn eval_between(target: Self, low: Self, high: Self, negated: bool) -> Result<SqlValue> {
+        let target_value = target.eval()?;
+
+        let between = logic::between(
+            target_value.sql_compare(&low.eval()?)?,
+            target_value.sql_compare(&high.eval()?)?,
+        );
+        Ok(Self::sql_bool(between.map(|b| b != negated)))
+    }
+
+    /// NULL is not distinct from NULL, and is distinct from any NOT NULL value.
+    fn eval_distinct(left: Self, right: Self) -> Result<bool> {
+        let left_sql_value = left.eval()?;
+        let right_sql_value = right.eval()?;
+        match (&left_sql_value, &right_sql_value) {
+            (SqlValue::Null, SqlValue::Null) => Ok(false),
+            (SqlValue::Null, _) | (_, SqlValue::Null) => Ok(true),
+            _ => left_sql_value
+                .sql_compare(&right_sql_value)
+                .map(|res| res != SqlCompareResult::Eq),
+        }
+    }
+
+    /// None as NULL (UNKNOWN).
+    fn sql_bool(b: Option<bool>) -> SqlValue {
+        b.map_or(SqlValue::Null, |b| {
+            SqlValue::NotNull(NnSqlValue::Boolean(b))
+        })
+    }
+
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `sql_value` is neither BOOLEAN nor NULL.
+    fn to_sql_bool(sql_value: SqlValue) -> Result<Option<bool>> {
+        match sql_value {
+            SqlValue::Null => Ok(None),
+            SqlValue::NotNull(_) => sql_value.to_bool().map(Some),
+        }
+    }
+
     fn eval_numerical_function(numerical_function: NumericalFunction<Self>) -> Result<SqlValue> {
         match numerical_function {
             NumericalFunction::AddVariant { left, right } => {
@@ -220,6 +468,62 @@ impl ValueExprPh2 {
             FunctionCall::DurationSecs { duration_secs } => {
                 Self::eval_function_duration_secs(*duration_secs)
             }
+            FunctionCall::RegexpMatch { target, pattern } => Self::eval_text_match(
+                "REGEXP_MATCH",
+                *target,
+                *pattern,
+                string_matching::regexp_match,
+            ),
+            FunctionCall::Coalesce { values } => Self::eval_function_coalesce(values),
+            FunctionCall::NullIf { value, compared } => {
+                Self::eval_function_nullif(*value, *compared)
+            }
+        }
+    }
+
+    /// Values after the first NOT NULL value are not evaluated.
+    fn eval_function_coalesce(values: Vec<Self>) -> Result<SqlValue> {
+        for expr in values {
+            let sql_value = expr.eval()?;
+            if let SqlValue::NotNull(_) = sql_value {
+                return Ok(sql_value);
+            }
+        }
+        Ok(SqlValue::Null)
+    }
+
+    /// NULL if `value` equals to `compared`. Otherwise `value` (including when either is NULL).
+    fn eval_function_nullif(value: Self, compared: Self) -> Result<SqlValue> {
+        let sql_value = value.eval()?;
+        let compared_value = compared.eval()?;
+        match sql_value.sql_compare(&compared_value)? {
+            SqlCompareResult::Eq => Ok(SqlValue::Null),
+            _ => Ok(sql_value),
+        }
+    }
+
+    /// NULL if either of `target` or `pattern` is NULL.
+    fn eval_text_match(
+        op_name: &str,
+        target: Self,
+        pattern: Self,
+        matcher: impl Fn(&str, &str) -> Result<bool>,
+    ) -> Result<SqlValue> {
+        let target_value = target.eval()?;
+        let pattern_value = pattern.eval()?;
+
+        match (&target_value, &pattern_value) {
+            (SqlValue::Null, _) | (_, SqlValue::Null) => Ok(SqlValue::Null),
+            (
+                SqlValue::NotNull(NnSqlValue::Text(s)),
+                SqlValue::NotNull(NnSqlValue::Text(pattern)),
+            ) => matcher(s, pattern).map(|b| SqlValue::NotNull(NnSqlValue::Boolean(b))),
+            _ => Err(SpringError::Sql(anyhow!(
+                "invalid parameter to {}: `({}, {})`",
+                op_name,
+                target_value,
+                pattern_value
+            ))),
         }
     }
 
@@ -277,3 +581,241 @@ pub struct AggrExpr {
     pub func: AggregateFunctionParameter,
     pub aggregated: ValueExpr,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn int(i: i32) -> ValueExprPh2 {
+        ValueExprPh2::Constant(SqlValue::NotNull(NnSqlValue::Integer(i)))
+    }
+    fn null() -> ValueExprPh2 {
+        ValueExprPh2::Constant(SqlValue::Null)
+    }
+    fn bool_value(b: bool) -> SqlValue {
+        SqlValue::NotNull(NnSqlValue::Boolean(b))
+    }
+
+    fn in_expr(target: ValueExprPh2, list: Vec<ValueExprPh2>, negated: bool) -> ValueExprPh2 {
+        ValueExprPh2::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+            ComparisonFunction::InVariant {
+                target: Box::new(target),
+                list,
+                negated,
+            },
+        ))
+    }
+    fn between_expr(
+        target: ValueExprPh2,
+        low: ValueExprPh2,
+        high: ValueExprPh2,
+        negated: bool,
+    ) -> ValueExprPh2 {
+        ValueExprPh2::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+            ComparisonFunction::BetweenVariant {
+                target: Box::new(target),
+                low: Box::new(low),
+                high: Box::new(high),
+                negated,
+            },
+        ))
+    }
+    fn comparison_expr(
+        f: impl Fn(Box<ValueExprPh2>, Box<ValueExprPh2>) -> ComparisonFunction<ValueExprPh2>,
+        left: ValueExprPh2,
+        right: ValueExprPh2,
+    ) -> ValueExprPh2 {
+        ValueExprPh2::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(f(
+            Box::new(left),
+            Box::new(right),
+        )))
+    }
+    fn and_expr(left: ValueExprPh2, right: ValueExprPh2) -> ValueExprPh2 {
+        ValueExprPh2::BinaryExpr(BinaryExpr::LogicalFunctionVariant(
+            LogicalFunction::AndVariant {
+                left: Box::new(left),
+                right: Box::new(right),
+            },
+        ))
+    }
+    fn bool_expr(b: bool) -> ValueExprPh2 {
+        ValueExprPh2::Constant(bool_value(b))
+    }
+
+    #[test]
+    fn test_in() {
+        assert_eq!(
+            in_expr(int(2), vec![int(1), int(2)], false).eval().unwrap(),
+            bool_value(true)
+        );
+        assert_eq!(
+            in_expr(int(3), vec![int(1), int(2)], false).eval().unwrap(),
+            bool_value(false)
+        );
+        assert_eq!(
+            in_expr(int(3), vec![int(1), int(2)], true).eval().unwrap(),
+            bool_value(true)
+        );
+
+        // NULL in the list makes non-matching result unknown
+        assert_eq!(
+            in_expr(int(2), vec![null(), int(2)], false).eval().unwrap(),
+            bool_value(true)
+        );
+        assert!(matches!(
+            in_expr(int(3), vec![null(), int(2)], false).eval().unwrap(),
+            SqlValue::Null
+        ));
+        assert!(matches!(
+            in_expr(int(3), vec![null(), int(2)], true).eval().unwrap(),
+            SqlValue::Null
+        ));
+        assert!(matches!(
+            in_expr(null(), vec![int(1)], false).eval().unwrap(),
+            SqlValue::Null
+        ));
+    }
+
+    #[test]
+    fn test_between() {
+        assert_eq!(
+            between_expr(int(1), int(1), int(3), false).eval().unwrap(),
+            bool_value(true)
+        );
+        assert_eq!(
+            between_expr(int(3), int(1), int(3), false).eval().unwrap(),
+            bool_value(true)
+        );
+        assert_eq!(
+            between_expr(int(4), int(1), int(3), false).eval().unwrap(),
+            bool_value(false)
+        );
+        assert_eq!(
+            between_expr(int(4), int(1), int(3), true).eval().unwrap(),
+            bool_value(true)
+        );
+
+        // FALSE on either side decides the result even with NULL
+        assert_eq!(
+            between_expr(int(0), int(1), null(), false).eval().unwrap(),
+            bool_value(false)
+        );
+        assert!(matches!(
+            between_expr(int(2), int(1), null(), false).eval().unwrap(),
+            SqlValue::Null
+        ));
+        assert!(matches!(
+            between_expr(null(), int(1), int(3), true).eval().unwrap(),
+            SqlValue::Null
+        ));
+    }
+
+    #[test]
+    fn test_equal_null() {
+        let eq = |left, right| {
+            comparison_expr(
+                |left, right| ComparisonFunction::EqualVariant { left, right },
+                left,
+                right,
+            )
+        };
+        assert_eq!(eq(int(1), int(1)).eval().unwrap(), bool_value(true));
+        assert_eq!(eq(int(1), int(2)).eval().unwrap(), bool_value(false));
+        assert!(matches!(eq(int(1), null()).eval().unwrap(), SqlValue::Null));
+        assert!(matches!(eq(null(), null()).eval().unwrap(), SqlValue::Null));
+    }
+
+    #[test]
+    fn test_and_null() {
+        let t = || bool_expr(true);
+        let f = || bool_expr(false);
+
+        assert_eq!(and_expr(t(), t()).eval().unwrap(), bool_value(true));
+        assert_eq!(and_expr(t(), f()).eval().unwrap(), bool_value(false));
+        assert_eq!(and_expr(f(), null()).eval().unwrap(), bool_value(false));
+        assert_eq!(and_expr(null(), f()).eval().unwrap(), bool_value(false));
+        assert!(matches!(
+            and_expr(t(), null()).eval().unwrap(),
+            SqlValue::Null
+        ));
+        assert!(matches!(
+            and_expr(null(), null()).eval().unwrap(),
+            SqlValue::Null
+        ));
+        assert!(and_expr(int(1), t()).eval().is_err());
+    }
+
+    #[test]
+    fn test_is_distinct_from() {
+        let distinct = |left, right| {
+            comparison_expr(
+                |left, right| ComparisonFunction::IsDistinctFromVariant { left, right },
+                left,
+                right,
+            )
+            .eval()
+            .unwrap()
+        };
+        let not_distinct = |left, right| {
+            comparison_expr(
+                |left, right| ComparisonFunction::IsNotDistinctFromVariant { left, right },
+                left,
+                right,
+            )
+            .eval()
+            .unwrap()
+        };
+
+        assert_eq!(distinct(int(1), int(1)), bool_value(false));
+        assert_eq!(distinct(int(1), int(2)), bool_value(true));
+        assert_eq!(distinct(int(1), null()), bool_value(true));
+        assert_eq!(distinct(null(), int(1)), bool_value(true));
+        assert_eq!(distinct(null(), null()), bool_value(false));
+
+        assert_eq!(not_distinct(int(1), int(1)), bool_value(true));
+        assert_eq!(not_distinct(int(1), null()), bool_value(false));
+        assert_eq!(not_distinct(null(), null()), bool_value(true));
+    }
+
+    #[test]
+    fn test_coalesce() {
+        let coalesce =
+            |values| ValueExprPh2::FunctionCall(FunctionCall::Coalesce { values }).eval();
+
+        assert_eq!(
+            coalesce(vec![null(), int(1), int(2)]).unwrap(),
+            SqlValue::NotNull(NnSqlValue::Integer(1))
+        );
+        assert_eq!(
+            coalesce(vec![int(2)]).unwrap(),
+            SqlValue::NotNull(NnSqlValue::Integer(2))
+        );
+        assert!(matches!(
+            coalesce(vec![null(), null()]).unwrap(),
+            SqlValue::Null
+        ));
+    }
+
+    #[test]
+    fn test_nullif() {
+        let nullif = |value, compared| {
+            ValueExprPh2::FunctionCall(FunctionCall::NullIf {
+                value: Box::new(value),
+                compared: Box::new(compared),
+            })
+            .eval()
+            .unwrap()
+        };
+
+        assert!(matches!(nullif(int(1), int(1)), SqlValue::Null));
+        assert_eq!(
+            nullif(int(1), int(2)),
+            SqlValue::NotNull(NnSqlValue::Integer(1))
+        );
+        assert_eq!(
+            nullif(int(1), null()),
+            SqlValue::NotNull(NnSqlValue::Integer(1))
+        );
+        assert!(matches!(nullif(null(), int(1)), SqlValue::Null));
+    }
+}
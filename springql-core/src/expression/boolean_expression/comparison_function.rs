@@ -15,4 +15,50 @@ where
         /// Right operand
         right: Box<E>,
     },
+
+    /// `LIKE` operation (`%` matches any sequence of characters and `_` matches any single character)
+    LikeVariant {
+        /// String to match
+        left: Box<E>,
+        /// Pattern
+        right: Box<E>,
+    },
+
+    /// `IS DISTINCT FROM` operation (NULL-safe `<>`: never evaluated into NULL)
+    IsDistinctFromVariant {
+        /// Left operand
+        left: Box<E>,
+        /// Right operand
+        right: Box<E>,
+    },
+
+    /// `IS NOT DISTINCT FROM` operation (NULL-safe `=`: never evaluated into NULL)
+    IsNotDistinctFromVariant {
+        /// Left operand
+        left: Box<E>,
+        /// Right operand
+        right: Box<E>,
+    },
+
+    /// `[NOT] IN (v1, v2, ...)` predicate
+    InVariant {
+        /// Value to search for
+        target: Box<E>,
+        /// Candidates
+        list: Vec<E>,
+        /// `NOT IN`
+        negated: bool,
+    },
+
+    /// `[NOT] BETWEEN low AND high` predicate (inclusive on both ends)
+    BetweenVariant {
+        /// Value to compare
+        target: Box<E>,
+        /// Lower bound
+        low: Box<E>,
+        /// Upper bound
+        high: Box<E>,
+        /// `NOT BETWEEN`
+        negated: bool,
+    },
 }
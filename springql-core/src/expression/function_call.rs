@@ -21,4 +21,23 @@ where
     /// FLOOR_TIME("2020-01-01 01:11:11.000000000", DURATION_SECS(10 * 60)) -> "2020-01-01 01:10:00.000000000"
     /// ```
     FloorTime { target: Box<E>, resolution: Box<E> },
+
+    /// ```text
+    /// REGEXP_MATCH("WBA12345", "^WB[AS]") -> TRUE
+    /// ```
+    ///
+    /// Matches if any part of target matches the pattern (use `^` and `$` to match the whole).
+    RegexpMatch { target: Box<E>, pattern: Box<E> },
+
+    /// ```text
+    /// COALESCE(NULL, NULL, 1, 2) -> 1
+    /// COALESCE(NULL) -> NULL
+    /// ```
+    Coalesce { values: Vec<E> },
+
+    /// ```text
+    /// NULLIF(1, 1) -> NULL
+    /// NULLIF(1, 2) -> 1
+    /// ```
+    NullIf { value: Box<E>, compared: Box<E> },
 }
@@ -21,4 +21,28 @@ pub enum BinaryOperator {
 
     /// AND
     And,
+
+    /// LIKE
+    Like,
+
+    /// IS DISTINCT FROM
+    IsDistinctFrom,
+
+    /// IS NOT DISTINCT FROM
+    IsNotDistinctFrom,
+}
+
+impl BinaryOperator {
+    /// Operators with higher precedence bind tighter. Operators with the same precedence are left-associative.
+    pub fn precedence(&self) -> u8 {
+        match self {
+            BinaryOperator::And => 1,
+            BinaryOperator::Equal
+            | BinaryOperator::Like
+            | BinaryOperator::IsDistinctFrom
+            | BinaryOperator::IsNotDistinctFrom => 2,
+            BinaryOperator::Add => 3,
+            BinaryOperator::Mul => 4,
+        }
+    }
 }
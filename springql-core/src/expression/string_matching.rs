@@ -0,0 +1,65 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{cell::RefCell, collections::HashMap};
+
+use anyhow::Context;
+use regex::Regex;
+
+use crate::api::error::{Result, SpringError};
+
+/// Compiled regular expressions are cached per thread. The cache is cleared when it gets full.
+const REGEXP_CACHE_CAPACITY: usize = 64;
+
+thread_local! {
+    static REGEXP_CACHE: RefCell<HashMap<String, Regex>> = RefCell::new(HashMap::new());
+}
+
+pub(super) use springql_expr::like;
+
+/// # Failure
+///
+/// - `SpringError::Sql` when:
+///   - `pattern` is not a valid regular expression.
+pub(super) fn regexp_match(s: &str, pattern: &str) -> Result<bool> {
+    REGEXP_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if !cache.contains_key(pattern) {
+            if cache.len() >= REGEXP_CACHE_CAPACITY {
+                cache.clear();
+            }
+            cache.insert(pattern.to_string(), compile_regexp(pattern)?);
+        }
+        Ok(cache[pattern].is_match(s))
+    })
+}
+
+/// # Failure
+///
+/// - `SpringError::Sql` when:
+///   - `pattern` is not a valid regular expression.
+pub fn validate_regexp(pattern: &str) -> Result<()> {
+    compile_regexp(pattern).map(|_| ())
+}
+
+fn compile_regexp(pattern: &str) -> Result<Regex> {
+    Regex::new(pattern)
+        .with_context(|| format!("invalid regular expression: `{}`", pattern))
+        .map_err(SpringError::Sql)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regexp_match() {
+        assert!(regexp_match("WBA12345", "^WB[AS]").unwrap());
+        assert!(regexp_match("code=E042", r"E\d{3}").unwrap());
+        assert!(!regexp_match("WBA12345", "^WBS").unwrap());
+
+        assert!(matches!(
+            regexp_match("WBA12345", "(").unwrap_err(),
+            SpringError::Sql(_)
+        ));
+    }
+}
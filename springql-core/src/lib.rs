@@ -7,9 +7,11 @@
 #[macro_use]
 extern crate derive_new;
 
+mod alloc_instrumentation;
 mod connection;
 mod expr_resolver;
 mod expression;
+mod management;
 mod mem_size;
 mod pipeline;
 mod sql_processor;
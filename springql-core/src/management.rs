@@ -0,0 +1,463 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Listener accepting commands for a running pipeline over a UNIX domain socket and/or TCP.
+//!
+//! Line-based protocol: a client sends a command per line and receives a JSON line per command.
+//!
+//! ```text
+//! AUTH <token>               (first line, only when `management.auth_token` is set)
+//! PING                       -> {"ok":true,"result":"PONG"}
+//! STATUS                     -> {"ok":true,"result":"<source readers and sink writers>"}
+//! CREATE SOURCE STREAM ...   -> {"ok":true,"result":null}
+//! INFER SCHEMA ...           -> {"ok":true,"result":"CREATE SOURCE STREAM ..."}
+//! (error)                    -> {"ok":false,"error":"..."}
+//! ```
+
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use anyhow::{anyhow, Context};
+use serde_json::json;
+
+use crate::{
+    api::{
+        error::{foreign_info::ForeignInfo, Result, SpringError},
+        SpringManagementConfig, SpringPermissionConfig,
+    },
+    connection::Connection,
+    sql_processor::{SqlProcessor, TypeChecking},
+    stream_engine::EngineMutex,
+};
+
+/// Interval to check the stop flag while no client connects.
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Stops accepting new clients on drop. Sessions already established live until clients close them.
+#[derive(Debug)]
+pub(crate) struct ManagementListener {
+    stop: Arc<AtomicBool>,
+    uds_path: Option<PathBuf>,
+}
+
+impl ManagementListener {
+    /// # Returns
+    ///
+    /// `Ok(None)` if neither `uds_path` nor `tcp_addr` is set.
+    ///
+    /// Commands from clients are restricted by `permission` as well as `SpringPipeline::command()`.
+    /// Pumps created by clients are type checked by `type_checking` unless they specify it.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to listen on `tcp_addr` or `uds_path`.
+    pub(crate) fn start(
+        config: &SpringManagementConfig,
+        permission: &SpringPermissionConfig,
+        type_checking: TypeChecking,
+        engine: EngineMutex,
+    ) -> Result<Option<Self>> {
+        if !config.is_enabled() {
+            return Ok(None);
+        }
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let session = Session {
+            engine,
+            permission: permission.clone(),
+            type_checking,
+            auth_token: config.auth_token.clone(),
+        };
+
+        if let Some(tcp_addr) = &config.tcp_addr {
+            let listener = Self::bind_tcp(tcp_addr)?;
+            let (stop, session) = (stop.clone(), session.clone());
+            let _ = thread::Builder::new()
+                .name("ManagementTcp".into())
+                .spawn(move || {
+                    Self::accept_loop(
+                        || listener.accept().map(|(stream, _)| stream),
+                        |stream: &TcpStream| stream.set_nonblocking(false),
+                        &stop,
+                        &session,
+                    )
+                });
+        }
+
+        let uds_path = match &config.uds_path {
+            Some(uds_path) => Some(Self::start_uds(
+                PathBuf::from(uds_path),
+                stop.clone(),
+                session,
+            )?),
+            None => None,
+        };
+
+        Ok(Some(Self { stop, uds_path }))
+    }
+
+    fn bind_tcp(tcp_addr: &str) -> Result<TcpListener> {
+        let sock_addr =
+            tcp_addr
+                .parse::<SocketAddr>()
+                .map_err(|e| SpringError::InvalidConfigValue {
+                    path: "management.tcp_addr".to_string(),
+                    value: tcp_addr.to_string(),
+                    source: anyhow::Error::from(e),
+                })?;
+        let foreign_io = |e: anyhow::Error| SpringError::ForeignIo {
+            source: e,
+            foreign_info: ForeignInfo::GenericTcp(sock_addr),
+        };
+
+        let listener = TcpListener::bind(sock_addr)
+            .context("failed to listen for management commands")
+            .map_err(foreign_io)?;
+        listener
+            .set_nonblocking(true)
+            .context("failed to set non-blocking")
+            .map_err(foreign_io)?;
+
+        log::info!(
+            "[ManagementListener] Accepting commands at {}",
+            listener.local_addr().unwrap_or(sock_addr)
+        );
+        Ok(listener)
+    }
+
+    #[cfg(unix)]
+    fn start_uds(path: PathBuf, stop: Arc<AtomicBool>, session: Session) -> Result<PathBuf> {
+        use std::{
+            fs,
+            os::unix::{
+                fs::PermissionsExt,
+                net::{UnixListener, UnixStream},
+            },
+        };
+
+        let foreign_io = |e: anyhow::Error| SpringError::ForeignIo {
+            source: e,
+            foreign_info: ForeignInfo::UnixSocket(path.clone()),
+        };
+
+        // a socket file left by a previous process prevents binding
+        if path.exists() {
+            fs::remove_file(&path)
+                .context("failed to remove stale socket file")
+                .map_err(foreign_io)?;
+        }
+        let listener = UnixListener::bind(&path)
+            .context("failed to listen for management commands")
+            .map_err(foreign_io)?;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600))
+            .context("failed to restrict permission of socket file")
+            .map_err(foreign_io)?;
+        listener
+            .set_nonblocking(true)
+            .context("failed to set non-blocking")
+            .map_err(foreign_io)?;
+
+        log::info!("[ManagementListener] Accepting commands at {:?}", path);
+
+        let _ = thread::Builder::new()
+            .name("ManagementUds".into())
+            .spawn(move || {
+                Self::accept_loop(
+                    || listener.accept().map(|(stream, _)| stream),
+                    |stream: &UnixStream| stream.set_nonblocking(false),
+                    &stop,
+                    &session,
+                )
+            });
+
+        Ok(path)
+    }
+
+    #[cfg(not(unix))]
+    fn start_uds(path: PathBuf, _stop: Arc<AtomicBool>, _session: Session) -> Result<PathBuf> {
+        Err(SpringError::ForeignIo {
+            source: anyhow!("UNIX domain sockets are not available on this platform"),
+            foreign_info: ForeignInfo::UnixSocket(path),
+        })
+    }
+
+    fn accept_loop<S, A, B>(accept: A, set_blocking: B, stop: &AtomicBool, session: &Session)
+    where
+        S: std::io::Read + Write + Send + 'static,
+        S: TryCloneStream,
+        A: Fn() -> std::io::Result<S>,
+        B: Fn(&S) -> std::io::Result<()>,
+    {
+        while !stop.load(Ordering::Relaxed) {
+            match accept() {
+                Ok(stream) => {
+                    if let Err(e) = set_blocking(&stream) {
+                        log::warn!("[ManagementListener] Failed to set blocking: {:?}", e);
+                        continue;
+                    }
+                    let session = session.clone();
+                    let _ = thread::Builder::new()
+                        .name("ManagementSession".into())
+                        .spawn(move || match stream.try_clone_stream() {
+                            Ok(writer) => session.serve(BufReader::new(stream), writer),
+                            Err(e) => log::warn!(
+                                "[ManagementListener] Failed to clone a connection: {:?}",
+                                e
+                            ),
+                        });
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    thread::sleep(ACCEPT_POLL_INTERVAL)
+                }
+                Err(e) => log::warn!("[ManagementListener] Failed to accept a client: {:?}", e),
+            }
+        }
+    }
+}
+
+/// State shared by the clients of a listener.
+#[derive(Clone, Debug)]
+struct Session {
+    engine: EngineMutex,
+    permission: SpringPermissionConfig,
+    type_checking: TypeChecking,
+    auth_token: Option<String>,
+}
+
+impl Session {
+    /// Serves a client until it closes the connection or fails authentication.
+    fn serve(&self, reader: impl BufRead, mut writer: impl Write) {
+        // a session has its own processor as `SqlProcessor` holds no state shared among clients
+        let sql_processor = SqlProcessor::new(self.type_checking);
+        let mut authenticated = self.auth_token.is_none();
+
+        for line in reader.lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => {
+                    log::warn!("[ManagementListener] Failed to read a command: {:?}", e);
+                    return;
+                }
+            };
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let response = if authenticated {
+                Self::respond(self.handle(line, &sql_processor))
+            } else {
+                match (line.strip_prefix("AUTH "), &self.auth_token) {
+                    (Some(token), Some(expected)) if token_matches(token.trim(), expected) => {
+                        authenticated = true;
+                        Self::respond(Ok(None))
+                    }
+                    _ => {
+                        let _ = writeln!(
+                            writer,
+                            "{}",
+                            Self::respond(Err(anyhow!("authentication failed")))
+                        );
+                        log::warn!("[ManagementListener] Closing a client failed to authenticate");
+                        return;
+                    }
+                }
+            };
+
+            if writeln!(writer, "{}", response)
+                .and_then(|_| writer.flush())
+                .is_err()
+            {
+                return;
+            }
+        }
+    }
+
+    fn handle(&self, line: &str, sql_processor: &SqlProcessor) -> anyhow::Result<Option<String>> {
+        match line.to_ascii_uppercase().as_str() {
+            "PING" => Ok(Some("PONG".to_string())),
+            "STATUS" => self.status().map(Some).map_err(anyhow::Error::from),
+            _ => {
+                log::info!("[ManagementListener] Executing: {}", line);
+                Connection::execute(
+                    self.engine.get()?,
+                    sql_processor,
+                    Some(&self.permission),
+                    line,
+                )
+                .map_err(anyhow::Error::from)
+            }
+        }
+    }
+
+    fn status(&self) -> Result<String> {
+        let engine = self.engine.get()?;
+        let pipeline = engine.current_pipeline();
+
+        let sources = pipeline.all_sources().into_iter().map(|source| {
+            format!(
+                "source reader {} ({:?}) -> {}",
+                source.name(),
+                source.source_reader_type(),
+                source.dest_source_stream()
+            )
+        });
+        let sinks = pipeline.all_sinks().into_iter().map(|sink| {
+            format!(
+                "sink writer {} ({:?}) <- {}",
+                sink.name(),
+                sink.sink_writer_type(),
+                sink.sink_upstream()
+            )
+        });
+        Ok(sources.chain(sinks).collect::<Vec<_>>().join("\n"))
+    }
+
+    fn respond(result: anyhow::Result<Option<String>>) -> String {
+        match result {
+            Ok(text) => json!({ "ok": true, "result": text }),
+            Err(e) => json!({ "ok": false, "error": format!("{:?}", e) }),
+        }
+        .to_string()
+    }
+}
+
+impl Drop for ManagementListener {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(path) = &self.uds_path {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// Compares an AUTH token in constant time for tokens of the same length, not to leak how many leading bytes match.
+fn token_matches(token: &str, expected: &str) -> bool {
+    let (token, expected) = (token.as_bytes(), expected.as_bytes());
+    token.len() == expected.len()
+        && token
+            .iter()
+            .zip(expected)
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Streams whose write half can be taken apart from the read half.
+trait TryCloneStream: Sized {
+    fn try_clone_stream(&self) -> std::io::Result<Self>;
+}
+
+impl TryCloneStream for TcpStream {
+    fn try_clone_stream(&self) -> std::io::Result<Self> {
+        self.try_clone()
+    }
+}
+
+#[cfg(unix)]
+impl TryCloneStream for std::os::unix::net::UnixStream {
+    fn try_clone_stream(&self) -> std::io::Result<Self> {
+        self.try_clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::SpringConfig;
+
+    fn fx_listener(role: &str) -> (ManagementListener, SocketAddr) {
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let tcp_addr = SocketAddr::from(([127, 0, 0, 1], port));
+        let config = SpringManagementConfig {
+            uds_path: None,
+            tcp_addr: Some(tcp_addr.to_string()),
+            auth_token: Some("secret".to_string()),
+        };
+        let engine = EngineMutex::new(&SpringConfig::default());
+        let permission = SpringPermissionConfig {
+            role: role.to_string(),
+            allowed_statements: None,
+        };
+        let listener =
+            ManagementListener::start(&config, &permission, TypeChecking::Lenient, engine)
+                .unwrap()
+                .unwrap();
+        (listener, tcp_addr)
+    }
+
+    fn request(stream: &mut BufReader<TcpStream>, line: &str) -> serde_json::Value {
+        writeln!(stream.get_mut(), "{}", line).unwrap();
+        let mut response = String::new();
+        stream.read_line(&mut response).unwrap();
+        serde_json::from_str(&response).unwrap()
+    }
+
+    #[test]
+    fn test_token_matches() {
+        assert!(token_matches("secret", "secret"));
+        assert!(!token_matches("secreT", "secret"));
+        assert!(!token_matches("secre", "secret"));
+        assert!(!token_matches("", "secret"));
+    }
+
+    #[test]
+    fn test_management_tcp() {
+        let (_listener, tcp_addr) = fx_listener("admin");
+        let mut client = BufReader::new(TcpStream::connect(tcp_addr).unwrap());
+
+        assert_eq!(request(&mut client, "AUTH secret")["ok"], true);
+        assert_eq!(request(&mut client, "PING")["result"], "PONG");
+
+        let response = request(
+            &mut client,
+            "CREATE SOURCE STREAM source_trade (ts TIMESTAMP NOT NULL ROWTIME, ticker TEXT NOT NULL);",
+        );
+        assert_eq!(response["ok"], true);
+        assert!(response["result"].is_null());
+
+        let response = request(&mut client, "CREATE PUMP broken AS SELECT;");
+        assert_eq!(response["ok"], false);
+    }
+
+    #[test]
+    fn test_management_tcp_auth_failure() {
+        let (_listener, tcp_addr) = fx_listener("admin");
+        let mut client = BufReader::new(TcpStream::connect(tcp_addr).unwrap());
+
+        let response = request(&mut client, "PING");
+        assert_eq!(response["ok"], false);
+
+        let mut rest = String::new();
+        assert_eq!(client.read_line(&mut rest).unwrap(), 0, "closed");
+    }
+
+    #[test]
+    fn test_management_tcp_permission_denied() {
+        let (_listener, tcp_addr) = fx_listener("operator");
+        let mut client = BufReader::new(TcpStream::connect(tcp_addr).unwrap());
+
+        assert_eq!(request(&mut client, "AUTH secret")["ok"], true);
+        let response = request(
+            &mut client,
+            "CREATE SOURCE STREAM source_trade (ts TIMESTAMP NOT NULL ROWTIME, ticker TEXT NOT NULL);",
+        );
+        assert_eq!(response["ok"], false);
+        assert!(response["error"]
+            .as_str()
+            .unwrap()
+            .contains("CREATE SOURCE STREAM"));
+    }
+}
@@ -1,22 +1,31 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use std::mem::size_of;
+
 /// This trait requires estimation on how much heap memory [bytes] used by impl queue.
 ///
 /// Rust does not provide standard way to calculate heap memory size (Box, Vec, for example).
 /// Since Memory State Machine uses total memory size of queues, we need to impl this trait by ourselves.
 pub trait MemSize {
+    /// Heap memory owned by `self`. Objects shared by `Arc` are not counted.
     fn mem_size(&self) -> usize;
-}
-
-const RAW_POINTER_SIZE: usize = 8;
 
-/// Note: assuming many objects share the same Arc so interior object size is negligible.
-#[inline]
-pub fn arc_overhead_size() -> usize {
-    RAW_POINTER_SIZE
+    /// Memory used by `self` stored in a queue or a window: size of `self` itself and its heap memory.
+    ///
+    /// Rows with many small columns consume more than the sum of their values, so this size (not the number of rows) is accounted.
+    fn stored_size(&self) -> usize
+    where
+        Self: Sized,
+    {
+        size_of::<Self>() + self.mem_size()
+    }
 }
 
-#[inline]
-pub fn chrono_naive_date_time_overhead_size() -> usize {
-    4 * 3
+/// Heap memory of a `Vec` with `capacity`, including heap memory owned by its `elements`.
+pub fn vec_mem_size<'a, T>(capacity: usize, elements: impl Iterator<Item = &'a T>) -> usize
+where
+    T: MemSize + 'a,
+{
+    let elements_heap_size: usize = elements.map(MemSize::mem_size).sum();
+    capacity * size_of::<T>() + elements_heap_size
 }
@@ -12,6 +12,8 @@ mod relation;
 mod sink_writer_model;
 mod source_reader_model;
 mod stream_model;
+mod system_stream;
+mod tags;
 
 #[cfg(test)]
 pub mod test_support;
@@ -21,15 +23,54 @@ pub use name::{
     AggrAlias, ColumnName, CorrelationAlias, PumpName, QueueName, SinkWriterName, SourceReaderName,
     StreamName, ValueAlias,
 };
+#[cfg(feature = "avro")]
+pub use option::AvroOptions;
+#[cfg(feature = "source-can")]
+pub use option::CANOptions;
+#[cfg(feature = "sink-gpio")]
+pub use option::GpioOptions;
+#[cfg(feature = "source-opc-ua")]
+pub use option::OpcUaOptions;
+#[cfg(feature = "protobuf")]
+pub use option::ProtobufOptions;
+#[cfg(feature = "sink-cloud-iot")]
+pub use option::{validate_aws_iot_topic, AwsIotCoreOptions, AzureIotHubAuth, AzureIotHubOptions};
 pub use option::{
-    CANOptions, Http1ClientOptions, HttpMethod, InMemoryQueueOptions, NetClientOptions,
-    NetProtocol, NetServerOptions, Options, OptionsBuilder,
+    ColumnTemplate, CommandOptions, CsvOptions, FileSinkFormat, FileSinkOptions, FileSourceOptions,
+    FileStartPosition, InMemoryQueueOptions, InfluxDbOptions, InfluxDbProtocol, JsonEnvelope,
+    JsonLayout, JsonPath, JsonShapeOptions, ModbusOptions, ModbusRegister, ModbusTable,
+    ModbusValueType, ModbusWordOrder, NetClientOptions, NetHttpOptions, NetProtocol,
+    NetServerOptions, OfflineSpoolOptions, Options, OptionsBuilder, PumpOptions, SinkFormatOptions,
+    SourceFormatOptions, SourceMetadataOptions, SpringqlLinkSinkOptions, SpringqlLinkSourceOptions,
+    UdpSourceOptions, UnixSocketOptions,
 };
-pub use pipeline_graph::{Edge, PipelineGraph};
+#[cfg(feature = "sink-http")]
+pub use option::{Http1ClientOptions, HttpMethod};
+#[cfg(feature = "source-kafka")]
+pub use option::{KafkaSourceOptions, KafkaStartOffset};
+#[cfg(feature = "mqtt")]
+pub use option::{MqttQos, MqttSinkOptions, MqttSourceOptions};
+#[cfg(feature = "nats-messaging")]
+pub use option::{NatsMode, NatsSinkOptions, NatsSourceOptions};
+#[cfg(feature = "sink-parquet")]
+pub use option::{ParquetCompression, ParquetOptions};
+#[cfg(feature = "redis-stream")]
+pub use option::{RedisConsumerGroup, RedisStreamSinkOptions, RedisStreamSourceOptions};
+#[cfg(feature = "source-ros2")]
+pub use option::{Ros2Field, Ros2FieldType, Ros2Options, Ros2Reliability};
+#[cfg(feature = "sink-s3")]
+pub use option::{S3ObjectFormat, S3Options};
+#[cfg(feature = "zeromq")]
+pub use option::{
+    ZeroMqEndpoint, ZeroMqSinkOptions, ZeroMqSinkSocketType, ZeroMqSocketOptions,
+    ZeroMqSourceOptions,
+};
+pub use pipeline_graph::{Edge, PipelineGraph, PipelineWarning};
 pub use pipeline_version::PipelineVersion;
 pub use pump_model::{
-    AggregateFunctionParameter, AggregateParameter, GroupByLabels, JoinParameter, JoinType,
-    PumpInputType, PumpModel, WindowOperationParameter, WindowParameter,
+    AggregateFunctionParameter, AggregateParameter, GroupByLabels, IntervalJoinBound,
+    JoinBuildSide, JoinKey, JoinParameter, JoinType, PumpInputType, PumpModel, WindowAlignment,
+    WindowMetadata, WindowOperationParameter, WindowParameter,
 };
 pub use relation::{
     ColumnConstraint, ColumnDataType, ColumnDefinition, F32LooseType, I64LooseType,
@@ -38,8 +79,13 @@ pub use relation::{
 pub use sink_writer_model::{SinkWriterModel, SinkWriterType};
 pub use source_reader_model::{SourceReaderModel, SourceReaderType};
 pub use stream_model::{StreamModel, StreamShape};
+pub use system_stream::{SystemStream, SYSTEM_STREAM_PREFIX};
+pub use tags::Tags;
 
-use std::{collections::HashSet, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use anyhow::anyhow;
 
@@ -50,6 +96,9 @@ pub struct Pipeline {
     version: PipelineVersion,
     object_names: HashSet<String>,
     graph: PipelineGraph,
+
+    /// Set by `ALTER STREAM s SET TRACE SAMPLE rate`
+    stream_trace_sample_rates: HashMap<StreamName, f32>,
 }
 
 impl Pipeline {
@@ -58,6 +107,7 @@ impl Pipeline {
             version,
             object_names: HashSet::default(),
             graph: PipelineGraph::default(),
+            stream_trace_sample_rates: HashMap::default(),
         }
     }
 
@@ -69,14 +119,22 @@ impl Pipeline {
         &self.graph
     }
 
+    /// System streams are available even before created.
+    ///
     /// # Failure
     ///
     /// - `SpringError::Sql` when:
     ///   - Stream is not registered in pipeline
     pub fn get_stream(&self, stream: &StreamName) -> Result<Arc<StreamModel>> {
-        self.graph.get_stream(stream)
+        self.graph.get_stream(stream).or_else(|e| {
+            SystemStream::from_name(stream)
+                .map(|system_stream| Arc::new(system_stream.stream_model()))
+                .ok_or(e)
+        })
     }
 
+    /// System streams the pump reads from are created (with their source readers) if not yet.
+    ///
     /// # Failure
     ///
     /// - `SpringError::Sql` when:
@@ -86,19 +144,147 @@ impl Pipeline {
     pub fn add_pump(&mut self, pump: PumpModel) -> Result<()> {
         self.update_version();
         self.register_name(pump.name().as_ref())?;
+
+        for upstream in pump.upstreams() {
+            if let Some(system_stream) = SystemStream::from_name(upstream) {
+                if self.graph.get_stream(upstream).is_err() {
+                    self.add_system_stream(system_stream)?;
+                }
+            }
+        }
+
         self.graph.add_pump(pump)
     }
 
+    /// Starts (`Some(sample_rate)`) or stops (`None`) logging sampled rows of a stream.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Stream is not registered in pipeline
+    pub fn set_stream_trace(&mut self, stream: StreamName, sample_rate: Option<f32>) -> Result<()> {
+        let _ = self.get_stream(&stream)?;
+        self.update_version();
+        match sample_rate {
+            Some(sample_rate) => {
+                let _ = self.stream_trace_sample_rates.insert(stream, sample_rate);
+            }
+            None => {
+                let _ = self.stream_trace_sample_rates.remove(&stream);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn stream_trace_sample_rates(&self) -> impl Iterator<Item = (&StreamName, f32)> {
+        self.stream_trace_sample_rates
+            .iter()
+            .map(|(stream, sample_rate)| (stream, *sample_rate))
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Pump is not registered in pipeline
+    pub fn get_pump(&self, pump: &PumpName) -> Result<Arc<PumpModel>> {
+        self.graph.get_pump(pump)
+    }
+
+    /// Removes a pump. Its name can be used again.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Pump is not registered in pipeline
+    pub fn remove_pump(&mut self, pump: &PumpName) -> Result<()> {
+        self.graph.remove_pump(pump)?;
+        self.update_version();
+        let _ = self.object_names.remove(pump.as_ref());
+        Ok(())
+    }
+
+    /// Stops (`true`) or restarts (`false`) a pump. Tasks of stopped pumps are not executed.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Pump is not registered in pipeline
+    pub fn set_pump_stopped(&mut self, pump: &PumpName, stopped: bool) -> Result<()> {
+        self.graph.set_pump_stopped(pump, stopped)?;
+        self.update_version();
+        Ok(())
+    }
+
+    /// Removes a stream without any pump, source reader, or sink writer. Its name can be used again.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Stream is not registered in pipeline
+    ///   - Stream is a system stream
+    ///   - Stream is read or written by a pump, a source reader, or a sink writer
+    pub fn remove_stream(&mut self, stream: &StreamName) -> Result<()> {
+        if SystemStream::from_name(stream).is_some() {
+            return Err(SpringError::Sql(anyhow!(
+                r#"system stream "{}" cannot be dropped"#,
+                stream
+            )));
+        }
+        self.graph.remove_stream(stream)?;
+        self.update_version();
+        let _ = self.object_names.remove(stream.as_ref());
+        let _ = self.stream_trace_sample_rates.remove(stream);
+        Ok(())
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Source reader is not registered in pipeline
+    ///   - Source reader is for a system stream
+    pub fn remove_source_reader(&mut self, source_reader: &SourceReaderName) -> Result<()> {
+        self.graph.remove_source_reader(source_reader)?;
+        self.update_version();
+        Ok(())
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Sink writer is not registered in pipeline
+    pub fn remove_sink_writer(&mut self, sink_writer: &SinkWriterName) -> Result<()> {
+        self.graph.remove_sink_writer(sink_writer)?;
+        self.update_version();
+        Ok(())
+    }
+
     /// # Failure
     ///
     /// - `SpringError::Sql` when:
     ///   - Name of stream is already used in the same pipeline
+    ///   - Name of stream starts with `SYSTEM_STREAM_PREFIX` or is in `spring` namespace
     pub fn add_stream(&mut self, stream: Arc<StreamModel>) -> Result<()> {
+        if stream.name().as_ref().starts_with(SYSTEM_STREAM_PREFIX)
+            || stream.name().namespace() == Some("spring")
+        {
+            return Err(SpringError::Sql(anyhow!(
+                r#"stream name "{}" is reserved for system streams"#,
+                stream.name()
+            )));
+        }
         self.update_version();
         self.register_name(stream.name().as_ref())?;
         self.graph.add_stream(stream)
     }
 
+    fn add_system_stream(&mut self, system_stream: SystemStream) -> Result<()> {
+        let stream = system_stream.stream_model();
+        self.register_name(stream.name().as_ref())?;
+        self.graph.add_stream(Arc::new(stream))?;
+        self.graph
+            .add_source_reader(system_stream.source_reader_model())
+    }
+
     /// # Failure
     ///
     /// TODO
@@ -114,6 +300,12 @@ impl Pipeline {
         self.graph.add_sink_writer(sink_writer)
     }
 
+    pub fn all_streams(&self) -> Vec<&Arc<StreamModel>> {
+        self.graph.all_streams()
+    }
+    pub fn all_pumps(&self) -> Vec<&Arc<PumpModel>> {
+        self.graph.all_pumps()
+    }
     pub fn all_sources(&self) -> Vec<&SourceReaderModel> {
         self.graph.all_sources()
     }
@@ -121,6 +313,11 @@ impl Pipeline {
         self.graph.all_sinks()
     }
 
+    /// Unused streams, pumps, and columns, for `SHOW WARNINGS`.
+    pub fn warnings(&self) -> Vec<PipelineWarning> {
+        self.graph.warnings()
+    }
+
     /// # Failure
     ///
     /// - `SpringError::Sql` when:
@@ -19,6 +19,14 @@ pub enum ColumnReference {
     PTime { stream_name: StreamName },
 }
 
+impl ColumnReference {
+    pub fn stream_name(&self) -> &StreamName {
+        match self {
+            Self::Column { stream_name, .. } | Self::PTime { stream_name } => stream_name,
+        }
+    }
+}
+
 impl MemSize for ColumnReference {
     fn mem_size(&self) -> usize {
         match self {
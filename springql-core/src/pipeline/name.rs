@@ -2,7 +2,7 @@
 
 use std::fmt::Display;
 
-use crate::mem_size::MemSize;
+use crate::{mem_size::MemSize, pipeline::SYSTEM_STREAM_PREFIX};
 
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, new)]
 pub struct StreamName(String);
@@ -137,6 +137,19 @@ impl StreamName {
     pub fn virtual_root() -> Self {
         Self::new("__st_virtual_root__".to_string())
     }
+
+    /// `tenant_a` for `tenant_a.s1`.
+    ///
+    /// # Returns
+    ///
+    /// `None` for streams without namespace and system streams.
+    pub fn namespace(&self) -> Option<&str> {
+        if self.0.starts_with(SYSTEM_STREAM_PREFIX) {
+            None
+        } else {
+            self.0.split_once('.').map(|(namespace, _)| namespace)
+        }
+    }
 }
 
 impl SinkWriterName {
@@ -144,6 +157,10 @@ impl SinkWriterName {
         Self::new("NET_CLIENT_SINK".to_string())
     }
 
+    pub fn net_mqtt_sink() -> Self {
+        Self::new("NET_MQTT_SINK".to_string())
+    }
+
     pub fn http11_sink() -> Self {
         Self::new("HTTP1_SINK".to_string())
     }
@@ -151,6 +168,58 @@ impl SinkWriterName {
     pub fn in_memory_queue_sink() -> Self {
         Self::new("IN_MEMORY_QUEUE_SERVER_SINK".to_string())
     }
+
+    pub fn command_sink() -> Self {
+        Self::new("COMMAND_SINK".to_string())
+    }
+
+    pub fn gpio_sink() -> Self {
+        Self::new("GPIO_SINK".to_string())
+    }
+
+    pub fn azure_iot_hub_sink() -> Self {
+        Self::new("AZURE_IOT_HUB_SINK".to_string())
+    }
+
+    pub fn aws_iot_core_sink() -> Self {
+        Self::new("AWS_IOT_CORE_SINK".to_string())
+    }
+
+    pub fn redis_stream_sink() -> Self {
+        Self::new("REDIS_STREAM_SINK".to_string())
+    }
+
+    pub fn nats_sink() -> Self {
+        Self::new("NATS_SINK".to_string())
+    }
+
+    pub fn zeromq_sink() -> Self {
+        Self::new("ZEROMQ_SINK".to_string())
+    }
+
+    pub fn influxdb_sink() -> Self {
+        Self::new("INFLUXDB_SINK".to_string())
+    }
+
+    pub fn parquet_sink() -> Self {
+        Self::new("PARQUET_SINK".to_string())
+    }
+
+    pub fn s3_sink() -> Self {
+        Self::new("S3_SINK".to_string())
+    }
+
+    pub fn springql_link_sink() -> Self {
+        Self::new("SPRINGQL_LINK_SINK".to_string())
+    }
+
+    pub fn file_sink() -> Self {
+        Self::new("FILE_SINK".to_string())
+    }
+
+    pub fn unix_socket_sink() -> Self {
+        Self::new("UNIX_SOCKET_SINK".to_string())
+    }
 }
 
 impl MemSize for StreamName {
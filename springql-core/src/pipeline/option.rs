@@ -1,20 +1,117 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+#[cfg(feature = "avro")]
+mod avro_options;
+#[cfg(feature = "source-can")]
 mod can_options;
+#[cfg(feature = "sink-cloud-iot")]
+mod cloud_iot_options;
+mod column_template;
+mod command_options;
+mod file_options;
+#[cfg(feature = "sink-gpio")]
+mod gpio_options;
+#[cfg(feature = "sink-http")]
 mod http1_client_options;
 mod in_memory_queue_options;
+mod influxdb_options;
+mod json_path;
+mod json_shape_options;
+#[cfg(feature = "source-kafka")]
+mod kafka_options;
+mod modbus_options;
+#[cfg(feature = "mqtt")]
+mod mqtt_options;
+#[cfg(feature = "nats-messaging")]
+mod nats_options;
 mod net_client_options;
+mod net_http_options;
 mod net_protocol;
 mod net_server_options;
+mod offline_spool_options;
+#[cfg(feature = "source-opc-ua")]
+mod opc_ua_options;
 mod options_builder;
+#[cfg(feature = "sink-parquet")]
+mod parquet_options;
+#[cfg(feature = "protobuf")]
+mod protobuf_options;
+mod pump_options;
+#[cfg(feature = "redis-stream")]
+mod redis_stream_options;
+#[cfg(feature = "source-ros2")]
+mod ros2_options;
+#[cfg(feature = "sink-s3")]
+mod s3_options;
+mod sink_format_options;
+mod source_format_options;
+mod source_metadata_options;
+mod springql_link_options;
+mod udp_options;
+mod unix_socket_options;
+#[cfg(feature = "zeromq")]
+mod zeromq_options;
 
+#[cfg(feature = "avro")]
+pub use avro_options::AvroOptions;
+#[cfg(feature = "source-can")]
 pub use can_options::CANOptions;
+#[cfg(feature = "sink-cloud-iot")]
+pub use cloud_iot_options::{
+    validate_aws_iot_topic, AwsIotCoreOptions, AzureIotHubAuth, AzureIotHubOptions,
+};
+pub use column_template::ColumnTemplate;
+pub use command_options::CommandOptions;
+pub use file_options::{FileSinkFormat, FileSinkOptions, FileSourceOptions, FileStartPosition};
+#[cfg(feature = "sink-gpio")]
+pub use gpio_options::GpioOptions;
+#[cfg(feature = "sink-http")]
 pub use http1_client_options::{Http1ClientOptions, HttpMethod};
 pub use in_memory_queue_options::InMemoryQueueOptions;
+pub use influxdb_options::{InfluxDbOptions, InfluxDbProtocol};
+pub use json_path::JsonPath;
+pub use json_shape_options::{JsonEnvelope, JsonLayout, JsonShapeOptions};
+#[cfg(feature = "source-kafka")]
+pub use kafka_options::{KafkaSourceOptions, KafkaStartOffset};
+pub use modbus_options::{
+    ModbusOptions, ModbusRegister, ModbusTable, ModbusValueType, ModbusWordOrder,
+};
+#[cfg(feature = "mqtt")]
+pub use mqtt_options::{MqttQos, MqttSinkOptions, MqttSourceOptions};
+#[cfg(feature = "nats-messaging")]
+pub use nats_options::{NatsMode, NatsSinkOptions, NatsSourceOptions};
 pub use net_client_options::NetClientOptions;
+pub use net_http_options::NetHttpOptions;
 pub use net_protocol::NetProtocol;
 pub use net_server_options::NetServerOptions;
+pub use offline_spool_options::OfflineSpoolOptions;
+#[cfg(feature = "source-opc-ua")]
+pub use opc_ua_options::OpcUaOptions;
 pub use options_builder::OptionsBuilder;
+#[cfg(feature = "sink-parquet")]
+pub use parquet_options::{ParquetCompression, ParquetOptions};
+#[cfg(feature = "protobuf")]
+pub use protobuf_options::ProtobufOptions;
+pub use pump_options::PumpOptions;
+#[cfg(feature = "redis-stream")]
+pub use redis_stream_options::{
+    RedisConsumerGroup, RedisStreamSinkOptions, RedisStreamSourceOptions,
+};
+#[cfg(feature = "source-ros2")]
+pub use ros2_options::{Ros2Field, Ros2FieldType, Ros2Options, Ros2Reliability};
+#[cfg(feature = "sink-s3")]
+pub use s3_options::{S3ObjectFormat, S3Options};
+pub use sink_format_options::SinkFormatOptions;
+pub use source_format_options::{CsvOptions, SourceFormatOptions};
+pub use source_metadata_options::SourceMetadataOptions;
+pub use springql_link_options::{SpringqlLinkSinkOptions, SpringqlLinkSourceOptions};
+pub use udp_options::UdpSourceOptions;
+pub use unix_socket_options::UnixSocketOptions;
+#[cfg(feature = "zeromq")]
+pub use zeromq_options::{
+    ZeroMqEndpoint, ZeroMqSinkOptions, ZeroMqSinkSocketType, ZeroMqSocketOptions,
+    ZeroMqSourceOptions,
+};
 
 use std::collections::HashMap;
 
@@ -46,6 +143,30 @@ impl Options {
             })
     }
 
+    /// # Returns
+    ///
+    /// `Ok(None)` when key is not found in this Options.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidOption` when:
+    ///   - value for the key is invalid.
+    pub fn get_optional<V, F>(&self, key: &str, value_parser: F) -> Result<Option<V>>
+    where
+        F: FnOnce(&String) -> std::result::Result<V, anyhow::Error>,
+    {
+        self.0
+            .get(key)
+            .map(|value| {
+                value_parser(value).map_err(|e| SpringError::InvalidOption {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                    source: e,
+                })
+            })
+            .transpose()
+    }
+
     pub fn as_key_values(&self) -> Vec<(&str, &str)> {
         self.0
             .iter()
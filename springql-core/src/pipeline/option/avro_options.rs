@@ -0,0 +1,118 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use anyhow::{anyhow, Context};
+use reqwest::Url;
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::option::Options,
+};
+
+/// Avro records, optionally in Confluent wire format (a magic byte and a schema ID before each datum).
+///
+/// ```text
+/// FORMAT 'AVRO',
+/// AVRO_SCHEMA_REGISTRY_URL 'http://localhost:8081',
+/// AVRO_SUBJECT 'trade-value',
+/// AVRO_SCHEMA '{"type": "record", "name": "trade", "fields": [...]}'
+/// ```
+///
+/// - Sources: datums are decoded by the schema of their ID if `AVRO_SCHEMA_REGISTRY_URL` is set, else by `AVRO_SCHEMA`.
+///   If both are set, `AVRO_SCHEMA` is the reader schema to resolve datums into.
+/// - Sinks: rows are encoded by `AVRO_SCHEMA`. If `AVRO_SCHEMA_REGISTRY_URL` is set, the schema is registered under `AVRO_SUBJECT`
+///   and its ID is prefixed to each datum.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct AvroOptions {
+    /// Schema in JSON.
+    pub schema: Option<String>,
+    pub schema_registry_url: Option<Url>,
+    pub subject: Option<String>,
+}
+
+impl AvroOptions {
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidOption` when:
+    ///   - `AVRO_SCHEMA` is not set.
+    ///   - `AVRO_SUBJECT` is not set while `AVRO_SCHEMA_REGISTRY_URL` is set.
+    pub fn validate_for_sink(&self) -> Result<()> {
+        if self.schema.is_none() {
+            return Err(SpringError::InvalidOption {
+                key: "AVRO_SCHEMA".to_string(),
+                value: "".to_string(),
+                source: anyhow!("AVRO_SCHEMA is required to write Avro"),
+            });
+        }
+        if self.schema_registry_url.is_some() && self.subject.is_none() {
+            return Err(SpringError::InvalidOption {
+                key: "AVRO_SUBJECT".to_string(),
+                value: "".to_string(),
+                source: anyhow!("AVRO_SUBJECT is required to register the schema"),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<&Options> for AvroOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        let avro_options = Self {
+            schema: options.get_optional("AVRO_SCHEMA", |schema_str| {
+                apache_avro::Schema::parse_str(schema_str).context("invalid Avro schema")?;
+                Ok(schema_str.to_string())
+            })?,
+            schema_registry_url: options.get_optional("AVRO_SCHEMA_REGISTRY_URL", |url_str| {
+                Url::parse(url_str).context("failed to parse URL")
+            })?,
+            subject: options
+                .get_optional("AVRO_SUBJECT", |subject_str| Ok(subject_str.to_string()))?,
+        };
+
+        if avro_options.schema.is_none() && avro_options.schema_registry_url.is_none() {
+            return Err(SpringError::InvalidOption {
+                key: "AVRO_SCHEMA".to_string(),
+                value: "".to_string(),
+                source: anyhow!("AVRO_SCHEMA or AVRO_SCHEMA_REGISTRY_URL is required"),
+            });
+        }
+        Ok(avro_options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::OptionsBuilder;
+
+    use super::*;
+
+    const SCHEMA: &str =
+        r#"{"type": "record", "name": "trade", "fields": [{"name": "amount", "type": "int"}]}"#;
+
+    #[test]
+    fn test_avro_options() {
+        let options = OptionsBuilder::default()
+            .add("AVRO_SCHEMA_REGISTRY_URL", "http://localhost:8081")
+            .build();
+        let avro_options = AvroOptions::try_from(&options).unwrap();
+        assert!(avro_options.schema.is_none());
+        assert!(avro_options.validate_for_sink().is_err());
+
+        let options = OptionsBuilder::default()
+            .add("AVRO_SCHEMA_REGISTRY_URL", "http://localhost:8081")
+            .add("AVRO_SUBJECT", "trade-value")
+            .add("AVRO_SCHEMA", SCHEMA)
+            .build();
+        let avro_options = AvroOptions::try_from(&options).unwrap();
+        assert!(avro_options.validate_for_sink().is_ok());
+
+        let options = OptionsBuilder::default().build();
+        assert!(AvroOptions::try_from(&options).is_err());
+
+        let options = OptionsBuilder::default()
+            .add("AVRO_SCHEMA", r#"{"type": "unknown"}"#)
+            .build();
+        assert!(AvroOptions::try_from(&options).is_err());
+    }
+}
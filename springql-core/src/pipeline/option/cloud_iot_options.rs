@@ -0,0 +1,194 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{path::PathBuf, str::FromStr, time::Duration};
+
+use anyhow::{anyhow, Context};
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::option::{ColumnTemplate, Options},
+};
+
+/// Authentication of a device to Azure IoT Hub.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum AzureIotHubAuth {
+    /// SAS tokens are generated from the device's shared access key and renewed before expiry.
+    SharedAccessKey {
+        /// Decoded from the base64 `SHARED_ACCESS_KEY` option.
+        key: Vec<u8>,
+        token_ttl: Duration,
+    },
+    /// X.509 client certificate (PEM files).
+    X509 {
+        cert_file: PathBuf,
+        key_file: PathBuf,
+    },
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct AzureIotHubOptions {
+    /// e.g. `my-hub.azure-devices.net`
+    pub host_name: String,
+    pub device_id: String,
+    pub auth: AzureIotHubAuth,
+    /// PEM file of CA certificates to verify the hub.
+    pub ca_file: PathBuf,
+    pub port: u16,
+    /// MQTT QoS (0 or 1).
+    pub qos: u8,
+}
+
+impl TryFrom<&Options> for AzureIotHubOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        let shared_access_key = options.get_optional("SHARED_ACCESS_KEY", |key_str| {
+            base64::decode(key_str).context("SHARED_ACCESS_KEY must be base64")
+        })?;
+        let cert_file = options.get_optional("CERT_FILE", |s| Ok(PathBuf::from(s)))?;
+
+        let auth = match (shared_access_key, cert_file) {
+            (Some(key), None) => AzureIotHubAuth::SharedAccessKey {
+                key,
+                token_ttl: options
+                    .get_optional("SAS_TTL_SECS", |ttl_str| {
+                        let ttl_secs: u64 = ttl_str.parse().context("invalid SAS_TTL_SECS")?;
+                        anyhow::ensure!(ttl_secs > 0, "SAS_TTL_SECS must be positive");
+                        Ok(Duration::from_secs(ttl_secs))
+                    })?
+                    .unwrap_or_else(|| Duration::from_secs(3600)),
+            },
+            (None, Some(cert_file)) => AzureIotHubAuth::X509 {
+                cert_file,
+                key_file: options.get("KEY_FILE", |s| Ok(PathBuf::from(s)))?,
+            },
+            _ => {
+                return Err(SpringError::InvalidOption {
+                    key: "SHARED_ACCESS_KEY".to_string(),
+                    value: "(not found)".to_string(),
+                    source: anyhow!("exactly one of SHARED_ACCESS_KEY or CERT_FILE must be set"),
+                })
+            }
+        };
+
+        Ok(Self {
+            host_name: options.get("HOST_NAME", |s| Ok(s.to_string()))?,
+            device_id: options.get("DEVICE_ID", |s| Ok(s.to_string()))?,
+            auth,
+            ca_file: options.get("CA_FILE", |s| Ok(PathBuf::from(s)))?,
+            port: parse_port(options)?,
+            qos: parse_qos(options)?,
+        })
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct AwsIotCoreOptions {
+    /// Device data endpoint (e.g. `xxxxxxxx-ats.iot.ap-northeast-1.amazonaws.com`).
+    pub endpoint: String,
+    /// MQTT client ID (usually the thing name).
+    pub client_id: String,
+    /// May contain `${column}` placeholders (e.g. `dt/vehicles/${vehicle_id}/telemetry`).
+    pub topic: ColumnTemplate,
+    /// X.509 client certificate (PEM file).
+    pub cert_file: PathBuf,
+    pub key_file: PathBuf,
+    /// PEM file of CA certificates to verify the endpoint (e.g. Amazon Root CA 1).
+    pub ca_file: PathBuf,
+    /// 8883 by default. 443 connects with ALPN `x-amzn-mqtt-ca`.
+    pub port: u16,
+    /// MQTT QoS (0 or 1).
+    pub qos: u8,
+}
+
+impl TryFrom<&Options> for AwsIotCoreOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        Ok(Self {
+            endpoint: options.get("ENDPOINT", |s| Ok(s.to_string()))?,
+            client_id: options.get("CLIENT_ID", |s| Ok(s.to_string()))?,
+            topic: options.get("TOPIC", |topic_str| {
+                let template = ColumnTemplate::from_str(topic_str)?;
+                // placeholders are filled at write time
+                let sample_topic = template.render(|_| Ok("_".to_string()))?;
+                validate_aws_iot_topic(&sample_topic)?;
+                Ok(template)
+            })?,
+            cert_file: options.get("CERT_FILE", |s| Ok(PathBuf::from(s)))?,
+            key_file: options.get("KEY_FILE", |s| Ok(PathBuf::from(s)))?,
+            ca_file: options.get("CA_FILE", |s| Ok(PathBuf::from(s)))?,
+            port: parse_port(options)?,
+            qos: parse_qos(options)?,
+        })
+    }
+}
+
+/// Checks AWS IoT Core restrictions on topic names to publish.
+pub fn validate_aws_iot_topic(topic: &str) -> anyhow::Result<()> {
+    anyhow::ensure!(!topic.is_empty(), "topic must not be empty");
+    anyhow::ensure!(
+        topic.len() <= 256,
+        "topic must be at most 256 bytes: {}",
+        topic
+    );
+    anyhow::ensure!(
+        topic.matches('/').count() <= 7,
+        "topic must have at most 7 forward slashes: {}",
+        topic
+    );
+    anyhow::ensure!(
+        !topic.starts_with('$'),
+        "topics starting with `$` are reserved: {}",
+        topic
+    );
+    anyhow::ensure!(
+        !topic.contains(|c| c == '+' || c == '#'),
+        "wildcards are not allowed in topics to publish: {}",
+        topic
+    );
+    Ok(())
+}
+
+fn parse_port(options: &Options) -> Result<u16> {
+    Ok(options
+        .get_optional("PORT", |port_str| port_str.parse().context("invalid port"))?
+        .unwrap_or(8883))
+}
+
+fn parse_qos(options: &Options) -> Result<u8> {
+    Ok(options
+        .get_optional("QOS", |qos_str| {
+            let qos: u8 = qos_str.parse().context("invalid QOS")?;
+            anyhow::ensure!(qos <= 1, "QOS must be 0 or 1");
+            Ok(qos)
+        })?
+        .unwrap_or(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::OptionsBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_validate_aws_iot_topic() {
+        assert!(validate_aws_iot_topic("dt/vehicles/v1/telemetry").is_ok());
+
+        assert!(validate_aws_iot_topic("").is_err());
+        assert!(validate_aws_iot_topic("$aws/things/v1/shadow/update").is_err());
+        assert!(validate_aws_iot_topic("a/b/c/d/e/f/g/h/i").is_err());
+        assert!(validate_aws_iot_topic("dt/+/telemetry").is_err());
+    }
+
+    #[test]
+    fn test_azure_iot_hub_auth_required() {
+        let options = OptionsBuilder::default()
+            .add("HOST_NAME", "my-hub.azure-devices.net")
+            .add("DEVICE_ID", "device-1")
+            .add("CA_FILE", "/etc/ssl/certs/ca-certificates.crt")
+            .build();
+        assert!(AzureIotHubOptions::try_from(&options).is_err());
+    }
+}
@@ -0,0 +1,121 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::str::FromStr;
+
+use anyhow::anyhow;
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::ColumnName,
+};
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+enum TemplateSegment {
+    Literal(String),
+    Placeholder(ColumnName),
+}
+
+/// Option value with `${column}` placeholders, filled from a row's columns at write time.
+///
+/// ```text
+/// alerts/${vehicle_id}
+/// http://127.0.0.1:8080/vehicles/${vehicle_id}/speed
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ColumnTemplate(Vec<TemplateSegment>);
+
+impl ColumnTemplate {
+    pub fn has_placeholder(&self) -> bool {
+        self.0
+            .iter()
+            .any(|segment| matches!(segment, TemplateSegment::Placeholder(_)))
+    }
+
+    /// # Failure
+    ///
+    /// Raises Err from `column_value` as-is.
+    pub fn render<F>(&self, column_value: F) -> Result<String>
+    where
+        F: Fn(&ColumnName) -> Result<String>,
+    {
+        self.0
+            .iter()
+            .map(|segment| match segment {
+                TemplateSegment::Literal(s) => Ok(s.clone()),
+                TemplateSegment::Placeholder(column) => column_value(column),
+            })
+            .collect()
+    }
+}
+
+impl FromStr for ColumnTemplate {
+    type Err = SpringError;
+
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidFormat` when:
+    ///   - `${` is not closed.
+    ///   - Placeholder is empty.
+    fn from_str(s: &str) -> Result<Self> {
+        let mut segments = vec![];
+
+        let mut rest = s;
+        while let Some(start) = rest.find("${") {
+            if start > 0 {
+                segments.push(TemplateSegment::Literal(rest[..start].to_string()));
+            }
+
+            let after_start = &rest[start + 2..];
+            let end = after_start
+                .find('}')
+                .ok_or_else(|| SpringError::InvalidFormat {
+                    s: s.to_string(),
+                    source: anyhow!("`${{` is not closed"),
+                })?;
+            let column = after_start[..end].trim();
+            if column.is_empty() {
+                return Err(SpringError::InvalidFormat {
+                    s: s.to_string(),
+                    source: anyhow!("empty placeholder"),
+                });
+            }
+            segments.push(TemplateSegment::Placeholder(ColumnName::new(
+                column.to_string(),
+            )));
+
+            rest = &after_start[end + 1..];
+        }
+        if !rest.is_empty() {
+            segments.push(TemplateSegment::Literal(rest.to_string()));
+        }
+
+        Ok(Self(segments))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let template = ColumnTemplate::from_str("alerts/${vehicle_id}/${ kind }").unwrap();
+        assert!(template.has_placeholder());
+        assert_eq!(
+            template
+                .render(|column| Ok(format!("<{}>", column)))
+                .unwrap(),
+            "alerts/<vehicle_id>/<kind>"
+        );
+
+        let template = ColumnTemplate::from_str("alerts").unwrap();
+        assert!(!template.has_placeholder());
+        assert_eq!(template.render(|_| unreachable!()).unwrap(), "alerts");
+    }
+
+    #[test]
+    fn test_invalid_template() {
+        assert!(ColumnTemplate::from_str("alerts/${vehicle_id").is_err());
+        assert!(ColumnTemplate::from_str("alerts/${}").is_err());
+    }
+}
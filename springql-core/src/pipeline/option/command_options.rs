@@ -0,0 +1,53 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::path::PathBuf;
+
+use anyhow::{ensure, Context};
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::option::Options,
+};
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct CommandOptions {
+    /// Executable to run.
+    pub command: PathBuf,
+    /// Arguments separated by whitespaces.
+    pub args: Vec<String>,
+    /// Number of rows (JSON lines) written to the stdin of a command run. 1 by default.
+    pub batch_size: usize,
+    /// Maximum number of command runs at the same time. 1 by default.
+    pub max_concurrency: usize,
+}
+
+impl TryFrom<&Options> for CommandOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        Ok(Self {
+            command: options.get("COMMAND", |command_str| Ok(PathBuf::from(command_str)))?,
+            args: options
+                .get_optional("ARGS", |args_str| {
+                    Ok(args_str.split_whitespace().map(String::from).collect())
+                })?
+                .unwrap_or_default(),
+            batch_size: options
+                .get_optional("BATCH_SIZE", |batch_size_str| {
+                    let batch_size: usize = batch_size_str.parse().context("invalid batch size")?;
+                    ensure!(batch_size > 0, "BATCH_SIZE must be positive");
+                    Ok(batch_size)
+                })?
+                .unwrap_or(1),
+            max_concurrency: options
+                .get_optional("MAX_CONCURRENCY", |max_concurrency_str| {
+                    let max_concurrency: usize = max_concurrency_str
+                        .parse()
+                        .context("invalid max concurrency")?;
+                    ensure!(max_concurrency > 0, "MAX_CONCURRENCY must be positive");
+                    Ok(max_concurrency)
+                })?
+                .unwrap_or(1),
+        })
+    }
+}
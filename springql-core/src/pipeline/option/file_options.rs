@@ -0,0 +1,233 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{path::PathBuf, str::FromStr, time::Duration};
+
+use anyhow::{anyhow, Context};
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{option::Options, JsonPath},
+};
+
+/// Where to start reading a file.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum FileStartPosition {
+    /// Reads existing lines first.
+    Beginning,
+    /// Reads only lines appended after the source reader starts.
+    End,
+}
+
+impl FromStr for FileStartPosition {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "BEGINNING" => Ok(Self::Beginning),
+            "END" => Ok(Self::End),
+            _ => Err(anyhow!("START_POSITION must be BEGINNING or END")),
+        }
+    }
+}
+
+/// Tails a file whose lines are rows.
+///
+/// ```text
+/// PATH '/var/log/sensor/temperature.log',
+/// POLL_INTERVAL_MSEC '100',
+/// START_POSITION 'BEGINNING'
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct FileSourceOptions {
+    pub path: PathBuf,
+    /// How often to check appended lines, rotation and truncation at the end of the file. 100 msec by default.
+    pub poll_interval: Duration,
+    /// END by default. Files created or rotated after the source reader starts are always read from the beginning.
+    pub start_position: FileStartPosition,
+    /// Selects a JSON array (or a JSON object) of rows in each line.
+    pub json_records_path: Option<JsonPath>,
+}
+
+impl TryFrom<&Options> for FileSourceOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        Ok(Self {
+            path: options.get("PATH", |path_str| Ok(PathBuf::from(path_str)))?,
+            poll_interval: options
+                .get_optional("POLL_INTERVAL_MSEC", |msec_str| {
+                    let msec: u64 = msec_str.parse().context("invalid POLL_INTERVAL_MSEC")?;
+                    anyhow::ensure!(msec > 0, "POLL_INTERVAL_MSEC must be positive");
+                    Ok(Duration::from_millis(msec))
+                })?
+                .unwrap_or_else(|| Duration::from_millis(100)),
+            start_position: options
+                .get_optional("START_POSITION", |s| s.parse())?
+                .unwrap_or(FileStartPosition::End),
+            json_records_path: options.get_optional("JSON_RECORDS_PATH", |path_str| {
+                path_str.parse::<JsonPath>().context("invalid JSONPath")
+            })?,
+        })
+    }
+}
+
+/// Format of lines written by the FILE sink writer.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum FileSinkFormat {
+    /// Default. A JSON object per line, shaped by `JsonShapeOptions`.
+    Json,
+    /// A CSV record per line.
+    Csv {
+        /// Whether to write a header with column names at the beginning of each file. true by default.
+        header: bool,
+        /// ',' by default.
+        delimiter: char,
+    },
+}
+
+/// Appends rows to a file, which is rotated to `<PATH>.<n>` by size or time.
+///
+/// ```text
+/// PATH '/var/log/springql/alerts.jsonl',
+/// FORMAT 'CSV',
+/// CSV_HEADER 'true',
+/// CSV_DELIMITER ',',
+/// MAX_FILE_SIZE_BYTES '10485760',
+/// ROLL_INTERVAL_SEC '3600'
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct FileSinkOptions {
+    /// Its directory must exist.
+    pub path: PathBuf,
+    pub format: FileSinkFormat,
+    /// Rotates the file when it reaches this size. Not rotated by size by default.
+    pub max_file_size_bytes: Option<u64>,
+    /// Rotates the file when this time has passed since it was opened. Not rotated by time by default.
+    pub roll_interval: Option<Duration>,
+}
+
+impl TryFrom<&Options> for FileSinkOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        let format = match options.get_optional("FORMAT", |format_str| match format_str {
+            "JSON" | "CSV" => Ok(format_str.to_string()),
+            _ => Err(anyhow!("FORMAT must be JSON or CSV")),
+        })? {
+            Some(format) if format == "CSV" => FileSinkFormat::Csv {
+                header: options
+                    .get_optional("CSV_HEADER", |header_str| {
+                        header_str
+                            .parse::<bool>()
+                            .map_err(|_| anyhow!("CSV_HEADER must be true or false"))
+                    })?
+                    .unwrap_or(true),
+                delimiter: options
+                    .get_optional("CSV_DELIMITER", |delimiter_str| {
+                        let mut chars = delimiter_str.chars();
+                        match (chars.next(), chars.next()) {
+                            (Some(c), None) if !matches!(c, '"' | '\r' | '\n') => Ok(c),
+                            _ => Err(anyhow!(
+                                "CSV_DELIMITER must be a character other than quote and newlines"
+                            )),
+                        }
+                    })?
+                    .unwrap_or(','),
+            },
+            _ => FileSinkFormat::Json,
+        };
+
+        Ok(Self {
+            path: options.get("PATH", |path_str| Ok(PathBuf::from(path_str)))?,
+            format,
+            max_file_size_bytes: options.get_optional(
+                "MAX_FILE_SIZE_BYTES",
+                |max_file_size_str| {
+                    let max_file_size: u64 =
+                        max_file_size_str.parse().context("invalid max file size")?;
+                    anyhow::ensure!(max_file_size > 0, "MAX_FILE_SIZE_BYTES must be positive");
+                    Ok(max_file_size)
+                },
+            )?,
+            roll_interval: options.get_optional("ROLL_INTERVAL_SEC", |roll_interval_str| {
+                let secs: u64 = roll_interval_str.parse().context("invalid roll interval")?;
+                anyhow::ensure!(secs > 0, "ROLL_INTERVAL_SEC must be positive");
+                Ok(Duration::from_secs(secs))
+            })?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::OptionsBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_file_source_options() {
+        let options = OptionsBuilder::default()
+            .add("PATH", "/var/log/sensor.log")
+            .build();
+        let options = FileSourceOptions::try_from(&options).unwrap();
+        assert_eq!(options.path, PathBuf::from("/var/log/sensor.log"));
+        assert_eq!(options.poll_interval, Duration::from_millis(100));
+        assert_eq!(options.start_position, FileStartPosition::End);
+
+        let options = OptionsBuilder::default()
+            .add("PATH", "/var/log/sensor.log")
+            .add("POLL_INTERVAL_MSEC", "10")
+            .add("START_POSITION", "BEGINNING")
+            .build();
+        let options = FileSourceOptions::try_from(&options).unwrap();
+        assert_eq!(options.poll_interval, Duration::from_millis(10));
+        assert_eq!(options.start_position, FileStartPosition::Beginning);
+
+        let options = OptionsBuilder::default()
+            .add("PATH", "/var/log/sensor.log")
+            .add("START_POSITION", "LATEST")
+            .build();
+        assert!(matches!(
+            FileSourceOptions::try_from(&options),
+            Err(SpringError::InvalidOption { .. })
+        ));
+    }
+
+    #[test]
+    fn test_file_sink_options() {
+        let options = OptionsBuilder::default()
+            .add("PATH", "/var/log/alerts.jsonl")
+            .build();
+        let options = FileSinkOptions::try_from(&options).unwrap();
+        assert_eq!(options.format, FileSinkFormat::Json);
+        assert_eq!(options.max_file_size_bytes, None);
+        assert_eq!(options.roll_interval, None);
+
+        let options = OptionsBuilder::default()
+            .add("PATH", "/var/log/alerts.csv")
+            .add("FORMAT", "CSV")
+            .add("CSV_DELIMITER", ";")
+            .add("MAX_FILE_SIZE_BYTES", "1024")
+            .add("ROLL_INTERVAL_SEC", "60")
+            .build();
+        let options = FileSinkOptions::try_from(&options).unwrap();
+        assert_eq!(
+            options.format,
+            FileSinkFormat::Csv {
+                header: true,
+                delimiter: ';'
+            }
+        );
+        assert_eq!(options.max_file_size_bytes, Some(1024));
+        assert_eq!(options.roll_interval, Some(Duration::from_secs(60)));
+
+        let options = OptionsBuilder::default()
+            .add("PATH", "/var/log/alerts.avro")
+            .add("FORMAT", "AVRO")
+            .build();
+        assert!(matches!(
+            FileSinkOptions::try_from(&options),
+            Err(SpringError::InvalidOption { .. })
+        ));
+    }
+}
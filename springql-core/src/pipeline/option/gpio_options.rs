@@ -0,0 +1,46 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{option::Options, ColumnName},
+};
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct GpioOptions {
+    /// GPIO number in the Linux sysfs interface (e.g. `17` for `/sys/class/gpio/gpio17`).
+    pub pin: u32,
+    /// BOOLEAN column to drive the pin. TRUE sets the pin high (active).
+    pub value_column: ColumnName,
+    /// Inverts the pin level (TRUE sets the pin low).
+    pub active_low: bool,
+    /// Root of the sysfs GPIO interface. `/sys/class/gpio` by default.
+    pub sysfs_root: PathBuf,
+}
+
+impl TryFrom<&Options> for GpioOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        Ok(Self {
+            pin: options.get("PIN", |pin_str| pin_str.parse().context("invalid GPIO pin"))?,
+            value_column: options.get("VALUE_COLUMN", |column_str| {
+                Ok(ColumnName::new(column_str.to_string()))
+            })?,
+            active_low: options
+                .get_optional("ACTIVE_LOW", |active_low_str| {
+                    active_low_str
+                        .to_lowercase()
+                        .parse()
+                        .context("ACTIVE_LOW must be true or false")
+                })?
+                .unwrap_or(false),
+            sysfs_root: options
+                .get_optional("SYSFS_ROOT", |root_str| Ok(PathBuf::from(root_str)))?
+                .unwrap_or_else(|| PathBuf::from("/sys/class/gpio")),
+        })
+    }
+}
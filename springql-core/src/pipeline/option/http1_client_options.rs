@@ -7,7 +7,10 @@ use reqwest::Url;
 
 use crate::{
     api::error::{Result, SpringError},
-    pipeline::{option::Options, ColumnName},
+    pipeline::{
+        option::{ColumnTemplate, Options},
+        ColumnName,
+    },
 };
 
 #[derive(Clone, Eq, PartialEq, Debug)]
@@ -34,9 +37,14 @@ pub struct Http1ClientOptions {
     pub remote_host: IpAddr,
     pub remote_port: u16,
     pub method: HttpMethod,
-    pub url: Url,
+    /// May contain `${column}` placeholders (e.g. `http://127.0.0.1:8080/vehicles/${vehicle_id}`).
+    pub url: ColumnTemplate,
     pub headers: HashMap<String, String>,
-    pub blob_body_column: ColumnName,
+    /// Sends the BLOB column as-is for each row. Rows are sent as JSON if not set.
+    pub blob_body_column: Option<ColumnName>,
+    /// Sends a JSON array of this number of rows in a request (rows for different URLs are not batched together).
+    /// A JSON object is sent per row if not set.
+    pub batch_size: Option<usize>,
 }
 
 impl TryFrom<&Options> for Http1ClientOptions {
@@ -55,13 +63,22 @@ impl TryFrom<&Options> for Http1ClientOptions {
             method: options.get("METHOD", |method_str| {
                 method_str.parse().context("invalid HTTP method")
             })?,
-            url: options.get("URL", |path_str| {
-                Url::parse(path_str.as_str()).context("failed to parse URL")
+            url: options.get("URL", |url_str| {
+                let template = ColumnTemplate::from_str(url_str)?;
+                // placeholders are filled at write time
+                let sample_url = template.render(|_| Ok("_".to_string()))?;
+                Url::parse(&sample_url).context("failed to parse URL")?;
+                Ok(template)
             })?,
             headers,
-            blob_body_column: options.get("BLOB_BODY_COLUMN", |column_str| {
+            blob_body_column: options.get_optional("BLOB_BODY_COLUMN", |column_str| {
                 Ok(ColumnName::new(column_str.to_string()))
             })?,
+            batch_size: options.get_optional("BATCH_SIZE", |batch_size_str| {
+                let batch_size: usize = batch_size_str.parse().context("invalid batch size")?;
+                anyhow::ensure!(batch_size > 0, "BATCH_SIZE must be positive");
+                Ok(batch_size)
+            })?,
         })
     }
 }
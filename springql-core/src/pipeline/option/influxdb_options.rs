@@ -0,0 +1,180 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{net::IpAddr, str::FromStr};
+
+use anyhow::{anyhow, Context};
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{
+        option::{ColumnTemplate, Options},
+        ColumnName,
+    },
+};
+
+/// Transport of InfluxDB line protocol.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum InfluxDbProtocol {
+    /// Write API of InfluxDB 2.x (`/api/v2/write`).
+    HttpV2 {
+        org: String,
+        bucket: String,
+        /// Sent as `Authorization: Token <token>` if set.
+        token: Option<String>,
+    },
+    /// Write API of InfluxDB 1.x (`/write`).
+    HttpV1 { database: String },
+    /// UDP service (a datagram per row).
+    Udp,
+}
+
+/// Writes rows as InfluxDB line protocol.
+///
+/// ```text
+/// PROTOCOL 'HTTP',
+/// REMOTE_HOST '127.0.0.1',
+/// REMOTE_PORT '8086',
+/// ORG 'factory',
+/// BUCKET 'edge',
+/// TOKEN '...',
+/// MEASUREMENT 'temperature_${site}',
+/// TAG_COLUMNS 'site,device',
+/// FIELD_COLUMNS 'avg_temperature,max_temperature',
+/// TIMESTAMP_COLUMN 'ts',
+/// BATCH_SIZE '100'
+/// ```
+///
+/// `DATABASE 'edge'` instead of `ORG` and `BUCKET` for InfluxDB 1.x.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct InfluxDbOptions {
+    /// `PROTOCOL` is `HTTP` (default) or `UDP`.
+    pub protocol: InfluxDbProtocol,
+    pub remote_host: IpAddr,
+    /// 8086 by default for HTTP, 8089 for UDP.
+    pub remote_port: u16,
+    /// May contain `${column}` placeholders.
+    pub measurement: ColumnTemplate,
+    pub tag_columns: Vec<ColumnName>,
+    /// All columns other than tags and the timestamp if not set.
+    pub field_columns: Option<Vec<ColumnName>>,
+    /// Timestamp of points (in nanoseconds). Assigned by the server if not set.
+    pub timestamp_column: Option<ColumnName>,
+    /// Lines per HTTP request. 1 by default.
+    pub batch_size: usize,
+}
+
+impl TryFrom<&Options> for InfluxDbOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        let protocol_str = options
+            .get_optional("PROTOCOL", |s| Ok(s.to_string()))?
+            .unwrap_or_else(|| "HTTP".to_string());
+        let string = |key: &str| options.get_optional(key, |s| Ok(s.to_string()));
+        let protocol = match protocol_str.as_str() {
+            "UDP" => InfluxDbProtocol::Udp,
+            "HTTP" => match string("DATABASE")? {
+                Some(database) => InfluxDbProtocol::HttpV1 { database },
+                None => InfluxDbProtocol::HttpV2 {
+                    org: options.get("ORG", |s| Ok(s.to_string()))?,
+                    bucket: options.get("BUCKET", |s| Ok(s.to_string()))?,
+                    token: string("TOKEN")?,
+                },
+            },
+            _ => {
+                return Err(SpringError::InvalidOption {
+                    key: "PROTOCOL".to_string(),
+                    value: protocol_str,
+                    source: anyhow!("PROTOCOL must be HTTP or UDP"),
+                })
+            }
+        };
+        let default_port = match protocol {
+            InfluxDbProtocol::Udp => 8089,
+            _ => 8086,
+        };
+
+        let columns = |columns_str: &String| {
+            Ok(columns_str
+                .split(',')
+                .map(|column| ColumnName::new(column.trim().to_string()))
+                .collect::<Vec<_>>())
+        };
+
+        Ok(Self {
+            protocol,
+            remote_host: options.get("REMOTE_HOST", |remote_host_str| {
+                remote_host_str.parse().context("invalid remote host")
+            })?,
+            remote_port: options
+                .get_optional("REMOTE_PORT", |remote_port_str| {
+                    remote_port_str.parse().context("invalid remote port")
+                })?
+                .unwrap_or(default_port),
+            measurement: options.get("MEASUREMENT", |measurement_str| {
+                Ok(ColumnTemplate::from_str(measurement_str)?)
+            })?,
+            tag_columns: options
+                .get_optional("TAG_COLUMNS", columns)?
+                .unwrap_or_default(),
+            field_columns: options.get_optional("FIELD_COLUMNS", columns)?,
+            timestamp_column: options.get_optional("TIMESTAMP_COLUMN", |column_str| {
+                Ok(ColumnName::new(column_str.to_string()))
+            })?,
+            batch_size: options
+                .get_optional("BATCH_SIZE", |batch_size_str| {
+                    let batch_size: usize = batch_size_str.parse().context("invalid batch size")?;
+                    anyhow::ensure!(batch_size > 0, "BATCH_SIZE must be positive");
+                    Ok(batch_size)
+                })?
+                .unwrap_or(1),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::OptionsBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_influxdb_options() {
+        let options = OptionsBuilder::default()
+            .add("REMOTE_HOST", "127.0.0.1")
+            .add("DATABASE", "edge")
+            .add("MEASUREMENT", "temperature")
+            .add("TAG_COLUMNS", "site, device")
+            .build();
+        let options = InfluxDbOptions::try_from(&options).unwrap();
+        assert_eq!(
+            options.protocol,
+            InfluxDbProtocol::HttpV1 {
+                database: "edge".to_string()
+            }
+        );
+        assert_eq!(options.remote_port, 8086);
+        assert_eq!(
+            options.tag_columns,
+            vec![
+                ColumnName::new("site".to_string()),
+                ColumnName::new("device".to_string())
+            ]
+        );
+
+        let options = OptionsBuilder::default()
+            .add("PROTOCOL", "UDP")
+            .add("REMOTE_HOST", "127.0.0.1")
+            .add("MEASUREMENT", "temperature")
+            .build();
+        let options = InfluxDbOptions::try_from(&options).unwrap();
+        assert_eq!(options.protocol, InfluxDbProtocol::Udp);
+        assert_eq!(options.remote_port, 8089);
+
+        let options = OptionsBuilder::default()
+            .add("REMOTE_HOST", "127.0.0.1")
+            .add("MEASUREMENT", "temperature")
+            .build();
+        assert!(InfluxDbOptions::try_from(&options).is_err());
+    }
+}
@@ -0,0 +1,148 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+
+use crate::api::error::{Result, SpringError};
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+enum JsonPathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// Subset of JSONPath to select a value in a JSON document.
+///
+/// Supports only the root (`$`), child keys (`.records`, `['records']`), and array indices (`[0]`).
+///
+/// ```text
+/// $
+/// $.records
+/// $.payload.records
+/// $['payload'][0]
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct JsonPath(Vec<JsonPathSegment>);
+
+impl JsonPath {
+    /// # Returns
+    ///
+    /// None if `json` does not have a value at this path.
+    pub fn select<'a>(&self, json: &'a serde_json::Value) -> Option<&'a serde_json::Value> {
+        self.0.iter().try_fold(json, |v, segment| match segment {
+            JsonPathSegment::Key(key) => v.get(key),
+            JsonPathSegment::Index(idx) => v.get(idx),
+        })
+    }
+}
+
+impl FromStr for JsonPath {
+    type Err = SpringError;
+
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidFormat` when:
+    ///   - `s` is not in the supported subset of JSONPath.
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = |e: anyhow::Error| SpringError::InvalidFormat {
+            s: s.to_string(),
+            source: e,
+        };
+
+        let mut rest = s
+            .strip_prefix('$')
+            .context("JSONPath must start with `$`")
+            .map_err(invalid)?;
+
+        let mut segments = vec![];
+        while !rest.is_empty() {
+            if let Some(r) = rest.strip_prefix('.') {
+                let end = r.find(|c| c == '.' || c == '[').unwrap_or(r.len());
+                let key = &r[..end];
+                if key.is_empty() {
+                    return Err(invalid(anyhow!("empty key in JSONPath")));
+                }
+                segments.push(JsonPathSegment::Key(key.to_string()));
+                rest = &r[end..];
+            } else if let Some(r) = rest.strip_prefix('[') {
+                let end = r
+                    .find(']')
+                    .context("unclosed `[` in JSONPath")
+                    .map_err(invalid)?;
+                let inner = &r[..end];
+                let segment = if let Some(key) =
+                    inner.strip_prefix('\'').and_then(|k| k.strip_suffix('\''))
+                {
+                    JsonPathSegment::Key(key.to_string())
+                } else {
+                    JsonPathSegment::Index(
+                        inner
+                            .parse()
+                            .context("array index in JSONPath must be an unsigned integer")
+                            .map_err(invalid)?,
+                    )
+                };
+                segments.push(segment);
+                rest = &r[end + 1..];
+            } else {
+                return Err(invalid(anyhow!(
+                    "unexpected character in JSONPath: {}",
+                    rest
+                )));
+            }
+        }
+
+        Ok(Self(segments))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn test_select() {
+        let json = json!({
+            "payload": {
+                "records": [{"c": 1}, {"c": 2}]
+            }
+        });
+
+        assert_eq!(JsonPath::from_str("$").unwrap().select(&json), Some(&json));
+        assert_eq!(
+            JsonPath::from_str("$.payload.records")
+                .unwrap()
+                .select(&json),
+            Some(&json!([{"c": 1}, {"c": 2}]))
+        );
+        assert_eq!(
+            JsonPath::from_str("$['payload'].records[1]")
+                .unwrap()
+                .select(&json),
+            Some(&json!({"c": 2}))
+        );
+        assert_eq!(
+            JsonPath::from_str("$.payload.missing")
+                .unwrap()
+                .select(&json),
+            None
+        );
+    }
+
+    #[test]
+    fn test_invalid_path() {
+        for s in ["records", "$.", "$[x]", "$[0", "$records"] {
+            assert!(
+                matches!(
+                    JsonPath::from_str(s).unwrap_err(),
+                    SpringError::InvalidFormat { .. }
+                ),
+                "{}",
+                s
+            );
+        }
+    }
+}
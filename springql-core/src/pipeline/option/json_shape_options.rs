@@ -0,0 +1,191 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, ensure, Context};
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{option::Options, ColumnName},
+};
+
+/// Layout of a JSON payload.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum JsonLayout {
+    /// A field per column.
+    Flat,
+    /// Field names are split by `JSON_NESTING_SEPARATOR` into nested objects (e.g. `pos__lat` into `{"pos": {"lat": ...}}`).
+    Nested,
+}
+
+/// Envelope wrapping a JSON payload: `{"schema": "<name>", "version": "<version>", "payload": {...}}`.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct JsonEnvelope {
+    pub schema: String,
+    /// `version` field is omitted if not set.
+    pub version: Option<String>,
+}
+
+/// Shape of JSON payloads of sink writers which send rows as JSON.
+///
+/// ```text
+/// JSON_NULLS 'EXCLUDE',
+/// JSON_LAYOUT 'NESTED',
+/// JSON_NESTING_SEPARATOR '__',
+/// JSON_RENAME 'ts:timestamp,lat:pos__lat,lng:pos__lng',
+/// JSON_ENVELOPE_SCHEMA 'vehicle_position',
+/// JSON_ENVELOPE_VERSION '2'
+/// ```
+///
+/// Every option is optional and the default is a flat object with a field per column, including NULLs.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct JsonShapeOptions {
+    /// Whether to output fields with NULL. INCLUDE by default.
+    pub include_nulls: bool,
+    /// FLAT by default.
+    pub layout: JsonLayout,
+    /// `__` by default.
+    pub nesting_separator: String,
+    /// column -> field name. Renamed before nested.
+    pub renames: BTreeMap<ColumnName, String>,
+    pub envelope: Option<JsonEnvelope>,
+}
+
+impl Default for JsonShapeOptions {
+    fn default() -> Self {
+        Self {
+            include_nulls: true,
+            layout: JsonLayout::Flat,
+            nesting_separator: "__".to_string(),
+            renames: BTreeMap::new(),
+            envelope: None,
+        }
+    }
+}
+
+impl TryFrom<&Options> for JsonShapeOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        let default = Self::default();
+
+        let include_nulls = options
+            .get_optional("JSON_NULLS", |nulls_str| match nulls_str.as_str() {
+                "INCLUDE" => Ok(true),
+                "EXCLUDE" => Ok(false),
+                _ => Err(anyhow!("JSON_NULLS must be INCLUDE or EXCLUDE")),
+            })?
+            .unwrap_or(default.include_nulls);
+
+        let layout = options
+            .get_optional("JSON_LAYOUT", |layout_str| match layout_str.as_str() {
+                "FLAT" => Ok(JsonLayout::Flat),
+                "NESTED" => Ok(JsonLayout::Nested),
+                _ => Err(anyhow!("JSON_LAYOUT must be FLAT or NESTED")),
+            })?
+            .unwrap_or(default.layout);
+
+        let nesting_separator = options
+            .get_optional("JSON_NESTING_SEPARATOR", |separator_str| {
+                ensure!(
+                    !separator_str.is_empty(),
+                    "JSON_NESTING_SEPARATOR must not be empty"
+                );
+                Ok(separator_str.to_string())
+            })?
+            .unwrap_or(default.nesting_separator);
+
+        let renames = options
+            .get_optional("JSON_RENAME", |renames_str| {
+                renames_str
+                    .split(',')
+                    .map(|pair| {
+                        let (column, field) = pair
+                            .split_once(':')
+                            .context("JSON_RENAME must be `column:field` pairs separated by ','")?;
+                        let (column, field) = (column.trim(), field.trim());
+                        ensure!(
+                            !column.is_empty() && !field.is_empty(),
+                            "empty column or field in JSON_RENAME"
+                        );
+                        Ok((ColumnName::new(column.to_string()), field.to_string()))
+                    })
+                    .collect::<anyhow::Result<BTreeMap<_, _>>>()
+            })?
+            .unwrap_or_default();
+
+        let envelope_version = options.get_optional("JSON_ENVELOPE_VERSION", |version_str| {
+            Ok(version_str.to_string())
+        })?;
+        let envelope = options
+            .get_optional("JSON_ENVELOPE_SCHEMA", |schema_str| {
+                Ok(schema_str.to_string())
+            })?
+            .map(|schema| JsonEnvelope {
+                schema,
+                version: envelope_version.clone(),
+            });
+        if envelope.is_none() && envelope_version.is_some() {
+            return Err(SpringError::InvalidOption {
+                key: "JSON_ENVELOPE_VERSION".to_string(),
+                value: envelope_version.unwrap_or_default(),
+                source: anyhow!("JSON_ENVELOPE_VERSION requires JSON_ENVELOPE_SCHEMA"),
+            });
+        }
+
+        Ok(Self {
+            include_nulls,
+            layout,
+            nesting_separator,
+            renames,
+            envelope,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::OptionsBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_json_shape_options() {
+        let options = OptionsBuilder::default()
+            .add("REMOTE_HOST", "127.0.0.1")
+            .build();
+        assert_eq!(
+            JsonShapeOptions::try_from(&options).unwrap(),
+            JsonShapeOptions::default()
+        );
+
+        let options = OptionsBuilder::default()
+            .add("JSON_NULLS", "EXCLUDE")
+            .add("JSON_LAYOUT", "NESTED")
+            .add("JSON_RENAME", "ts:timestamp, lat:pos__lat")
+            .add("JSON_ENVELOPE_SCHEMA", "vehicle_position")
+            .build();
+        let shape = JsonShapeOptions::try_from(&options).unwrap();
+        assert!(!shape.include_nulls);
+        assert_eq!(shape.layout, JsonLayout::Nested);
+        assert_eq!(
+            shape.renames.get(&ColumnName::new("lat".to_string())),
+            Some(&"pos__lat".to_string())
+        );
+        assert_eq!(
+            shape.envelope,
+            Some(JsonEnvelope {
+                schema: "vehicle_position".to_string(),
+                version: None
+            })
+        );
+
+        let options = OptionsBuilder::default().add("JSON_RENAME", "ts").build();
+        assert!(JsonShapeOptions::try_from(&options).is_err());
+
+        let options = OptionsBuilder::default()
+            .add("JSON_ENVELOPE_VERSION", "2")
+            .build();
+        assert!(JsonShapeOptions::try_from(&options).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{option::Options, JsonPath},
+};
+
+/// Where a consumer group without committed offsets starts reading partitions.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum KafkaStartOffset {
+    Earliest,
+    Latest,
+}
+
+impl FromStr for KafkaStartOffset {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "EARLIEST" => Ok(Self::Earliest),
+            "LATEST" => Ok(Self::Latest),
+            _ => Err(anyhow!("START_OFFSET must be EARLIEST or LATEST")),
+        }
+    }
+}
+
+impl From<KafkaStartOffset> for kafka::client::FetchOffset {
+    fn from(start_offset: KafkaStartOffset) -> Self {
+        match start_offset {
+            KafkaStartOffset::Earliest => Self::Earliest,
+            KafkaStartOffset::Latest => Self::Latest,
+        }
+    }
+}
+
+/// Consumes a Kafka topic whose messages are JSON rows.
+///
+/// ```text
+/// BROKERS '192.168.0.10:9092,192.168.0.11:9092',
+/// TOPIC 'sensors',
+/// GROUP_ID 'springql',
+/// START_OFFSET 'EARLIEST'
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct KafkaSourceOptions {
+    /// Bootstrap brokers (host:port).
+    pub brokers: Vec<String>,
+    pub topic: String,
+    /// Consumer group whose offsets are committed to Kafka.
+    pub group_id: String,
+    /// LATEST by default. Used only when the group has no committed offset for a partition.
+    pub start_offset: KafkaStartOffset,
+    /// Selects a JSON array (or a JSON object) of rows in each message.
+    pub json_records_path: Option<JsonPath>,
+}
+
+impl TryFrom<&Options> for KafkaSourceOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        Ok(Self {
+            brokers: options.get("BROKERS", |brokers_str| {
+                let brokers = brokers_str
+                    .split(',')
+                    .map(|broker| broker.trim().to_string())
+                    .filter(|broker| !broker.is_empty())
+                    .collect::<Vec<_>>();
+                anyhow::ensure!(!brokers.is_empty(), "BROKERS must not be empty");
+                Ok(brokers)
+            })?,
+            topic: options.get("TOPIC", |topic_str| {
+                anyhow::ensure!(!topic_str.is_empty(), "TOPIC must not be empty");
+                Ok(topic_str.to_string())
+            })?,
+            group_id: options.get("GROUP_ID", |group_id_str| {
+                anyhow::ensure!(!group_id_str.is_empty(), "GROUP_ID must not be empty");
+                Ok(group_id_str.to_string())
+            })?,
+            start_offset: options
+                .get_optional("START_OFFSET", |start_offset_str| start_offset_str.parse())?
+                .unwrap_or(KafkaStartOffset::Latest),
+            json_records_path: options.get_optional("JSON_RECORDS_PATH", |path_str| {
+                path_str.parse::<JsonPath>().context("invalid JSONPath")
+            })?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::OptionsBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_kafka_source_options() {
+        let options = OptionsBuilder::default()
+            .add("BROKERS", "127.0.0.1:9092, 127.0.0.1:9093")
+            .add("TOPIC", "sensors")
+            .add("GROUP_ID", "springql")
+            .add("START_OFFSET", "EARLIEST")
+            .build();
+        let options = KafkaSourceOptions::try_from(&options).unwrap();
+        assert_eq!(
+            options.brokers,
+            vec!["127.0.0.1:9092".to_string(), "127.0.0.1:9093".to_string()]
+        );
+        assert_eq!(options.start_offset, KafkaStartOffset::Earliest);
+
+        let options = OptionsBuilder::default()
+            .add("BROKERS", "127.0.0.1:9092")
+            .add("TOPIC", "sensors")
+            .add("GROUP_ID", "springql")
+            .build();
+        let options = KafkaSourceOptions::try_from(&options).unwrap();
+        assert_eq!(options.start_offset, KafkaStartOffset::Latest);
+
+        // offsets are committed to a consumer group
+        let options = OptionsBuilder::default()
+            .add("BROKERS", "127.0.0.1:9092")
+            .add("TOPIC", "sensors")
+            .build();
+        assert!(KafkaSourceOptions::try_from(&options).is_err());
+
+        let options = OptionsBuilder::default()
+            .add("BROKERS", "127.0.0.1:9092")
+            .add("TOPIC", "sensors")
+            .add("GROUP_ID", "springql")
+            .add("START_OFFSET", "NOW")
+            .build();
+        assert!(KafkaSourceOptions::try_from(&options).is_err());
+    }
+}
@@ -0,0 +1,237 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{net::IpAddr, str::FromStr, time::Duration};
+
+use anyhow::{anyhow, bail, Context};
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{option::Options, ColumnName},
+};
+
+/// Modbus data table.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ModbusTable {
+    Coil,
+    DiscreteInput,
+    InputRegister,
+    HoldingRegister,
+}
+
+/// Interpretation of register values.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ModbusValueType {
+    /// Coils and discrete inputs.
+    Bool,
+    U16,
+    I16,
+    /// Two registers.
+    U32,
+    /// Two registers.
+    I32,
+    /// Two registers (IEEE 754).
+    F32,
+}
+
+impl ModbusValueType {
+    /// Number of registers (or bits) to read.
+    pub fn quantity(&self) -> u16 {
+        match self {
+            Self::Bool | Self::U16 | Self::I16 => 1,
+            Self::U32 | Self::I32 | Self::F32 => 2,
+        }
+    }
+}
+
+/// A register (or coil) mapped to a column.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ModbusRegister {
+    pub column: ColumnName,
+    pub table: ModbusTable,
+    /// 0-based address in the table (e.g. 0 for holding register 40001).
+    pub address: u16,
+    pub value_type: ModbusValueType,
+}
+
+impl ModbusRegister {
+    /// Parses `<table>:<address>[:<type>]` (e.g. `holding:100:f32`).
+    ///
+    /// - table: `coil`, `discrete`, `input` or `holding`
+    /// - type: `bool` (default for coils and discrete inputs), `u16` (default for registers), `i16`, `u32`, `i32` or `f32`
+    fn parse(column: ColumnName, s: &str) -> anyhow::Result<Self> {
+        let mut parts = s.split(':');
+
+        let table = match parts.next().unwrap_or_default() {
+            "coil" => ModbusTable::Coil,
+            "discrete" => ModbusTable::DiscreteInput,
+            "input" => ModbusTable::InputRegister,
+            "holding" => ModbusTable::HoldingRegister,
+            table => bail!("unknown Modbus table: {}", table),
+        };
+        let address = parts
+            .next()
+            .context("address is missing")?
+            .parse()
+            .context("invalid address")?;
+
+        let is_bit = matches!(table, ModbusTable::Coil | ModbusTable::DiscreteInput);
+        let value_type = match (parts.next(), is_bit) {
+            (None, true) | (Some("bool"), true) => ModbusValueType::Bool,
+            (None, false) | (Some("u16"), false) => ModbusValueType::U16,
+            (Some("i16"), false) => ModbusValueType::I16,
+            (Some("u32"), false) => ModbusValueType::U32,
+            (Some("i32"), false) => ModbusValueType::I32,
+            (Some("f32"), false) => ModbusValueType::F32,
+            (Some(typ), _) => bail!("type {} is not available for {:?}", typ, table),
+        };
+        if parts.next().is_some() {
+            bail!("expected `<table>:<address>[:<type>]`");
+        }
+
+        Ok(Self {
+            column,
+            table,
+            address,
+            value_type,
+        })
+    }
+}
+
+/// Order of 16-bit words in 32-bit values.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ModbusWordOrder {
+    /// The first register has the high word.
+    BigEndian,
+    LittleEndian,
+}
+
+impl FromStr for ModbusWordOrder {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "BIG" => Ok(Self::BigEndian),
+            "LITTLE" => Ok(Self::LittleEndian),
+            _ => Err(anyhow!("WORD_ORDER must be BIG or LITTLE")),
+        }
+    }
+}
+
+/// Polls registers of a Modbus TCP server.
+///
+/// ```text
+/// REMOTE_HOST '192.168.0.10',
+/// REMOTE_PORT '502',
+/// UNIT_ID '1',
+/// POLL_INTERVAL_MSEC '1000',
+/// REGISTER_<column> '<table>:<address>[:<type>]',
+/// WORD_ORDER 'BIG',
+/// TIMESTAMP_COLUMN 'ts'
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ModbusOptions {
+    pub remote_host: IpAddr,
+    /// 502 by default.
+    pub remote_port: u16,
+    /// 1 by default.
+    pub unit_id: u8,
+    /// 1000 milliseconds by default.
+    pub poll_interval: Duration,
+    /// In the order of columns.
+    pub registers: Vec<ModbusRegister>,
+    /// BIG by default.
+    pub word_order: ModbusWordOrder,
+    /// Column to put the poll time into.
+    pub timestamp_column: Option<ColumnName>,
+}
+
+impl TryFrom<&Options> for ModbusOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        let mut registers = options
+            .as_key_values()
+            .into_iter()
+            .filter_map(|(key, value)| {
+                let column = ColumnName::new(key.strip_prefix("REGISTER_")?.to_string());
+                let register =
+                    ModbusRegister::parse(column, value).map_err(|e| SpringError::InvalidOption {
+                        key: key.to_string(),
+                        value: value.to_string(),
+                        source: e,
+                    });
+                Some(register)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        registers.sort_by(|a, b| a.column.cmp(&b.column));
+        if registers.is_empty() {
+            return Err(SpringError::InvalidOption {
+                key: "REGISTER_<column>".to_string(),
+                value: "(not found)".to_string(),
+                source: anyhow!("at least one register must be polled"),
+            });
+        }
+
+        Ok(Self {
+            remote_host: options.get("REMOTE_HOST", |remote_host_str| {
+                remote_host_str.parse().context("invalid remote host")
+            })?,
+            remote_port: options
+                .get_optional("REMOTE_PORT", |remote_port_str| {
+                    remote_port_str.parse().context("invalid remote port")
+                })?
+                .unwrap_or(502),
+            unit_id: options
+                .get_optional("UNIT_ID", |unit_id_str| {
+                    unit_id_str.parse().context("invalid unit ID")
+                })?
+                .unwrap_or(1),
+            poll_interval: options
+                .get_optional("POLL_INTERVAL_MSEC", |msec_str| {
+                    let msec: u64 = msec_str.parse().context("invalid POLL_INTERVAL_MSEC")?;
+                    anyhow::ensure!(msec > 0, "POLL_INTERVAL_MSEC must be positive");
+                    Ok(Duration::from_millis(msec))
+                })?
+                .unwrap_or_else(|| Duration::from_millis(1000)),
+            registers,
+            word_order: options
+                .get_optional("WORD_ORDER", |s| s.parse())?
+                .unwrap_or(ModbusWordOrder::BigEndian),
+            timestamp_column: options.get_optional("TIMESTAMP_COLUMN", |column_str| {
+                Ok(ColumnName::new(column_str.to_string()))
+            })?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn register(s: &str) -> anyhow::Result<ModbusRegister> {
+        ModbusRegister::parse(ColumnName::new("c".to_string()), s)
+    }
+
+    #[test]
+    fn test_parse_register() {
+        let r = register("holding:100:f32").unwrap();
+        assert_eq!(r.table, ModbusTable::HoldingRegister);
+        assert_eq!(r.address, 100);
+        assert_eq!(r.value_type, ModbusValueType::F32);
+
+        assert_eq!(
+            register("coil:3").unwrap().value_type,
+            ModbusValueType::Bool
+        );
+        assert_eq!(
+            register("input:3").unwrap().value_type,
+            ModbusValueType::U16
+        );
+
+        assert!(register("holding:100:bool").is_err());
+        assert!(register("coil:3:u16").is_err());
+        assert!(register("unknown:1").is_err());
+        assert!(register("holding").is_err());
+        assert!(register("holding:70000").is_err());
+    }
+}
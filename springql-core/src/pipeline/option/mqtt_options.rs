@@ -0,0 +1,251 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    process,
+    str::FromStr,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use anyhow::{anyhow, Context};
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{
+        option::{ColumnTemplate, Options},
+        JsonPath,
+    },
+};
+
+const DEFAULT_PORT: u16 = 1883;
+
+/// Quality of service of MQTT messages.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum MqttQos {
+    AtMostOnce,
+    AtLeastOnce,
+    ExactlyOnce,
+}
+
+impl FromStr for MqttQos {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "0" => Ok(Self::AtMostOnce),
+            "1" => Ok(Self::AtLeastOnce),
+            "2" => Ok(Self::ExactlyOnce),
+            _ => Err(anyhow!("QOS must be 0, 1 or 2")),
+        }
+    }
+}
+
+impl From<MqttQos> for rumqttc::QoS {
+    fn from(qos: MqttQos) -> Self {
+        match qos {
+            MqttQos::AtMostOnce => Self::AtMostOnce,
+            MqttQos::AtLeastOnce => Self::AtLeastOnce,
+            MqttQos::ExactlyOnce => Self::ExactlyOnce,
+        }
+    }
+}
+
+/// Subscribes to an MQTT topic filter whose messages are JSON rows.
+///
+/// ```text
+/// REMOTE_HOST '192.168.0.10',
+/// REMOTE_PORT '1883',
+/// TOPIC 'factory/+/temperature',
+/// QOS '1',
+/// CLIENT_ID 'gateway-1',
+/// USER_NAME 'gateway',
+/// PASSWORD '...'
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct MqttSourceOptions {
+    pub remote_host: String,
+    /// 1883 by default.
+    pub remote_port: u16,
+    /// May contain wildcards (`+`, `#`).
+    pub topic: String,
+    /// 0 by default.
+    pub qos: MqttQos,
+    /// Unique in the process by default. Set a fixed one to let the broker keep the session.
+    pub client_id: String,
+    /// Selects a JSON array (or a JSON object) of rows in each message.
+    pub json_records_path: Option<JsonPath>,
+    /// (user name, password). Anonymous if not set.
+    pub user: Option<(String, String)>,
+}
+
+impl TryFrom<&Options> for MqttSourceOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        Ok(Self {
+            remote_host: options.get("REMOTE_HOST", |s| Ok(s.to_string()))?,
+            remote_port: parse_remote_port(options)?,
+            topic: options.get("TOPIC", |topic_str| {
+                anyhow::ensure!(!topic_str.is_empty(), "TOPIC must not be empty");
+                Ok(topic_str.to_string())
+            })?,
+            qos: parse_qos(options)?,
+            client_id: parse_client_id(options)?,
+            json_records_path: options.get_optional("JSON_RECORDS_PATH", |path_str| {
+                path_str.parse::<JsonPath>().context("invalid JSONPath")
+            })?,
+            user: parse_user(options)?,
+        })
+    }
+}
+
+/// Publishes rows as JSON messages to an MQTT topic.
+///
+/// ```text
+/// REMOTE_HOST '192.168.0.10',
+/// TOPIC 'alerts/${site}',
+/// QOS '1',
+/// RETAIN 'false'
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct MqttSinkOptions {
+    pub remote_host: String,
+    /// 1883 by default.
+    pub remote_port: u16,
+    /// May contain `${column}` placeholders. Must not contain wildcards.
+    pub topic: ColumnTemplate,
+    /// 0 by default.
+    pub qos: MqttQos,
+    /// false by default.
+    pub retain: bool,
+    /// Unique in the process by default.
+    pub client_id: String,
+    /// (user name, password). Anonymous if not set.
+    pub user: Option<(String, String)>,
+}
+
+impl TryFrom<&Options> for MqttSinkOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        Ok(Self {
+            remote_host: options.get("REMOTE_HOST", |s| Ok(s.to_string()))?,
+            remote_port: parse_remote_port(options)?,
+            topic: options.get("TOPIC", |topic_str| {
+                anyhow::ensure!(
+                    !topic_str.contains(['+', '#']),
+                    "TOPIC must not contain wildcards"
+                );
+                Ok(ColumnTemplate::from_str(topic_str)?)
+            })?,
+            qos: parse_qos(options)?,
+            retain: options
+                .get_optional("RETAIN", |retain_str| {
+                    retain_str
+                        .parse::<bool>()
+                        .context("RETAIN must be true or false")
+                })?
+                .unwrap_or(false),
+            client_id: parse_client_id(options)?,
+            user: parse_user(options)?,
+        })
+    }
+}
+
+fn parse_remote_port(options: &Options) -> Result<u16> {
+    Ok(options
+        .get_optional("REMOTE_PORT", |port_str| {
+            port_str.parse::<u16>().context("invalid REMOTE_PORT")
+        })?
+        .unwrap_or(DEFAULT_PORT))
+}
+
+fn parse_qos(options: &Options) -> Result<MqttQos> {
+    Ok(options
+        .get_optional("QOS", |qos_str| qos_str.parse())?
+        .unwrap_or(MqttQos::AtMostOnce))
+}
+
+fn parse_client_id(options: &Options) -> Result<String> {
+    static SEQ: AtomicUsize = AtomicUsize::new(0);
+
+    Ok(options
+        .get_optional("CLIENT_ID", |client_id_str| {
+            anyhow::ensure!(!client_id_str.is_empty(), "CLIENT_ID must not be empty");
+            Ok(client_id_str.to_string())
+        })?
+        .unwrap_or_else(|| {
+            // brokers disconnect an existing client with the same ID
+            format!(
+                "springql-{}-{}",
+                process::id(),
+                SEQ.fetch_add(1, Ordering::Relaxed)
+            )
+        }))
+}
+
+fn parse_user(options: &Options) -> Result<Option<(String, String)>> {
+    let user_name = options.get_optional("USER_NAME", |s| Ok(s.to_string()))?;
+    user_name
+        .map(|user_name| {
+            let password = options.get("PASSWORD", |s| Ok(s.to_string()))?;
+            Ok((user_name, password))
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::OptionsBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_mqtt_source_options() {
+        let options = OptionsBuilder::default()
+            .add("REMOTE_HOST", "127.0.0.1")
+            .add("TOPIC", "factory/+/temperature")
+            .add("QOS", "2")
+            .build();
+        let options = MqttSourceOptions::try_from(&options).unwrap();
+        assert_eq!(options.remote_port, 1883);
+        assert_eq!(options.qos, MqttQos::ExactlyOnce);
+        assert_eq!(options.user, None);
+
+        // unique client IDs by default
+        let options2 = OptionsBuilder::default()
+            .add("REMOTE_HOST", "127.0.0.1")
+            .add("TOPIC", "factory/+/temperature")
+            .build();
+        let options2 = MqttSourceOptions::try_from(&options2).unwrap();
+        assert_ne!(options.client_id, options2.client_id);
+
+        let options = OptionsBuilder::default()
+            .add("REMOTE_HOST", "127.0.0.1")
+            .add("TOPIC", "factory/+/temperature")
+            .add("QOS", "3")
+            .build();
+        assert!(MqttSourceOptions::try_from(&options).is_err());
+    }
+
+    #[test]
+    fn test_mqtt_sink_options() {
+        let options = OptionsBuilder::default()
+            .add("REMOTE_HOST", "127.0.0.1")
+            .add("REMOTE_PORT", "11883")
+            .add("TOPIC", "alerts/${site}")
+            .add("RETAIN", "true")
+            .add("CLIENT_ID", "gateway-1")
+            .build();
+        let options = MqttSinkOptions::try_from(&options).unwrap();
+        assert_eq!(options.remote_port, 11883);
+        assert_eq!(options.qos, MqttQos::AtMostOnce);
+        assert!(options.retain);
+        assert_eq!(options.client_id, "gateway-1");
+
+        let options = OptionsBuilder::default()
+            .add("REMOTE_HOST", "127.0.0.1")
+            .add("TOPIC", "alerts/#")
+            .build();
+        assert!(MqttSinkOptions::try_from(&options).is_err());
+    }
+}
@@ -0,0 +1,169 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{
+        option::{ColumnTemplate, Options},
+        JsonPath,
+    },
+};
+
+/// Delivery mode of NATS messages.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum NatsMode {
+    /// Core NATS (at-most-once).
+    Core,
+    /// JetStream (at-least-once). Messages are acknowledged by source readers and publications are acknowledged by the server for sink writers.
+    JetStream,
+}
+
+impl FromStr for NatsMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "CORE" => Ok(Self::Core),
+            "JETSTREAM" => Ok(Self::JetStream),
+            _ => Err(anyhow!("MODE must be CORE or JETSTREAM")),
+        }
+    }
+}
+
+/// Subscribes to a NATS subject whose messages are JSON rows.
+///
+/// ```text
+/// URL 'nats://127.0.0.1:4222',
+/// SUBJECT 'sensors.>',
+/// QUEUE_GROUP 'springql',
+/// MODE 'JETSTREAM',
+/// DURABLE_NAME 'gateway-1',
+/// USER_NAME 'gateway',
+/// PASSWORD '...'
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct NatsSourceOptions {
+    /// Comma-separated server URLs.
+    pub url: String,
+    /// May contain wildcards (`*`, `>`).
+    pub subject: String,
+    /// Messages are distributed among subscribers in the same queue group.
+    pub queue_group: Option<String>,
+    /// CORE by default.
+    pub mode: NatsMode,
+    /// Durable consumer name for JetStream, which resumes from the last acknowledged message.
+    pub durable_name: Option<String>,
+    /// Selects a JSON array (or a JSON object) of rows in each message.
+    pub json_records_path: Option<JsonPath>,
+    /// (user name, password). Anonymous if not set.
+    pub user: Option<(String, String)>,
+}
+
+impl TryFrom<&Options> for NatsSourceOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        let mode = parse_mode(options)?;
+        let durable_name = options.get_optional("DURABLE_NAME", |s| Ok(s.to_string()))?;
+        if durable_name.is_some() && mode != NatsMode::JetStream {
+            return Err(SpringError::InvalidOption {
+                key: "DURABLE_NAME".to_string(),
+                value: durable_name.unwrap_or_default(),
+                source: anyhow!("DURABLE_NAME is available only in JETSTREAM mode"),
+            });
+        }
+
+        Ok(Self {
+            url: options.get("URL", |s| Ok(s.to_string()))?,
+            subject: options.get("SUBJECT", |s| Ok(s.to_string()))?,
+            queue_group: options.get_optional("QUEUE_GROUP", |s| Ok(s.to_string()))?,
+            mode,
+            durable_name,
+            json_records_path: options.get_optional("JSON_RECORDS_PATH", |path_str| {
+                path_str.parse::<JsonPath>().context("invalid JSONPath")
+            })?,
+            user: parse_user(options)?,
+        })
+    }
+}
+
+/// Publishes rows as JSON messages to a NATS subject.
+///
+/// ```text
+/// URL 'nats://127.0.0.1:4222',
+/// SUBJECT 'alerts.${site}',
+/// MODE 'CORE'
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct NatsSinkOptions {
+    /// Comma-separated server URLs.
+    pub url: String,
+    /// May contain `${column}` placeholders.
+    pub subject: ColumnTemplate,
+    /// CORE by default.
+    pub mode: NatsMode,
+    /// (user name, password). Anonymous if not set.
+    pub user: Option<(String, String)>,
+}
+
+impl TryFrom<&Options> for NatsSinkOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        Ok(Self {
+            url: options.get("URL", |s| Ok(s.to_string()))?,
+            subject: options.get("SUBJECT", |subject_str| {
+                Ok(ColumnTemplate::from_str(subject_str)?)
+            })?,
+            mode: parse_mode(options)?,
+            user: parse_user(options)?,
+        })
+    }
+}
+
+fn parse_mode(options: &Options) -> Result<NatsMode> {
+    Ok(options
+        .get_optional("MODE", |s| s.parse())?
+        .unwrap_or(NatsMode::Core))
+}
+
+fn parse_user(options: &Options) -> Result<Option<(String, String)>> {
+    let user_name = options.get_optional("USER_NAME", |s| Ok(s.to_string()))?;
+    user_name
+        .map(|user_name| {
+            let password = options.get("PASSWORD", |s| Ok(s.to_string()))?;
+            Ok((user_name, password))
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::OptionsBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_nats_source_options() {
+        let options = OptionsBuilder::default()
+            .add("URL", "nats://127.0.0.1:4222")
+            .add("SUBJECT", "sensors.>")
+            .add("MODE", "JETSTREAM")
+            .add("DURABLE_NAME", "gateway-1")
+            .build();
+        let options = NatsSourceOptions::try_from(&options).unwrap();
+        assert_eq!(options.mode, NatsMode::JetStream);
+        assert_eq!(options.durable_name, Some("gateway-1".to_string()));
+        assert_eq!(options.queue_group, None);
+
+        let options = OptionsBuilder::default()
+            .add("URL", "nats://127.0.0.1:4222")
+            .add("SUBJECT", "sensors.>")
+            .add("DURABLE_NAME", "gateway-1")
+            .build();
+        assert!(NatsSourceOptions::try_from(&options).is_err());
+    }
+}
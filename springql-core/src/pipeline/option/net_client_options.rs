@@ -6,7 +6,7 @@ use anyhow::Context;
 
 use crate::{
     api::error::{Result, SpringError},
-    pipeline::{option::Options, NetProtocol},
+    pipeline::{option::Options, JsonPath, NetProtocol},
 };
 
 #[derive(Clone, Eq, PartialEq, Debug)]
@@ -14,6 +14,9 @@ pub struct NetClientOptions {
     pub protocol: NetProtocol,
     pub remote_host: IpAddr,
     pub remote_port: u16,
+
+    /// Selects a JSON array (or a JSON object) of rows in each payload.
+    pub json_records_path: Option<JsonPath>,
 }
 
 impl TryFrom<&Options> for NetClientOptions {
@@ -32,6 +35,9 @@ impl TryFrom<&Options> for NetClientOptions {
             remote_port: options.get("REMOTE_PORT", |remote_port_str| {
                 remote_port_str.parse().context("invalid remote port")
             })?,
+            json_records_path: options.get_optional("JSON_RECORDS_PATH", |path_str| {
+                path_str.parse::<JsonPath>().context("invalid JSONPath")
+            })?,
         })
     }
 }
@@ -0,0 +1,98 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{collections::HashMap, time::Duration};
+
+use anyhow::Context;
+use reqwest::Url;
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{option::Options, JsonPath},
+};
+
+/// Polls an HTTP endpoint with GET requests.
+///
+/// ```text
+/// URL 'http://192.168.0.10:8080/api/v1/readings',
+/// INTERVAL_MS '1000',
+/// HEADER_<name> '<value>',
+/// JSON_RECORDS_PATH '$.readings'
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct NetHttpOptions {
+    pub url: Url,
+    /// 1000 milliseconds by default.
+    pub interval: Duration,
+    pub headers: HashMap<String, String>,
+
+    /// Selects a JSON array (or a JSON object) of rows in each response body.
+    pub json_records_path: Option<JsonPath>,
+}
+
+impl TryFrom<&Options> for NetHttpOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        let headers = options
+            .as_key_values()
+            .into_iter()
+            .filter_map(|(key, value)| {
+                let name = key.strip_prefix("HEADER_")?;
+                Some((name.to_string(), value.to_string()))
+            })
+            .collect();
+
+        Ok(Self {
+            url: options.get("URL", |url_str| {
+                let url = Url::parse(url_str).context("failed to parse URL")?;
+                anyhow::ensure!(
+                    matches!(url.scheme(), "http" | "https"),
+                    "URL must start with http:// or https://"
+                );
+                Ok(url)
+            })?,
+            interval: options
+                .get_optional("INTERVAL_MS", |msec_str| {
+                    let msec: u64 = msec_str.parse().context("invalid INTERVAL_MS")?;
+                    anyhow::ensure!(msec > 0, "INTERVAL_MS must be positive");
+                    Ok(Duration::from_millis(msec))
+                })?
+                .unwrap_or_else(|| Duration::from_millis(1000)),
+            headers,
+            json_records_path: options.get_optional("JSON_RECORDS_PATH", |path_str| {
+                path_str.parse::<JsonPath>().context("invalid JSONPath")
+            })?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::OptionsBuilder;
+
+    #[test]
+    fn test_net_http_options() {
+        let options = OptionsBuilder::default()
+            .add("URL", "http://127.0.0.1:8080/readings")
+            .add("HEADER_Authorization", "Bearer xyz")
+            .build();
+        let options = NetHttpOptions::try_from(&options).unwrap();
+        assert_eq!(options.interval, Duration::from_millis(1000));
+        assert_eq!(
+            options.headers.get("Authorization"),
+            Some(&"Bearer xyz".to_string())
+        );
+
+        let options = OptionsBuilder::default()
+            .add("URL", "http://127.0.0.1:8080/readings")
+            .add("INTERVAL_MS", "0")
+            .build();
+        assert!(NetHttpOptions::try_from(&options).is_err());
+
+        let options = OptionsBuilder::default()
+            .add("URL", "tcp://127.0.0.1:8080")
+            .build();
+        assert!(NetHttpOptions::try_from(&options).is_err());
+    }
+}
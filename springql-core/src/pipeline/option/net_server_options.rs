@@ -4,13 +4,16 @@ use anyhow::Context;
 
 use crate::{
     api::error::{Result, SpringError},
-    pipeline::{option::Options, NetProtocol},
+    pipeline::{option::Options, JsonPath, NetProtocol},
 };
 
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub struct NetServerOptions {
     pub protocol: NetProtocol,
     pub port: u16,
+
+    /// Selects a JSON array (or a JSON object) of rows in each payload.
+    pub json_records_path: Option<JsonPath>,
 }
 
 impl TryFrom<&Options> for NetServerOptions {
@@ -26,6 +29,9 @@ impl TryFrom<&Options> for NetServerOptions {
             port: options.get("PORT", |remote_port_str| {
                 remote_port_str.parse().context("invalid port")
             })?,
+            json_records_path: options.get_optional("JSON_RECORDS_PATH", |path_str| {
+                path_str.parse::<JsonPath>().context("invalid JSONPath")
+            })?,
         })
     }
 }
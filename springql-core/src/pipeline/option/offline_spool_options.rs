@@ -0,0 +1,94 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{path::PathBuf, time::Duration};
+
+use anyhow::{ensure, Context};
+
+use crate::{api::error::Result, pipeline::option::Options};
+
+/// Persists rows to a bounded on-disk spool while the remote endpoint of a sink writer is unreachable,
+/// and replays them in order when it comes back.
+///
+/// Available for any sink writer type.
+///
+/// ```text
+/// OFFLINE_SPOOL_DIRECTORY '/var/spool/springql/net_sink',
+/// OFFLINE_SPOOL_MAX_BYTES '67108864',
+/// OFFLINE_SPOOL_RETRY_INTERVAL_MSEC '5000'
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct OfflineSpoolOptions {
+    /// Must exist and must not be shared with other sink writers.
+    pub directory: PathBuf,
+    /// The oldest spooled rows are dropped to keep the spool under this size. 64 MiB by default.
+    pub max_bytes: u64,
+    /// Interval to reconnect and replay rows while the endpoint is unreachable. 5 seconds by default.
+    pub retry_interval: Duration,
+}
+
+impl OfflineSpoolOptions {
+    /// # Returns
+    ///
+    /// `Ok(None)` when `OFFLINE_SPOOL_DIRECTORY` is not set.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidOption` when:
+    ///   - value for a key is invalid.
+    pub fn from_options(options: &Options) -> Result<Option<Self>> {
+        let directory = match options.get_optional("OFFLINE_SPOOL_DIRECTORY", |directory_str| {
+            Ok(PathBuf::from(directory_str))
+        })? {
+            Some(directory) => directory,
+            None => return Ok(None),
+        };
+
+        Ok(Some(Self {
+            directory,
+            max_bytes: options
+                .get_optional("OFFLINE_SPOOL_MAX_BYTES", |max_bytes_str| {
+                    let max_bytes: u64 = max_bytes_str.parse().context("invalid max spool size")?;
+                    ensure!(max_bytes > 0, "OFFLINE_SPOOL_MAX_BYTES must be positive");
+                    Ok(max_bytes)
+                })?
+                .unwrap_or(64 * 1024 * 1024),
+            retry_interval: options
+                .get_optional("OFFLINE_SPOOL_RETRY_INTERVAL_MSEC", |interval_str| {
+                    let msecs: u64 = interval_str.parse().context("invalid retry interval")?;
+                    Ok(Duration::from_millis(msecs))
+                })?
+                .unwrap_or(Duration::from_secs(5)),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::OptionsBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_offline_spool_options() {
+        let options = OptionsBuilder::default()
+            .add("REMOTE_HOST", "127.0.0.1")
+            .build();
+        assert_eq!(OfflineSpoolOptions::from_options(&options).unwrap(), None);
+
+        let options = OptionsBuilder::default()
+            .add("OFFLINE_SPOOL_DIRECTORY", "/tmp/springql")
+            .add("OFFLINE_SPOOL_RETRY_INTERVAL_MSEC", "100")
+            .build();
+        let spool_options = OfflineSpoolOptions::from_options(&options)
+            .unwrap()
+            .unwrap();
+        assert_eq!(spool_options.max_bytes, 64 * 1024 * 1024);
+        assert_eq!(spool_options.retry_interval, Duration::from_millis(100));
+
+        let options = OptionsBuilder::default()
+            .add("OFFLINE_SPOOL_DIRECTORY", "/tmp/springql")
+            .add("OFFLINE_SPOOL_MAX_BYTES", "0")
+            .build();
+        assert!(OfflineSpoolOptions::from_options(&options).is_err());
+    }
+}
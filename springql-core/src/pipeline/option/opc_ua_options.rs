@@ -0,0 +1,114 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{option::Options, ColumnName},
+};
+
+/// Subscribes to OPC UA nodes, each mapped to a column.
+///
+/// ```text
+/// ENDPOINT_URL 'opc.tcp://192.168.0.10:4840',
+/// NODE_<column> 'ns=2;s=Line1/Temperature',
+/// PUBLISHING_INTERVAL_MSEC '1000',
+/// TIMESTAMP_COLUMN 'ts',
+/// USER_NAME 'operator',
+/// PASSWORD '...'
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct OpcUaOptions {
+    pub endpoint_url: String,
+    /// (column, node ID) in the order of columns.
+    pub nodes: Vec<(ColumnName, String)>,
+    /// How often the server publishes data changes. 1000 milliseconds by default.
+    pub publishing_interval: Duration,
+    /// Column to put the latest source timestamp of changed values into.
+    pub timestamp_column: Option<ColumnName>,
+    /// (user name, password). Anonymous if not set.
+    pub user: Option<(String, String)>,
+}
+
+impl TryFrom<&Options> for OpcUaOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        let mut nodes: Vec<(ColumnName, String)> = options
+            .as_key_values()
+            .into_iter()
+            .filter_map(|(key, value)| {
+                key.strip_prefix("NODE_")
+                    .map(|column| (ColumnName::new(column.to_string()), value.to_string()))
+            })
+            .collect();
+        nodes.sort();
+        if nodes.is_empty() {
+            return Err(SpringError::InvalidOption {
+                key: "NODE_<column>".to_string(),
+                value: "(not found)".to_string(),
+                source: anyhow!("at least one node must be subscribed to"),
+            });
+        }
+
+        let user_name = options.get_optional("USER_NAME", |s| Ok(s.to_string()))?;
+        let user = user_name
+            .map(|user_name| {
+                let password = options.get("PASSWORD", |s| Ok(s.to_string()))?;
+                Ok((user_name, password))
+            })
+            .transpose()?;
+
+        Ok(Self {
+            endpoint_url: options.get("ENDPOINT_URL", |s| Ok(s.to_string()))?,
+            nodes,
+            publishing_interval: options
+                .get_optional("PUBLISHING_INTERVAL_MSEC", |msec_str| {
+                    let msec: u64 = msec_str
+                        .parse()
+                        .context("invalid PUBLISHING_INTERVAL_MSEC")?;
+                    Ok(Duration::from_millis(msec))
+                })?
+                .unwrap_or_else(|| Duration::from_millis(1000)),
+            timestamp_column: options.get_optional("TIMESTAMP_COLUMN", |column_str| {
+                Ok(ColumnName::new(column_str.to_string()))
+            })?,
+            user,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::OptionsBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_opc_ua_options() {
+        let options = OptionsBuilder::default()
+            .add("ENDPOINT_URL", "opc.tcp://127.0.0.1:4840")
+            .add("NODE_temperature", "ns=2;s=Line1/Temperature")
+            .add("NODE_running", "ns=2;i=1001")
+            .build();
+        let options = OpcUaOptions::try_from(&options).unwrap();
+
+        assert_eq!(
+            options.nodes,
+            vec![
+                (
+                    ColumnName::new("running".to_string()),
+                    "ns=2;i=1001".to_string()
+                ),
+                (
+                    ColumnName::new("temperature".to_string()),
+                    "ns=2;s=Line1/Temperature".to_string()
+                ),
+            ]
+        );
+        assert_eq!(options.publishing_interval, Duration::from_millis(1000));
+        assert_eq!(options.user, None);
+    }
+}
@@ -0,0 +1,133 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{path::PathBuf, str::FromStr, time::Duration};
+
+use anyhow::{anyhow, ensure, Context};
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::option::Options,
+};
+
+/// Compression codec of Parquet column chunks.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ParquetCompression {
+    Uncompressed,
+    Snappy,
+}
+
+impl FromStr for ParquetCompression {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "UNCOMPRESSED" => Ok(Self::Uncompressed),
+            "SNAPPY" => Ok(Self::Snappy),
+            _ => Err(anyhow!("COMPRESSION must be UNCOMPRESSED or SNAPPY")),
+        }
+    }
+}
+
+/// Writes rows into rolling Parquet files.
+///
+/// ```text
+/// DIRECTORY '/var/spool/springql',
+/// FILE_PREFIX 'sensor',
+/// ROW_GROUP_SIZE '1000',
+/// MAX_FILE_SIZE_BYTES '67108864',
+/// ROLL_INTERVAL_SEC '600',
+/// COMPRESSION 'SNAPPY'
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ParquetOptions {
+    /// Must exist.
+    pub directory: PathBuf,
+    /// `springql` by default.
+    pub file_prefix: String,
+    /// Rows buffered in memory before written as a row group. 1000 by default.
+    pub row_group_size: usize,
+    /// A file is closed when its size reaches this. 64 MiB by default.
+    pub max_file_size_bytes: u64,
+    /// A file is closed when this time has passed since its first row. Not closed by time if not set.
+    pub roll_interval: Option<Duration>,
+    /// SNAPPY by default.
+    pub compression: ParquetCompression,
+}
+
+impl TryFrom<&Options> for ParquetOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        Ok(Self {
+            directory: options.get("DIRECTORY", |directory_str| {
+                Ok(PathBuf::from(directory_str))
+            })?,
+            file_prefix: options
+                .get_optional("FILE_PREFIX", |file_prefix_str| {
+                    ensure!(
+                        !file_prefix_str.contains(std::path::is_separator),
+                        "FILE_PREFIX must not contain path separators"
+                    );
+                    Ok(file_prefix_str.to_string())
+                })?
+                .unwrap_or_else(|| "springql".to_string()),
+            row_group_size: options
+                .get_optional("ROW_GROUP_SIZE", |row_group_size_str| {
+                    let row_group_size: usize = row_group_size_str
+                        .parse()
+                        .context("invalid row group size")?;
+                    ensure!(row_group_size > 0, "ROW_GROUP_SIZE must be positive");
+                    Ok(row_group_size)
+                })?
+                .unwrap_or(1000),
+            max_file_size_bytes: options
+                .get_optional("MAX_FILE_SIZE_BYTES", |max_file_size_str| {
+                    let max_file_size: u64 =
+                        max_file_size_str.parse().context("invalid max file size")?;
+                    ensure!(max_file_size > 0, "MAX_FILE_SIZE_BYTES must be positive");
+                    Ok(max_file_size)
+                })?
+                .unwrap_or(64 * 1024 * 1024),
+            roll_interval: options.get_optional("ROLL_INTERVAL_SEC", |roll_interval_str| {
+                let secs: u64 = roll_interval_str.parse().context("invalid roll interval")?;
+                ensure!(secs > 0, "ROLL_INTERVAL_SEC must be positive");
+                Ok(Duration::from_secs(secs))
+            })?,
+            compression: options
+                .get_optional("COMPRESSION", |s| s.parse())?
+                .unwrap_or(ParquetCompression::Snappy),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::OptionsBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_parquet_options() {
+        let options = OptionsBuilder::default()
+            .add("DIRECTORY", "/tmp/springql")
+            .add("ROLL_INTERVAL_SEC", "60")
+            .build();
+        let options = ParquetOptions::try_from(&options).unwrap();
+        assert_eq!(options.file_prefix, "springql");
+        assert_eq!(options.row_group_size, 1000);
+        assert_eq!(options.roll_interval, Some(Duration::from_secs(60)));
+        assert_eq!(options.compression, ParquetCompression::Snappy);
+
+        let options = OptionsBuilder::default()
+            .add("DIRECTORY", "/tmp/springql")
+            .add("FILE_PREFIX", "a/b")
+            .build();
+        assert!(ParquetOptions::try_from(&options).is_err());
+
+        let options = OptionsBuilder::default()
+            .add("DIRECTORY", "/tmp/springql")
+            .add("ROW_GROUP_SIZE", "0")
+            .build();
+        assert!(ParquetOptions::try_from(&options).is_err());
+    }
+}
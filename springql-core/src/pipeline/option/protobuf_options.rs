@@ -0,0 +1,75 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::option::Options,
+};
+
+/// Protocol Buffers messages, each of which is a row.
+///
+/// ```text
+/// FORMAT 'PROTOBUF',
+/// PROTO_DESCRIPTOR_FILE '/etc/springql/telemetry.desc',
+/// PROTO_MESSAGE 'telemetry.v1.VehicleStatus'
+/// ```
+///
+/// `PROTO_DESCRIPTOR_FILE` is a compiled `FileDescriptorSet`, e.g. by `protoc --include_imports --descriptor_set_out`.
+/// Fields are mapped into columns by their names in `.proto` (not in lowerCamelCase),
+/// and `google.protobuf.Timestamp` fields are mapped into `TIMESTAMP` columns.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ProtobufOptions {
+    pub descriptor_file: PathBuf,
+    /// Fully-qualified message name.
+    pub message: String,
+}
+
+impl TryFrom<&Options> for ProtobufOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        Ok(Self {
+            descriptor_file: options.get("PROTO_DESCRIPTOR_FILE", |s| Ok(PathBuf::from(s)))?,
+            message: options.get("PROTO_MESSAGE", |s| {
+                let message = s.trim_start_matches('.');
+                if message.is_empty() {
+                    Err(anyhow!(
+                        "PROTO_MESSAGE must be a fully-qualified message name"
+                    ))
+                } else {
+                    Ok(message.to_string())
+                }
+            })?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::OptionsBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_protobuf_options() {
+        let options = OptionsBuilder::default()
+            .add("PROTO_DESCRIPTOR_FILE", "/tmp/telemetry.desc")
+            .add("PROTO_MESSAGE", ".telemetry.v1.VehicleStatus")
+            .build();
+        assert_eq!(
+            ProtobufOptions::try_from(&options).unwrap(),
+            ProtobufOptions {
+                descriptor_file: PathBuf::from("/tmp/telemetry.desc"),
+                message: "telemetry.v1.VehicleStatus".to_string(),
+            }
+        );
+
+        let options = OptionsBuilder::default()
+            .add("PROTO_DESCRIPTOR_FILE", "/tmp/telemetry.desc")
+            .build();
+        assert!(ProtobufOptions::try_from(&options).is_err());
+    }
+}
@@ -0,0 +1,61 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{option::Options, JoinBuildSide},
+};
+
+/// Options of `CREATE PUMP`, to override choices of the query planner.
+///
+/// ```text
+/// JOIN_BUILD_SIDE 'RIGHT'
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct PumpOptions {
+    /// AUTO by default. Only for JOIN with window clause.
+    pub join_build_side: JoinBuildSide,
+}
+
+impl TryFrom<&Options> for PumpOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        Ok(Self {
+            join_build_side: options
+                .get_optional("JOIN_BUILD_SIDE", |side_str| side_str.parse())?
+                .unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::OptionsBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_pump_options() {
+        let options = OptionsBuilder::default().build();
+        assert_eq!(
+            PumpOptions::try_from(&options).unwrap().join_build_side,
+            JoinBuildSide::Auto
+        );
+
+        let options = OptionsBuilder::default()
+            .add("JOIN_BUILD_SIDE", "LEFT")
+            .build();
+        assert_eq!(
+            PumpOptions::try_from(&options).unwrap().join_build_side,
+            JoinBuildSide::Left
+        );
+
+        let options = OptionsBuilder::default()
+            .add("JOIN_BUILD_SIDE", "SMALLER")
+            .build();
+        assert!(matches!(
+            PumpOptions::try_from(&options).unwrap_err(),
+            SpringError::InvalidOption { .. }
+        ));
+    }
+}
@@ -0,0 +1,164 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{
+        option::{ColumnTemplate, Options},
+        JsonPath,
+    },
+};
+
+/// Consumer group to read a Redis stream in.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct RedisConsumerGroup {
+    pub group: String,
+    pub consumer: String,
+}
+
+/// Reads a JSON field of entries in a Redis stream (`XREAD` or `XREADGROUP`).
+///
+/// ```text
+/// URL 'redis://127.0.0.1:6379/0',
+/// STREAM_KEY 'sensors',
+/// FIELD 'json',
+/// GROUP 'springql',
+/// CONSUMER 'gateway-1',
+/// START_ID '$',
+/// COUNT '100'
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct RedisStreamSourceOptions {
+    pub url: String,
+    pub stream_key: String,
+    /// Field whose value is a JSON row (or rows). `json` by default.
+    pub field: String,
+    /// Reads in a consumer group (created if not exist) and acknowledges read entries if set.
+    pub consumer_group: Option<RedisConsumerGroup>,
+    /// Entry ID to start reading after (`$` by default: only new entries; `0`: all entries).
+    /// For consumer groups, used only on creating the group.
+    pub start_id: String,
+    /// Maximum entries per read. 100 by default.
+    pub count: usize,
+    /// Selects a JSON array (or a JSON object) of rows in each field value.
+    pub json_records_path: Option<JsonPath>,
+}
+
+impl TryFrom<&Options> for RedisStreamSourceOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        let group = options.get_optional("GROUP", |s| Ok(s.to_string()))?;
+        let consumer = options.get_optional("CONSUMER", |s| Ok(s.to_string()))?;
+        let consumer_group = match (group, consumer) {
+            (Some(group), Some(consumer)) => Some(RedisConsumerGroup { group, consumer }),
+            (None, None) => None,
+            (Some(_), None) | (None, Some(_)) => {
+                return Err(SpringError::InvalidOption {
+                    key: "GROUP".to_string(),
+                    value: "(not found)".to_string(),
+                    source: anyhow!("GROUP and CONSUMER must be set together"),
+                })
+            }
+        };
+
+        Ok(Self {
+            url: options.get("URL", |s| Ok(s.to_string()))?,
+            stream_key: options.get("STREAM_KEY", |s| Ok(s.to_string()))?,
+            field: parse_field(options)?,
+            consumer_group,
+            start_id: options
+                .get_optional("START_ID", |s| Ok(s.to_string()))?
+                .unwrap_or_else(|| "$".to_string()),
+            count: options
+                .get_optional("COUNT", |count_str| {
+                    let count: usize = count_str.parse().context("invalid COUNT")?;
+                    anyhow::ensure!(count > 0, "COUNT must be positive");
+                    Ok(count)
+                })?
+                .unwrap_or(100),
+            json_records_path: options.get_optional("JSON_RECORDS_PATH", |path_str| {
+                path_str.parse::<JsonPath>().context("invalid JSONPath")
+            })?,
+        })
+    }
+}
+
+/// Appends a row as a JSON field of an entry to a Redis stream (`XADD`).
+///
+/// ```text
+/// URL 'redis://127.0.0.1:6379/0',
+/// STREAM_KEY 'alerts/${site}',
+/// FIELD 'json',
+/// MAXLEN '10000'
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct RedisStreamSinkOptions {
+    pub url: String,
+    /// May contain `${column}` placeholders.
+    pub stream_key: ColumnTemplate,
+    /// `json` by default.
+    pub field: String,
+    /// Trims the stream to approximately this number of entries (`MAXLEN ~`).
+    pub max_len: Option<usize>,
+}
+
+impl TryFrom<&Options> for RedisStreamSinkOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        Ok(Self {
+            url: options.get("URL", |s| Ok(s.to_string()))?,
+            stream_key: options.get("STREAM_KEY", |key_str| {
+                Ok(ColumnTemplate::from_str(key_str)?)
+            })?,
+            field: parse_field(options)?,
+            max_len: options.get_optional("MAXLEN", |max_len_str| {
+                max_len_str.parse().context("invalid MAXLEN")
+            })?,
+        })
+    }
+}
+
+fn parse_field(options: &Options) -> Result<String> {
+    Ok(options
+        .get_optional("FIELD", |s| Ok(s.to_string()))?
+        .unwrap_or_else(|| "json".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::OptionsBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_redis_stream_source_options() {
+        let options = OptionsBuilder::default()
+            .add("URL", "redis://127.0.0.1:6379")
+            .add("STREAM_KEY", "sensors")
+            .add("GROUP", "springql")
+            .add("CONSUMER", "gateway-1")
+            .build();
+        let options = RedisStreamSourceOptions::try_from(&options).unwrap();
+        assert_eq!(
+            options.consumer_group,
+            Some(RedisConsumerGroup {
+                group: "springql".to_string(),
+                consumer: "gateway-1".to_string()
+            })
+        );
+        assert_eq!(options.field, "json");
+        assert_eq!(options.start_id, "$");
+
+        let options = OptionsBuilder::default()
+            .add("URL", "redis://127.0.0.1:6379")
+            .add("STREAM_KEY", "sensors")
+            .add("GROUP", "springql")
+            .build();
+        assert!(RedisStreamSourceOptions::try_from(&options).is_err());
+    }
+}
@@ -0,0 +1,234 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context};
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{option::Options, ColumnName},
+};
+
+/// Primitive types of ROS2 message fields (IDL names).
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Ros2FieldType {
+    Bool,
+    Byte,
+    Int8,
+    Uint8,
+    Int16,
+    Uint16,
+    Int32,
+    Uint32,
+    Int64,
+    Uint64,
+    Float32,
+    Float64,
+    String,
+}
+
+impl FromStr for Ros2FieldType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let typ = match s {
+            "bool" => Self::Bool,
+            "byte" => Self::Byte,
+            "int8" => Self::Int8,
+            "uint8" | "char" => Self::Uint8,
+            "int16" => Self::Int16,
+            "uint16" => Self::Uint16,
+            "int32" => Self::Int32,
+            "uint32" => Self::Uint32,
+            "int64" => Self::Int64,
+            "uint64" => Self::Uint64,
+            "float32" => Self::Float32,
+            "float64" => Self::Float64,
+            "string" => Self::String,
+            _ => bail!("unsupported ROS2 field type: {}", s),
+        };
+        Ok(typ)
+    }
+}
+
+/// A field in the flattened layout of a ROS2 message.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Ros2Field {
+    /// Dot-separated path (e.g. `header.stamp.sec`).
+    pub path: String,
+    pub field_type: Ros2FieldType,
+}
+
+/// Reliability QoS of the subscription.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Ros2Reliability {
+    /// Compatible with both reliable and best-effort publishers.
+    BestEffort,
+    Reliable,
+}
+
+impl FromStr for Ros2Reliability {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "BEST_EFFORT" => Ok(Self::BestEffort),
+            "RELIABLE" => Ok(Self::Reliable),
+            _ => Err(anyhow!("RELIABILITY must be BEST_EFFORT or RELIABLE")),
+        }
+    }
+}
+
+/// Subscribes to a ROS2 topic over DDS and maps message fields to columns.
+///
+/// Messages are decoded by `LAYOUT`: every field of the message type, flattened in declaration order.
+/// Arrays and sequences are not supported.
+///
+/// ```text
+/// DOMAIN_ID '0',
+/// TOPIC '/robot1/battery',
+/// MESSAGE_TYPE 'my_msgs/msg/Battery',
+/// LAYOUT 'header.stamp.sec:int32,header.stamp.nanosec:uint32,header.frame_id:string,voltage:float32,charging:bool',
+/// FIELD_<column> 'voltage',
+/// RELIABILITY 'BEST_EFFORT'
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Ros2Options {
+    /// 0 by default.
+    pub domain_id: u16,
+    /// ROS2 topic name (e.g. `/chatter`).
+    pub topic: String,
+    /// ROS2 message type (e.g. `std_msgs/msg/String`).
+    pub message_type: String,
+    pub layout: Vec<Ros2Field>,
+    /// (column, field path) in the order of columns.
+    pub fields: Vec<(ColumnName, String)>,
+    /// BEST_EFFORT by default.
+    pub reliability: Ros2Reliability,
+}
+
+impl Ros2Options {
+    /// DDS topic name of the ROS2 topic (`/chatter` -> `rt/chatter`).
+    pub fn dds_topic_name(&self) -> String {
+        format!("rt/{}", self.topic.trim_start_matches('/'))
+    }
+
+    /// DDS type name of the ROS2 message type (`std_msgs/msg/String` -> `std_msgs::msg::dds_::String_`).
+    pub fn dds_type_name(&self) -> String {
+        let mut parts: Vec<&str> = self.message_type.split('/').collect();
+        let name = parts.pop().unwrap_or_default();
+        format!("{}::dds_::{}_", parts.join("::"), name)
+    }
+}
+
+impl TryFrom<&Options> for Ros2Options {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        let layout = options.get("LAYOUT", |layout_str| {
+            layout_str
+                .split(',')
+                .map(|field_str| {
+                    let (path, field_type) = field_str
+                        .trim()
+                        .split_once(':')
+                        .with_context(|| format!("expected `<path>:<type>`: {}", field_str))?;
+                    Ok(Ros2Field {
+                        path: path.to_string(),
+                        field_type: field_type.parse()?,
+                    })
+                })
+                .collect::<anyhow::Result<Vec<_>>>()
+        })?;
+
+        let mut fields: Vec<(ColumnName, String)> = options
+            .as_key_values()
+            .into_iter()
+            .filter_map(|(key, value)| {
+                let column = ColumnName::new(key.strip_prefix("FIELD_")?.to_string());
+                Some((key, column, value))
+            })
+            .map(|(key, column, path)| {
+                if layout.iter().any(|field| field.path == path) {
+                    Ok((column, path.to_string()))
+                } else {
+                    Err(SpringError::InvalidOption {
+                        key: key.to_string(),
+                        value: path.to_string(),
+                        source: anyhow!("field not found in LAYOUT"),
+                    })
+                }
+            })
+            .collect::<Result<_>>()?;
+        fields.sort();
+        if fields.is_empty() {
+            return Err(SpringError::InvalidOption {
+                key: "FIELD_<column>".to_string(),
+                value: "(not found)".to_string(),
+                source: anyhow!("at least one field must be mapped to a column"),
+            });
+        }
+
+        Ok(Self {
+            domain_id: options
+                .get_optional("DOMAIN_ID", |domain_id_str| {
+                    domain_id_str.parse().context("invalid DOMAIN_ID")
+                })?
+                .unwrap_or(0),
+            topic: options.get("TOPIC", |s| Ok(s.to_string()))?,
+            message_type: options.get("MESSAGE_TYPE", |message_type_str| {
+                anyhow::ensure!(
+                    message_type_str.split('/').count() == 3,
+                    "expected `<package>/msg/<name>`"
+                );
+                Ok(message_type_str.to_string())
+            })?,
+            layout,
+            fields,
+            reliability: options
+                .get_optional("RELIABILITY", |s| s.parse())?
+                .unwrap_or(Ros2Reliability::BestEffort),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::OptionsBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_ros2_options() {
+        let options = OptionsBuilder::default()
+            .add("TOPIC", "/robot1/battery")
+            .add("MESSAGE_TYPE", "my_msgs/msg/Battery")
+            .add("LAYOUT", "frame_id:string, voltage:float32")
+            .add("FIELD_v", "voltage")
+            .build();
+        let options = Ros2Options::try_from(&options).unwrap();
+        assert_eq!(
+            options.layout,
+            vec![
+                Ros2Field {
+                    path: "frame_id".to_string(),
+                    field_type: Ros2FieldType::String
+                },
+                Ros2Field {
+                    path: "voltage".to_string(),
+                    field_type: Ros2FieldType::Float32
+                },
+            ]
+        );
+        assert_eq!(options.dds_topic_name(), "rt/robot1/battery");
+        assert_eq!(options.dds_type_name(), "my_msgs::msg::dds_::Battery_");
+
+        let options = OptionsBuilder::default()
+            .add("TOPIC", "/robot1/battery")
+            .add("MESSAGE_TYPE", "my_msgs/msg/Battery")
+            .add("LAYOUT", "voltage:float32")
+            .add("FIELD_v", "current")
+            .build();
+        assert!(Ros2Options::try_from(&options).is_err());
+    }
+}
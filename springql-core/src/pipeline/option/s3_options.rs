@@ -0,0 +1,187 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{path::PathBuf, str::FromStr, time::Duration};
+
+use anyhow::{anyhow, ensure, Context};
+use reqwest::Url;
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::option::Options,
+};
+
+/// Format of uploaded objects.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum S3ObjectFormat {
+    /// A JSON object per line.
+    JsonLines,
+    /// A Parquet file (SNAPPY compressed). Requires `sink-parquet` feature.
+    Parquet,
+}
+
+impl FromStr for S3ObjectFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "JSONL" => Ok(Self::JsonLines),
+            "PARQUET" => Ok(Self::Parquet),
+            _ => Err(anyhow!("FORMAT must be JSONL or PARQUET")),
+        }
+    }
+}
+
+/// Batches rows into objects and uploads them to S3-compatible storage.
+///
+/// ```text
+/// ENDPOINT 'https://s3.ap-northeast-1.amazonaws.com',
+/// REGION 'ap-northeast-1',
+/// BUCKET 'vehicle-logs',
+/// KEY_PREFIX 'car-0001/',
+/// ACCESS_KEY_ID '...',
+/// SECRET_ACCESS_KEY '...',
+/// FORMAT 'JSONL',
+/// GZIP 'TRUE',
+/// BATCH_SIZE '10000',
+/// BATCH_INTERVAL_SEC '300',
+/// SPOOL_DIRECTORY '/var/spool/springql/s3',
+/// MAX_SPOOL_BYTES '1073741824'
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct S3Options {
+    /// Objects are put to `<ENDPOINT>/<BUCKET>/<KEY_PREFIX><object name>` (path-style).
+    pub endpoint: Url,
+    pub region: String,
+    pub bucket: String,
+    /// Empty by default.
+    pub key_prefix: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    /// JSONL by default.
+    pub format: S3ObjectFormat,
+    /// Gzip JSON lines objects. TRUE by default. Ignored for Parquet objects.
+    pub gzip: bool,
+    /// Rows in an object. 1000 by default.
+    pub batch_size: usize,
+    /// An object is closed when this time has passed since its first row. Not closed by time if not set.
+    pub batch_interval: Option<Duration>,
+    /// Objects are written here before uploaded and kept until uploaded. Must exist.
+    pub spool_directory: PathBuf,
+    /// The oldest spooled objects are deleted to keep the spool under this size. 256 MiB by default.
+    pub max_spool_bytes: u64,
+    /// Upper bound of the exponential backoff between upload attempts while offline. 300 seconds by default.
+    pub max_retry_interval: Duration,
+    /// Timeout of a PUT request. 60 seconds by default.
+    pub upload_timeout: Duration,
+}
+
+impl TryFrom<&Options> for S3Options {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        let string = |key: &str| options.get(key, |s| Ok(s.to_string()));
+
+        Ok(Self {
+            endpoint: options.get("ENDPOINT", |endpoint_str| {
+                let endpoint = Url::parse(endpoint_str).context("invalid endpoint URL")?;
+                ensure!(
+                    matches!(endpoint.scheme(), "http" | "https") && endpoint.has_host(),
+                    "ENDPOINT must be an http(s) URL"
+                );
+                Ok(endpoint)
+            })?,
+            region: string("REGION")?,
+            bucket: string("BUCKET")?,
+            key_prefix: options
+                .get_optional("KEY_PREFIX", |key_prefix_str| {
+                    ensure!(
+                        !key_prefix_str.starts_with('/'),
+                        "KEY_PREFIX must not start with '/'"
+                    );
+                    Ok(key_prefix_str.to_string())
+                })?
+                .unwrap_or_default(),
+            access_key_id: string("ACCESS_KEY_ID")?,
+            secret_access_key: string("SECRET_ACCESS_KEY")?,
+            format: options
+                .get_optional("FORMAT", |s| s.parse())?
+                .unwrap_or(S3ObjectFormat::JsonLines),
+            gzip: options
+                .get_optional("GZIP", |gzip_str| match gzip_str.as_str() {
+                    "TRUE" => Ok(true),
+                    "FALSE" => Ok(false),
+                    _ => Err(anyhow!("GZIP must be TRUE or FALSE")),
+                })?
+                .unwrap_or(true),
+            batch_size: options
+                .get_optional("BATCH_SIZE", |batch_size_str| {
+                    let batch_size: usize = batch_size_str.parse().context("invalid batch size")?;
+                    ensure!(batch_size > 0, "BATCH_SIZE must be positive");
+                    Ok(batch_size)
+                })?
+                .unwrap_or(1000),
+            batch_interval: options.get_optional("BATCH_INTERVAL_SEC", |interval_str| {
+                let secs: u64 = interval_str.parse().context("invalid batch interval")?;
+                ensure!(secs > 0, "BATCH_INTERVAL_SEC must be positive");
+                Ok(Duration::from_secs(secs))
+            })?,
+            spool_directory: options.get("SPOOL_DIRECTORY", |directory_str| {
+                Ok(PathBuf::from(directory_str))
+            })?,
+            max_spool_bytes: options
+                .get_optional("MAX_SPOOL_BYTES", |max_spool_str| {
+                    max_spool_str.parse().context("invalid max spool size")
+                })?
+                .unwrap_or(256 * 1024 * 1024),
+            max_retry_interval: options
+                .get_optional("MAX_RETRY_INTERVAL_SEC", |interval_str| {
+                    let secs: u64 = interval_str.parse().context("invalid retry interval")?;
+                    Ok(Duration::from_secs(secs))
+                })?
+                .unwrap_or(Duration::from_secs(300)),
+            upload_timeout: options
+                .get_optional("UPLOAD_TIMEOUT_SEC", |timeout_str| {
+                    let secs: u64 = timeout_str.parse().context("invalid upload timeout")?;
+                    ensure!(secs > 0, "UPLOAD_TIMEOUT_SEC must be positive");
+                    Ok(Duration::from_secs(secs))
+                })?
+                .unwrap_or(Duration::from_secs(60)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::OptionsBuilder;
+
+    use super::*;
+
+    fn builder() -> OptionsBuilder {
+        OptionsBuilder::default()
+            .add("ENDPOINT", "http://127.0.0.1:9000")
+            .add("REGION", "us-east-1")
+            .add("BUCKET", "logs")
+            .add("ACCESS_KEY_ID", "minio")
+            .add("SECRET_ACCESS_KEY", "minio123")
+            .add("SPOOL_DIRECTORY", "/tmp/springql")
+    }
+
+    #[test]
+    fn test_s3_options() {
+        let options = S3Options::try_from(&builder().build()).unwrap();
+        assert_eq!(options.key_prefix, "");
+        assert_eq!(options.format, S3ObjectFormat::JsonLines);
+        assert!(options.gzip);
+        assert_eq!(options.batch_size, 1000);
+        assert_eq!(options.batch_interval, None);
+
+        let options = builder().add("ENDPOINT", "s3.amazonaws.com").build();
+        assert!(S3Options::try_from(&options).is_err());
+
+        let options = builder().add("KEY_PREFIX", "/car-0001/").build();
+        assert!(S3Options::try_from(&options).is_err());
+
+        let options = builder().add("FORMAT", "CSV").build();
+        assert!(S3Options::try_from(&options).is_err());
+    }
+}
@@ -0,0 +1,69 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use anyhow::anyhow;
+
+#[cfg(feature = "avro")]
+use crate::pipeline::AvroOptions;
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::option::Options,
+};
+
+/// Format of payloads to a sink. Fields are shaped by [JsonShapeOptions](crate::pipeline::JsonShapeOptions) in any format.
+///
+/// ```text
+/// FORMAT 'AVRO',
+/// AVRO_SCHEMA '{"type": "record", "name": "trade", "fields": [...]}'
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum SinkFormatOptions {
+    /// Default.
+    Json,
+    #[cfg(feature = "avro")]
+    Avro(AvroOptions),
+}
+
+impl TryFrom<&Options> for SinkFormatOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        let format = options.get_optional("FORMAT", |format_str| match format_str {
+            "JSON" => Ok(format_str.to_string()),
+            "AVRO" if cfg!(feature = "avro") => Ok(format_str.to_string()),
+            "AVRO" => Err(anyhow!("FORMAT 'AVRO' requires `avro` feature")),
+            _ => Err(anyhow!("FORMAT must be JSON or AVRO")),
+        })?;
+        match format.as_deref() {
+            #[cfg(feature = "avro")]
+            Some("AVRO") => {
+                let avro_options = AvroOptions::try_from(options)?;
+                avro_options.validate_for_sink()?;
+                Ok(Self::Avro(avro_options))
+            }
+            _ => Ok(Self::Json),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::OptionsBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_sink_format_options() {
+        let options = OptionsBuilder::default().build();
+        assert_eq!(
+            SinkFormatOptions::try_from(&options).unwrap(),
+            SinkFormatOptions::Json
+        );
+
+        let options = OptionsBuilder::default().add("FORMAT", "CSV").build();
+        assert!(SinkFormatOptions::try_from(&options).is_err());
+
+        // AVRO_SCHEMA is required (and `avro` feature)
+        let options = OptionsBuilder::default().add("FORMAT", "AVRO").build();
+        assert!(SinkFormatOptions::try_from(&options).is_err());
+    }
+}
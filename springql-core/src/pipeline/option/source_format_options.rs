@@ -0,0 +1,216 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use anyhow::anyhow;
+
+#[cfg(feature = "avro")]
+use crate::pipeline::AvroOptions;
+#[cfg(feature = "protobuf")]
+use crate::pipeline::ProtobufOptions;
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{option::Options, ColumnName},
+};
+
+/// CSV records separated by newlines.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct CsvOptions {
+    /// Whether the first record is a header with column names. true by default.
+    pub header: bool,
+    /// ',' by default.
+    pub delimiter: char,
+    /// Column names of fields. Overrides the header if both are given.
+    pub columns: Option<Vec<ColumnName>>,
+}
+
+/// Format of payloads from a source.
+///
+/// ```text
+/// FORMAT 'CSV',
+/// CSV_HEADER 'false',
+/// CSV_COLUMNS 'ts,sensor,temperature',
+/// CSV_DELIMITER ';'
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum SourceFormatOptions {
+    /// Default.
+    Json,
+    Csv(CsvOptions),
+    /// Binary payloads. Not for source readers reading text lines.
+    #[cfg(feature = "avro")]
+    Avro(AvroOptions),
+    /// Binary payloads. Not for source readers reading text lines.
+    #[cfg(feature = "protobuf")]
+    Protobuf(ProtobufOptions),
+}
+
+impl SourceFormatOptions {
+    pub fn csv(&self) -> Option<&CsvOptions> {
+        match self {
+            Self::Csv(csv_options) => Some(csv_options),
+            _ => None,
+        }
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidOption` when:
+    ///   - the format is binary (`reader_type` reads text).
+    #[cfg_attr(
+        not(any(feature = "avro", feature = "protobuf")),
+        allow(unused_variables)
+    )]
+    pub fn ensure_text(&self, reader_type: &str) -> Result<()> {
+        match self {
+            Self::Json | Self::Csv(_) => Ok(()),
+            #[cfg(feature = "avro")]
+            Self::Avro(_) => Err(binary_format_error("AVRO", reader_type)),
+            #[cfg(feature = "protobuf")]
+            Self::Protobuf(_) => Err(binary_format_error("PROTOBUF", reader_type)),
+        }
+    }
+}
+
+#[cfg(any(feature = "avro", feature = "protobuf"))]
+fn binary_format_error(format: &str, reader_type: &str) -> SpringError {
+    SpringError::InvalidOption {
+        key: "FORMAT".to_string(),
+        value: format.to_string(),
+        source: anyhow!(
+            "{} source reader does not support binary formats",
+            reader_type
+        ),
+    }
+}
+
+impl TryFrom<&Options> for SourceFormatOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        let format = options.get_optional("FORMAT", |format_str| match format_str {
+            "JSON" | "CSV" => Ok(format_str.to_string()),
+            "AVRO" if cfg!(feature = "avro") => Ok(format_str.to_string()),
+            "AVRO" => Err(anyhow!("FORMAT 'AVRO' requires `avro` feature")),
+            "PROTOBUF" if cfg!(feature = "protobuf") => Ok(format_str.to_string()),
+            "PROTOBUF" => Err(anyhow!("FORMAT 'PROTOBUF' requires `protobuf` feature")),
+            _ => Err(anyhow!("FORMAT must be JSON, CSV, AVRO or PROTOBUF")),
+        })?;
+        match format.as_deref() {
+            Some("CSV") => {}
+            #[cfg(feature = "avro")]
+            Some("AVRO") => return Ok(Self::Avro(AvroOptions::try_from(options)?)),
+            #[cfg(feature = "protobuf")]
+            Some("PROTOBUF") => return Ok(Self::Protobuf(ProtobufOptions::try_from(options)?)),
+            _ => return Ok(Self::Json),
+        }
+
+        let header = options
+            .get_optional("CSV_HEADER", |header_str| {
+                header_str
+                    .parse::<bool>()
+                    .map_err(|_| anyhow!("CSV_HEADER must be true or false"))
+            })?
+            .unwrap_or(true);
+        let delimiter = options
+            .get_optional("CSV_DELIMITER", |delimiter_str| {
+                let mut chars = delimiter_str.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if !matches!(c, '"' | '\r' | '\n') => Ok(c),
+                    _ => Err(anyhow!(
+                        "CSV_DELIMITER must be a character other than quote and newlines"
+                    )),
+                }
+            })?
+            .unwrap_or(',');
+        let columns = options.get_optional("CSV_COLUMNS", |columns_str| {
+            let columns = columns_str
+                .split(',')
+                .map(|column| column.trim())
+                .collect::<Vec<_>>();
+            anyhow::ensure!(
+                columns.iter().all(|column| !column.is_empty()),
+                "CSV_COLUMNS must be comma-separated column names"
+            );
+            Ok(columns
+                .into_iter()
+                .map(|column| ColumnName::new(column.to_string()))
+                .collect())
+        })?;
+
+        if !header && columns.is_none() {
+            return Err(SpringError::InvalidOption {
+                key: "CSV_COLUMNS".to_string(),
+                value: "".to_string(),
+                source: anyhow!("CSV_COLUMNS is required if CSV_HEADER is false"),
+            });
+        }
+
+        Ok(Self::Csv(CsvOptions {
+            header,
+            delimiter,
+            columns,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::OptionsBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_source_format_options() {
+        let options = OptionsBuilder::default().build();
+        assert_eq!(
+            SourceFormatOptions::try_from(&options).unwrap(),
+            SourceFormatOptions::Json
+        );
+
+        let options = OptionsBuilder::default().add("FORMAT", "CSV").build();
+        assert_eq!(
+            SourceFormatOptions::try_from(&options).unwrap(),
+            SourceFormatOptions::Csv(CsvOptions {
+                header: true,
+                delimiter: ',',
+                columns: None
+            })
+        );
+
+        let options = OptionsBuilder::default()
+            .add("FORMAT", "CSV")
+            .add("CSV_HEADER", "false")
+            .add("CSV_DELIMITER", "\t")
+            .add("CSV_COLUMNS", "ts, temperature")
+            .build();
+        let options = SourceFormatOptions::try_from(&options).unwrap();
+        let csv_options = options.csv().unwrap();
+        assert_eq!(csv_options.delimiter, '\t');
+        assert_eq!(
+            csv_options.columns,
+            Some(vec![
+                ColumnName::new("ts".to_string()),
+                ColumnName::new("temperature".to_string())
+            ])
+        );
+
+        // column names are unknown
+        let options = OptionsBuilder::default()
+            .add("FORMAT", "CSV")
+            .add("CSV_HEADER", "false")
+            .build();
+        assert!(SourceFormatOptions::try_from(&options).is_err());
+
+        let options = OptionsBuilder::default()
+            .add("FORMAT", "CSV")
+            .add("CSV_DELIMITER", ";;")
+            .build();
+        assert!(SourceFormatOptions::try_from(&options).is_err());
+
+        let options = OptionsBuilder::default().add("FORMAT", "XML").build();
+        assert!(SourceFormatOptions::try_from(&options).is_err());
+
+        // PROTO_DESCRIPTOR_FILE and PROTO_MESSAGE are required (and `protobuf` feature)
+        let options = OptionsBuilder::default().add("FORMAT", "PROTOBUF").build();
+        assert!(SourceFormatOptions::try_from(&options).is_err());
+    }
+}
@@ -0,0 +1,98 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::collections::BTreeMap;
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{option::Options, ColumnName},
+};
+
+/// Maps transport metadata (topic, partition, key, QoS, headers) of a source into stream columns.
+///
+/// ```text
+/// MAP_TOPIC_TO 'topic_col',
+/// MAP_PARTITION_TO 'partition_col',
+/// MAP_KEY_TO 'key_col',
+/// MAP_QOS_TO 'qos_col',
+/// MAP_HEADER_<header name>_TO 'header_col'
+/// ```
+///
+/// Source readers which do not have a kind of metadata put NULL to the mapped column.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct SourceMetadataOptions {
+    pub topic_column: Option<ColumnName>,
+    pub partition_column: Option<ColumnName>,
+    pub key_column: Option<ColumnName>,
+    pub qos_column: Option<ColumnName>,
+
+    /// header name -> column
+    pub header_columns: BTreeMap<String, ColumnName>,
+}
+
+impl SourceMetadataOptions {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+impl TryFrom<&Options> for SourceMetadataOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        let column = |key: &str| {
+            options.get_optional(key, |column_str| {
+                Ok(ColumnName::new(column_str.to_string()))
+            })
+        };
+
+        let header_columns = options
+            .as_key_values()
+            .into_iter()
+            .filter_map(|(key, value)| {
+                key.strip_prefix("MAP_HEADER_")
+                    .and_then(|k| k.strip_suffix("_TO"))
+                    .map(|header| (header.to_string(), ColumnName::new(value.to_string())))
+            })
+            .collect();
+
+        Ok(Self {
+            topic_column: column("MAP_TOPIC_TO")?,
+            partition_column: column("MAP_PARTITION_TO")?,
+            key_column: column("MAP_KEY_TO")?,
+            qos_column: column("MAP_QOS_TO")?,
+            header_columns,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::OptionsBuilder;
+
+    #[test]
+    fn test_source_metadata_options() {
+        let options = OptionsBuilder::default()
+            .add("NAME", "q")
+            .add("MAP_TOPIC_TO", "topic")
+            .add("MAP_HEADER_Content-Type_TO", "content_type")
+            .build();
+        let metadata_options = SourceMetadataOptions::try_from(&options).unwrap();
+
+        assert_eq!(
+            metadata_options.topic_column,
+            Some(ColumnName::new("topic".to_string()))
+        );
+        assert_eq!(metadata_options.partition_column, None);
+        assert_eq!(
+            metadata_options.header_columns.get("Content-Type"),
+            Some(&ColumnName::new("content_type".to_string()))
+        );
+        assert!(!metadata_options.is_empty());
+
+        let options = OptionsBuilder::default().add("NAME", "q").build();
+        assert!(SourceMetadataOptions::try_from(&options)
+            .unwrap()
+            .is_empty());
+    }
+}
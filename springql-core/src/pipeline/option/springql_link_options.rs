@@ -0,0 +1,65 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::net::{IpAddr, Ipv4Addr};
+
+use anyhow::Context;
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::option::Options,
+};
+
+/// Listens for rows from `SPRINGQL_LINK` sink writers of other SpringQL instances.
+///
+/// ```text
+/// LISTEN_HOST '0.0.0.0',
+/// PORT '19870'
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct SpringqlLinkSourceOptions {
+    /// 0.0.0.0 by default.
+    pub listen_host: IpAddr,
+    pub port: u16,
+}
+
+impl TryFrom<&Options> for SpringqlLinkSourceOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        Ok(Self {
+            listen_host: options
+                .get_optional("LISTEN_HOST", |listen_host_str| {
+                    listen_host_str.parse().context("invalid listen host")
+                })?
+                .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED)),
+            port: options.get("PORT", |port_str| port_str.parse().context("invalid port"))?,
+        })
+    }
+}
+
+/// Sends rows to a `SPRINGQL_LINK` source reader of another SpringQL instance.
+///
+/// ```text
+/// REMOTE_HOST '192.168.1.10',
+/// REMOTE_PORT '19870'
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct SpringqlLinkSinkOptions {
+    pub remote_host: IpAddr,
+    pub remote_port: u16,
+}
+
+impl TryFrom<&Options> for SpringqlLinkSinkOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        Ok(Self {
+            remote_host: options.get("REMOTE_HOST", |remote_host_str| {
+                remote_host_str.parse().context("invalid remote host")
+            })?,
+            remote_port: options.get("REMOTE_PORT", |remote_port_str| {
+                remote_port_str.parse().context("invalid remote port")
+            })?,
+        })
+    }
+}
@@ -0,0 +1,92 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::net::{IpAddr, Ipv4Addr};
+
+use anyhow::Context;
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{option::Options, JsonPath},
+};
+
+/// Maximum payload size of a UDP datagram over IPv4.
+const DEFAULT_MAX_DATAGRAM_SIZE: usize = 65507;
+
+/// Receives UDP datagrams (including broadcasts) with a row (or rows in a JSON array) per datagram.
+///
+/// ```text
+/// PORT '17000',
+/// BIND_HOST '0.0.0.0',
+/// MAX_DATAGRAM_SIZE '1024'
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct UdpSourceOptions {
+    /// 0.0.0.0 (all interfaces) by default.
+    pub bind_host: IpAddr,
+    pub port: u16,
+    /// Datagrams larger than this are dropped. 65507 bytes by default.
+    pub max_datagram_size: usize,
+
+    /// Selects a JSON array (or a JSON object) of rows in each payload.
+    pub json_records_path: Option<JsonPath>,
+}
+
+impl TryFrom<&Options> for UdpSourceOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        Ok(Self {
+            bind_host: options
+                .get_optional("BIND_HOST", |bind_host_str| {
+                    bind_host_str.parse().context("invalid bind host")
+                })?
+                .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED)),
+            port: options.get("PORT", |port_str| port_str.parse().context("invalid port"))?,
+            max_datagram_size: options
+                .get_optional("MAX_DATAGRAM_SIZE", |size_str| {
+                    let size: usize = size_str.parse().context("invalid max datagram size")?;
+                    anyhow::ensure!(
+                        (1..=DEFAULT_MAX_DATAGRAM_SIZE).contains(&size),
+                        "MAX_DATAGRAM_SIZE must be in 1..={}",
+                        DEFAULT_MAX_DATAGRAM_SIZE
+                    );
+                    Ok(size)
+                })?
+                .unwrap_or(DEFAULT_MAX_DATAGRAM_SIZE),
+            json_records_path: options.get_optional("JSON_RECORDS_PATH", |path_str| {
+                path_str.parse::<JsonPath>().context("invalid JSONPath")
+            })?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::OptionsBuilder;
+
+    #[test]
+    fn test_udp_source_options() {
+        let options = OptionsBuilder::default().add("PORT", "17000").build();
+        let options = UdpSourceOptions::try_from(&options).unwrap();
+        assert_eq!(options.bind_host, IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+        assert_eq!(options.max_datagram_size, DEFAULT_MAX_DATAGRAM_SIZE);
+
+        let options = OptionsBuilder::default()
+            .add("PORT", "17000")
+            .add("BIND_HOST", "127.0.0.1")
+            .add("MAX_DATAGRAM_SIZE", "1024")
+            .build();
+        let options = UdpSourceOptions::try_from(&options).unwrap();
+        assert_eq!(options.bind_host, IpAddr::V4(Ipv4Addr::LOCALHOST));
+        assert_eq!(options.max_datagram_size, 1024);
+
+        for size in ["0", "65508"] {
+            let options = OptionsBuilder::default()
+                .add("PORT", "17000")
+                .add("MAX_DATAGRAM_SIZE", size)
+                .build();
+            assert!(UdpSourceOptions::try_from(&options).is_err());
+        }
+    }
+}
@@ -0,0 +1,33 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{option::Options, JsonPath},
+};
+
+/// Options of `UNIX_SOCKET` source reader and sink writer, connecting to a UNIX domain socket another process listens on.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct UnixSocketOptions {
+    /// Path of the socket file.
+    pub path: PathBuf,
+
+    /// Selects a JSON array (or a JSON object) of rows in each payload.
+    pub json_records_path: Option<JsonPath>,
+}
+
+impl TryFrom<&Options> for UnixSocketOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        Ok(Self {
+            path: options.get("PATH", |path_str| Ok(PathBuf::from(path_str)))?,
+            json_records_path: options.get_optional("JSON_RECORDS_PATH", |path_str| {
+                path_str.parse::<JsonPath>().context("invalid JSONPath")
+            })?,
+        })
+    }
+}
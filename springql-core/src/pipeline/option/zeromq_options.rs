@@ -0,0 +1,201 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{str::FromStr, time::Duration};
+
+use anyhow::{anyhow, Context};
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{
+        option::{ColumnTemplate, Options},
+        JsonPath,
+    },
+};
+
+/// Either connects to peers or binds to a local endpoint.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum ZeroMqEndpoint {
+    /// `CONNECT 'tcp://192.168.0.10:5556,tcp://192.168.0.11:5556'`
+    Connect(Vec<String>),
+    /// `BIND 'tcp://*:5556'`
+    Bind(String),
+}
+
+impl ZeroMqEndpoint {
+    fn parse(options: &Options) -> Result<Self> {
+        let connect = options.get_optional("CONNECT", |endpoints_str| {
+            Ok(endpoints_str
+                .split(',')
+                .map(|endpoint| endpoint.trim().to_string())
+                .collect())
+        })?;
+        let bind = options.get_optional("BIND", |s| Ok(s.to_string()))?;
+
+        match (connect, bind) {
+            (Some(endpoints), None) => Ok(Self::Connect(endpoints)),
+            (None, Some(endpoint)) => Ok(Self::Bind(endpoint)),
+            (connect, bind) => Err(SpringError::InvalidOption {
+                key: "CONNECT".to_string(),
+                value: format!("{:?} (BIND {:?})", connect, bind),
+                source: anyhow!("either CONNECT or BIND must be set"),
+            }),
+        }
+    }
+}
+
+/// Socket options common to source readers and sink writers. libzmq defaults if not set.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct ZeroMqSocketOptions {
+    /// High water mark (messages queued per peer).
+    pub hwm: Option<i32>,
+    pub reconnect_interval: Option<Duration>,
+    /// How long pending messages are kept on close.
+    pub linger: Option<Duration>,
+}
+
+impl TryFrom<&Options> for ZeroMqSocketOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        let msec = |key: &'static str| {
+            options.get_optional(key, |msec_str| {
+                let msec: u64 = msec_str
+                    .parse()
+                    .with_context(|| format!("invalid {}", key))?;
+                Ok(Duration::from_millis(msec))
+            })
+        };
+
+        Ok(Self {
+            hwm: options.get_optional("HWM", |hwm_str| hwm_str.parse().context("invalid HWM"))?,
+            reconnect_interval: msec("RECONNECT_INTERVAL_MSEC")?,
+            linger: msec("LINGER_MSEC")?,
+        })
+    }
+}
+
+/// Subscribes to topics with a SUB socket. Messages are JSON rows, optionally preceded by a topic frame.
+///
+/// ```text
+/// CONNECT 'tcp://192.168.0.10:5556',
+/// TOPICS 'sensors/,alarms/',
+/// HWM '1000',
+/// RECONNECT_INTERVAL_MSEC '100'
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ZeroMqSourceOptions {
+    pub endpoint: ZeroMqEndpoint,
+    /// Topic prefixes to subscribe to (comma-separated). All messages if not set.
+    pub topics: Vec<String>,
+    pub socket_options: ZeroMqSocketOptions,
+    /// Selects a JSON array (or a JSON object) of rows in each message.
+    pub json_records_path: Option<JsonPath>,
+}
+
+impl TryFrom<&Options> for ZeroMqSourceOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        Ok(Self {
+            endpoint: ZeroMqEndpoint::parse(options)?,
+            topics: options
+                .get_optional("TOPICS", |topics_str| {
+                    Ok(topics_str.split(',').map(|t| t.to_string()).collect())
+                })?
+                .unwrap_or_else(|| vec!["".to_string()]),
+            socket_options: ZeroMqSocketOptions::try_from(options)?,
+            json_records_path: options.get_optional("JSON_RECORDS_PATH", |path_str| {
+                path_str.parse::<JsonPath>().context("invalid JSONPath")
+            })?,
+        })
+    }
+}
+
+/// Socket type of ZeroMQ sink writers.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ZeroMqSinkSocketType {
+    /// Fan-out to all subscribers. Messages are dropped while no subscriber is connected.
+    PUB,
+    /// Load-balanced among pullers. Blocks (until timeout) while no puller is connected.
+    PUSH,
+}
+
+impl FromStr for ZeroMqSinkSocketType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "PUB" => Ok(Self::PUB),
+            "PUSH" => Ok(Self::PUSH),
+            _ => Err(anyhow!("SOCKET_TYPE must be PUB or PUSH")),
+        }
+    }
+}
+
+/// Sends rows as JSON messages with a PUB or PUSH socket.
+///
+/// ```text
+/// SOCKET_TYPE 'PUB',
+/// BIND 'tcp://*:5557',
+/// TOPIC 'alerts/${site}',
+/// LINGER_MSEC '1000'
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ZeroMqSinkOptions {
+    /// PUB by default.
+    pub socket_type: ZeroMqSinkSocketType,
+    pub endpoint: ZeroMqEndpoint,
+    /// Sent as the first frame if set. May contain `${column}` placeholders.
+    pub topic: Option<ColumnTemplate>,
+    pub socket_options: ZeroMqSocketOptions,
+}
+
+impl TryFrom<&Options> for ZeroMqSinkOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        Ok(Self {
+            socket_type: options
+                .get_optional("SOCKET_TYPE", |s| s.parse())?
+                .unwrap_or(ZeroMqSinkSocketType::PUB),
+            endpoint: ZeroMqEndpoint::parse(options)?,
+            topic: options.get_optional("TOPIC", |topic_str| {
+                Ok(ColumnTemplate::from_str(topic_str)?)
+            })?,
+            socket_options: ZeroMqSocketOptions::try_from(options)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::OptionsBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_zeromq_source_options() {
+        let options = OptionsBuilder::default()
+            .add("CONNECT", "tcp://127.0.0.1:5556, tcp://127.0.0.1:5557")
+            .add("TOPICS", "sensors/,alarms/")
+            .add("HWM", "1000")
+            .build();
+        let options = ZeroMqSourceOptions::try_from(&options).unwrap();
+        assert_eq!(
+            options.endpoint,
+            ZeroMqEndpoint::Connect(vec![
+                "tcp://127.0.0.1:5556".to_string(),
+                "tcp://127.0.0.1:5557".to_string()
+            ])
+        );
+        assert_eq!(options.topics, vec!["sensors/", "alarms/"]);
+        assert_eq!(options.socket_options.hwm, Some(1000));
+
+        let options = OptionsBuilder::default()
+            .add("CONNECT", "tcp://127.0.0.1:5556")
+            .add("BIND", "tcp://*:5556")
+            .build();
+        assert!(ZeroMqSourceOptions::try_from(&options).is_err());
+    }
+}
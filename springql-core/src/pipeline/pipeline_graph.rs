@@ -4,29 +4,39 @@
 //!
 //! Nodes are streams and edges are one of source reader, sink writer, or pump.
 //! Some internal node may have multiple incoming edges. In this case, these edges share the same pump.
+//! A router pump has an outgoing edge to each of its downstreams, sharing the same pump.
 //!
 //! A PipelineGraph has a "virtual root stream", who has outgoing edges to all source streams, to keep source readers.
 //! It also has "virtual leaf streams", who has an incoming edge from each sink stream, to keep sink writers.
 
 mod edge;
+mod pipeline_warning;
 mod stream_node;
 
 pub use edge::Edge;
+pub use pipeline_warning::PipelineWarning;
 pub use stream_node::StreamNode;
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use anyhow::anyhow;
 use petgraph::{
     graph::{DiGraph, EdgeReference, NodeIndex},
     visit::EdgeRef,
+    EdgeDirection,
 };
 
 use crate::{
     api::error::{Result, SpringError},
     pipeline::{
-        name::StreamName, pump_model::PumpModel, sink_writer_model::SinkWriterModel,
-        source_reader_model::SourceReaderModel, stream_model::StreamModel,
+        name::{PumpName, SinkWriterName, SourceReaderName, StreamName},
+        pump_model::PumpModel,
+        sink_writer_model::SinkWriterModel,
+        source_reader_model::{SourceReaderModel, SourceReaderType},
+        stream_model::StreamModel,
     },
 };
 
@@ -34,6 +44,9 @@ use crate::{
 pub struct PipelineGraph {
     graph: DiGraph<StreamNode, Edge>,
     stream_nodes: HashMap<StreamName, NodeIndex>,
+
+    /// Set by `ALTER PUMP p STOP`
+    stopped_pumps: HashSet<PumpName>,
 }
 
 impl Default for PipelineGraph {
@@ -47,6 +60,7 @@ impl Default for PipelineGraph {
         Self {
             graph,
             stream_nodes,
+            stopped_pumps: HashSet::default(),
         }
     }
 }
@@ -81,6 +95,30 @@ impl PipelineGraph {
         upstream_edges.collect()
     }
 
+    /// Streams other than virtual ones.
+    pub fn all_streams(&self) -> Vec<&Arc<StreamModel>> {
+        self.graph
+            .node_weights()
+            .filter_map(|node| match node {
+                StreamNode::Stream(stream) => Some(stream),
+                StreamNode::VirtualRoot | StreamNode::VirtualLeaf { .. } => None,
+            })
+            .collect()
+    }
+
+    /// A pump appears once even if it has multiple edges (e.g. JOIN and ROUTER pumps).
+    pub fn all_pumps(&self) -> Vec<&Arc<PumpModel>> {
+        let mut pumps: Vec<&Arc<PumpModel>> = vec![];
+        for edge in self.graph.edge_weights() {
+            if let Edge::Pump { pump_model, .. } = edge {
+                if !pumps.iter().any(|pump| pump.name() == pump_model.name()) {
+                    pumps.push(pump_model);
+                }
+            }
+        }
+        pumps
+    }
+
     pub fn all_sources(&self) -> Vec<&SourceReaderModel> {
         self.graph
             .edge_references()
@@ -103,12 +141,21 @@ impl PipelineGraph {
     pub fn add_pump(&mut self, pump: PumpModel) -> Result<()> {
         let pump = Arc::new(pump);
 
-        let downstream_node = self.stream_nodes.get(pump.downstream()).ok_or_else(|| {
-            SpringError::Sql(anyhow!(
-                r#"downstream "{}" does not exist in pipeline"#,
-                pump.downstream()
-            ))
-        })?;
+        let downstream_nodes = pump
+            .downstreams()
+            .into_iter()
+            .map(|downstream_name| {
+                self.stream_nodes
+                    .get(downstream_name)
+                    .copied()
+                    .ok_or_else(|| {
+                        SpringError::Sql(anyhow!(
+                            r#"downstream "{}" does not exist in pipeline"#,
+                            downstream_name
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<_>>>()?;
 
         for upstream_name in pump.upstreams() {
             let upstream_node = self.stream_nodes.get(upstream_name).ok_or_else(|| {
@@ -118,19 +165,154 @@ impl PipelineGraph {
                 ))
             })?;
 
-            let _ = self.graph.add_edge(
-                *upstream_node,
-                *downstream_node,
-                Edge::Pump {
-                    pump_model: pump.clone(),
-                    upstream: upstream_name.clone(),
-                },
-            );
+            for downstream_node in &downstream_nodes {
+                let _ = self.graph.add_edge(
+                    *upstream_node,
+                    *downstream_node,
+                    Edge::Pump {
+                        pump_model: pump.clone(),
+                        upstream: upstream_name.clone(),
+                    },
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn get_pump(&self, name: &PumpName) -> Result<Arc<PumpModel>> {
+        self.graph
+            .edge_references()
+            .find_map(|edge| match edge.weight() {
+                Edge::Pump { pump_model, .. } if pump_model.name() == name => {
+                    Some(pump_model.clone())
+                }
+                _ => None,
+            })
+            .ok_or_else(|| {
+                SpringError::Sql(anyhow!(r#"pump "{}" does not exist in pipeline"#, name))
+            })
+    }
+
+    pub fn set_pump_stopped(&mut self, name: &PumpName, stopped: bool) -> Result<()> {
+        let _ = self.get_pump(name)?;
+        if stopped {
+            let _ = self.stopped_pumps.insert(name.clone());
+        } else {
+            let _ = self.stopped_pumps.remove(name);
+        }
+        Ok(())
+    }
+
+    pub fn is_pump_stopped(&self, name: &PumpName) -> bool {
+        self.stopped_pumps.contains(name)
+    }
+
+    /// Removes all edges of a pump.
+    pub fn remove_pump(&mut self, name: &PumpName) -> Result<()> {
+        let _ = self.get_pump(name)?;
+        let _ = self.stopped_pumps.remove(name);
+        self.graph.retain_edges(|graph, edge| {
+            !matches!(
+                graph.edge_weight(edge),
+                Some(Edge::Pump { pump_model, .. }) if pump_model.name() == name
+            )
+        });
+        Ok(())
+    }
+
+    /// Only a stream without any edge is removed.
+    pub fn remove_stream(&mut self, name: &StreamName) -> Result<()> {
+        let node = self._find_stream(name)?;
+        if let Some(edge) = self
+            .graph
+            .edges_directed(node, EdgeDirection::Incoming)
+            .chain(self.graph.edges_directed(node, EdgeDirection::Outgoing))
+            .next()
+        {
+            let user = match edge.weight() {
+                Edge::Pump { pump_model, .. } => format!(r#"pump "{}""#, pump_model.name()),
+                Edge::Source(source_reader) => {
+                    format!(r#"source reader "{}""#, source_reader.name())
+                }
+                Edge::Sink(sink_writer) => format!(r#"sink writer "{}""#, sink_writer.name()),
+            };
+            return Err(SpringError::Sql(anyhow!(
+                r#"stream "{}" is used by {}"#,
+                name,
+                user
+            )));
+        }
+
+        let _ = self.graph.remove_node(node);
+        self.reindex_stream_nodes();
+        Ok(())
+    }
+
+    pub fn remove_source_reader(&mut self, name: &SourceReaderName) -> Result<()> {
+        let edge = self
+            .graph
+            .edge_references()
+            .find_map(|edge| match edge.weight() {
+                Edge::Source(source_reader) if source_reader.name() == name => Some((
+                    edge.id(),
+                    matches!(
+                        source_reader.source_reader_type(),
+                        SourceReaderType::System(_)
+                    ),
+                )),
+                _ => None,
+            });
+        match edge {
+            None => Err(SpringError::Sql(anyhow!(
+                r#"source reader "{}" does not exist in pipeline"#,
+                name
+            ))),
+            Some((_, true)) => Err(SpringError::Sql(anyhow!(
+                r#"source reader "{}" of system stream cannot be dropped"#,
+                name
+            ))),
+            Some((edge, false)) => {
+                let _ = self.graph.remove_edge(edge);
+                Ok(())
+            }
         }
+    }
 
+    /// The virtual leaf stream of the sink writer is also removed.
+    pub fn remove_sink_writer(&mut self, name: &SinkWriterName) -> Result<()> {
+        let leaf_node = self
+            .graph
+            .edge_references()
+            .find_map(|edge| match edge.weight() {
+                Edge::Sink(sink_writer) if sink_writer.name() == name => Some(edge.target()),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                SpringError::Sql(anyhow!(
+                    r#"sink writer "{}" does not exist in pipeline"#,
+                    name
+                ))
+            })?;
+
+        let _ = self.graph.remove_node(leaf_node);
+        self.reindex_stream_nodes();
         Ok(())
     }
 
+    /// Node indices are shifted by node removals.
+    fn reindex_stream_nodes(&mut self) {
+        self.stream_nodes = self
+            .graph
+            .node_indices()
+            .filter_map(|node| match &self.graph[node] {
+                StreamNode::Stream(stream) => Some((stream.name().clone(), node)),
+                StreamNode::VirtualRoot => Some((StreamName::virtual_root(), node)),
+                StreamNode::VirtualLeaf { .. } => None,
+            })
+            .collect();
+    }
+
     fn _find_stream(&self, name: &StreamName) -> Result<NodeIndex> {
         Ok(*self.stream_nodes.get(name).ok_or_else(|| {
             SpringError::Sql(anyhow!(r#"stream "{}" does not exist in pipeline"#, name))
@@ -0,0 +1,195 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt,
+};
+
+use petgraph::{graph::NodeIndex, visit::EdgeRef, EdgeDirection};
+
+use crate::pipeline::{
+    name::{ColumnName, PumpName, StreamName},
+    pipeline_graph::{Edge, PipelineGraph, StreamNode},
+    ColumnReference, SystemStream,
+};
+
+/// Objects in a pipeline which do no useful work, for `SHOW WARNINGS`.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub enum PipelineWarning {
+    /// No pump or sink writer reads from the stream.
+    UnconsumedStream(StreamName),
+
+    /// None of the streams the pump inserts into reaches a sink writer.
+    PumpWithoutSink(PumpName),
+
+    /// The column is not referenced by any pump (or sink writer with `PROJECT`) reading from the stream.
+    /// ROWTIME columns are not reported as they order rows.
+    UnusedColumn {
+        stream: StreamName,
+        column: ColumnName,
+    },
+}
+
+impl fmt::Display for PipelineWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PipelineWarning::UnconsumedStream(stream) => write!(
+                f,
+                r#"stream "{}" is not read by any pump or sink writer"#,
+                stream
+            ),
+            PipelineWarning::PumpWithoutSink(pump) => write!(
+                f,
+                r#"pump "{}" inserts into streams which do not reach any sink writer"#,
+                pump
+            ),
+            PipelineWarning::UnusedColumn { stream, column } => write!(
+                f,
+                r#"column "{}" of stream "{}" is not used downstream"#,
+                column, stream
+            ),
+        }
+    }
+}
+
+impl PipelineGraph {
+    /// Warnings in the order of streams (by name), pumps (by name), and columns (by stream name and column order).
+    /// System streams are not analyzed.
+    pub fn warnings(&self) -> Vec<PipelineWarning> {
+        let streams = self.user_stream_nodes();
+        let reaching_sink = self.nodes_reaching_sink();
+
+        let unconsumed_streams = streams
+            .iter()
+            .filter(|(_, node)| {
+                self.graph
+                    .edges_directed(*node, EdgeDirection::Outgoing)
+                    .next()
+                    .is_none()
+            })
+            .map(|(name, _)| PipelineWarning::UnconsumedStream((*name).clone()));
+
+        // a router pump has an edge to each downstream
+        let mut pump_reaches_sink = BTreeMap::new();
+        for edge in self.graph.edge_references() {
+            if let Edge::Pump { pump_model, .. } = edge.weight() {
+                *pump_reaches_sink
+                    .entry(pump_model.name().clone())
+                    .or_insert(false) |= reaching_sink.contains(&edge.target());
+            }
+        }
+        let pumps_without_sink = pump_reaches_sink
+            .into_iter()
+            .filter(|(_, reaches_sink)| !reaches_sink)
+            .map(|(pump, _)| PipelineWarning::PumpWithoutSink(pump));
+
+        let unused_columns = streams
+            .iter()
+            .flat_map(|(name, node)| self.unused_columns(name, *node));
+
+        unconsumed_streams
+            .chain(pumps_without_sink)
+            .chain(unused_columns)
+            .collect()
+    }
+
+    /// Streams other than system streams, sorted by name.
+    fn user_stream_nodes(&self) -> Vec<(&StreamName, NodeIndex)> {
+        let mut streams = self
+            .stream_nodes
+            .iter()
+            .filter(|(name, node)| {
+                matches!(self.graph[**node], StreamNode::Stream(_))
+                    && SystemStream::from_name(name).is_none()
+            })
+            .map(|(name, node)| (name, *node))
+            .collect::<Vec<_>>();
+        streams.sort();
+        streams
+    }
+
+    /// Stream nodes with a path to a sink writer through pumps.
+    fn nodes_reaching_sink(&self) -> HashSet<NodeIndex> {
+        let mut reaching = self
+            .graph
+            .edge_references()
+            .filter(|edge| matches!(edge.weight(), Edge::Sink(_)))
+            .map(|edge| edge.source())
+            .collect::<HashSet<_>>();
+
+        let mut frontier = reaching.iter().copied().collect::<Vec<_>>();
+        while let Some(node) = frontier.pop() {
+            for edge in self.graph.edges_directed(node, EdgeDirection::Incoming) {
+                if matches!(edge.weight(), Edge::Pump { .. }) && reaching.insert(edge.source()) {
+                    frontier.push(edge.source());
+                }
+            }
+        }
+        reaching
+    }
+
+    fn unused_columns(&self, stream_name: &StreamName, node: NodeIndex) -> Vec<PipelineWarning> {
+        let stream = match &self.graph[node] {
+            StreamNode::Stream(stream) => stream,
+            StreamNode::VirtualRoot | StreamNode::VirtualLeaf { .. } => return vec![],
+        };
+
+        let mut used_columns = HashSet::new();
+        let mut consumed = false;
+        for edge in self.graph.edges_directed(node, EdgeDirection::Outgoing) {
+            consumed = true;
+            match edge.weight() {
+                Edge::Pump { pump_model, .. } => {
+                    used_columns.extend(
+                        pump_model
+                            .query_plan()
+                            .expr_resolver
+                            .column_references()
+                            .into_iter()
+                            .filter_map(|colref| match colref {
+                                ColumnReference::Column {
+                                    stream_name: colref_stream,
+                                    column_name,
+                                } if colref_stream == stream_name => Some(column_name),
+                                _ => None,
+                            }),
+                    );
+                }
+                Edge::Source(_) => {}
+                Edge::Sink(sink_writer) => match sink_writer.projection() {
+                    // a sink writer without PROJECT writes all columns
+                    None => return vec![],
+                    Some(projection) => {
+                        let filter_columns = sink_writer
+                            .filter()
+                            .into_iter()
+                            .flat_map(|filter| filter.column_references())
+                            .filter_map(|colref| match colref {
+                                ColumnReference::Column { column_name, .. } => Some(column_name),
+                                ColumnReference::PTime { .. } => None,
+                            });
+                        used_columns.extend(projection.iter().chain(filter_columns));
+                    }
+                },
+            }
+        }
+        if !consumed {
+            return vec![];
+        }
+
+        stream
+            .shape()
+            .columns()
+            .iter()
+            .map(|coldef| coldef.column_name())
+            .filter(|column_name| {
+                !used_columns.contains(column_name)
+                    && stream.shape().event_time() != Some(*column_name)
+            })
+            .map(|column_name| PipelineWarning::UnusedColumn {
+                stream: stream_name.clone(),
+                column: column_name.clone(),
+            })
+            .collect()
+    }
+}
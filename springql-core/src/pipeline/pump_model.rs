@@ -6,24 +6,41 @@ mod window_parameter;
 
 pub use pump_input_type::PumpInputType;
 pub use window_operation_parameter::{
-    AggregateFunctionParameter, AggregateParameter, GroupByLabels, JoinParameter, JoinType,
-    WindowOperationParameter,
+    AggregateFunctionParameter, AggregateParameter, GroupByLabels, IntervalJoinBound,
+    JoinBuildSide, JoinKey, JoinParameter, JoinType, WindowMetadata, WindowOperationParameter,
 };
-pub use window_parameter::WindowParameter;
+pub use window_parameter::{WindowAlignment, WindowParameter};
 
 use crate::{
-    pipeline::name::{PumpName, StreamName},
-    stream_engine::command::{InsertPlan, QueryPlan},
+    pipeline::{
+        name::{PumpName, StreamName},
+        tags::Tags,
+    },
+    stream_engine::command::{PumpOutputPlan, QueryPlan},
 };
 
 #[derive(Clone, PartialEq, Debug, new)]
 pub struct PumpModel {
     name: PumpName,
     query_plan: QueryPlan,
-    insert_plan: InsertPlan,
+    output_plan: PumpOutputPlan,
+
+    /// `PRIORITY WHEN` is specified. Its condition value follows the other values from `query_plan`.
+    prioritized: bool,
+
+    /// `BEST_EFFORT` is specified.
+    best_effort: bool,
+
+    /// `WITH TAGS (...)`
+    #[new(default)]
+    tags: Tags,
 }
 
 impl PumpModel {
+    pub fn with_tags(self, tags: Tags) -> Self {
+        Self { tags, ..self }
+    }
+
     pub fn name(&self) -> &PumpName {
         &self.name
     }
@@ -36,8 +53,22 @@ impl PumpModel {
         &self.query_plan
     }
 
-    pub fn insert_plan(&self) -> &InsertPlan {
-        &self.insert_plan
+    pub fn output_plan(&self) -> &PumpOutputPlan {
+        &self.output_plan
+    }
+
+    /// Rows matching `PRIORITY WHEN` condition overtake other rows in downstream queues.
+    pub fn prioritized(&self) -> bool {
+        self.prioritized
+    }
+
+    /// Best-effort pumps are paused while memory state is Severe or Critical.
+    pub fn best_effort(&self) -> bool {
+        self.best_effort
+    }
+
+    pub fn tags(&self) -> &Tags {
+        &self.tags
     }
 
     /// Has more than 1 upstreams on JOIN, for example.
@@ -45,7 +76,8 @@ impl PumpModel {
         self.query_plan.upstreams()
     }
 
-    pub fn downstream(&self) -> &StreamName {
-        self.insert_plan.stream()
+    /// Has more than 1 downstreams on ROUTER, for example.
+    pub fn downstreams(&self) -> Vec<&StreamName> {
+        self.output_plan.downstreams()
     }
 }
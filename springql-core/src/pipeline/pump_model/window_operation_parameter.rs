@@ -3,8 +3,10 @@
 mod aggregate;
 mod join_parameter;
 
-pub use aggregate::{AggregateFunctionParameter, AggregateParameter, GroupByLabels};
-pub use join_parameter::{JoinParameter, JoinType};
+pub use aggregate::{
+    AggregateFunctionParameter, AggregateParameter, GroupByLabels, WindowMetadata,
+};
+pub use join_parameter::{IntervalJoinBound, JoinBuildSide, JoinKey, JoinParameter, JoinType};
 
 /// Window operation parameters
 #[derive(Clone, PartialEq, Debug)]
@@ -17,20 +17,38 @@ impl GroupByLabels {
 /// TODO `support complex expression with aggregations`
 ///
 /// ```sql
-/// SELECT group_by, aggr_expr.func(aggr_expr.aggregated)
+/// SELECT group_by, aggr_expr.func(aggr_expr.aggregated) [, aggr_expr.func(aggr_expr.aggregated) ...] [, window_metadata]
 ///   FROM s
 ///   [GROUP BY group_by]
 ///   SLIDING WINDOW ...;
 /// ```
 #[derive(Clone, PartialEq, Debug, new)]
 pub struct AggregateParameter {
-    // TODO multiple aggr_expr
-    pub aggr_func: AggregateFunctionParameter,
-    pub aggr_expr: AggrExprLabel,
+    /// Aggregate expressions in select_list, in the order of appearance. Not empty.
+    pub aggr_exprs: Vec<(AggregateFunctionParameter, AggrExprLabel)>,
     pub group_by: GroupByLabels,
+
+    /// Window metadata in select_list.
+    pub window_metadata: Vec<(ValueExprLabel, WindowMetadata)>,
 }
 
+/// NULLs are ignored. See `AggregateState::finalize()` for result types.
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum AggregateFunctionParameter {
     Avg,
+    Count,
+    Sum,
+    Min,
+    Max,
+}
+
+/// Pseudo-columns of a pane, selectable in SELECT with aggregation over window.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum WindowMetadata {
+    /// `WINDOW_START`: the pane's start (inclusive) as TIMESTAMP.
+    WindowStart,
+    /// `WINDOW_END`: the pane's end (exclusive) as TIMESTAMP.
+    WindowEnd,
+    /// `PANE_ROW_COUNT`: number of rows aggregated into the output row as BIGINT.
+    PaneRowCount,
 }
@@ -1,6 +1,10 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use crate::{expr_resolver::ValueExprLabel, pipeline::field::ColumnReference};
+use std::str::FromStr;
+
+use anyhow::anyhow;
+
+use crate::{expr_resolver::ValueExprLabel, pipeline::field::ColumnReference, time::Duration};
 
 /// TODO `support complex expression with aggregations`
 ///
@@ -8,7 +12,7 @@ use crate::{expr_resolver::ValueExprLabel, pipeline::field::ColumnReference};
 /// SELECT s.c1, t.c2
 ///   FROM s
 ///   LEFT OUTER JOIN t
-///   ON s.c1 = t.c1
+///   ON s.c1 = t.c1 AND floor_time(s.ts, duration_secs(1)) = floor_time(t.ts, duration_secs(1))
 ///   SLIDING WINDOW ...;
 /// ```
 #[derive(Clone, PartialEq, Debug, new)]
@@ -19,10 +23,77 @@ pub struct JoinParameter {
     pub left_colrefs: Vec<ColumnReference>,
     pub right_colrefs: Vec<ColumnReference>,
 
-    pub on_expr: ValueExprLabel,
+    /// Composite key from equalities in ON clause (`left_expr = right_expr AND ...`).
+    /// Join panes only try joining tuples with the same key. Empty if ON clause has no such equality.
+    pub keys: Vec<JoinKey>,
+
+    /// Rest of ON clause, evaluated for tuples with the same key. `None` if ON clause consists only of `keys`.
+    pub on_expr: Option<ValueExprLabel>,
+
+    /// Side of a join pane to build a hash table from, set by `JOIN_BUILD_SIDE` option of the pump.
+    #[new(default)]
+    pub build_side: JoinBuildSide,
+}
+
+/// Pair of expressions of a join key, each of which refers only to columns from left / right.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, new)]
+pub struct JoinKey {
+    pub left: ValueExprLabel,
+    pub right: ValueExprLabel,
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum JoinType {
     LeftOuter,
 }
+
+/// Side of a join pane whose tuples are put into a hash table by join key on close. Tuples from the other side probe it.
+///
+/// The result is the same regardless of the build side.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum JoinBuildSide {
+    /// The side with fewer tuples in each pane when it closes.
+    ///
+    /// Only the tuple counts of the closing pane are compared; input rates in task metrics are not used.
+    Auto,
+    Left,
+    Right,
+}
+
+impl Default for JoinBuildSide {
+    fn default() -> Self {
+        Self::Auto
+    }
+}
+
+impl FromStr for JoinBuildSide {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "AUTO" => Ok(Self::Auto),
+            "LEFT" => Ok(Self::Left),
+            "RIGHT" => Ok(Self::Right),
+            _ => Err(anyhow!("JOIN_BUILD_SIDE must be AUTO, LEFT or RIGHT")),
+        }
+    }
+}
+
+/// Time bound of an interval join, which joins without windows.
+///
+/// ```text
+/// left.ROWTIME + lower <= right.ROWTIME <= left.ROWTIME + upper
+/// ```
+///
+/// ```sql
+/// SELECT s.c1, t.c2
+///   FROM s
+///   LEFT OUTER JOIN t
+///   ON t.ts BETWEEN s.ts AND s.ts + INTERVAL '5' SECOND;
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Debug, new)]
+pub struct IntervalJoinBound {
+    /// Can be negative
+    pub lower: Duration,
+    pub upper: Duration,
+}
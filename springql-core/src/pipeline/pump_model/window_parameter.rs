@@ -1,8 +1,22 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use crate::stream_engine::time::SpringEventDuration;
+use std::time::Duration;
+
+use anyhow::anyhow;
+
+use crate::{
+    api::error::{Result, SpringError},
+    stream_engine::time::{SpringDuration, SpringEventDuration, WallClockDuration},
+};
 
 /// Window parameters
+///
+/// - `allowed_delay`: delay of the watermark from the latest ROWTIME (grace period). A pane is closed after the watermark passes its end.
+/// - `allowed_lateness`: how long a closed pane accepts late rows. Each late row updates the pane and outputs its result again.
+/// - `min_watermark`: for joins, tracks a watermark per input and uses the minimum one, so that panes are not closed until both inputs catch up.
+/// - `input_idle_timeout`: with `min_watermark`, an input without rows for this (wall-clock) duration is marked idle and ignored in the minimum watermark until it resumes.
+/// - `emit_on_update`: for window aggregations, outputs the current result of a group each time a row updates it, instead of outputting panes' results when they close.
+/// - `trace_triggers`: diagnostic mode to log each pane opening, closing, late merge, and emitted row.
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub enum WindowParameter {
     /// Time-based sliding window
@@ -17,16 +31,23 @@ pub enum WindowParameter {
     /// -----------------------------------> t
     ///      :00  :05  :10  :15  :20
     /// ```
+    ///
+    /// `period` (hop interval) is positive and not longer than `length`, but does not have to divide it.
     TimedSlidingWindow {
         length: SpringEventDuration,
         period: SpringEventDuration,
         allowed_delay: SpringEventDuration,
+        allowed_lateness: SpringEventDuration,
+        min_watermark: bool,
+        input_idle_timeout: Option<WallClockDuration>,
+        emit_on_update: bool,
+        trace_triggers: bool,
     },
 
     /// Time-based fixed window
     ///
     /// ```text
-    /// length = 10sec, allowed_delay = 0;
+    /// length = 10sec, allowed_delay = 0, offset = 0;
     ///
     /// pane1 |         |
     /// pane2           |         |
@@ -38,6 +59,13 @@ pub enum WindowParameter {
     TimedFixedWindow {
         length: SpringEventDuration,
         allowed_delay: SpringEventDuration,
+        allowed_lateness: SpringEventDuration,
+        min_watermark: bool,
+        input_idle_timeout: Option<WallClockDuration>,
+        /// Panes open at `UNIX epoch + offset + length * n`. Smaller than `length`.
+        offset: SpringEventDuration,
+        emit_on_update: bool,
+        trace_triggers: bool,
     },
 }
 
@@ -62,4 +90,170 @@ impl WindowParameter {
             WindowParameter::TimedFixedWindow { allowed_delay, .. } => *allowed_delay,
         }
     }
+
+    pub fn allowed_lateness(&self) -> SpringEventDuration {
+        match self {
+            WindowParameter::TimedSlidingWindow {
+                allowed_lateness, ..
+            } => *allowed_lateness,
+            WindowParameter::TimedFixedWindow {
+                allowed_lateness, ..
+            } => *allowed_lateness,
+        }
+    }
+
+    pub fn min_watermark(&self) -> bool {
+        match self {
+            WindowParameter::TimedSlidingWindow { min_watermark, .. } => *min_watermark,
+            WindowParameter::TimedFixedWindow { min_watermark, .. } => *min_watermark,
+        }
+    }
+
+    pub fn input_idle_timeout(&self) -> Option<WallClockDuration> {
+        match self {
+            WindowParameter::TimedSlidingWindow {
+                input_idle_timeout, ..
+            } => *input_idle_timeout,
+            WindowParameter::TimedFixedWindow {
+                input_idle_timeout, ..
+            } => *input_idle_timeout,
+        }
+    }
+
+    pub fn emit_on_update(&self) -> bool {
+        match self {
+            WindowParameter::TimedSlidingWindow { emit_on_update, .. } => *emit_on_update,
+            WindowParameter::TimedFixedWindow { emit_on_update, .. } => *emit_on_update,
+        }
+    }
+
+    pub fn trace_triggers(&self) -> bool {
+        match self {
+            WindowParameter::TimedSlidingWindow { trace_triggers, .. } => *trace_triggers,
+            WindowParameter::TimedFixedWindow { trace_triggers, .. } => *trace_triggers,
+        }
+    }
+
+    pub fn offset(&self) -> SpringEventDuration {
+        match self {
+            WindowParameter::TimedSlidingWindow { .. } => SpringEventDuration::from_secs(0),
+            WindowParameter::TimedFixedWindow { offset, .. } => *offset,
+        }
+    }
+}
+
+/// Boundary which fixed window panes are aligned to (`ALIGNED TO ...` clause).
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum WindowAlignment {
+    /// `ALIGNED TO MINUTE | HOUR | DAY`: panes open at the start of each unit in UTC.
+    Unit(SpringEventDuration),
+
+    /// `ALIGNED TO 'HH:MM +HH:MM'`: a pane opens at the wall-clock time in the UTC offset.
+    WallClock(String),
+}
+
+impl WindowAlignment {
+    /// Offset of pane boundaries from the UNIX epoch for windows with `length`.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `length` is neither a divisor nor a multiple of the alignment unit.
+    ///   - wall-clock time is not in `HH:MM +HH:MM` format.
+    pub fn offset(&self, length: SpringEventDuration) -> Result<SpringEventDuration> {
+        let length_nanos = length.as_std().as_nanos();
+
+        match self {
+            WindowAlignment::Unit(unit) => {
+                let unit_nanos = unit.as_std().as_nanos();
+                if unit_nanos % length_nanos == 0 || length_nanos % unit_nanos == 0 {
+                    Ok(SpringEventDuration::from_secs(0))
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "window length ({}) must divide or be a multiple of {} to be aligned",
+                        length,
+                        unit
+                    )))
+                }
+            }
+            WindowAlignment::WallClock(s) => {
+                let utc_secs = Self::parse_wall_clock(s).ok_or_else(|| {
+                    SpringError::Sql(anyhow!(
+                        "`{}` is not a wall-clock time like '00:00 +09:00'",
+                        s
+                    ))
+                })?;
+                let offset_nanos =
+                    (utc_secs as i128 * 1_000_000_000).rem_euclid(length_nanos as i128);
+                Ok(SpringEventDuration::from_std(Duration::from_nanos(
+                    offset_nanos as u64,
+                )))
+            }
+        }
+    }
+
+    /// Parses `HH:MM +HH:MM` into seconds from 00:00 in UTC (may be negative).
+    fn parse_wall_clock(s: &str) -> Option<i64> {
+        fn hh_mm(s: &str) -> Option<i64> {
+            let (h, m) = s.split_once(':')?;
+            let (h, m) = (h.parse::<i64>().ok()?, m.parse::<i64>().ok()?);
+            ((0..24).contains(&h) && (0..60).contains(&m)).then(|| h * 3600 + m * 60)
+        }
+
+        let (time, utc_offset) = s.trim().split_once(' ')?;
+        let time = hh_mm(time)?;
+        let utc_offset = if let Some(o) = utc_offset.strip_prefix('+') {
+            hh_mm(o)?
+        } else {
+            -hh_mm(utc_offset.strip_prefix('-')?)?
+        };
+        Some(time - utc_offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_alignment_offset() {
+        let hour = SpringEventDuration::from_secs(3600);
+        let day = SpringEventDuration::from_secs(24 * 3600);
+
+        assert_eq!(
+            WindowAlignment::Unit(hour)
+                .offset(SpringEventDuration::from_secs(15 * 60))
+                .unwrap(),
+            SpringEventDuration::from_secs(0)
+        );
+        assert!(WindowAlignment::Unit(hour)
+            .offset(SpringEventDuration::from_secs(7 * 60))
+            .is_err());
+
+        // 00:00 in JST is 15:00 in UTC
+        assert_eq!(
+            WindowAlignment::WallClock("00:00 +09:00".to_string())
+                .offset(day)
+                .unwrap(),
+            SpringEventDuration::from_secs(15 * 3600)
+        );
+        assert_eq!(
+            WindowAlignment::WallClock("00:30 +09:00".to_string())
+                .offset(hour)
+                .unwrap(),
+            SpringEventDuration::from_secs(30 * 60)
+        );
+        assert_eq!(
+            WindowAlignment::WallClock("06:00 -05:00".to_string())
+                .offset(day)
+                .unwrap(),
+            SpringEventDuration::from_secs(11 * 3600)
+        );
+        assert!(WindowAlignment::WallClock("24:00 +09:00".to_string())
+            .offset(day)
+            .is_err());
+        assert!(WindowAlignment::WallClock("00:00".to_string())
+            .offset(day)
+            .is_err());
+    }
 }
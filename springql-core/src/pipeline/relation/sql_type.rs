@@ -1,5 +1,7 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use std::fmt;
+
 /// SQL type.
 ///
 /// SQL types are hierarchically categorized as follows:
@@ -86,6 +88,32 @@ impl SqlType {
     }
 }
 
+/// Type name in DDL (e.g. `UNSIGNED BIGINT`).
+impl fmt::Display for SqlType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SqlType::NumericComparable(n) => match n {
+                NumericComparableType::I64Loose(i) => match i {
+                    I64LooseType::SmallInt => "SMALLINT",
+                    I64LooseType::Integer => "INTEGER",
+                    I64LooseType::BigInt => "BIGINT",
+                },
+                NumericComparableType::U64Loose(u) => match u {
+                    U64LooseType::UnsignedInteger => "UNSIGNED INTEGER",
+                    U64LooseType::UnsignedBigInt => "UNSIGNED BIGINT",
+                },
+                NumericComparableType::F32Loose(F32LooseType::Float) => "FLOAT",
+            },
+            SqlType::StringComparableLoose(StringComparableLoseType::Text) => "TEXT",
+            SqlType::BinaryComparable => "BLOB",
+            SqlType::BooleanComparable => "BOOLEAN",
+            SqlType::TimestampComparable => "TIMESTAMP",
+            SqlType::DurationComparable => "DURATION",
+        };
+        write!(f, "{}", name)
+    }
+}
+
 /// Numeric types (comparable).
 #[allow(clippy::enum_variant_names)]
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
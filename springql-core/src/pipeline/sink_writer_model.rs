@@ -1,18 +1,33 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 mod sink_writer_type;
-use crate::pipeline::{
-    name::{SinkWriterName, StreamName},
-    option::Options,
-};
 pub use sink_writer_type::SinkWriterType;
 
-#[derive(Clone, PartialEq, Eq, Debug, new)]
+use anyhow::Context;
+
+use crate::{
+    api::error::Result,
+    expression::ValueExpr,
+    pipeline::{
+        name::{ColumnName, SinkWriterName, StreamName},
+        option::Options,
+    },
+    stream_engine::time::{SpringDuration, WallClockDuration},
+};
+
+#[derive(Clone, PartialEq, Debug, new)]
 pub struct SinkWriterModel {
     name: SinkWriterName,
     sink_writer_type: SinkWriterType,
     sink_upstream: StreamName,
     options: Options,
+
+    /// `FILTER WHERE condition`: rows for which the condition is not TRUE are not written.
+    #[new(default)]
+    filter: Option<ValueExpr>,
+    /// `PROJECT (c1, c2, ...)`: only these columns are written, in this order.
+    #[new(default)]
+    projection: Option<Vec<ColumnName>>,
 }
 
 impl SinkWriterModel {
@@ -31,4 +46,43 @@ impl SinkWriterModel {
     pub fn options(&self) -> &Options {
         &self.options
     }
+
+    pub fn with_filter(self, filter: ValueExpr) -> Self {
+        Self {
+            filter: Some(filter),
+            ..self
+        }
+    }
+
+    pub fn with_projection(self, projection: Vec<ColumnName>) -> Self {
+        Self {
+            projection: Some(projection),
+            ..self
+        }
+    }
+
+    pub fn filter(&self) -> Option<&ValueExpr> {
+        self.filter.as_ref()
+    }
+
+    pub fn projection(&self) -> Option<&[ColumnName]> {
+        self.projection.as_deref()
+    }
+
+    /// Latency SLO from `LATENCY_SLO_MSEC` option, available for any type of sink writer.
+    ///
+    /// Latency of a row is measured from its ROWTIME to when it is written to the sink.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::InvalidOption` when:
+    ///   - `LATENCY_SLO_MSEC` is not an unsigned integer.
+    pub fn latency_slo(&self) -> Result<Option<WallClockDuration>> {
+        self.options.get_optional("LATENCY_SLO_MSEC", |msec_str| {
+            msec_str
+                .parse()
+                .map(WallClockDuration::from_millis)
+                .context("invalid latency SLO")
+        })
+    }
 }
@@ -1,20 +1,76 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use std::fmt;
+
 use crate::pipeline::name::SinkWriterName;
 
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub enum SinkWriterType {
     Net,
+    NetMqtt,
     Http1Client,
     InMemoryQueue,
+    Gpio,
+    Command,
+    AzureIotHub,
+    AwsIotCore,
+    RedisStream,
+    Nats,
+    ZeroMq,
+    InfluxDb,
+    Parquet,
+    S3,
+    SpringqlLink,
+    File,
+    UnixSocket,
+}
+
+/// Type name in `CREATE SINK WRITER ... TYPE`.
+impl fmt::Display for SinkWriterType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            SinkWriterType::Net => "NET_CLIENT",
+            SinkWriterType::NetMqtt => "NET_MQTT",
+            SinkWriterType::Http1Client => "HTTP1_CLIENT",
+            SinkWriterType::InMemoryQueue => "IN_MEMORY_QUEUE",
+            SinkWriterType::Gpio => "GPIO",
+            SinkWriterType::Command => "COMMAND",
+            SinkWriterType::AzureIotHub => "AZURE_IOT_HUB",
+            SinkWriterType::AwsIotCore => "AWS_IOT_CORE",
+            SinkWriterType::RedisStream => "REDIS_STREAM",
+            SinkWriterType::Nats => "NATS",
+            SinkWriterType::ZeroMq => "ZEROMQ",
+            SinkWriterType::InfluxDb => "INFLUXDB",
+            SinkWriterType::Parquet => "PARQUET",
+            SinkWriterType::S3 => "S3",
+            SinkWriterType::SpringqlLink => "SPRINGQL_LINK",
+            SinkWriterType::File => "FILE",
+            SinkWriterType::UnixSocket => "UNIX_SOCKET",
+        };
+        write!(f, "{}", s)
+    }
 }
 
 impl From<&SinkWriterType> for SinkWriterName {
     fn from(sink_writer_type: &SinkWriterType) -> Self {
         match sink_writer_type {
             SinkWriterType::Net => SinkWriterName::net_sink(),
+            SinkWriterType::NetMqtt => SinkWriterName::net_mqtt_sink(),
             SinkWriterType::Http1Client => SinkWriterName::http11_sink(),
             SinkWriterType::InMemoryQueue => SinkWriterName::in_memory_queue_sink(),
+            SinkWriterType::Gpio => SinkWriterName::gpio_sink(),
+            SinkWriterType::Command => SinkWriterName::command_sink(),
+            SinkWriterType::AzureIotHub => SinkWriterName::azure_iot_hub_sink(),
+            SinkWriterType::AwsIotCore => SinkWriterName::aws_iot_core_sink(),
+            SinkWriterType::RedisStream => SinkWriterName::redis_stream_sink(),
+            SinkWriterType::Nats => SinkWriterName::nats_sink(),
+            SinkWriterType::ZeroMq => SinkWriterName::zeromq_sink(),
+            SinkWriterType::InfluxDb => SinkWriterName::influxdb_sink(),
+            SinkWriterType::Parquet => SinkWriterName::parquet_sink(),
+            SinkWriterType::S3 => SinkWriterName::s3_sink(),
+            SinkWriterType::SpringqlLink => SinkWriterName::springql_link_sink(),
+            SinkWriterType::File => SinkWriterName::file_sink(),
+            SinkWriterType::UnixSocket => SinkWriterName::unix_socket_sink(),
         }
     }
 }
@@ -1,10 +1,57 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use std::fmt;
+
+use crate::pipeline::SystemStream;
+
 #[allow(clippy::upper_case_acronyms)]
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub enum SourceReaderType {
     NetClient,
     NetServer,
+    NetHttp,
+    NetMqtt,
     CAN,
     InMemoryQueue,
+    OpcUa,
+    ModbusTcp,
+    RedisStream,
+    Nats,
+    Kafka,
+    ZeroMq,
+    Ros2,
+    SpringqlLink,
+    File,
+    UnixSocket,
+    Udp,
+
+    /// Reads rows the stream engine puts into a system stream. Not available in DDL.
+    System(SystemStream),
+}
+
+/// Type name in `CREATE SOURCE READER ... TYPE`.
+impl fmt::Display for SourceReaderType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            SourceReaderType::NetClient => "NET_CLIENT",
+            SourceReaderType::NetServer => "NET_SERVER",
+            SourceReaderType::NetHttp => "NET_HTTP",
+            SourceReaderType::NetMqtt => "NET_MQTT",
+            SourceReaderType::CAN => "CAN",
+            SourceReaderType::InMemoryQueue => "IN_MEMORY_QUEUE",
+            SourceReaderType::OpcUa => "OPC_UA",
+            SourceReaderType::ModbusTcp => "MODBUS_TCP",
+            SourceReaderType::RedisStream => "REDIS_STREAM",
+            SourceReaderType::Nats => "NATS",
+            SourceReaderType::Kafka => "KAFKA",
+            SourceReaderType::ZeroMq => "ZEROMQ",
+            SourceReaderType::Ros2 => "ROS2",
+            SourceReaderType::SpringqlLink => "SPRINGQL_LINK",
+            SourceReaderType::File => "FILE",
+            SourceReaderType::UnixSocket => "UNIX_SOCKET",
+            SourceReaderType::Udp => "UDP",
+            SourceReaderType::System(_) => "SYSTEM",
+        };
+        write!(f, "{}", s)
+    }
 }
@@ -4,15 +4,23 @@ mod stream_shape;
 
 pub use stream_shape::StreamShape;
 
-use crate::pipeline::{field::ColumnReference, name::StreamName};
+use crate::pipeline::{field::ColumnReference, name::StreamName, tags::Tags};
 
 #[derive(Clone, Eq, PartialEq, Debug, new)]
 pub struct StreamModel {
     name: StreamName,
     shape: StreamShape,
+
+    /// `WITH TAGS (...)`
+    #[new(default)]
+    tags: Tags,
 }
 
 impl StreamModel {
+    pub fn with_tags(self, tags: Tags) -> Self {
+        Self { tags, ..self }
+    }
+
     pub fn name(&self) -> &StreamName {
         &self.name
     }
@@ -21,6 +29,10 @@ impl StreamModel {
         &self.shape
     }
 
+    pub fn tags(&self) -> &Tags {
+        &self.tags
+    }
+
     pub fn column_references(&self) -> Vec<ColumnReference> {
         self.shape
             .column_names()
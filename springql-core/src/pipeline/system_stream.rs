@@ -0,0 +1,162 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use crate::pipeline::{
+    ColumnConstraint, ColumnDataType, ColumnDefinition, ColumnName, OptionsBuilder,
+    SourceReaderModel, SourceReaderName, SourceReaderType, SqlType, StreamModel, StreamName,
+    StreamShape,
+};
+
+/// Prefix of system stream names. User-defined streams cannot have it.
+pub const SYSTEM_STREAM_PREFIX: &str = "spring._";
+
+/// Built-in source streams to monitor the stream engine itself.
+///
+/// A system stream is created on demand when a pump reads from it, and rows are put into it only after that.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum SystemStream {
+    /// `spring._task_metrics`: a row per task at every `system_stream.task_metrics_interval_msec`.
+    ///
+    /// ```sql
+    /// (
+    ///   ts TIMESTAMP NOT NULL ROWTIME,
+    ///   task_id TEXT NOT NULL,
+    ///   num_input_rows BIGINT NOT NULL,
+    ///   avg_gain_bytes_per_sec FLOAT NOT NULL,
+    ///   tags TEXT NOT NULL,  -- e.g. 'subsystem=brakes', from `WITH TAGS` of the pump or the stream
+    ///   panes_opened BIGINT NOT NULL,  -- pane triggers of windows the task dispatches rows to (0 for tasks without windows)
+    ///   panes_closed BIGINT NOT NULL,
+    ///   late_merges BIGINT NOT NULL,
+    ///   emitted_rows BIGINT NOT NULL,
+    ///   row_errors BIGINT NOT NULL,  -- rows skipped by errors in the task
+    ///   batch_size BIGINT NOT NULL  -- executions per scheduling, tuned in micro-batch mode (`worker.micro_batch` config). 1 otherwise
+    /// )
+    /// ```
+    TaskMetrics,
+
+    /// `spring._events`: a row per engine event (memory state transition, purge, namespace over its memory budget, namespace or pump over its quota).
+    ///
+    /// ```sql
+    /// (
+    ///   ts TIMESTAMP NOT NULL ROWTIME,
+    ///   event_type TEXT NOT NULL,
+    ///   detail TEXT NOT NULL
+    /// )
+    /// ```
+    Events,
+
+    /// `spring._row_errors`: a row per input row a pump or a sink writer skipped by an error (e.g. invalid regular expression, NULL into a NOT NULL column, NULL in `BLOB_BODY_COLUMN`).
+    ///
+    /// `pump` and `stream` are the sink task and its upstream for sink writers.
+    ///
+    /// ```sql
+    /// (
+    ///   ts TIMESTAMP NOT NULL ROWTIME,
+    ///   pump TEXT NOT NULL,
+    ///   stream TEXT NOT NULL,  -- upstreams of the pump separated by ','
+    ///   code TEXT NOT NULL,  -- `SpringError::code()`
+    ///   field TEXT NOT NULL,  -- e.g. 'WHERE', 'SELECT #2', 'INSERT INTO s', 'SINK WRITER w'
+    ///   error TEXT NOT NULL
+    /// )
+    /// ```
+    RowErrors,
+}
+
+impl SystemStream {
+    pub fn from_name(name: &StreamName) -> Option<Self> {
+        match name.as_ref() {
+            "spring._task_metrics" => Some(Self::TaskMetrics),
+            "spring._events" => Some(Self::Events),
+            "spring._row_errors" => Some(Self::RowErrors),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> StreamName {
+        let name = match self {
+            Self::TaskMetrics => "spring._task_metrics",
+            Self::Events => "spring._events",
+            Self::RowErrors => "spring._row_errors",
+        };
+        StreamName::new(name.to_string())
+    }
+
+    pub fn stream_model(&self) -> StreamModel {
+        let col = |name: &str, sql_type: SqlType| {
+            ColumnDefinition::new(
+                ColumnDataType::new(ColumnName::new(name.to_string()), sql_type, false),
+                vec![],
+            )
+        };
+        let ts = ColumnDefinition::new(
+            ColumnDataType::new(
+                ColumnName::new("ts".to_string()),
+                SqlType::timestamp(),
+                false,
+            ),
+            vec![ColumnConstraint::Rowtime],
+        );
+
+        let cols = match self {
+            Self::TaskMetrics => vec![
+                ts,
+                col("task_id", SqlType::text()),
+                col("num_input_rows", SqlType::big_int()),
+                col("avg_gain_bytes_per_sec", SqlType::float()),
+                col("tags", SqlType::text()),
+                col("panes_opened", SqlType::big_int()),
+                col("panes_closed", SqlType::big_int()),
+                col("late_merges", SqlType::big_int()),
+                col("emitted_rows", SqlType::big_int()),
+                col("row_errors", SqlType::big_int()),
+                col("batch_size", SqlType::big_int()),
+            ],
+            Self::Events => vec![
+                ts,
+                col("event_type", SqlType::text()),
+                col("detail", SqlType::text()),
+            ],
+            Self::RowErrors => vec![
+                ts,
+                col("pump", SqlType::text()),
+                col("stream", SqlType::text()),
+                col("code", SqlType::text()),
+                col("field", SqlType::text()),
+                col("error", SqlType::text()),
+            ],
+        };
+        let shape = StreamShape::new(cols).expect("system stream shape must be valid");
+        StreamModel::new(self.name(), shape)
+    }
+
+    pub fn source_reader_model(&self) -> SourceReaderModel {
+        SourceReaderModel::new(
+            SourceReaderName::new(self.name().to_string()),
+            SourceReaderType::System(*self),
+            self.name(),
+            OptionsBuilder::default().build(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_stream_name() {
+        for system_stream in [
+            SystemStream::TaskMetrics,
+            SystemStream::Events,
+            SystemStream::RowErrors,
+        ] {
+            let name = system_stream.name();
+            assert!(name.as_ref().starts_with(SYSTEM_STREAM_PREFIX));
+            assert_eq!(SystemStream::from_name(&name), Some(system_stream));
+            assert_eq!(system_stream.stream_model().name(), &name);
+        }
+        assert_eq!(
+            SystemStream::from_name(&StreamName::new("_events".to_string())),
+            None
+        );
+    }
+}
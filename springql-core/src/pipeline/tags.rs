@@ -0,0 +1,75 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{collections::BTreeMap, fmt::Display};
+
+use anyhow::anyhow;
+
+use crate::api::error::{Result, SpringError};
+
+/// Labels given by `WITH TAGS ('key' = 'value', ...)` to streams and pumps.
+///
+/// Tags are propagated into metrics, events and web console reports to group and filter objects of large pipelines.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct Tags(BTreeMap<String, String>);
+
+impl Tags {
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - a key appears twice.
+    pub fn new(key_values: Vec<(String, String)>) -> Result<Self> {
+        let mut tags = BTreeMap::new();
+        for (key, value) in key_values {
+            if tags.insert(key.clone(), value).is_some() {
+                return Err(SpringError::Sql(anyhow!(r#"tag "{}" appears twice"#, key)));
+            }
+        }
+        Ok(Self(tags))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(String::as_str)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+}
+
+/// `key1=value1,key2=value2` (sorted by keys)
+impl Display for Tags {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = self
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join(",");
+        write!(f, "{}", s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tags() {
+        let tags = Tags::new(vec![
+            ("subsystem".to_string(), "brakes".to_string()),
+            ("owner".to_string(), "chassis".to_string()),
+        ])
+        .unwrap();
+        assert_eq!(tags.get("subsystem"), Some("brakes"));
+        assert_eq!(tags.to_string(), "owner=chassis,subsystem=brakes");
+
+        assert!(Tags::new(vec![
+            ("subsystem".to_string(), "brakes".to_string()),
+            ("subsystem".to_string(), "steering".to_string()),
+        ])
+        .is_err());
+    }
+}
@@ -2,29 +2,65 @@
 
 mod query_planner;
 mod sql_parser;
+mod type_checker;
 
 pub use sql_parser::{
-    ColumnConstraintSyntax, CreatePump, DurationFunction, FromItemSyntax, GroupingElementSyntax,
+    ColumnConstraintSyntax, CreatePump, CreateReplicator, CreateRouter, DurationFunction,
+    ExplainTarget, FromItemSyntax, GroupingElementSyntax, InferSchema, InferSchemaSource,
     OptionSyntax, ParseSuccess, PestParserImpl, SelectFieldSyntax, SelectStreamSyntax, SqlParser,
     SubFromItemSyntax,
 };
+pub use type_checker::TypeChecking;
+
+use anyhow::anyhow;
 
 use crate::{
-    api::error::Result,
-    pipeline::{Pipeline, PumpModel, SinkWriterModel, SourceReaderModel, StreamModel},
-    sql_processor::query_planner::QueryPlanner,
-    stream_engine::command::{AlterPipelineCommand, Command, QueryPlan},
+    api::{
+        error::{Result, SpringError},
+        SpringConfig,
+    },
+    expression::ValueExpr,
+    pipeline::{
+        ColumnReference, JoinBuildSide, Pipeline, PumpModel, PumpName, PumpOptions,
+        SinkWriterModel, SourceReaderModel, StreamModel, StreamName,
+    },
+    sql_processor::{query_planner::QueryPlanner, type_checker::TypeChecker},
+    stream_engine::command::{
+        AlterPipelineCommand, Command, InferSchemaCommand, JoinOp, PumpOutputPlan, QueryPlan,
+        ReplicatePlan, RoutePlan,
+    },
 };
 
-#[derive(Debug, Default)]
-pub struct SqlProcessor(SqlParser);
+/// Stream name in the DDL suggested by `INFER SCHEMA` from an ad-hoc source reader without `FOR` clause.
+const INFERRED_STREAM_NAME: &str = "inferred_stream";
+
+#[derive(Debug)]
+pub struct SqlProcessor {
+    parser: SqlParser,
+
+    /// For pumps without `STRICT` or `LENIENT`.
+    type_checking: TypeChecking,
+}
+
+impl Default for SqlProcessor {
+    fn default() -> Self {
+        Self::new(TypeChecking::Lenient)
+    }
+}
 
 impl SqlProcessor {
+    pub fn new(type_checking: TypeChecking) -> Self {
+        Self {
+            parser: SqlParser::default(),
+            type_checking,
+        }
+    }
+
     /// # Failures
     ///
     /// - `SpringError::Sql` on syntax and semantics error.
     pub fn compile<S: Into<String>>(&self, sql: S, pipeline: &Pipeline) -> Result<Command> {
-        let command = match self.0.parse(sql)? {
+        let command = match self.parser.parse(sql)? {
             ParseSuccess::CreateSourceStream(source_stream_model) => {
                 self.compile_create_source_stream(source_stream_model, pipeline)?
             }
@@ -43,6 +79,45 @@ impl SqlProcessor {
             ParseSuccess::CreatePump(create_pump) => {
                 self.compile_create_pump(*create_pump, pipeline)?
             }
+            ParseSuccess::CreateRouter(create_router) => {
+                self.compile_create_router(*create_router, pipeline)?
+            }
+            ParseSuccess::CreateReplicator(create_replicator) => {
+                self.compile_create_replicator(*create_replicator, pipeline)?
+            }
+            ParseSuccess::SwitchPump { from_pump, to_pump } => {
+                self.compile_switch_pump(from_pump, to_pump, pipeline)?
+            }
+            ParseSuccess::DropPump(pump_name) => {
+                Command::AlterPipeline(AlterPipelineCommand::DropPump(pump_name))
+            }
+            ParseSuccess::DropStream(stream_name) => {
+                Command::AlterPipeline(AlterPipelineCommand::DropStream(stream_name))
+            }
+            ParseSuccess::DropSourceReader(source_reader_name) => {
+                Command::AlterPipeline(AlterPipelineCommand::DropSourceReader(source_reader_name))
+            }
+            ParseSuccess::DropSinkWriter(sink_writer_name) => {
+                Command::AlterPipeline(AlterPipelineCommand::DropSinkWriter(sink_writer_name))
+            }
+            ParseSuccess::AlterPumpState { pump_name, stopped } => {
+                Command::AlterPipeline(AlterPipelineCommand::AlterPumpState { pump_name, stopped })
+            }
+            ParseSuccess::AlterStreamTrace {
+                stream_name,
+                trace_sample_rate,
+            } => self.compile_alter_stream_trace(stream_name, trace_sample_rate, pipeline)?,
+            ParseSuccess::InferSchema(infer_schema) => {
+                self.compile_infer_schema(infer_schema, pipeline)?
+            }
+            ParseSuccess::ShowHistory => Command::ShowHistory,
+            ParseSuccess::ShowWarnings => Command::ShowWarnings,
+            ParseSuccess::ShowObjects(show_objects_command) => {
+                Command::ShowObjects(show_objects_command)
+            }
+            ParseSuccess::Explain(explain_target) => {
+                self.compile_explain(*explain_target, pipeline)?
+            }
         };
         Ok(command)
     }
@@ -58,12 +133,19 @@ impl SqlProcessor {
         ))
     }
 
+    /// # Failures
+    ///
+    /// - `SpringError::InvalidOption` when:
+    ///   - a value overriding `SpringSourceReaderConfig` is invalid.
     fn compile_create_source_reader(
         &self,
         source_reader_model: SourceReaderModel,
         _pipeline: &Pipeline,
     ) -> Result<Command> {
         // TODO semantic check
+        let _ = SpringConfig::default()
+            .source_reader
+            .overridden_by(source_reader_model.options())?;
         Ok(Command::AlterPipeline(
             AlterPipelineCommand::CreateSourceReader(source_reader_model),
         ))
@@ -91,25 +173,404 @@ impl SqlProcessor {
         ))
     }
 
+    /// # Failures
+    ///
+    /// - `SpringError::InvalidOption` when:
+    ///   - `LATENCY_SLO_MSEC` is invalid.
+    ///   - a value overriding `SpringSinkWriterConfig` is invalid.
+    /// - `SpringError::Sql` when:
+    ///   - `FILTER WHERE` condition references a column not in the upstream stream.
+    ///   - `PROJECT` has a column not in the upstream stream.
     fn compile_create_sink_writer(
         &self,
         sink_writer_model: SinkWriterModel,
-        _pipeline: &Pipeline,
+        pipeline: &Pipeline,
     ) -> Result<Command> {
-        // TODO semantic check
+        if sink_writer_model.filter().is_some() || sink_writer_model.projection().is_some() {
+            let upstream = pipeline.get_stream(sink_writer_model.sink_upstream())?;
+            let column_names = upstream.shape().column_names();
+
+            let filter_columns = sink_writer_model
+                .filter()
+                .into_iter()
+                .flat_map(|filter| filter.column_references())
+                .map(|colref| match colref {
+                    ColumnReference::Column {
+                        stream_name,
+                        column_name,
+                    } if stream_name == upstream.name() => Ok(column_name),
+                    _ => Err(SpringError::Sql(anyhow!(
+                        r#"FILTER WHERE condition of sink writer "{}" must reference columns of "{}" (got {:?})"#,
+                        sink_writer_model.name(),
+                        upstream.name(),
+                        colref
+                    ))),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let projected_columns = sink_writer_model.projection().unwrap_or_default();
+
+            if let Some(column_name) = filter_columns
+                .into_iter()
+                .chain(projected_columns)
+                .find(|column_name| !column_names.contains(*column_name))
+            {
+                return Err(SpringError::Sql(anyhow!(
+                    r#"column "{}" is not in stream "{}""#,
+                    column_name,
+                    upstream.name()
+                )));
+            }
+        }
+
+        let _ = sink_writer_model.latency_slo()?;
+        let _ = SpringConfig::default()
+            .sink_writer
+            .overridden_by(sink_writer_model.options())?;
         Ok(Command::AlterPipeline(
             AlterPipelineCommand::CreateSinkWriter(sink_writer_model),
         ))
     }
 
+    /// `PRIORITY WHEN condition` is compiled as an extra select field following the inserted ones.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `PRIORITY WHEN` is used with GROUP BY or window.
+    ///   - types mismatch in a strictly type checked pump. See `TypeChecker::check_pump()`.
+    /// - `SpringError::InvalidOption` when:
+    ///   - `OPTIONS` has an invalid value, or `JOIN_BUILD_SIDE` for a pump without JOIN with window clause.
     fn compile_create_pump(&self, create_pump: CreatePump, pipeline: &Pipeline) -> Result<Command> {
-        let query_plan = self.compile_select_stream(create_pump.select_stream_syntax, pipeline)?;
-        let pump = PumpModel::new(create_pump.pump_name, query_plan, create_pump.insert_plan);
+        let pump = self.compile_pump(create_pump, pipeline)?;
+        Ok(Command::AlterPipeline(AlterPipelineCommand::CreatePump(
+            Box::new(pump),
+        )))
+    }
+
+    fn compile_pump(&self, create_pump: CreatePump, pipeline: &Pipeline) -> Result<PumpModel> {
+        let pump_options = PumpOptions::try_from(&create_pump.options)?;
+        let mut select_stream_syntax = create_pump.select_stream_syntax;
+
+        let prioritized = create_pump.priority_condition.is_some();
+        if let Some(priority_condition) = create_pump.priority_condition {
+            if !select_stream_syntax.grouping_elements.is_empty()
+                || select_stream_syntax.window_clause.is_some()
+            {
+                return Err(SpringError::Sql(anyhow!(
+                    r#"PRIORITY WHEN cannot be used with GROUP BY or window (pump "{}")"#,
+                    create_pump.pump_name
+                )));
+            }
+            select_stream_syntax
+                .fields
+                .push(SelectFieldSyntax::ValueExpr {
+                    value_expr: priority_condition,
+                    alias: None,
+                });
+        }
+
+        let mut query_plan = self.compile_select_stream(select_stream_syntax, pipeline)?;
+        Self::set_join_build_side(&mut query_plan, pump_options.join_build_side)?;
+        if create_pump.type_checking.unwrap_or(self.type_checking) == TypeChecking::Strict {
+            TypeChecker::check_pump(
+                &create_pump.pump_name,
+                &query_plan,
+                &create_pump.insert_plan,
+                prioritized,
+                pipeline,
+            )?;
+        }
+
+        let pump = PumpModel::new(
+            create_pump.pump_name,
+            query_plan,
+            PumpOutputPlan::Insert(create_pump.insert_plan),
+            prioritized,
+            create_pump.best_effort,
+        )
+        .with_tags(create_pump.tags);
+        Ok(pump)
+    }
+
+    /// Overrides the build side of window join panes, which is the side with fewer tuples in each pane by default.
+    fn set_join_build_side(query_plan: &mut QueryPlan, build_side: JoinBuildSide) -> Result<()> {
+        match &mut query_plan.lower_ops.join {
+            JoinOp::JoinWindow(join_window_op) => {
+                join_window_op.join_param.build_side = build_side;
+                Ok(())
+            }
+            _ if build_side == JoinBuildSide::Auto => Ok(()),
+            _ => Err(SpringError::InvalidOption {
+                key: "JOIN_BUILD_SIDE".to_string(),
+                value: format!("{:?}", build_side).to_uppercase(),
+                source: anyhow!("JOIN_BUILD_SIDE is only for JOIN with window clause"),
+            }),
+        }
+    }
+
+    /// Compiles the statement to explain in the same way as it is executed, without altering the pipeline.
+    fn compile_explain(
+        &self,
+        explain_target: ExplainTarget,
+        pipeline: &Pipeline,
+    ) -> Result<Command> {
+        let query_plan = match explain_target {
+            ExplainTarget::CreatePump(create_pump) => self
+                .compile_pump(create_pump, pipeline)?
+                .query_plan()
+                .clone(),
+            ExplainTarget::SelectStream(select_stream_syntax) => {
+                self.compile_select_stream(select_stream_syntax, pipeline)?
+            }
+        };
+        Ok(Command::Explain(Box::new(query_plan)))
+    }
+
+    /// A router is a pump which selects all columns and conditions from its upstream in one pass:
+    ///
+    /// ```sql
+    /// SELECT STREAM s.c1, s.c2, ..., (condition 1), (condition 2), ... FROM s
+    /// ```
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Upstream or a downstream stream is not found in pipeline.
+    ///   - A downstream stream has different columns from upstream.
+    fn compile_create_router(
+        &self,
+        create_router: CreateRouter,
+        pipeline: &Pipeline,
+    ) -> Result<Command> {
+        let from_stream = pipeline.get_stream(&create_router.from_stream)?;
+
+        let (conditions, when_streams): (Vec<_>, Vec<_>) = create_router.routes.into_iter().unzip();
+        for to_stream in when_streams.iter().chain(create_router.else_stream.iter()) {
+            let to_stream = pipeline.get_stream(to_stream)?;
+            Self::validate_same_columns(&from_stream, &to_stream)?;
+        }
+
+        let query_plan = self.compile_select_all_columns(&from_stream, conditions, pipeline)?;
+
+        let route_plan = RoutePlan::new(
+            from_stream.shape().column_names(),
+            when_streams,
+            create_router.else_stream,
+        );
+        let pump = PumpModel::new(
+            create_router.router_name,
+            query_plan,
+            PumpOutputPlan::Route(route_plan),
+            false,
+            false,
+        );
         Ok(Command::AlterPipeline(AlterPipelineCommand::CreatePump(
             Box::new(pump),
         )))
     }
 
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Upstream or a downstream stream is not found in pipeline.
+    ///   - A downstream stream has different columns from upstream.
+    ///   - A downstream stream appears twice.
+    fn compile_create_replicator(
+        &self,
+        create_replicator: CreateReplicator,
+        pipeline: &Pipeline,
+    ) -> Result<Command> {
+        let from_stream = pipeline.get_stream(&create_replicator.from_stream)?;
+
+        for (i, to_stream) in create_replicator.to_streams.iter().enumerate() {
+            if create_replicator.to_streams[..i].contains(to_stream) {
+                return Err(SpringError::Sql(anyhow!(
+                    r#"replicator destination "{}" appears twice"#,
+                    to_stream
+                )));
+            }
+            let to_stream = pipeline.get_stream(to_stream)?;
+            Self::validate_same_columns(&from_stream, &to_stream)?;
+        }
+
+        let query_plan = self.compile_select_all_columns(&from_stream, vec![], pipeline)?;
+
+        let replicate_plan = ReplicatePlan::new(
+            from_stream.shape().column_names(),
+            create_replicator.to_streams,
+        );
+        let pump = PumpModel::new(
+            create_replicator.replicator_name,
+            query_plan,
+            PumpOutputPlan::Replicate(replicate_plan),
+            false,
+            false,
+        );
+        Ok(Command::AlterPipeline(AlterPipelineCommand::CreatePump(
+            Box::new(pump),
+        )))
+    }
+
+    /// A named source reader is sampled with its type and options, and its stream is the default name of the suggested stream.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - the named source reader does not exist in pipeline.
+    fn compile_infer_schema(
+        &self,
+        infer_schema: InferSchema,
+        pipeline: &Pipeline,
+    ) -> Result<Command> {
+        let (source_reader_type, options, default_stream_name) = match infer_schema.source {
+            InferSchemaSource::Adhoc {
+                source_reader_type,
+                options,
+            } => (
+                source_reader_type,
+                options,
+                StreamName::new(INFERRED_STREAM_NAME.to_string()),
+            ),
+            InferSchemaSource::Named(source_reader_name) => {
+                let source_reader = pipeline
+                    .all_sources()
+                    .into_iter()
+                    .find(|source_reader| source_reader.name() == &source_reader_name)
+                    .ok_or_else(|| {
+                        SpringError::Sql(anyhow!(
+                            r#"source reader "{}" does not exist in pipeline"#,
+                            source_reader_name
+                        ))
+                    })?;
+                (
+                    source_reader.source_reader_type().clone(),
+                    source_reader.options().clone(),
+                    source_reader.dest_source_stream().clone(),
+                )
+            }
+        };
+
+        Ok(Command::InferSchema(InferSchemaCommand::new(
+            infer_schema.stream_name.unwrap_or(default_stream_name),
+            source_reader_type,
+            options,
+            infer_schema.n_samples,
+        )))
+    }
+
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `from_pump` or `to_pump` does not exist in pipeline.
+    ///   - `from_pump` and `to_pump` do not insert into the same streams.
+    fn compile_switch_pump(
+        &self,
+        from_pump: PumpName,
+        to_pump: PumpName,
+        pipeline: &Pipeline,
+    ) -> Result<Command> {
+        let from_pump_model = pipeline.get_pump(&from_pump)?;
+        let to_pump_model = pipeline.get_pump(&to_pump)?;
+
+        if from_pump == to_pump {
+            Err(SpringError::Sql(anyhow!(
+                r#"cannot switch pump "{}" to itself"#,
+                from_pump
+            )))
+        } else if from_pump_model.downstreams() != to_pump_model.downstreams() {
+            Err(SpringError::Sql(anyhow!(
+                r#"pump "{}" must insert into the same streams as "{}""#,
+                to_pump,
+                from_pump
+            )))
+        } else {
+            Ok(Command::AlterPipeline(AlterPipelineCommand::SwitchPump {
+                from_pump,
+                to_pump,
+            }))
+        }
+    }
+
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `stream_name` does not exist in pipeline.
+    ///   - `trace_sample_rate` is not in (0.0, 1.0].
+    fn compile_alter_stream_trace(
+        &self,
+        stream_name: StreamName,
+        trace_sample_rate: Option<f32>,
+        pipeline: &Pipeline,
+    ) -> Result<Command> {
+        let _ = pipeline.get_stream(&stream_name)?;
+
+        match trace_sample_rate {
+            Some(rate) if rate <= 0.0 || rate > 1.0 => Err(SpringError::Sql(anyhow!(
+                "TRACE SAMPLE must be in (0.0, 1.0] but got {}",
+                rate
+            ))),
+            _ => Ok(Command::AlterPipeline(
+                AlterPipelineCommand::AlterStreamTrace {
+                    stream_name,
+                    trace_sample_rate,
+                },
+            )),
+        }
+    }
+
+    /// `SELECT STREAM s.c1, s.c2, ..., (extra expr 1), (extra expr 2), ... FROM s`
+    fn compile_select_all_columns(
+        &self,
+        from_stream: &StreamModel,
+        extra_exprs: Vec<ValueExpr>,
+        pipeline: &Pipeline,
+    ) -> Result<QueryPlan> {
+        let fields = from_stream
+            .column_references()
+            .into_iter()
+            .map(ValueExpr::ColumnReference)
+            .chain(extra_exprs)
+            .map(|value_expr| SelectFieldSyntax::ValueExpr {
+                value_expr,
+                alias: None,
+            })
+            .collect();
+        let select_stream_syntax = SelectStreamSyntax {
+            fields,
+            from_item: FromItemSyntax::StreamVariant(SubFromItemSyntax {
+                stream_name: from_stream.name().clone(),
+                alias: None,
+            }),
+            where_clause: None,
+            grouping_elements: vec![],
+            window_clause: None,
+        };
+        self.compile_select_stream(select_stream_syntax, pipeline)
+    }
+
+    fn validate_same_columns(from_stream: &StreamModel, to_stream: &StreamModel) -> Result<()> {
+        let data_types = |stream: &StreamModel| {
+            let mut data_types = stream
+                .shape()
+                .columns()
+                .iter()
+                .map(|coldef| coldef.column_data_type().clone())
+                .collect::<Vec<_>>();
+            data_types.sort_by(|a, b| a.column_name().cmp(b.column_name()));
+            data_types
+        };
+
+        if data_types(from_stream) == data_types(to_stream) {
+            Ok(())
+        } else {
+            Err(SpringError::Sql(anyhow!(
+                r#"destination "{}" must have the same columns as "{}""#,
+                to_stream.name(),
+                from_stream.name()
+            )))
+        }
+    }
+
     fn compile_select_stream(
         &self,
         select_stream_syntax: SelectStreamSyntax,
@@ -122,14 +583,16 @@ impl SqlProcessor {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use super::*;
     use crate::{
         pipeline::{
-            OptionsBuilder, PipelineVersion, SinkWriterModel, SinkWriterName, SinkWriterType,
-            SourceReaderModel, SourceReaderName, SourceReaderType, StreamModel, StreamName,
-            StreamShape,
+            ColumnName, OptionsBuilder, PipelineVersion, SinkWriterModel, SinkWriterName,
+            SinkWriterType, SourceReaderModel, SourceReaderName, SourceReaderType, StreamModel,
+            StreamName, StreamShape, Tags,
         },
-        stream_engine::command::AlterPipelineCommand,
+        stream_engine::command::{AlterPipelineCommand, InferSchemaCommand, ShowObjectsCommand},
     };
     use pretty_assertions::assert_eq;
 
@@ -157,6 +620,137 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_create_stream_with_tags() {
+        let processor = SqlProcessor::default();
+        let pipeline = Pipeline::new(PipelineVersion::new());
+
+        let sql = "
+            CREATE STREAM trade (
+              ts TIMESTAMP NOT NULL ROWTIME,    
+              ticker TEXT NOT NULL,
+              amount INTEGER NOT NULL
+            ) WITH TAGS ('subsystem' = 'brakes');
+            ";
+        let command = processor.compile(sql, &pipeline).unwrap();
+
+        let expected_tags =
+            Tags::new(vec![("subsystem".to_string(), "brakes".to_string())]).unwrap();
+        let expected_stream = StreamModel::new(
+            StreamName::new("trade".to_string()),
+            StreamShape::fx_trade(),
+        )
+        .with_tags(expected_tags);
+
+        assert_eq!(
+            command,
+            Command::AlterPipeline(AlterPipelineCommand::CreateStream(expected_stream))
+        );
+    }
+
+    #[test]
+    fn test_comments() {
+        let processor = SqlProcessor::default();
+        let pipeline = Pipeline::new(PipelineVersion::new());
+
+        let sql = "
+            -- trades from the exchange
+            CREATE STREAM trade ( /* columns
+              are ordered by ROWTIME first */
+              ts TIMESTAMP NOT NULL ROWTIME,    -- event time
+              ticker TEXT NOT NULL,
+              amount INTEGER NOT NULL
+            ) WITH TAGS ('note' = ' -- not a comment /* */'); -- trailing comment
+            ";
+        let command = processor.compile(sql, &pipeline).unwrap();
+
+        let expected_tags = Tags::new(vec![(
+            "note".to_string(),
+            " -- not a comment /* */".to_string(),
+        )])
+        .unwrap();
+        let expected_stream = StreamModel::new(
+            StreamName::new("trade".to_string()),
+            StreamShape::fx_trade(),
+        )
+        .with_tags(expected_tags);
+
+        assert_eq!(
+            command,
+            Command::AlterPipeline(AlterPipelineCommand::CreateStream(expected_stream))
+        );
+    }
+
+    #[test]
+    fn test_create_stream_with_quoted_identifiers() {
+        let processor = SqlProcessor::default();
+        let pipeline = Pipeline::new(PipelineVersion::new());
+
+        let sql = r#"
+            CREATE STREAM "can0.speed-raw" (
+              "ts" TIMESTAMP NOT NULL ROWTIME,
+              "wheel-speed" INTEGER NOT NULL,
+              "say ""hi""" TEXT,
+              "select" TEXT,
+              速度 FLOAT
+            );
+            "#;
+        let command = processor.compile(sql, &pipeline).unwrap();
+
+        if let Command::AlterPipeline(AlterPipelineCommand::CreateStream(stream)) = command {
+            assert_eq!(stream.name().as_ref(), "can0.speed-raw");
+            assert_eq!(
+                stream
+                    .shape()
+                    .column_names()
+                    .iter()
+                    .map(|c| c.as_ref())
+                    .collect::<Vec<_>>(),
+                vec!["ts", "wheel-speed", "say \"hi\"", "select", "速度"]
+            );
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn test_create_stream_in_namespace() {
+        let processor = SqlProcessor::default();
+        let pipeline = Pipeline::new(PipelineVersion::new());
+
+        let sql = "
+            CREATE STREAM tenant_a.trade (
+              ts TIMESTAMP NOT NULL ROWTIME,
+              ticker TEXT NOT NULL,
+              amount INTEGER NOT NULL
+            );
+            ";
+        let command = processor.compile(sql, &pipeline).unwrap();
+
+        if let Command::AlterPipeline(AlterPipelineCommand::CreateStream(stream)) = command {
+            assert_eq!(stream.name().as_ref(), "tenant_a.trade");
+            assert_eq!(stream.name().namespace(), Some("tenant_a"));
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn test_to_identifier() {
+        assert_eq!(PestParserImpl::to_identifier("speed"), "speed");
+        assert_eq!(PestParserImpl::to_identifier("速度"), "速度");
+        assert_eq!(PestParserImpl::to_identifier("selection"), "selection");
+        assert_eq!(PestParserImpl::to_identifier("select"), "\"select\"");
+        assert_eq!(
+            PestParserImpl::to_identifier("can0.speed-raw"),
+            "\"can0.speed-raw\""
+        );
+        assert_eq!(
+            PestParserImpl::to_identifier("say \"hi\""),
+            "\"say \"\"hi\"\"\"\""
+        );
+    }
+
     #[test]
     fn test_create_source_reader() {
         let processor = SqlProcessor::default();
@@ -241,4 +835,434 @@ mod tests {
             Command::AlterPipeline(AlterPipelineCommand::CreateSinkWriter(expected_sink))
         );
     }
+
+    #[test]
+    fn test_create_sink_writer_filter_project() {
+        let processor = SqlProcessor::default();
+        let pipeline = Pipeline::fx_sink_only();
+
+        let sql = "
+            CREATE SINK WRITER tcp_sink_trade FOR sink_1
+              FILTER WHERE sink_1.amount >= 100
+              PROJECT (ts, ticker)
+              TYPE NET_CLIENT OPTIONS (
+                REMOTE_PORT '17890'
+              );
+            ";
+        let command = processor.compile(sql, &pipeline).unwrap();
+        if let Command::AlterPipeline(AlterPipelineCommand::CreateSinkWriter(sink_writer)) = command
+        {
+            assert!(sink_writer.filter().is_some());
+            assert_eq!(
+                sink_writer.projection().unwrap(),
+                &[ColumnName::fx_timestamp(), ColumnName::fx_ticker()]
+            );
+        } else {
+            unreachable!("{:?}", command)
+        }
+
+        let sql = "
+            CREATE SINK WRITER tcp_sink_trade FOR sink_1
+              PROJECT (ts, price)
+              TYPE NET_CLIENT OPTIONS (
+                REMOTE_PORT '17890'
+              );
+            ";
+        assert!(processor.compile(sql, &pipeline).is_err());
+    }
+
+    #[test]
+    fn test_infer_schema() {
+        let processor = SqlProcessor::default();
+        let pipeline = Pipeline::new(PipelineVersion::new());
+
+        let sql = "
+            INFER SCHEMA FOR source_trade
+              FROM SOURCE READER TYPE NET_SERVER OPTIONS (
+                PROTOCOL 'TCP',
+                PORT '17890'
+              )
+              SAMPLE 100;
+            ";
+        let command = processor.compile(sql, &pipeline).unwrap();
+
+        let expected_options = OptionsBuilder::default()
+            .add("PROTOCOL", "TCP")
+            .add("PORT", "17890")
+            .build();
+
+        assert_eq!(
+            command,
+            Command::InferSchema(InferSchemaCommand::new(
+                StreamName::new("source_trade".to_string()),
+                SourceReaderType::NetServer,
+                expected_options,
+                100
+            ))
+        );
+    }
+
+    #[test]
+    fn test_infer_schema_from_named_source_reader() {
+        let processor = SqlProcessor::default();
+        let mut pipeline = Pipeline::new(PipelineVersion::new());
+
+        let options = OptionsBuilder::default()
+            .add("PROTOCOL", "TCP")
+            .add("PORT", "17890")
+            .build();
+        pipeline
+            .add_stream(Arc::new(StreamModel::new(
+                StreamName::new("source_trade".to_string()),
+                StreamShape::fx_trade(),
+            )))
+            .unwrap();
+        pipeline
+            .add_source_reader(SourceReaderModel::new(
+                SourceReaderName::new("tcp_trade".to_string()),
+                SourceReaderType::NetServer,
+                StreamName::new("source_trade".to_string()),
+                options.clone(),
+            ))
+            .unwrap();
+
+        let command = processor
+            .compile(
+                "INFER SCHEMA FROM SOURCE READER tcp_trade SAMPLE 10;",
+                &pipeline,
+            )
+            .unwrap();
+        assert_eq!(
+            command,
+            Command::InferSchema(InferSchemaCommand::new(
+                StreamName::new("source_trade".to_string()),
+                SourceReaderType::NetServer,
+                options,
+                10
+            ))
+        );
+
+        assert!(processor
+            .compile(
+                "INFER SCHEMA FROM SOURCE READER no_such_reader SAMPLE 10;",
+                &pipeline,
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_show_history() {
+        let processor = SqlProcessor::default();
+        let pipeline = Pipeline::new(PipelineVersion::new());
+
+        let command = processor.compile("show history;", &pipeline).unwrap();
+        assert_eq!(command, Command::ShowHistory);
+    }
+
+    #[test]
+    fn test_show_warnings() {
+        let processor = SqlProcessor::default();
+        let pipeline = Pipeline::new(PipelineVersion::new());
+
+        let command = processor.compile("SHOW WARNINGS", &pipeline).unwrap();
+        assert_eq!(command, Command::ShowWarnings);
+    }
+
+    #[test]
+    fn test_show_objects() {
+        let processor = SqlProcessor::default();
+        let pipeline = Pipeline::new(PipelineVersion::new());
+
+        for (sql, expected) in [
+            ("SHOW STREAMS", ShowObjectsCommand::Streams),
+            ("show pumps;", ShowObjectsCommand::Pumps),
+            ("SHOW SOURCE READERS", ShowObjectsCommand::SourceReaders),
+            ("SHOW SINK WRITERS;", ShowObjectsCommand::SinkWriters),
+        ] {
+            let command = processor.compile(sql, &pipeline).unwrap();
+            assert_eq!(command, Command::ShowObjects(expected));
+        }
+        assert!(processor.compile("SHOW SINKS", &pipeline).is_err());
+    }
+
+    #[test]
+    fn test_drop() {
+        let processor = SqlProcessor::default();
+        let pipeline = Pipeline::new(PipelineVersion::new());
+
+        for (sql, expected) in [
+            (
+                "DROP PUMP pu;",
+                AlterPipelineCommand::DropPump(PumpName::new("pu".to_string())),
+            ),
+            (
+                "DROP STREAM st;",
+                AlterPipelineCommand::DropStream(StreamName::new("st".to_string())),
+            ),
+            (
+                "DROP SOURCE READER sr;",
+                AlterPipelineCommand::DropSourceReader(SourceReaderName::new("sr".to_string())),
+            ),
+            (
+                "DROP SINK WRITER sw;",
+                AlterPipelineCommand::DropSinkWriter(SinkWriterName::new("sw".to_string())),
+            ),
+        ] {
+            let command = processor.compile(sql, &pipeline).unwrap();
+            assert_eq!(command, Command::AlterPipeline(expected));
+        }
+    }
+
+    #[test]
+    fn test_alter_pump() {
+        let processor = SqlProcessor::default();
+        let pipeline = Pipeline::new(PipelineVersion::new());
+
+        for (sql, stopped) in [
+            ("ALTER PUMP pu STOP;", true),
+            ("alter pump pu start", false),
+        ] {
+            let command = processor.compile(sql, &pipeline).unwrap();
+            assert_eq!(
+                command,
+                Command::AlterPipeline(AlterPipelineCommand::AlterPumpState {
+                    pump_name: PumpName::new("pu".to_string()),
+                    stopped
+                })
+            );
+        }
+        assert!(processor.compile("ALTER PUMP pu;", &pipeline).is_err());
+    }
+
+    #[test]
+    fn test_infer_schema_zero_sample() {
+        let processor = SqlProcessor::default();
+        let pipeline = Pipeline::new(PipelineVersion::new());
+
+        let sql = "
+            INFER SCHEMA FROM SOURCE READER TYPE IN_MEMORY_QUEUE OPTIONS (
+                NAME 'q'
+              )
+              SAMPLE 0;
+            ";
+        assert!(processor.compile(sql, &pipeline).is_err());
+    }
+
+    fn fx_pipeline_for_type_checking() -> Pipeline {
+        let processor = SqlProcessor::default();
+        let mut pipeline = Pipeline::new(PipelineVersion::new());
+
+        for sql in [
+            "CREATE STREAM source_1 (ts TIMESTAMP NOT NULL ROWTIME, code TEXT, amount INTEGER NOT NULL);",
+            "CREATE STREAM sink_1 (ts TIMESTAMP NOT NULL ROWTIME, code TEXT NOT NULL, amount INTEGER NOT NULL);",
+        ] {
+            match processor.compile(sql, &pipeline).unwrap() {
+                Command::AlterPipeline(AlterPipelineCommand::CreateStream(stream)) => {
+                    pipeline.add_stream(Arc::new(stream)).unwrap()
+                }
+                _ => unreachable!(),
+            }
+        }
+        pipeline
+    }
+
+    fn create_pump(type_checking: &str, select_list: &str, where_clause: &str) -> String {
+        format!(
+            "CREATE PUMP pump_1 {} AS INSERT INTO sink_1 (ts, code, amount) SELECT STREAM {} FROM source_1 {};",
+            type_checking, select_list, where_clause
+        )
+    }
+
+    #[test]
+    fn test_create_pump_strict_type_checking() {
+        let processor = SqlProcessor::new(TypeChecking::Strict);
+        let pipeline = fx_pipeline_for_type_checking();
+        let compile_err = |select_list: &str, where_clause: &str| {
+            processor
+                .compile(create_pump("", select_list, where_clause), &pipeline)
+                .unwrap_err()
+                .to_string()
+        };
+
+        for (select_list, where_clause) in [
+            (
+                "source_1.ts, COALESCE(source_1.code, 'none'), source_1.amount",
+                "",
+            ),
+            ("source_1.ts, 'E042', 42", "WHERE source_1.code LIKE 'E%'"),
+        ] {
+            processor
+                .compile(create_pump("", select_list, where_clause), &pipeline)
+                .unwrap();
+        }
+
+        assert_eq!(
+            compile_err("source_1.ts, 'E042', source_1.amount + 1", ""),
+            r#"type mismatch in pump "pump_1": column "amount" of stream "sink_1" is INTEGER NOT NULL but BIGINT NOT NULL is inserted"#
+        );
+        assert_eq!(
+            compile_err("source_1.ts, source_1.code, source_1.amount", ""),
+            r#"type mismatch in pump "pump_1": column "code" of stream "sink_1" is TEXT NOT NULL but TEXT is inserted"#
+        );
+        assert_eq!(
+            compile_err("source_1.ts, 'E042', 'E042'", ""),
+            r#"type mismatch in pump "pump_1": column "amount" of stream "sink_1" is INTEGER NOT NULL but "E042" is inserted"#
+        );
+        assert_eq!(
+            compile_err(
+                "source_1.ts, 'E042', source_1.amount",
+                "WHERE source_1.code + 1"
+            ),
+            r#"type mismatch in pump "pump_1": `+` cannot take TEXT and SMALLINT NOT NULL"#
+        );
+        assert_eq!(
+            compile_err(
+                "source_1.ts, 'E042', source_1.amount",
+                "WHERE source_1.amount"
+            ),
+            r#"type mismatch in pump "pump_1": WHERE condition must be BOOLEAN but is INTEGER NOT NULL"#
+        );
+        assert_eq!(
+            compile_err("source_1.ts, 'E042'", ""),
+            r#"type mismatch in pump "pump_1": 3 columns of stream "sink_1" are inserted but 2 fields are selected"#
+        );
+    }
+
+    #[test]
+    fn test_create_pump_type_checking_override() {
+        let pipeline = fx_pipeline_for_type_checking();
+        let select_list = "source_1.ts, source_1.code, source_1.amount + 1";
+
+        let lenient_processor = SqlProcessor::default();
+        assert!(lenient_processor
+            .compile(create_pump("", select_list, ""), &pipeline)
+            .is_ok());
+        assert!(lenient_processor
+            .compile(create_pump("STRICT", select_list, ""), &pipeline)
+            .is_err());
+
+        let strict_processor = SqlProcessor::new(TypeChecking::Strict);
+        assert!(strict_processor
+            .compile(create_pump("LENIENT", select_list, ""), &pipeline)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_create_pump_join_build_side() {
+        let processor = SqlProcessor::default();
+        let mut pipeline = fx_pipeline_for_type_checking();
+        match processor
+            .compile(
+                "CREATE STREAM source_2 (ts TIMESTAMP NOT NULL ROWTIME, code TEXT NOT NULL);",
+                &pipeline,
+            )
+            .unwrap()
+        {
+            Command::AlterPipeline(AlterPipelineCommand::CreateStream(stream)) => {
+                pipeline.add_stream(Arc::new(stream)).unwrap()
+            }
+            _ => unreachable!(),
+        }
+
+        let build_side = |options: &str| -> Result<JoinBuildSide> {
+            let sql = format!(
+                "CREATE PUMP pump_1 AS INSERT INTO sink_1 (ts, code, amount)
+                   SELECT STREAM source_1.ts, source_2.code, source_1.amount FROM source_1
+                   LEFT OUTER JOIN source_2 ON source_1.code = source_2.code
+                   FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0) {};",
+                options
+            );
+            match processor.compile(sql, &pipeline)? {
+                Command::AlterPipeline(AlterPipelineCommand::CreatePump(pump)) => {
+                    match &pump.query_plan().lower_ops.join {
+                        JoinOp::JoinWindow(join_window_op) => {
+                            Ok(join_window_op.join_param.build_side)
+                        }
+                        join_op => unreachable!("{:?}", join_op),
+                    }
+                }
+                _ => unreachable!(),
+            }
+        };
+
+        assert_eq!(build_side("").unwrap(), JoinBuildSide::Auto);
+        assert_eq!(
+            build_side("OPTIONS (JOIN_BUILD_SIDE 'LEFT')").unwrap(),
+            JoinBuildSide::Left
+        );
+        assert_eq!(
+            build_side("OPTIONS (JOIN_BUILD_SIDE 'RIGHT')").unwrap(),
+            JoinBuildSide::Right
+        );
+        assert!(matches!(
+            build_side("OPTIONS (JOIN_BUILD_SIDE 'SMALLER')").unwrap_err(),
+            SpringError::InvalidOption { .. }
+        ));
+
+        // only for window joins
+        let select_list = "source_1.ts, 'E042', source_1.amount";
+        assert!(processor
+            .compile(
+                create_pump("", select_list, "OPTIONS (JOIN_BUILD_SIDE 'AUTO')"),
+                &pipeline
+            )
+            .is_ok());
+        assert!(matches!(
+            processor
+                .compile(
+                    create_pump("", select_list, "OPTIONS (JOIN_BUILD_SIDE 'LEFT')"),
+                    &pipeline
+                )
+                .unwrap_err(),
+            SpringError::InvalidOption { .. }
+        ));
+    }
+
+    #[test]
+    fn test_explain() {
+        let processor = SqlProcessor::new(TypeChecking::Strict);
+        let pipeline = fx_pipeline_for_type_checking();
+        let select_list = "source_1.ts, 'E042', source_1.amount";
+        let where_clause = "WHERE source_1.amount > 10";
+
+        let query_plan = match processor
+            .compile(create_pump("", select_list, where_clause), &pipeline)
+            .unwrap()
+        {
+            Command::AlterPipeline(AlterPipelineCommand::CreatePump(pump)) => {
+                pump.query_plan().clone()
+            }
+            _ => unreachable!(),
+        };
+
+        let command = processor
+            .compile(
+                format!("EXPLAIN {}", create_pump("", select_list, where_clause)),
+                &pipeline,
+            )
+            .unwrap();
+        assert_eq!(command, Command::Explain(Box::new(query_plan.clone())));
+
+        let command = processor
+            .compile(
+                format!(
+                    "explain SELECT STREAM {} FROM source_1 {}",
+                    select_list, where_clause
+                ),
+                &pipeline,
+            )
+            .unwrap();
+        assert_eq!(command, Command::Explain(Box::new(query_plan)));
+
+        // same checks as CREATE PUMP
+        assert!(processor
+            .compile(
+                format!(
+                    "EXPLAIN {}",
+                    create_pump("", "source_1.ts, 'E042', 'E042'", "")
+                ),
+                &pipeline
+            )
+            .is_err());
+    }
 }
@@ -57,18 +57,22 @@
 
 mod select_syntax_analyzer;
 
+use anyhow::anyhow;
+
 use crate::{
-    api::error::Result,
-    expr_resolver::{ExprLabel, ExprResolver},
+    api::error::{Result, SpringError},
+    expr_resolver::{ExprLabel, ExprResolver, ValueExprLabel},
+    expression::ValueExpr,
     pipeline::{
-        AggregateParameter, GroupByLabels, Pipeline, WindowOperationParameter, WindowParameter,
+        AggregateParameter, GroupByLabels, Pipeline, WindowMetadata, WindowOperationParameter,
+        WindowParameter,
     },
     sql_processor::{
         query_planner::select_syntax_analyzer::SelectSyntaxAnalyzer,
         sql_parser::{GroupingElementSyntax, SelectStreamSyntax},
     },
     stream_engine::command::{
-        GroupAggregateWindowOp, JoinOp, LowerOps, ProjectionOp, QueryPlan, UpperOps,
+        GroupAggregateWindowOp, JoinOp, LowerOps, ProjectionOp, QueryPlan, SelectionOp, UpperOps,
     },
 };
 
@@ -100,7 +104,8 @@ impl QueryPlanner {
         };
 
         let join = self.create_join_op(&mut expr_resolver, pipeline)?;
-        let lower_ops = LowerOps { join };
+        let selection = self.create_selection_op(&mut expr_resolver);
+        let lower_ops = LowerOps { join, selection };
 
         Ok(QueryPlan::new(upper_ops, lower_ops, expr_resolver))
     }
@@ -118,10 +123,40 @@ impl QueryPlanner {
                 window_param,
                 op_param: WindowOperationParameter::Aggregate(group_aggr_param),
             })),
-            _ => Ok(None),
+            _ => {
+                if Self::window_metadata(projection_op, expr_resolver).is_empty() {
+                    Ok(None)
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "WINDOW_START, WINDOW_END and PANE_ROW_COUNT are only available in aggregation over window"
+                    )))
+                }
+            }
         }
     }
 
+    /// Window metadata (pseudo-columns) in select_list.
+    fn window_metadata(
+        projection_op: &ProjectionOp,
+        expr_resolver: &ExprResolver,
+    ) -> Vec<(ValueExprLabel, WindowMetadata)> {
+        projection_op
+            .expr_labels
+            .iter()
+            .filter_map(|label| match label {
+                ExprLabel::Value(value_label) => {
+                    match expr_resolver.resolve_value_expr(*value_label) {
+                        ValueExpr::WindowMetadata(window_metadata) => {
+                            Some((*value_label, window_metadata))
+                        }
+                        _ => None,
+                    }
+                }
+                ExprLabel::Aggr(_) => None,
+            })
+            .collect()
+    }
+
     fn create_window_param(&self) -> Option<WindowParameter> {
         self.analyzer.window_parameter()
     }
@@ -144,32 +179,40 @@ impl QueryPlanner {
             })
             .collect::<Vec<_>>();
 
-        match aggr_labels.len() {
-            1 => {
-                let aggr_label = aggr_labels.get(0).expect("len checked");
-                let aggr_func = expr_resolver.resolve_aggr_expr(*aggr_label).func;
-
-                let group_by_labels = grouping_elements
-                    .iter()
-                    .map(|grouping_elem| match grouping_elem {
-                        GroupingElementSyntax::ValueExpr(expr) => {
-                            Ok(expr_resolver.register_value_expr(expr.clone()))
-                        }
-                        GroupingElementSyntax::ValueAlias(alias) => {
-                            expr_resolver.resolve_value_alias(alias.clone())
-                        }
-                    })
-                    .collect::<Result<Vec<_>>>()?;
-
-                Ok(Some(AggregateParameter::new(
-                    aggr_func,
-                    *aggr_label,
-                    GroupByLabels::new(group_by_labels),
-                )))
-            }
-            0 => Ok(None),
-            _ => unimplemented!("2 or more aggregate expressions"),
+        if aggr_labels.is_empty() {
+            return Ok(None);
         }
+
+        let aggr_exprs = aggr_labels
+            .into_iter()
+            .map(|aggr_label| (expr_resolver.resolve_aggr_expr(aggr_label).func, aggr_label))
+            .collect();
+
+        let group_by_labels = grouping_elements
+            .iter()
+            .map(|grouping_elem| match grouping_elem {
+                GroupingElementSyntax::ValueExpr(expr) => {
+                    Ok(expr_resolver.register_value_expr(expr.clone()))
+                }
+                GroupingElementSyntax::ValueAlias(alias) => {
+                    expr_resolver.resolve_value_alias(alias.clone())
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Some(AggregateParameter::new(
+            aggr_exprs,
+            GroupByLabels::new(group_by_labels),
+            Self::window_metadata(projection_op, expr_resolver),
+        )))
+    }
+
+    fn create_selection_op(&self, expr_resolver: &mut ExprResolver) -> Option<SelectionOp> {
+        self.analyzer
+            .where_condition()
+            .map(|condition| SelectionOp {
+                condition: expr_resolver.register_value_expr(condition),
+            })
     }
 
     fn create_join_op(
@@ -1,14 +1,25 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use anyhow::anyhow;
+
 use crate::{
-    api::error::Result,
-    expr_resolver::ExprResolver,
-    pipeline::{JoinParameter, Pipeline},
+    api::error::{Result, SpringError},
+    expr_resolver::{ExprResolver, ValueExprLabel},
+    expression::{BinaryExpr, ComparisonFunction, LogicalFunction, NumericalFunction, ValueExpr},
+    pipeline::{
+        ColumnReference, IntervalJoinBound, JoinKey, JoinParameter, Pipeline, StreamModel,
+        StreamName,
+    },
     sql_processor::{
         query_planner::SelectSyntaxAnalyzer,
         sql_parser::{FromItemSyntax, SubFromItemSyntax},
     },
-    stream_engine::command::{CollectOp, JoinOp, JoinWindowOp},
+    stream_engine::{
+        command::{CollectOp, IntervalJoinOp, JoinOp, JoinWindowOp},
+        time::SpringDuration,
+        NnSqlValue, SqlValue,
+    },
+    time::Duration,
 };
 
 impl SelectSyntaxAnalyzer {
@@ -32,29 +43,255 @@ impl SelectSyntaxAnalyzer {
                 let left_collect_op = Self::sub_from_item_to_collect_op(left_sub.clone());
                 let right_collect_op = Self::sub_from_item_to_collect_op(right_sub.clone());
 
-                let left_colrefs = pipeline
-                    .get_stream(&left_sub.stream_name)?
-                    .column_references();
-                let right_colrefs = pipeline
-                    .get_stream(&right_sub.stream_name)?
-                    .column_references();
+                let left_stream = pipeline.get_stream(&left_sub.stream_name)?;
+                let right_stream = pipeline.get_stream(&right_sub.stream_name)?;
+
+                let mut conjuncts = Self::conjuncts(on_expr);
+
+                match self.window_parameter() {
+                    Some(window_param) => {
+                        if window_param.emit_on_update() {
+                            return Err(SpringError::Sql(anyhow!(
+                                "EMIT ON UPDATE is only available for window aggregation, not for JOIN"
+                            )));
+                        }
+
+                        let (keys, on_expr_label) = Self::split_join_keys(
+                            expr_resolver,
+                            conjuncts,
+                            left_stream.name(),
+                            right_stream.name(),
+                        );
+                        let join_param = JoinParameter::new(
+                            join_type,
+                            left_stream.column_references(),
+                            right_stream.column_references(),
+                            keys,
+                            on_expr_label,
+                        );
+
+                        Ok(JoinOp::JoinWindow(JoinWindowOp {
+                            left: left_collect_op,
+                            right: right_collect_op,
+                            window_param,
+                            join_param,
+                        }))
+                    }
+                    None => {
+                        let rowtime_colrefs = [
+                            Self::rowtime_colref(left_stream.as_ref()),
+                            Self::rowtime_colref(right_stream.as_ref()),
+                        ];
+                        let bound_idx = conjuncts
+                            .iter()
+                            .map(|conjunct| {
+                                Self::interval_join_bound(conjunct, &rowtime_colrefs)
+                            })
+                            .collect::<Result<Vec<_>>>()?
+                            .into_iter()
+                            .position(|bound| bound.is_some())
+                            .ok_or_else(|| {
+                                SpringError::Sql(anyhow!(
+                                    "JOIN without window clause must bound ROWTIMEs in ON clause (e.g. `ON r.ts BETWEEN l.ts AND l.ts + INTERVAL '5' SECOND`)"
+                                ))
+                            })?;
+                        let bound = Self::interval_join_bound(
+                            &conjuncts.remove(bound_idx),
+                            &rowtime_colrefs,
+                        )?
+                        .expect("found above");
+
+                        let (keys, on_expr_label) = Self::split_join_keys(
+                            expr_resolver,
+                            conjuncts,
+                            left_stream.name(),
+                            right_stream.name(),
+                        );
+                        let join_param = JoinParameter::new(
+                            join_type,
+                            left_stream.column_references(),
+                            right_stream.column_references(),
+                            keys,
+                            on_expr_label,
+                        );
+
+                        Ok(JoinOp::IntervalJoin(IntervalJoinOp {
+                            left: left_collect_op,
+                            right: right_collect_op,
+                            bound,
+                            join_param,
+                        }))
+                    }
+                }
+            }
+        }
+    }
+
+    /// Column reference to ROWTIME (event time column or processing time) of a stream.
+    fn rowtime_colref(stream: &StreamModel) -> ColumnReference {
+        let stream_name = stream.name().clone();
+        match stream.shape().event_time() {
+            Some(column_name) => ColumnReference::Column {
+                stream_name,
+                column_name: column_name.clone(),
+            },
+            None => ColumnReference::PTime { stream_name },
+        }
+    }
+
+    /// Extracts the time bound from `right.ts BETWEEN left.ts [+ d1] AND left.ts + d2` (or `left.ts BETWEEN right.ts ...`).
+    ///
+    /// # Returns
+    ///
+    /// `None` if `conjunct` is not a time bound between ROWTIMEs of left and right.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - the time bound is empty (lower > upper).
+    fn interval_join_bound(
+        conjunct: &ValueExpr,
+        rowtime_colrefs: &[ColumnReference; 2],
+    ) -> Result<Option<IntervalJoinBound>> {
+        let (target, low, high) = match conjunct {
+            ValueExpr::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+                ComparisonFunction::BetweenVariant {
+                    target,
+                    low,
+                    high,
+                    negated: false,
+                },
+            )) => (target, low, high),
+            _ => return Ok(None),
+        };
 
-                let on_expr_label = expr_resolver.register_value_expr(on_expr);
+        let rowtime_offsets = (
+            Self::rowtime_offset(target, rowtime_colrefs),
+            Self::rowtime_offset(low, rowtime_colrefs),
+            Self::rowtime_offset(high, rowtime_colrefs),
+        );
+        let (target_colref, target_offset, low_offset, high_offset) = match rowtime_offsets {
+            (Some((t, t_offset)), Some((l, l_offset)), Some((h, h_offset))) if l == h && t != l => {
+                (t, t_offset, l_offset, h_offset)
+            }
+            _ => return Ok(None),
+        };
+
+        // target.ts BETWEEN other.ts + (low_offset - target_offset) AND other.ts + (high_offset - target_offset)
+        let low_diff = low_offset + (-target_offset);
+        let high_diff = high_offset + (-target_offset);
+        let bound = if target_colref == &rowtime_colrefs[1] {
+            IntervalJoinBound::new(low_diff, high_diff)
+        } else {
+            IntervalJoinBound::new(-high_diff, -low_diff)
+        };
+
+        if bound.lower > bound.upper {
+            Err(SpringError::Sql(anyhow!(
+                "time bound of JOIN is empty: {:?}",
+                conjunct
+            )))
+        } else {
+            Ok(Some(bound))
+        }
+    }
+
+    /// `Some((rowtime_colref, offset))` if `expr` is `ROWTIME [+ duration]` of left or right.
+    fn rowtime_offset<'a>(
+        expr: &ValueExpr,
+        rowtime_colrefs: &'a [ColumnReference; 2],
+    ) -> Option<(&'a ColumnReference, Duration)> {
+        let as_rowtime = |expr: &ValueExpr| match expr {
+            ValueExpr::ColumnReference(colref) => {
+                rowtime_colrefs.iter().find(|rowtime| *rowtime == colref)
+            }
+            _ => None,
+        };
+        let as_duration = |expr: &ValueExpr| match expr {
+            ValueExpr::Constant(SqlValue::NotNull(NnSqlValue::Duration(d))) => {
+                Some(d.to_duration())
+            }
+            _ => None,
+        };
 
-                let join_param =
-                    JoinParameter::new(join_type, left_colrefs, right_colrefs, on_expr_label);
+        match expr {
+            ValueExpr::BinaryExpr(BinaryExpr::NumericalFunctionVariant(
+                NumericalFunction::AddVariant { left, right },
+            )) => as_rowtime(left)
+                .zip(as_duration(right))
+                .or_else(|| as_rowtime(right).zip(as_duration(left))),
+            _ => as_rowtime(expr).map(|rowtime| (rowtime, Duration::seconds(0))),
+        }
+    }
+
+    /// Splits conjuncts of ON clause into join keys (`left_expr = right_expr`) and the rest.
+    fn split_join_keys(
+        expr_resolver: &mut ExprResolver,
+        conjuncts: Vec<ValueExpr>,
+        left_stream: &StreamName,
+        right_stream: &StreamName,
+    ) -> (Vec<JoinKey>, Option<ValueExprLabel>) {
+        let refers_only = |expr: &ValueExpr, stream: &StreamName| {
+            let colrefs = expr.column_references();
+            !colrefs.is_empty() && colrefs.iter().all(|colref| colref.stream_name() == stream)
+        };
+
+        let mut keys = Vec::new();
+        let mut rest: Option<ValueExpr> = None;
+        for conjunct in conjuncts {
+            let key_exprs = match &conjunct {
+                ValueExpr::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+                    ComparisonFunction::EqualVariant { left, right },
+                )) => {
+                    if refers_only(left.as_ref(), left_stream)
+                        && refers_only(right.as_ref(), right_stream)
+                    {
+                        Some((left.as_ref().clone(), right.as_ref().clone()))
+                    } else if refers_only(left.as_ref(), right_stream)
+                        && refers_only(right.as_ref(), left_stream)
+                    {
+                        Some((right.as_ref().clone(), left.as_ref().clone()))
+                    } else {
+                        None
+                    }
+                }
+                _ => None,
+            };
 
-                let window_param = self
-                    .window_parameter()
-                    .expect("JOIN must take window clause");
+            match key_exprs {
+                Some((left_expr, right_expr)) => keys.push(JoinKey::new(
+                    expr_resolver.register_value_expr(left_expr),
+                    expr_resolver.register_value_expr(right_expr),
+                )),
+                None => {
+                    rest = Some(match rest {
+                        None => conjunct,
+                        Some(rest) => ValueExpr::BinaryExpr(BinaryExpr::LogicalFunctionVariant(
+                            LogicalFunction::AndVariant {
+                                left: Box::new(rest),
+                                right: Box::new(conjunct),
+                            },
+                        )),
+                    })
+                }
+            }
+        }
+
+        let rest_label = rest.map(|rest| expr_resolver.register_value_expr(rest));
+        (keys, rest_label)
+    }
 
-                Ok(JoinOp::JoinWindow(JoinWindowOp {
-                    left: left_collect_op,
-                    right: right_collect_op,
-                    window_param,
-                    join_param,
-                }))
+    /// `a AND (b AND c)` -> `[a, b, c]`
+    fn conjuncts(expr: ValueExpr) -> Vec<ValueExpr> {
+        match expr {
+            ValueExpr::BinaryExpr(BinaryExpr::LogicalFunctionVariant(
+                LogicalFunction::AndVariant { left, right },
+            )) => {
+                let mut conjuncts = Self::conjuncts(*left);
+                conjuncts.extend(Self::conjuncts(*right));
+                conjuncts
             }
+            _ => vec![expr],
         }
     }
 
@@ -0,0 +1,9 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use crate::{expression::ValueExpr, sql_processor::query_planner::SelectSyntaxAnalyzer};
+
+impl SelectSyntaxAnalyzer {
+    pub fn where_condition(&self) -> Option<ValueExpr> {
+        self.select_syntax.where_clause.clone()
+    }
+}
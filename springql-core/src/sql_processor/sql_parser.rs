@@ -4,7 +4,10 @@ mod parse_success;
 mod pest_parser_impl;
 mod syntax;
 
-pub use parse_success::{CreatePump, ParseSuccess};
+pub use parse_success::{
+    CreatePump, CreateReplicator, CreateRouter, ExplainTarget, InferSchema, InferSchemaSource,
+    ParseSuccess,
+};
 pub use pest_parser_impl::PestParserImpl;
 pub use syntax::*;
 
@@ -1,9 +1,13 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 use crate::{
-    pipeline::{PumpName, SinkWriterModel, SourceReaderModel, StreamModel},
-    sql_processor::sql_parser::syntax::SelectStreamSyntax,
-    stream_engine::command::InsertPlan,
+    expression::ValueExpr,
+    pipeline::{
+        Options, PumpName, SinkWriterModel, SinkWriterName, SourceReaderModel, SourceReaderName,
+        SourceReaderType, StreamModel, StreamName, Tags,
+    },
+    sql_processor::{sql_parser::syntax::SelectStreamSyntax, TypeChecking},
+    stream_engine::command::{InsertPlan, ShowObjectsCommand},
 };
 
 #[allow(clippy::enum_variant_names)]
@@ -15,6 +19,49 @@ pub enum ParseSuccess {
     CreateSinkStream(StreamModel),
     CreateSinkWriter(SinkWriterModel),
     CreatePump(Box<CreatePump>),
+    CreateRouter(Box<CreateRouter>),
+    CreateReplicator(Box<CreateReplicator>),
+
+    /// `SWITCH PUMP from_pump TO to_pump`
+    SwitchPump {
+        from_pump: PumpName,
+        to_pump: PumpName,
+    },
+
+    /// `DROP PUMP pump_name`
+    DropPump(PumpName),
+    /// `DROP STREAM stream_name`
+    DropStream(StreamName),
+    /// `DROP SOURCE READER source_reader_name`
+    DropSourceReader(SourceReaderName),
+    /// `DROP SINK WRITER sink_writer_name`
+    DropSinkWriter(SinkWriterName),
+
+    /// `ALTER PUMP pump_name STOP` (`stopped: true`) or `ALTER PUMP pump_name START` (`stopped: false`)
+    AlterPumpState {
+        pump_name: PumpName,
+        stopped: bool,
+    },
+
+    /// `ALTER STREAM stream_name SET TRACE SAMPLE rate` or `ALTER STREAM stream_name SET TRACE OFF` (`None`)
+    AlterStreamTrace {
+        stream_name: StreamName,
+        trace_sample_rate: Option<f32>,
+    },
+
+    InferSchema(InferSchema),
+
+    /// `SHOW HISTORY`
+    ShowHistory,
+
+    /// `SHOW WARNINGS`
+    ShowWarnings,
+
+    /// `SHOW STREAMS`, `SHOW PUMPS`, `SHOW SOURCE READERS`, or `SHOW SINK WRITERS`
+    ShowObjects(ShowObjectsCommand),
+
+    /// `EXPLAIN CREATE PUMP ...` or `EXPLAIN SELECT STREAM ...`
+    Explain(Box<ExplainTarget>),
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -22,4 +69,63 @@ pub struct CreatePump {
     pub pump_name: PumpName,
     pub select_stream_syntax: SelectStreamSyntax,
     pub insert_plan: InsertPlan,
+
+    /// `PRIORITY WHEN condition`
+    pub priority_condition: Option<ValueExpr>,
+
+    /// `CREATE PUMP pump_name BEST_EFFORT AS ...`
+    pub best_effort: bool,
+
+    /// `CREATE PUMP pump_name STRICT AS ...` or `CREATE PUMP pump_name LENIENT AS ...`. `None` to follow `pipeline.type_checking` config.
+    pub type_checking: Option<TypeChecking>,
+
+    /// `OPTIONS (...)` after the query. See `PumpOptions`.
+    pub options: Options,
+
+    /// `WITH TAGS (...)`
+    pub tags: Tags,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct CreateRouter {
+    pub router_name: PumpName,
+    pub from_stream: StreamName,
+
+    /// (condition, destination stream) in `WHEN ... TO ...` order.
+    pub routes: Vec<(ValueExpr, StreamName)>,
+    pub else_stream: Option<StreamName>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct CreateReplicator {
+    pub replicator_name: PumpName,
+    pub from_stream: StreamName,
+    pub to_streams: Vec<StreamName>,
+}
+
+/// `INFER SCHEMA [FOR stream_name] FROM SOURCE READER ... SAMPLE n_samples`
+#[derive(Clone, PartialEq, Debug)]
+pub struct InferSchema {
+    pub stream_name: Option<StreamName>,
+    pub source: InferSchemaSource,
+    pub n_samples: usize,
+}
+
+/// Source reader `INFER SCHEMA` samples rows from.
+#[derive(Clone, PartialEq, Debug)]
+pub enum InferSchemaSource {
+    /// `SOURCE READER TYPE source_reader_type OPTIONS (...)`, which is not a part of the pipeline.
+    Adhoc {
+        source_reader_type: SourceReaderType,
+        options: Options,
+    },
+    /// `SOURCE READER source_reader_name` in the pipeline.
+    Named(SourceReaderName),
+}
+
+/// Statement whose query plan `EXPLAIN` shows.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ExplainTarget {
+    CreatePump(CreatePump),
+    SelectStream(SelectStreamSyntax),
 }
@@ -12,41 +12,77 @@ use pest::{iterators::Pairs, Parser};
 use crate::{
     api::error::{Result, SpringError},
     expression::{
-        AggrExpr, BinaryExpr, BinaryOperator, ComparisonFunction, FunctionCall, LogicalFunction,
-        NumericalFunction, UnaryOperator, ValueExpr,
+        validate_regexp, AggrExpr, BinaryExpr, BinaryOperator, ComparisonFunction, FunctionCall,
+        LogicalFunction, NumericalFunction, UnaryOperator, ValueExpr,
     },
     pipeline::{
         AggrAlias, AggregateFunctionParameter, ColumnConstraint, ColumnDataType, ColumnDefinition,
         ColumnName, ColumnReference, CorrelationAlias, JoinType, OptionsBuilder, PumpName,
         SinkWriterModel, SinkWriterName, SinkWriterType, SourceReaderModel, SourceReaderName,
-        SourceReaderType, SqlType, StreamModel, StreamName, StreamShape, ValueAlias,
-        WindowParameter,
+        SourceReaderType, SqlType, StreamModel, StreamName, StreamShape, Tags, ValueAlias,
+        WindowAlignment, WindowMetadata, WindowParameter,
     },
-    sql_processor::sql_parser::{
-        parse_success::{CreatePump, ParseSuccess},
-        pest_parser_impl::{
-            generated_parser::{GeneratedParser, Rule},
-            helper::{parse_child, parse_child_seq, self_as_str, try_parse_child, FnParseParams},
-        },
-        syntax::{
-            ColumnConstraintSyntax, DurationFunction, FromItemSyntax, GroupingElementSyntax,
-            OptionSyntax, SelectFieldSyntax, SelectStreamSyntax, SubFromItemSyntax,
+    sql_processor::{
+        sql_parser::{
+            parse_success::{
+                CreatePump, CreateReplicator, CreateRouter, ExplainTarget, InferSchema,
+                InferSchemaSource, ParseSuccess,
+            },
+            pest_parser_impl::{
+                generated_parser::{GeneratedParser, Rule},
+                helper::{
+                    parse_child, parse_child_seq, self_as_str, try_parse_child, FnParseParams,
+                },
+            },
+            syntax::{
+                ColumnConstraintSyntax, DurationFunction, FromItemSyntax, GroupingElementSyntax,
+                OptionSyntax, SelectFieldSyntax, SelectStreamSyntax, SubFromItemSyntax,
+                ValueExprSuffixSyntax,
+            },
         },
+        TypeChecking,
     },
     stream_engine::{
-        command::InsertPlan,
-        time::{SpringDuration, SpringEventDuration},
+        command::{InsertPlan, ShowObjectsCommand},
+        time::{SpringDuration, SpringEventDuration, WallClockDuration},
         NnSqlValue, SqlValue,
     },
 };
 
+/// Max depth of nested expressions in a SQL, low enough for the recursive descent parser not to overflow the stack.
+const MAX_NESTING_DEPTH: usize = 128;
+
 #[derive(Debug, Default)]
 pub struct PestParserImpl;
 
 impl PestParserImpl {
+    /// Formats `name` as an identifier in SQL: as-is if it is a valid unquoted identifier, otherwise quoted (`"my-stream"`).
+    pub fn to_identifier(name: &str) -> String {
+        let is_unquoted = !name.starts_with('"')
+            && GeneratedParser::parse(Rule::identifier, name)
+                .ok()
+                .and_then(|mut pairs| pairs.next())
+                .map_or(false, |pair| pair.as_str() == name);
+        if is_unquoted {
+            name.to_string()
+        } else {
+            format!("\"{}\"", name.replace('"', "\"\""))
+        }
+    }
+
     pub fn parse<S: Into<String>>(&self, sql: S) -> Result<ParseSuccess> {
         let sql = sql.into();
 
+        // deeply nested input overflows the stack of recursive descent
+        let depth = nesting_depth(&sql);
+        if depth > MAX_NESTING_DEPTH {
+            return Err(SpringError::Sql(anyhow!(
+                "too deeply nested expression: {} (max {})",
+                depth,
+                MAX_NESTING_DEPTH
+            )));
+        }
+
         let pairs: Pairs<Rule> = GeneratedParser::parse(Rule::command, &sql)
             .context("failed to parse SQL")
             .map_err(SpringError::Sql)?;
@@ -126,31 +162,38 @@ impl PestParserImpl {
         })
     }
 
+    /// Digit separators (`_`) are ignored. Hex integers (`0xFF`) are parsed as non-negative values.
     fn parse_integer_constant(mut params: FnParseParams) -> Result<SqlValue> {
         let s = self_as_str(&mut params);
+        let digits = s.replace('_', "");
+
+        let i = match digits
+            .strip_prefix("0x")
+            .or_else(|| digits.strip_prefix("0X"))
+        {
+            Some(hex) => i64::from_str_radix(hex, 16),
+            None => digits.parse::<i64>(),
+        }
+        .map_err(|_e| {
+            SpringError::Sql(anyhow!(
+                "integer value `{}` could not be parsed as i64 (max supported size)",
+                s
+            ))
+        })?;
 
-        s.parse::<i16>()
-            .map(|i| SqlValue::NotNull(NnSqlValue::SmallInt(i)))
-            .or_else(|_| {
-                s.parse::<i32>()
-                    .map(|i| SqlValue::NotNull(NnSqlValue::Integer(i)))
-            })
-            .or_else(|_| {
-                s.parse::<i64>()
-                    .map(|i| SqlValue::NotNull(NnSqlValue::BigInt(i)))
-            })
-            .map_err(|_e| {
-                SpringError::Sql(anyhow!(
-                    "integer value `{}` could not be parsed as i64 (max supported size)",
-                    s
-                ))
-            })
+        let value = i16::try_from(i)
+            .map(NnSqlValue::SmallInt)
+            .or_else(|_| i32::try_from(i).map(NnSqlValue::Integer))
+            .unwrap_or(NnSqlValue::BigInt(i));
+        Ok(SqlValue::NotNull(value))
     }
 
+    /// Digit separators (`_`) are ignored.
     fn parse_float_constant(mut params: FnParseParams) -> Result<SqlValue> {
         let s = self_as_str(&mut params);
 
-        s.parse::<f32>()
+        s.replace('_', "")
+            .parse::<f32>()
             .map(|f| SqlValue::NotNull(NnSqlValue::Float(OrderedFloat(f))))
             .map_err(|_e| {
                 SpringError::Sql(anyhow!(
@@ -187,6 +230,15 @@ impl PestParserImpl {
     }
 
     fn parse_duration_constant(mut params: FnParseParams) -> Result<SqlValue> {
+        if let Some(event_duration) = try_parse_child(
+            &mut params,
+            Rule::interval_constant,
+            Self::parse_interval_constant,
+            identity,
+        )? {
+            return Ok(SqlValue::NotNull(NnSqlValue::Duration(event_duration)));
+        }
+
         let duration_function = parse_child(
             &mut params,
             Rule::duration_function,
@@ -212,6 +264,43 @@ impl PestParserImpl {
         Ok(SqlValue::NotNull(NnSqlValue::Duration(event_duration)))
     }
 
+    fn parse_interval_constant(mut params: FnParseParams) -> Result<SpringEventDuration> {
+        let integer_constant = parse_child(
+            &mut params,
+            Rule::integer_constant,
+            Self::parse_integer_constant,
+            identity,
+        )?;
+        let unit_millis = parse_child(
+            &mut params,
+            Rule::interval_unit,
+            Self::parse_interval_unit,
+            identity,
+        )?;
+
+        let n = integer_constant.to_i64()? as u64;
+        n.checked_mul(unit_millis)
+            .map(SpringEventDuration::from_millis)
+            .ok_or_else(|| SpringError::Sql(anyhow!("too long INTERVAL: {}", n)))
+    }
+
+    /// # Returns
+    ///
+    /// Milliseconds of the unit
+    fn parse_interval_unit(mut params: FnParseParams) -> Result<u64> {
+        let s = self_as_str(&mut params);
+        match s.to_lowercase().as_ref() {
+            "millisecond" => Ok(1),
+            "second" => Ok(1_000),
+            "minute" => Ok(60 * 1_000),
+            "hour" => Ok(60 * 60 * 1_000),
+            "day" => Ok(24 * 60 * 60 * 1_000),
+            _ => Err(SpringError::Sql(anyhow!(
+                "Does not match any child rule of interval_unit.",
+            ))),
+        }
+    }
+
     fn parse_duration_function(mut params: FnParseParams) -> Result<DurationFunction> {
         let s = self_as_str(&mut params);
         match s.to_lowercase().as_ref() {
@@ -241,12 +330,34 @@ impl PestParserImpl {
     }
 
     fn parse_binary_operator(mut params: FnParseParams) -> Result<BinaryOperator> {
+        if try_parse_child(
+            &mut params,
+            Rule::is_distinct_from_operator,
+            |_| Ok(()),
+            identity,
+        )?
+        .is_some()
+        {
+            return Ok(BinaryOperator::IsDistinctFrom);
+        }
+        if try_parse_child(
+            &mut params,
+            Rule::is_not_distinct_from_operator,
+            |_| Ok(()),
+            identity,
+        )?
+        .is_some()
+        {
+            return Ok(BinaryOperator::IsNotDistinctFrom);
+        }
+
         let s = self_as_str(&mut params);
         match s.to_lowercase().as_str() {
             "=" => Ok(BinaryOperator::Equal),
             "+" => Ok(BinaryOperator::Add),
             "*" => Ok(BinaryOperator::Mul),
             "and" => Ok(BinaryOperator::And),
+            "like" => Ok(BinaryOperator::Like),
             _ => Err(SpringError::Sql(anyhow!(
                 "Does not match any child rule of binary_operator.",
             ))),
@@ -296,6 +407,108 @@ impl PestParserImpl {
             Self::parse_create_pump_command,
             identity,
         )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::create_router_command,
+            Self::parse_create_router_command,
+            identity,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::create_replicator_command,
+            Self::parse_create_replicator_command,
+            identity,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::switch_pump_command,
+            Self::parse_switch_pump_command,
+            identity,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::drop_pump_command,
+            Self::parse_drop_pump_command,
+            identity,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::drop_stream_command,
+            Self::parse_drop_stream_command,
+            identity,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::drop_source_reader_command,
+            Self::parse_drop_source_reader_command,
+            identity,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::drop_sink_writer_command,
+            Self::parse_drop_sink_writer_command,
+            identity,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::alter_pump_command,
+            Self::parse_alter_pump_command,
+            identity,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::alter_stream_command,
+            Self::parse_alter_stream_command,
+            identity,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::infer_schema_command,
+            Self::parse_infer_schema_command,
+            identity,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::show_history_command,
+            |_| Ok(ParseSuccess::ShowHistory),
+            identity,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::show_warnings_command,
+            |_| Ok(ParseSuccess::ShowWarnings),
+            identity,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::show_streams_command,
+            |_| Ok(ParseSuccess::ShowObjects(ShowObjectsCommand::Streams)),
+            identity,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::show_pumps_command,
+            |_| Ok(ParseSuccess::ShowObjects(ShowObjectsCommand::Pumps)),
+            identity,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::show_source_readers_command,
+            |_| Ok(ParseSuccess::ShowObjects(ShowObjectsCommand::SourceReaders)),
+            identity,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::show_sink_writers_command,
+            |_| Ok(ParseSuccess::ShowObjects(ShowObjectsCommand::SinkWriters)),
+            identity,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::explain_command,
+            Self::parse_explain_command,
+            identity,
+        )?)
         .ok_or_else(|| {
             SpringError::Sql(anyhow!(
                 "Does not match any child rule of command: {}",
@@ -323,9 +536,10 @@ impl PestParserImpl {
             &Self::parse_column_definition,
             &identity,
         )?;
+        let tags = Self::try_parse_tag_specifications(&mut params)?;
 
         let stream_shape = StreamShape::new(column_definitions)?;
-        let source_stream = StreamModel::new(source_stream_name, stream_shape);
+        let source_stream = StreamModel::new(source_stream_name, stream_shape).with_tags(tags);
 
         Ok(ParseSuccess::CreateSourceStream(source_stream))
     }
@@ -399,9 +613,10 @@ impl PestParserImpl {
             &Self::parse_column_definition,
             &identity,
         )?;
+        let tags = Self::try_parse_tag_specifications(&mut params)?;
 
         let stream_shape = StreamShape::new(column_definitions)?;
-        let stream = StreamModel::new(stream_name, stream_shape);
+        let stream = StreamModel::new(stream_name, stream_shape).with_tags(tags);
 
         Ok(ParseSuccess::CreateStream(stream))
     }
@@ -425,9 +640,10 @@ impl PestParserImpl {
             &Self::parse_column_definition,
             &identity,
         )?;
+        let tags = Self::try_parse_tag_specifications(&mut params)?;
 
         let stream_shape = StreamShape::new(column_definitions)?;
-        let sink_stream = StreamModel::new(sink_stream_name, stream_shape);
+        let sink_stream = StreamModel::new(sink_stream_name, stream_shape).with_tags(tags);
 
         Ok(ParseSuccess::CreateSinkStream(sink_stream))
     }
@@ -451,6 +667,18 @@ impl PestParserImpl {
             Self::parse_stream_name,
             identity,
         )?;
+        let filter = try_parse_child(
+            &mut params,
+            Rule::sink_filter_clause,
+            Self::parse_sink_filter_clause,
+            identity,
+        )?;
+        let projection = try_parse_child(
+            &mut params,
+            Rule::sink_project_clause,
+            Self::parse_sink_project_clause,
+            identity,
+        )?;
         let sink_writer_type = parse_child(
             &mut params,
             Rule::sink_writer_type,
@@ -472,29 +700,66 @@ impl PestParserImpl {
         }
         let options = options.build();
 
-        let sink_writer = SinkWriterModel::new(
+        let mut sink_writer = SinkWriterModel::new(
             sink_writer_name,
             sink_writer_type,
             sink_stream_name,
             options,
         );
+        if let Some(filter) = filter {
+            sink_writer = sink_writer.with_filter(filter);
+        }
+        if let Some(projection) = projection {
+            sink_writer = sink_writer.with_projection(projection);
+        }
 
         Ok(ParseSuccess::CreateSinkWriter(sink_writer))
     }
 
+    fn parse_sink_filter_clause(mut params: FnParseParams) -> Result<ValueExpr> {
+        parse_child(
+            &mut params,
+            Rule::where_clause,
+            Self::parse_where_clause,
+            identity,
+        )
+    }
+
+    fn parse_sink_project_clause(mut params: FnParseParams) -> Result<Vec<ColumnName>> {
+        parse_child_seq(
+            &mut params,
+            Rule::column_name,
+            &Self::parse_column_name,
+            &identity,
+        )
+    }
+
     /*
      * ----------------------------------------------------------------------------
      * CREATE PUMP
      * ----------------------------------------------------------------------------
      */
 
-    fn parse_create_pump_command(mut params: FnParseParams) -> Result<ParseSuccess> {
+    fn parse_create_pump_command(params: FnParseParams) -> Result<ParseSuccess> {
+        let create_pump = Self::parse_create_pump(params)?;
+        Ok(ParseSuccess::CreatePump(Box::new(create_pump)))
+    }
+
+    fn parse_create_pump(mut params: FnParseParams) -> Result<CreatePump> {
         let pump_name = parse_child(
             &mut params,
             Rule::pump_name,
             Self::parse_pump_name,
             identity,
         )?;
+        let best_effort =
+            try_parse_child(&mut params, Rule::best_effort, |_| Ok(()), identity)?.is_some();
+        let type_checking = try_parse_child(
+            &mut params,
+            Rule::type_checking,
+            Self::parse_type_checking,
+            identity,
+        )?;
         let into_stream = parse_child(
             &mut params,
             Rule::stream_name,
@@ -513,14 +778,377 @@ impl PestParserImpl {
             Self::parse_select_stream,
             identity,
         )?;
+        let priority_condition = try_parse_child(
+            &mut params,
+            Rule::priority_clause,
+            Self::parse_priority_clause,
+            identity,
+        )?;
+        let option_syntaxes = try_parse_child(
+            &mut params,
+            Rule::option_specifications,
+            &Self::parse_option_specifications,
+            &identity,
+        )?;
+        let tags = Self::try_parse_tag_specifications(&mut params)?;
+
+        let mut options = OptionsBuilder::default();
+        if let Some(option_syntaxes) = option_syntaxes {
+            for o in option_syntaxes {
+                options = options.add(o.option_name, o.option_value);
+            }
+        }
 
-        Ok(ParseSuccess::CreatePump(Box::new(CreatePump {
+        Ok(CreatePump {
             pump_name,
             select_stream_syntax,
             insert_plan: InsertPlan::new(into_stream, insert_column_names),
+            priority_condition,
+            best_effort,
+            type_checking,
+            options: options.build(),
+            tags,
+        })
+    }
+
+    fn parse_type_checking(mut params: FnParseParams) -> Result<TypeChecking> {
+        let s = self_as_str(&mut params);
+        match s.to_ascii_uppercase().as_str() {
+            "STRICT" => Ok(TypeChecking::Strict),
+            "LENIENT" => Ok(TypeChecking::Lenient),
+            x => Err(SpringError::Sql(anyhow!(
+                "Unsupported type checking: {}",
+                x
+            ))),
+        }
+    }
+
+    fn parse_priority_clause(mut params: FnParseParams) -> Result<ValueExpr> {
+        parse_child(
+            &mut params,
+            Rule::condition,
+            Self::parse_condition,
+            identity,
+        )
+    }
+
+    /*
+     * ----------------------------------------------------------------------------
+     * CREATE ROUTER
+     * ----------------------------------------------------------------------------
+     */
+
+    fn parse_create_router_command(mut params: FnParseParams) -> Result<ParseSuccess> {
+        let router_name = parse_child(
+            &mut params,
+            Rule::pump_name,
+            Self::parse_pump_name,
+            identity,
+        )?;
+        let from_stream = parse_child(
+            &mut params,
+            Rule::stream_name,
+            Self::parse_stream_name,
+            identity,
+        )?;
+        let routes = parse_child_seq(
+            &mut params,
+            Rule::route_clause,
+            &Self::parse_route_clause,
+            &identity,
+        )?;
+        let else_stream = try_parse_child(
+            &mut params,
+            Rule::stream_name,
+            Self::parse_stream_name,
+            identity,
+        )?;
+
+        Ok(ParseSuccess::CreateRouter(Box::new(CreateRouter {
+            router_name,
+            from_stream,
+            routes,
+            else_stream,
         })))
     }
 
+    fn parse_route_clause(mut params: FnParseParams) -> Result<(ValueExpr, StreamName)> {
+        let condition = parse_child(
+            &mut params,
+            Rule::condition,
+            Self::parse_condition,
+            identity,
+        )?;
+        let to_stream = parse_child(
+            &mut params,
+            Rule::stream_name,
+            Self::parse_stream_name,
+            identity,
+        )?;
+        Ok((condition, to_stream))
+    }
+
+    /*
+     * ----------------------------------------------------------------------------
+     * CREATE REPLICATOR
+     * ----------------------------------------------------------------------------
+     */
+
+    fn parse_create_replicator_command(mut params: FnParseParams) -> Result<ParseSuccess> {
+        let replicator_name = parse_child(
+            &mut params,
+            Rule::pump_name,
+            Self::parse_pump_name,
+            identity,
+        )?;
+        let from_stream = parse_child(
+            &mut params,
+            Rule::stream_name,
+            Self::parse_stream_name,
+            identity,
+        )?;
+        let to_streams = parse_child_seq(
+            &mut params,
+            Rule::stream_name,
+            &Self::parse_stream_name,
+            &identity,
+        )?;
+
+        Ok(ParseSuccess::CreateReplicator(Box::new(CreateReplicator {
+            replicator_name,
+            from_stream,
+            to_streams,
+        })))
+    }
+
+    /*
+     * ----------------------------------------------------------------------------
+     * SWITCH PUMP
+     * ----------------------------------------------------------------------------
+     */
+
+    fn parse_switch_pump_command(mut params: FnParseParams) -> Result<ParseSuccess> {
+        let from_pump = parse_child(
+            &mut params,
+            Rule::pump_name,
+            Self::parse_pump_name,
+            identity,
+        )?;
+        let to_pump = parse_child(
+            &mut params,
+            Rule::pump_name,
+            Self::parse_pump_name,
+            identity,
+        )?;
+
+        Ok(ParseSuccess::SwitchPump { from_pump, to_pump })
+    }
+
+    /*
+     * ----------------------------------------------------------------------------
+     * DROP
+     * ----------------------------------------------------------------------------
+     */
+
+    fn parse_drop_pump_command(mut params: FnParseParams) -> Result<ParseSuccess> {
+        let pump_name = parse_child(
+            &mut params,
+            Rule::pump_name,
+            Self::parse_pump_name,
+            identity,
+        )?;
+        Ok(ParseSuccess::DropPump(pump_name))
+    }
+
+    fn parse_drop_stream_command(mut params: FnParseParams) -> Result<ParseSuccess> {
+        let stream_name = parse_child(
+            &mut params,
+            Rule::stream_name,
+            Self::parse_stream_name,
+            identity,
+        )?;
+        Ok(ParseSuccess::DropStream(stream_name))
+    }
+
+    fn parse_drop_source_reader_command(mut params: FnParseParams) -> Result<ParseSuccess> {
+        let source_reader_name = parse_child(
+            &mut params,
+            Rule::source_reader_name,
+            Self::parse_source_reader_name,
+            identity,
+        )?;
+        Ok(ParseSuccess::DropSourceReader(source_reader_name))
+    }
+
+    fn parse_drop_sink_writer_command(mut params: FnParseParams) -> Result<ParseSuccess> {
+        let sink_writer_name = parse_child(
+            &mut params,
+            Rule::sink_writer_name,
+            Self::parse_sink_writer_name,
+            identity,
+        )?;
+        Ok(ParseSuccess::DropSinkWriter(sink_writer_name))
+    }
+
+    /*
+     * ----------------------------------------------------------------------------
+     * ALTER PUMP
+     * ----------------------------------------------------------------------------
+     */
+
+    fn parse_alter_pump_command(mut params: FnParseParams) -> Result<ParseSuccess> {
+        let pump_name = parse_child(
+            &mut params,
+            Rule::pump_name,
+            Self::parse_pump_name,
+            identity,
+        )?;
+        let stopped = try_parse_child(&mut params, Rule::pump_stop, |_| Ok(()), identity)?;
+        let _ = try_parse_child(&mut params, Rule::pump_start, |_| Ok(()), identity)?;
+
+        Ok(ParseSuccess::AlterPumpState {
+            pump_name,
+            stopped: stopped.is_some(),
+        })
+    }
+
+    /*
+     * ----------------------------------------------------------------------------
+     * ALTER STREAM
+     * ----------------------------------------------------------------------------
+     */
+
+    fn parse_alter_stream_command(mut params: FnParseParams) -> Result<ParseSuccess> {
+        let stream_name = parse_child(
+            &mut params,
+            Rule::stream_name,
+            Self::parse_stream_name,
+            identity,
+        )?;
+        let trace_sample_rate = try_parse_child(
+            &mut params,
+            Rule::trace_sample,
+            Self::parse_trace_sample,
+            identity,
+        )?;
+        let _ = try_parse_child(&mut params, Rule::trace_off, |_| Ok(()), identity)?;
+
+        Ok(ParseSuccess::AlterStreamTrace {
+            stream_name,
+            trace_sample_rate,
+        })
+    }
+
+    fn parse_trace_sample(mut params: FnParseParams) -> Result<f32> {
+        let sample_rate = parse_child(
+            &mut params,
+            Rule::numeric_constant,
+            Self::parse_numeric_constant,
+            identity,
+        )?;
+        match sample_rate {
+            SqlValue::NotNull(nn_sql_value) => nn_sql_value.unpack::<f32>(),
+            SqlValue::Null => unreachable!("numeric constant is not NULL"),
+        }
+    }
+
+    /*
+     * ----------------------------------------------------------------------------
+     * INFER SCHEMA
+     * ----------------------------------------------------------------------------
+     */
+
+    fn parse_infer_schema_command(mut params: FnParseParams) -> Result<ParseSuccess> {
+        let stream_name = try_parse_child(
+            &mut params,
+            Rule::stream_name,
+            Self::parse_stream_name,
+            identity,
+        )?;
+        let source_reader_type = try_parse_child(
+            &mut params,
+            Rule::source_reader_type,
+            Self::parse_source_reader_type,
+            identity,
+        )?;
+        let source = match source_reader_type {
+            Some(source_reader_type) => {
+                let option_syntaxes = try_parse_child(
+                    &mut params,
+                    Rule::option_specifications,
+                    &Self::parse_option_specifications,
+                    &identity,
+                )?;
+
+                let mut options = OptionsBuilder::default();
+                if let Some(option_syntaxes) = option_syntaxes {
+                    for o in option_syntaxes {
+                        options = options.add(o.option_name, o.option_value);
+                    }
+                }
+
+                InferSchemaSource::Adhoc {
+                    source_reader_type,
+                    options: options.build(),
+                }
+            }
+            None => parse_child(
+                &mut params,
+                Rule::source_reader_name,
+                Self::parse_source_reader_name,
+                InferSchemaSource::Named,
+            )?,
+        };
+        let n_samples = parse_child(
+            &mut params,
+            Rule::integer_constant,
+            Self::parse_integer_constant,
+            identity,
+        )?;
+
+        let n_samples = n_samples.to_i64()?;
+        if n_samples <= 0 {
+            return Err(SpringError::Sql(anyhow!(
+                "SAMPLE must be positive: {}",
+                n_samples
+            )));
+        }
+
+        Ok(ParseSuccess::InferSchema(InferSchema {
+            stream_name,
+            source,
+            n_samples: n_samples as usize,
+        }))
+    }
+
+    /*
+     * ----------------------------------------------------------------------------
+     * EXPLAIN
+     * ----------------------------------------------------------------------------
+     */
+
+    fn parse_explain_command(mut params: FnParseParams) -> Result<ParseSuccess> {
+        let target = try_parse_child(
+            &mut params,
+            Rule::create_pump_command,
+            Self::parse_create_pump,
+            ExplainTarget::CreatePump,
+        )?
+        .or(try_parse_child(
+            &mut params,
+            Rule::select_stream_command,
+            Self::parse_select_stream,
+            ExplainTarget::SelectStream,
+        )?)
+        .ok_or_else(|| {
+            SpringError::Sql(anyhow!(
+                "Does not match any child rule of EXPLAIN: {}",
+                params.sql
+            ))
+        })?;
+        Ok(ParseSuccess::Explain(Box::new(target)))
+    }
+
     /*
      * ----------------------------------------------------------------------------
      * SELECT
@@ -540,6 +1168,12 @@ impl PestParserImpl {
             Self::parse_from_item,
             identity,
         )?;
+        let where_clause = try_parse_child(
+            &mut params,
+            Rule::where_clause,
+            Self::parse_where_clause,
+            identity,
+        )?;
         let grouping_elements = try_parse_child(
             &mut params,
             Rule::group_by_clause,
@@ -556,6 +1190,7 @@ impl PestParserImpl {
         Ok(SelectStreamSyntax {
             fields,
             from_item,
+            where_clause,
             grouping_elements: grouping_elements.unwrap_or_default(),
             window_clause,
         })
@@ -671,6 +1306,15 @@ impl PestParserImpl {
         }
     }
 
+    fn parse_where_clause(mut params: FnParseParams) -> Result<ValueExpr> {
+        parse_child(
+            &mut params,
+            Rule::condition,
+            Self::parse_condition,
+            identity,
+        )
+    }
+
     fn parse_group_by_clause(mut params: FnParseParams) -> Result<Vec<GroupingElementSyntax>> {
         parse_child_seq(
             &mut params,
@@ -722,6 +1366,9 @@ impl PestParserImpl {
             identity,
         )?;
         let length = length.to_event_duration()?;
+        if length.as_std().is_zero() {
+            return Err(SpringError::Sql(anyhow!("window length must be positive")));
+        }
 
         let allowed_delay = parse_child(
             &mut params,
@@ -731,9 +1378,30 @@ impl PestParserImpl {
         )?;
         let allowed_delay = allowed_delay.to_event_duration()?;
 
+        let offset = try_parse_child(
+            &mut params,
+            Rule::window_alignment,
+            Self::parse_window_alignment,
+            identity,
+        )?
+        .map(|alignment| alignment.offset(length))
+        .transpose()?
+        .unwrap_or_else(|| SpringEventDuration::from_secs(0));
+
+        let allowed_lateness = Self::parse_optional_allowed_lateness(&mut params)?;
+        let emit_on_update = Self::parse_optional_emit_policy(&mut params)?;
+        let (min_watermark, input_idle_timeout) = Self::parse_optional_min_watermark(&mut params)?;
+        let trace_triggers = Self::parse_optional_trace_triggers(&mut params)?;
+
         Ok(WindowParameter::TimedFixedWindow {
             length,
             allowed_delay,
+            allowed_lateness,
+            min_watermark,
+            input_idle_timeout,
+            offset,
+            emit_on_update,
+            trace_triggers,
         })
     }
 
@@ -753,6 +1421,14 @@ impl PestParserImpl {
             identity,
         )?;
         let period = period.to_event_duration()?;
+        // period longer than length would leave rows out of any pane
+        if period.as_std().is_zero() || period > length {
+            return Err(SpringError::Sql(anyhow!(
+                "window period ({}) must be positive and not longer than length ({})",
+                period,
+                length
+            )));
+        }
 
         let allowed_delay = parse_child(
             &mut params,
@@ -762,14 +1438,62 @@ impl PestParserImpl {
         )?;
         let allowed_delay = allowed_delay.to_event_duration()?;
 
+        let allowed_lateness = Self::parse_optional_allowed_lateness(&mut params)?;
+        let emit_on_update = Self::parse_optional_emit_policy(&mut params)?;
+        let (min_watermark, input_idle_timeout) = Self::parse_optional_min_watermark(&mut params)?;
+        let trace_triggers = Self::parse_optional_trace_triggers(&mut params)?;
+
         Ok(WindowParameter::TimedSlidingWindow {
             length,
             period,
             allowed_delay,
+            allowed_lateness,
+            min_watermark,
+            input_idle_timeout,
+            emit_on_update,
+            trace_triggers,
         })
     }
 
-    fn parse_window_length(mut params: FnParseParams) -> Result<SqlValue> {
+    fn parse_window_length(mut params: FnParseParams) -> Result<SqlValue> {
+        parse_child(
+            &mut params,
+            Rule::duration_constant,
+            Self::parse_duration_constant,
+            identity,
+        )
+    }
+    fn parse_window_period(mut params: FnParseParams) -> Result<SqlValue> {
+        parse_child(
+            &mut params,
+            Rule::duration_constant,
+            Self::parse_duration_constant,
+            identity,
+        )
+    }
+    fn parse_allowed_delay(mut params: FnParseParams) -> Result<SqlValue> {
+        parse_child(
+            &mut params,
+            Rule::duration_constant,
+            Self::parse_duration_constant,
+            identity,
+        )
+    }
+
+    fn parse_optional_allowed_lateness(params: &mut FnParseParams) -> Result<SpringEventDuration> {
+        try_parse_child(
+            params,
+            Rule::allowed_lateness,
+            Self::parse_allowed_lateness,
+            identity,
+        )?
+        .map(|allowed_lateness| allowed_lateness.to_event_duration())
+        .transpose()
+        .map(|allowed_lateness| {
+            allowed_lateness.unwrap_or_else(|| SpringEventDuration::from_secs(0))
+        })
+    }
+    fn parse_allowed_lateness(mut params: FnParseParams) -> Result<SqlValue> {
         parse_child(
             &mut params,
             Rule::duration_constant,
@@ -777,19 +1501,94 @@ impl PestParserImpl {
             identity,
         )
     }
-    fn parse_window_period(mut params: FnParseParams) -> Result<SqlValue> {
-        parse_child(
+
+    /// Returns `(min_watermark, input_idle_timeout)`.
+    fn parse_optional_min_watermark(
+        params: &mut FnParseParams,
+    ) -> Result<(bool, Option<WallClockDuration>)> {
+        try_parse_child(
+            params,
+            Rule::min_watermark,
+            Self::parse_min_watermark,
+            identity,
+        )
+        .map(|input_idle_timeout| match input_idle_timeout {
+            Some(input_idle_timeout) => (true, input_idle_timeout),
+            None => (false, None),
+        })
+    }
+    fn parse_min_watermark(mut params: FnParseParams) -> Result<Option<WallClockDuration>> {
+        try_parse_child(
+            &mut params,
+            Rule::input_idle_timeout,
+            Self::parse_input_idle_timeout,
+            identity,
+        )
+    }
+    fn parse_input_idle_timeout(mut params: FnParseParams) -> Result<WallClockDuration> {
+        let duration = parse_child(
             &mut params,
             Rule::duration_constant,
             Self::parse_duration_constant,
             identity,
+        )?;
+        let duration = duration.to_event_duration()?;
+        Ok(WallClockDuration::from_std(*duration.as_std()))
+    }
+
+    /// Returns true for `EMIT ON UPDATE`, and false for `EMIT ON CLOSE` or no emit policy.
+    fn parse_optional_emit_policy(params: &mut FnParseParams) -> Result<bool> {
+        Ok(
+            try_parse_child(params, Rule::emit_policy, Self::parse_emit_policy, identity)?
+                .unwrap_or(false),
         )
     }
-    fn parse_allowed_delay(mut params: FnParseParams) -> Result<SqlValue> {
+
+    fn parse_emit_policy(mut params: FnParseParams) -> Result<bool> {
+        let on_update =
+            try_parse_child(&mut params, Rule::emit_on_update, |_| Ok(()), identity)?.is_some();
+        try_parse_child(&mut params, Rule::emit_on_close, |_| Ok(()), identity)?;
+        Ok(on_update)
+    }
+
+    fn parse_optional_trace_triggers(params: &mut FnParseParams) -> Result<bool> {
+        Ok(try_parse_child(params, Rule::trace_triggers, |_| Ok(()), identity)?.is_some())
+    }
+
+    fn parse_window_alignment(mut params: FnParseParams) -> Result<WindowAlignment> {
+        try_parse_child(
+            &mut params,
+            Rule::alignment_unit,
+            Self::parse_alignment_unit,
+            WindowAlignment::Unit,
+        )?
+        .or(try_parse_child(
+            &mut params,
+            Rule::alignment_time,
+            Self::parse_alignment_time,
+            WindowAlignment::WallClock,
+        )?)
+        .ok_or_else(|| {
+            SpringError::Sql(anyhow!("Failed to parse window alignment: {}", params.sql))
+        })
+    }
+    fn parse_alignment_unit(mut params: FnParseParams) -> Result<SpringEventDuration> {
+        let s = self_as_str(&mut params);
+        match s.to_lowercase().as_ref() {
+            "minute" => Ok(SpringEventDuration::from_secs(60)),
+            "hour" => Ok(SpringEventDuration::from_secs(60 * 60)),
+            "day" => Ok(SpringEventDuration::from_secs(24 * 60 * 60)),
+            _ => Err(SpringError::Sql(anyhow!(
+                "alignment unit `{}` is invalid",
+                s
+            ))),
+        }
+    }
+    fn parse_alignment_time(mut params: FnParseParams) -> Result<String> {
         parse_child(
             &mut params,
-            Rule::duration_constant,
-            Self::parse_duration_constant,
+            Rule::string_content,
+            Self::parse_string_content,
             identity,
         )
     }
@@ -810,54 +1609,211 @@ impl PestParserImpl {
     }
 
     fn parse_value_expr(mut params: FnParseParams) -> Result<ValueExpr> {
-        let expr = parse_child(
+        let first = parse_child(
+            &mut params,
+            Rule::sub_value_expr,
+            Self::parse_sub_value_expr,
+            identity,
+        )?;
+
+        let mut suffixes = Vec::new();
+        loop {
+            let suffix = if let Some(bin_op) = try_parse_child(
+                &mut params,
+                Rule::binary_operator,
+                Self::parse_binary_operator,
+                identity,
+            )? {
+                let right_expr = parse_child(
+                    &mut params,
+                    Rule::sub_value_expr,
+                    Self::parse_sub_value_expr,
+                    identity,
+                )?;
+                Some(ValueExprSuffixSyntax::BinaryOperator(bin_op, right_expr))
+            } else {
+                try_parse_child(
+                    &mut params,
+                    Rule::in_predicate,
+                    Self::parse_in_predicate,
+                    identity,
+                )?
+                .or(try_parse_child(
+                    &mut params,
+                    Rule::between_predicate,
+                    Self::parse_between_predicate,
+                    identity,
+                )?)
+            };
+
+            match suffix {
+                Some(suffix) => suffixes.push(suffix),
+                None => break,
+            }
+        }
+
+        Ok(Self::fold_value_expr(first, suffixes))
+    }
+
+    fn parse_in_predicate(mut params: FnParseParams) -> Result<ValueExprSuffixSyntax> {
+        let negated = try_parse_child(&mut params, Rule::not_keyword, |_| Ok(()), identity)?;
+        let list = parse_child_seq(
+            &mut params,
+            Rule::value_expr,
+            &Self::parse_value_expr,
+            &identity,
+        )?;
+        Ok(ValueExprSuffixSyntax::In {
+            list,
+            negated: negated.is_some(),
+        })
+    }
+
+    fn parse_between_predicate(mut params: FnParseParams) -> Result<ValueExprSuffixSyntax> {
+        let negated = try_parse_child(&mut params, Rule::not_keyword, |_| Ok(()), identity)?;
+        let low = parse_child(
+            &mut params,
+            Rule::between_bound,
+            Self::parse_between_bound,
+            identity,
+        )?;
+        let high = parse_child(
+            &mut params,
+            Rule::between_bound,
+            Self::parse_between_bound,
+            identity,
+        )?;
+        Ok(ValueExprSuffixSyntax::Between {
+            low,
+            high,
+            negated: negated.is_some(),
+        })
+    }
+
+    fn parse_between_bound(mut params: FnParseParams) -> Result<ValueExpr> {
+        let first = parse_child(
             &mut params,
             Rule::sub_value_expr,
             Self::parse_sub_value_expr,
             identity,
         )?;
 
-        if let Some(bin_op) = try_parse_child(
+        let mut suffixes = Vec::new();
+        while let Some(bin_op) = try_parse_child(
             &mut params,
-            Rule::binary_operator,
+            Rule::arithmetic_operator,
             Self::parse_binary_operator,
             identity,
         )? {
             let right_expr = parse_child(
                 &mut params,
-                Rule::value_expr,
-                Self::parse_value_expr,
+                Rule::sub_value_expr,
+                Self::parse_sub_value_expr,
                 identity,
             )?;
+            suffixes.push(ValueExprSuffixSyntax::BinaryOperator(bin_op, right_expr));
+        }
 
-            match bin_op {
-                BinaryOperator::Equal => Ok(ValueExpr::BinaryExpr(
-                    BinaryExpr::ComparisonFunctionVariant(ComparisonFunction::EqualVariant {
-                        left: Box::new(expr),
-                        right: Box::new(right_expr),
-                    }),
-                )),
-                BinaryOperator::Add => Ok(ValueExpr::BinaryExpr(
-                    BinaryExpr::NumericalFunctionVariant(NumericalFunction::AddVariant {
-                        left: Box::new(expr),
-                        right: Box::new(right_expr),
-                    }),
-                )),
-                BinaryOperator::Mul => Ok(ValueExpr::BinaryExpr(
-                    BinaryExpr::NumericalFunctionVariant(NumericalFunction::MulVariant {
-                        left: Box::new(expr),
-                        right: Box::new(right_expr),
-                    }),
-                )),
-                BinaryOperator::And => Ok(ValueExpr::BinaryExpr(
-                    BinaryExpr::LogicalFunctionVariant(LogicalFunction::AndVariant {
-                        left: Box::new(expr),
-                        right: Box::new(right_expr),
-                    }),
-                )),
+        Ok(Self::fold_value_expr(first, suffixes))
+    }
+
+    /// Builds an expression tree from `e0 op1 e1 op2 e2 ...` by operators' precedence (operator-precedence parsing).
+    ///
+    /// Predicates (IN, BETWEEN) take the preceding operand, built from operators with higher precedence, as their target.
+    fn fold_value_expr(first: ValueExpr, suffixes: Vec<ValueExprSuffixSyntax>) -> ValueExpr {
+        fn reduce_while(
+            operands: &mut Vec<ValueExpr>,
+            operators: &mut Vec<BinaryOperator>,
+            precedence: u8,
+        ) {
+            // left-associative: reduce operators with higher or the same precedence first
+            while operators
+                .last()
+                .map_or(false, |top| top.precedence() >= precedence)
+            {
+                let bin_op = operators.pop().expect("checked");
+                let right = operands.pop().expect("operands are more than operators");
+                let left = operands.pop().expect("operands are more than operators");
+                operands.push(PestParserImpl::binary_expr(bin_op, left, right));
+            }
+        }
+
+        let mut operands = vec![first];
+        let mut operators: Vec<BinaryOperator> = Vec::new();
+
+        for suffix in suffixes {
+            match suffix {
+                ValueExprSuffixSyntax::BinaryOperator(bin_op, expr) => {
+                    reduce_while(&mut operands, &mut operators, bin_op.precedence());
+                    operators.push(bin_op);
+                    operands.push(expr);
+                }
+                ValueExprSuffixSyntax::In { list, negated } => {
+                    reduce_while(
+                        &mut operands,
+                        &mut operators,
+                        BinaryOperator::Equal.precedence(),
+                    );
+                    let target = operands.pop().expect("at least 1 operand");
+                    operands.push(ValueExpr::BinaryExpr(
+                        BinaryExpr::ComparisonFunctionVariant(ComparisonFunction::InVariant {
+                            target: Box::new(target),
+                            list,
+                            negated,
+                        }),
+                    ));
+                }
+                ValueExprSuffixSyntax::Between { low, high, negated } => {
+                    reduce_while(
+                        &mut operands,
+                        &mut operators,
+                        BinaryOperator::Equal.precedence(),
+                    );
+                    let target = operands.pop().expect("at least 1 operand");
+                    operands.push(ValueExpr::BinaryExpr(
+                        BinaryExpr::ComparisonFunctionVariant(ComparisonFunction::BetweenVariant {
+                            target: Box::new(target),
+                            low: Box::new(low),
+                            high: Box::new(high),
+                            negated,
+                        }),
+                    ));
+                }
             }
-        } else {
-            Ok(expr)
+        }
+        reduce_while(&mut operands, &mut operators, 0);
+
+        operands.pop().expect("reduced into 1 expression")
+    }
+
+    fn binary_expr(bin_op: BinaryOperator, left: ValueExpr, right: ValueExpr) -> ValueExpr {
+        let (left, right) = (Box::new(left), Box::new(right));
+        match bin_op {
+            BinaryOperator::Equal => ValueExpr::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+                ComparisonFunction::EqualVariant { left, right },
+            )),
+            BinaryOperator::Like => ValueExpr::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+                ComparisonFunction::LikeVariant { left, right },
+            )),
+            BinaryOperator::IsDistinctFrom => {
+                ValueExpr::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+                    ComparisonFunction::IsDistinctFromVariant { left, right },
+                ))
+            }
+            BinaryOperator::IsNotDistinctFrom => {
+                ValueExpr::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+                    ComparisonFunction::IsNotDistinctFromVariant { left, right },
+                ))
+            }
+            BinaryOperator::Add => ValueExpr::BinaryExpr(BinaryExpr::NumericalFunctionVariant(
+                NumericalFunction::AddVariant { left, right },
+            )),
+            BinaryOperator::Mul => ValueExpr::BinaryExpr(BinaryExpr::NumericalFunctionVariant(
+                NumericalFunction::MulVariant { left, right },
+            )),
+            BinaryOperator::And => ValueExpr::BinaryExpr(BinaryExpr::LogicalFunctionVariant(
+                LogicalFunction::AndVariant { left, right },
+            )),
         }
     }
 
@@ -874,6 +1830,12 @@ impl PestParserImpl {
             Self::parse_column_reference,
             ValueExpr::ColumnReference,
         )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::window_metadata,
+            Self::parse_window_metadata,
+            ValueExpr::WindowMetadata,
+        )?)
         .or({
             if let Some(uni_op) = try_parse_child(
                 &mut params,
@@ -883,8 +1845,8 @@ impl PestParserImpl {
             )? {
                 Some(parse_child(
                     &mut params,
-                    Rule::value_expr,
-                    Self::parse_value_expr,
+                    Rule::sub_value_expr,
+                    Self::parse_sub_value_expr,
                     |expr| ValueExpr::UnaryOperator(uni_op.clone(), Box::new(expr)),
                 )?)
             } else {
@@ -897,6 +1859,12 @@ impl PestParserImpl {
             Self::parse_function_call,
             ValueExpr::FunctionCall,
         )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::value_expr,
+            Self::parse_value_expr,
+            identity,
+        )?) // parenthesized
         .ok_or_else(|| {
             SpringError::Sql(anyhow!("Does not match any child rule of sub_value_expr.",))
         })
@@ -989,7 +1957,45 @@ impl PestParserImpl {
                     )))
                 }
             }
-            "floor" => unimplemented!(),
+            "regexp_match" => {
+                if parameters.len() == 2 {
+                    if let ValueExpr::Constant(SqlValue::NotNull(NnSqlValue::Text(pattern))) =
+                        &parameters[1]
+                    {
+                        validate_regexp(pattern)?;
+                    }
+                    Ok(FunctionCall::RegexpMatch {
+                        target: Box::new(parameters[0].clone()),
+                        pattern: Box::new(parameters[1].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "regexp_match() takes exactly two parameters (target, pattern)."
+                    )))
+                }
+            }
+            "coalesce" => {
+                if parameters.is_empty() {
+                    Err(SpringError::Sql(anyhow!(
+                        "coalesce() takes one or more parameters (value, ...)."
+                    )))
+                } else {
+                    Ok(FunctionCall::Coalesce { values: parameters })
+                }
+            }
+            "nullif" => {
+                if parameters.len() == 2 {
+                    Ok(FunctionCall::NullIf {
+                        value: Box::new(parameters[0].clone()),
+                        compared: Box::new(parameters[1].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "nullif() takes exactly two parameters (value, compared)."
+                    )))
+                }
+            }
+            "floor" => Err(SpringError::Sql(anyhow!("floor() is not supported yet."))),
             _ => Err(SpringError::Sql(anyhow!(
                 "unknown function {}",
                 function_name.to_lowercase()
@@ -1014,19 +2020,48 @@ impl PestParserImpl {
             Self::parse_aggregate_name,
             identity,
         )?;
-        let aggregated = parse_child(
-            &mut params,
-            Rule::value_expr,
-            &Self::parse_value_expr,
-            &identity,
-        )?;
+        let aggregated =
+            match try_parse_child(&mut params, Rule::aggr_asterisk, |_| Ok(()), identity)? {
+                // COUNT(*) counts rows, which never evaluate into NULL
+                Some(()) if func == AggregateFunctionParameter::Count => {
+                    ValueExpr::Constant(SqlValue::NotNull(NnSqlValue::BigInt(1)))
+                }
+                Some(()) => {
+                    return Err(SpringError::Sql(anyhow!(
+                        "`*` is only available in COUNT(*), not in {:?}",
+                        func
+                    )))
+                }
+                None => parse_child(
+                    &mut params,
+                    Rule::value_expr,
+                    &Self::parse_value_expr,
+                    &identity,
+                )?,
+            };
         Ok(AggrExpr { func, aggregated })
     }
 
+    fn parse_window_metadata(mut params: FnParseParams) -> Result<WindowMetadata> {
+        let s = self_as_str(&mut params);
+        match s.to_lowercase().as_str() {
+            "window_start" => Ok(WindowMetadata::WindowStart),
+            "window_end" => Ok(WindowMetadata::WindowEnd),
+            "pane_row_count" => Ok(WindowMetadata::PaneRowCount),
+            _ => Err(SpringError::Sql(anyhow!(
+                "Does not match any child rule of window_metadata.",
+            ))),
+        }
+    }
+
     fn parse_aggregate_name(mut params: FnParseParams) -> Result<AggregateFunctionParameter> {
         let s = self_as_str(&mut params);
         match s.to_lowercase().as_str() {
             "avg" => Ok(AggregateFunctionParameter::Avg),
+            "count" => Ok(AggregateFunctionParameter::Count),
+            "sum" => Ok(AggregateFunctionParameter::Sum),
+            "min" => Ok(AggregateFunctionParameter::Min),
+            "max" => Ok(AggregateFunctionParameter::Max),
             _ => Err(SpringError::Sql(anyhow!(
                 "unknown aggregate function {}",
                 s.to_lowercase()
@@ -1040,9 +2075,13 @@ impl PestParserImpl {
      * ================================================================================================
      */
 
+    /// Double quotes of a quoted identifier are removed.
     fn parse_identifier(mut params: FnParseParams) -> Result<String> {
         let s = self_as_str(&mut params);
-        Ok(s.to_string())
+        match s.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+            Some(quoted) => Ok(quoted.replace("\"\"", "\"")),
+            None => Ok(s.to_string()),
+        }
     }
 
     /*
@@ -1103,14 +2142,17 @@ impl PestParserImpl {
      */
 
     fn parse_integer_type(mut params: FnParseParams) -> Result<SqlType> {
-        let s = self_as_str(&mut params);
+        let s = self_as_str(&mut params)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
         match s.to_ascii_uppercase().as_str() {
+            "SMALLINT" => Ok(SqlType::small_int()),
             "INTEGER" => Ok(SqlType::integer()),
+            "BIGINT" => Ok(SqlType::big_int()),
             "UNSIGNED INTEGER" => Ok(SqlType::unsigned_integer()),
-            x => {
-                eprintln!("Unexpected data type parsed: {}", x);
-                unreachable!();
-            }
+            "UNSIGNED BIGINT" => Ok(SqlType::unsigned_big_int()),
+            x => Err(SpringError::Sql(anyhow!("Unsupported integer type: {}", x))),
         }
     }
 
@@ -1182,6 +2224,22 @@ impl PestParserImpl {
      */
 
     fn parse_stream_name(mut params: FnParseParams) -> Result<StreamName> {
+        if let Some(stream_name) = try_parse_child(
+            &mut params,
+            Rule::system_stream_name,
+            Self::parse_system_stream_name,
+            StreamName::new,
+        )? {
+            return Ok(stream_name);
+        }
+        if let Some(stream_name) = try_parse_child(
+            &mut params,
+            Rule::namespaced_stream_name,
+            Self::parse_namespaced_stream_name,
+            StreamName::new,
+        )? {
+            return Ok(stream_name);
+        }
         parse_child(
             &mut params,
             Rule::identifier,
@@ -1190,6 +2248,36 @@ impl PestParserImpl {
         )
     }
 
+    fn parse_system_stream_name(mut params: FnParseParams) -> Result<String> {
+        let s = self_as_str(&mut params);
+        Ok(s.to_lowercase())
+    }
+
+    fn parse_namespaced_stream_name(mut params: FnParseParams) -> Result<String> {
+        let namespace = parse_child(
+            &mut params,
+            Rule::namespace,
+            Self::parse_namespace,
+            identity,
+        )?;
+        let stream_name = parse_child(
+            &mut params,
+            Rule::identifier,
+            Self::parse_identifier,
+            identity,
+        )?;
+        Ok(format!("{}.{}", namespace, stream_name))
+    }
+
+    fn parse_namespace(mut params: FnParseParams) -> Result<String> {
+        parse_child(
+            &mut params,
+            Rule::identifier,
+            Self::parse_identifier,
+            identity,
+        )
+    }
+
     fn parse_pump_name(mut params: FnParseParams) -> Result<PumpName> {
         parse_child(
             &mut params,
@@ -1217,8 +2305,21 @@ impl PestParserImpl {
         match typ.as_ref() {
             "NET_CLIENT" => Ok(SourceReaderType::NetClient),
             "NET_SERVER" => Ok(SourceReaderType::NetServer),
+            "NET_HTTP" => Ok(SourceReaderType::NetHttp),
+            "NET_MQTT" => Ok(SourceReaderType::NetMqtt),
             "CAN" => Ok(SourceReaderType::CAN),
             "IN_MEMORY_QUEUE" => Ok(SourceReaderType::InMemoryQueue),
+            "OPC_UA" => Ok(SourceReaderType::OpcUa),
+            "MODBUS_TCP" => Ok(SourceReaderType::ModbusTcp),
+            "REDIS_STREAM" => Ok(SourceReaderType::RedisStream),
+            "NATS" => Ok(SourceReaderType::Nats),
+            "KAFKA" => Ok(SourceReaderType::Kafka),
+            "ZEROMQ" => Ok(SourceReaderType::ZeroMq),
+            "ROS2" => Ok(SourceReaderType::Ros2),
+            "SPRINGQL_LINK" => Ok(SourceReaderType::SpringqlLink),
+            "FILE" => Ok(SourceReaderType::File),
+            "UNIX_SOCKET" => Ok(SourceReaderType::UnixSocket),
+            "UDP" => Ok(SourceReaderType::Udp),
             _ => Err(SpringError::Sql(anyhow!(
                 "Invalid source reader name: {}",
                 typ
@@ -1243,8 +2344,22 @@ impl PestParserImpl {
         )?;
         match typ.as_ref() {
             "NET_CLIENT" => Ok(SinkWriterType::Net),
+            "NET_MQTT" => Ok(SinkWriterType::NetMqtt),
             "HTTP1_CLIENT" => Ok(SinkWriterType::Http1Client),
             "IN_MEMORY_QUEUE" => Ok(SinkWriterType::InMemoryQueue),
+            "GPIO" => Ok(SinkWriterType::Gpio),
+            "COMMAND" => Ok(SinkWriterType::Command),
+            "AZURE_IOT_HUB" => Ok(SinkWriterType::AzureIotHub),
+            "AWS_IOT_CORE" => Ok(SinkWriterType::AwsIotCore),
+            "REDIS_STREAM" => Ok(SinkWriterType::RedisStream),
+            "NATS" => Ok(SinkWriterType::Nats),
+            "ZEROMQ" => Ok(SinkWriterType::ZeroMq),
+            "INFLUXDB" => Ok(SinkWriterType::InfluxDb),
+            "PARQUET" => Ok(SinkWriterType::Parquet),
+            "S3" => Ok(SinkWriterType::S3),
+            "SPRINGQL_LINK" => Ok(SinkWriterType::SpringqlLink),
+            "FILE" => Ok(SinkWriterType::File),
+            "UNIX_SOCKET" => Ok(SinkWriterType::UnixSocket),
             _ => Err(SpringError::Sql(anyhow!(
                 "Invalid source reader name: {}",
                 typ
@@ -1280,6 +2395,22 @@ impl PestParserImpl {
     }
 
     fn parse_correlation(mut params: FnParseParams) -> Result<StreamName> {
+        if let Some(stream_name) = try_parse_child(
+            &mut params,
+            Rule::system_stream_name,
+            Self::parse_system_stream_name,
+            StreamName::new,
+        )? {
+            return Ok(stream_name);
+        }
+        if let Some(stream_name) = try_parse_child(
+            &mut params,
+            Rule::namespaced_stream_name,
+            Self::parse_namespaced_stream_name,
+            StreamName::new,
+        )? {
+            return Ok(stream_name);
+        }
         parse_child(
             &mut params,
             Rule::identifier,
@@ -1405,4 +2536,159 @@ impl PestParserImpl {
             option_value,
         })
     }
+
+    /*
+     * ----------------------------------------------------------------------------
+     * Tag Specifications
+     * ----------------------------------------------------------------------------
+     */
+
+    /// Empty tags if `WITH TAGS` is not specified.
+    fn try_parse_tag_specifications(params: &mut FnParseParams) -> Result<Tags> {
+        let key_values = try_parse_child(
+            params,
+            Rule::tag_specifications,
+            &Self::parse_tag_specifications,
+            &identity,
+        )?;
+        key_values.map_or_else(|| Ok(Tags::default()), Tags::new)
+    }
+
+    fn parse_tag_specifications(mut params: FnParseParams) -> Result<Vec<(String, String)>> {
+        parse_child_seq(
+            &mut params,
+            Rule::tag_specification,
+            &Self::parse_tag_specification,
+            &identity,
+        )
+    }
+
+    fn parse_tag_specification(mut params: FnParseParams) -> Result<(String, String)> {
+        let key = parse_child(
+            &mut params,
+            Rule::tag_key,
+            &Self::parse_tag_string,
+            &identity,
+        )?;
+        let value = parse_child(
+            &mut params,
+            Rule::tag_value,
+            &Self::parse_tag_string,
+            &identity,
+        )?;
+        Ok((key, value))
+    }
+
+    fn parse_tag_string(mut params: FnParseParams) -> Result<String> {
+        parse_child(
+            &mut params,
+            Rule::string_content,
+            &Self::parse_string_content,
+            &identity,
+        )
+    }
+}
+
+/// Max depth of nested expressions outside quotes and comments: parentheses and runs of unary `-`.
+fn nesting_depth(sql: &str) -> usize {
+    let mut chars = sql.chars().peekable();
+    let (mut paren_depth, mut minus_run, mut max_depth) = (0_usize, 0_usize, 0_usize);
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' | '"' => {
+                chars.by_ref().find(|&q| q == c);
+                minus_run = 0;
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                chars.by_ref().find(|&c| c == '\n');
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                chars.by_ref().find(|&c| {
+                    let end = prev == '*' && c == '/';
+                    prev = c;
+                    end
+                });
+            }
+            '(' => {
+                paren_depth += 1;
+                minus_run = 0;
+            }
+            ')' => paren_depth = paren_depth.saturating_sub(1),
+            '-' => minus_run += 1,
+            c if c.is_whitespace() => {}
+            _ => minus_run = 0,
+        }
+        max_depth = max_depth.max(paren_depth + minus_run);
+    }
+    max_depth
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nesting_depth() {
+        assert_eq!(nesting_depth("SELECT STREAM 1;"), 0);
+        assert_eq!(nesting_depth("f((1), (2 + (3)))"), 3);
+        assert_eq!(nesting_depth("'(((' || \"(((\" || (1)"), 1);
+        assert_eq!(nesting_depth("))(("), 2);
+        assert_eq!(nesting_depth("1 - - -2"), 3);
+        assert_eq!(nesting_depth("-- ((( ---\n/* ((( */ (1)"), 1);
+    }
+
+    #[test]
+    fn test_too_deeply_nested() {
+        let parser = PestParserImpl::default();
+
+        let sql = format!(
+            "CREATE PUMP p AS INSERT INTO s (c) SELECT STREAM {}1{} FROM t;",
+            "(".repeat(100_000),
+            ")".repeat(100_000)
+        );
+        assert!(matches!(parser.parse(sql), Err(SpringError::Sql(_))));
+    }
+
+    #[test]
+    fn test_too_many_unary_minus() {
+        let parser = PestParserImpl::default();
+
+        let sql = format!(
+            "CREATE PUMP p AS INSERT INTO s (c) SELECT STREAM {}1 FROM t;",
+            "- ".repeat(100_000)
+        );
+        assert!(matches!(parser.parse(sql), Err(SpringError::Sql(_))));
+    }
+
+    #[test]
+    fn test_invalid_window_period_is_error() {
+        let parser = PestParserImpl::default();
+
+        for window in [
+            "SLIDING WINDOW DURATION_SECS(10), DURATION_SECS(0), DURATION_SECS(0)",
+            "SLIDING WINDOW DURATION_SECS(10), DURATION_SECS(20), DURATION_SECS(0)",
+            "FIXED WINDOW DURATION_SECS(0), DURATION_SECS(0)",
+        ] {
+            let sql = format!(
+                "CREATE PUMP p AS INSERT INTO s (c) SELECT STREAM COUNT(t.c) AS c FROM t {};",
+                window
+            );
+            assert!(matches!(parser.parse(sql), Err(SpringError::Sql(_))));
+        }
+
+        // hop interval not dividing length
+        let sql = "CREATE PUMP p AS INSERT INTO s (c) SELECT STREAM COUNT(t.c) AS c FROM t SLIDING WINDOW DURATION_SECS(10), DURATION_SECS(4), DURATION_SECS(0);";
+        assert!(parser.parse(sql).is_ok());
+    }
+
+    #[test]
+    fn test_unsupported_function_is_error() {
+        let parser = PestParserImpl::default();
+
+        let sql = "CREATE PUMP p AS INSERT INTO s (c) SELECT STREAM FLOOR(t.c) FROM t;";
+        assert!(matches!(parser.parse(sql), Err(SpringError::Sql(_))));
+    }
 }
@@ -2,7 +2,7 @@
 
 use std::collections::VecDeque;
 
-use anyhow::Context;
+use anyhow::{anyhow, Context};
 use pest::iterators::{Pair, Pairs};
 
 use crate::{
@@ -68,8 +68,9 @@ pub fn parse_child<T, ChildRet>(
 
         Ok(ret_closure(child_ast))
     } else {
-        panic!(
-            "Hit to unexpected rule: got({:?}); expected({:?})\n\
+        // a parser bug: the grammar allows a term which parser functions do not expect
+        Err(SpringError::Sql(anyhow!(
+            "Hit to unexpected rule (parser bug): got({:?}); expected({:?})\n\
         Pair: {}\n\
         {:#?}
         ",
@@ -77,7 +78,7 @@ pub fn parse_child<T, ChildRet>(
             child_term,
             child_pair,
             params
-        );
+        )))
     }
 }
 
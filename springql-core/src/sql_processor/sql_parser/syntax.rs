@@ -1,7 +1,7 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 use crate::{
-    expression::{AggrExpr, ValueExpr},
+    expression::{AggrExpr, BinaryOperator, ValueExpr},
     pipeline::{AggrAlias, CorrelationAlias, JoinType, StreamName, ValueAlias, WindowParameter},
 };
 
@@ -22,6 +22,9 @@ pub struct SelectStreamSyntax {
     pub fields: Vec<SelectFieldSyntax>,
     pub from_item: FromItemSyntax,
 
+    /// None when no WHERE clause is supplied.
+    pub where_clause: Option<ValueExpr>,
+
     /// Empty when no GROUP BY clause is supplied.
     pub grouping_elements: Vec<GroupingElementSyntax>,
 
@@ -59,6 +62,21 @@ pub struct SubFromItemSyntax {
     pub alias: Option<CorrelationAlias>,
 }
 
+/// What follows an operand in a value expression.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ValueExprSuffixSyntax {
+    /// `op right`
+    BinaryOperator(BinaryOperator, ValueExpr),
+    /// `[NOT] IN (list...)`
+    In { list: Vec<ValueExpr>, negated: bool },
+    /// `[NOT] BETWEEN low AND high`
+    Between {
+        low: ValueExpr,
+        high: ValueExpr,
+        negated: bool,
+    },
+}
+
 #[derive(Clone, PartialEq, Debug)]
 pub enum GroupingElementSyntax {
     ValueExpr(ValueExpr),
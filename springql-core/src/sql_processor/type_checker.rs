@@ -0,0 +1,634 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Compile-time type checking of pumps against stream schemas.
+//!
+//! Types of expressions are inferred from the columns of upstream streams in the same way as they are evaluated at runtime,
+//! so that a strictly checked pump never fails to evaluate an expression or to insert a row due to types or NULLs.
+
+use std::{collections::HashMap, fmt, mem};
+
+use anyhow::anyhow;
+
+use crate::{
+    api::error::{Result, SpringError},
+    expr_resolver::{ExprLabel, ExprResolver, ValueExprLabel},
+    expression::{
+        AggrExpr, BinaryExpr, ComparisonFunction, FunctionCall, LogicalFunction, NumericalFunction,
+        UnaryOperator, ValueExpr,
+    },
+    pipeline::{
+        AggregateFunctionParameter, ColumnReference, JoinType, NumericComparableType, Pipeline,
+        PumpName, SqlType, StreamName, WindowMetadata, WindowOperationParameter,
+    },
+    stream_engine::{
+        command::{
+            GroupAggregateWindowOp, InsertPlan, IntervalJoinOp, JoinOp, JoinWindowOp, QueryPlan,
+        },
+        NnSqlValue, SqlValue,
+    },
+};
+
+/// How types in a pump are checked.
+///
+/// Set by `pipeline.type_checking` config, and overridden by `CREATE PUMP pump_name STRICT AS ...` or `CREATE PUMP pump_name LENIENT AS ...`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum TypeChecking {
+    /// CREATE PUMP fails if an expression has operands of mismatched types, or a selected field may not be inserted into its column as-is
+    /// (including a nullable field into a NOT NULL column).
+    Strict,
+
+    /// Values are converted into column types at runtime. Rows failing evaluation or conversion are routed to `spring._row_errors`.
+    Lenient,
+}
+
+/// Type and nullability of an expression.
+#[derive(Clone, Eq, PartialEq, Debug)]
+struct ExprType {
+    /// `None` for NULL constant, which matches any type.
+    sql_type: Option<SqlType>,
+    nullable: bool,
+}
+
+impl ExprType {
+    fn new(sql_type: SqlType, nullable: bool) -> Self {
+        Self {
+            sql_type: Some(sql_type),
+            nullable,
+        }
+    }
+
+    fn null() -> Self {
+        Self {
+            sql_type: None,
+            nullable: true,
+        }
+    }
+
+    fn boolean(nullable: bool) -> Self {
+        Self::new(SqlType::boolean(), nullable)
+    }
+
+    fn is(&self, sql_type: &SqlType) -> bool {
+        self.sql_type.as_ref().map_or(true, |t| t == sql_type)
+    }
+
+    fn is_integer(&self) -> bool {
+        matches!(
+            self.sql_type,
+            Some(SqlType::NumericComparable(
+                NumericComparableType::I64Loose(_) | NumericComparableType::U64Loose(_)
+            ))
+        )
+    }
+}
+
+impl fmt::Display for ExprType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.sql_type, self.nullable) {
+            (None, _) => write!(f, "NULL"),
+            (Some(sql_type), true) => write!(f, "{}", sql_type),
+            (Some(sql_type), false) => write!(f, "{} NOT NULL", sql_type),
+        }
+    }
+}
+
+/// Infers types of expressions in a query plan from the columns of its upstream streams.
+#[derive(Debug)]
+pub(super) struct TypeChecker<'a> {
+    pump_name: &'a PumpName,
+    expr_resolver: &'a ExprResolver,
+
+    /// Columns and processing times of upstream streams.
+    columns: HashMap<ColumnReference, ExprType>,
+}
+
+impl<'a> TypeChecker<'a> {
+    /// `prioritized` pumps have the `PRIORITY WHEN` condition as the last select field.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - an upstream or the downstream stream is not found in pipeline.
+    ///   - an expression references a column not in upstream streams.
+    ///   - an expression has operands of mismatched types (e.g. `TEXT + INTEGER`), or a possibly NULL operand where NULL is an error.
+    ///   - a condition in WHERE, ON or PRIORITY WHEN clause is not BOOLEAN.
+    ///   - the number of select fields differs from the number of inserted columns.
+    ///   - a select field has a different type from its column (except for widening between signed integers),
+    ///     or is nullable while its column is NOT NULL.
+    pub(super) fn check_pump(
+        pump_name: &'a PumpName,
+        query_plan: &'a QueryPlan,
+        insert_plan: &InsertPlan,
+        prioritized: bool,
+        pipeline: &Pipeline,
+    ) -> Result<()> {
+        let checker = Self::new(pump_name, query_plan, pipeline)?;
+        checker.check_lower_ops(query_plan)?;
+
+        let mut fields = checker.select_fields(query_plan)?;
+        if prioritized {
+            let (priority_type, _) = fields.pop().expect("PRIORITY WHEN condition");
+            checker.ensure_boolean("PRIORITY WHEN condition", &priority_type)?;
+        }
+        checker.check_insert(fields, insert_plan, pipeline)
+    }
+
+    fn new(
+        pump_name: &'a PumpName,
+        query_plan: &'a QueryPlan,
+        pipeline: &Pipeline,
+    ) -> Result<Self> {
+        let mut columns = HashMap::new();
+        match &query_plan.lower_ops.join {
+            JoinOp::Collect(collect) => {
+                Self::add_columns(&mut columns, &collect.stream, false, pipeline)?;
+            }
+            JoinOp::JoinWindow(JoinWindowOp {
+                left,
+                right,
+                join_param,
+                ..
+            })
+            | JoinOp::IntervalJoin(IntervalJoinOp {
+                left,
+                right,
+                join_param,
+                ..
+            }) => {
+                // columns from right are NULL for left rows without matching right rows
+                let right_nullable = match join_param.join_type {
+                    JoinType::LeftOuter => true,
+                };
+                Self::add_columns(&mut columns, &left.stream, false, pipeline)?;
+                Self::add_columns(&mut columns, &right.stream, right_nullable, pipeline)?;
+            }
+        }
+
+        Ok(Self {
+            pump_name,
+            expr_resolver: &query_plan.expr_resolver,
+            columns,
+        })
+    }
+
+    fn add_columns(
+        columns: &mut HashMap<ColumnReference, ExprType>,
+        stream_name: &StreamName,
+        nullable: bool,
+        pipeline: &Pipeline,
+    ) -> Result<()> {
+        let stream = pipeline.get_stream(stream_name)?;
+        for coldef in stream.shape().columns() {
+            let data_type = coldef.column_data_type();
+            columns.insert(
+                ColumnReference::Column {
+                    stream_name: stream_name.clone(),
+                    column_name: data_type.column_name().clone(),
+                },
+                ExprType::new(
+                    data_type.sql_type().clone(),
+                    nullable || data_type.nullable(),
+                ),
+            );
+        }
+        columns.insert(
+            ColumnReference::PTime {
+                stream_name: stream_name.clone(),
+            },
+            ExprType::new(SqlType::timestamp(), nullable),
+        );
+        Ok(())
+    }
+
+    fn check_lower_ops(&self, query_plan: &QueryPlan) -> Result<()> {
+        match &query_plan.lower_ops.join {
+            JoinOp::Collect(_) => {}
+            JoinOp::JoinWindow(JoinWindowOp { join_param, .. })
+            | JoinOp::IntervalJoin(IntervalJoinOp { join_param, .. }) => {
+                for key in &join_param.keys {
+                    let left = self.infer_label(key.left)?;
+                    let right = self.infer_label(key.right)?;
+                    self.ensure_comparable("ON", &left, &right)?;
+                }
+                if let Some(on_expr) = join_param.on_expr {
+                    self.ensure_boolean("ON condition", &self.infer_label(on_expr)?)?;
+                }
+            }
+        }
+
+        if let Some(selection) = &query_plan.lower_ops.selection {
+            self.ensure_boolean("WHERE condition", &self.infer_label(selection.condition)?)?;
+        }
+        Ok(())
+    }
+
+    /// # Returns
+    ///
+    /// Types of select fields with their values if they are NOT NULL constants.
+    fn select_fields(&self, query_plan: &QueryPlan) -> Result<Vec<(ExprType, Option<NnSqlValue>)>> {
+        if let Some(GroupAggregateWindowOp {
+            op_param: WindowOperationParameter::Aggregate(aggr_param),
+            ..
+        }) = &query_plan.upper_ops.group_aggr_window
+        {
+            for label in aggr_param.group_by.as_labels() {
+                self.infer_label(*label)?;
+            }
+        }
+
+        query_plan
+            .upper_ops
+            .projection
+            .expr_labels
+            .iter()
+            .map(|label| match label {
+                ExprLabel::Value(value_label) => {
+                    let value_expr = self.expr_resolver.resolve_value_expr(*value_label);
+                    let expr_type = self.infer(&value_expr)?;
+                    let constant = match value_expr {
+                        ValueExpr::Constant(SqlValue::NotNull(value)) => Some(value),
+                        _ => None,
+                    };
+                    Ok((expr_type, constant))
+                }
+                ExprLabel::Aggr(aggr_label) => {
+                    let aggr_expr = self.expr_resolver.resolve_aggr_expr(*aggr_label);
+                    Ok((self.infer_aggr(&aggr_expr)?, None))
+                }
+            })
+            .collect()
+    }
+
+    fn check_insert(
+        &self,
+        fields: Vec<(ExprType, Option<NnSqlValue>)>,
+        insert_plan: &InsertPlan,
+        pipeline: &Pipeline,
+    ) -> Result<()> {
+        let stream = pipeline.get_stream(insert_plan.stream())?;
+        let column_names = insert_plan.column_order();
+        if fields.len() != column_names.len() {
+            return Err(self.error(format!(
+                r#"{} columns of stream "{}" are inserted but {} fields are selected"#,
+                column_names.len(),
+                stream.name(),
+                fields.len()
+            )));
+        }
+
+        for (column_name, (field_type, constant)) in column_names.iter().zip(fields) {
+            let data_type = stream
+                .shape()
+                .columns()
+                .iter()
+                .map(|coldef| coldef.column_data_type())
+                .find(|data_type| data_type.column_name() == column_name)
+                .ok_or_else(|| {
+                    SpringError::Sql(anyhow!(
+                        r#"column "{}" is not in stream "{}""#,
+                        column_name,
+                        stream.name()
+                    ))
+                })?;
+            let column_type = data_type.sql_type();
+
+            let type_matches = match (&constant, &field_type.sql_type) {
+                (Some(value), _) => value.try_convert(column_type).is_ok(),
+                (None, Some(sql_type)) => Self::widens_to(sql_type, column_type),
+                (None, None) => true,
+            };
+            if !type_matches || (field_type.nullable && !data_type.nullable()) {
+                return Err(self.error(format!(
+                    r#"column "{}" of stream "{}" is {} but {} is inserted"#,
+                    column_name,
+                    stream.name(),
+                    ExprType::new(column_type.clone(), data_type.nullable()),
+                    constant.map_or_else(|| field_type.to_string(), |value| value.to_string()),
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Conversions between the types never fail at runtime.
+    fn widens_to(from: &SqlType, to: &SqlType) -> bool {
+        from == to
+            || [
+                (SqlType::small_int(), SqlType::integer()),
+                (SqlType::small_int(), SqlType::big_int()),
+                (SqlType::integer(), SqlType::big_int()),
+            ]
+            .contains(&(from.clone(), to.clone()))
+    }
+
+    fn infer_label(&self, label: ValueExprLabel) -> Result<ExprType> {
+        self.infer(&self.expr_resolver.resolve_value_expr(label))
+    }
+
+    fn infer(&self, value_expr: &ValueExpr) -> Result<ExprType> {
+        match value_expr {
+            ValueExpr::Constant(SqlValue::Null) => Ok(ExprType::null()),
+            ValueExpr::Constant(SqlValue::NotNull(value)) => {
+                Ok(ExprType::new(value.sql_type(), false))
+            }
+            ValueExpr::ColumnReference(colref) => {
+                self.columns.get(colref).cloned().ok_or_else(|| {
+                    let name = match colref {
+                        ColumnReference::Column {
+                            stream_name,
+                            column_name,
+                        } => format!("{}.{}", stream_name, column_name),
+                        ColumnReference::PTime { stream_name } => format!("{}.PTIME", stream_name),
+                    };
+                    self.error(format!("{} is not in upstream streams", name))
+                })
+            }
+            ValueExpr::WindowMetadata(window_metadata) => Ok(match window_metadata {
+                WindowMetadata::WindowStart | WindowMetadata::WindowEnd => {
+                    ExprType::new(SqlType::timestamp(), false)
+                }
+                WindowMetadata::PaneRowCount => ExprType::new(SqlType::big_int(), false),
+            }),
+            ValueExpr::UnaryOperator(UnaryOperator::Minus, child) => {
+                let child = self.infer(child)?;
+                match &child.sql_type {
+                    None
+                    | Some(SqlType::NumericComparable(
+                        NumericComparableType::I64Loose(_) | NumericComparableType::F32Loose(_),
+                    )) => Ok(child),
+                    Some(_) => Err(self.error(format!("`-` cannot negate {}", child))),
+                }
+            }
+            ValueExpr::BinaryExpr(BinaryExpr::LogicalFunctionVariant(
+                LogicalFunction::AndVariant { left, right },
+            )) => {
+                let (left, right) = (self.infer(left)?, self.infer(right)?);
+                self.ensure_boolean("operand of AND", &left)?;
+                self.ensure_boolean("operand of AND", &right)?;
+                Ok(ExprType::boolean(left.nullable || right.nullable))
+            }
+            ValueExpr::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(comparison_function)) => {
+                self.infer_comparison(comparison_function)
+            }
+            ValueExpr::BinaryExpr(BinaryExpr::NumericalFunctionVariant(numerical_function)) => {
+                self.infer_numerical(numerical_function)
+            }
+            ValueExpr::FunctionCall(function_call) => self.infer_function_call(function_call),
+        }
+    }
+
+    fn infer_comparison(
+        &self,
+        comparison_function: &ComparisonFunction<ValueExpr>,
+    ) -> Result<ExprType> {
+        match comparison_function {
+            ComparisonFunction::EqualVariant { left, right } => {
+                let (left, right) = (self.infer(left)?, self.infer(right)?);
+                self.ensure_comparable("=", &left, &right)?;
+                Ok(ExprType::boolean(left.nullable || right.nullable))
+            }
+            ComparisonFunction::LikeVariant { left, right } => {
+                let (left, right) = (self.infer(left)?, self.infer(right)?);
+                self.ensure(left.is(&SqlType::text()), "target of LIKE", "TEXT", &left)?;
+                self.ensure(
+                    right.is(&SqlType::text()),
+                    "pattern of LIKE",
+                    "TEXT",
+                    &right,
+                )?;
+                Ok(ExprType::boolean(left.nullable || right.nullable))
+            }
+            ComparisonFunction::IsDistinctFromVariant { left, right }
+            | ComparisonFunction::IsNotDistinctFromVariant { left, right } => {
+                let (left, right) = (self.infer(left)?, self.infer(right)?);
+                self.ensure_comparable("IS DISTINCT FROM", &left, &right)?;
+                Ok(ExprType::boolean(false))
+            }
+            ComparisonFunction::InVariant { target, list, .. } => {
+                let target = self.infer(target)?;
+                let mut nullable = target.nullable;
+                for item in list {
+                    let item = self.infer(item)?;
+                    self.ensure_comparable("IN", &target, &item)?;
+                    nullable |= item.nullable;
+                }
+                Ok(ExprType::boolean(nullable))
+            }
+            ComparisonFunction::BetweenVariant {
+                target, low, high, ..
+            } => {
+                let target = self.infer(target)?;
+                let (low, high) = (self.infer(low)?, self.infer(high)?);
+                self.ensure_comparable("BETWEEN", &target, &low)?;
+                self.ensure_comparable("BETWEEN", &target, &high)?;
+                Ok(ExprType::boolean(
+                    target.nullable || low.nullable || high.nullable,
+                ))
+            }
+        }
+    }
+
+    /// Integers are added / multiplied as BIGINT or UNSIGNED BIGINT. TIMESTAMP + DURATION is TIMESTAMP.
+    fn infer_numerical(
+        &self,
+        numerical_function: &NumericalFunction<ValueExpr>,
+    ) -> Result<ExprType> {
+        let (op, left, right) = match numerical_function {
+            NumericalFunction::AddVariant { left, right } => ("+", left, right),
+            NumericalFunction::MulVariant { left, right } => ("*", left, right),
+        };
+        let (left, right) = (self.infer(left)?, self.infer(right)?);
+
+        let sql_type = match (&left.sql_type, &right.sql_type) {
+            // NULL + x is NULL
+            (None, _) | (_, None) => None,
+            (Some(SqlType::TimestampComparable), Some(SqlType::DurationComparable))
+            | (Some(SqlType::DurationComparable), Some(SqlType::TimestampComparable))
+                if op == "+" =>
+            {
+                Some(SqlType::timestamp())
+            }
+            (Some(SqlType::NumericComparable(l)), Some(SqlType::NumericComparable(r))) => {
+                match (l, r) {
+                    (NumericComparableType::I64Loose(_), NumericComparableType::I64Loose(_)) => {
+                        Some(SqlType::big_int())
+                    }
+                    (NumericComparableType::U64Loose(_), NumericComparableType::U64Loose(_)) => {
+                        Some(SqlType::unsigned_big_int())
+                    }
+                    (NumericComparableType::F32Loose(_), NumericComparableType::F32Loose(_)) => {
+                        Some(SqlType::float())
+                    }
+                    _ => return Err(self.mismatch(op, &left, &right)),
+                }
+            }
+            _ => return Err(self.mismatch(op, &left, &right)),
+        };
+        Ok(ExprType {
+            sql_type,
+            nullable: left.nullable || right.nullable,
+        })
+    }
+
+    fn infer_function_call(&self, function_call: &FunctionCall<ValueExpr>) -> Result<ExprType> {
+        match function_call {
+            FunctionCall::DurationMillis {
+                duration_millis: arg,
+            }
+            | FunctionCall::DurationSecs { duration_secs: arg } => {
+                let arg = self.infer(arg)?;
+                self.ensure(
+                    arg.is_integer() && !arg.nullable,
+                    "argument of DURATION_MILLIS / DURATION_SECS",
+                    "an integer NOT NULL",
+                    &arg,
+                )?;
+                Ok(ExprType::new(SqlType::duration(), false))
+            }
+            FunctionCall::FloorTime { target, resolution } => {
+                let (target, resolution) = (self.infer(target)?, self.infer(resolution)?);
+                self.ensure(
+                    target.is(&SqlType::timestamp()) && !target.nullable,
+                    "target of FLOOR_TIME",
+                    "TIMESTAMP NOT NULL",
+                    &target,
+                )?;
+                self.ensure(
+                    resolution.is(&SqlType::duration()) && !resolution.nullable,
+                    "resolution of FLOOR_TIME",
+                    "DURATION NOT NULL",
+                    &resolution,
+                )?;
+                Ok(ExprType::new(SqlType::timestamp(), false))
+            }
+            FunctionCall::RegexpMatch { target, pattern } => {
+                let (target, pattern) = (self.infer(target)?, self.infer(pattern)?);
+                self.ensure(
+                    target.is(&SqlType::text()),
+                    "target of REGEXP_MATCH",
+                    "TEXT",
+                    &target,
+                )?;
+                self.ensure(
+                    pattern.is(&SqlType::text()),
+                    "pattern of REGEXP_MATCH",
+                    "TEXT",
+                    &pattern,
+                )?;
+                Ok(ExprType::boolean(target.nullable || pattern.nullable))
+            }
+            FunctionCall::Coalesce { values } => {
+                let values = values
+                    .iter()
+                    .map(|value| self.infer(value))
+                    .collect::<Result<Vec<_>>>()?;
+                let sql_type = values.iter().find_map(|value| value.sql_type.clone());
+                if let Some(sql_type) = &sql_type {
+                    for value in &values {
+                        self.ensure(
+                            value.is(sql_type),
+                            "argument of COALESCE",
+                            &sql_type.to_string(),
+                            value,
+                        )?;
+                    }
+                }
+                Ok(ExprType {
+                    sql_type,
+                    nullable: values.iter().all(|value| value.nullable),
+                })
+            }
+            FunctionCall::NullIf { value, compared } => {
+                let (value, compared) = (self.infer(value)?, self.infer(compared)?);
+                self.ensure_comparable("NULLIF", &value, &compared)?;
+                Ok(ExprType {
+                    sql_type: value.sql_type,
+                    nullable: true,
+                })
+            }
+        }
+    }
+
+    /// See `AggregateState::finalize()` for result types.
+    fn infer_aggr(&self, aggr_expr: &AggrExpr) -> Result<ExprType> {
+        let aggregated = self.infer(&aggr_expr.aggregated)?;
+        let numeric = matches!(
+            aggregated.sql_type,
+            None | Some(SqlType::NumericComparable(_))
+        );
+
+        let sql_type = match aggr_expr.func {
+            AggregateFunctionParameter::Count => {
+                return Ok(ExprType::new(SqlType::big_int(), false))
+            }
+            AggregateFunctionParameter::Min | AggregateFunctionParameter::Max => {
+                aggregated.sql_type.clone()
+            }
+            AggregateFunctionParameter::Avg => {
+                self.ensure(numeric, "argument of AVG", "numeric", &aggregated)?;
+                Some(SqlType::float())
+            }
+            AggregateFunctionParameter::Sum => {
+                self.ensure(numeric, "argument of SUM", "numeric", &aggregated)?;
+                match &aggregated.sql_type {
+                    Some(SqlType::NumericComparable(NumericComparableType::I64Loose(_))) => {
+                        Some(SqlType::big_int())
+                    }
+                    Some(SqlType::NumericComparable(NumericComparableType::U64Loose(_))) => {
+                        Some(SqlType::unsigned_big_int())
+                    }
+                    sql_type => sql_type.clone(),
+                }
+            }
+        };
+        Ok(ExprType {
+            sql_type,
+            nullable: aggregated.nullable,
+        })
+    }
+
+    fn ensure_boolean(&self, what: &str, expr_type: &ExprType) -> Result<()> {
+        self.ensure(
+            expr_type.is(&SqlType::boolean()),
+            what,
+            "BOOLEAN",
+            expr_type,
+        )
+    }
+
+    /// Numeric types are comparable with each other if they are all signed integers, unsigned integers, or floats.
+    fn ensure_comparable(&self, op: &str, left: &ExprType, right: &ExprType) -> Result<()> {
+        let comparable = match (&left.sql_type, &right.sql_type) {
+            (Some(SqlType::NumericComparable(l)), Some(SqlType::NumericComparable(r))) => {
+                mem::discriminant(l) == mem::discriminant(r)
+            }
+            (Some(l), Some(r)) => l == r,
+            _ => true,
+        };
+        if comparable {
+            Ok(())
+        } else {
+            Err(self.mismatch(op, left, right))
+        }
+    }
+
+    fn ensure(&self, cond: bool, what: &str, expected: &str, actual: &ExprType) -> Result<()> {
+        if cond {
+            Ok(())
+        } else {
+            Err(self.error(format!("{} must be {} but is {}", what, expected, actual)))
+        }
+    }
+
+    fn mismatch(&self, op: &str, left: &ExprType, right: &ExprType) -> SpringError {
+        self.error(format!("`{}` cannot take {} and {}", op, left, right))
+    }
+
+    fn error(&self, reason: String) -> SpringError {
+        SpringError::Sql(anyhow!(
+            r#"type mismatch in pump "{}": {}"#,
+            self.pump_name,
+            reason
+        ))
+    }
+}
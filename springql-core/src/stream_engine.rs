@@ -4,24 +4,41 @@
 
 pub mod autonomous_executor;
 pub mod command;
+mod ddl_wal;
 mod in_memory_queue_repository;
+mod pipeline_history;
+mod pipeline_objects;
+mod pipeline_snapshot;
+mod schema_inferrer;
 mod sql_executor;
 pub mod time;
 
-use std::sync::{Arc, Mutex, MutexGuard};
+use std::{
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
 
 use anyhow::anyhow;
 
 pub use crate::stream_engine::autonomous_executor::SpringValue;
 pub use autonomous_executor::{NnSqlValue, RowTime, SqlCompareResult, SqlValue, StreamRow, Tuple};
+pub use pipeline_snapshot::PipelineSnapshot;
 
 use crate::{
-    api::{error::Result, SpringConfig, SpringError},
-    pipeline::{Pipeline, QueueName},
+    api::{error::Result, SpringConfig, SpringError, SpringSourceReaderConfig, SpringTaskHook},
+    pipeline::{
+        ColumnDataType, ColumnDefinition, InMemoryQueueOptions, Pipeline, QueueName,
+        SinkWriterType, StreamName,
+    },
     stream_engine::{
-        autonomous_executor::{AutonomousExecutor, SchemalessRow},
-        command::AlterPipelineCommand,
+        autonomous_executor::{AutonomousExecutor, SchemalessRow, SourceSampler, StreamTap},
+        command::{AlterPipelineCommand, InferSchemaCommand, ShowObjectsCommand},
+        ddl_wal::DdlWal,
         in_memory_queue_repository::InMemoryQueueRepository,
+        pipeline_history::PipelineHistory,
+        pipeline_objects::object_rows,
+        pipeline_snapshot::PipelineSnapshot,
+        schema_inferrer::SchemaInferrer,
         sql_executor::SqlExecutor,
     },
 };
@@ -35,9 +52,12 @@ impl EngineMutex {
         Self(Arc::new(Mutex::new(engine)))
     }
 
+    /// Fatal errors got by workers are left for API calls (see `StreamEngine::take_fatal_error()`).
+    ///
     /// # Failure
     ///
-    /// - `SpringError::ThreadPoisoned`
+    /// - `SpringError::SpringQlCoreIo` when:
+    ///   - another thread sharing the stream engine got panic.
     pub fn get(&self) -> Result<MutexGuard<'_, StreamEngine>> {
         self.0
             .lock()
@@ -59,6 +79,13 @@ impl EngineMutex {
 pub struct StreamEngine {
     sql_executor: SqlExecutor,
     autonomous_executor: AutonomousExecutor,
+    history: PipelineHistory,
+    /// Statements which altered the pipeline as-is in the applied order, for snapshots.
+    statements: Vec<String>,
+    /// Set after statements in it are replayed.
+    ddl_wal: Option<DdlWal>,
+
+    source_reader_config: SpringSourceReaderConfig,
 }
 
 impl StreamEngine {
@@ -67,17 +94,157 @@ impl StreamEngine {
         Self {
             sql_executor: SqlExecutor::default(),
             autonomous_executor: AutonomousExecutor::new(config),
+            history: PipelineHistory::default(),
+            statements: Vec::new(),
+            ddl_wal: None,
+            source_reader_config: config.source_reader,
         }
     }
 
+    /// A fatal error got by workers since the last call, if any.
+    /// Workers continue after it but results are not reliable, so API calls return it to the application.
+    pub fn take_fatal_error(&self) -> Option<SpringError> {
+        self.autonomous_executor.take_fatal_error()
+    }
+
     pub fn current_pipeline(&self) -> &Pipeline {
         self.sql_executor.current_pipeline()
     }
 
-    pub fn alter_pipeline(&mut self, command: AlterPipelineCommand) -> Result<()> {
+    /// `statement` is the SQL text `command` is compiled from, recorded in the history and the DDL WAL on success.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to append `statement` to the DDL WAL. The pipeline is not altered.
+    pub fn alter_pipeline(&mut self, command: AlterPipelineCommand, statement: &str) -> Result<()> {
         log::debug!("[StreamEngine] alter_pipeline({:?})", command);
-        let pipeline = self.sql_executor.alter_pipeline(command)?;
-        self.autonomous_executor.notify_pipeline_update(pipeline)
+        let ddl_wal = &mut self.ddl_wal;
+        let pipeline = self
+            .sql_executor
+            .alter_pipeline(command, |new_pipeline| match ddl_wal {
+                Some(ddl_wal) => ddl_wal.append(new_pipeline.version(), statement),
+                None => Ok(()),
+            })?;
+        let version = pipeline.version();
+        self.autonomous_executor.notify_pipeline_update(pipeline)?;
+        self.history.record(version, statement);
+        self.statements.push(statement.to_string());
+        Ok(())
+    }
+
+    /// Statements which altered the pipeline so far, and rows in queues and states of windows with aggregation.
+    pub fn snapshot(&self) -> PipelineSnapshot {
+        PipelineSnapshot::new(self.statements.clone(), self.autonomous_executor.snapshot())
+    }
+
+    /// Puts rows and window states in `snapshot` into the pipeline, which must be altered by `snapshot.statements()` beforehand.
+    ///
+    /// # Failure
+    ///
+    /// - Raises Err from `AutonomousExecutor::restore()` as-is.
+    pub fn restore_state(&self, snapshot: PipelineSnapshot) -> Result<()> {
+        self.autonomous_executor.restore(snapshot.into_state())
+    }
+
+    /// Opens the DDL WAL if `recovery.ddl_wal_path` is set.
+    ///
+    /// # Returns
+    ///
+    /// The WAL to be attached by `attach_ddl_wal()` after the returned statements in it are replayed.
+    ///
+    /// # Failure
+    ///
+    /// - Raises Err from `DdlWal::open()` as-is.
+    pub fn open_ddl_wal(config: &SpringConfig) -> Result<Option<(DdlWal, Vec<String>)>> {
+        DdlWal::open(&config.recovery)
+    }
+
+    /// Statements altering the pipeline are appended to `ddl_wal` from now on.
+    pub fn attach_ddl_wal(&mut self, ddl_wal: DdlWal) {
+        self.ddl_wal = Some(ddl_wal);
+    }
+
+    /// Statements which altered the pipeline so far, a line per statement.
+    pub fn show_history(&self) -> String {
+        self.history.show()
+    }
+
+    /// Unused streams, pumps, and columns in the current pipeline, a line per warning.
+    pub fn show_warnings(&self) -> String {
+        self.current_pipeline()
+            .warnings()
+            .iter()
+            .map(|warning| warning.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Streams, pumps, source readers, or sink writers in the current pipeline, a row per object.
+    pub fn show_objects(&self, command: ShowObjectsCommand) -> Vec<SchemalessRow> {
+        object_rows(self.current_pipeline(), command)
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - stream named `stream_name` does not exist in pipeline.
+    pub fn tap_stream(
+        &self,
+        stream_name: StreamName,
+        capacity: usize,
+        ttl: Duration,
+    ) -> Result<Arc<StreamTap>> {
+        let _ = self.current_pipeline().get_stream(&stream_name)?;
+        Ok(self
+            .autonomous_executor
+            .tap_stream(stream_name, capacity, ttl))
+    }
+
+    /// Registers a hook invoked before and after each task execution.
+    pub fn add_task_hook(&self, hook: Arc<dyn SpringTaskHook>) {
+        self.autonomous_executor.add_task_hook(hook)
+    }
+
+    /// See `AutonomousExecutor::run_until_idle()`.
+    pub fn run_until_idle(&self) {
+        self.autonomous_executor.run_until_idle()
+    }
+
+    pub fn source_reader_config(&self) -> &SpringSourceReaderConfig {
+        &self.source_reader_config
+    }
+
+    /// Samples rows from a source reader which is not a part of the pipeline (see `InferSchemaCommand`), and suggests a DDL for them.
+    ///
+    /// Sampling waits for the source, so this does not take the stream engine and callers call it without holding `EngineMutex`.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` / `SpringError::InvalidOption` when:
+    ///   - failed to start the source reader.
+    /// - `SpringError::ForeignSourceTimeout` when:
+    ///   - the source does not provide any row within timeout.
+    /// - `SpringError::InvalidFormat` when:
+    ///   - sampled rows cannot be typed.
+    pub fn infer_schema(
+        command: InferSchemaCommand,
+        source_reader_config: &SpringSourceReaderConfig,
+    ) -> Result<String> {
+        log::debug!("[StreamEngine] infer_schema({:?})", command);
+
+        let rows = SourceSampler::sample(
+            command.source_reader_type(),
+            command.options(),
+            source_reader_config,
+            command.n_samples(),
+        )?;
+
+        let mut inferrer = SchemaInferrer::new(command.stream_name().clone());
+        for row in rows {
+            inferrer.feed(row)?;
+        }
+        inferrer.suggest_ddl()
     }
 
     /// # Returns
@@ -98,6 +265,39 @@ impl StreamEngine {
         Ok(row)
     }
 
+    /// Columns of rows a sink writer writes into an in-memory queue, in the order of its `PROJECT` clause if any.
+    ///
+    /// `None` if no in-memory queue sink writer writes into `queue_name`.
+    pub fn in_memory_queue_columns(&self, queue_name: &QueueName) -> Option<Vec<ColumnDataType>> {
+        let pipeline = self.current_pipeline();
+
+        let sink_writer = pipeline.all_sinks().into_iter().find(|sink_writer| {
+            sink_writer.sink_writer_type() == &SinkWriterType::InMemoryQueue
+                && InMemoryQueueOptions::try_from(sink_writer.options())
+                    .map_or(false, |options| &options.queue_name == queue_name)
+        })?;
+        let stream = pipeline.get_stream(sink_writer.sink_upstream()).ok()?;
+        let columns = stream
+            .shape()
+            .columns()
+            .iter()
+            .map(ColumnDefinition::column_data_type);
+
+        let columns = match sink_writer.projection() {
+            Some(projection) => projection
+                .iter()
+                .filter_map(|column_name| {
+                    columns
+                        .clone()
+                        .find(|column| column.column_name() == column_name)
+                })
+                .cloned()
+                .collect(),
+            None => columns.cloned().collect(),
+        };
+        Some(columns)
+    }
+
     /// # Failure
     ///
     /// - `SpringError::Unavailable` when:
@@ -1,7 +1,9 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 mod args;
+mod chaos_injector;
 mod event_queue;
+mod fatal_error;
 mod main_job_lock;
 mod memory_state_machine;
 mod memory_state_machine_worker;
@@ -12,9 +14,14 @@ mod purger_worker;
 mod queue;
 mod repositories;
 mod row;
+mod row_error_log;
+mod snapshot;
+mod stream_tap;
+mod system_stream_repository;
 mod task;
 mod task_executor;
 mod task_graph;
+mod task_hook;
 mod worker;
 
 #[cfg(test)]
@@ -25,28 +32,35 @@ pub use row::{
     ColumnValues, JsonObject, NnSqlValue, RowTime, SchemalessRow, SourceRow, SqlCompareResult,
     SqlValue, SqlValueHashKey, StreamColumns, StreamRow,
 };
+pub use snapshot::ExecutorSnapshot;
+pub use stream_tap::StreamTap;
 pub use task::{
     NetClientSourceReader, NetServerSourceReader, SinkWriterRepository, SourceReader,
-    SourceReaderRepository, SourceTask, Task, TaskContext, Tuple, Window,
+    SourceReaderRepository, SourceSampler, SourceTask, Task, TaskContext, Tuple, Window,
 };
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
+
+use anyhow::anyhow;
+use parking_lot::Mutex;
 
 use crate::{
     api::{
-        error::{Result, SpringError},
-        SpringConfig,
+        error::{Result, SpringError, SpringErrorClass},
+        SpringConfig, SpringTaskHook,
     },
-    pipeline::Pipeline,
+    pipeline::{Pipeline, QueueName, StreamName},
     stream_engine::autonomous_executor::{
         args::{Coordinators, EventQueues, Locks},
         event_queue::{BlockingEventQueue, Event, NonBlockingEventQueue},
         main_job_lock::MainJobLock,
         memory_state_machine_worker::MemoryStateMachineWorker,
+        performance_metrics::MetricsUpdateByTaskExecutionOrPurge,
         performance_monitor_worker::PerformanceMonitorWorker,
         pipeline_derivatives::PipelineDerivatives,
         purger_worker::{PurgerWorker, PurgerWorkerThreadArg},
         repositories::Repositories,
+        task::ProcessedRows,
         task_executor::{TaskExecutor, TaskExecutorLock},
         worker::{WorkerSetupCoordinator, WorkerStopCoordinator},
     },
@@ -60,9 +74,13 @@ use crate::{
 #[derive(Debug)]
 pub struct AutonomousExecutor {
     b_event_queue: Arc<BlockingEventQueue>,
+    nb_event_queue: Arc<NonBlockingEventQueue>,
 
     main_job_lock: Arc<MainJobLock>,
     task_executor: TaskExecutor,
+    repos: Arc<Repositories>,
+    /// The latest one published, for snapshots.
+    pipeline_derivatives: Mutex<Option<Arc<PipelineDerivatives>>>,
 
     // just holds these ownership
     _memory_state_machine_worker: MemoryStateMachineWorker,
@@ -101,6 +119,7 @@ impl AutonomousExecutor {
         );
         let performance_monitor_worker = PerformanceMonitorWorker::new(
             config,
+            repos.clone(),
             locks.main_job_lock.clone(),
             event_queues.clone(),
             coordinators.clone(),
@@ -109,7 +128,19 @@ impl AutonomousExecutor {
             locks.main_job_lock.clone(),
             event_queues.clone(),
             coordinators.clone(),
-            PurgerWorkerThreadArg::new(repos, locks.task_executor_lock.clone()),
+            PurgerWorkerThreadArg::new(
+                repos.clone(),
+                locks.task_executor_lock.clone(),
+                config.memory.purge_report_queue.clone().map(QueueName::new),
+                config
+                    .namespaces
+                    .iter()
+                    .filter_map(|(namespace, namespace_config)| {
+                        let budget = namespace_config.memory_upper_limit_bytes?;
+                        Some((namespace.clone(), budget))
+                    })
+                    .collect(),
+            ),
         );
 
         coordinators
@@ -119,8 +150,11 @@ impl AutonomousExecutor {
 
         Self {
             b_event_queue: event_queues.blocking,
+            nb_event_queue: event_queues.non_blocking,
             main_job_lock: locks.main_job_lock,
             task_executor,
+            repos,
+            pipeline_derivatives: Mutex::new(None),
             _memory_state_machine_worker: memory_state_machine_worker,
             _performance_monitor_worker: performance_monitor_worker,
             _purger_worker: purger_worker,
@@ -137,6 +171,8 @@ impl AutonomousExecutor {
         task_executor.cleanup(&lock, pipeline_derivatives.task_graph());
         task_executor.update_pipeline(&lock, pipeline_derivatives.clone())?;
 
+        *self.pipeline_derivatives.lock() = Some(pipeline_derivatives.clone());
+
         let event = Event::UpdatePipeline {
             pipeline_derivatives,
         };
@@ -145,27 +181,184 @@ impl AutonomousExecutor {
         Ok(())
     }
 
+    /// Copies rows in queues and states of windows with aggregation, while main jobs in workers are blocked.
+    pub fn snapshot(&self) -> ExecutorSnapshot {
+        let _lock = self.main_job_lock.main_job_barrier();
+
+        let aggr_windows = self
+            .pipeline_derivatives
+            .lock()
+            .as_ref()
+            .map_or_else(Vec::new, |derivatives| {
+                derivatives.task_repo().snapshot_aggr_windows()
+            });
+        ExecutorSnapshot {
+            row_queues: self.repos.row_queue_repository().snapshot(),
+            window_queues: self.repos.window_queue_repository().snapshot(),
+            aggr_windows,
+        }
+    }
+
+    /// Puts rows and window states from a snapshot into the current pipeline, while main jobs in workers are blocked.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - queues, streams or pumps in the snapshot do not match the current pipeline.
+    pub fn restore(&self, snapshot: ExecutorSnapshot) -> Result<()> {
+        let _lock = self.main_job_lock.main_job_barrier();
+
+        let derivatives = match self.pipeline_derivatives.lock().clone() {
+            Some(derivatives) => derivatives,
+            None if snapshot == ExecutorSnapshot::default() => return Ok(()),
+            None => {
+                return Err(SpringError::Sql(anyhow!(
+                    "cannot restore a snapshot into an empty pipeline"
+                )))
+            }
+        };
+        let pipeline = derivatives.pipeline();
+
+        self.repos
+            .row_queue_repository()
+            .restore(snapshot.row_queues, pipeline)?;
+        self.repos
+            .window_queue_repository()
+            .restore(snapshot.window_queues, pipeline)?;
+        derivatives
+            .task_repo()
+            .restore_aggr_windows(snapshot.aggr_windows)
+    }
+
+    /// Executes tasks in the calling thread until no task processes a row, while main jobs in workers are blocked.
+    ///
+    /// Rows pushed into sources beforehand are processed through the pipeline regardless of worker scheduling,
+    /// so that tests get the same output for the same input. Windows are closed only by event time of the rows.
+    pub fn run_until_idle(&self) {
+        let _lock = self.main_job_lock.main_job_barrier();
+
+        let derivatives = match self.pipeline_derivatives.lock().clone() {
+            Some(derivatives) => derivatives,
+            None => return,
+        };
+        let task_graph = derivatives.task_graph();
+        let task_ids = task_graph
+            .tasks_in_topological_order()
+            .into_iter()
+            .filter(|task_id| !task_graph.is_stopped(task_id))
+            .collect::<Vec<_>>();
+
+        loop {
+            let processed_rows =
+                task_ids
+                    .iter()
+                    .fold(ProcessedRows::default(), |acc_processed_rows, task_id| {
+                        let task = derivatives
+                            .get_task(task_id)
+                            .expect("task id got from task graph");
+                        let context = TaskContext::new(
+                            task_id.clone(),
+                            derivatives.clone(),
+                            self.repos.clone(),
+                        );
+
+                        let processed_rows = task
+                            .run(&context)
+                            .map(|run_result| {
+                                self.nb_event_queue
+                                    .publish(Event::IncrementalUpdateMetrics {
+                                        metrics_update_by_task_execution_or_purge: Arc::new(
+                                            MetricsUpdateByTaskExecutionOrPurge::TaskExecution(
+                                                run_result.metrics,
+                                            ),
+                                        ),
+                                    });
+                                run_result.processed_rows
+                            })
+                            .unwrap_or_else(|e| {
+                                Self::handle_error(e, &self.repos);
+                                ProcessedRows::default()
+                            });
+                        acc_processed_rows + processed_rows
+                    });
+            if processed_rows.is_empty() {
+                break;
+            }
+        }
+    }
+
+    /// Starts copying rows traversing `stream` into a new tap.
+    pub fn tap_stream(&self, stream: StreamName, capacity: usize, ttl: Duration) -> Arc<StreamTap> {
+        let tap = Arc::new(StreamTap::new(stream, capacity, ttl));
+        self.repos.stream_tap_repository().register(tap.clone());
+        tap
+    }
+
+    /// Registers a hook invoked before and after each task execution.
+    pub fn add_task_hook(&self, hook: Arc<dyn SpringTaskHook>) {
+        self.repos.task_hook_repository().register(hook);
+    }
+
+    /// A fatal error got by workers since the last call, if any.
+    pub fn take_fatal_error(&self) -> Option<SpringError> {
+        self.repos.fatal_error_slot().take()
+    }
+
     /// Workers in autonomous executor may get SpringError but it must continue their work.
-    /// This method provides common way, like logging, to handle an error and then continue their work.
-    fn handle_error(e: SpringError) {
-        match e {
-            SpringError::ForeignSourceTimeout { .. } | SpringError::InputTimeout { .. } => {
+    /// This method provides common way, like logging, to handle an error according to its class and then continue their work.
+    ///
+    /// A fatal error is also kept in `repos` to be returned by the next API call (see `take_fatal_error()`).
+    fn handle_error(e: SpringError, repos: &Repositories) {
+        match e.class() {
+            // No input has arrived yet. Usual case for workers polling sources and queues.
+            SpringErrorClass::Recoverable
+                if matches!(
+                    e,
+                    SpringError::ForeignSourceTimeout { .. } | SpringError::InputTimeout { .. }
+                ) =>
+            {
                 log::trace!("{:?}", e)
             }
+            SpringErrorClass::Recoverable => log::warn!("[{}] {:?}", e.code(), e),
+            SpringErrorClass::Invalid => log::error!("[{}] {:?}", e.code(), e),
+            SpringErrorClass::Fatal => {
+                log::error!("[{}] (fatal; results may be unreliable) {:?}", e.code(), e);
+                repos.fatal_error_slot().set(e);
+            }
+        }
+    }
+}
 
-            // IO Error / Value Error
-            SpringError::ForeignIo { .. }
-            | SpringError::SpringQlCoreIo(_)
-            | SpringError::Unavailable { .. }
-            | SpringError::Time(_) => log::warn!("{:?}", e),
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-            SpringError::InvalidOption { .. }
-            | SpringError::InvalidFormat { .. }
-            | SpringError::Sql(_)
-            | SpringError::ThreadPoisoned(_) => log::error!("{:?}", e),
+    #[test]
+    fn test_handle_fatal_error() {
+        let executor = AutonomousExecutor::new(&SpringConfig::default());
 
-            SpringError::InvalidConfig { .. } => unreachable!("must be handled on startup"),
-            SpringError::Null { .. } => unreachable!("must be handled on startup"),
-        }
+        AutonomousExecutor::handle_error(
+            SpringError::InputTimeout {
+                task_name: "t".to_string(),
+                source: anyhow!("no input"),
+            },
+            &executor.repos,
+        );
+        assert!(executor.take_fatal_error().is_none());
+
+        AutonomousExecutor::handle_error(
+            SpringError::ThreadPoisoned(anyhow!("first")),
+            &executor.repos,
+        );
+        AutonomousExecutor::handle_error(
+            SpringError::SpringQlCoreIo(anyhow!("second")),
+            &executor.repos,
+        );
+        assert!(matches!(
+            executor.take_fatal_error(),
+            Some(SpringError::ThreadPoisoned(_))
+        ));
+        // reported only once
+        assert!(executor.take_fatal_error().is_none());
     }
 }
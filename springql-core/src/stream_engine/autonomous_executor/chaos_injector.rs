@@ -0,0 +1,98 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Fault injection by `[chaos]` config. Injects nothing without `chaos` feature.
+
+use std::{thread, time::Duration};
+
+use anyhow::anyhow;
+
+use crate::{
+    api::{
+        error::{Result, SpringError},
+        SpringChaosConfig,
+    },
+    pipeline::SourceReaderName,
+};
+
+const PPM: u32 = 1_000_000;
+
+#[derive(Debug, new)]
+pub struct ChaosInjector {
+    config: SpringChaosConfig,
+}
+
+impl ChaosInjector {
+    /// Sleeps before a task execution.
+    pub fn delay_task(&self) {
+        if self.hit(self.config.task_delay_ppm) {
+            log::debug!(
+                "[ChaosInjector] delaying a task by {} msec",
+                self.config.task_delay_msec
+            );
+            thread::sleep(Duration::from_millis(self.config.task_delay_msec as u64));
+        }
+    }
+
+    /// Whether to drop a row instead of putting it into a row queue.
+    pub fn drop_row(&self) -> bool {
+        let hit = self.hit(self.config.row_drop_ppm);
+        if hit {
+            log::debug!("[ChaosInjector] dropping a row");
+        }
+        hit
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::Unavailable` when:
+    ///   - a read from `source_reader` is chosen to fail.
+    pub fn fail_source_read(&self, source_reader: &SourceReaderName) -> Result<()> {
+        if self.hit(self.config.source_read_failure_ppm) {
+            Err(SpringError::Unavailable {
+                resource: format!("source reader {}", source_reader),
+                source: anyhow!("read failure injected by chaos mode"),
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Whether to kill a task worker thread.
+    pub fn kill_worker(&self) -> bool {
+        self.hit(self.config.worker_kill_ppm)
+    }
+
+    fn hit(&self, ppm: u32) -> bool {
+        cfg!(feature = "chaos") && ppm > 0 && fastrand::u32(0..PPM) < ppm
+    }
+}
+
+#[cfg(all(test, feature = "chaos"))]
+mod tests {
+    use super::*;
+
+    fn config(ppm: u32) -> SpringChaosConfig {
+        SpringChaosConfig {
+            task_delay_ppm: ppm,
+            task_delay_msec: 1,
+            row_drop_ppm: ppm,
+            source_read_failure_ppm: ppm,
+            worker_kill_ppm: ppm,
+        }
+    }
+
+    #[test]
+    fn test_chaos_injector() {
+        let source_reader = SourceReaderName::new("source_reader_1".to_string());
+
+        let never = ChaosInjector::new(config(0));
+        assert!(!never.drop_row());
+        assert!(never.fail_source_read(&source_reader).is_ok());
+        assert!(!never.kill_worker());
+
+        let always = ChaosInjector::new(config(PPM));
+        assert!(always.drop_row());
+        assert!(always.fail_source_read(&source_reader).is_err());
+        assert!(always.kill_worker());
+    }
+}
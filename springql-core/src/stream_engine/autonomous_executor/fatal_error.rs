@@ -0,0 +1,29 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Fatal errors got by workers, reported to the application by the next API call.
+
+use parking_lot::Mutex;
+
+use crate::api::error::SpringError;
+
+/// The first fatal error got by workers and not reported yet.
+///
+/// Results after a fatal error are not reliable, so the application should get it instead of only a log.
+/// The next `SpringPipeline` API call returns it (see `EngineMutex::get()`).
+#[derive(Debug, Default)]
+pub struct FatalErrorSlot(Mutex<Option<SpringError>>);
+
+impl FatalErrorSlot {
+    /// Keeps `e` unless another fatal error is not reported yet. Later ones are only logged by callers.
+    pub fn set(&self, e: SpringError) {
+        let mut slot = self.0.lock();
+        if slot.is_none() {
+            *slot = Some(e);
+        }
+    }
+
+    /// The fatal error not reported yet, which is then cleared.
+    pub fn take(&self) -> Option<SpringError> {
+        self.0.lock().take()
+    }
+}
@@ -95,6 +95,11 @@ impl MemoryStateTransition {
         }
     }
 
+    #[allow(clippy::wrong_self_convention)]
+    pub fn from_state(&self) -> MemoryState {
+        self.from_state
+    }
+
     #[allow(clippy::wrong_self_convention)]
     pub fn to_state(&self) -> MemoryState {
         self.to_state
@@ -8,6 +8,7 @@
 //!
 //! `TransitMemoryState` event is published on state transition and generic workers are supposed to
 //! change their scheduler on Moderate and Severe state.
+//! On Severe state, purger worker also pauses `BEST_EFFORT` pumps, and resumes them on Moderate state.
 //! On Critical state, generic workers are stopped and purger worker cleans all rows and windows.
 
 mod memory_state_machine_worker_thread;
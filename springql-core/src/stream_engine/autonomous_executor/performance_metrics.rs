@@ -8,24 +8,41 @@ mod task_metrics;
 
 pub use metrics_update_command::{
     InQueueMetricsUpdateByCollect, InQueueMetricsUpdateByTask, MetricsUpdateByTaskExecution,
-    MetricsUpdateByTaskExecutionOrPurge, OutQueueMetricsUpdateByTask, TaskMetricsUpdateByTask,
-    WindowInFlowByWindowTask,
+    MetricsUpdateByTaskExecutionOrPurge, OutQueueMetricsUpdateByTask, SinkHealth,
+    TaskMetricsUpdateByTask, WindowInFlowByWindowTask,
 };
 pub use performance_metrics_summary::PerformanceMetricsSummary;
-pub use queue_metrics::{RowQueueMetrics, WindowQueueMetrics};
+pub use queue_metrics::{RowQueueMetrics, WindowQueueMetrics, WindowTriggers};
 pub use task_metrics::TaskMetrics;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 use crate::{
     pipeline::PipelineVersion,
-    stream_engine::autonomous_executor::task_graph::{
-        QueueId, RowQueueId, TaskGraph, TaskId, WindowQueueId,
+    stream_engine::{
+        autonomous_executor::task_graph::{QueueId, RowQueueId, TaskGraph, TaskId, WindowQueueId},
+        time::WallClockDuration,
     },
 };
 
+/// Rows and bytes in queues of a namespace.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct NamespaceUsage {
+    pub rows: u64,
+    pub bytes: u64,
+}
+
+/// How schedulers treat tasks of a namespace or a pump over its quota.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum Throttle {
+    /// Scheduled after other tasks.
+    Deprioritize,
+    /// Not scheduled.
+    Pause,
+}
+
 /// Performance metrics of task execution. It has the same lifetime as a TaskGraph (i.e. a Pipeline).
 ///
 /// It is monitored by `PerformanceMonitorWorker`,
@@ -50,6 +67,12 @@ pub struct PerformanceMetrics {
     tasks: HashMap<TaskId, RwLock<TaskMetrics>>,
     row_queues: HashMap<RowQueueId, RwLock<RowQueueMetrics>>,
     window_queues: HashMap<WindowQueueId, RwLock<WindowQueueMetrics>>,
+
+    /// Queues carrying rows of streams in a namespace, to their namespaces.
+    queue_namespaces: HashMap<QueueId, String>,
+
+    /// Tasks of namespaces and pumps over their quotas. Set by `PerformanceMonitorWorker`.
+    throttles: RwLock<HashMap<TaskId, Throttle>>,
 }
 
 impl PerformanceMetrics {
@@ -58,6 +81,7 @@ impl PerformanceMetrics {
         task_ids: Vec<TaskId>,
         row_queue_ids: Vec<RowQueueId>,
         window_queue_ids: Vec<WindowQueueId>,
+        queue_namespaces: HashMap<QueueId, String>,
     ) -> Self {
         let tasks = task_ids
             .into_iter()
@@ -79,15 +103,32 @@ impl PerformanceMetrics {
             tasks,
             row_queues,
             window_queues,
+            queue_namespaces,
+            throttles: RwLock::default(),
         }
     }
 
     pub fn from_task_graph(graph: &TaskGraph) -> Self {
+        let row_queue_ids = graph.row_queues();
+        let window_queue_ids = graph.window_queues();
+
+        let queue_namespaces = row_queue_ids
+            .iter()
+            .cloned()
+            .map(QueueId::from)
+            .chain(window_queue_ids.iter().cloned().map(QueueId::from))
+            .filter_map(|queue_id| {
+                let namespace = graph.queue_namespace(&queue_id)?.to_string();
+                Some((queue_id, namespace))
+            })
+            .collect();
+
         Self::new(
             *graph.pipeline_version(),
             graph.tasks(),
-            graph.row_queues(),
-            graph.window_queues(),
+            row_queue_ids,
+            window_queue_ids,
+            queue_namespaces,
         )
     }
 
@@ -127,6 +168,63 @@ impl PerformanceMetrics {
         }
     }
 
+    /// Resets metrics of queues purged for their namespace over its memory budget.
+    /// Queues not in this metrics (purged in another version of pipeline) are ignored.
+    pub fn update_by_purge_queues(&self, queue_ids: &[QueueId]) {
+        for queue_id in queue_ids {
+            match queue_id {
+                QueueId::Row(id) => {
+                    if let Some(q) = self.row_queues.get(id) {
+                        q.write().update_by_purge()
+                    }
+                }
+                QueueId::Window(id) => {
+                    if let Some(q) = self.window_queues.get(id) {
+                        q.write().update_by_purge()
+                    }
+                }
+            }
+        }
+    }
+
+    /// Rows and bytes in queues of each namespace.
+    pub fn namespace_usages(&self) -> BTreeMap<String, NamespaceUsage> {
+        let mut usages = BTreeMap::<String, NamespaceUsage>::new();
+        for (queue_id, namespace) in &self.queue_namespaces {
+            let (rows, bytes) = match queue_id {
+                QueueId::Row(id) => {
+                    let q = self.get_row_queue_read(id);
+                    (q.rows(), q.bytes())
+                }
+                QueueId::Window(id) => {
+                    let q = self.get_window_queue_read(id);
+                    (q.rows_waiting(), q.bytes())
+                }
+            };
+            let usage = usages.entry(namespace.clone()).or_default();
+            usage.rows += rows;
+            usage.bytes += bytes;
+        }
+        usages
+    }
+
+    /// Bytes in a queue. Queues not in this metrics have 0 bytes.
+    pub fn queue_bytes(&self, queue_id: &QueueId) -> u64 {
+        match queue_id {
+            QueueId::Row(id) => self.row_queues.get(id).map_or(0, |q| q.read().bytes()),
+            QueueId::Window(id) => self.window_queues.get(id).map_or(0, |q| q.read().bytes()),
+        }
+    }
+
+    /// `None` if the task is not over quotas.
+    pub fn throttle(&self, task_id: &TaskId) -> Option<Throttle> {
+        self.throttles.read().get(task_id).copied()
+    }
+
+    pub fn set_throttles(&self, throttles: HashMap<TaskId, Throttle>) {
+        *self.throttles.write() = throttles;
+    }
+
     pub fn rows_for_task_input(&self, queue_id: &QueueId) -> u64 {
         match queue_id {
             QueueId::Row(id) => {
@@ -140,6 +238,33 @@ impl PerformanceMetrics {
         }
     }
 
+    /// Pane triggers of the window `queue_id` dispatches rows to (zero for row queues).
+    pub fn window_triggers_for_task_input(&self, queue_id: &QueueId) -> WindowTriggers {
+        match queue_id {
+            QueueId::Row(_) => WindowTriggers::default(),
+            QueueId::Window(id) => {
+                let q = self.get_window_queue_read(id);
+                q.triggers()
+            }
+        }
+    }
+
+    /// # Returns
+    ///
+    /// `None` if `task_id` is not a sink task or it has not written any row yet.
+    pub fn last_sink_latency(&self, task_id: &TaskId) -> Option<WallClockDuration> {
+        let t = self.get_task_read(task_id);
+        t.last_sink_latency()
+    }
+
+    /// # Returns
+    ///
+    /// `None` if `task_id` has not been executed yet.
+    pub fn avg_execution_secs(&self, task_id: &TaskId) -> Option<f64> {
+        let t = self.get_task_read(task_id);
+        t.avg_execution_secs()
+    }
+
     pub fn avg_gain_bytes_per_sec(&self, task_id: &TaskId) -> f32 {
         let t = self.get_task_read(task_id);
         t.avg_gain_bytes_per_sec()
@@ -4,6 +4,6 @@ mod metrics_update_by_task_execution;
 
 pub use metrics_update_by_task_execution::{
     InQueueMetricsUpdateByCollect, InQueueMetricsUpdateByTask, MetricsUpdateByTaskExecution,
-    MetricsUpdateByTaskExecutionOrPurge, OutQueueMetricsUpdateByTask, TaskMetricsUpdateByTask,
-    WindowInFlowByWindowTask,
+    MetricsUpdateByTaskExecutionOrPurge, OutQueueMetricsUpdateByTask, SinkHealth,
+    TaskMetricsUpdateByTask, WindowInFlowByWindowTask,
 };
@@ -1,16 +1,18 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use std::ops::Add;
+use std::{cmp::max, ops::Add};
 
 use crate::stream_engine::{
     autonomous_executor::task_graph::{QueueId, RowQueueId, TaskId, WindowQueueId},
-    time::{SpringDuration, WallClockDuration},
+    time::{SpringDuration, SpringEventDuration, WallClockDuration},
 };
 
 #[derive(Clone, Eq, PartialEq, Debug, new)]
 pub enum MetricsUpdateByTaskExecutionOrPurge {
     TaskExecution(MetricsUpdateByTaskExecution),
     Purge,
+    /// Purge of queues of a namespace over its memory budget.
+    PurgeQueues(Vec<QueueId>),
 }
 
 /// Metrics update per task execution.
@@ -58,11 +60,58 @@ impl MetricsUpdateByTaskExecution {
     pub fn window_queue_gain_bytes(&self, id: &WindowQueueId) -> i64 {
         self.window_queue_waiting_gain_bytes(id) + self.window_queue_window_gain_bytes(id)
     }
+    pub fn window_queue_in_flow(&self, id: &WindowQueueId) -> WindowInFlowByWindowTask {
+        self.in_queues
+            .iter()
+            .filter_map(|in_q| match &in_q.by_collect {
+                InQueueMetricsUpdateByCollect::Row { .. } => None,
+                InQueueMetricsUpdateByCollect::Window { queue_id, .. } => {
+                    (queue_id == id).then(|| in_q.window_in_flow)
+                }
+            })
+            .fold(WindowInFlowByWindowTask::zero(), |acc, window_in_flow| {
+                acc + window_in_flow
+            })
+    }
 
-    fn task_execution_time(&self) -> WallClockDuration {
+    pub fn sink_latency(&self) -> Option<WallClockDuration> {
+        self.task.sink_latency
+    }
+
+    pub fn sink_health(&self) -> Option<SinkHealth> {
+        self.task.sink_health
+    }
+
+    pub fn row_errors(&self) -> u64 {
+        self.task.row_errors
+    }
+
+    /// Batch size decided for the next batch of the task. See `worker.micro_batch` config.
+    pub fn batch_size(&self) -> Option<u64> {
+        self.task.batch_size
+    }
+
+    pub fn with_batch_size(self, batch_size: u64) -> Self {
+        Self {
+            task: TaskMetricsUpdateByTask {
+                batch_size: Some(batch_size),
+                ..self.task
+            },
+            ..self
+        }
+    }
+
+    pub fn task_execution_time(&self) -> WallClockDuration {
         self.task.execution_time
     }
 
+    /// Rows put into all the output queues.
+    pub fn rows_put(&self) -> u64 {
+        self.out_queues
+            .iter()
+            .fold(0, |acc, out_q| acc + out_q.rows_put)
+    }
+
     fn task_gain_bytes(&self) -> i64 {
         self.task_put_bytes() as i64
             - self.row_task_used_bytes() as i64
@@ -208,6 +257,52 @@ impl MetricsUpdateByTaskExecution {
 pub struct TaskMetricsUpdateByTask {
     task_id: TaskId,
     execution_time: WallClockDuration,
+
+    /// Latency of a row written by a sink task.
+    #[new(default)]
+    sink_latency: Option<WallClockDuration>,
+
+    /// Health of the foreign sink reported by the sink writer.
+    #[new(default)]
+    sink_health: Option<SinkHealth>,
+
+    /// Rows skipped by errors in processing them.
+    #[new(default)]
+    row_errors: u64,
+
+    /// Batch size decided after the batch this execution ends.
+    #[new(default)]
+    batch_size: Option<u64>,
+}
+
+impl TaskMetricsUpdateByTask {
+    pub fn with_sink_latency(self, sink_latency: WallClockDuration) -> Self {
+        Self {
+            sink_latency: Some(sink_latency),
+            ..self
+        }
+    }
+
+    pub fn with_sink_health(self, sink_health: SinkHealth) -> Self {
+        Self {
+            sink_health: Some(sink_health),
+            ..self
+        }
+    }
+
+    pub fn with_row_errors(self, row_errors: u64) -> Self {
+        Self { row_errors, ..self }
+    }
+}
+
+/// Health of a foreign sink observed by a sink writer (counts are totals since the sink writer started).
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct SinkHealth {
+    /// Retried requests to the foreign sink.
+    pub retries: u64,
+    /// Rows dropped without requests while the circuit breaker is open.
+    pub dropped_rows: u64,
+    pub circuit_open: bool,
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
@@ -261,12 +356,42 @@ impl InQueueMetricsUpdateByCollect {
 pub struct WindowInFlowByWindowTask {
     pub window_gain_bytes_states: i64,
     pub window_gain_bytes_rows: i64,
+
+    /// Rows older than the watermark but dispatched within allowed lateness.
+    #[new(default)]
+    pub late_rows: u64,
+    /// Rows older than the watermark and allowed lateness, which are dropped.
+    #[new(default)]
+    pub dropped_late_rows: u64,
+    /// Maximum of `max(ROWTIME) - ROWTIME` among dispatched rows.
+    #[new(default)]
+    pub max_disorder: Option<SpringEventDuration>,
+
+    /// Panes generated.
+    #[new(default)]
+    pub panes_opened: u64,
+    /// Panes closed (fired) for the first time by the watermark.
+    #[new(default)]
+    pub panes_closed: u64,
+    /// Panes closed again because late rows are merged into them within allowed lateness.
+    #[new(default)]
+    pub late_merges: u64,
+    /// Rows output by closed panes.
+    #[new(default)]
+    pub emitted_rows: u64,
 }
 impl WindowInFlowByWindowTask {
     pub fn zero() -> Self {
         Self {
             window_gain_bytes_states: 0,
             window_gain_bytes_rows: 0,
+            late_rows: 0,
+            dropped_late_rows: 0,
+            max_disorder: None,
+            panes_opened: 0,
+            panes_closed: 0,
+            late_merges: 0,
+            emitted_rows: 0,
         }
     }
 }
@@ -278,6 +403,13 @@ impl Add for WindowInFlowByWindowTask {
         Self {
             window_gain_bytes_states: self.window_gain_bytes_states + rhs.window_gain_bytes_states,
             window_gain_bytes_rows: self.window_gain_bytes_rows + rhs.window_gain_bytes_rows,
+            late_rows: self.late_rows + rhs.late_rows,
+            dropped_late_rows: self.dropped_late_rows + rhs.dropped_late_rows,
+            max_disorder: max(self.max_disorder, rhs.max_disorder),
+            panes_opened: self.panes_opened + rhs.panes_opened,
+            panes_closed: self.panes_closed + rhs.panes_closed,
+            late_merges: self.late_merges + rhs.late_merges,
+            emitted_rows: self.emitted_rows + rhs.emitted_rows,
         }
     }
 }
@@ -1,8 +1,10 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use std::fmt::Display;
+use std::{collections::BTreeMap, fmt::Display};
 
-use crate::stream_engine::autonomous_executor::performance_metrics::PerformanceMetrics;
+use crate::stream_engine::autonomous_executor::performance_metrics::{
+    NamespaceUsage, PerformanceMetrics,
+};
 
 /// Summary of `PerformanceMetrics`.
 ///
@@ -10,15 +12,21 @@ use crate::stream_engine::autonomous_executor::performance_metrics::PerformanceM
 /// - transits memory state diagram
 /// - changes task scheduler
 /// - launches purger
+///
+/// `PurgerWorker` also purges queues of namespaces over their memory budgets.
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub struct PerformanceMetricsSummary {
     pub queue_total_bytes: u64,
+    pub namespace_usages: BTreeMap<String, NamespaceUsage>,
 }
 
 impl From<&PerformanceMetrics> for PerformanceMetricsSummary {
     fn from(pm: &PerformanceMetrics) -> Self {
         let queue_total_bytes = Self::queue_total_bytes(pm);
-        Self { queue_total_bytes }
+        Self {
+            queue_total_bytes,
+            namespace_usages: pm.namespace_usages(),
+        }
     }
 }
 
@@ -4,4 +4,4 @@ mod row_queue_metrics;
 mod window_queue_metrics;
 
 pub use row_queue_metrics::RowQueueMetrics;
-pub use window_queue_metrics::WindowQueueMetrics;
+pub use window_queue_metrics::{WindowQueueMetrics, WindowTriggers};
@@ -1,19 +1,32 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use crate::stream_engine::autonomous_executor::{
-    performance_metrics::{
-        calculation::floor0, metrics_update_command::MetricsUpdateByTaskExecution,
+use std::{cmp::max, ops::Add};
+
+use crate::stream_engine::{
+    autonomous_executor::{
+        performance_metrics::{
+            calculation::floor0, metrics_update_command::MetricsUpdateByTaskExecution,
+        },
+        task_graph::WindowQueueId,
     },
-    task_graph::WindowQueueId,
+    time::SpringEventDuration,
 };
 
 /// Stock monitor of a window queue.
+///
+/// Also counts out-of-order rows dispatched from the queue to a window and pane triggers caused by them (not reset by purge).
 #[derive(Clone, Eq, PartialEq, Debug, Default)]
 pub struct WindowQueueMetrics {
     /// might be negative value if `dispatched` event is subscribed earlier than `put` event.
     rows_waiting: i64,
     /// ditto
     bytes: i64,
+
+    late_rows: u64,
+    dropped_late_rows: u64,
+    max_disorder: Option<SpringEventDuration>,
+
+    triggers: WindowTriggers,
 }
 
 impl WindowQueueMetrics {
@@ -24,6 +37,19 @@ impl WindowQueueMetrics {
     ) {
         self.rows_waiting += command.window_queue_waiting_gain_rows(id);
         self.bytes += command.window_queue_gain_bytes(id);
+
+        let window_in_flow = command.window_queue_in_flow(id);
+        self.late_rows += window_in_flow.late_rows;
+        self.dropped_late_rows += window_in_flow.dropped_late_rows;
+        self.max_disorder = max(self.max_disorder, window_in_flow.max_disorder);
+
+        self.triggers = self.triggers
+            + WindowTriggers {
+                panes_opened: window_in_flow.panes_opened,
+                panes_closed: window_in_flow.panes_closed,
+                late_merges: window_in_flow.late_merges,
+                emitted_rows: window_in_flow.emitted_rows,
+            };
     }
 
     pub fn update_by_purge(&mut self) {
@@ -40,4 +66,50 @@ impl WindowQueueMetrics {
     pub fn bytes(&self) -> u64 {
         floor0(self.bytes)
     }
+
+    /// Total number of rows dispatched to closed panes within allowed lateness.
+    pub fn late_rows(&self) -> u64 {
+        self.late_rows
+    }
+
+    /// Total number of rows dropped because they are later than allowed lateness.
+    pub fn dropped_late_rows(&self) -> u64 {
+        self.dropped_late_rows
+    }
+
+    /// Maximum delay of a row's ROWTIME from the latest ROWTIME observed.
+    pub fn max_disorder(&self) -> Option<SpringEventDuration> {
+        self.max_disorder
+    }
+
+    /// Total pane triggers of the window the queue dispatches rows to.
+    pub fn triggers(&self) -> WindowTriggers {
+        self.triggers
+    }
+}
+
+/// Total counts of pane triggers in a window.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct WindowTriggers {
+    /// Panes generated.
+    pub panes_opened: u64,
+    /// Panes closed for the first time by the watermark.
+    pub panes_closed: u64,
+    /// Times closed panes are closed again with late rows within allowed lateness.
+    pub late_merges: u64,
+    /// Rows output by closed panes.
+    pub emitted_rows: u64,
+}
+
+impl Add for WindowTriggers {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            panes_opened: self.panes_opened + rhs.panes_opened,
+            panes_closed: self.panes_closed + rhs.panes_closed,
+            late_merges: self.late_merges + rhs.late_merges,
+            emitted_rows: self.emitted_rows + rhs.emitted_rows,
+        }
+    }
 }
@@ -1,7 +1,11 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use crate::stream_engine::autonomous_executor::performance_metrics::{
-    calculation::next_avg, metrics_update_command::MetricsUpdateByTaskExecution,
+use crate::stream_engine::{
+    autonomous_executor::performance_metrics::{
+        calculation::next_avg,
+        metrics_update_command::{MetricsUpdateByTaskExecution, SinkHealth},
+    },
+    time::{SpringDuration, WallClockDuration},
 };
 
 /// Flow monitor of a task (including in-memory queue sink) execution.
@@ -9,6 +13,17 @@ use crate::stream_engine::autonomous_executor::performance_metrics::{
 pub struct TaskMetrics {
     avg_gain_bytes_per_sec: f32,
     n_executions: u64,
+    /// Total time spent in executions, which quotas limit as CPU time.
+    execution_secs: f64,
+
+    /// Latency of the last row written by a sink task.
+    last_sink_latency: Option<WallClockDuration>,
+    /// Health of the foreign sink last reported by a sink task.
+    last_sink_health: Option<SinkHealth>,
+    /// Rows skipped by errors in processing them so far.
+    row_errors: u64,
+    /// Batch size last decided for the task in micro-batch mode.
+    last_batch_size: Option<u64>,
 }
 
 impl TaskMetrics {
@@ -16,14 +31,55 @@ impl TaskMetrics {
         let n = self.n_executions;
 
         self.n_executions += 1;
+        self.execution_secs += command.task_execution_time().as_secs_f64();
         self.avg_gain_bytes_per_sec = next_avg(
             self.avg_gain_bytes_per_sec,
             n,
             command.task_gain_bytes_per_sec(),
         );
+        if let Some(sink_latency) = command.sink_latency() {
+            self.last_sink_latency = Some(sink_latency);
+        }
+        if let Some(sink_health) = command.sink_health() {
+            self.last_sink_health = Some(sink_health);
+        }
+        self.row_errors += command.row_errors();
+        if let Some(batch_size) = command.batch_size() {
+            self.last_batch_size = Some(batch_size);
+        }
     }
 
     pub fn avg_gain_bytes_per_sec(&self) -> f32 {
         self.avg_gain_bytes_per_sec
     }
+
+    pub fn execution_secs(&self) -> f64 {
+        self.execution_secs
+    }
+
+    /// `None` if the task has not been executed yet.
+    pub fn avg_execution_secs(&self) -> Option<f64> {
+        if self.n_executions == 0 {
+            None
+        } else {
+            Some(self.execution_secs / self.n_executions as f64)
+        }
+    }
+
+    pub fn last_sink_latency(&self) -> Option<WallClockDuration> {
+        self.last_sink_latency
+    }
+
+    pub fn last_sink_health(&self) -> Option<SinkHealth> {
+        self.last_sink_health
+    }
+
+    pub fn row_errors(&self) -> u64 {
+        self.row_errors
+    }
+
+    /// Executions of the task per scheduling. 1 unless micro-batch mode tunes it.
+    pub fn batch_size(&self) -> u64 {
+        self.last_batch_size.unwrap_or(1)
+    }
 }
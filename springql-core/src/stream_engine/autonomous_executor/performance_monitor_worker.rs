@@ -1,6 +1,7 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 mod performance_monitor_worker_thread;
+mod quota_enforcer;
 
 mod web_console_reporter;
 
@@ -14,6 +15,7 @@ use crate::{
         args::{Coordinators, EventQueues},
         main_job_lock::MainJobLock,
         performance_monitor_worker::performance_monitor_worker_thread::PerformanceMonitorWorkerThreadArg,
+        repositories::Repositories,
         worker::WorkerHandle,
     },
 };
@@ -30,6 +32,7 @@ pub struct PerformanceMonitorWorker {
 impl PerformanceMonitorWorker {
     pub fn new(
         config: &SpringConfig,
+        repos: Arc<Repositories>,
         main_job_lock: Arc<MainJobLock>,
         event_queues: EventQueues,
         coordinators: Coordinators,
@@ -38,7 +41,7 @@ impl PerformanceMonitorWorker {
             main_job_lock,
             event_queues,
             coordinators,
-            PerformanceMonitorWorkerThreadArg::from(config),
+            PerformanceMonitorWorkerThreadArg::new(config, repos),
         );
         Self { _handle: handle }
     }
@@ -1,9 +1,15 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use std::{sync::Arc, thread, time::Duration};
+use std::{
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
 
 use crate::{
+    alloc_instrumentation::AllocDrift,
     api::SpringConfig,
+    pipeline::SystemStream,
     stream_engine::{
         autonomous_executor::{
             event_queue::{
@@ -12,9 +18,13 @@ use crate::{
             memory_state_machine::MemoryStateTransition,
             performance_metrics::{
                 MetricsUpdateByTaskExecutionOrPurge, PerformanceMetrics, PerformanceMetricsSummary,
+                WindowTriggers,
+            },
+            performance_monitor_worker::{
+                quota_enforcer::QuotaEnforcer, web_console_reporter::WebConsoleReporter,
             },
-            performance_monitor_worker::web_console_reporter::WebConsoleReporter,
             pipeline_derivatives::PipelineDerivatives,
+            repositories::Repositories,
             worker::{WorkerSetupCoordinator, WorkerThread, WorkerThreadLoopState},
         },
         time::{SpringDuration, WallClockDuration},
@@ -23,6 +33,9 @@ use crate::{
 
 const CLOCK_MSEC: u64 = 10;
 
+/// Interval to measure usages of namespaces and pumps and throttle ones over their quotas.
+const QUOTA_ENFORCEMENT_INTERVAL_MSEC: i32 = 1_000;
+
 /// Runs a worker thread.
 #[derive(Debug)]
 pub struct PerformanceMonitorWorkerThread;
@@ -30,11 +43,12 @@ pub struct PerformanceMonitorWorkerThread;
 #[derive(Debug)]
 pub struct PerformanceMonitorWorkerThreadArg {
     config: SpringConfig,
+    repos: Arc<Repositories>,
     web_console_reporter: Option<WebConsoleReporter>,
 }
 
-impl From<&SpringConfig> for PerformanceMonitorWorkerThreadArg {
-    fn from(config: &SpringConfig) -> Self {
+impl PerformanceMonitorWorkerThreadArg {
+    pub fn new(config: &SpringConfig, repos: Arc<Repositories>) -> Self {
         let web_console_reporter = if config.web_console.enable_report_post {
             Some(WebConsoleReporter::new(
                 &config.web_console.host,
@@ -46,6 +60,7 @@ impl From<&SpringConfig> for PerformanceMonitorWorkerThreadArg {
         };
         Self {
             config: config.clone(),
+            repos,
             web_console_reporter,
         }
     }
@@ -57,6 +72,11 @@ pub struct PerformanceMonitorWorkerLoopState {
     metrics: Option<Arc<PerformanceMetrics>>,
     countdown_metrics_summary_msec: i32,
     countdown_web_console_msec: i32,
+    countdown_task_metrics_msec: i32,
+    countdown_quota_enforcement_msec: i32,
+
+    quota_enforcer: QuotaEnforcer,
+    last_quota_enforcement: Instant,
 }
 
 impl WorkerThreadLoopState for PerformanceMonitorWorkerLoopState {
@@ -75,6 +95,11 @@ impl WorkerThreadLoopState for PerformanceMonitorWorkerLoopState {
                 .performance_metrics_summary_report_interval_msec
                 as i32,
             countdown_web_console_msec: thread_arg.config.web_console.report_interval_msec as i32,
+            countdown_task_metrics_msec: thread_arg.config.system_stream.task_metrics_interval_msec
+                as i32,
+            countdown_quota_enforcement_msec: QUOTA_ENFORCEMENT_INTERVAL_MSEC,
+            quota_enforcer: QuotaEnforcer::new(&thread_arg.config),
+            last_quota_enforcement: Instant::now(),
         }
     }
 
@@ -115,14 +140,22 @@ impl WorkerThread for PerformanceMonitorWorkerThread {
         ) {
             let mut state = current_state;
 
-            state = Self::report_metrics_summary(
+            state = Self::report_metrics_summary(state, metrics.as_ref(), event_queue, thread_arg);
+
+            if state.quota_enforcer.is_enabled() {
+                state = Self::enforce_quotas(
+                    state,
+                    pipeline_derivatives.as_ref(),
+                    metrics.as_ref(),
+                    thread_arg,
+                );
+            }
+
+            state = Self::put_task_metrics(
                 state,
+                pipeline_derivatives.as_ref(),
                 metrics.as_ref(),
-                event_queue,
-                thread_arg
-                    .config
-                    .memory
-                    .performance_metrics_summary_report_interval_msec as i32,
+                thread_arg,
             );
 
             if let Some(web_console_reporter) = &thread_arg.web_console_reporter {
@@ -156,6 +189,7 @@ impl WorkerThread for PerformanceMonitorWorkerThread {
         ));
         state.metrics = Some(metrics.clone());
         event_queue.publish(Event::ReplacePerformanceMetrics { metrics });
+        state.quota_enforcer.reset();
 
         state.pipeline_derivatives = Some(pipeline_derivatives);
 
@@ -184,6 +218,9 @@ impl WorkerThread for PerformanceMonitorWorkerThread {
                     m.update_by_task_execution(metrics_diff)
                 }
                 MetricsUpdateByTaskExecutionOrPurge::Purge => m.update_by_purge(),
+                MetricsUpdateByTaskExecutionOrPurge::PurgeQueues(queue_ids) => {
+                    m.update_by_purge_queues(queue_ids)
+                }
             }
         }
         state
@@ -213,18 +250,23 @@ impl PerformanceMonitorWorkerThread {
         state: PerformanceMonitorWorkerLoopState,
         metrics: &PerformanceMetrics,
         event_queue: &NonBlockingEventQueue,
-        report_interval_msec: i32,
+        thread_arg: &PerformanceMonitorWorkerThreadArg,
     ) -> PerformanceMonitorWorkerLoopState {
         let mut state = state;
 
         if state.countdown_metrics_summary_msec <= 0 {
-            state.countdown_metrics_summary_msec = report_interval_msec;
+            state.countdown_metrics_summary_msec = thread_arg
+                .config
+                .memory
+                .performance_metrics_summary_report_interval_msec
+                as i32;
 
             let metrics_summary = Arc::new(PerformanceMetricsSummary::from(metrics));
             log::trace!(
                 "PerformanceMonitorWorkerThread::report_metrics_summary: metrics_summary={:?}",
                 metrics_summary
             );
+            Self::reconcile_alloc(metrics_summary.queue_total_bytes, thread_arg);
             event_queue.publish(Event::ReportMetricsSummary { metrics_summary })
         } else {
             state.countdown_metrics_summary_msec -= CLOCK_MSEC as i32;
@@ -233,6 +275,118 @@ impl PerformanceMonitorWorkerThread {
         state
     }
 
+    /// Compares bytes accounted in queues and windows with live bytes tracked by `InstrumentedAllocator`.
+    /// Does nothing unless `alloc-instrumentation` feature is enabled and the allocator is installed.
+    fn reconcile_alloc(accounted_bytes: u64, thread_arg: &PerformanceMonitorWorkerThreadArg) {
+        if let Some(drift) = AllocDrift::measure(accounted_bytes) {
+            log::debug!(
+                "[PerformanceMonitorWorker] allocation drift: {:.1}% ({:?})",
+                drift.drift_percent(),
+                drift
+            );
+
+            if drift.should_warn() {
+                log::warn!(
+                    "[PerformanceMonitorWorker] memory accounting drifts {:.1}% from allocations: accounted {} bytes, allocated {} bytes in queues and {} bytes in windows",
+                    drift.drift_percent(),
+                    drift.accounted_bytes,
+                    drift.tracked_queue_bytes,
+                    drift.tracked_window_bytes,
+                );
+                thread_arg.repos.system_stream_repository().put_event(
+                    "ALLOC_DRIFT",
+                    format!(
+                        "{:.1}% (accounted: {} bytes, allocated: {} bytes)",
+                        drift.drift_percent(),
+                        drift.accounted_bytes,
+                        drift.tracked_bytes()
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Throttles tasks of namespaces and pumps over their quotas, and puts `QUOTA_EXCEEDED` / `QUOTA_RECOVERED` events into `spring._events`.
+    fn enforce_quotas(
+        state: PerformanceMonitorWorkerLoopState,
+        pipeline_derivatives: &PipelineDerivatives,
+        metrics: &PerformanceMetrics,
+        thread_arg: &PerformanceMonitorWorkerThreadArg,
+    ) -> PerformanceMonitorWorkerLoopState {
+        let mut state = state;
+
+        if state.countdown_quota_enforcement_msec <= 0 {
+            state.countdown_quota_enforcement_msec = QUOTA_ENFORCEMENT_INTERVAL_MSEC;
+
+            let elapsed = state.last_quota_enforcement.elapsed();
+            state.last_quota_enforcement = Instant::now();
+
+            let events =
+                state
+                    .quota_enforcer
+                    .enforce(pipeline_derivatives.task_graph(), metrics, elapsed);
+            for (kind, detail) in events {
+                log::warn!("[PerformanceMonitorWorker] {}: {}", kind, detail);
+                thread_arg
+                    .repos
+                    .system_stream_repository()
+                    .put_event(kind, detail);
+            }
+        } else {
+            state.countdown_quota_enforcement_msec -= CLOCK_MSEC as i32;
+        }
+
+        state
+    }
+
+    /// Puts a row per task into `spring._task_metrics` system stream.
+    fn put_task_metrics(
+        state: PerformanceMonitorWorkerLoopState,
+        pipeline_derivatives: &PipelineDerivatives,
+        metrics: &PerformanceMetrics,
+        thread_arg: &PerformanceMonitorWorkerThreadArg,
+    ) -> PerformanceMonitorWorkerLoopState {
+        let mut state = state;
+
+        if state.countdown_task_metrics_msec <= 0 {
+            state.countdown_task_metrics_msec =
+                thread_arg.config.system_stream.task_metrics_interval_msec as i32;
+
+            let system_stream_repo = thread_arg.repos.system_stream_repository();
+            if system_stream_repo.is_open(SystemStream::TaskMetrics) {
+                let task_graph = pipeline_derivatives.task_graph();
+                for (task_id, task_metrics) in metrics.get_tasks() {
+                    let num_input_rows = task_graph
+                        .input_queues(task_id)
+                        .iter()
+                        .map(|queue_id| metrics.rows_for_task_input(queue_id))
+                        .sum();
+                    let window_triggers = task_graph
+                        .input_queues(task_id)
+                        .iter()
+                        .map(|queue_id| metrics.window_triggers_for_task_input(queue_id))
+                        .fold(WindowTriggers::default(), |acc, triggers| acc + triggers);
+                    let tags = task_graph
+                        .task_tags(task_id)
+                        .map_or_else(String::new, |tags| tags.to_string());
+                    system_stream_repo.put_task_metrics(
+                        task_id.to_string(),
+                        num_input_rows,
+                        task_metrics.avg_gain_bytes_per_sec(),
+                        window_triggers,
+                        task_metrics.row_errors(),
+                        task_metrics.batch_size(),
+                        tags,
+                    );
+                }
+            }
+        } else {
+            state.countdown_task_metrics_msec -= CLOCK_MSEC as i32;
+        }
+
+        state
+    }
+
     fn post_web_console(
         state: PerformanceMonitorWorkerLoopState,
         pipeline_derivatives: &PipelineDerivatives,
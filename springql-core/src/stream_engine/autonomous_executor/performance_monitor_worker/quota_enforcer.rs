@@ -0,0 +1,301 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    time::Duration,
+};
+
+use crate::{
+    api::SpringConfig,
+    stream_engine::autonomous_executor::{
+        performance_metrics::{PerformanceMetrics, Throttle},
+        task_graph::{TaskGraph, TaskId},
+    },
+};
+
+/// Namespace or pump with quotas.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+enum QuotaOwner {
+    Namespace(String),
+    Pump(String),
+}
+
+impl Display for QuotaOwner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QuotaOwner::Namespace(namespace) => write!(f, "namespace {}", namespace),
+            QuotaOwner::Pump(pump) => write!(f, "pump {}", pump),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+struct Quota {
+    /// CPU time per wall-clock time.
+    cpu_time_ratio: Option<f64>,
+    /// Bytes in output queues of a pump. Namespaces' memory budgets are enforced by `PurgerWorker`.
+    memory_bytes: Option<u64>,
+    throttle: Throttle,
+}
+
+impl Quota {
+    fn new(
+        cpu_time_upper_limit_percent: Option<u32>,
+        memory_bytes: Option<u64>,
+        on_quota_exceeded: Option<&str>,
+    ) -> Option<Self> {
+        let cpu_time_ratio = cpu_time_upper_limit_percent.map(|percent| percent as f64 / 100.0);
+        let throttle = match on_quota_exceeded {
+            Some("pause") => Throttle::Pause,
+            _ => Throttle::Deprioritize,
+        };
+        (cpu_time_ratio.is_some() || memory_bytes.is_some()).then(|| Self {
+            cpu_time_ratio,
+            memory_bytes,
+            throttle,
+        })
+    }
+}
+
+/// Usage of a quota owner in an enforcement period.
+#[derive(Clone, PartialEq, Debug, Default)]
+struct Usage {
+    cpu_secs: f64,
+    memory_bytes: u64,
+}
+
+/// Throttles tasks of namespaces and pumps over their CPU time and memory quotas (`[namespaces.<namespace>]` and `[pumps.<pump>]` configs).
+///
+/// CPU time is measured as execution time of tasks. CPU time over a quota is carried over as excess,
+/// and the owner is throttled until idle periods pay back the excess.
+#[derive(Debug)]
+pub struct QuotaEnforcer {
+    quotas: BTreeMap<QuotaOwner, Quota>,
+
+    /// Execution time of each task at the last enforcement.
+    last_execution_secs: HashMap<TaskId, f64>,
+    /// CPU time used over quotas.
+    excess_cpu_secs: BTreeMap<QuotaOwner, f64>,
+    /// Owners throttled at the last enforcement.
+    offenders: BTreeMap<QuotaOwner, String>,
+}
+
+impl QuotaEnforcer {
+    pub fn new(config: &SpringConfig) -> Self {
+        let namespace_quotas =
+            config
+                .namespaces
+                .iter()
+                .filter_map(|(namespace, namespace_config)| {
+                    let quota = Quota::new(
+                        namespace_config.cpu_time_upper_limit_percent,
+                        None,
+                        namespace_config.on_quota_exceeded.as_deref(),
+                    )?;
+                    Some((QuotaOwner::Namespace(namespace.clone()), quota))
+                });
+        let pump_quotas = config.pumps.iter().filter_map(|(pump, pump_config)| {
+            let quota = Quota::new(
+                pump_config.cpu_time_upper_limit_percent,
+                pump_config.memory_upper_limit_bytes,
+                pump_config.on_quota_exceeded.as_deref(),
+            )?;
+            Some((QuotaOwner::Pump(pump.clone()), quota))
+        });
+
+        Self {
+            quotas: namespace_quotas.chain(pump_quotas).collect(),
+            last_execution_secs: HashMap::new(),
+            excess_cpu_secs: BTreeMap::new(),
+            offenders: BTreeMap::new(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.quotas.is_empty()
+    }
+
+    /// Called when metrics are replaced for a new pipeline.
+    pub fn reset(&mut self) {
+        self.last_execution_secs.clear();
+    }
+
+    /// Measures usages in the last `elapsed` and sets throttles of offenders' tasks to `metrics`.
+    ///
+    /// # Returns
+    ///
+    /// System events (kind, detail) for owners newly over or back under their quotas.
+    pub fn enforce(
+        &mut self,
+        graph: &TaskGraph,
+        metrics: &PerformanceMetrics,
+        elapsed: Duration,
+    ) -> Vec<(&'static str, String)> {
+        let usages = self.usages(graph, metrics);
+
+        let mut offenders = BTreeMap::new();
+        for (owner, quota) in &self.quotas {
+            let usage = usages.get(owner).cloned().unwrap_or_default();
+
+            let excess_cpu_secs = self.excess_cpu_secs.entry(owner.clone()).or_default();
+            if let Some(ratio) = quota.cpu_time_ratio {
+                let allowed_secs = elapsed.as_secs_f64() * ratio;
+                *excess_cpu_secs = (*excess_cpu_secs + usage.cpu_secs - allowed_secs).max(0.0);
+            }
+
+            let reason = if *excess_cpu_secs > 0.0 {
+                Some(format!(
+                    "CPU time {:.0} ms over quota",
+                    *excess_cpu_secs * 1000.0
+                ))
+            } else {
+                quota
+                    .memory_bytes
+                    .filter(|&limit| usage.memory_bytes > limit)
+                    .map(|limit| format!("{} bytes > {} bytes", usage.memory_bytes, limit))
+            };
+            if let Some(reason) = reason {
+                let _ = offenders.insert(owner.clone(), reason);
+            }
+        }
+
+        metrics.set_throttles(self.throttles(graph, &offenders));
+
+        let exceeded = offenders
+            .iter()
+            .filter(|(owner, _)| !self.offenders.contains_key(owner))
+            .map(|(owner, reason)| ("QUOTA_EXCEEDED", format!("{}: {}", owner, reason)));
+        let recovered = self
+            .offenders
+            .keys()
+            .filter(|owner| !offenders.contains_key(owner))
+            .map(|owner| ("QUOTA_RECOVERED", owner.to_string()));
+        let events = exceeded.chain(recovered).collect();
+
+        self.offenders = offenders;
+        events
+    }
+
+    fn usages(
+        &mut self,
+        graph: &TaskGraph,
+        metrics: &PerformanceMetrics,
+    ) -> HashMap<QuotaOwner, Usage> {
+        let mut usages = HashMap::<QuotaOwner, Usage>::new();
+        for (task_id, task_metrics) in metrics.get_tasks() {
+            let execution_secs = task_metrics.execution_secs();
+            let last_execution_secs = self
+                .last_execution_secs
+                .insert(task_id.clone(), execution_secs)
+                .unwrap_or_default();
+            let cpu_secs = (execution_secs - last_execution_secs).max(0.0);
+
+            for owner in Self::owners(graph, task_id) {
+                let usage = usages.entry(owner).or_default();
+                usage.cpu_secs += cpu_secs;
+                if matches!(task_id, TaskId::Pump { .. }) {
+                    usage.memory_bytes += graph
+                        .output_queues(task_id)
+                        .iter()
+                        .map(|queue_id| metrics.queue_bytes(queue_id))
+                        .sum::<u64>();
+                }
+            }
+        }
+        usages
+    }
+
+    /// Throttles of tasks owned by `offenders`. The stricter one applies to a task of an offending namespace and an offending pump.
+    fn throttles(
+        &self,
+        graph: &TaskGraph,
+        offenders: &BTreeMap<QuotaOwner, String>,
+    ) -> HashMap<TaskId, Throttle> {
+        graph
+            .tasks()
+            .into_iter()
+            .filter_map(|task_id| {
+                let throttle = Self::owners(graph, &task_id)
+                    .into_iter()
+                    .filter(|owner| offenders.contains_key(owner))
+                    .map(|owner| self.quotas[&owner].throttle)
+                    .max()?;
+                Some((task_id, throttle))
+            })
+            .collect()
+    }
+
+    fn owners(graph: &TaskGraph, task_id: &TaskId) -> Vec<QuotaOwner> {
+        let namespace = graph
+            .task_namespace(task_id)
+            .map(|namespace| QuotaOwner::Namespace(namespace.to_string()));
+        let pump = match task_id {
+            TaskId::Pump { id, .. } => Some(QuotaOwner::Pump(id.clone())),
+            _ => None,
+        };
+        namespace.into_iter().chain(pump).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quota_enforcer_cpu_time() {
+        // pump_task2 runs 250 us in total
+        let graph = TaskGraph::fx_split_join();
+        let metrics = PerformanceMetrics::fx_split_join();
+
+        let config = SpringConfig::new(
+            r#"
+            [pumps.pump_task2]
+            cpu_time_upper_limit_percent = 1
+            on_quota_exceeded = "pause"
+            "#,
+        )
+        .unwrap();
+        let mut enforcer = QuotaEnforcer::new(&config);
+        assert!(enforcer.is_enabled());
+
+        // 100 us allowed
+        let events = enforcer.enforce(&graph, &metrics, Duration::from_millis(10));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].0, "QUOTA_EXCEEDED");
+        assert_eq!(
+            metrics.throttle(&TaskId::fx_split_join_t2()),
+            Some(Throttle::Pause)
+        );
+        assert_eq!(metrics.throttle(&TaskId::fx_split_join_t4()), None);
+
+        // excess (150 us) is paid back in an idle period
+        let events = enforcer.enforce(&graph, &metrics, Duration::from_millis(20));
+        assert_eq!(
+            events,
+            vec![("QUOTA_RECOVERED", "pump pump_task2".to_string())]
+        );
+        assert_eq!(metrics.throttle(&TaskId::fx_split_join_t2()), None);
+    }
+
+    #[test]
+    fn test_quota_enforcer_memory() {
+        let graph = TaskGraph::fx_split_join();
+        let metrics = PerformanceMetrics::fx_split_join();
+
+        let config = SpringConfig::new(
+            "
+            [pumps.pump_task2]
+            memory_upper_limit_bytes = 1
+            ",
+        )
+        .unwrap();
+        let mut enforcer = QuotaEnforcer::new(&config);
+
+        let _ = enforcer.enforce(&graph, &metrics, Duration::from_secs(1));
+        assert_eq!(
+            metrics.throttle(&TaskId::fx_split_join_t2()),
+            Some(Throttle::Deprioritize)
+        );
+    }
+}
@@ -2,15 +2,21 @@
 
 use serde_json::json;
 
-use crate::stream_engine::autonomous_executor::{
-    performance_metrics::{PerformanceMetrics, RowQueueMetrics, TaskMetrics, WindowQueueMetrics},
-    task_graph::{RowQueueId, TaskGraph, TaskId, WindowQueueId},
+use crate::stream_engine::{
+    autonomous_executor::{
+        performance_metrics::{
+            PerformanceMetrics, RowQueueMetrics, SinkHealth, TaskMetrics, WindowQueueMetrics,
+        },
+        task_graph::{RowQueueId, TaskGraph, TaskId, WindowQueueId},
+    },
+    time::SpringDuration,
 };
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct WebConsoleRequest {
     tasks: Vec<TaskRequest>,
     queues: Vec<QueueRequest>,
+    namespaces: Vec<NamespaceRequest>,
 }
 
 impl WebConsoleRequest {
@@ -18,7 +24,7 @@ impl WebConsoleRequest {
         let tasks = metrics
             .get_tasks()
             .iter()
-            .map(|(id, metrics)| TaskRequest::from_metrics(id, &*metrics))
+            .map(|(id, metrics)| TaskRequest::from_metrics(id, &*metrics, graph))
             .collect();
 
         let queues = metrics
@@ -33,7 +39,21 @@ impl WebConsoleRequest {
             )
             .collect();
 
-        Self { tasks, queues }
+        let namespaces = metrics
+            .namespace_usages()
+            .into_iter()
+            .map(|(namespace, usage)| NamespaceRequest {
+                namespace,
+                num_rows: usage.rows,
+                total_bytes: usage.bytes,
+            })
+            .collect();
+
+        Self {
+            tasks,
+            queues,
+            namespaces,
+        }
     }
 }
 
@@ -43,6 +63,7 @@ impl WebConsoleRequest {
             {
                 "tasks": self.tasks.iter().map(TaskRequest::to_json).collect::<Vec<_>>(),
                 "queues": self.queues.iter().map(QueueRequest::to_json).collect::<Vec<_>>(),
+                "namespaces": self.namespaces.iter().map(NamespaceRequest::to_json).collect::<Vec<_>>(),
             }
         )
     }
@@ -53,10 +74,19 @@ struct TaskRequest {
     id: String,
     type_: String,
     avg_gain_bytes_per_sec: f32,
+    tags: Vec<(String, String)>,
+    sink_health: Option<SinkHealth>,
+    row_errors: u64,
+    batch_size: u64,
 }
 
 impl TaskRequest {
-    fn from_metrics(id: &TaskId, metrics: &TaskMetrics) -> Self {
+    fn from_metrics(id: &TaskId, metrics: &TaskMetrics, graph: &TaskGraph) -> Self {
+        let tags = graph.task_tags(id).map_or_else(Vec::new, |tags| {
+            tags.iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        });
         Self {
             id: id.to_string(),
             type_: match id {
@@ -66,6 +96,10 @@ impl TaskRequest {
             }
             .to_string(),
             avg_gain_bytes_per_sec: metrics.avg_gain_bytes_per_sec(),
+            tags,
+            sink_health: metrics.last_sink_health(),
+            row_errors: metrics.row_errors(),
+            batch_size: metrics.batch_size(),
         }
     }
 
@@ -75,6 +109,38 @@ impl TaskRequest {
                 "id": self.id.clone(),
                 "type": self.type_.clone(),
                 "avg-gain-bytes-per-sec": self.avg_gain_bytes_per_sec,
+                "tags": self
+                    .tags
+                    .iter()
+                    .map(|(k, v)| (k.clone(), json!(v)))
+                    .collect::<serde_json::Map<_, _>>(),
+                "sink-health": self.sink_health.map(|sink_health| json!({
+                    "retries": sink_health.retries,
+                    "dropped-rows": sink_health.dropped_rows,
+                    "circuit-open": sink_health.circuit_open,
+                })),
+                "row-errors": self.row_errors,
+                "batch-size": self.batch_size,
+            }
+        )
+    }
+}
+
+/// Rows and bytes in queues of streams in a namespace.
+#[derive(Clone, PartialEq, Debug)]
+struct NamespaceRequest {
+    namespace: String,
+    num_rows: u64,
+    total_bytes: u64,
+}
+
+impl NamespaceRequest {
+    fn to_json(&self) -> serde_json::Value {
+        json!(
+            {
+                "namespace": self.namespace.clone(),
+                "num-rows": self.num_rows,
+                "total-bytes": self.total_bytes,
             }
         )
     }
@@ -110,6 +176,15 @@ impl QueueRequest {
         let queue = QueueInnerRequest::Window {
             num_rows_waiting: metrics.rows_waiting(),
             total_bytes: metrics.bytes(),
+            num_late_rows: metrics.late_rows(),
+            num_dropped_late_rows: metrics.dropped_late_rows(),
+            max_disorder_secs: metrics
+                .max_disorder()
+                .map_or(0.0, |max_disorder| max_disorder.as_secs_f32()),
+            num_panes_opened: metrics.triggers().panes_opened,
+            num_panes_closed: metrics.triggers().panes_closed,
+            num_late_merges: metrics.triggers().late_merges,
+            num_emitted_rows: metrics.triggers().emitted_rows,
         };
         Self {
             id: id.to_string(),
@@ -147,6 +222,13 @@ enum QueueInnerRequest {
     Window {
         num_rows_waiting: u64,
         total_bytes: u64,
+        num_late_rows: u64,
+        num_dropped_late_rows: u64,
+        max_disorder_secs: f32,
+        num_panes_opened: u64,
+        num_panes_closed: u64,
+        num_late_merges: u64,
+        num_emitted_rows: u64,
     },
 }
 
@@ -165,9 +247,23 @@ impl QueueInnerRequest {
             QueueInnerRequest::Window {
                 num_rows_waiting,
                 total_bytes,
+                num_late_rows,
+                num_dropped_late_rows,
+                max_disorder_secs,
+                num_panes_opened,
+                num_panes_closed,
+                num_late_merges,
+                num_emitted_rows,
             } => json!({
                 "num-rows-waiting": num_rows_waiting,
                 "total-bytes": total_bytes,
+                "num-late-rows": num_late_rows,
+                "num-dropped-late-rows": num_dropped_late_rows,
+                "max-disorder-secs": max_disorder_secs,
+                "num-panes-opened": num_panes_opened,
+                "num-panes-closed": num_panes_closed,
+                "num-late-merges": num_late_merges,
+                "num-emitted-rows": num_emitted_rows,
             }),
         }
     }
@@ -1,7 +1,10 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+mod stream_tracer;
 mod task_repository;
 
+pub use stream_tracer::StreamTracers;
+
 use std::sync::Arc;
 
 use crate::{
@@ -20,16 +23,19 @@ pub struct PipelineDerivatives {
     pipeline: Pipeline,
     task_graph: TaskGraph,
     task_repo: TaskRepository,
+    stream_tracers: StreamTracers,
 }
 
 impl PipelineDerivatives {
     pub fn new(pipeline: Pipeline) -> Self {
         let task_graph = TaskGraph::from(&pipeline);
         let task_repo = TaskRepository::from(pipeline.as_graph());
+        let stream_tracers = StreamTracers::from(&pipeline);
         Self {
             pipeline,
             task_graph,
             task_repo,
+            stream_tracers,
         }
     }
 
@@ -49,6 +55,10 @@ impl PipelineDerivatives {
         &self.task_repo
     }
 
+    pub fn stream_tracers(&self) -> &StreamTracers {
+        &self.stream_tracers
+    }
+
     /// # Failure
     ///
     /// - `SpringError::Sql` when:
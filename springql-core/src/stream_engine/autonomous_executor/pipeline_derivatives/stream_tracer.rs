@@ -0,0 +1,98 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+
+use crate::{
+    pipeline::{Pipeline, StreamName},
+    stream_engine::autonomous_executor::row::StreamRow,
+};
+
+/// Rows logged per second for each stream at most, even if the sample rate is high.
+const MAX_TRACED_ROWS_PER_SEC: u32 = 10;
+
+/// Logs sampled rows traversing streams set by `ALTER STREAM s SET TRACE SAMPLE rate`.
+#[derive(Debug, Default)]
+pub struct StreamTracers {
+    tracers: HashMap<StreamName, StreamTracer>,
+}
+
+impl StreamTracers {
+    pub fn trace<'a>(&self, stream: &StreamName, rows: impl Iterator<Item = &'a StreamRow>) {
+        if let Some(tracer) = self.tracers.get(stream) {
+            rows.for_each(|row| {
+                let _ = tracer.trace(stream, row);
+            });
+        }
+    }
+}
+
+impl From<&Pipeline> for StreamTracers {
+    fn from(pipeline: &Pipeline) -> Self {
+        let tracers = pipeline
+            .stream_trace_sample_rates()
+            .map(|(stream, sample_rate)| (stream.clone(), StreamTracer::new(sample_rate)))
+            .collect();
+        Self { tracers }
+    }
+}
+
+#[derive(Debug)]
+struct StreamTracer {
+    sample_rate: f32,
+
+    /// (start of the current 1-second period, rows logged in the period)
+    period: Mutex<(Instant, u32)>,
+}
+
+impl StreamTracer {
+    fn new(sample_rate: f32) -> Self {
+        Self {
+            sample_rate,
+            period: Mutex::new((Instant::now(), 0)),
+        }
+    }
+
+    /// Returns whether `row` is logged.
+    fn trace(&self, stream: &StreamName, row: &StreamRow) -> bool {
+        if fastrand::f32() >= self.sample_rate {
+            return false;
+        }
+
+        let mut period = self.period.lock();
+        let (started_at, n_traced) = &mut *period;
+        if started_at.elapsed() >= Duration::from_secs(1) {
+            *started_at = Instant::now();
+            *n_traced = 0;
+        }
+
+        if *n_traced < MAX_TRACED_ROWS_PER_SEC {
+            *n_traced += 1;
+            log::info!("[StreamTracer] {}: {:?}", stream, row);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limit() {
+        let tracer = StreamTracer::new(1.0);
+        let stream = StreamName::fx_city_temperature();
+        let row = StreamRow::fx_city_temperature_tokyo();
+
+        let n_traced = (0..MAX_TRACED_ROWS_PER_SEC * 2)
+            .filter(|_| tracer.trace(&stream, &row))
+            .count();
+        assert_eq!(n_traced, MAX_TRACED_ROWS_PER_SEC as usize);
+    }
+}
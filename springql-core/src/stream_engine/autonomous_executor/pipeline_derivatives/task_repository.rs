@@ -8,6 +8,7 @@ use crate::{
     api::error::{Result, SpringError},
     pipeline::PipelineGraph,
     stream_engine::autonomous_executor::{
+        snapshot::AggrWindowSnapshot,
         task::{Task, Window},
         task_graph::TaskId,
     },
@@ -39,12 +40,52 @@ impl TaskRepository {
                     w.borrow_mut().purge()
                 } else if let Some(mut w) = pump_task.get_aggr_window_mut() {
                     w.borrow_mut().purge()
+                } else if let Some(mut w) = pump_task.get_interval_join_mut() {
+                    w.borrow_mut().purge()
                 } else {
                     // nothing to purge
                 }
             }
         }
     }
+
+    pub fn snapshot_aggr_windows(&self) -> Vec<AggrWindowSnapshot> {
+        self.repo
+            .iter()
+            .filter_map(|(task_id, task)| match task.as_ref() {
+                Task::Pump(pump_task) => pump_task
+                    .get_aggr_window_mut()
+                    .map(|w| w.snapshot(task_id.to_string())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - a pump in `snapshots` is not in this repository or does not have aggregation.
+    /// - Raises Err from `AggrWindow::restore()` as-is.
+    pub fn restore_aggr_windows(&self, snapshots: Vec<AggrWindowSnapshot>) -> Result<()> {
+        for snapshot in snapshots {
+            let mut window = self
+                .repo
+                .iter()
+                .find(|(task_id, _)| task_id.to_string() == snapshot.pump)
+                .and_then(|(_, task)| match task.as_ref() {
+                    Task::Pump(pump_task) => pump_task.get_aggr_window_mut(),
+                    _ => None,
+                })
+                .ok_or_else(|| {
+                    SpringError::Sql(anyhow!(
+                        "pump {} with aggregation in the snapshot is not in the pipeline",
+                        snapshot.pump
+                    ))
+                })?;
+            window.restore(snapshot)?;
+        }
+        Ok(())
+    }
 }
 
 impl From<&PipelineGraph> for TaskRepository {
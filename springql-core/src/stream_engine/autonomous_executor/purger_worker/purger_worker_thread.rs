@@ -1,28 +1,46 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use std::{sync::Arc, thread, time::Duration};
+use std::{collections::BTreeMap, sync::Arc, thread, time::Duration};
 
-use crate::stream_engine::autonomous_executor::{
-    event_queue::{BlockingEventTag, Event, EventTag, NonBlockingEventQueue, NonBlockingEventTag},
-    memory_state_machine::{MemoryState, MemoryStateTransition},
-    performance_metrics::{
-        MetricsUpdateByTaskExecutionOrPurge, PerformanceMetrics, PerformanceMetricsSummary,
+use crate::{
+    pipeline::QueueName,
+    stream_engine::{
+        autonomous_executor::{
+            event_queue::{
+                BlockingEventTag, Event, EventTag, NonBlockingEventQueue, NonBlockingEventTag,
+            },
+            memory_state_machine::{MemoryState, MemoryStateTransition},
+            performance_metrics::{
+                MetricsUpdateByTaskExecutionOrPurge, PerformanceMetrics, PerformanceMetricsSummary,
+            },
+            pipeline_derivatives::PipelineDerivatives,
+            queue::PurgedRows,
+            repositories::Repositories,
+            task_executor::TaskExecutorLock,
+            task_graph::{QueueId, TaskGraph},
+            worker::{WorkerSetupCoordinator, WorkerThread, WorkerThreadLoopState},
+        },
+        in_memory_queue_repository::InMemoryQueueRepository,
+        time::SystemTimestamp,
     },
-    pipeline_derivatives::PipelineDerivatives,
-    repositories::Repositories,
-    task_executor::TaskExecutorLock,
-    worker::{WorkerSetupCoordinator, WorkerThread, WorkerThreadLoopState},
 };
 
 #[derive(Debug, new)]
 pub struct PurgerWorkerThreadArg {
     repos: Arc<Repositories>,
     task_executor_lock: Arc<TaskExecutorLock>,
+
+    /// In-memory queue to report purged rows (`memory.purge_report_queue` config).
+    purge_report_queue: Option<QueueName>,
+
+    /// Namespaces to their memory budgets in bytes (`namespaces.<namespace>.memory_upper_limit_bytes` config).
+    namespace_budgets: BTreeMap<String, u64>,
 }
 
 #[derive(Debug)]
 pub struct PurgerWorkerLoopState {
     pipeline_derivatives: Option<Arc<PipelineDerivatives>>,
+    memory_state: MemoryState,
 }
 impl WorkerThreadLoopState for PurgerWorkerLoopState {
     type ThreadArg = PurgerWorkerThreadArg;
@@ -33,6 +51,7 @@ impl WorkerThreadLoopState for PurgerWorkerLoopState {
     {
         Self {
             pipeline_derivatives: None,
+            memory_state: MemoryState::Moderate,
         }
     }
 
@@ -60,6 +79,7 @@ impl WorkerThread for PurgerWorkerThread {
         vec![
             EventTag::Blocking(BlockingEventTag::UpdatePipeline),
             EventTag::NonBlocking(NonBlockingEventTag::TransitMemoryState),
+            EventTag::NonBlocking(NonBlockingEventTag::ReportMetricsSummary),
         ]
     }
 
@@ -68,7 +88,7 @@ impl WorkerThread for PurgerWorkerThread {
         _thread_arg: &Self::ThreadArg,
         _event_queue: &NonBlockingEventQueue,
     ) -> Self::LoopState {
-        // Do nothing in loop. Only curious about TransitMemoryState event.
+        // Do nothing in loop. Only curious about TransitMemoryState and ReportMetricsSummary events.
         thread::sleep(Duration::from_millis(100));
         current_state
     }
@@ -76,13 +96,17 @@ impl WorkerThread for PurgerWorkerThread {
     fn ev_update_pipeline(
         current_state: Self::LoopState,
         pipeline_derivatives: Arc<PipelineDerivatives>,
-        _thread_arg: &Self::ThreadArg,
+        thread_arg: &Self::ThreadArg,
         _event_queue: Arc<NonBlockingEventQueue>,
     ) -> Self::LoopState {
         log::debug!("[PurgerWorker] got UpdatePipeline event",);
 
         let mut state = current_state;
         state.pipeline_derivatives = Some(pipeline_derivatives);
+        if state.memory_state != MemoryState::Moderate {
+            // queues have been recreated for the new pipeline
+            Self::set_best_effort_pumps_paused(&state, thread_arg, true);
+        }
         state
     }
 
@@ -92,9 +116,24 @@ impl WorkerThread for PurgerWorkerThread {
         thread_arg: &Self::ThreadArg,
         event_queue: Arc<NonBlockingEventQueue>,
     ) -> Self::LoopState {
+        let mut current_state = current_state;
+        current_state.memory_state = memory_state_transition.to_state();
+
+        thread_arg.repos.system_stream_repository().put_event(
+            "MEMORY_STATE",
+            format!(
+                "{:?} -> {:?}",
+                memory_state_transition.from_state(),
+                memory_state_transition.to_state()
+            ),
+        );
+
         match memory_state_transition.to_state() {
-            MemoryState::Moderate | MemoryState::Severe => {
-                // do nothing
+            MemoryState::Moderate => {
+                Self::set_best_effort_pumps_paused(&current_state, thread_arg, false);
+            }
+            MemoryState::Severe => {
+                Self::set_best_effort_pumps_paused(&current_state, thread_arg, true);
             }
             MemoryState::Critical => {
                 log::warn!("[PurgerWorker] Start purging...",);
@@ -105,8 +144,22 @@ impl WorkerThread for PurgerWorkerThread {
                 // purge queues
                 let row_queue_repo = thread_arg.repos.row_queue_repository();
                 let window_queue_repo = thread_arg.repos.window_queue_repository();
-                row_queue_repo.purge();
-                window_queue_repo.purge();
+                let purged_row_queues = row_queue_repo
+                    .purge()
+                    .into_iter()
+                    .map(|(queue_id, purged)| (QueueId::from(queue_id), purged));
+                let purged_window_queues = window_queue_repo
+                    .purge()
+                    .into_iter()
+                    .map(|(queue_id, purged)| (QueueId::from(queue_id), purged));
+                Self::report_purged_rows(
+                    thread_arg,
+                    current_state
+                        .pipeline_derivatives
+                        .as_ref()
+                        .map(|pd| pd.task_graph()),
+                    purged_row_queues.chain(purged_window_queues).collect(),
+                );
 
                 // purge windows
                 if let Some(pd) = &current_state.pipeline_derivatives {
@@ -147,11 +200,189 @@ impl WorkerThread for PurgerWorkerThread {
     }
 
     fn ev_report_metrics_summary(
-        _current_state: Self::LoopState,
-        _metrics_summary: Arc<PerformanceMetricsSummary>,
-        _thread_arg: &Self::ThreadArg,
-        _event_queue: Arc<NonBlockingEventQueue>,
+        current_state: Self::LoopState,
+        metrics_summary: Arc<PerformanceMetricsSummary>,
+        thread_arg: &Self::ThreadArg,
+        event_queue: Arc<NonBlockingEventQueue>,
     ) -> Self::LoopState {
-        unreachable!()
+        if let Some(pd) = &current_state.pipeline_derivatives {
+            for (namespace, usage) in &metrics_summary.namespace_usages {
+                match thread_arg.namespace_budgets.get(namespace) {
+                    Some(&budget) if usage.bytes > budget => Self::purge_namespace(
+                        thread_arg,
+                        pd.task_graph(),
+                        namespace,
+                        usage.bytes,
+                        budget,
+                        &event_queue,
+                    ),
+                    _ => {}
+                }
+            }
+        }
+        current_state
+    }
+}
+
+impl PurgerWorkerThread {
+    /// Purges queues of `namespace` over its memory budget. Queues of other namespaces are untouched.
+    fn purge_namespace(
+        thread_arg: &PurgerWorkerThreadArg,
+        task_graph: &TaskGraph,
+        namespace: &str,
+        bytes: u64,
+        budget: u64,
+        event_queue: &NonBlockingEventQueue,
+    ) {
+        log::warn!(
+            "[PurgerWorker] Namespace {} uses {} bytes over its budget ({} bytes). Purging its queues...",
+            namespace,
+            bytes,
+            budget
+        );
+        thread_arg.repos.system_stream_repository().put_event(
+            "NAMESPACE_OVER_BUDGET",
+            format!("{}: {} bytes > {} bytes", namespace, bytes, budget),
+        );
+
+        let queue_ids = task_graph.namespace_queues(namespace);
+        let mut row_queue_ids = Vec::new();
+        let mut window_queue_ids = Vec::new();
+        for queue_id in &queue_ids {
+            match queue_id {
+                QueueId::Row(id) => row_queue_ids.push(id.clone()),
+                QueueId::Window(id) => window_queue_ids.push(id.clone()),
+            }
+        }
+
+        let purged = {
+            let _lock = thread_arg.task_executor_lock.task_execution_barrier();
+            let purged_row_queues = thread_arg
+                .repos
+                .row_queue_repository()
+                .purge_queues(&row_queue_ids)
+                .into_iter()
+                .map(|(queue_id, purged)| (QueueId::from(queue_id), purged));
+            let purged_window_queues = thread_arg
+                .repos
+                .window_queue_repository()
+                .purge_queues(&window_queue_ids)
+                .into_iter()
+                .map(|(queue_id, purged)| (QueueId::from(queue_id), purged));
+            purged_row_queues.chain(purged_window_queues).collect()
+        };
+        Self::report_purged_rows(thread_arg, Some(task_graph), purged);
+
+        event_queue.publish(Event::IncrementalUpdateMetrics {
+            metrics_update_by_task_execution_or_purge: Arc::new(
+                MetricsUpdateByTaskExecutionOrPurge::PurgeQueues(queue_ids),
+            ),
+        });
+    }
+
+    /// Logs purged rows and puts their summaries into `spring._events` and `memory.purge_report_queue` if set.
+    ///
+    /// PURGE events have tags of the tasks consuming purged queues.
+    fn report_purged_rows(
+        thread_arg: &PurgerWorkerThreadArg,
+        task_graph: Option<&TaskGraph>,
+        purged: Vec<(QueueId, PurgedRows)>,
+    ) {
+        let purged_at = SystemTimestamp::now();
+
+        for (queue_id, purged_rows) in purged {
+            let queue_name = match &queue_id {
+                QueueId::Row(id) => id.to_string(),
+                QueueId::Window(id) => id.to_string(),
+            };
+            log::warn!(
+                "[PurgerWorker] Purged {} rows in queue {}",
+                purged_rows.n_rows(),
+                queue_name
+            );
+
+            let rowtime_range = purged_rows
+                .rowtime_range()
+                .map_or_else(String::new, |(min, max)| {
+                    format!(" (ROWTIME {} - {})", min.to_string(), max.to_string())
+                });
+            let tags = task_graph
+                .and_then(|g| g.queue_tags(&queue_id))
+                .map_or_else(String::new, |tags| format!(" [{}]", tags));
+            thread_arg.repos.system_stream_repository().put_event(
+                "PURGE",
+                format!(
+                    "{}: {} rows{}{}",
+                    queue_name,
+                    purged_rows.n_rows(),
+                    rowtime_range,
+                    tags
+                ),
+            );
+
+            if let Some(report_queue) = &thread_arg.purge_report_queue {
+                let res = InMemoryQueueRepository::instance()
+                    .get(report_queue)
+                    .and_then(|q| {
+                        purged_rows
+                            .to_report_row(&queue_name, purged_at)
+                            .map(|row| q.push(row))
+                    });
+                if let Err(e) = res {
+                    log::warn!(
+                        "[PurgerWorker] Failed to report purged rows to queue {}: {:?}",
+                        report_queue,
+                        e
+                    );
+                }
+            }
+        }
+    }
+
+    /// Pauses (or resumes) `BEST_EFFORT` pumps by pausing (or resuming) their input queues.
+    /// Upstream tasks stop putting rows into paused queues, while rows already in them remain until resumed.
+    /// Queues of pumps stopped by `ALTER PUMP p STOP` are kept paused.
+    fn set_best_effort_pumps_paused(
+        state: &PurgerWorkerLoopState,
+        thread_arg: &PurgerWorkerThreadArg,
+        paused: bool,
+    ) {
+        if let Some(pd) = &state.pipeline_derivatives {
+            let task_graph = pd.task_graph();
+            let row_queue_repo = thread_arg.repos.row_queue_repository();
+            let window_queue_repo = thread_arg.repos.window_queue_repository();
+
+            for task_id in task_graph
+                .best_effort_tasks()
+                .into_iter()
+                .filter(|task_id| !task_graph.is_stopped(task_id))
+            {
+                log::warn!(
+                    "[PurgerWorker] {} best-effort pump {}",
+                    if paused { "Pausing" } else { "Resuming" },
+                    task_id
+                );
+                for queue_id in task_graph.input_queues(&task_id) {
+                    match queue_id {
+                        QueueId::Row(id) => {
+                            let q = row_queue_repo.get(&id);
+                            if paused {
+                                q.pause()
+                            } else {
+                                q.resume()
+                            }
+                        }
+                        QueueId::Window(id) => {
+                            let q = window_queue_repo.get(&id);
+                            if paused {
+                                q.pause()
+                            } else {
+                                q.resume()
+                            }
+                        }
+                    }
+                }
+            }
+        }
     }
 }
@@ -1,10 +1,12 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+mod purged_rows;
 mod row_queue;
 mod row_queue_repository;
 mod window_queue;
 mod window_queue_repository;
 
+pub use purged_rows::PurgedRows;
 pub use row_queue::RowQueue;
 pub use row_queue_repository::RowQueueRepository;
 pub use window_queue::WindowQueue;
@@ -0,0 +1,83 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use crate::{
+    api::error::Result,
+    pipeline::ColumnName,
+    stream_engine::{
+        autonomous_executor::row::{SchemalessRow, StreamRow},
+        time::SpringTimestamp,
+        NnSqlValue, SqlValue,
+    },
+};
+
+/// Summary of rows discarded from a queue by a purge.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct PurgedRows {
+    n_rows: u64,
+
+    /// (min ROWTIME, max ROWTIME) of the purged rows. `None` if no row is purged.
+    rowtime_range: Option<(SpringTimestamp, SpringTimestamp)>,
+}
+
+impl PurgedRows {
+    pub fn from_rows<'a>(rows: impl Iterator<Item = &'a StreamRow>) -> Self {
+        rows.fold(Self::default(), |mut acc, row| {
+            let rowtime = row.rowtime().as_timestamp();
+            acc.n_rows += 1;
+            acc.rowtime_range = Some(acc.rowtime_range.map_or((rowtime, rowtime), |(min, max)| {
+                (min.min(rowtime), max.max(rowtime))
+            }));
+            acc
+        })
+    }
+
+    pub fn n_rows(&self) -> u64 {
+        self.n_rows
+    }
+
+    pub fn rowtime_range(&self) -> Option<(SpringTimestamp, SpringTimestamp)> {
+        self.rowtime_range
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n_rows == 0
+    }
+
+    /// Summary row reported to `memory.purge_report_queue`.
+    ///
+    /// Columns: `purged_at TIMESTAMP`, `queue_name TEXT`, `n_rows BIGINT`, `min_rowtime TIMESTAMP`, `max_rowtime TIMESTAMP`.
+    pub fn to_report_row(
+        &self,
+        queue_name: &str,
+        purged_at: SpringTimestamp,
+    ) -> Result<SchemalessRow> {
+        let ts = |t: Option<SpringTimestamp>| {
+            t.map_or(SqlValue::Null, |t| {
+                SqlValue::NotNull(NnSqlValue::Timestamp(t))
+            })
+        };
+
+        let mut row = SchemalessRow::with_capacity(5);
+        row.insert(
+            ColumnName::new("purged_at".to_string()),
+            SqlValue::NotNull(NnSqlValue::Timestamp(purged_at)),
+        )?;
+        row.insert(
+            ColumnName::new("queue_name".to_string()),
+            SqlValue::NotNull(NnSqlValue::Text(queue_name.to_string())),
+        )?;
+        row.insert(
+            ColumnName::new("n_rows".to_string()),
+            SqlValue::NotNull(NnSqlValue::BigInt(self.n_rows as i64)),
+        )?;
+        row.insert(
+            ColumnName::new("min_rowtime".to_string()),
+            ts(self.rowtime_range.map(|(min, _)| min)),
+        )?;
+        row.insert(
+            ColumnName::new("max_rowtime".to_string()),
+            ts(self.rowtime_range.map(|(_, max)| max)),
+        )?;
+        Ok(row)
+    }
+}
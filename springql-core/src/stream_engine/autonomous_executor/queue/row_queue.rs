@@ -1,17 +1,27 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use std::{collections::VecDeque, sync::Mutex};
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
 
-use crate::stream_engine::autonomous_executor::row::StreamRow;
+use crate::stream_engine::autonomous_executor::{queue::PurgedRows, row::StreamRow};
 
 /// Input queue of row tasks.
 ///
-/// Just a FIFO buffer.
+/// Just a FIFO buffer, except that high-priority rows (`PRIORITY WHEN`) are used before any other rows.
 ///
 /// ![Row queue](https://raw.githubusercontent.com/SpringQL/SpringQL/main/springql-core/doc/img/row-queue.drawio.svg)
 #[derive(Debug, Default)]
 pub struct RowQueue {
     q: Mutex<VecDeque<StreamRow>>,
+    priority_q: Mutex<VecDeque<StreamRow>>,
+
+    /// Writers must not put rows into a paused queue (e.g. input queue of a paused `BEST_EFFORT` pump or a stopped pump).
+    paused: AtomicBool,
 }
 
 impl RowQueue {
@@ -22,17 +32,148 @@ impl RowQueue {
             .push_back(row);
     }
 
+    pub fn put_priority(&self, row: StreamRow) {
+        self.priority_q
+            .lock()
+            .expect("mutex in RowQueue is poisoned")
+            .push_back(row);
+    }
+
     pub fn use_(&self) -> Option<StreamRow> {
-        self.q
+        self.priority_q
             .lock()
             .expect("mutex in RowQueue is poisoned")
             .pop_front()
+            .or_else(|| {
+                self.q
+                    .lock()
+                    .expect("mutex in RowQueue is poisoned")
+                    .pop_front()
+            })
     }
 
-    pub fn purge(&self) {
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::Relaxed)
+    }
+
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::Relaxed)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// Copies rows in the queue.
+    ///
+    /// # Returns
+    ///
+    /// (high-priority rows, other rows)
+    pub fn snapshot(&self) -> (Vec<StreamRow>, Vec<StreamRow>) {
+        let priority_q = self
+            .priority_q
+            .lock()
+            .expect("mutex in RowQueue is poisoned");
+        let q = self.q.lock().expect("mutex in RowQueue is poisoned");
+        (
+            priority_q.iter().cloned().collect(),
+            q.iter().cloned().collect(),
+        )
+    }
+
+    /// Puts rows from a snapshot after rows currently in the queue.
+    pub fn restore(&self, priority_rows: Vec<StreamRow>, rows: Vec<StreamRow>) {
+        self.priority_q
+            .lock()
+            .expect("mutex in RowQueue is poisoned")
+            .extend(priority_rows);
         self.q
             .lock()
             .expect("mutex in RowQueue is poisoned")
-            .clear()
+            .extend(rows);
+    }
+
+    /// Discards all rows in the queue.
+    pub fn purge(&self) -> PurgedRows {
+        let mut priority_q = self
+            .priority_q
+            .lock()
+            .expect("mutex in RowQueue is poisoned");
+        let mut q = self.q.lock().expect("mutex in RowQueue is poisoned");
+
+        let purged = PurgedRows::from_rows(priority_q.iter().chain(q.iter()));
+        priority_q.clear();
+        q.clear();
+        purged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::stream_engine::{time::SpringTimestamp, NnSqlValue, SqlValue};
+
+    #[test]
+    fn test_priority_row_overtakes() {
+        let q = RowQueue::default();
+
+        q.put(StreamRow::fx_city_temperature_tokyo());
+        q.put(StreamRow::fx_city_temperature_osaka());
+        q.put_priority(StreamRow::fx_city_temperature_london());
+
+        assert_eq!(q.use_(), Some(StreamRow::fx_city_temperature_london()));
+        assert_eq!(q.use_(), Some(StreamRow::fx_city_temperature_tokyo()));
+        assert_eq!(q.use_(), Some(StreamRow::fx_city_temperature_osaka()));
+        assert_eq!(q.use_(), None);
+    }
+
+    #[test]
+    fn test_pause() {
+        let q = RowQueue::default();
+        assert!(!q.is_paused());
+
+        q.pause();
+        assert!(q.is_paused());
+
+        q.resume();
+        assert!(!q.is_paused());
+    }
+
+    #[test]
+    fn test_purge() {
+        let q = RowQueue::default();
+        assert!(q.purge().is_empty());
+
+        q.put(StreamRow::fx_city_temperature_tokyo());
+        q.put_priority(StreamRow::fx_city_temperature_osaka());
+
+        let purged = q.purge();
+        assert_eq!(purged.n_rows(), 2);
+        assert_eq!(q.use_(), None);
+
+        let row = purged
+            .to_report_row("q", SpringTimestamp::fx_ts1())
+            .unwrap();
+        let rowtimes = [
+            StreamRow::fx_city_temperature_tokyo()
+                .rowtime()
+                .as_timestamp(),
+            StreamRow::fx_city_temperature_osaka()
+                .rowtime()
+                .as_timestamp(),
+        ];
+        assert_eq!(
+            row.get_by_index(2).unwrap(),
+            &SqlValue::NotNull(NnSqlValue::BigInt(2))
+        );
+        assert_eq!(
+            row.get_by_index(3).unwrap(),
+            &SqlValue::NotNull(NnSqlValue::Timestamp(*rowtimes.iter().min().unwrap()))
+        );
+        assert_eq!(
+            row.get_by_index(4).unwrap(),
+            &SqlValue::NotNull(NnSqlValue::Timestamp(*rowtimes.iter().max().unwrap()))
+        );
     }
 }
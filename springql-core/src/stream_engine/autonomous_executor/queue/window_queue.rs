@@ -1,17 +1,28 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use std::{collections::VecDeque, sync::Mutex};
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
 
-use crate::stream_engine::autonomous_executor::row::StreamRow;
+use crate::stream_engine::autonomous_executor::{queue::PurgedRows, row::StreamRow};
 
 /// Input queue of window tasks.
 ///
 /// Window queue has complicated structure, compared to row queue.
+/// High-priority rows (`PRIORITY WHEN`) are dispatched before any other waiting rows.
 ///
 /// ![Window queue](https://raw.githubusercontent.com/SpringQL/SpringQL/main/springql-core/doc/img/window-queue.drawio.svg)
 #[derive(Debug, Default)]
 pub struct WindowQueue {
     waiting_q: Mutex<VecDeque<StreamRow>>,
+    priority_waiting_q: Mutex<VecDeque<StreamRow>>,
+
+    /// Writers must not put rows into a paused queue (e.g. input queue of a paused `BEST_EFFORT` pump or a stopped pump).
+    paused: AtomicBool,
 }
 
 impl WindowQueue {
@@ -22,17 +33,84 @@ impl WindowQueue {
             .push_back(row);
     }
 
+    pub fn put_priority(&self, row: StreamRow) {
+        self.priority_waiting_q
+            .lock()
+            .expect("mutex in WindowQueue is poisoned")
+            .push_back(row);
+    }
+
     pub fn dispatch(&self) -> Option<StreamRow> {
-        self.waiting_q
+        self.priority_waiting_q
             .lock()
             .expect("mutex in WindowQueue is poisoned")
             .pop_front()
+            .or_else(|| {
+                self.waiting_q
+                    .lock()
+                    .expect("mutex in WindowQueue is poisoned")
+                    .pop_front()
+            })
+    }
+
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::Relaxed)
+    }
+
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::Relaxed)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// Copies rows in the queue.
+    ///
+    /// # Returns
+    ///
+    /// (high-priority rows, other rows)
+    pub fn snapshot(&self) -> (Vec<StreamRow>, Vec<StreamRow>) {
+        let priority_q = self
+            .priority_waiting_q
+            .lock()
+            .expect("mutex in WindowQueue is poisoned");
+        let q = self
+            .waiting_q
+            .lock()
+            .expect("mutex in WindowQueue is poisoned");
+        (
+            priority_q.iter().cloned().collect(),
+            q.iter().cloned().collect(),
+        )
     }
 
-    pub fn purge(&self) {
+    /// Puts rows from a snapshot after rows currently in the queue.
+    pub fn restore(&self, priority_rows: Vec<StreamRow>, rows: Vec<StreamRow>) {
+        self.priority_waiting_q
+            .lock()
+            .expect("mutex in WindowQueue is poisoned")
+            .extend(priority_rows);
         self.waiting_q
             .lock()
             .expect("mutex in WindowQueue is poisoned")
-            .clear()
+            .extend(rows);
+    }
+
+    /// Discards all rows in the queue.
+    pub fn purge(&self) -> PurgedRows {
+        let mut priority_q = self
+            .priority_waiting_q
+            .lock()
+            .expect("mutex in WindowQueue is poisoned");
+        let mut q = self
+            .waiting_q
+            .lock()
+            .expect("mutex in WindowQueue is poisoned");
+
+        let purged = PurgedRows::from_rows(priority_q.iter().chain(q.iter()));
+        priority_q.clear();
+        q.clear();
+        purged
     }
 }
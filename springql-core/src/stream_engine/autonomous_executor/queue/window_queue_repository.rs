@@ -5,10 +5,18 @@ use std::{
     sync::Arc,
 };
 
+use anyhow::anyhow;
 use parking_lot::RwLock;
 
-use crate::stream_engine::autonomous_executor::{
-    queue::window_queue::WindowQueue, task_graph::WindowQueueId,
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::Pipeline,
+    stream_engine::autonomous_executor::{
+        queue::{window_queue::WindowQueue, PurgedRows},
+        row::StreamRow,
+        snapshot::{QueueSnapshot, RowSnapshot},
+        task_graph::WindowQueueId,
+    },
 };
 
 #[derive(Debug, Default)]
@@ -39,10 +47,70 @@ impl WindowQueueRepository {
         });
     }
 
-    pub fn purge(&self) {
-        let mut repo = self.repo.write();
-        repo.iter_mut().for_each(|(_, queue)| {
-            queue.purge();
-        });
+    /// Discards all rows in all queues.
+    ///
+    /// # Returns
+    ///
+    /// Summaries of queues that had at least a row.
+    pub fn purge(&self) -> Vec<(WindowQueueId, PurgedRows)> {
+        let repo = self.repo.write();
+        repo.iter()
+            .map(|(queue_id, queue)| (queue_id.clone(), queue.purge()))
+            .filter(|(_, purged)| !purged.is_empty())
+            .collect()
+    }
+
+    /// Purges only `queue_ids`. Queues not in this repository are ignored.
+    pub fn purge_queues(&self, queue_ids: &[WindowQueueId]) -> Vec<(WindowQueueId, PurgedRows)> {
+        let repo = self.repo.read();
+        queue_ids
+            .iter()
+            .filter_map(|queue_id| Some((queue_id.clone(), repo.get(queue_id)?.purge())))
+            .filter(|(_, purged)| !purged.is_empty())
+            .collect()
+    }
+
+    pub fn snapshot(&self) -> Vec<QueueSnapshot> {
+        let repo = self.repo.read();
+        repo.iter()
+            .map(|(queue_id, queue)| {
+                let (priority_rows, rows) = queue.snapshot();
+                QueueSnapshot {
+                    queue: queue_id.to_string(),
+                    priority_rows: priority_rows.iter().map(StreamRow::to_snapshot).collect(),
+                    rows: rows.iter().map(StreamRow::to_snapshot).collect(),
+                }
+            })
+            .filter(|snapshot| !snapshot.priority_rows.is_empty() || !snapshot.rows.is_empty())
+            .collect()
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - a queue in `snapshots` is not in this repository.
+    ///   - a row in `snapshots` does not match `pipeline`.
+    pub fn restore(&self, snapshots: Vec<QueueSnapshot>, pipeline: &Pipeline) -> Result<()> {
+        let repo = self.repo.read();
+        for snapshot in snapshots {
+            let queue = repo
+                .get(&WindowQueueId::new(snapshot.queue.clone()))
+                .ok_or_else(|| {
+                    SpringError::Sql(anyhow!(
+                        "queue {} in the snapshot is not in the pipeline",
+                        snapshot.queue
+                    ))
+                })?;
+            let into_rows = |rows: Vec<RowSnapshot>| {
+                rows.into_iter()
+                    .map(|row| StreamRow::from_snapshot(row, pipeline))
+                    .collect::<Result<Vec<_>>>()
+            };
+            queue.restore(
+                into_rows(snapshot.priority_rows)?,
+                into_rows(snapshot.rows)?,
+            );
+        }
+        Ok(())
     }
 }
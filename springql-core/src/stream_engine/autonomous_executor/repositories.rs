@@ -1,10 +1,18 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use std::sync::Arc;
+
 use crate::{
     api::SpringConfig,
     stream_engine::autonomous_executor::{
+        chaos_injector::ChaosInjector,
+        fatal_error::FatalErrorSlot,
         queue::{RowQueueRepository, WindowQueueRepository},
+        row_error_log::RowErrorLogger,
+        stream_tap::StreamTapRepository,
+        system_stream_repository::SystemStreamRepository,
         task::{SinkWriterRepository, SourceReaderRepository},
+        task_hook::TaskHookRepository,
     },
 };
 
@@ -15,15 +23,31 @@ pub struct Repositories {
     window_queue_repository: WindowQueueRepository,
     source_reader_repository: SourceReaderRepository,
     sink_writer_repository: SinkWriterRepository,
+    stream_tap_repository: StreamTapRepository,
+    task_hook_repository: TaskHookRepository,
+    system_stream_repository: Arc<SystemStreamRepository>,
+    row_error_logger: RowErrorLogger,
+    chaos_injector: ChaosInjector,
+    fatal_error_slot: FatalErrorSlot,
 }
 
 impl Repositories {
     pub fn new(config: &SpringConfig) -> Self {
+        let system_stream_repository = Arc::new(SystemStreamRepository::new(&config.system_stream));
         Self {
             row_queue_repository: RowQueueRepository::default(),
             window_queue_repository: WindowQueueRepository::default(),
-            source_reader_repository: SourceReaderRepository::new(config.source_reader),
+            source_reader_repository: SourceReaderRepository::new(
+                config.source_reader,
+                system_stream_repository.clone(),
+            ),
             sink_writer_repository: SinkWriterRepository::new(config.sink_writer),
+            stream_tap_repository: StreamTapRepository::default(),
+            task_hook_repository: TaskHookRepository::default(),
+            system_stream_repository,
+            row_error_logger: RowErrorLogger::new(config.row_error),
+            chaos_injector: ChaosInjector::new(config.chaos),
+            fatal_error_slot: FatalErrorSlot::default(),
         }
     }
 
@@ -42,4 +66,28 @@ impl Repositories {
     pub fn sink_writer_repository(&self) -> &SinkWriterRepository {
         &self.sink_writer_repository
     }
+
+    pub fn stream_tap_repository(&self) -> &StreamTapRepository {
+        &self.stream_tap_repository
+    }
+
+    pub fn task_hook_repository(&self) -> &TaskHookRepository {
+        &self.task_hook_repository
+    }
+
+    pub fn system_stream_repository(&self) -> &SystemStreamRepository {
+        &self.system_stream_repository
+    }
+
+    pub fn row_error_logger(&self) -> &RowErrorLogger {
+        &self.row_error_logger
+    }
+
+    pub fn chaos_injector(&self) -> &ChaosInjector {
+        &self.chaos_injector
+    }
+
+    pub fn fatal_error_slot(&self) -> &FatalErrorSlot {
+        &self.fatal_error_slot
+    }
 }
@@ -11,7 +11,12 @@ mod value;
 
 pub use column::StreamColumns;
 pub use column_values::ColumnValues;
-pub use foreign_row::{CANFrameSourceRow, JsonObject, JsonSourceRow, SourceRow};
+#[cfg(feature = "source-can")]
+pub use foreign_row::CANFrameSourceRow;
+pub use foreign_row::{
+    CsvParser, CsvWriter, JsonObject, JsonSourceRow, LinkDecoder, LinkEncoder, PayloadDecoder,
+    PayloadEncoder, SourceMetadata, SourceRow,
+};
 pub use rowtime::RowTime;
 pub use schemaless_row::SchemalessRow;
 pub use stream_row::StreamRow;
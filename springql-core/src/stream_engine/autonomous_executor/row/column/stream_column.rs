@@ -6,7 +6,7 @@ use anyhow::{anyhow, Context};
 
 use crate::{
     api::error::{Result, SpringError},
-    mem_size::{arc_overhead_size, MemSize},
+    mem_size::{vec_mem_size, MemSize},
     pipeline::{ColumnDefinition, ColumnName, StreamModel},
     stream_engine::{
         autonomous_executor::row::{column_values::ColumnValues, value::SqlValue},
@@ -27,9 +27,7 @@ pub struct StreamColumns {
 
 impl MemSize for StreamColumns {
     fn mem_size(&self) -> usize {
-        let stream_shape_size = arc_overhead_size();
-        let values_size: usize = self.values.iter().map(|v| v.mem_size()).sum();
-        stream_shape_size + values_size
+        vec_mem_size(self.values.capacity(), self.values.iter())
     }
 }
 
@@ -15,6 +15,18 @@ use crate::stream_engine::autonomous_executor::row::value::SqlValue;
 pub struct ColumnValues(Vec<(ColumnName, SqlValue)>);
 
 impl ColumnValues {
+    pub fn with_capacity(n_columns: usize) -> Self {
+        Self(Vec::with_capacity(n_columns))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&ColumnName, &SqlValue)> {
+        self.0.iter().map(|colval| (&colval.0, &colval.1))
+    }
+
     /// # Failure
     ///
     /// - `SpringError::Sql` when:
@@ -64,7 +76,11 @@ impl ColumnValues {
         Ok(v)
     }
 
-    fn find_idx(&self, column_name: &ColumnName) -> Result<usize> {
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `column_name` is not included.
+    pub(crate) fn find_idx(&self, column_name: &ColumnName) -> Result<usize> {
         self.0
             .iter()
             .enumerate()
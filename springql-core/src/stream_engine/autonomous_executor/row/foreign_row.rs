@@ -1,7 +1,13 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 mod format;
+mod source_metadata;
 mod source_row;
 
-pub use format::JsonObject;
-pub use source_row::{CANFrameSourceRow, JsonSourceRow, SourceRow};
+pub use format::{
+    CsvParser, CsvWriter, JsonObject, LinkDecoder, LinkEncoder, PayloadDecoder, PayloadEncoder,
+};
+pub use source_metadata::SourceMetadata;
+#[cfg(feature = "source-can")]
+pub use source_row::CANFrameSourceRow;
+pub use source_row::{JsonSourceRow, SourceRow};
@@ -1,5 +1,19 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+#[cfg(feature = "avro")]
+mod avro;
+mod csv;
 mod json;
+mod link_frame;
+mod payload;
+#[cfg(feature = "protobuf")]
+mod protobuf;
 
+#[cfg(feature = "avro")]
+pub use avro::{AvroDecoder, AvroEncoder};
+pub use csv::{CsvParser, CsvWriter};
 pub use json::JsonObject;
+pub use link_frame::{LinkDecoder, LinkEncoder};
+pub use payload::{PayloadDecoder, PayloadEncoder};
+#[cfg(feature = "protobuf")]
+pub use protobuf::ProtobufDecoder;
@@ -0,0 +1,356 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{collections::HashMap, time::Duration};
+
+use anyhow::{anyhow, Context};
+use apache_avro::{types::Value, Schema};
+use ordered_float::OrderedFloat;
+use reqwest::Url;
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    pipeline::{AvroOptions, ColumnName},
+    stream_engine::{
+        autonomous_executor::row::{
+            column_values::ColumnValues,
+            foreign_row::format::JsonObject,
+            value::{NnSqlValue, SqlValue},
+        },
+        time::SpringTimestamp,
+    },
+};
+
+/// First byte of Confluent wire format, followed by a 4-byte big-endian schema ID.
+const MAGIC_BYTE: u8 = 0;
+const REGISTRY_TIMEOUT: Duration = Duration::from_secs(5);
+const REGISTRY_CONTENT_TYPE: &str = "application/vnd.schemaregistry.v1+json";
+
+/// Client of a Confluent schema registry. Schemas are cached by their IDs.
+#[derive(Debug)]
+struct SchemaRegistry {
+    url: Url,
+    client: reqwest::blocking::Client,
+    schemas: HashMap<u32, Schema>,
+}
+
+impl SchemaRegistry {
+    fn new(url: Url) -> Self {
+        let client = reqwest::blocking::Client::builder()
+            .timeout(REGISTRY_TIMEOUT)
+            .build()
+            .expect("failed to build HTTP client");
+        Self {
+            url,
+            client,
+            schemas: HashMap::new(),
+        }
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to get the schema from the registry.
+    fn schema(&mut self, id: u32) -> Result<&Schema> {
+        if !self.schemas.contains_key(&id) {
+            let schema = self
+                .get_schema(id)
+                .with_context(|| format!("failed to get Avro schema (ID: {})", id))
+                .map_err(|e| self.foreign_io(e))?;
+            self.schemas.insert(id, schema);
+        }
+        Ok(&self.schemas[&id])
+    }
+
+    fn get_schema(&self, id: u32) -> anyhow::Result<Schema> {
+        let url = self.url.join(&format!("schemas/ids/{}", id))?;
+        let resp: serde_json::Value = self
+            .client
+            .get(url)
+            .header(reqwest::header::ACCEPT, REGISTRY_CONTENT_TYPE)
+            .send()?
+            .error_for_status()?
+            .json()?;
+        let schema_str = resp["schema"]
+            .as_str()
+            .context("response does not have schema")?;
+        Ok(Schema::parse_str(schema_str)?)
+    }
+
+    /// Registers a schema under a subject (or looks up the registered one) and returns its ID.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to register the schema.
+    fn register(&self, subject: &str, schema: &Schema) -> Result<u32> {
+        self.post_schema(subject, schema)
+            .with_context(|| format!("failed to register Avro schema under {}", subject))
+            .map_err(|e| self.foreign_io(e))
+    }
+
+    fn post_schema(&self, subject: &str, schema: &Schema) -> anyhow::Result<u32> {
+        let url = self.url.join(&format!("subjects/{}/versions", subject))?;
+        let body = serde_json::json!({ "schema": schema.canonical_form() });
+        let resp: serde_json::Value = self
+            .client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, REGISTRY_CONTENT_TYPE)
+            .body(body.to_string())
+            .send()?
+            .error_for_status()?
+            .json()?;
+        let id = resp["id"].as_u64().context("response does not have id")?;
+        Ok(id as u32)
+    }
+
+    fn foreign_io(&self, e: anyhow::Error) -> SpringError {
+        SpringError::ForeignIo {
+            source: e,
+            foreign_info: ForeignInfo::HttpEndpoint(self.url.to_string()),
+        }
+    }
+}
+
+/// Decodes Avro records into column values.
+///
+/// Fields are mapped to SQL values by their Avro types:
+///
+/// - `int` / `long` / `float` / `double` / `boolean` into INTEGER / BIGINT / FLOAT / FLOAT / BOOLEAN.
+/// - `string`, `enum` and `uuid` into TEXT, and `bytes` and `fixed` into BLOB.
+/// - `timestamp-millis` and `timestamp-micros` into TIMESTAMP.
+/// - `null` and unions by their values.
+#[derive(Debug)]
+pub struct AvroDecoder {
+    /// Writer schema without registry, or reader schema with registry.
+    schema: Option<Schema>,
+    registry: Option<SchemaRegistry>,
+}
+
+impl AvroDecoder {
+    pub fn new(options: &AvroOptions) -> Self {
+        Self {
+            schema: options
+                .schema
+                .as_deref()
+                .map(|schema_str| Schema::parse_str(schema_str).expect("validated in AvroOptions")),
+            registry: options.schema_registry_url.clone().map(SchemaRegistry::new),
+        }
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidFormat` when:
+    ///   - `payload` is not an Avro record of the schema.
+    ///   - a field cannot be mapped to SQL type (nested, for example).
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to get the writer schema from the registry.
+    pub fn decode(&mut self, payload: &[u8]) -> Result<ColumnValues> {
+        let value = match &mut self.registry {
+            Some(registry) => {
+                let (id, mut datum) = split_wire_format(payload)?;
+                let writer_schema = registry.schema(id)?;
+                apache_avro::from_avro_datum(writer_schema, &mut datum, self.schema.as_ref())
+            }
+            None => {
+                let schema = self.schema.as_ref().expect("validated in AvroOptions");
+                apache_avro::from_avro_datum(schema, &mut &payload[..], None)
+            }
+        }
+        .context("failed to decode Avro datum")
+        .map_err(|e| invalid_format(payload, e))?;
+
+        match value {
+            Value::Record(fields) => fields.into_iter().try_fold(
+                ColumnValues::default(),
+                |mut column_values, (name, value)| {
+                    let sql_value = to_sql_value(value)
+                        .with_context(|| format!("cannot read Avro field {}", name))
+                        .map_err(|e| invalid_format(payload, e))?;
+                    column_values.insert(ColumnName::new(name), sql_value)?;
+                    Ok(column_values)
+                },
+            ),
+            _ => Err(invalid_format(
+                payload,
+                anyhow!("Avro datum must be a record"),
+            )),
+        }
+    }
+}
+
+/// Encodes JSON rows into Avro records.
+///
+/// JSON values are resolved against the schema (e.g. numbers into `int` or `float`, and TIMESTAMP columns as `string`).
+#[derive(Debug)]
+pub struct AvroEncoder {
+    schema: Schema,
+    /// Set if the schema is registered.
+    schema_id: Option<u32>,
+}
+
+impl AvroEncoder {
+    /// Registers the schema if the registry is set.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to register the schema.
+    pub fn new(options: &AvroOptions) -> Result<Self> {
+        let schema = Schema::parse_str(options.schema.as_deref().expect("validated for sink"))
+            .expect("validated in AvroOptions");
+        let schema_id = match &options.schema_registry_url {
+            Some(url) => {
+                let subject = options.subject.as_deref().expect("validated for sink");
+                let id = SchemaRegistry::new(url.clone()).register(subject, &schema)?;
+                log::info!(
+                    "[AvroEncoder] Registered Avro schema under {} (ID: {})",
+                    subject,
+                    id
+                );
+                Some(id)
+            }
+            None => None,
+        };
+        Ok(Self { schema, schema_id })
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `json` does not match the schema.
+    pub fn encode(&self, json: JsonObject) -> Result<Vec<u8>> {
+        let json = serde_json::Value::from(json);
+        let value = Value::from(json.clone())
+            .resolve(&self.schema)
+            .and_then(|value| apache_avro::to_avro_datum(&self.schema, value))
+            .with_context(|| format!("row does not match Avro schema: {}", json))
+            .map_err(SpringError::Sql)?;
+
+        match self.schema_id {
+            Some(id) => {
+                let mut payload = Vec::with_capacity(5 + value.len());
+                payload.push(MAGIC_BYTE);
+                payload.extend_from_slice(&id.to_be_bytes());
+                payload.extend(value);
+                Ok(payload)
+            }
+            None => Ok(value),
+        }
+    }
+}
+
+fn split_wire_format(payload: &[u8]) -> Result<(u32, &[u8])> {
+    match payload {
+        [MAGIC_BYTE, b0, b1, b2, b3, datum @ ..] => {
+            Ok((u32::from_be_bytes([*b0, *b1, *b2, *b3]), datum))
+        }
+        _ => Err(invalid_format(
+            payload,
+            anyhow!("payload is not in Confluent wire format"),
+        )),
+    }
+}
+
+fn to_sql_value(value: Value) -> anyhow::Result<SqlValue> {
+    let nn_sql_value = match value {
+        Value::Null => return Ok(SqlValue::Null),
+        Value::Union(_, value) => return to_sql_value(*value),
+        Value::Boolean(b) => NnSqlValue::Boolean(b),
+        Value::Int(i) => NnSqlValue::Integer(i),
+        Value::Long(l) => NnSqlValue::BigInt(l),
+        Value::Float(f) => NnSqlValue::Float(OrderedFloat(f)),
+        Value::Double(d) => NnSqlValue::Float(OrderedFloat(d as f32)),
+        Value::String(s) | Value::Enum(_, s) => NnSqlValue::Text(s),
+        Value::Uuid(uuid) => NnSqlValue::Text(uuid.to_string()),
+        Value::Bytes(bytes) | Value::Fixed(_, bytes) => NnSqlValue::Blob(bytes),
+        Value::TimestampMillis(millis) => NnSqlValue::Timestamp(
+            SpringTimestamp::from_unix_timestamp_nanos(millis as i128 * 1_000_000)?,
+        ),
+        Value::TimestampMicros(micros) => NnSqlValue::Timestamp(
+            SpringTimestamp::from_unix_timestamp_nanos(micros as i128 * 1_000)?,
+        ),
+        other => return Err(anyhow!("unsupported Avro value: {:?}", other)),
+    };
+    Ok(SqlValue::NotNull(nn_sql_value))
+}
+
+fn invalid_format(payload: &[u8], e: anyhow::Error) -> SpringError {
+    SpringError::InvalidFormat {
+        s: String::from_utf8_lossy(payload).to_string(),
+        source: e,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = r#"{
+        "type": "record",
+        "name": "trade",
+        "fields": [
+            {"name": "ts", "type": "string"},
+            {"name": "ticker", "type": ["null", "string"]},
+            {"name": "amount", "type": "int"},
+            {"name": "price", "type": "double"}
+        ]
+    }"#;
+
+    fn avro_options() -> AvroOptions {
+        AvroOptions {
+            schema: Some(SCHEMA.to_string()),
+            schema_registry_url: None,
+            subject: None,
+        }
+    }
+
+    #[test]
+    fn test_avro_encode_decode() {
+        let encoder = AvroEncoder::new(&avro_options()).unwrap();
+        let payload = encoder
+            .encode(JsonObject::new(serde_json::json!({
+                "ts": "2020-01-01 00:00:00.000000000",
+                "ticker": "ORCL",
+                "amount": 20,
+                "price": 1.5
+            })))
+            .unwrap();
+
+        let mut decoder = AvroDecoder::new(&avro_options());
+        let mut column_values = decoder.decode(&payload).unwrap();
+        assert_eq!(
+            column_values
+                .remove(&ColumnName::new("ticker".to_string()))
+                .unwrap(),
+            SqlValue::NotNull(NnSqlValue::Text("ORCL".to_string()))
+        );
+        assert_eq!(
+            column_values
+                .remove(&ColumnName::new("amount".to_string()))
+                .unwrap(),
+            SqlValue::NotNull(NnSqlValue::Integer(20))
+        );
+        assert_eq!(
+            column_values
+                .remove(&ColumnName::new("price".to_string()))
+                .unwrap(),
+            SqlValue::NotNull(NnSqlValue::Float(OrderedFloat(1.5)))
+        );
+
+        // missing field
+        assert!(encoder
+            .encode(JsonObject::new(serde_json::json!({"ts": "x"})))
+            .is_err());
+        assert!(decoder.decode(&[0xff]).is_err());
+    }
+
+    #[test]
+    fn test_split_wire_format() {
+        assert_eq!(
+            split_wire_format(&[0, 0, 0, 1, 2, 42]).unwrap(),
+            (258, &[42][..])
+        );
+        assert!(split_wire_format(&[1, 0, 0, 1, 2, 42]).is_err());
+        assert!(split_wire_format(&[0, 0]).is_err());
+    }
+}
@@ -0,0 +1,356 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use anyhow::anyhow;
+use ordered_float::OrderedFloat;
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{ColumnName, CsvOptions},
+    stream_engine::autonomous_executor::row::{
+        column_values::ColumnValues,
+        value::{NnSqlValue, SqlValue},
+        SchemalessRow,
+    },
+};
+
+/// Field in a CSV record.
+#[derive(Clone, Eq, PartialEq, Debug)]
+struct CsvField {
+    value: String,
+    quoted: bool,
+}
+
+impl CsvField {
+    /// Unquoted fields are typed like JSON scalars: empty as NULL, integers as BIGINT, other numbers as FLOAT,
+    /// `true` / `false` as BOOLEAN, and others as TEXT. Quoted fields are always TEXT.
+    fn into_sql_value(self) -> SqlValue {
+        if self.quoted {
+            return SqlValue::NotNull(NnSqlValue::Text(self.value));
+        }
+
+        let s = self.value.trim();
+        if s.is_empty() {
+            SqlValue::Null
+        } else if let Ok(i) = s.parse::<i64>() {
+            SqlValue::NotNull(NnSqlValue::BigInt(i))
+        } else if let Ok(f) = s.parse::<f32>() {
+            SqlValue::NotNull(NnSqlValue::Float(OrderedFloat(f)))
+        } else if let Ok(b) = s.parse::<bool>() {
+            SqlValue::NotNull(NnSqlValue::Boolean(b))
+        } else {
+            SqlValue::NotNull(NnSqlValue::Text(self.value))
+        }
+    }
+}
+
+/// Parses CSV records (RFC 4180 with a configurable delimiter) into column values.
+///
+/// A parser keeps the header it read first, so a stream of CSV text can be parsed chunk by chunk.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct CsvParser {
+    options: CsvOptions,
+    columns: Option<Vec<ColumnName>>,
+    header_read: bool,
+}
+
+impl CsvParser {
+    pub fn new(options: &CsvOptions) -> Self {
+        Self {
+            options: options.clone(),
+            columns: options.columns.clone(),
+            header_read: false,
+        }
+    }
+
+    /// Parses records separated by newlines. Empty lines are ignored.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidFormat` when:
+    ///   - a quoted field is not closed in `text`.
+    ///   - a record does not have as many fields as columns.
+    pub fn parse(&mut self, text: &str) -> Result<Vec<ColumnValues>> {
+        let mut rows = Vec::new();
+        for record in self.split_records(text)? {
+            if self.options.header && !self.header_read {
+                self.header_read = true;
+                if self.columns.is_none() {
+                    let columns = record
+                        .into_iter()
+                        .map(|field| ColumnName::new(field.value.trim().to_string()))
+                        .collect();
+                    self.columns = Some(columns);
+                }
+                continue;
+            }
+
+            let columns = self
+                .columns
+                .as_ref()
+                .expect("CSV_COLUMNS or header must be provided");
+            if record.len() != columns.len() {
+                return Err(SpringError::InvalidFormat {
+                    s: text.to_string(),
+                    source: anyhow!(
+                        "CSV record has {} fields while {} columns are expected",
+                        record.len(),
+                        columns.len()
+                    ),
+                });
+            }
+
+            let mut column_values = ColumnValues::default();
+            for (column, field) in columns.iter().zip(record) {
+                column_values.insert(column.clone(), field.into_sql_value())?;
+            }
+            rows.push(column_values);
+        }
+        Ok(rows)
+    }
+
+    fn split_records(&self, text: &str) -> Result<Vec<Vec<CsvField>>> {
+        let mut records = Vec::new();
+        let mut record = Vec::new();
+        let mut field = CsvField {
+            value: String::new(),
+            quoted: false,
+        };
+        let mut in_quotes = false;
+
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            if in_quotes {
+                match c {
+                    '"' if chars.peek() == Some(&'"') => {
+                        let _ = chars.next();
+                        field.value.push('"');
+                    }
+                    '"' => in_quotes = false,
+                    _ => field.value.push(c),
+                }
+            } else if c == '"' && field.value.is_empty() && !field.quoted {
+                in_quotes = true;
+                field.quoted = true;
+            } else if c == self.options.delimiter {
+                record.push(std::mem::replace(
+                    &mut field,
+                    CsvField {
+                        value: String::new(),
+                        quoted: false,
+                    },
+                ));
+            } else if c == '\n' || c == '\r' {
+                if c == '\r' && chars.peek() == Some(&'\n') {
+                    let _ = chars.next();
+                }
+                Self::end_record(&mut records, &mut record, &mut field);
+            } else {
+                field.value.push(c);
+            }
+        }
+
+        if in_quotes {
+            return Err(SpringError::InvalidFormat {
+                s: text.to_string(),
+                source: anyhow!("quoted CSV field is not closed"),
+            });
+        }
+        Self::end_record(&mut records, &mut record, &mut field);
+        Ok(records)
+    }
+
+    /// Ends a record unless it is an empty line.
+    fn end_record(
+        records: &mut Vec<Vec<CsvField>>,
+        record: &mut Vec<CsvField>,
+        field: &mut CsvField,
+    ) {
+        let field = std::mem::replace(
+            field,
+            CsvField {
+                value: String::new(),
+                quoted: false,
+            },
+        );
+        if record.is_empty() && field.value.is_empty() && !field.quoted {
+            return;
+        }
+        record.push(field);
+        records.push(std::mem::take(record));
+    }
+}
+
+/// Writes rows as CSV records readable by `CsvParser`.
+///
+/// NULL is written as an empty field, and TEXT is always quoted not to be read back as numbers or booleans.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct CsvWriter {
+    delimiter: char,
+}
+
+impl CsvWriter {
+    pub fn new(delimiter: char) -> Self {
+        Self { delimiter }
+    }
+
+    /// Header record with column names of `row`, ending with a newline.
+    pub fn header(&self, row: &SchemalessRow) -> String {
+        let fields = row
+            .clone()
+            .into_iter()
+            .map(|(column, _)| self.field(column.as_ref(), false))
+            .collect::<Vec<_>>();
+        self.join(fields)
+    }
+
+    /// Record ending with a newline.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `row` has a BLOB value.
+    pub fn record(&self, row: SchemalessRow) -> Result<String> {
+        let fields = row
+            .into_iter()
+            .map(|(column, value)| match value {
+                SqlValue::Null => Ok(String::new()),
+                SqlValue::NotNull(NnSqlValue::Text(s)) => Ok(self.field(&s, true)),
+                SqlValue::NotNull(NnSqlValue::Boolean(b)) => Ok(b.to_string()),
+                SqlValue::NotNull(NnSqlValue::Blob(_)) => Err(SpringError::Sql(anyhow!(
+                    "cannot write BLOB column `{}` as CSV",
+                    column
+                ))),
+                SqlValue::NotNull(nn_sql_value) => Ok(self.field(&nn_sql_value.to_string(), false)),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(self.join(fields))
+    }
+
+    /// Quotes `s` if `quote` or it has special characters.
+    fn field(&self, s: &str, quote: bool) -> String {
+        if quote || s.contains(|c| c == self.delimiter || matches!(c, '"' | '\r' | '\n')) {
+            format!("\"{}\"", s.replace('"', "\"\""))
+        } else {
+            s.to_string()
+        }
+    }
+
+    fn join(&self, fields: Vec<String>) -> String {
+        let mut record = fields.join(&self.delimiter.to_string());
+        record.push('\n');
+        record
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn csv_options(header: bool, columns: Option<&[&str]>) -> CsvOptions {
+        CsvOptions {
+            header,
+            delimiter: ',',
+            columns: columns.map(|columns| {
+                columns
+                    .iter()
+                    .map(|column| ColumnName::new(column.to_string()))
+                    .collect()
+            }),
+        }
+    }
+
+    fn value(row: &ColumnValues, column: &str) -> SqlValue {
+        row.clone()
+            .remove(&ColumnName::new(column.to_string()))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_csv_parser_header() {
+        let mut parser = CsvParser::new(&csv_options(true, None));
+
+        // header in the first chunk
+        let rows = parser
+            .parse("ts,city,temperature,note\r\n2020-01-01 00:00:00.000000000,Tokyo,21,\"a, \"\"b\"\"\"\n")
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(
+            value(&rows[0], "city"),
+            SqlValue::NotNull(NnSqlValue::Text("Tokyo".to_string()))
+        );
+        assert_eq!(
+            value(&rows[0], "temperature"),
+            SqlValue::NotNull(NnSqlValue::BigInt(21))
+        );
+        assert_eq!(
+            value(&rows[0], "note"),
+            SqlValue::NotNull(NnSqlValue::Text("a, \"b\"".to_string()))
+        );
+
+        let rows = parser
+            .parse("\n2020-01-01 00:00:01.000000000,Osaka,,\"007\"\n")
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(value(&rows[0], "temperature"), SqlValue::Null);
+        assert_eq!(
+            value(&rows[0], "note"),
+            SqlValue::NotNull(NnSqlValue::Text("007".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_csv_parser_columns() {
+        let mut parser = CsvParser::new(&CsvOptions {
+            delimiter: ';',
+            ..csv_options(false, Some(&["ts", "temperature", "alert"]))
+        });
+        let rows = parser
+            .parse("2020-01-01 00:00:00.000000000;-3.5;true\n2020-01-01 00:00:01.000000000;1;false")
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            value(&rows[0], "temperature"),
+            SqlValue::NotNull(NnSqlValue::Float(OrderedFloat(-3.5)))
+        );
+        assert_eq!(
+            value(&rows[1], "alert"),
+            SqlValue::NotNull(NnSqlValue::Boolean(false))
+        );
+
+        assert!(parser.parse("2020-01-01 00:00:00.000000000;1").is_err());
+        assert!(parser
+            .parse("2020-01-01 00:00:00.000000000;1;\"true")
+            .is_err());
+    }
+
+    #[test]
+    fn test_csv_writer_round_trip() {
+        let mut row = SchemalessRow::default();
+        for (column, value) in [
+            (
+                "ticker",
+                SqlValue::NotNull(NnSqlValue::Text("a, \"b\"".to_string())),
+            ),
+            (
+                "code",
+                SqlValue::NotNull(NnSqlValue::Text("007".to_string())),
+            ),
+            ("amount", SqlValue::NotNull(NnSqlValue::BigInt(21))),
+            ("alert", SqlValue::NotNull(NnSqlValue::Boolean(true))),
+            ("note", SqlValue::Null),
+        ] {
+            row.insert(ColumnName::new(column.to_string()), value)
+                .unwrap();
+        }
+
+        let writer = CsvWriter::new(',');
+        let header = writer.header(&row);
+        assert_eq!(header, "ticker,code,amount,alert,note\n");
+        let record = writer.record(row.clone()).unwrap();
+        assert_eq!(record, "\"a, \"\"b\"\"\",\"007\",21,true,\n");
+
+        let mut parser = CsvParser::new(&csv_options(true, None));
+        let rows = parser.parse(&(header + &record)).unwrap();
+        assert_eq!(rows, vec![row.into_column_values()]);
+    }
+}
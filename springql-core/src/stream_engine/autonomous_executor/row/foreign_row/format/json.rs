@@ -1,10 +1,12 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use anyhow::Context;
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Context};
 
 use crate::{
     api::error::{Result, SpringError},
-    pipeline::ColumnName,
+    pipeline::{ColumnName, JsonPath},
     stream_engine::autonomous_executor::row::{column_values::ColumnValues, value::SqlValue},
 };
 
@@ -39,6 +41,51 @@ impl JsonObject {
         Ok(Self::new(json_v))
     }
 
+    /// Expands a payload into JSON objects, each of which is a row.
+    ///
+    /// The value selected by `records_path` (the root if None) can be:
+    ///
+    /// - a JSON object: a single row.
+    /// - a JSON array of objects: each element is a row.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidFormat` when:
+    ///   - `records_path` does not select any value.
+    ///   - Selected value is neither an object nor an array of objects.
+    pub fn into_records(self, records_path: Option<&JsonPath>) -> Result<Vec<Self>> {
+        let selected = match records_path {
+            Some(path) => path
+                .select(&self.0)
+                .ok_or_else(|| SpringError::InvalidFormat {
+                    s: self.0.to_string(),
+                    source: anyhow!("records path {:?} does not match", path),
+                })?,
+            None => &self.0,
+        };
+
+        match selected {
+            serde_json::Value::Object(_) => Ok(vec![Self::new(selected.clone())]),
+            serde_json::Value::Array(elements) => elements
+                .iter()
+                .map(|element| {
+                    if element.is_object() {
+                        Ok(Self::new(element.clone()))
+                    } else {
+                        Err(SpringError::InvalidFormat {
+                            s: element.to_string(),
+                            source: anyhow!("each element in records must be JSON object"),
+                        })
+                    }
+                })
+                .collect(),
+            _ => Err(SpringError::InvalidFormat {
+                s: selected.to_string(),
+                source: anyhow!("records must be JSON object or array of JSON objects"),
+            }),
+        }
+    }
+
     /// # Failure
     ///
     /// - `SpringError::InvalidFormat` when:
@@ -68,6 +115,22 @@ impl JsonObject {
             })
     }
 
+    /// Numbers at the top level as they are in JSON, since `into_column_values()` converts them into FLOAT.
+    pub fn numbers(&self) -> HashMap<String, serde_json::Number> {
+        self.0
+            .as_object()
+            .map(|top_object| {
+                top_object
+                    .iter()
+                    .filter_map(|(k, v)| match v {
+                        serde_json::Value::Number(n) => Some((k.clone(), n.clone())),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     fn to_column_value(
         json_key: &str,
         json_value: &serde_json::Value,
@@ -77,3 +140,53 @@ impl JsonObject {
         Ok((column_name, sql_value))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_into_records_object() {
+        let j = JsonObject::new(json!({"c": 1}));
+        assert_eq!(j.clone().into_records(None).unwrap(), vec![j]);
+    }
+
+    #[test]
+    fn test_into_records_array() {
+        let j = JsonObject::new(json!([{"c": 1}, {"c": 2}]));
+        assert_eq!(
+            j.into_records(None).unwrap(),
+            vec![
+                JsonObject::new(json!({"c": 1})),
+                JsonObject::new(json!({"c": 2}))
+            ]
+        );
+
+        let j = JsonObject::new(json!([{"c": 1}, 2]));
+        assert!(j.into_records(None).is_err());
+    }
+
+    #[test]
+    fn test_into_records_envelope() {
+        let path = JsonPath::from_str("$.records").unwrap();
+
+        let j = JsonObject::new(json!({"device": "d1", "records": [{"c": 1}, {"c": 2}]}));
+        assert_eq!(
+            j.into_records(Some(&path)).unwrap(),
+            vec![
+                JsonObject::new(json!({"c": 1})),
+                JsonObject::new(json!({"c": 2}))
+            ]
+        );
+
+        let j = JsonObject::new(json!({"records": []}));
+        assert!(j.into_records(Some(&path)).unwrap().is_empty());
+
+        let j = JsonObject::new(json!({"c": 1}));
+        assert!(j.into_records(Some(&path)).is_err());
+    }
+}
@@ -0,0 +1,447 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Binary row encoding of `SPRINGQL_LINK` source readers and sink writers.
+//!
+//! A connection is a sequence of frames, each of which is prefixed by its length (u32).
+//! All integers are big-endian.
+//!
+//! ```text
+//! schema frame: 0x01 | version (u8) | schema hash (u64) | n_columns (u16) | (name length (u16) | name (UTF-8))*
+//! row frame:    0x02 | schema hash (u64) | (type tag (u8) | value)*
+//! ```
+//!
+//! A schema frame is sent before the first row frame and whenever column names change.
+//! Row frames carry only values, in the column order of the schema with the same hash.
+
+use std::{
+    collections::HashMap,
+    io::{self, Read},
+    time::Duration,
+};
+
+use anyhow::{anyhow, ensure};
+use ordered_float::OrderedFloat;
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::ColumnName,
+    stream_engine::{
+        autonomous_executor::row::SchemalessRow,
+        time::{SpringDuration, SpringEventDuration, SpringTimestamp},
+        NnSqlValue, SqlValue,
+    },
+};
+
+const LINK_VERSION: u8 = 1;
+
+const SCHEMA_FRAME: u8 = 0x01;
+const ROW_FRAME: u8 = 0x02;
+
+/// Frames larger than this are rejected.
+const MAX_FRAME_LEN: u32 = 16 * 1024 * 1024;
+
+const TAG_NULL: u8 = 0;
+const TAG_SMALLINT: u8 = 1;
+const TAG_INTEGER: u8 = 2;
+const TAG_BIGINT: u8 = 3;
+const TAG_UNSIGNED_INTEGER: u8 = 4;
+const TAG_UNSIGNED_BIGINT: u8 = 5;
+const TAG_FLOAT: u8 = 6;
+const TAG_TEXT: u8 = 7;
+const TAG_BLOB: u8 = 8;
+const TAG_BOOLEAN: u8 = 9;
+const TAG_TIMESTAMP: u8 = 10;
+const TAG_DURATION: u8 = 11;
+
+/// FNV-1a hash of column names (each terminated by 0x00).
+fn schema_hash(column_names: &[ColumnName]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in column_names
+        .iter()
+        .flat_map(|column_name| column_name.as_ref().bytes().chain([0]))
+    {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+/// Encodes rows into frames. An encoder must be used for a single connection.
+#[derive(Debug, Default)]
+pub struct LinkEncoder {
+    /// Schema of the last row.
+    schema: Option<(u64, Vec<ColumnName>)>,
+}
+
+impl LinkEncoder {
+    /// Appends frames of `row` to `buf` (with a schema frame if its columns differ from the previous row's).
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - a column name or a value is too long to encode.
+    pub fn encode(&mut self, row: SchemalessRow, buf: &mut Vec<u8>) -> Result<()> {
+        let (column_names, values): (Vec<_>, Vec<_>) = row.into_iter().unzip();
+
+        let hash = match &self.schema {
+            Some((hash, last_column_names)) if last_column_names == &column_names => *hash,
+            _ => {
+                let hash = schema_hash(&column_names);
+                Self::write_frame(buf, |body| {
+                    body.push(SCHEMA_FRAME);
+                    body.push(LINK_VERSION);
+                    body.extend_from_slice(&hash.to_be_bytes());
+                    let n_columns = u16::try_from(column_names.len())
+                        .map_err(|_| anyhow!("too many columns"))?;
+                    body.extend_from_slice(&n_columns.to_be_bytes());
+                    for column_name in &column_names {
+                        let name = column_name.as_ref().as_bytes();
+                        let len = u16::try_from(name.len())
+                            .map_err(|_| anyhow!("too long column name: {}", column_name))?;
+                        body.extend_from_slice(&len.to_be_bytes());
+                        body.extend_from_slice(name);
+                    }
+                    Ok(())
+                })?;
+                self.schema = Some((hash, column_names));
+                hash
+            }
+        };
+
+        Self::write_frame(buf, |body| {
+            body.push(ROW_FRAME);
+            body.extend_from_slice(&hash.to_be_bytes());
+            values
+                .into_iter()
+                .try_for_each(|value| Self::write_value(body, value))
+        })
+    }
+
+    fn write_frame(
+        buf: &mut Vec<u8>,
+        write_body: impl FnOnce(&mut Vec<u8>) -> anyhow::Result<()>,
+    ) -> Result<()> {
+        let len_pos = buf.len();
+        buf.extend_from_slice(&[0; 4]);
+        write_body(buf).map_err(SpringError::Sql)?;
+
+        let len = (buf.len() - len_pos - 4) as u64;
+        if len > MAX_FRAME_LEN as u64 {
+            buf.truncate(len_pos);
+            return Err(SpringError::Sql(anyhow!(
+                "frame of {} bytes exceeds {} bytes",
+                len,
+                MAX_FRAME_LEN
+            )));
+        }
+        buf[len_pos..len_pos + 4].copy_from_slice(&(len as u32).to_be_bytes());
+        Ok(())
+    }
+
+    fn write_value(body: &mut Vec<u8>, value: SqlValue) -> anyhow::Result<()> {
+        let nn_sql_value = match value {
+            SqlValue::Null => {
+                body.push(TAG_NULL);
+                return Ok(());
+            }
+            SqlValue::NotNull(nn_sql_value) => nn_sql_value,
+        };
+        match nn_sql_value {
+            NnSqlValue::SmallInt(v) => {
+                body.push(TAG_SMALLINT);
+                body.extend_from_slice(&v.to_be_bytes());
+            }
+            NnSqlValue::Integer(v) => {
+                body.push(TAG_INTEGER);
+                body.extend_from_slice(&v.to_be_bytes());
+            }
+            NnSqlValue::BigInt(v) => {
+                body.push(TAG_BIGINT);
+                body.extend_from_slice(&v.to_be_bytes());
+            }
+            NnSqlValue::UnsignedInteger(v) => {
+                body.push(TAG_UNSIGNED_INTEGER);
+                body.extend_from_slice(&v.to_be_bytes());
+            }
+            NnSqlValue::UnsignedBigInt(v) => {
+                body.push(TAG_UNSIGNED_BIGINT);
+                body.extend_from_slice(&v.to_be_bytes());
+            }
+            NnSqlValue::Float(v) => {
+                body.push(TAG_FLOAT);
+                body.extend_from_slice(&v.into_inner().to_be_bytes());
+            }
+            NnSqlValue::Text(v) => {
+                body.push(TAG_TEXT);
+                Self::write_bytes(body, v.as_bytes())?;
+            }
+            NnSqlValue::Blob(v) => {
+                body.push(TAG_BLOB);
+                Self::write_bytes(body, &v)?;
+            }
+            NnSqlValue::Boolean(v) => {
+                body.push(TAG_BOOLEAN);
+                body.push(v as u8);
+            }
+            NnSqlValue::Timestamp(v) => {
+                let nanos = i64::try_from(v.unix_timestamp_nanos())
+                    .map_err(|_| anyhow!("timestamp out of range: {}", v.to_string()))?;
+                body.push(TAG_TIMESTAMP);
+                body.extend_from_slice(&nanos.to_be_bytes());
+            }
+            NnSqlValue::Duration(v) => {
+                let nanos = u64::try_from(v.as_std().as_nanos())
+                    .map_err(|_| anyhow!("duration out of range: {}", v))?;
+                body.push(TAG_DURATION);
+                body.extend_from_slice(&nanos.to_be_bytes());
+            }
+        }
+        Ok(())
+    }
+
+    fn write_bytes(body: &mut Vec<u8>, bytes: &[u8]) -> anyhow::Result<()> {
+        let len = u32::try_from(bytes.len()).map_err(|_| anyhow!("too long value"))?;
+        body.extend_from_slice(&len.to_be_bytes());
+        body.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+/// Decodes frames into rows. A decoder must be used for a single connection.
+#[derive(Debug, Default)]
+pub struct LinkDecoder {
+    /// schema hash -> column names
+    schemas: HashMap<u64, Vec<ColumnName>>,
+}
+
+impl LinkDecoder {
+    /// Reads a frame from `reader`.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(None)` on EOF at a frame boundary.
+    pub fn read_frame(reader: &mut impl Read) -> io::Result<Option<Vec<u8>>> {
+        let mut len = [0; 4];
+        match reader.read_exact(&mut len) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e),
+        }
+        let len = u32::from_be_bytes(len);
+        if len > MAX_FRAME_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("frame of {} bytes exceeds {} bytes", len, MAX_FRAME_LEN),
+            ));
+        }
+
+        let mut frame = vec![0; len as usize];
+        reader.read_exact(&mut frame)?;
+        Ok(Some(frame))
+    }
+
+    /// # Returns
+    ///
+    /// A row for a row frame, `None` for a schema frame.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidFormat` when:
+    ///   - `frame` is broken.
+    ///   - a row frame refers to a schema not received yet.
+    pub fn decode(&mut self, frame: &[u8]) -> Result<Option<SchemalessRow>> {
+        let mut cursor = FrameCursor(frame);
+        let decoded = match cursor.u8() {
+            Ok(SCHEMA_FRAME) => self.decode_schema(&mut cursor).map(|_| None),
+            Ok(ROW_FRAME) => self.decode_row(&mut cursor).map(Some),
+            Ok(kind) => Err(anyhow!("unknown frame kind: {:#04x}", kind)),
+            Err(e) => Err(e),
+        }
+        .and_then(|decoded| {
+            ensure!(cursor.0.is_empty(), "trailing bytes in frame");
+            Ok(decoded)
+        });
+
+        decoded.map_err(|e| SpringError::InvalidFormat {
+            s: format!("{:02x?}", frame),
+            source: e,
+        })
+    }
+
+    fn decode_schema(&mut self, cursor: &mut FrameCursor) -> anyhow::Result<()> {
+        let version = cursor.u8()?;
+        ensure!(
+            version == LINK_VERSION,
+            "unsupported SPRINGQL_LINK version: {}",
+            version
+        );
+        let hash = cursor.u64()?;
+        let n_columns = cursor.u16()?;
+        let column_names = (0..n_columns)
+            .map(|_| {
+                let len = cursor.u16()?;
+                let name = std::str::from_utf8(cursor.take(len as usize)?)?;
+                Ok(ColumnName::new(name.to_string()))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        ensure!(
+            schema_hash(&column_names) == hash,
+            "schema hash mismatch for {:?}",
+            column_names
+        );
+
+        self.schemas.insert(hash, column_names);
+        Ok(())
+    }
+
+    fn decode_row(&self, cursor: &mut FrameCursor) -> anyhow::Result<SchemalessRow> {
+        let hash = cursor.u64()?;
+        let column_names = self
+            .schemas
+            .get(&hash)
+            .ok_or_else(|| anyhow!("row frame of unknown schema hash: {:#018x}", hash))?;
+
+        let mut row = SchemalessRow::with_capacity(column_names.len());
+        for column_name in column_names {
+            let value = Self::decode_value(cursor)?;
+            row.insert(column_name.clone(), value)?;
+        }
+        Ok(row)
+    }
+
+    fn decode_value(cursor: &mut FrameCursor) -> anyhow::Result<SqlValue> {
+        let nn_sql_value = match cursor.u8()? {
+            TAG_NULL => return Ok(SqlValue::Null),
+            TAG_SMALLINT => NnSqlValue::SmallInt(i16::from_be_bytes(cursor.array()?)),
+            TAG_INTEGER => NnSqlValue::Integer(i32::from_be_bytes(cursor.array()?)),
+            TAG_BIGINT => NnSqlValue::BigInt(i64::from_be_bytes(cursor.array()?)),
+            TAG_UNSIGNED_INTEGER => {
+                NnSqlValue::UnsignedInteger(u32::from_be_bytes(cursor.array()?))
+            }
+            TAG_UNSIGNED_BIGINT => NnSqlValue::UnsignedBigInt(cursor.u64()?),
+            TAG_FLOAT => NnSqlValue::Float(OrderedFloat(f32::from_be_bytes(cursor.array()?))),
+            TAG_TEXT => NnSqlValue::Text(String::from_utf8(cursor.bytes()?.to_vec())?),
+            TAG_BLOB => NnSqlValue::Blob(cursor.bytes()?.to_vec()),
+            TAG_BOOLEAN => NnSqlValue::Boolean(cursor.u8()? != 0),
+            TAG_TIMESTAMP => NnSqlValue::Timestamp(SpringTimestamp::from_unix_timestamp_nanos(
+                i64::from_be_bytes(cursor.array()?) as i128,
+            )?),
+            TAG_DURATION => NnSqlValue::Duration(SpringEventDuration::from_std(
+                Duration::from_nanos(cursor.u64()?),
+            )),
+            tag => return Err(anyhow!("unknown type tag: {}", tag)),
+        };
+        Ok(SqlValue::NotNull(nn_sql_value))
+    }
+}
+
+struct FrameCursor<'a>(&'a [u8]);
+
+impl<'a> FrameCursor<'a> {
+    fn take(&mut self, n: usize) -> anyhow::Result<&'a [u8]> {
+        ensure!(self.0.len() >= n, "unexpected end of frame");
+        let (taken, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    fn array<const N: usize>(&mut self) -> anyhow::Result<[u8; N]> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn u8(&mut self) -> anyhow::Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+    fn u16(&mut self) -> anyhow::Result<u16> {
+        Ok(u16::from_be_bytes(self.array()?))
+    }
+    fn u64(&mut self) -> anyhow::Result<u64> {
+        Ok(u64::from_be_bytes(self.array()?))
+    }
+
+    /// Length-prefixed (u32) bytes.
+    fn bytes(&mut self) -> anyhow::Result<&'a [u8]> {
+        let len = u32::from_be_bytes(self.array()?);
+        self.take(len as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_all(decoder: &mut LinkDecoder, mut buf: &[u8]) -> Vec<SchemalessRow> {
+        let mut rows = vec![];
+        while let Some(frame) = LinkDecoder::read_frame(&mut buf).unwrap() {
+            rows.extend(decoder.decode(&frame).unwrap());
+        }
+        rows
+    }
+
+    #[test]
+    fn test_link_frame_round_trip() {
+        let mut row = SchemalessRow::with_capacity(4);
+        row.insert(
+            ColumnName::new("ts".to_string()),
+            SqlValue::NotNull(NnSqlValue::Timestamp(SpringTimestamp::fx_ts1())),
+        )
+        .unwrap();
+        row.insert(
+            ColumnName::new("speed".to_string()),
+            SqlValue::NotNull(NnSqlValue::Float(OrderedFloat(42.5))),
+        )
+        .unwrap();
+        row.insert(
+            ColumnName::new("payload".to_string()),
+            SqlValue::NotNull(NnSqlValue::Blob(vec![0x00, 0xff])),
+        )
+        .unwrap();
+        row.insert(ColumnName::new("note".to_string()), SqlValue::Null)
+            .unwrap();
+
+        let mut encoder = LinkEncoder::default();
+        let mut buf = vec![];
+        encoder.encode(row.clone(), &mut buf).unwrap();
+        let first_len = buf.len();
+        encoder.encode(row.clone(), &mut buf).unwrap();
+        assert!(
+            buf.len() - first_len < first_len,
+            "schema frame must not be resent"
+        );
+        encoder
+            .encode(SchemalessRow::fx_city_temperature_tokyo(), &mut buf)
+            .unwrap();
+
+        let rows = decode_all(&mut LinkDecoder::default(), &buf);
+        assert_eq!(rows.len(), 3);
+        for decoded in &rows[..2] {
+            for i_col in 0..3 {
+                assert_eq!(
+                    decoded.get_by_index(i_col).unwrap(),
+                    row.get_by_index(i_col).unwrap()
+                );
+            }
+            assert!(matches!(decoded.get_by_index(3).unwrap(), SqlValue::Null));
+        }
+        assert_eq!(rows[2], SchemalessRow::fx_city_temperature_tokyo());
+    }
+
+    #[test]
+    fn test_link_frame_unknown_schema() {
+        let mut buf = vec![];
+        LinkEncoder::default()
+            .encode(SchemalessRow::fx_city_temperature_tokyo(), &mut buf)
+            .unwrap();
+
+        let mut reader = buf.as_slice();
+        let _schema_frame = LinkDecoder::read_frame(&mut reader).unwrap().unwrap();
+        let row_frame = LinkDecoder::read_frame(&mut reader).unwrap().unwrap();
+
+        assert!(matches!(
+            LinkDecoder::default().decode(&row_frame).unwrap_err(),
+            SpringError::InvalidFormat { .. }
+        ));
+    }
+}
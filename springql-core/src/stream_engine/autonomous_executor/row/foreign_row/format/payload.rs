@@ -0,0 +1,132 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use anyhow::Context;
+
+#[cfg(feature = "avro")]
+use crate::stream_engine::autonomous_executor::row::foreign_row::format::{
+    AvroDecoder, AvroEncoder,
+};
+#[cfg(feature = "protobuf")]
+use crate::stream_engine::autonomous_executor::row::foreign_row::{
+    format::ProtobufDecoder, source_row::JsonSourceRow,
+};
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{CsvOptions, JsonPath, SinkFormatOptions, SourceFormatOptions},
+    stream_engine::autonomous_executor::row::foreign_row::{
+        format::{CsvParser, JsonObject},
+        source_row::SourceRow,
+    },
+};
+
+/// Decodes message payloads by `FORMAT` of a source reader, for sources delivering discrete messages.
+#[derive(Debug)]
+pub enum PayloadDecoder {
+    Json {
+        records_path: Option<JsonPath>,
+    },
+    /// Each message has its own header.
+    Csv(CsvOptions),
+    #[cfg(feature = "avro")]
+    Avro(AvroDecoder),
+    #[cfg(feature = "protobuf")]
+    Protobuf(ProtobufDecoder),
+}
+
+impl PayloadDecoder {
+    /// `json_records_path` is for JSON payloads.
+    ///
+    /// # Failure
+    ///
+    /// - Raises Err from `ProtobufDecoder::new()` as-is.
+    pub fn new(
+        format_options: &SourceFormatOptions,
+        json_records_path: Option<&JsonPath>,
+    ) -> Result<Self> {
+        let decoder = match format_options {
+            SourceFormatOptions::Json => Self::Json {
+                records_path: json_records_path.cloned(),
+            },
+            SourceFormatOptions::Csv(csv_options) => Self::Csv(csv_options.clone()),
+            #[cfg(feature = "avro")]
+            SourceFormatOptions::Avro(avro_options) => Self::Avro(AvroDecoder::new(avro_options)),
+            #[cfg(feature = "protobuf")]
+            SourceFormatOptions::Protobuf(protobuf_options) => {
+                Self::Protobuf(ProtobufDecoder::new(protobuf_options)?)
+            }
+        };
+        Ok(decoder)
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidFormat` when:
+    ///   - `payload` is malformed in the format.
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to get the Avro schema of `payload` from the registry.
+    pub fn decode(&mut self, payload: &[u8]) -> Result<Vec<SourceRow>> {
+        match self {
+            Self::Json { records_path } => {
+                SourceRow::from_json_records(utf8(payload)?, records_path.as_ref())
+            }
+            Self::Csv(csv_options) => {
+                SourceRow::from_csv_records(&mut CsvParser::new(csv_options), utf8(payload)?)
+            }
+            #[cfg(feature = "avro")]
+            Self::Avro(avro_decoder) => {
+                let column_values = avro_decoder.decode(payload)?;
+                Ok(vec![SourceRow::Raw(column_values.into())])
+            }
+            #[cfg(feature = "protobuf")]
+            Self::Protobuf(protobuf_decoder) => {
+                let json = protobuf_decoder.decode(payload)?;
+                Ok(vec![SourceRow::Json(JsonSourceRow::from_json(json))])
+            }
+        }
+    }
+}
+
+/// Encodes rows (shaped into JSON) by `FORMAT` of a sink writer, for sinks accepting binary messages.
+#[derive(Debug)]
+pub enum PayloadEncoder {
+    Json,
+    #[cfg(feature = "avro")]
+    Avro(AvroEncoder),
+}
+
+impl PayloadEncoder {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to register the Avro schema.
+    pub fn new(format_options: &SinkFormatOptions) -> Result<Self> {
+        match format_options {
+            SinkFormatOptions::Json => Ok(Self::Json),
+            #[cfg(feature = "avro")]
+            SinkFormatOptions::Avro(avro_options) => {
+                Ok(Self::Avro(AvroEncoder::new(avro_options)?))
+            }
+        }
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `json` does not match the Avro schema.
+    pub fn encode(&self, json: JsonObject) -> Result<Vec<u8>> {
+        match self {
+            Self::Json => Ok(json.to_string().into_bytes()),
+            #[cfg(feature = "avro")]
+            Self::Avro(avro_encoder) => avro_encoder.encode(json),
+        }
+    }
+}
+
+fn utf8(payload: &[u8]) -> Result<&str> {
+    std::str::from_utf8(payload)
+        .context("payload is not UTF-8")
+        .map_err(|e| SpringError::InvalidFormat {
+            source: e,
+            s: String::from_utf8_lossy(payload).to_string(),
+        })
+}
@@ -0,0 +1,174 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::fs;
+
+use anyhow::{anyhow, Context};
+use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor, SerializeOptions};
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    pipeline::ProtobufOptions,
+    stream_engine::autonomous_executor::row::foreign_row::format::JsonObject,
+};
+
+/// Decodes Protocol Buffers messages into JSON objects by the message descriptor.
+///
+/// The JSON mapping is the canonical one but with field names in `.proto`, 64-bit integers as numbers,
+/// and fields with default values, so that every field is a column.
+#[derive(Debug)]
+pub struct ProtobufDecoder {
+    message: MessageDescriptor,
+}
+
+impl ProtobufDecoder {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to read the descriptor file.
+    /// - `SpringError::InvalidOption` when:
+    ///   - the descriptor file is not a `FileDescriptorSet`.
+    ///   - `PROTO_MESSAGE` is not found in the descriptor file.
+    pub fn new(options: &ProtobufOptions) -> Result<Self> {
+        let descriptor_file = &options.descriptor_file;
+        let invalid_option = |source| SpringError::InvalidOption {
+            key: "PROTO_DESCRIPTOR_FILE".to_string(),
+            value: descriptor_file.display().to_string(),
+            source,
+        };
+
+        let bytes = fs::read(descriptor_file)
+            .context("failed to read descriptor file")
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::File(descriptor_file.clone()),
+            })?;
+        let pool = DescriptorPool::decode(bytes.as_slice())
+            .context("not a compiled FileDescriptorSet")
+            .map_err(invalid_option)?;
+
+        let message = pool.get_message_by_name(&options.message).ok_or_else(|| {
+            SpringError::InvalidOption {
+                key: "PROTO_MESSAGE".to_string(),
+                value: options.message.clone(),
+                source: anyhow!("message not found in {}", descriptor_file.display()),
+            }
+        })?;
+        Ok(Self { message })
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidFormat` when:
+    ///   - `payload` is not an encoded message of the descriptor.
+    pub fn decode(&self, payload: &[u8]) -> Result<JsonObject> {
+        let invalid_format = |e| SpringError::InvalidFormat {
+            s: format!("{:?}", payload),
+            source: e,
+        };
+
+        let message = DynamicMessage::decode(self.message.clone(), payload)
+            .with_context(|| format!("failed to decode {}", self.message.full_name()))
+            .map_err(invalid_format)?;
+
+        let serialize_options = SerializeOptions::new()
+            .use_proto_field_name(true)
+            .stringify_64_bit_integers(false)
+            .skip_default_fields(false);
+        let json = message
+            .serialize_with_options(serde_json::value::Serializer, &serialize_options)
+            .context("failed to map message into JSON")
+            .map_err(invalid_format)?;
+        Ok(JsonObject::new(json))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use prost::Message;
+    use prost_reflect::Value;
+    use prost_types::{
+        field_descriptor_proto::{Label, Type},
+        DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+    };
+
+    use super::*;
+
+    fn field(name: &str, number: i32, typ: Type) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number),
+            label: Some(Label::Optional as i32),
+            r#type: Some(typ as i32),
+            ..Default::default()
+        }
+    }
+
+    /// `message telemetry.Status { string vehicle = 1; int64 speed = 2; double temperature = 3; }`
+    fn descriptor_file() -> tempfile::NamedTempFile {
+        let file_descriptor_set = FileDescriptorSet {
+            file: vec![FileDescriptorProto {
+                name: Some("telemetry.proto".to_string()),
+                package: Some("telemetry".to_string()),
+                syntax: Some("proto3".to_string()),
+                message_type: vec![DescriptorProto {
+                    name: Some("Status".to_string()),
+                    field: vec![
+                        field("vehicle", 1, Type::String),
+                        field("speed", 2, Type::Int64),
+                        field("temperature", 3, Type::Double),
+                    ],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+        let file = tempfile::NamedTempFile::new().unwrap();
+        fs::write(file.path(), file_descriptor_set.encode_to_vec()).unwrap();
+        file
+    }
+
+    fn decoder(file: &tempfile::NamedTempFile, message: &str) -> Result<ProtobufDecoder> {
+        ProtobufDecoder::new(&ProtobufOptions {
+            descriptor_file: file.path().to_path_buf(),
+            message: message.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_decode() {
+        let file = descriptor_file();
+        let decoder = decoder(&file, "telemetry.Status").unwrap();
+
+        let mut message = DynamicMessage::new(decoder.message.clone());
+        message.set_field_by_name("vehicle", Value::String("car-1".to_string()));
+        message.set_field_by_name("speed", Value::I64(42));
+        let payload = message.encode_to_vec();
+
+        let json = serde_json::Value::from(decoder.decode(&payload).unwrap());
+        assert_eq!(
+            json,
+            serde_json::json!({"vehicle": "car-1", "speed": 42, "temperature": 0.0})
+        );
+
+        assert!(matches!(
+            decoder.decode(&[0xff, 0xff]),
+            Err(SpringError::InvalidFormat { .. })
+        ));
+    }
+
+    #[test]
+    fn test_new_fails() {
+        let file = descriptor_file();
+        assert!(matches!(
+            decoder(&file, "telemetry.Unknown"),
+            Err(SpringError::InvalidOption { .. })
+        ));
+
+        let not_descriptor = tempfile::NamedTempFile::new().unwrap();
+        fs::write(not_descriptor.path(), b"\xff\xff").unwrap();
+        assert!(matches!(
+            decoder(&not_descriptor, "telemetry.Status"),
+            Err(SpringError::InvalidOption { .. })
+        ));
+    }
+}
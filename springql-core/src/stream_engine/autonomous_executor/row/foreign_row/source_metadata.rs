@@ -0,0 +1,108 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::collections::HashMap;
+
+use crate::{
+    api::error::Result,
+    pipeline::SourceMetadataOptions,
+    stream_engine::{
+        autonomous_executor::row::schemaless_row::SchemalessRow, NnSqlValue, SqlValue,
+    },
+};
+
+/// Transport metadata attached to a payload by a foreign source (e.g. topic of a message broker).
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct SourceMetadata {
+    pub topic: Option<String>,
+    pub partition: Option<i32>,
+    pub key: Option<String>,
+    pub qos: Option<i16>,
+    pub headers: HashMap<String, String>,
+}
+
+impl SourceMetadata {
+    /// Inserts metadata into `row` as columns specified by `options`.
+    /// Metadata which the source does not have are inserted as NULL.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `row` already has a column to map metadata into.
+    pub fn insert_into(
+        &self,
+        options: &SourceMetadataOptions,
+        row: &mut SchemalessRow,
+    ) -> Result<()> {
+        fn value<T>(v: Option<T>, f: impl FnOnce(T) -> NnSqlValue) -> SqlValue {
+            v.map(|v| SqlValue::NotNull(f(v))).unwrap_or(SqlValue::Null)
+        }
+
+        if let Some(column) = &options.topic_column {
+            row.insert(column.clone(), value(self.topic.clone(), NnSqlValue::Text))?;
+        }
+        if let Some(column) = &options.partition_column {
+            row.insert(column.clone(), value(self.partition, NnSqlValue::Integer))?;
+        }
+        if let Some(column) = &options.key_column {
+            row.insert(column.clone(), value(self.key.clone(), NnSqlValue::Text))?;
+        }
+        if let Some(column) = &options.qos_column {
+            row.insert(column.clone(), value(self.qos, NnSqlValue::SmallInt))?;
+        }
+        for (header, column) in &options.header_columns {
+            row.insert(
+                column.clone(),
+                value(self.headers.get(header).cloned(), NnSqlValue::Text),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::{ColumnName, OptionsBuilder};
+
+    #[test]
+    fn test_insert_into() {
+        let options = SourceMetadataOptions::try_from(
+            &OptionsBuilder::default()
+                .add("MAP_TOPIC_TO", "topic")
+                .add("MAP_QOS_TO", "qos")
+                .add("MAP_HEADER_device_TO", "device")
+                .build(),
+        )
+        .unwrap();
+
+        let metadata = SourceMetadata {
+            topic: Some("sensors/1".to_string()),
+            headers: vec![("device".to_string(), "d1".to_string())]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        };
+
+        let mut row = SchemalessRow::default();
+        metadata.insert_into(&options, &mut row).unwrap();
+
+        assert_eq!(
+            row.get_by_column_name(&ColumnName::new("topic".to_string()))
+                .unwrap(),
+            &SqlValue::NotNull(NnSqlValue::Text("sensors/1".to_string()))
+        );
+        assert_eq!(
+            row.get_by_column_name(&ColumnName::new("qos".to_string()))
+                .unwrap(),
+            &SqlValue::Null
+        );
+        assert_eq!(
+            row.get_by_column_name(&ColumnName::new("device".to_string()))
+                .unwrap(),
+            &SqlValue::NotNull(NnSqlValue::Text("d1".to_string()))
+        );
+
+        // column already exists
+        assert!(metadata.insert_into(&options, &mut row).is_err());
+    }
+}
@@ -1,14 +1,23 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+#[cfg(feature = "source-can")]
 mod can_frame_source_row;
 mod json_source_row;
 
+#[cfg(feature = "source-can")]
 pub use can_frame_source_row::CANFrameSourceRow;
 pub use json_source_row::JsonSourceRow;
 
 use crate::{
     api::{error::Result, SpringError},
-    stream_engine::autonomous_executor::row::schemaless_row::SchemalessRow,
+    pipeline::{JsonPath, SourceMetadataOptions},
+    stream_engine::autonomous_executor::row::{
+        foreign_row::{
+            format::{CsvParser, JsonObject},
+            source_metadata::SourceMetadata,
+        },
+        schemaless_row::SchemalessRow,
+    },
 };
 
 /// Input row from foreign sources (retrieved from SourceReader).
@@ -17,6 +26,7 @@ use crate::{
 #[derive(Clone, PartialEq, Debug)]
 pub enum SourceRow {
     Json(JsonSourceRow),
+    #[cfg(feature = "source-can")]
     CANFrame(CANFrameSourceRow),
     Raw(SchemalessRow),
 }
@@ -30,6 +40,74 @@ impl SourceRow {
         let json_source_row = JsonSourceRow::parse(json)?;
         Ok(Self::Json(json_source_row))
     }
+
+    /// Expands a JSON payload which may have multiple rows.
+    ///
+    /// See `JsonObject::into_records()` for supported payloads.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidFormat` when:
+    ///   - `json` cannot be parsed as a JSON
+    ///   - `json` does not have rows at `records_path`
+    pub fn from_json_records(json: &str, records_path: Option<&JsonPath>) -> Result<Vec<Self>> {
+        let json_obj = JsonObject::parse(json)?;
+        Self::from_json_object_records(json_obj, records_path)
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidFormat` when:
+    ///   - `json_obj` does not have rows at `records_path`
+    pub fn from_json_object_records(
+        json_obj: JsonObject,
+        records_path: Option<&JsonPath>,
+    ) -> Result<Vec<Self>> {
+        let rows = json_obj
+            .into_records(records_path)?
+            .into_iter()
+            .map(|record| Self::Json(JsonSourceRow::from_json(record)))
+            .collect();
+        Ok(rows)
+    }
+
+    /// Expands CSV records. `parser` keeps the header for later chunks of the same source.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidFormat` when:
+    ///   - `csv` has a malformed record.
+    pub fn from_csv_records(parser: &mut CsvParser, csv: &str) -> Result<Vec<Self>> {
+        let rows = parser
+            .parse(csv)?
+            .into_iter()
+            .map(|column_values| Self::Raw(column_values.into()))
+            .collect();
+        Ok(rows)
+    }
+
+    /// Maps transport metadata into columns.
+    /// Returns `self` as-is if `options` do not map any metadata.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidFormat` when:
+    ///   - this row cannot be converted into columns.
+    /// - `SpringError::Sql` when:
+    ///   - this row already has a column to map metadata into.
+    pub fn with_metadata(
+        self,
+        metadata: &SourceMetadata,
+        options: &SourceMetadataOptions,
+    ) -> Result<Self> {
+        if options.is_empty() {
+            Ok(self)
+        } else {
+            let mut row = SchemalessRow::try_from(self)?;
+            metadata.insert_into(options, &mut row)?;
+            Ok(Self::Raw(row))
+        }
+    }
 }
 
 impl TryFrom<SourceRow> for SchemalessRow {
@@ -38,6 +116,7 @@ impl TryFrom<SourceRow> for SchemalessRow {
     fn try_from(row: SourceRow) -> Result<Self> {
         match row {
             SourceRow::Json(json_source_row) => json_source_row.into_schemaless_row(),
+            #[cfg(feature = "source-can")]
             SourceRow::CANFrame(can_frame_source_row) => can_frame_source_row.into_schemaless_row(),
             SourceRow::Raw(schemaless_row) => Ok(schemaless_row),
         }
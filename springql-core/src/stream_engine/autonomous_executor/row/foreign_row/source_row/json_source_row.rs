@@ -1,5 +1,7 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use std::collections::HashMap;
+
 use crate::{
     api::error::Result,
     stream_engine::autonomous_executor::row::{
@@ -23,6 +25,11 @@ impl JsonSourceRow {
         Self(json)
     }
 
+    /// See `JsonObject::numbers()`.
+    pub fn numbers(&self) -> HashMap<String, serde_json::Number> {
+        self.0.numbers()
+    }
+
     pub fn into_schemaless_row(self) -> Result<SchemalessRow> {
         // JsonSourceRow -> JsonObject -> ColumnValues -> SchemalessRow
         let column_values = self.0.into_column_values()?;
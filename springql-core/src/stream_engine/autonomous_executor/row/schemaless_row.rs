@@ -2,12 +2,14 @@
 
 use std::vec;
 
+use anyhow::anyhow;
+
 use crate::{
-    api::error::Result,
-    pipeline::ColumnName,
+    api::error::{Result, SpringError},
+    pipeline::{ColumnName, ColumnTemplate, JsonLayout, JsonShapeOptions},
     stream_engine::{
         autonomous_executor::{ColumnValues, JsonObject},
-        SqlValue, StreamRow,
+        NnSqlValue, SqlValue, StreamRow,
     },
 };
 
@@ -22,6 +24,22 @@ pub struct SchemalessRow {
 }
 
 impl SchemalessRow {
+    /// Row which can have `n_columns` columns without reallocation.
+    pub fn with_capacity(n_columns: usize) -> Self {
+        Self {
+            colvals: ColumnValues::with_capacity(n_columns),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.colvals.len()
+    }
+
+    /// Columns and their values in order.
+    pub fn iter(&self) -> impl Iterator<Item = (&ColumnName, &SqlValue)> {
+        self.colvals.iter()
+    }
+
     /// # Failure
     ///
     /// - `SpringError::Sql` when:
@@ -38,6 +56,14 @@ impl SchemalessRow {
         self.colvals.get_by_column_name(column_name)
     }
 
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Column name is not found
+    pub fn column_index(&self, column_name: &ColumnName) -> Result<usize> {
+        self.colvals.find_idx(column_name)
+    }
+
     /// # Failure
     ///
     /// - `SpringError::Sql` when:
@@ -48,6 +74,90 @@ impl SchemalessRow {
     pub fn into_column_values(self) -> ColumnValues {
         self.colvals
     }
+
+    /// Fills placeholders in `template` with values of this row. TEXT values are filled without quotes.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Column in a placeholder is not found
+    ///   - Column in a placeholder is NULL
+    pub fn render(&self, template: &ColumnTemplate) -> Result<String> {
+        template.render(|column_name| match self.get_by_column_name(column_name)? {
+            SqlValue::Null => Err(SpringError::Sql(anyhow!(
+                "cannot fill template placeholder with NULL column: {}",
+                column_name
+            ))),
+            SqlValue::NotNull(NnSqlValue::Text(s)) => Ok(s.clone()),
+            SqlValue::NotNull(nn_sql_value) => Ok(nn_sql_value.to_string()),
+        })
+    }
+
+    /// Converts into a JSON object shaped by `shape`.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - a field conflicts with another one (e.g. `pos` and `pos__lat` in NESTED layout).
+    ///   - a value cannot be converted into JSON (e.g. DURATION).
+    pub fn into_json(self, shape: &JsonShapeOptions) -> Result<JsonObject> {
+        let mut payload = serde_json::Map::new();
+        for (col, val) in self {
+            if !shape.include_nulls && matches!(val, SqlValue::Null) {
+                continue;
+            }
+            let field = shape
+                .renames
+                .get(&col)
+                .cloned()
+                .unwrap_or_else(|| col.to_string());
+            let path = match shape.layout {
+                JsonLayout::Flat => vec![field.as_str()],
+                JsonLayout::Nested => field.split(shape.nesting_separator.as_str()).collect(),
+            };
+            let value = serde_json::Value::try_from(val)?;
+            Self::insert_json_field(&mut payload, &path, value).map_err(|e| {
+                SpringError::Sql(e.context(format!(r#"cannot output column "{}""#, col)))
+            })?;
+        }
+
+        let json = match &shape.envelope {
+            Some(envelope) => {
+                let mut enveloped = serde_json::Map::new();
+                enveloped.insert("schema".to_string(), envelope.schema.clone().into());
+                if let Some(version) = &envelope.version {
+                    enveloped.insert("version".to_string(), version.clone().into());
+                }
+                enveloped.insert("payload".to_string(), payload.into());
+                serde_json::Value::from(enveloped)
+            }
+            None => serde_json::Value::from(payload),
+        };
+        Ok(JsonObject::new(json))
+    }
+
+    fn insert_json_field(
+        object: &mut serde_json::Map<String, serde_json::Value>,
+        path: &[&str],
+        value: serde_json::Value,
+    ) -> anyhow::Result<()> {
+        let (key, rest) = path.split_first().expect("field path must not be empty");
+        if rest.is_empty() {
+            if object.contains_key(*key) {
+                return Err(anyhow!(r#"field "{}" already exists"#, key));
+            }
+            object.insert(key.to_string(), value);
+            Ok(())
+        } else {
+            let child = object
+                .entry(key.to_string())
+                .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+            match child {
+                serde_json::Value::Object(child) => Self::insert_json_field(child, rest, value),
+                _ => Err(anyhow!(r#"field "{}" is not an object"#, key)),
+            }
+        }
+    }
 }
 
 impl From<StreamRow> for SchemalessRow {
@@ -72,14 +182,20 @@ impl IntoIterator for SchemalessRow {
     }
 }
 
-impl From<SchemalessRow> for JsonObject {
-    fn from(row: SchemalessRow) -> Self {
+/// # Failure
+///
+/// - `SpringError::Sql` when:
+///   - a value cannot be converted into JSON (e.g. DURATION).
+impl TryFrom<SchemalessRow> for JsonObject {
+    type Error = SpringError;
+
+    fn try_from(row: SchemalessRow) -> Result<Self> {
         let map = row
             .into_iter()
-            .map(|(col, val)| (col.to_string(), serde_json::Value::from(val)))
-            .collect::<serde_json::Map<String, serde_json::Value>>();
+            .map(|(col, val)| Ok((col.to_string(), serde_json::Value::try_from(val)?)))
+            .collect::<Result<serde_json::Map<String, serde_json::Value>>>()?;
         let v = serde_json::Value::from(map);
-        JsonObject::new(v)
+        Ok(JsonObject::new(v))
     }
 }
 
@@ -87,7 +203,7 @@ impl From<SchemalessRow> for JsonObject {
 mod tests {
     use serde_json::json;
 
-    use crate::stream_engine::time::SpringTimestamp;
+    use crate::{pipeline::JsonEnvelope, stream_engine::time::SpringTimestamp};
 
     use super::*;
 
@@ -101,6 +217,77 @@ mod tests {
             "temperature": 21
         }));
 
-        assert_eq!(JsonObject::from(row), json);
+        assert_eq!(JsonObject::try_from(row).unwrap(), json);
+    }
+
+    #[test]
+    fn test_into_json_shaped() {
+        let mut row = SchemalessRow::with_capacity(4);
+        row.insert(
+            ColumnName::new("ts".to_string()),
+            SqlValue::NotNull(NnSqlValue::Timestamp(SpringTimestamp::fx_ts1())),
+        )
+        .unwrap();
+        row.insert(
+            ColumnName::new("lat".to_string()),
+            SqlValue::NotNull(NnSqlValue::Integer(35_681_236)),
+        )
+        .unwrap();
+        row.insert(
+            ColumnName::new("pos__lng".to_string()),
+            SqlValue::NotNull(NnSqlValue::Integer(139_767_125)),
+        )
+        .unwrap();
+        row.insert(ColumnName::new("speed".to_string()), SqlValue::Null)
+            .unwrap();
+
+        assert_eq!(
+            row.clone().into_json(&JsonShapeOptions::default()).unwrap(),
+            JsonObject::try_from(row.clone()).unwrap()
+        );
+
+        let shape = JsonShapeOptions {
+            include_nulls: false,
+            layout: JsonLayout::Nested,
+            renames: [(ColumnName::new("lat".to_string()), "pos__lat".to_string())]
+                .into_iter()
+                .collect(),
+            envelope: Some(JsonEnvelope {
+                schema: "vehicle_position".to_string(),
+                version: Some("2".to_string()),
+            }),
+            ..JsonShapeOptions::default()
+        };
+        assert_eq!(
+            row.clone().into_json(&shape).unwrap(),
+            JsonObject::new(json!({
+                "schema": "vehicle_position",
+                "version": "2",
+                "payload": {
+                    "ts": SpringTimestamp::fx_ts1().to_string(),
+                    "pos": {"lat": 35_681_236, "lng": 139_767_125}
+                }
+            }))
+        );
+
+        let shape = JsonShapeOptions {
+            renames: [(ColumnName::new("lat".to_string()), "pos".to_string())]
+                .into_iter()
+                .collect(),
+            layout: JsonLayout::Nested,
+            ..JsonShapeOptions::default()
+        };
+        assert!(row.into_json(&shape).is_err());
+    }
+
+    #[test]
+    fn test_render() {
+        let row = SchemalessRow::fx_city_temperature_tokyo();
+
+        let template = "alerts/${city}".parse().unwrap();
+        assert_eq!(row.render(&template).unwrap(), "alerts/Tokyo");
+
+        let template = "alerts/${no_such_column}".parse().unwrap();
+        assert!(row.render(&template).is_err());
     }
 }
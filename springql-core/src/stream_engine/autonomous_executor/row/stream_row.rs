@@ -5,9 +5,11 @@ use std::{sync::Arc, vec};
 use crate::{
     api::error::Result,
     mem_size::MemSize,
-    pipeline::{ColumnName, StreamModel},
+    pipeline::{ColumnName, Pipeline, StreamModel, StreamName},
     stream_engine::{
-        autonomous_executor::{row::schemaless_row::SchemalessRow, ColumnValues, StreamColumns},
+        autonomous_executor::{
+            row::schemaless_row::SchemalessRow, snapshot::RowSnapshot, ColumnValues, StreamColumns,
+        },
         time::{SpringTimestamp, SystemTimestamp},
         RowTime, SqlValue,
     },
@@ -48,6 +50,39 @@ impl StreamRow {
         self.cols.stream_model()
     }
 
+    pub fn to_snapshot(&self) -> RowSnapshot {
+        RowSnapshot {
+            stream: self.stream_model().name().to_string(),
+            processing_time: self.processing_time,
+            columns: self
+                .cols
+                .clone()
+                .into_iter()
+                .map(|(column_name, value)| (column_name.to_string(), value))
+                .collect(),
+        }
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - the stream in `snapshot` is not in `pipeline`.
+    ///   - columns in `snapshot` do not match the stream.
+    pub fn from_snapshot(snapshot: RowSnapshot, pipeline: &Pipeline) -> Result<Self> {
+        let stream_model = pipeline.get_stream(&StreamName::new(snapshot.stream))?;
+
+        let mut column_values = ColumnValues::with_capacity(snapshot.columns.len());
+        for (column_name, value) in snapshot.columns {
+            column_values.insert(ColumnName::new(column_name), value)?;
+        }
+        let cols = StreamColumns::new(stream_model, column_values)?;
+
+        Ok(Self {
+            processing_time: snapshot.processing_time,
+            cols,
+        })
+    }
+
     /// ROWTIME. See: <https://docs.sqlstream.com/glossary/rowtime-gl/>
     ///
     /// ROWTIME is a:
@@ -103,6 +138,21 @@ mod tests {
 
     use super::*;
 
+    use crate::pipeline::PipelineVersion;
+
+    #[test]
+    fn test_snapshot() {
+        let mut pipeline = Pipeline::new(PipelineVersion::new());
+        pipeline
+            .add_stream(Arc::new(StreamModel::fx_city_temperature()))
+            .unwrap();
+        let row = StreamRow::fx_city_temperature_tokyo();
+
+        let restored = StreamRow::from_snapshot(row.to_snapshot(), &pipeline).unwrap();
+        assert_eq!(restored, row);
+        assert_eq!(restored.rowtime(), row.rowtime());
+    }
+
     #[test]
     fn test_partial_eq() {
         assert_eq!(
@@ -118,4 +168,13 @@ mod tests {
             StreamRow::fx_city_temperature_osaka()
         );
     }
+
+    #[test]
+    fn test_stored_size() {
+        let tokyo = StreamRow::fx_city_temperature_tokyo();
+        assert!(tokyo.stored_size() >= std::mem::size_of::<StreamRow>() + "Tokyo".len());
+
+        let london = StreamRow::fx_city_temperature_london();
+        assert!(london.stored_size() > tokyo.stored_size());
+    }
 }
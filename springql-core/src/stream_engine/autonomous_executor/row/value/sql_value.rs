@@ -1,11 +1,10 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 mod nn_sql_value;
-mod sql_compare_result;
 mod sql_value_hash_key;
 
 pub use nn_sql_value::NnSqlValue;
-pub use sql_compare_result::SqlCompareResult;
+pub use springql_expr::SqlCompareResult;
 pub use sql_value_hash_key::SqlValueHashKey;
 
 use std::{
@@ -16,6 +15,7 @@ use std::{
 
 use anyhow::anyhow;
 use ordered_float::OrderedFloat;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     api::error::{Result, SpringError},
@@ -61,7 +61,7 @@ use crate::{
 /// # Examples
 ///
 /// See: `test_sql_value_example()`.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum SqlValue {
     /// NULL value.
     Null,
@@ -209,11 +209,17 @@ impl TryFrom<&serde_json::Value> for SqlValue {
     }
 }
 
-impl From<SqlValue> for serde_json::Value {
-    fn from(sql_value: SqlValue) -> Self {
+/// # Failure
+///
+/// - `SpringError::Sql` when:
+///   - `sql_value` cannot be converted into JSON (see `NnSqlValue`).
+impl TryFrom<SqlValue> for serde_json::Value {
+    type Error = SpringError;
+
+    fn try_from(sql_value: SqlValue) -> Result<Self> {
         match sql_value {
-            SqlValue::Null => serde_json::Value::Null,
-            SqlValue::NotNull(nn_sql_value) => serde_json::Value::from(nn_sql_value),
+            SqlValue::Null => Ok(serde_json::Value::Null),
+            SqlValue::NotNull(nn_sql_value) => serde_json::Value::try_from(nn_sql_value),
         }
     }
 }
@@ -3,12 +3,12 @@
 use std::{
     fmt::Display,
     hash::Hash,
-    mem::size_of,
     ops::{Add, Mul},
 };
 
 use anyhow::anyhow;
 use ordered_float::OrderedFloat;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     api::error::{Result, SpringError},
@@ -19,15 +19,15 @@ use crate::{
     },
     stream_engine::{
         autonomous_executor::row::value::{
-            sql_convertible::ToNnSqlValue, sql_value::sql_compare_result::SqlCompareResult,
+            sql_convertible::ToNnSqlValue, sql_value::SqlCompareResult,
         },
-        time::{SpringEventDuration, SpringTimestamp},
+        time::{SpringDuration, SpringEventDuration, SpringTimestamp},
         SpringValue,
     },
 };
 
 /// NOT NULL value.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum NnSqlValue {
     /// SMALLINT
     SmallInt(i16),
@@ -66,23 +66,20 @@ pub enum NnSqlValue {
 impl MemSize for NnSqlValue {
     fn mem_size(&self) -> usize {
         match self {
-            NnSqlValue::SmallInt(_) => size_of::<i16>(),
-            NnSqlValue::Integer(_) => size_of::<i32>(),
-            NnSqlValue::BigInt(_) => size_of::<i64>(),
-
-            NnSqlValue::UnsignedInteger(_) => size_of::<u32>(),
-            NnSqlValue::UnsignedBigInt(_) => size_of::<u64>(),
-
-            NnSqlValue::Float(_) => size_of::<f32>(),
-
             NnSqlValue::Text(s) => s.capacity(),
             NnSqlValue::Blob(v) => v.capacity(),
 
-            NnSqlValue::Boolean(_) => size_of::<bool>(),
-
             NnSqlValue::Timestamp(ts) => ts.mem_size(),
-
             NnSqlValue::Duration(dur) => dur.mem_size(),
+
+            // stored inline
+            NnSqlValue::SmallInt(_)
+            | NnSqlValue::Integer(_)
+            | NnSqlValue::BigInt(_)
+            | NnSqlValue::UnsignedInteger(_)
+            | NnSqlValue::UnsignedBigInt(_)
+            | NnSqlValue::Float(_)
+            | NnSqlValue::Boolean(_) => 0,
         }
     }
 }
@@ -301,6 +298,10 @@ impl NnSqlValue {
                     }
                 }
             }
+            (SqlType::BinaryComparable, SqlType::BinaryComparable) => {
+                let (self_v, other_v) = (self.unpack::<Vec<u8>>()?, other.unpack::<Vec<u8>>()?);
+                Ok(SqlCompareResult::from(self_v.cmp(&other_v)))
+            }
             (SqlType::BooleanComparable, SqlType::BooleanComparable) => {
                 let (self_b, other_b) = (self.unpack::<bool>()?, other.unpack::<bool>()?);
                 Ok(SqlCompareResult::from(self_b.cmp(&other_b)))
@@ -312,6 +313,13 @@ impl NnSqlValue {
                 );
                 Ok(SqlCompareResult::from(self_t.cmp(&other_t)))
             }
+            (SqlType::DurationComparable, SqlType::DurationComparable) => {
+                let (self_d, other_d) = (
+                    self.unpack::<SpringEventDuration>()?,
+                    other.unpack::<SpringEventDuration>()?,
+                );
+                Ok(SqlCompareResult::from(self_d.cmp(&other_d)))
+            }
             (_, _) => Err(SpringError::Sql(anyhow!(
                 "`self` and `other` are not in comparable type - self: {:?}, other: {:?}",
                 self,
@@ -342,22 +350,31 @@ impl NnSqlValue {
     }
 }
 
-impl From<NnSqlValue> for serde_json::Value {
-    fn from(nn_sql_value: NnSqlValue) -> Self {
+/// BLOB is converted into an array of bytes.
+///
+/// # Failure
+///
+/// - `SpringError::Sql` when:
+///   - `nn_sql_value` is a DURATION (just an intermediate type, not in stream definitions).
+impl TryFrom<NnSqlValue> for serde_json::Value {
+    type Error = SpringError;
+
+    fn try_from(nn_sql_value: NnSqlValue) -> Result<Self> {
         match nn_sql_value {
-            NnSqlValue::SmallInt(i) => serde_json::Value::from(i),
-            NnSqlValue::Integer(i) => serde_json::Value::from(i),
-            NnSqlValue::BigInt(i) => serde_json::Value::from(i),
-            NnSqlValue::UnsignedInteger(u) => serde_json::Value::from(u),
-            NnSqlValue::UnsignedBigInt(u) => serde_json::Value::from(u),
-            NnSqlValue::Float(f) => serde_json::Value::from(f.into_inner()),
-            NnSqlValue::Text(s) => serde_json::Value::from(s),
-            NnSqlValue::Boolean(b) => serde_json::Value::from(b),
-            NnSqlValue::Timestamp(t) => serde_json::Value::from(t.to_string()),
-            NnSqlValue::Duration(_) => {
-                unimplemented!("never appear in stream definition (just an intermediate type)")
-            }
-            NnSqlValue::Blob(_) => unimplemented!("cannot convert BLOB data into JSON"),
+            NnSqlValue::SmallInt(i) => Ok(serde_json::Value::from(i)),
+            NnSqlValue::Integer(i) => Ok(serde_json::Value::from(i)),
+            NnSqlValue::BigInt(i) => Ok(serde_json::Value::from(i)),
+            NnSqlValue::UnsignedInteger(u) => Ok(serde_json::Value::from(u)),
+            NnSqlValue::UnsignedBigInt(u) => Ok(serde_json::Value::from(u)),
+            NnSqlValue::Float(f) => Ok(serde_json::Value::from(f.into_inner())),
+            NnSqlValue::Text(s) => Ok(serde_json::Value::from(s)),
+            NnSqlValue::Boolean(b) => Ok(serde_json::Value::from(b)),
+            NnSqlValue::Timestamp(t) => Ok(serde_json::Value::from(t.to_string())),
+            NnSqlValue::Duration(_) => Err(SpringError::Sql(anyhow!(
+                "{} cannot be converted into JSON",
+                nn_sql_value
+            ))),
+            NnSqlValue::Blob(v) => Ok(serde_json::Value::from(v)),
         }
     }
 }
@@ -366,6 +383,13 @@ impl Add for NnSqlValue {
     type Output = Result<Self>;
 
     fn add(self, rhs: Self) -> Self::Output {
+        match (&self, &rhs) {
+            (Self::Timestamp(ts), Self::Duration(d)) | (Self::Duration(d), Self::Timestamp(ts)) => {
+                return Ok(Self::Timestamp(*ts + d.to_duration()));
+            }
+            _ => {}
+        }
+
         match (self.sql_type(), rhs.sql_type()) {
             (SqlType::NumericComparable(ref self_n), SqlType::NumericComparable(ref rhs_n)) => {
                 match (self_n, rhs_n) {
@@ -433,7 +457,50 @@ impl Mul for NnSqlValue {
 
 #[cfg(test)]
 mod tests {
+    use std::{collections::hash_map::DefaultHasher, hash::Hasher};
+
+    use proptest::prelude::*;
+
     use super::*;
+    use crate::stream_engine::autonomous_executor::test_support::strategy::nn_sql_value;
+
+    fn hash(v: &NnSqlValue) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        v.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    proptest! {
+        #[test]
+        fn test_sql_compare_antisymmetric(v1 in nn_sql_value(), v2 in nn_sql_value()) {
+            let expected = match v1.sql_compare(&v2) {
+                Ok(SqlCompareResult::LessThan) => Ok(SqlCompareResult::GreaterThan),
+                Ok(SqlCompareResult::GreaterThan) => Ok(SqlCompareResult::LessThan),
+                Ok(res) => Ok(res),
+                Err(_) => Err(()),
+            };
+            prop_assert_eq!(v2.sql_compare(&v1).map_err(|_| ()), expected);
+        }
+
+        #[test]
+        fn test_eq_reflexive(v in nn_sql_value()) {
+            prop_assert_eq!(&v, &v);
+            prop_assert_eq!(hash(&v), hash(&v.clone()));
+        }
+
+        #[test]
+        fn test_loosely_eq_integers_have_eq_hash(i in any::<i16>()) {
+            let values = [
+                NnSqlValue::SmallInt(i),
+                NnSqlValue::Integer(i as i32),
+                NnSqlValue::BigInt(i as i64),
+            ];
+            for v in &values {
+                prop_assert_eq!(v, &values[0]);
+                prop_assert_eq!(hash(v), hash(&values[0]));
+            }
+        }
+    }
 
     #[test]
     fn test_unpack_loosely() -> Result<()> {
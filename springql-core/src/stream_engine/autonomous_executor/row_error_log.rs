@@ -0,0 +1,116 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Logging of errors in processing rows, sampled by `[row_error]` config so that a bad sensor does not flood logs.
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+
+use crate::api::{error::SpringError, SpringRowErrorConfig};
+
+/// Error in processing a row. The row is skipped.
+#[derive(Debug, new)]
+pub struct RowError {
+    /// Part of the query where the error occurred (e.g. `WHERE`, `SELECT #2`).
+    pub field: String,
+    pub error: SpringError,
+}
+
+/// Errors logged in the current interval, per (pump, error code).
+#[derive(Debug)]
+struct Sampler {
+    interval_started_at: Instant,
+    logged: u32,
+    /// Errors not logged since the last log.
+    suppressed: u64,
+}
+
+#[derive(Debug)]
+pub struct RowErrorLogger {
+    log_burst: u32,
+    log_interval: Duration,
+    samplers: Mutex<HashMap<(String, &'static str), Sampler>>,
+}
+
+impl RowErrorLogger {
+    pub fn new(config: SpringRowErrorConfig) -> Self {
+        Self {
+            log_burst: config.log_burst,
+            log_interval: Duration::from_millis(config.log_interval_msec as u64),
+            samplers: Mutex::default(),
+        }
+    }
+
+    /// Logs a structured record of `row_error` in `pump` reading from `streams`, unless sampled out.
+    pub fn log(&self, pump: &str, streams: &str, row_error: &RowError) {
+        let code = row_error.error.code();
+        if let Some(suppressed) = self.sample(pump, code, Instant::now()) {
+            log::warn!(
+                "[RowError] stream={} pump={} code={} field={:?} suppressed={} error={:?}",
+                streams,
+                pump,
+                code,
+                row_error.field,
+                suppressed,
+                row_error.error
+            );
+        }
+    }
+
+    /// # Returns
+    ///
+    /// `Some(errors not logged since the last log)` if an error of `code` in `pump` at `now` should be logged.
+    fn sample(&self, pump: &str, code: &'static str, now: Instant) -> Option<u64> {
+        let mut samplers = self.samplers.lock();
+        let sampler = samplers
+            .entry((pump.to_string(), code))
+            .or_insert_with(|| Sampler {
+                interval_started_at: now,
+                logged: 0,
+                suppressed: 0,
+            });
+
+        if now.duration_since(sampler.interval_started_at) >= self.log_interval {
+            sampler.interval_started_at = now;
+            sampler.logged = 0;
+        }
+        if sampler.logged < self.log_burst {
+            sampler.logged += 1;
+            Some(std::mem::take(&mut sampler.suppressed))
+        } else {
+            sampler.suppressed += 1;
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample() {
+        let logger = RowErrorLogger::new(SpringRowErrorConfig {
+            log_burst: 2,
+            log_interval_msec: 1_000,
+        });
+        let t0 = Instant::now();
+
+        assert_eq!(logger.sample("pump_a", "SQL", t0), Some(0));
+        assert_eq!(logger.sample("pump_a", "SQL", t0), Some(0));
+        assert_eq!(logger.sample("pump_a", "SQL", t0), None);
+        assert_eq!(logger.sample("pump_a", "SQL", t0), None);
+
+        // other pumps and codes are sampled separately
+        assert_eq!(logger.sample("pump_b", "SQL", t0), Some(0));
+        assert_eq!(logger.sample("pump_a", "NULL", t0), Some(0));
+
+        let t1 = t0 + Duration::from_millis(1_000);
+        assert_eq!(logger.sample("pump_a", "SQL", t1), Some(2));
+        assert_eq!(logger.sample("pump_a", "SQL", t1), Some(0));
+        assert_eq!(logger.sample("pump_a", "SQL", t1), None);
+    }
+}
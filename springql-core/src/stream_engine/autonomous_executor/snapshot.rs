@@ -0,0 +1,55 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Portable state of queues and windows, restored into a pipeline made from the same statements.
+//!
+//! Queues, windows and streams are identified by names so that a snapshot can be restored in another process.
+
+use serde::{Deserialize, Serialize};
+
+use crate::stream_engine::{
+    autonomous_executor::task::AggregateState, time::SpringTimestamp, NnSqlValue, SqlValue,
+};
+
+/// Join windows and interval joins are not included. They restart with empty panes.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct ExecutorSnapshot {
+    pub row_queues: Vec<QueueSnapshot>,
+    pub window_queues: Vec<QueueSnapshot>,
+    pub aggr_windows: Vec<AggrWindowSnapshot>,
+}
+
+/// Rows in a row queue or a window queue, in the order to be used.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct QueueSnapshot {
+    pub queue: String,
+    pub priority_rows: Vec<RowSnapshot>,
+    pub rows: Vec<RowSnapshot>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct RowSnapshot {
+    pub stream: String,
+    /// Kept as-is so that a restored row has the same ROWTIME.
+    pub processing_time: Option<SpringTimestamp>,
+    /// (column name, value)
+    pub columns: Vec<(String, SqlValue)>,
+}
+
+/// State of the window of a pump with aggregation.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct AggrWindowSnapshot {
+    pub pump: String,
+    /// Watermark is restored from the max ROWTIME.
+    pub max_rowtime: SpringTimestamp,
+    pub panes: Vec<AggrPaneSnapshot>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct AggrPaneSnapshot {
+    pub open_at: SpringTimestamp,
+    pub close_at: SpringTimestamp,
+    /// `Some` if the pane is already closed and kept for allowed lateness. `true` if late rows are dispatched after the last close.
+    pub fired: Option<bool>,
+    /// (GROUP BY values, states in the order of aggregate expressions)
+    pub groups: Vec<(Vec<NnSqlValue>, Vec<AggregateState>)>,
+}
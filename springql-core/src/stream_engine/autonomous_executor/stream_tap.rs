@@ -0,0 +1,141 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    collections::VecDeque,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use parking_lot::{Mutex, RwLock};
+
+use crate::{
+    pipeline::StreamName,
+    stream_engine::autonomous_executor::row::{SchemalessRow, StreamRow},
+};
+
+/// Bounded queue receiving copies of rows traversing a stream until it expires.
+///
+/// Rows are dropped while the queue is full.
+#[derive(Debug)]
+pub struct StreamTap {
+    stream: StreamName,
+    capacity: usize,
+    expires_at: Instant,
+    rows: Mutex<VecDeque<SchemalessRow>>,
+}
+
+impl StreamTap {
+    pub fn new(stream: StreamName, capacity: usize, ttl: Duration) -> Self {
+        Self {
+            stream,
+            capacity,
+            expires_at: Instant::now() + ttl,
+            rows: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        Instant::now() >= self.expires_at
+    }
+
+    pub fn pop_non_blocking(&self) -> Option<SchemalessRow> {
+        self.rows.lock().pop_front()
+    }
+
+    fn put(&self, row: &StreamRow) {
+        let mut rows = self.rows.lock();
+        if rows.len() < self.capacity {
+            rows.push_back(SchemalessRow::from(row.clone()));
+        }
+    }
+}
+
+/// Live stream taps. Expired taps and taps no one holds are removed lazily.
+#[derive(Debug, Default)]
+pub struct StreamTapRepository {
+    taps: RwLock<Vec<Arc<StreamTap>>>,
+}
+
+impl StreamTapRepository {
+    pub fn register(&self, tap: Arc<StreamTap>) {
+        self.taps.write().push(tap);
+    }
+
+    /// Copies `rows` traversing `stream` into its taps.
+    pub fn put<'a>(&self, stream: &StreamName, rows: impl Iterator<Item = &'a StreamRow> + Clone) {
+        let mut has_stale = false;
+        {
+            let taps = self.taps.read();
+            if taps.is_empty() {
+                return;
+            }
+
+            for tap in taps.iter() {
+                if tap.is_expired() || Arc::strong_count(tap) == 1 {
+                    has_stale = true;
+                } else if &tap.stream == stream {
+                    rows.clone().for_each(|row| tap.put(row));
+                }
+            }
+        }
+
+        if has_stale {
+            self.taps
+                .write()
+                .retain(|tap| !tap.is_expired() && Arc::strong_count(tap) > 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_tap() {
+        let repo = StreamTapRepository::default();
+        let tap = Arc::new(StreamTap::new(
+            StreamName::fx_city_temperature(),
+            2,
+            Duration::from_secs(60),
+        ));
+        repo.register(tap.clone());
+
+        let rows = vec![
+            StreamRow::fx_city_temperature_tokyo(),
+            StreamRow::fx_city_temperature_osaka(),
+            StreamRow::fx_city_temperature_london(),
+        ];
+        repo.put(&StreamName::fx_trade(), rows.iter());
+        assert!(tap.pop_non_blocking().is_none());
+
+        // bounded by capacity
+        repo.put(&StreamName::fx_city_temperature(), rows.iter());
+        assert_eq!(
+            tap.pop_non_blocking(),
+            Some(SchemalessRow::from(StreamRow::fx_city_temperature_tokyo()))
+        );
+        assert_eq!(
+            tap.pop_non_blocking(),
+            Some(SchemalessRow::from(StreamRow::fx_city_temperature_osaka()))
+        );
+        assert!(tap.pop_non_blocking().is_none());
+    }
+
+    #[test]
+    fn test_stream_tap_expired() {
+        let repo = StreamTapRepository::default();
+        let tap = Arc::new(StreamTap::new(
+            StreamName::fx_city_temperature(),
+            2,
+            Duration::from_secs(0),
+        ));
+        repo.register(tap.clone());
+        assert!(tap.is_expired());
+
+        let rows = vec![StreamRow::fx_city_temperature_tokyo()];
+        repo.put(&StreamName::fx_city_temperature(), rows.iter());
+        assert!(tap.pop_non_blocking().is_none());
+        assert!(repo.taps.read().is_empty());
+    }
+}
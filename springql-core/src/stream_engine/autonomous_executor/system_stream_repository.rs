@@ -0,0 +1,222 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
+
+use parking_lot::{Mutex, RwLock};
+
+use ordered_float::OrderedFloat;
+
+use crate::{
+    api::SpringSystemStreamConfig,
+    pipeline::{ColumnName, SystemStream},
+    stream_engine::{
+        autonomous_executor::{
+            performance_metrics::WindowTriggers, row::SchemalessRow, row_error_log::RowError,
+        },
+        time::SystemTimestamp,
+        NnSqlValue, SqlValue,
+    },
+};
+
+/// Bounded queue of rows put into a system stream by the stream engine.
+///
+/// Rows are dropped while the queue is full.
+#[derive(Debug)]
+pub struct SystemStreamQueue {
+    capacity: usize,
+    rows: Mutex<VecDeque<SchemalessRow>>,
+}
+
+impl SystemStreamQueue {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            rows: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub fn pop_non_blocking(&self) -> Option<SchemalessRow> {
+        self.rows.lock().pop_front()
+    }
+
+    fn put(&self, row: SchemalessRow) {
+        let mut rows = self.rows.lock();
+        if rows.len() < self.capacity {
+            rows.push_back(row);
+        }
+    }
+}
+
+/// Queues of system streams read by source readers.
+///
+/// Rows put into a system stream without a reader are dropped.
+#[derive(Debug)]
+pub struct SystemStreamRepository {
+    capacity: usize,
+    queues: RwLock<HashMap<SystemStream, Arc<SystemStreamQueue>>>,
+}
+
+impl SystemStreamRepository {
+    pub fn new(config: &SpringSystemStreamConfig) -> Self {
+        Self {
+            capacity: config.capacity as usize,
+            queues: RwLock::default(),
+        }
+    }
+
+    /// Starts collecting rows for `system_stream`. Returns the existing queue if already started.
+    pub fn open(&self, system_stream: SystemStream) -> Arc<SystemStreamQueue> {
+        self.queues
+            .write()
+            .entry(system_stream)
+            .or_insert_with(|| Arc::new(SystemStreamQueue::new(self.capacity)))
+            .clone()
+    }
+
+    pub fn is_open(&self, system_stream: SystemStream) -> bool {
+        self.queues.read().contains_key(&system_stream)
+    }
+
+    pub fn put(&self, system_stream: SystemStream, row: SchemalessRow) {
+        if let Some(queue) = self.queues.read().get(&system_stream) {
+            queue.put(row);
+        }
+    }
+
+    /// Puts a row into `spring._events`.
+    pub fn put_event(&self, event_type: &str, detail: String) {
+        if self.is_open(SystemStream::Events) {
+            let row = Self::row(vec![
+                ("event_type", NnSqlValue::Text(event_type.to_string())),
+                ("detail", NnSqlValue::Text(detail)),
+            ]);
+            self.put(SystemStream::Events, row);
+        }
+    }
+
+    /// Puts a row into `spring._task_metrics`.
+    ///
+    /// `tags` is formatted as `key1=value1,key2=value2` (empty if the task has no tag).
+    pub fn put_task_metrics(
+        &self,
+        task_id: String,
+        num_input_rows: u64,
+        avg_gain_bytes_per_sec: f32,
+        window_triggers: WindowTriggers,
+        row_errors: u64,
+        batch_size: u64,
+        tags: String,
+    ) {
+        if self.is_open(SystemStream::TaskMetrics) {
+            let row = Self::row(vec![
+                ("task_id", NnSqlValue::Text(task_id)),
+                ("num_input_rows", NnSqlValue::BigInt(num_input_rows as i64)),
+                (
+                    "avg_gain_bytes_per_sec",
+                    NnSqlValue::Float(OrderedFloat(avg_gain_bytes_per_sec)),
+                ),
+                ("tags", NnSqlValue::Text(tags)),
+                (
+                    "panes_opened",
+                    NnSqlValue::BigInt(window_triggers.panes_opened as i64),
+                ),
+                (
+                    "panes_closed",
+                    NnSqlValue::BigInt(window_triggers.panes_closed as i64),
+                ),
+                (
+                    "late_merges",
+                    NnSqlValue::BigInt(window_triggers.late_merges as i64),
+                ),
+                (
+                    "emitted_rows",
+                    NnSqlValue::BigInt(window_triggers.emitted_rows as i64),
+                ),
+                ("row_errors", NnSqlValue::BigInt(row_errors as i64)),
+                ("batch_size", NnSqlValue::BigInt(batch_size as i64)),
+            ]);
+            self.put(SystemStream::TaskMetrics, row);
+        }
+    }
+
+    /// Puts a row into `spring._row_errors`.
+    pub fn put_row_error(&self, pump: &str, streams: &str, row_error: &RowError) {
+        if self.is_open(SystemStream::RowErrors) {
+            let row = Self::row(vec![
+                ("pump", NnSqlValue::Text(pump.to_string())),
+                ("stream", NnSqlValue::Text(streams.to_string())),
+                ("code", NnSqlValue::Text(row_error.error.code().to_string())),
+                ("field", NnSqlValue::Text(row_error.field.clone())),
+                ("error", NnSqlValue::Text(format!("{:?}", row_error.error))),
+            ]);
+            self.put(SystemStream::RowErrors, row);
+        }
+    }
+
+    /// Row with `ts` column (current wall-clock time) and `cols`.
+    fn row(cols: Vec<(&str, NnSqlValue)>) -> SchemalessRow {
+        let mut row = SchemalessRow::with_capacity(cols.len() + 1);
+        let ts = ("ts", NnSqlValue::Timestamp(SystemTimestamp::now()));
+        for (col, v) in std::iter::once(ts).chain(cols) {
+            row.insert(ColumnName::new(col.to_string()), SqlValue::NotNull(v))
+                .expect("system stream columns are unique");
+        }
+        row
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::stream_engine::autonomous_executor::row::StreamRow;
+
+    #[test]
+    fn test_system_stream_repository() {
+        let repo = SystemStreamRepository::new(&SpringSystemStreamConfig {
+            capacity: 2,
+            task_metrics_interval_msec: 1_000,
+        });
+        let row = || SchemalessRow::from(StreamRow::fx_city_temperature_tokyo());
+
+        // not collected before opened
+        repo.put(SystemStream::Events, row());
+        assert!(!repo.is_open(SystemStream::Events));
+
+        let queue = repo.open(SystemStream::Events);
+        assert!(repo.is_open(SystemStream::Events));
+        assert!(queue.pop_non_blocking().is_none());
+
+        // bounded by capacity
+        (0..3).for_each(|_| repo.put(SystemStream::Events, row()));
+        repo.put(SystemStream::TaskMetrics, row());
+        assert_eq!(queue.pop_non_blocking(), Some(row()));
+        assert_eq!(queue.pop_non_blocking(), Some(row()));
+        assert!(queue.pop_non_blocking().is_none());
+    }
+
+    #[test]
+    fn test_put_event() {
+        let repo = SystemStreamRepository::new(&SpringSystemStreamConfig {
+            capacity: 10,
+            task_metrics_interval_msec: 1_000,
+        });
+        let queue = repo.open(SystemStream::Events);
+
+        repo.put_event("PURGE", "q: 1 rows".to_string());
+        let row = queue.pop_non_blocking().unwrap();
+        assert_eq!(
+            row.get_by_column_name(&ColumnName::new("event_type".to_string()))
+                .unwrap(),
+            &SqlValue::NotNull(NnSqlValue::Text("PURGE".to_string()))
+        );
+        assert_eq!(
+            row.get_by_column_name(&ColumnName::new("detail".to_string()))
+                .unwrap(),
+            &SqlValue::NotNull(NnSqlValue::Text("q: 1 rows".to_string()))
+        );
+    }
+}
@@ -13,13 +13,15 @@ mod processed_rows;
 pub use processed_rows::ProcessedRows;
 pub use sink_task::SinkWriterRepository;
 pub use source_task::{
-    NetClientSourceReader, NetServerSourceReader, SourceReader, SourceReaderRepository, SourceTask,
+    NetClientSourceReader, NetServerSourceReader, SourceReader, SourceReaderRepository,
+    SourceSampler, SourceTask,
 };
 pub use task_context::TaskContext;
 pub use tuple::Tuple;
-pub use window::Window;
+pub use window::{AggregateState, Window};
 
 use crate::{
+    alloc_instrumentation::{AllocScope, AllocSite},
     api::error::Result,
     pipeline::{Edge, PipelineGraph},
     stream_engine::autonomous_executor::{
@@ -62,6 +64,7 @@ impl Task {
     }
 
     pub fn run(&self, context: &TaskContext) -> Result<TaskRunResult> {
+        let _alloc_scope = AllocScope::enter(AllocSite::Queue);
         match self {
             Task::Pump(pump_task) => pump_task.run(context),
             Task::Source(source_task) => source_task.run(context),
@@ -18,4 +18,8 @@ impl ProcessedRows {
     pub fn is_empty(&self) -> bool {
         self.0 == 0
     }
+
+    pub fn as_u64(&self) -> u64 {
+        self.0
+    }
 }
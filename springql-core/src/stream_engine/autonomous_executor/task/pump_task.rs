@@ -18,7 +18,7 @@ use crate::{
             task::{
                 pump_task::pump_subtask::{InsertSubtask, QuerySubtask},
                 task_context::TaskContext,
-                window::{AggrWindow, JoinWindow},
+                window::{AggrWindow, IntervalJoin, JoinWindow},
                 ProcessedRows, TaskRunResult,
             },
             task_graph::TaskId,
@@ -34,6 +34,8 @@ const WAIT_ON_NO_INPUT: Duration = Duration::from_micros(100);
 #[derive(Debug)]
 pub struct PumpTask {
     id: TaskId,
+    /// Upstream stream names separated by `,`, for logs.
+    upstreams: String,
     query_subtask: QuerySubtask,
     insert_subtask: InsertSubtask,
 }
@@ -41,10 +43,17 @@ pub struct PumpTask {
 impl PumpTask {
     pub fn new(pump: &PumpModel, pipeline_graph: &PipelineGraph) -> Self {
         let id = TaskId::from_pump(pump);
+        let upstreams = pump
+            .upstreams()
+            .iter()
+            .map(|stream| stream.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
         let query_subtask = QuerySubtask::new(pump.query_plan().clone());
-        let insert_subtask = InsertSubtask::new(pump.insert_plan(), pipeline_graph);
+        let insert_subtask = InsertSubtask::new(pump, pipeline_graph);
         Self {
             id,
+            upstreams,
             query_subtask,
             insert_subtask,
         }
@@ -56,11 +65,12 @@ impl PumpTask {
 
     pub fn run(&self, context: &TaskContext) -> Result<TaskRunResult> {
         let stopwatch = WallClockStopwatch::start();
-        let (processed_rows, in_queue_metrics, out_queues_metrics) =
-            self.run_query_insert(context)?;
+        let (processed_rows, in_queue_metrics, out_queues_metrics, n_row_errors) =
+            self.run_query_insert(context);
         let execution_time = stopwatch.stop();
 
-        let task_metrics = TaskMetricsUpdateByTask::new(context.task(), execution_time);
+        let task_metrics = TaskMetricsUpdateByTask::new(context.task(), execution_time)
+            .with_row_errors(n_row_errors);
         let metrics = MetricsUpdateByTaskExecution::new(
             task_metrics,
             in_queue_metrics.map_or_else(Vec::new, |m| vec![m]),
@@ -73,27 +83,53 @@ impl PumpTask {
         })
     }
 
+    /// Rows failed in the query or in the insertion are skipped, logged, and put into `spring._row_errors`.
+    ///
+    /// # Returns
+    ///
+    /// Last element is the number of rows failed.
     fn run_query_insert(
         &self,
         context: &TaskContext,
-    ) -> Result<(
+    ) -> (
         ProcessedRows,
         Option<InQueueMetricsUpdateByTask>,
         Vec<OutQueueMetricsUpdateByTask>,
-    )> {
-        if let Some(query_subtask_out) = self.query_subtask.run(context)? {
+        u64,
+    ) {
+        if let Some(query_subtask_out) = self.query_subtask.run(context) {
             let processed_rows = query_subtask_out.processed_rows();
+
             let insert_subtask_out = self
                 .insert_subtask
                 .run(query_subtask_out.values_seq, context);
-            Ok((
+
+            let repos = context.repos();
+            let pump = self.id.to_string();
+            let row_errors = query_subtask_out
+                .row_errors
+                .iter()
+                .chain(insert_subtask_out.row_errors.iter());
+            let mut n_row_errors = 0;
+            for row_error in row_errors {
+                repos
+                    .row_error_logger()
+                    .log(&pump, &self.upstreams, row_error);
+                repos
+                    .system_stream_repository()
+                    .put_row_error(&pump, &self.upstreams, row_error);
+                n_row_errors += 1;
+            }
+
+            (
                 processed_rows,
                 Some(query_subtask_out.in_queue_metrics_update),
                 insert_subtask_out.out_queues_metrics_update,
-            ))
+                n_row_errors,
+            )
         } else {
             thread::sleep(WAIT_ON_NO_INPUT);
-            Ok((ProcessedRows::default(), None, vec![]))
+            (ProcessedRows::default(), None, vec![], 0)
         }
     }
 
@@ -103,4 +139,7 @@ impl PumpTask {
     pub fn get_join_window_mut(&self) -> Option<MutexGuard<JoinWindow>> {
         self.query_subtask.get_join_window_mut()
     }
+    pub fn get_interval_join_mut(&self) -> Option<MutexGuard<IntervalJoin>> {
+        self.query_subtask.get_interval_join_mut()
+    }
 }
@@ -3,92 +3,202 @@
 use std::sync::Arc;
 
 use crate::{
+    api::error::SpringError,
     mem_size::MemSize,
-    pipeline::{ColumnName, PipelineGraph, StreamModel},
+    pipeline::{ColumnName, PipelineGraph, PumpModel, StreamModel, StreamName},
     stream_engine::{
         autonomous_executor::{
             performance_metrics::OutQueueMetricsUpdateByTask,
             row::StreamRow,
+            row_error_log::RowError,
             task::{pump_task::pump_subtask::query_subtask::SqlValues, task_context::TaskContext},
             task_graph::QueueId,
         },
-        command::InsertPlan,
+        NnSqlValue, SqlValue,
     },
 };
 
 #[derive(Debug)]
 pub struct InsertSubtask {
-    into_stream: Arc<StreamModel>,
-
     /// INSERT INTO stream (c2, c3, c1) -- this one!
     column_order: Vec<ColumnName>,
+
+    /// A row goes to the i-th stream when the i-th condition value (following column values) is the first TRUE one.
+    when_streams: Vec<Arc<StreamModel>>,
+
+    /// A row goes to this stream when no condition is TRUE.
+    else_stream: Option<Arc<StreamModel>>,
+
+    /// Every row goes to all of these streams.
+    /// `INSERT INTO` has only one.
+    replica_streams: Vec<Arc<StreamModel>>,
+
+    /// The last value is `PRIORITY WHEN` condition. A row is put to the high-priority lane of queues when it is TRUE.
+    prioritized: bool,
 }
 
 #[derive(Debug, new)]
 pub struct InsertSubtaskOut {
     pub out_queues_metrics_update: Vec<OutQueueMetricsUpdateByTask>,
+    /// Errors of rows not inserted into a stream.
+    pub row_errors: Vec<RowError>,
 }
 
 impl InsertSubtask {
     /// # Panics
     ///
-    /// `plan` has invalid stream name
-    pub fn new(plan: &InsertPlan, pipeline_graph: &PipelineGraph) -> Self {
-        let into_stream = pipeline_graph
-            .get_stream(plan.stream())
-            .expect("plan has invalid stream name");
+    /// `pump` has invalid stream name
+    pub fn new(pump: &PumpModel, pipeline_graph: &PipelineGraph) -> Self {
+        let plan = pump.output_plan();
+        let get_stream = |stream_name: &StreamName| {
+            pipeline_graph
+                .get_stream(stream_name)
+                .expect("plan has invalid stream name")
+        };
         Self {
-            into_stream,
             column_order: plan.column_order().to_vec(),
+            when_streams: plan.when_streams().iter().map(get_stream).collect(),
+            else_stream: plan.else_stream().map(get_stream),
+            replica_streams: plan.replica_streams().iter().map(get_stream).collect(),
+            prioritized: pump.prioritized(),
         }
     }
 
+    /// Rows failed to be converted into a stream's shape (e.g. NULL into a NOT NULL column) are skipped and returned in `InsertSubtaskOut::row_errors`.
     pub fn run(&self, values_seq: Vec<SqlValues>, context: &TaskContext) -> InsertSubtaskOut {
         if values_seq.is_empty() {
-            InsertSubtaskOut::new(vec![])
+            InsertSubtaskOut::new(vec![], vec![])
         } else {
             let repos = context.repos();
             let row_q_repo = repos.row_queue_repository();
             let window_q_repo = repos.window_queue_repository();
-            let output_queues = context.output_queues();
 
-            let rows = values_seq
-                .into_iter()
-                .map(|values| values.into_row(self.into_stream.clone(), self.column_order.clone()))
-                .collect::<Vec<_>>();
+            let pipeline_derivatives = context.pipeline_derivatives();
+            let stream_tracers = pipeline_derivatives.stream_tracers();
 
-            let out_queues_metrics_update = output_queues
-                .into_iter()
-                .map(|q| match q {
-                    QueueId::Row(queue_id) => {
-                        let row_q = row_q_repo.get(&queue_id);
-                        let out = self.out_queue_metrics_update(queue_id.into(), &rows);
-                        for row in rows.clone() {
-                            row_q.put(row);
+            let mut out_queues_metrics_update = vec![];
+            let mut row_errors = vec![];
+            for (into_stream, rows) in self.route(values_seq, &mut row_errors) {
+                if rows.is_empty() {
+                    continue;
+                }
+                stream_tracers.trace(into_stream.name(), rows.iter().map(|(row, _)| row));
+                repos
+                    .stream_tap_repository()
+                    .put(into_stream.name(), rows.iter().map(|(row, _)| row));
+
+                for q in context.output_queues_of(into_stream.name()) {
+                    match q {
+                        QueueId::Row(queue_id) => {
+                            let row_q = row_q_repo.get(&queue_id);
+                            if row_q.is_paused() {
+                                continue;
+                            }
+                            let rows = rows
+                                .iter()
+                                .filter(|_| !repos.chaos_injector().drop_row())
+                                .cloned()
+                                .collect::<Vec<_>>();
+                            out_queues_metrics_update
+                                .push(self.out_queue_metrics_update(queue_id.into(), &rows));
+                            for (row, high_priority) in rows {
+                                if high_priority {
+                                    row_q.put_priority(row);
+                                } else {
+                                    row_q.put(row);
+                                }
+                            }
                         }
-                        out
-                    }
-                    QueueId::Window(queue_id) => {
-                        let window_queue = window_q_repo.get(&queue_id);
-                        let out = self.out_queue_metrics_update(queue_id.into(), &rows);
-                        for row in rows.clone() {
-                            window_queue.put(row);
+                        QueueId::Window(queue_id) => {
+                            let window_queue = window_q_repo.get(&queue_id);
+                            if window_queue.is_paused() {
+                                continue;
+                            }
+                            out_queues_metrics_update
+                                .push(self.out_queue_metrics_update(queue_id.into(), &rows));
+                            for (row, high_priority) in rows.clone() {
+                                if high_priority {
+                                    window_queue.put_priority(row);
+                                } else {
+                                    window_queue.put(row);
+                                }
+                            }
                         }
-                        out
                     }
-                })
-                .collect();
+                }
+            }
 
-            InsertSubtaskOut::new(out_queues_metrics_update)
+            InsertSubtaskOut::new(out_queues_metrics_update, row_errors)
         }
     }
 
+    /// Rows (with high-priority flag) for each destination stream. Rows matching no condition without ELSE are dropped.
+    #[allow(clippy::type_complexity)]
+    fn route(
+        &self,
+        values_seq: Vec<SqlValues>,
+        row_errors: &mut Vec<RowError>,
+    ) -> Vec<(&Arc<StreamModel>, Vec<(StreamRow, bool)>)> {
+        let mut dests = self
+            .when_streams
+            .iter()
+            .chain(self.else_stream.iter())
+            .chain(self.replica_streams.iter())
+            .map(|stream| (stream, vec![]))
+            .collect::<Vec<_>>();
+        let else_idx = self.when_streams.len();
+        let n_routed = else_idx + usize::from(self.else_stream.is_some());
+
+        for mut values in values_seq {
+            let mut conditions = values.split_off(self.column_order.len());
+            let high_priority = self.prioritized
+                && matches!(
+                    conditions.pop(),
+                    Some(SqlValue::NotNull(NnSqlValue::Boolean(true)))
+                );
+            let routed_idx = conditions
+                .iter()
+                .position(|cond| matches!(cond, SqlValue::NotNull(NnSqlValue::Boolean(true))))
+                .unwrap_or(else_idx);
+
+            let dest_idxs = (routed_idx < n_routed)
+                .then(|| routed_idx)
+                .into_iter()
+                .chain(n_routed..dests.len())
+                .collect::<Vec<_>>();
+
+            if let Some((last_idx, idxs)) = dest_idxs.split_last() {
+                for idx in idxs {
+                    let (stream, rows) = &mut dests[*idx];
+                    match values
+                        .clone()
+                        .into_row((*stream).clone(), self.column_order.clone())
+                    {
+                        Ok(row) => rows.push((row, high_priority)),
+                        Err(e) => row_errors.push(Self::row_error(stream, e)),
+                    }
+                }
+                let (stream, rows) = &mut dests[*last_idx];
+                match values.into_row((*stream).clone(), self.column_order.clone()) {
+                    Ok(row) => rows.push((row, high_priority)),
+                    Err(e) => row_errors.push(Self::row_error(stream, e)),
+                }
+            }
+        }
+
+        dests
+    }
+
+    fn row_error(stream: &StreamModel, e: SpringError) -> RowError {
+        RowError::new(format!("INSERT INTO {}", stream.name()), e)
+    }
+
     fn out_queue_metrics_update(
         &self,
         queue_id: QueueId,
-        rows: &[StreamRow],
+        rows: &[(StreamRow, bool)],
     ) -> OutQueueMetricsUpdateByTask {
-        let bytes_put: usize = rows.iter().map(|row| row.mem_size()).sum();
+        let bytes_put: usize = rows.iter().map(|(row, _)| row.stored_size()).sum();
         OutQueueMetricsUpdateByTask::new(queue_id, rows.len() as u64, bytes_put as u64)
     }
 }
@@ -4,16 +4,19 @@ mod collect_subtask;
 mod group_aggregate_window_subtask;
 mod join_subtask;
 mod projection_subtask;
+mod selection_subtask;
 
 use std::sync::{Arc, Mutex, MutexGuard};
 
+use anyhow::anyhow;
 use rand::{
     prelude::{SliceRandom, SmallRng},
     SeedableRng,
 };
 
 use crate::{
-    api::error::Result,
+    alloc_instrumentation::{AllocScope, AllocSite},
+    api::error::{Result, SpringError},
     expr_resolver::ExprResolver,
     pipeline::{ColumnName, StreamModel},
     stream_engine::{
@@ -22,15 +25,17 @@ use crate::{
                 InQueueMetricsUpdateByCollect, InQueueMetricsUpdateByTask, WindowInFlowByWindowTask,
             },
             row::{ColumnValues, StreamColumns, StreamRow},
+            row_error_log::RowError,
             task::{
                 pump_task::pump_subtask::query_subtask::{
                     collect_subtask::CollectSubtask,
                     group_aggregate_window_subtask::GroupAggregateWindowSubtask,
                     join_subtask::JoinSubtask, projection_subtask::ProjectionSubtask,
+                    selection_subtask::SelectionSubtask,
                 },
                 task_context::TaskContext,
                 tuple::Tuple,
-                window::{AggrWindow, JoinDir, JoinWindow},
+                window::{AggrWindow, IntervalJoin, JoinDir, JoinWindow},
                 ProcessedRows,
             },
         },
@@ -55,6 +60,8 @@ pub struct QuerySubtask {
     )>,
     left_collect_subtask: CollectSubtask, // left stream
 
+    selection_subtask: Option<SelectionSubtask>,
+
     rng: Mutex<SmallRng>,
 }
 
@@ -71,34 +78,48 @@ impl SqlValues {
     /// (fields[1], fields[2], fields[0])
     /// ```
     ///
-    /// # Panics
+    /// # Failure
     ///
-    /// - Tuple fields and column_order have different length.
-    /// - Type mismatch between `self.fields` (ordered) and `stream_shape`
-    /// - Duplicate column names in `column_order`
+    /// - `SpringError::Sql` when:
+    ///   - Tuple fields and column_order have different length.
+    ///   - Type mismatch between `self.fields` (ordered) and `stream_shape` (e.g. NULL into a NOT NULL column).
+    ///   - Duplicate column names in `column_order`
     pub fn into_row(
         self,
         stream_model: Arc<StreamModel>,
         column_order: Vec<ColumnName>,
-    ) -> StreamRow {
-        assert_eq!(self.0.len(), column_order.len());
+    ) -> Result<StreamRow> {
+        if self.0.len() != column_order.len() {
+            return Err(SpringError::Sql(anyhow!(
+                "{} values for {} columns of stream {}",
+                self.0.len(),
+                column_order.len(),
+                stream_model.name()
+            )));
+        }
+
+        let column_values = self.mk_column_values(column_order)?;
+        let stream_columns = StreamColumns::new(stream_model, column_values)?;
+        Ok(StreamRow::new(stream_columns))
+    }
 
-        let column_values = self.mk_column_values(column_order);
-        let stream_columns = StreamColumns::new(stream_model, column_values)
-            .expect("type or shape mismatch? must be checked on pump creation");
-        StreamRow::new(stream_columns)
+    /// Splits off values after `at` (e.g. ROUTER's conditions following column values).
+    ///
+    /// # Panics
+    ///
+    /// `at` > number of values
+    pub fn split_off(&mut self, at: usize) -> Vec<SqlValue> {
+        self.0.split_off(at)
     }
 
-    fn mk_column_values(self, column_order: Vec<ColumnName>) -> ColumnValues {
+    fn mk_column_values(self, column_order: Vec<ColumnName>) -> Result<ColumnValues> {
         let mut column_values = ColumnValues::default();
 
         for (column_name, value) in column_order.into_iter().zip(self.0.into_iter()) {
-            column_values
-                .insert(column_name, value)
-                .expect("duplicate column name");
+            column_values.insert(column_name, value)?;
         }
 
-        column_values
+        Ok(column_values)
     }
 }
 
@@ -106,6 +127,8 @@ impl SqlValues {
 pub struct QuerySubtaskOut {
     pub values_seq: Vec<SqlValues>,
     pub in_queue_metrics_update: InQueueMetricsUpdateByTask,
+    /// Errors of rows skipped.
+    pub row_errors: Vec<RowError>,
 }
 impl QuerySubtaskOut {
     pub fn processed_rows(&self) -> ProcessedRows {
@@ -118,6 +141,11 @@ impl QuerySubtask {
         let rng =
             Mutex::new(SmallRng::from_rng(rand::thread_rng()).expect("this generally won't fail"));
 
+        let selection_subtask = plan
+            .lower_ops
+            .selection
+            .clone()
+            .map(SelectionSubtask::from_selection_op);
         let (left_collect_subtask, join) = Self::subtasks_from_lower_ops(plan.lower_ops);
 
         let group_aggr_window_subtask = plan
@@ -133,6 +161,7 @@ impl QuerySubtask {
             group_aggr_window_subtask,
             left_collect_subtask,
             join,
+            selection_subtask,
             rng,
         }
     }
@@ -149,7 +178,17 @@ impl QuerySubtask {
                 let left_collect_subtask = CollectSubtask::from_collect_op(join_window_op.left);
                 let right_collect_subtask = CollectSubtask::from_collect_op(join_window_op.right);
                 let join_subtask =
-                    JoinSubtask::new(join_window_op.window_param, join_window_op.join_param);
+                    JoinSubtask::new_window(join_window_op.window_param, join_window_op.join_param);
+                (
+                    left_collect_subtask,
+                    Some((join_subtask, right_collect_subtask)),
+                )
+            }
+            JoinOp::IntervalJoin(interval_join_op) => {
+                let left_collect_subtask = CollectSubtask::from_collect_op(interval_join_op.left);
+                let right_collect_subtask = CollectSubtask::from_collect_op(interval_join_op.right);
+                let join_subtask =
+                    JoinSubtask::new_interval(interval_join_op.bound, interval_join_op.join_param);
                 (
                     left_collect_subtask,
                     Some((join_subtask, right_collect_subtask)),
@@ -158,25 +197,43 @@ impl QuerySubtask {
         }
     }
 
+    /// Rows failed in JOIN, WHERE clause, aggregation or select_list are skipped and returned in `QuerySubtaskOut::row_errors`.
+    ///
     /// # Returns
     ///
     /// None when input queue does not exist or is empty.
-    ///
-    /// # Failures
-    ///
-    /// TODO
-    pub fn run(&self, context: &TaskContext) -> Result<Option<QuerySubtaskOut>> {
-        match self.run_lower_ops(context) {
-            None => Ok(None),
-            Some((lower_tuples, in_queue_metrics_update_by_task)) => {
-                let (values_seq, in_queue_metrics_update) =
-                    self.run_upper_ops(lower_tuples, in_queue_metrics_update_by_task)?;
-
-                Ok(Some(QuerySubtaskOut::new(
-                    values_seq,
-                    in_queue_metrics_update,
-                )))
-            }
+    pub fn run(&self, context: &TaskContext) -> Option<QuerySubtaskOut> {
+        let mut row_errors = Vec::new();
+        let (lower_tuples, in_queue_metrics_update_by_task) =
+            self.run_lower_ops(context, &mut row_errors)?;
+        let lower_tuples = self.run_selection(lower_tuples, &mut row_errors);
+        let (values_seq, in_queue_metrics_update) = self.run_upper_ops(
+            lower_tuples,
+            in_queue_metrics_update_by_task,
+            &mut row_errors,
+        );
+        Some(QuerySubtaskOut::new(
+            values_seq,
+            in_queue_metrics_update,
+            row_errors,
+        ))
+    }
+
+    fn run_selection(&self, tuples: Vec<Tuple>, row_errors: &mut Vec<RowError>) -> Vec<Tuple> {
+        match &self.selection_subtask {
+            Some(selection_subtask) => tuples
+                .into_iter()
+                .filter(
+                    |tuple| match selection_subtask.run(&self.expr_resolver, tuple) {
+                        Ok(selected) => selected,
+                        Err(e) => {
+                            row_errors.push(RowError::new("WHERE".to_string(), e));
+                            false
+                        }
+                    },
+                )
+                .collect(),
+            None => tuples,
         }
     }
 
@@ -184,46 +241,64 @@ impl QuerySubtask {
         &self,
         tuples: Vec<Tuple>,
         in_queue_metrics_update_by_lower: InQueueMetricsUpdateByTask,
-    ) -> Result<(Vec<SqlValues>, InQueueMetricsUpdateByTask)> {
+        row_errors: &mut Vec<RowError>,
+    ) -> (Vec<SqlValues>, InQueueMetricsUpdateByTask) {
         let (values_seq, window_in_flow_upper_total) = tuples.into_iter().fold(
-            Ok((Vec::new(), WindowInFlowByWindowTask::zero())),
-            |res, tuple| {
-                let (mut values_seq_acc, window_in_flow_acc) = res?;
-
-                let (mut values_seq, window_in_flow) = self.run_upper_ops_inner(tuple)?;
+            (Vec::new(), WindowInFlowByWindowTask::zero()),
+            |(mut values_seq_acc, window_in_flow_acc), tuple| {
+                let (mut values_seq, window_in_flow) = self.run_upper_ops_inner(tuple, row_errors);
                 values_seq_acc.append(&mut values_seq);
-                Ok((values_seq_acc, window_in_flow_acc + window_in_flow))
+                (values_seq_acc, window_in_flow_acc + window_in_flow)
             },
-        )?;
+        );
         let in_queue_metrics_update_by_task = InQueueMetricsUpdateByTask::new(
             in_queue_metrics_update_by_lower.by_collect,
             Some(window_in_flow_upper_total + in_queue_metrics_update_by_lower.window_in_flow),
         );
 
-        Ok((values_seq, in_queue_metrics_update_by_task))
+        (values_seq, in_queue_metrics_update_by_task)
     }
     fn run_upper_ops_inner(
         &self,
         tuple: Tuple,
-    ) -> Result<(Vec<SqlValues>, WindowInFlowByWindowTask)> {
+        row_errors: &mut Vec<RowError>,
+    ) -> (Vec<SqlValues>, WindowInFlowByWindowTask) {
         if let Some(group_aggr_window_subtask) = &self.group_aggr_window_subtask {
-            let (aggregated_and_grouping_values_seq, window_in_flow) =
-                group_aggr_window_subtask.run(&self.expr_resolver, tuple);
+            let res = {
+                let _alloc_scope = AllocScope::enter(AllocSite::Window);
+                group_aggr_window_subtask.run(&self.expr_resolver, tuple)
+            };
+            let (aggregated_and_grouping_values_seq, window_in_flow) = match res {
+                Ok(out) => out,
+                Err(e) => {
+                    row_errors.push(RowError::new("AGGREGATE".to_string(), e));
+                    return (vec![], WindowInFlowByWindowTask::zero());
+                }
+            };
 
             let values_seq = aggregated_and_grouping_values_seq
                 .into_iter()
-                .map(|aggregated_and_grouping_values| {
+                .filter_map(|aggregated_and_grouping_values| {
                     self.projection_subtask
                         .run_with_aggr(aggregated_and_grouping_values)
+                        .map_err(|e| row_errors.push(e))
+                        .ok()
                 })
-                .collect::<Result<Vec<_>>>()?;
+                .collect();
 
-            Ok((values_seq, window_in_flow))
+            (values_seq, window_in_flow)
         } else {
-            let values = self
+            let values_seq = match self
                 .projection_subtask
-                .run_without_aggr(&self.expr_resolver, &tuple)?;
-            Ok((vec![values], WindowInFlowByWindowTask::zero()))
+                .run_without_aggr(&self.expr_resolver, &tuple)
+            {
+                Ok(values) => vec![values],
+                Err(e) => {
+                    row_errors.push(e);
+                    vec![]
+                }
+            };
+            (values_seq, WindowInFlowByWindowTask::zero())
         }
     }
 
@@ -233,6 +308,7 @@ impl QuerySubtask {
     fn run_lower_ops(
         &self,
         context: &TaskContext,
+        row_errors: &mut Vec<RowError>,
     ) -> Option<(Vec<Tuple>, InQueueMetricsUpdateByTask)> {
         match &self.join {
             Some((join_subtask, right_collect_subtask)) => self.run_join(
@@ -240,6 +316,7 @@ impl QuerySubtask {
                 &self.left_collect_subtask,
                 right_collect_subtask,
                 join_subtask,
+                row_errors,
             ),
             None => self
                 .run_left_collect(context)
@@ -264,13 +341,14 @@ impl QuerySubtask {
         left_collect_subtask: &CollectSubtask,
         right_collect_subtask: &CollectSubtask,
         join_subtask: &JoinSubtask,
+        row_errors: &mut Vec<RowError>,
     ) -> Option<(Vec<Tuple>, InQueueMetricsUpdateByTask)> {
         self.join_dir_candidates().into_iter().find_map(|dir| {
             let collect_subtask = match dir {
                 JoinDir::Left => left_collect_subtask,
                 JoinDir::Right => right_collect_subtask,
             };
-            self.run_join_core(context, collect_subtask, join_subtask, dir)
+            self.run_join_core(context, collect_subtask, join_subtask, dir, row_errors)
         })
     }
     fn join_dir_candidates(&self) -> [JoinDir; 2] {
@@ -289,11 +367,19 @@ impl QuerySubtask {
         collect_subtask: &CollectSubtask,
         join_subtask: &JoinSubtask,
         join_dir: JoinDir,
+        row_errors: &mut Vec<RowError>,
     ) -> Option<(Vec<Tuple>, InQueueMetricsUpdateByTask)> {
         collect_subtask
             .run(context)
             .map(|(tuple, metrics_collect)| {
-                let (tuples, metrics_join) = join_subtask.run(&self.expr_resolver, tuple, join_dir);
+                let res = {
+                    let _alloc_scope = AllocScope::enter(AllocSite::Window);
+                    join_subtask.run(&self.expr_resolver, tuple, join_dir)
+                };
+                let (tuples, metrics_join) = res.unwrap_or_else(|e| {
+                    row_errors.push(RowError::new("JOIN".to_string(), e));
+                    (vec![], WindowInFlowByWindowTask::zero())
+                });
                 let metrics = InQueueMetricsUpdateByTask::new(metrics_collect, Some(metrics_join));
                 (tuples, metrics)
             })
@@ -314,6 +400,11 @@ impl QuerySubtask {
     pub fn get_join_window_mut(&self) -> Option<MutexGuard<JoinWindow>> {
         self.join
             .as_ref()
-            .map(|(subtask, _)| subtask.get_window_mut())
+            .and_then(|(subtask, _)| subtask.get_window_mut())
+    }
+    pub fn get_interval_join_mut(&self) -> Option<MutexGuard<IntervalJoin>> {
+        self.join
+            .as_ref()
+            .and_then(|(subtask, _)| subtask.get_interval_join_mut())
     }
 }
@@ -57,7 +57,7 @@ impl CollectSubtask {
         let queue = row_q_repo.get(&queue_id);
         let opt_row = queue.use_();
         opt_row.map(|row| {
-            let bytes_used = row.mem_size();
+            let bytes_used = row.stored_size();
             let tuple = Tuple::from_row(row);
             (
                 tuple,
@@ -82,7 +82,7 @@ impl CollectSubtask {
         let queue = window_q_repo.get(&queue_id);
         let opt_row = queue.dispatch();
         opt_row.map(|row| {
-            let bytes_dispatched = row.mem_size();
+            let bytes_dispatched = row.stored_size();
             let tuple = Tuple::from_row(row);
             (
                 tuple,
@@ -3,6 +3,7 @@
 use std::sync::{Mutex, MutexGuard};
 
 use crate::{
+    api::error::Result,
     expr_resolver::ExprResolver,
     pipeline::{WindowOperationParameter, WindowParameter},
     stream_engine::autonomous_executor::{
@@ -23,16 +24,19 @@ impl GroupAggregateWindowSubtask {
         Self(Mutex::new(window))
     }
 
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - failed to evaluate GROUP BY or aggregated expressions for `tuple`.
     pub fn run(
         &self,
         expr_resolver: &ExprResolver,
         tuple: Tuple,
-    ) -> (Vec<AggregatedAndGroupingValues>, WindowInFlowByWindowTask) {
+    ) -> Result<(Vec<AggregatedAndGroupingValues>, WindowInFlowByWindowTask)> {
         self.0
             .lock()
             .expect("another thread accessing to window gets poisoned")
             .dispatch(expr_resolver, tuple, ())
-            .expect("dispatch failed")
     }
 
     pub fn get_window_mut(&self) -> MutexGuard<AggrWindow> {
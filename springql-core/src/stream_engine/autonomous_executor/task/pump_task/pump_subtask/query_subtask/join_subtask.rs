@@ -3,42 +3,76 @@
 use std::sync::{Mutex, MutexGuard};
 
 use crate::{
+    api::error::Result,
     expr_resolver::ExprResolver,
-    pipeline::{JoinParameter, WindowParameter},
+    pipeline::{IntervalJoinBound, JoinParameter, WindowParameter},
     stream_engine::autonomous_executor::{
         performance_metrics::WindowInFlowByWindowTask,
         task::{
             tuple::Tuple,
-            window::{JoinDir, JoinWindow, Window},
+            window::{IntervalJoin, JoinDir, JoinWindow, Window},
         },
     },
 };
 
 #[derive(Debug)]
-pub struct JoinSubtask(Mutex<JoinWindow>);
+pub enum JoinSubtask {
+    Window(Mutex<JoinWindow>),
+    Interval(Mutex<IntervalJoin>),
+}
 
 impl JoinSubtask {
-    pub fn new(window_param: WindowParameter, join_param: JoinParameter) -> Self {
+    pub fn new_window(window_param: WindowParameter, join_param: JoinParameter) -> Self {
         let window = JoinWindow::new(window_param, join_param);
-        Self(Mutex::new(window))
+        Self::Window(Mutex::new(window))
+    }
+
+    pub fn new_interval(bound: IntervalJoinBound, join_param: JoinParameter) -> Self {
+        let interval_join = IntervalJoin::new(bound, join_param);
+        Self::Interval(Mutex::new(interval_join))
     }
 
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - failed to evaluate join keys or ON clause for `tuple`.
     pub fn run(
         &self,
         expr_resolver: &ExprResolver,
         tuple: Tuple,
         dir: JoinDir,
-    ) -> (Vec<Tuple>, WindowInFlowByWindowTask) {
-        self.0
-            .lock()
-            .expect("another thread accessing to window gets poisoned")
-            .dispatch(expr_resolver, tuple, dir)
-            .expect("dispatch failed")
+    ) -> Result<(Vec<Tuple>, WindowInFlowByWindowTask)> {
+        match self {
+            Self::Window(window) => window
+                .lock()
+                .expect("another thread accessing to window gets poisoned")
+                .dispatch(expr_resolver, tuple, dir),
+            Self::Interval(interval_join) => interval_join
+                .lock()
+                .expect("another thread accessing to interval join gets poisoned")
+                .dispatch(expr_resolver, tuple, dir),
+        }
+    }
+
+    pub fn get_window_mut(&self) -> Option<MutexGuard<JoinWindow>> {
+        match self {
+            Self::Window(window) => Some(
+                window
+                    .lock()
+                    .expect("another thread accessing to window gets poisoned"),
+            ),
+            Self::Interval(_) => None,
+        }
     }
 
-    pub fn get_window_mut(&self) -> MutexGuard<JoinWindow> {
-        self.0
-            .lock()
-            .expect("another thread accessing to window gets poisoned")
+    pub fn get_interval_join_mut(&self) -> Option<MutexGuard<IntervalJoin>> {
+        match self {
+            Self::Window(_) => None,
+            Self::Interval(interval_join) => Some(
+                interval_join
+                    .lock()
+                    .expect("another thread accessing to interval join gets poisoned"),
+            ),
+        }
     }
 }
@@ -1,11 +1,14 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 use crate::{
-    api::error::Result,
+    api::error::SpringError,
     expr_resolver::{ExprLabel, ExprResolver},
-    stream_engine::autonomous_executor::task::{
-        pump_task::pump_subtask::query_subtask::SqlValues, tuple::Tuple,
-        window::AggregatedAndGroupingValues,
+    stream_engine::autonomous_executor::{
+        row_error_log::RowError,
+        task::{
+            pump_task::pump_subtask::query_subtask::SqlValues, tuple::Tuple,
+            window::AggregatedAndGroupingValues,
+        },
     },
 };
 
@@ -16,48 +19,67 @@ pub struct ProjectionSubtask {
 
 impl ProjectionSubtask {
     /// Projection for SELECT without aggregate.
+    ///
+    /// # Failures
+    ///
+    /// `RowError` with the position of the failed element in select_list (`SELECT #1` for the first).
     pub fn run_without_aggr(
         &self,
         expr_resolver: &ExprResolver,
         tuple: &Tuple,
-    ) -> Result<SqlValues> {
+    ) -> Result<SqlValues, RowError> {
         let values = self
             .exprs
             .iter()
-            .map(|label| match label {
-                ExprLabel::Value(group_by_value_label) => {
-                    expr_resolver.eval_value_expr(*group_by_value_label, tuple)
-                }
+            .enumerate()
+            .map(|(i, label)| match label {
+                ExprLabel::Value(group_by_value_label) => expr_resolver
+                    .eval_value_expr(*group_by_value_label, tuple)
+                    .map_err(|e| Self::row_error(i, e)),
                 ExprLabel::Aggr(_) => unreachable!("aggregate must not be in select_list"),
             })
-            .collect::<Result<Vec<_>>>()?;
+            .collect::<Result<Vec<_>, _>>()?;
 
         Ok(SqlValues::new(values))
     }
 
     /// Projection for SELECT with aggregate.
-    /// select_list must only have GROUP BY elements or aggregate expressions.
+    /// select_list must only have GROUP BY elements, aggregate expressions or window metadata.
     /// (Column reference without aggregate is not allowed.)
+    ///
+    /// # Failures
+    ///
+    /// `RowError` with the position of the failed element in select_list.
     pub fn run_with_aggr(
         &self,
         aggregated_and_grouping_values: AggregatedAndGroupingValues,
-    ) -> Result<SqlValues> {
+    ) -> Result<SqlValues, RowError> {
         let values = self
             .exprs
             .iter()
-            .map(|label| {
+            .enumerate()
+            .map(|(i, label)| {
                 let value = match label {
-                    ExprLabel::Value(group_by_value_label) => {
-                        aggregated_and_grouping_values.get_group_by_value(group_by_value_label)
+                    ExprLabel::Value(value_label) => {
+                        match aggregated_and_grouping_values.get_window_metadata_value(value_label)
+                        {
+                            Some(window_metadata_value) => Ok(window_metadata_value),
+                            None => aggregated_and_grouping_values.get_group_by_value(value_label),
+                        }
                     }
                     ExprLabel::Aggr(aggr_label) => {
                         aggregated_and_grouping_values.get_aggregated_value(aggr_label)
                     }
-                }?;
+                }
+                .map_err(|e| Self::row_error(i, e))?;
                 Ok(value.clone())
             })
-            .collect::<Result<Vec<_>>>()?;
+            .collect::<Result<Vec<_>, _>>()?;
 
         Ok(SqlValues::new(values))
     }
+
+    fn row_error(i_expr: usize, error: SpringError) -> RowError {
+        RowError::new(format!("SELECT #{}", i_expr + 1), error)
+    }
 }
@@ -0,0 +1,34 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use crate::{
+    api::error::Result,
+    expr_resolver::{ExprResolver, ValueExprLabel},
+    stream_engine::{autonomous_executor::task::tuple::Tuple, command::SelectionOp},
+};
+
+#[derive(Debug)]
+pub struct SelectionSubtask {
+    condition: ValueExprLabel,
+}
+
+impl SelectionSubtask {
+    pub fn from_selection_op(selection_op: SelectionOp) -> Self {
+        Self {
+            condition: selection_op.condition,
+        }
+    }
+
+    /// # Returns
+    ///
+    /// true if the tuple satisfies the condition (NULL is evaluated as FALSE).
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - the condition is not evaluated as BOOLEAN.
+    pub fn run(&self, expr_resolver: &ExprResolver, tuple: &Tuple) -> Result<bool> {
+        expr_resolver
+            .eval_value_expr(self.condition, tuple)?
+            .to_bool()
+    }
+}
@@ -2,26 +2,30 @@
 
 mod sink_writer;
 
-pub use sink_writer::{NetSinkWriter, SinkWriter, SinkWriterRepository};
+pub use sink_writer::{NetSinkWriter, SinkWriter, SinkWriterRepository, SpringqlLinkSinkWriter};
 
 use std::sync::Arc;
 
 use crate::{
-    api::error::Result,
+    api::error::{Result, SpringError},
+    expression::ValueExpr,
     mem_size::MemSize,
-    pipeline::{SinkWriterModel, SinkWriterName, StreamName},
+    pipeline::{ColumnName, SinkWriterModel, SinkWriterName, StreamName},
     stream_engine::{
         autonomous_executor::{
             performance_metrics::{
                 InQueueMetricsUpdateByCollect, InQueueMetricsUpdateByTask,
-                MetricsUpdateByTaskExecution, TaskMetricsUpdateByTask,
+                MetricsUpdateByTaskExecution, SinkHealth, TaskMetricsUpdateByTask,
             },
             repositories::Repositories,
-            row::StreamRow,
-            task::{task_context::TaskContext, ProcessedRows, TaskRunResult},
+            row::{SchemalessRow, StreamRow},
+            row_error_log::RowError,
+            task::{task_context::TaskContext, tuple::Tuple, ProcessedRows, TaskRunResult},
             task_graph::{QueueId, TaskId},
         },
-        time::WallClockStopwatch,
+        time::{
+            SpringDuration, SpringTimestamp, SystemTimestamp, WallClockDuration, WallClockStopwatch,
+        },
     },
 };
 
@@ -30,15 +34,26 @@ pub struct SinkTask {
     id: TaskId,
     upstream: StreamName,
     sink_writer_name: SinkWriterName,
+    latency_slo: Option<WallClockDuration>,
+    filter: Option<ValueExpr>,
+    projection: Option<Vec<ColumnName>>,
 }
 
 impl SinkTask {
+    /// # Panics
+    ///
+    /// `sink_writer` has invalid `LATENCY_SLO_MSEC` option
     pub fn new(sink_writer: &SinkWriterModel) -> Self {
         let id = TaskId::from_sink(sink_writer);
         Self {
             id,
             upstream: sink_writer.sink_upstream().clone(),
             sink_writer_name: sink_writer.name().clone(),
+            latency_slo: sink_writer
+                .latency_slo()
+                .expect("LATENCY_SLO_MSEC must be validated on CREATE SINK WRITER"),
+            filter: sink_writer.filter().cloned(),
+            projection: sink_writer.projection().map(|columns| columns.to_vec()),
         }
     }
 
@@ -46,6 +61,11 @@ impl SinkTask {
         &self.id
     }
 
+    /// A row which cannot be output (e.g. NULL in `BLOB_BODY_COLUMN`) is skipped, logged, and put into `spring._row_errors`.
+    ///
+    /// # Failure
+    ///
+    /// - Raises Err from the sink writer as-is, other than errors in the row.
     pub fn run(&self, context: &TaskContext) -> Result<TaskRunResult> {
         let stopwatch = WallClockStopwatch::start();
 
@@ -56,21 +76,44 @@ impl SinkTask {
             .task_graph()
             .input_queue(&context.task(), &self.upstream);
 
-        let (processed_rows, in_queues_metrics) = if let Some(in_queue_id) = opt_in_queue_id {
-            if let Some((row, in_queue_metrics)) = self.use_row_from(in_queue_id, repos) {
-                self.emit(row, context)?;
-                (ProcessedRows::new(1), vec![in_queue_metrics])
+        let mut n_row_errors = 0;
+        let (processed_rows, in_queues_metrics, sink_latency, sink_health) =
+            if let Some(in_queue_id) = opt_in_queue_id {
+                if let Some((row, in_queue_metrics)) = self.use_row_from(in_queue_id, repos) {
+                    let rowtime = row.rowtime().as_timestamp();
+                    let (sink_latency, sink_health) = match self.emit(row, context) {
+                        Ok(sink_health) => (self.latency_since(rowtime), sink_health),
+                        Err(e) if Self::is_row_error(&e) => {
+                            self.report_row_error(e, context);
+                            n_row_errors = 1;
+                            (None, None)
+                        }
+                        Err(e) => return Err(e),
+                    };
+                    (
+                        ProcessedRows::new(1),
+                        vec![in_queue_metrics],
+                        sink_latency,
+                        sink_health,
+                    )
+                } else {
+                    (ProcessedRows::default(), vec![], None, None)
+                }
             } else {
-                (ProcessedRows::default(), vec![])
-            }
-        } else {
-            (ProcessedRows::default(), vec![])
-        };
+                (ProcessedRows::default(), vec![], None, None)
+            };
 
         let execution_time = stopwatch.stop();
 
         let out_queues_metrics = vec![];
-        let task_metrics = TaskMetricsUpdateByTask::new(context.task(), execution_time);
+        let mut task_metrics = TaskMetricsUpdateByTask::new(context.task(), execution_time)
+            .with_row_errors(n_row_errors);
+        if let Some(sink_latency) = sink_latency {
+            task_metrics = task_metrics.with_sink_latency(sink_latency);
+        }
+        if let Some(sink_health) = sink_health {
+            task_metrics = task_metrics.with_sink_health(sink_health);
+        }
         let metrics =
             MetricsUpdateByTaskExecution::new(task_metrics, in_queues_metrics, out_queues_metrics);
         Ok(TaskRunResult {
@@ -79,6 +122,50 @@ impl SinkTask {
         })
     }
 
+    /// Whether `e` is caused by the row itself rather than the foreign sink, so that retrying the row never succeeds.
+    fn is_row_error(e: &SpringError) -> bool {
+        matches!(
+            e,
+            SpringError::Sql(_) | SpringError::InvalidFormat { .. } | SpringError::Null { .. }
+        )
+    }
+
+    fn report_row_error(&self, e: SpringError, context: &TaskContext) {
+        let row_error = RowError::new(format!("SINK WRITER {}", self.sink_writer_name), e);
+        let task = self.id.to_string();
+        let upstream = self.upstream.to_string();
+
+        let repos = context.repos();
+        repos.row_error_logger().log(&task, &upstream, &row_error);
+        repos
+            .system_stream_repository()
+            .put_row_error(&task, &upstream, &row_error);
+    }
+
+    /// Latency from `rowtime` until now. Logs a warning when it violates the latency SLO.
+    ///
+    /// # Returns
+    ///
+    /// `None` if `rowtime` is in the future (e.g. event time from a clock ahead of this host).
+    fn latency_since(&self, rowtime: SpringTimestamp) -> Option<WallClockDuration> {
+        let latency = (SystemTimestamp::now() - rowtime)
+            .to_std()
+            .ok()
+            .map(WallClockDuration::from_std)?;
+
+        if let Some(latency_slo) = self.latency_slo {
+            if latency > latency_slo {
+                log::warn!(
+                    "[SinkTask] latency SLO violation on {}: {} ms > {} ms",
+                    self.sink_writer_name,
+                    latency.as_std().as_millis(),
+                    latency_slo.as_std().as_millis()
+                );
+            }
+        }
+        Some(latency)
+    }
+
     fn use_row_from(
         &self,
         queue_id: QueueId,
@@ -89,7 +176,7 @@ impl SinkTask {
                 let row_q_repo = repos.row_queue_repository();
                 let queue = row_q_repo.get(&queue_id);
                 queue.use_().map(|row| {
-                    let bytes_used = row.mem_size();
+                    let bytes_used = row.stored_size();
                     (
                         row,
                         InQueueMetricsUpdateByTask::new(
@@ -107,17 +194,58 @@ impl SinkTask {
         }
     }
 
-    fn emit(&self, row: StreamRow, context: &TaskContext) -> Result<()> {
+    /// # Returns
+    ///
+    /// Health of the foreign sink reported by the sink writer.
+    /// `None` if the row is filtered out and not sent.
+    fn emit(&self, row: StreamRow, context: &TaskContext) -> Result<Option<SinkHealth>> {
+        if !self.passes_filter(&row)? {
+            return Ok(None);
+        }
+        let row = self.project(row.into())?;
+
         let sink_writer = context
             .repos()
             .sink_writer_repository()
             .get_sink_writer(&self.sink_writer_name);
 
-        sink_writer
+        let mut sink_writer = sink_writer
             .lock()
-            .expect("other worker threads sharing the same sink subtask must not get panic")
-            .send_row(row.into())?;
+            .expect("other worker threads sharing the same sink subtask must not get panic");
+        sink_writer.send_row(row)?;
+
+        Ok(sink_writer.health())
+    }
 
-        Ok(())
+    /// # Returns
+    ///
+    /// true if the row satisfies `FILTER WHERE` condition (NULL is evaluated as FALSE) or no filter is set.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - the condition is not evaluated as BOOLEAN.
+    fn passes_filter(&self, row: &StreamRow) -> Result<bool> {
+        match &self.filter {
+            Some(filter) => {
+                let tuple = Tuple::from_row(row.clone());
+                filter.clone().resolve_colref(&tuple)?.eval()?.to_bool()
+            }
+            None => Ok(true),
+        }
+    }
+
+    /// Keeps only the columns in `PROJECT` clause, in the clause order.
+    fn project(&self, row: SchemalessRow) -> Result<SchemalessRow> {
+        match &self.projection {
+            Some(columns) => {
+                let mut projected = SchemalessRow::with_capacity(columns.len());
+                for column in columns {
+                    projected.insert(column.clone(), row.get_by_column_name(column)?.clone())?;
+                }
+                Ok(projected)
+            }
+            None => Ok(row),
+        }
     }
 }
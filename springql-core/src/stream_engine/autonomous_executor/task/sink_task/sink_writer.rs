@@ -1,20 +1,45 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+#[cfg(feature = "sink-gpio")]
+mod gpio;
+#[cfg(feature = "sink-cloud-iot")]
+mod cloud_iot;
+mod command;
+mod file;
 mod in_memory_queue;
+mod influxdb;
+#[cfg(feature = "mqtt")]
+mod mqtt;
+#[cfg(feature = "nats-messaging")]
+mod nats;
 mod net;
+mod offline_spool;
+#[cfg(feature = "sink-parquet")]
+mod parquet;
+#[cfg(feature = "redis-stream")]
+mod redis_stream;
+#[cfg(feature = "sink-http")]
 mod http_client;
+#[cfg(feature = "sink-s3")]
+mod s3;
 mod sink_writer_factory;
 mod sink_writer_repository;
+mod springql_link;
+#[cfg(unix)]
+mod unix_socket;
+#[cfg(feature = "zeromq")]
+mod zeromq;
 
 pub use net::NetSinkWriter;
 pub use sink_writer_repository::SinkWriterRepository;
+pub use springql_link::SpringqlLinkSinkWriter;
 
 use std::fmt::Debug;
 
 use crate::{
     api::{error::Result, SpringSinkWriterConfig},
     pipeline::Options,
-    stream_engine::autonomous_executor::SchemalessRow,
+    stream_engine::autonomous_executor::{performance_metrics::SinkHealth, SchemalessRow},
 };
 
 /// Instance of SinkWriterModel.
@@ -36,4 +61,11 @@ pub trait SinkWriter: Debug + Sync + Send + 'static {
     /// - `SpringError::Null` when:
     ///   - `row` contains unexpected NULL column.
     fn send_row(&mut self, row: SchemalessRow) -> Result<()>;
+
+    /// Health of the foreign sink reported in task metrics after each `send_row()`.
+    ///
+    /// `None` for sink writers without retries or circuit breaker.
+    fn health(&self) -> Option<SinkHealth> {
+        None
+    }
 }
@@ -0,0 +1,328 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Sink writers for MQTT-based cloud IoT endpoints (Azure IoT Hub and AWS IoT Core).
+//!
+//! Connection details required by each cloud (client ID, user name, TLS, authentication and topic structure)
+//! are derived from a few options so that users need not hand-configure raw MQTT options.
+
+use std::{
+    fmt::Debug,
+    fs,
+    path::Path,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context;
+use hmac::{Hmac, Mac};
+use rumqttc::{Client, Event, Key, MqttOptions, Outgoing, Packet, QoS, Transport};
+use sha2::Sha256;
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSinkWriterConfig,
+    pipeline::{
+        validate_aws_iot_topic, AwsIotCoreOptions, AzureIotHubAuth, AzureIotHubOptions,
+        JsonShapeOptions, Options,
+    },
+    stream_engine::autonomous_executor::{task::sink_task::sink_writer::SinkWriter, SchemalessRow},
+};
+
+const AZURE_IOT_HUB_API_VERSION: &str = "2021-04-12";
+const AWS_IOT_CORE_ALPN: &str = "x-amzn-mqtt-ca";
+
+/// Publishes without waiting for the broker while this number of requests are in flight.
+const REQUEST_QUEUE_CAPACITY: usize = 100;
+const KEEP_ALIVE: Duration = Duration::from_secs(30);
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Sends a row as a JSON device-to-cloud message to Azure IoT Hub (`devices/{device_id}/messages/events/`).
+///
+/// With `SHARED_ACCESS_KEY`, a SAS token valid for `SAS_TTL_SECS` is generated and the connection is renewed before the token expires.
+#[derive(Debug)]
+pub struct AzureIotHubSinkWriter {
+    options: AzureIotHubOptions,
+    topic: String,
+    uplink: MqttUplink,
+    /// Time to renew the SAS token (`None` for X.509 authentication).
+    renew_at: Option<SystemTime>,
+
+    json_shape: JsonShapeOptions,
+}
+
+impl SinkWriter for AzureIotHubSinkWriter {
+    fn start(options: &Options, _config: &SpringSinkWriterConfig) -> Result<Self> {
+        let json_shape = JsonShapeOptions::try_from(options)?;
+        let options = AzureIotHubOptions::try_from(options)?;
+        // message properties let IoT Hub routing queries look into the JSON body
+        let topic = format!(
+            "devices/{}/messages/events/$.ct=application%2Fjson&$.ce=utf-8",
+            options.device_id
+        );
+        let (uplink, renew_at) = Self::connect(&options)?;
+
+        log::info!(
+            "[AzureIotHubSinkWriter] Ready to publish to {} as {}",
+            options.host_name,
+            options.device_id
+        );
+
+        Ok(Self {
+            json_shape,
+            options,
+            topic,
+            uplink,
+            renew_at,
+        })
+    }
+
+    fn send_row(&mut self, row: SchemalessRow) -> Result<()> {
+        if matches!(self.renew_at, Some(renew_at) if SystemTime::now() >= renew_at) {
+            log::info!("[AzureIotHubSinkWriter] Renewing SAS token");
+            let (uplink, renew_at) = Self::connect(&self.options)?;
+            self.uplink = uplink;
+            self.renew_at = renew_at;
+        }
+
+        let payload = row.into_json(&self.json_shape)?.to_string();
+        self.uplink
+            .publish(self.topic.clone(), self.options.qos, payload)
+    }
+}
+
+impl AzureIotHubSinkWriter {
+    fn connect(options: &AzureIotHubOptions) -> Result<(MqttUplink, Option<SystemTime>)> {
+        let mut mqtt_options =
+            MqttOptions::new(&options.device_id, &options.host_name, options.port);
+        let user_name = format!(
+            "{}/{}/?api-version={}",
+            options.host_name, options.device_id, AZURE_IOT_HUB_API_VERSION
+        );
+
+        let (client_auth, renew_at) = match &options.auth {
+            AzureIotHubAuth::SharedAccessKey { key, token_ttl } => {
+                let expires_at = SystemTime::now() + *token_ttl;
+                let expiry_secs = expires_at
+                    .duration_since(UNIX_EPOCH)
+                    .expect("system time after UNIX epoch")
+                    .as_secs();
+                let resource_uri = format!("{}/devices/{}", options.host_name, options.device_id);
+                mqtt_options.set_credentials(user_name, sas_token(&resource_uri, key, expiry_secs));
+                // renews after 90% of TTL not to be disconnected by the hub
+                (None, Some(SystemTime::now() + token_ttl.mul_f32(0.9)))
+            }
+            AzureIotHubAuth::X509 {
+                cert_file,
+                key_file,
+            } => {
+                mqtt_options.set_credentials(user_name, "");
+                (Some(client_auth(cert_file, key_file)?), None)
+            }
+        };
+
+        let transport = Transport::tls(read_pem(&options.ca_file)?, client_auth, None);
+        mqtt_options
+            .set_transport(transport)
+            .set_keep_alive(KEEP_ALIVE);
+
+        let uplink = MqttUplink::connect(
+            mqtt_options,
+            format!("{}:{}", options.host_name, options.port),
+        );
+        Ok((uplink, renew_at))
+    }
+}
+
+/// Sends a row as a JSON message to AWS IoT Core.
+///
+/// `TOPIC` may contain `${column}` placeholders filled from each row.
+#[derive(Debug)]
+pub struct AwsIotCoreSinkWriter {
+    options: AwsIotCoreOptions,
+    uplink: MqttUplink,
+
+    json_shape: JsonShapeOptions,
+}
+
+impl SinkWriter for AwsIotCoreSinkWriter {
+    fn start(options: &Options, _config: &SpringSinkWriterConfig) -> Result<Self> {
+        let json_shape = JsonShapeOptions::try_from(options)?;
+        let options = AwsIotCoreOptions::try_from(options)?;
+
+        let mut mqtt_options =
+            MqttOptions::new(&options.client_id, &options.endpoint, options.port);
+        let alpn = (options.port == 443).then(|| vec![AWS_IOT_CORE_ALPN.as_bytes().to_vec()]);
+        let transport = Transport::tls(
+            read_pem(&options.ca_file)?,
+            Some(client_auth(&options.cert_file, &options.key_file)?),
+            alpn,
+        );
+        mqtt_options
+            .set_transport(transport)
+            .set_keep_alive(KEEP_ALIVE);
+
+        let uplink = MqttUplink::connect(
+            mqtt_options,
+            format!("{}:{}", options.endpoint, options.port),
+        );
+
+        log::info!(
+            "[AwsIotCoreSinkWriter] Ready to publish to {} as {}",
+            options.endpoint,
+            options.client_id
+        );
+
+        Ok(Self {
+            options,
+            uplink,
+            json_shape,
+        })
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Column in topic placeholders is not found or NULL.
+    ///   - Filled topic violates AWS IoT Core restrictions.
+    fn send_row(&mut self, row: SchemalessRow) -> Result<()> {
+        let topic = row.render(&self.options.topic)?;
+        validate_aws_iot_topic(&topic).map_err(SpringError::Sql)?;
+
+        let payload = row.into_json(&self.json_shape)?.to_string();
+        self.uplink.publish(topic, self.options.qos, payload)
+    }
+}
+
+/// MQTT connection driven by a background thread, which reconnects on errors.
+struct MqttUplink {
+    broker: String,
+    client: Client,
+}
+
+impl MqttUplink {
+    fn connect(mqtt_options: MqttOptions, broker: String) -> Self {
+        let (client, mut connection) = Client::new(mqtt_options, REQUEST_QUEUE_CAPACITY);
+
+        let thread_broker = broker.clone();
+        thread::spawn(move || {
+            // ends when the client is dropped or disconnects
+            for notification in connection.iter() {
+                match notification {
+                    Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                        log::info!("[MqttUplink] Connected to {}", thread_broker)
+                    }
+                    Ok(Event::Outgoing(Outgoing::Disconnect)) => break,
+                    Ok(_) => {}
+                    Err(e) => {
+                        log::warn!(
+                            "[MqttUplink] Connection to {} failed (retrying in {:?}): {:?}",
+                            thread_broker,
+                            RECONNECT_INTERVAL,
+                            e
+                        );
+                        thread::sleep(RECONNECT_INTERVAL);
+                    }
+                }
+            }
+        });
+
+        Self { broker, client }
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - the request queue is full (e.g. disconnected for long).
+    fn publish(&mut self, topic: String, qos: u8, payload: String) -> Result<()> {
+        let qos = if qos == 0 {
+            QoS::AtMostOnce
+        } else {
+            QoS::AtLeastOnce
+        };
+        self.client
+            .try_publish(topic, qos, false, payload)
+            .context("failed to publish")
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::Mqtt(self.broker.clone()),
+            })
+    }
+}
+
+impl Debug for MqttUplink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MqttUplink")
+            .field("broker", &self.broker)
+            .finish()
+    }
+}
+
+impl Drop for MqttUplink {
+    fn drop(&mut self) {
+        // sent after queued publishes
+        let _ = self.client.try_disconnect();
+    }
+}
+
+/// SAS token for `resource_uri` (e.g. `{host_name}/devices/{device_id}`) valid until `expiry_secs` (UNIX time).
+fn sas_token(resource_uri: &str, key: &[u8], expiry_secs: u64) -> String {
+    let resource_uri = percent_encode(resource_uri);
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(format!("{}\n{}", resource_uri, expiry_secs).as_bytes());
+    let signature = base64::encode(mac.finalize().into_bytes());
+
+    format!(
+        "SharedAccessSignature sr={}&sig={}&se={}",
+        resource_uri,
+        percent_encode(&signature),
+        expiry_secs
+    )
+}
+
+/// Percent-encodes all characters but unreserved ones (RFC 3986).
+fn percent_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn client_auth(cert_file: &Path, key_file: &Path) -> Result<(Vec<u8>, Key)> {
+    let cert = read_pem(cert_file)?;
+    let key = read_pem(key_file)?;
+    // PKCS#1 RSA keys (issued by AWS IoT Core) or PKCS#8 keys
+    let key = if String::from_utf8_lossy(&key).contains("BEGIN RSA PRIVATE KEY") {
+        Key::RSA(key)
+    } else {
+        Key::ECC(key)
+    };
+    Ok((cert, key))
+}
+
+fn read_pem(path: &Path) -> Result<Vec<u8>> {
+    fs::read(path)
+        .context("failed to read PEM file")
+        .map_err(|e| SpringError::ForeignIo {
+            source: e,
+            foreign_info: ForeignInfo::File(path.to_path_buf()),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sas_token() {
+        let key = base64::decode("MDEyMzQ1Njc4OWFiY2RlZjAxMjM0NTY3ODlhYmNkZWY=").unwrap();
+        assert_eq!(
+            sas_token("my-hub.azure-devices.net/devices/device-1", &key, 1700000000),
+            "SharedAccessSignature sr=my-hub.azure-devices.net%2Fdevices%2Fdevice-1&sig=Ku4bEMolraSMKOGZrcDresMPNBGuaZtMeKlYimOPcxM%3D&se=1700000000"
+        );
+    }
+}
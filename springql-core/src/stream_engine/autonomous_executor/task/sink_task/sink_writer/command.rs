@@ -0,0 +1,217 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    collections::VecDeque,
+    io::Write,
+    path::Path,
+    process::{Child, Command, ExitStatus, Stdio},
+};
+
+use anyhow::Context;
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSinkWriterConfig,
+    pipeline::{CommandOptions, JsonShapeOptions, Options},
+    stream_engine::autonomous_executor::{task::sink_task::sink_writer::SinkWriter, SchemalessRow},
+};
+
+/// Runs a command per `BATCH_SIZE` rows, writing the rows into its stdin as JSON lines.
+///
+/// At most `MAX_CONCURRENCY` runs are in progress; sending a batch waits for the oldest run beyond that.
+/// A command exiting with failure is logged and does not stop the sink.
+///
+/// Rows in an incomplete batch are sent when the sink writer is dropped.
+#[derive(Debug)]
+pub struct CommandSinkWriter {
+    options: CommandOptions,
+
+    /// JSON lines of rows not sent yet.
+    batch: Vec<String>,
+    /// Command runs in progress (oldest first).
+    running: VecDeque<Child>,
+
+    json_shape: JsonShapeOptions,
+}
+
+impl SinkWriter for CommandSinkWriter {
+    fn start(options: &Options, _config: &SpringSinkWriterConfig) -> Result<Self> {
+        let json_shape = JsonShapeOptions::try_from(options)?;
+        let options = CommandOptions::try_from(options)?;
+
+        log::info!(
+            "[CommandSinkWriter] Ready to run {:?} per {} rows",
+            options.command,
+            options.batch_size
+        );
+
+        Ok(Self {
+            json_shape,
+            batch: Vec::with_capacity(options.batch_size),
+            running: VecDeque::new(),
+            options,
+        })
+    }
+
+    fn send_row(&mut self, row: SchemalessRow) -> Result<()> {
+        self.batch
+            .push(row.into_json(&self.json_shape)?.to_string());
+        if self.batch.len() >= self.options.batch_size {
+            self.send_batch()?;
+        }
+        Ok(())
+    }
+}
+
+impl CommandSinkWriter {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to run the command.
+    ///   - failed to write rows into the stdin of the command.
+    fn send_batch(&mut self) -> Result<()> {
+        self.reap_finished();
+        while self.running.len() >= self.options.max_concurrency {
+            let oldest = self.running.pop_front().expect("max_concurrency > 0");
+            self.wait(oldest);
+        }
+
+        let mut stdin_content = self.batch.join("\n");
+        stdin_content.push('\n');
+        self.batch.clear();
+
+        let mut child = Command::new(&self.options.command)
+            .args(&self.options.args)
+            .stdin(Stdio::piped())
+            .spawn()
+            .context("failed to run command")
+            .map_err(|e| self.foreign_io(e))?;
+
+        // stdin is closed on drop so that the command reads EOF
+        let write_result = child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(stdin_content.as_bytes());
+        self.running.push_back(child);
+
+        write_result
+            .with_context(|| format!("failed to write rows into stdin: {}", stdin_content))
+            .map_err(|e| self.foreign_io(e))
+    }
+
+    fn reap_finished(&mut self) {
+        let mut running = VecDeque::with_capacity(self.running.len());
+        while let Some(mut child) = self.running.pop_front() {
+            match child.try_wait() {
+                Ok(Some(status)) => log_failure(&self.options.command, status),
+                Ok(None) => running.push_back(child),
+                Err(e) => log::warn!(
+                    "[CommandSinkWriter] failed to wait {:?}: {:?}",
+                    self.options.command,
+                    e
+                ),
+            }
+        }
+        self.running = running;
+    }
+
+    fn wait(&self, mut child: Child) {
+        match child.wait() {
+            Ok(status) => log_failure(&self.options.command, status),
+            Err(e) => log::warn!(
+                "[CommandSinkWriter] failed to wait {:?}: {:?}",
+                self.options.command,
+                e
+            ),
+        }
+    }
+
+    fn foreign_io(&self, source: anyhow::Error) -> SpringError {
+        SpringError::ForeignIo {
+            source,
+            foreign_info: ForeignInfo::Command(self.options.command.clone()),
+        }
+    }
+}
+
+impl Drop for CommandSinkWriter {
+    fn drop(&mut self) {
+        if !self.batch.is_empty() {
+            if let Err(e) = self.send_batch() {
+                log::warn!("[CommandSinkWriter] failed to send the last batch: {:?}", e);
+            }
+        }
+        while let Some(child) = self.running.pop_front() {
+            self.wait(child);
+        }
+    }
+}
+
+fn log_failure(command: &Path, status: ExitStatus) {
+    if !status.success() {
+        log::warn!("[CommandSinkWriter] {:?} exited with {}", command, status);
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{fs, os::unix::fs::PermissionsExt};
+
+    use crate::{
+        pipeline::{ColumnName, OptionsBuilder},
+        stream_engine::{NnSqlValue, SqlValue},
+    };
+
+    use super::*;
+
+    fn row(n: i32) -> SchemalessRow {
+        let mut row = SchemalessRow::default();
+        row.insert(
+            ColumnName::new("n".to_string()),
+            SqlValue::NotNull(NnSqlValue::Integer(n)),
+        )
+        .unwrap();
+        row
+    }
+
+    #[test]
+    fn test_command_sink_writer_batch() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("append.sh");
+        fs::write(&script, "#!/bin/sh\ncat >> \"$1\"\n").unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        let out = dir.path().join("out.jsonl");
+
+        let options = OptionsBuilder::default()
+            .add("COMMAND", script.to_str().unwrap())
+            .add("ARGS", out.to_str().unwrap())
+            .add("BATCH_SIZE", "2")
+            .build();
+        let mut sink_writer =
+            CommandSinkWriter::start(&options, &SpringSinkWriterConfig::fx_default()).unwrap();
+        for n in 1..=3 {
+            sink_writer.send_row(row(n)).unwrap();
+        }
+        // sends the incomplete batch and waits for all runs
+        drop(sink_writer);
+
+        assert_eq!(
+            fs::read_to_string(&out).unwrap(),
+            "{\"n\":1}\n{\"n\":2}\n{\"n\":3}\n"
+        );
+    }
+
+    #[test]
+    fn test_command_not_found() {
+        let options = OptionsBuilder::default()
+            .add("COMMAND", "/nonexistent/command")
+            .build();
+        let mut sink_writer =
+            CommandSinkWriter::start(&options, &SpringSinkWriterConfig::fx_default()).unwrap();
+        assert!(matches!(
+            sink_writer.send_row(row(1)).unwrap_err(),
+            SpringError::ForeignIo { .. }
+        ));
+    }
+}
@@ -0,0 +1,246 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSinkWriterConfig,
+    pipeline::{FileSinkFormat, FileSinkOptions, JsonShapeOptions, Options},
+    stream_engine::autonomous_executor::{
+        row::CsvWriter, task::sink_task::sink_writer::SinkWriter, SchemalessRow,
+    },
+};
+
+/// Appends a line per row to `PATH`, as a JSON object (shaped by `JsonShapeOptions`) or a CSV record.
+///
+/// The file is rotated to `<PATH>.<n>` (`n` = 1, 2, ...) when it reaches `MAX_FILE_SIZE_BYTES` or `ROLL_INTERVAL_SEC`
+/// has passed since it was opened, and a new file is started at `PATH`. Empty files are not rotated.
+///
+/// Each line is written by a single `write` call without buffering, so that readers never see a partial line
+/// unless the disk is full.
+#[derive(Debug)]
+pub struct FileSinkWriter {
+    options: FileSinkOptions,
+    json_shape: JsonShapeOptions,
+
+    file: File,
+    /// Bytes in the current file.
+    size: u64,
+    opened_at: Instant,
+    /// `n` of the next `<PATH>.<n>`.
+    next_rotation_seq: u64,
+}
+
+impl SinkWriter for FileSinkWriter {
+    /// Appends to `PATH` if it exists.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to open the file.
+    /// - `SpringError::InvalidOption`
+    fn start(options: &Options, _config: &SpringSinkWriterConfig) -> Result<Self> {
+        let json_shape = JsonShapeOptions::try_from(options)?;
+        let options = FileSinkOptions::try_from(options)?;
+
+        let next_rotation_seq = last_rotation_seq(&options.path) + 1;
+        let (file, size) = open(&options.path)?;
+
+        log::info!(
+            "[FileSinkWriter] Ready to write into {}",
+            options.path.display()
+        );
+        Ok(Self {
+            options,
+            json_shape,
+            file,
+            size,
+            opened_at: Instant::now(),
+            next_rotation_seq,
+        })
+    }
+
+    fn send_row(&mut self, row: SchemalessRow) -> Result<()> {
+        if let Some(roll_interval) = self.options.roll_interval {
+            if self.size > 0 && self.opened_at.elapsed() >= roll_interval {
+                self.rotate()?;
+            }
+        }
+
+        let line = match self.options.format {
+            FileSinkFormat::Json => format!("{}\n", row.into_json(&self.json_shape)?),
+            FileSinkFormat::Csv { header, delimiter } => {
+                let csv_writer = CsvWriter::new(delimiter);
+                let mut line = if header && self.size == 0 {
+                    csv_writer.header(&row)
+                } else {
+                    String::new()
+                };
+                line.push_str(&csv_writer.record(row)?);
+                line
+            }
+        };
+        self.file
+            .write_all(line.as_bytes())
+            .map_err(|e| self.io_error(e, "failed to write row"))?;
+        self.size += line.len() as u64;
+
+        match self.options.max_file_size_bytes {
+            Some(max_file_size) if self.size >= max_file_size => self.rotate(),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl FileSinkWriter {
+    /// Renames the current file to `<PATH>.<n>` and opens a new file at `PATH`.
+    fn rotate(&mut self) -> Result<()> {
+        let rotated = rotated_path(&self.options.path, self.next_rotation_seq);
+        fs::rename(&self.options.path, &rotated)
+            .map_err(|e| self.io_error(e, "failed to rotate file"))?;
+        self.next_rotation_seq += 1;
+        log::info!("[FileSinkWriter] Rotated to {}", rotated.display());
+
+        let (file, size) = open(&self.options.path)?;
+        self.file = file;
+        self.size = size;
+        self.opened_at = Instant::now();
+        Ok(())
+    }
+
+    fn io_error(&self, e: io::Error, context: &'static str) -> SpringError {
+        SpringError::ForeignIo {
+            source: anyhow::Error::from(e).context(context),
+            foreign_info: ForeignInfo::File(self.options.path.clone()),
+        }
+    }
+}
+
+/// Opens `path` to append.
+///
+/// # Returns
+///
+/// The file and its current size.
+fn open(path: &Path) -> Result<(File, u64)> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|file| {
+            let size = file.metadata()?.len();
+            Ok((file, size))
+        })
+        .map_err(|e| SpringError::ForeignIo {
+            source: anyhow::Error::from(e).context("failed to open file"),
+            foreign_info: ForeignInfo::File(path.to_path_buf()),
+        })
+}
+
+fn rotated_path(path: &Path, seq: u64) -> PathBuf {
+    let mut rotated = path.as_os_str().to_os_string();
+    rotated.push(format!(".{}", seq));
+    PathBuf::from(rotated)
+}
+
+/// Largest `n` of existing `<path>.<n>`, or 0 if none.
+fn last_rotation_seq(path: &Path) -> u64 {
+    let (dir, file_name) = match (path.parent(), path.file_name()) {
+        (Some(dir), Some(file_name)) => (dir, file_name.to_string_lossy()),
+        _ => return 0,
+    };
+    let dir = if dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        dir
+    };
+    let prefix = format!("{}.", file_name);
+
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| {
+                    let name = entry.ok()?.file_name();
+                    name.to_str()?.strip_prefix(&prefix)?.parse::<u64>().ok()
+                })
+                .max()
+                .unwrap_or(0)
+        })
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        pipeline::{ColumnName, OptionsBuilder},
+        stream_engine::{NnSqlValue, SqlValue},
+    };
+
+    use super::*;
+
+    fn row(n: i32) -> SchemalessRow {
+        let mut row = SchemalessRow::default();
+        row.insert(
+            ColumnName::new("n".to_string()),
+            SqlValue::NotNull(NnSqlValue::Integer(n)),
+        )
+        .unwrap();
+        row
+    }
+
+    fn start(options: OptionsBuilder) -> FileSinkWriter {
+        FileSinkWriter::start(&options.build(), &SpringSinkWriterConfig::fx_default()).unwrap()
+    }
+
+    #[test]
+    fn test_file_sink_writer_rotation_by_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.jsonl");
+        // rotated by a previous run
+        fs::write(dir.path().join("out.jsonl.3"), "{\"n\":0}\n").unwrap();
+
+        let mut sink_writer = start(
+            OptionsBuilder::default()
+                .add("PATH", path.display().to_string())
+                .add("MAX_FILE_SIZE_BYTES", "16"),
+        );
+        for n in 1..=5 {
+            sink_writer.send_row(row(n)).unwrap();
+        }
+
+        assert_eq!(
+            fs::read_to_string(dir.path().join("out.jsonl.4")).unwrap(),
+            "{\"n\":1}\n{\"n\":2}\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("out.jsonl.5")).unwrap(),
+            "{\"n\":3}\n{\"n\":4}\n"
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"n\":5}\n");
+    }
+
+    #[test]
+    fn test_file_sink_writer_csv_appends() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.csv");
+        let options = || {
+            OptionsBuilder::default()
+                .add("PATH", path.display().to_string())
+                .add("FORMAT", "CSV")
+        };
+
+        let mut sink_writer = start(options());
+        sink_writer.send_row(row(1)).unwrap();
+        drop(sink_writer);
+
+        // header is not written again to the existing file
+        let mut sink_writer = start(options());
+        sink_writer.send_row(row(2)).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "n\n1\n2\n");
+    }
+}
@@ -0,0 +1,129 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context};
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSinkWriterConfig,
+    pipeline::{ColumnName, GpioOptions, Options},
+    stream_engine::{
+        autonomous_executor::{task::sink_task::sink_writer::SinkWriter, SchemalessRow},
+        SqlValue,
+    },
+};
+
+/// Drives a GPIO output pin from a BOOLEAN column via the Linux sysfs interface.
+///
+/// The pin is exported and set to output on start. Its level is written only when the column value changes.
+#[derive(Debug)]
+pub struct GpioSinkWriter {
+    pin_dir: PathBuf,
+    value_column: ColumnName,
+    level: Option<bool>,
+}
+
+impl SinkWriter for GpioSinkWriter {
+    fn start(options: &Options, _config: &SpringSinkWriterConfig) -> Result<Self> {
+        let options = GpioOptions::try_from(options)?;
+        let pin_dir = options.sysfs_root.join(format!("gpio{}", options.pin));
+
+        if !pin_dir.exists() {
+            write_sysfs(&options.sysfs_root.join("export"), &options.pin.to_string())?;
+        }
+        write_sysfs(&pin_dir.join("direction"), "out")?;
+        write_sysfs(
+            &pin_dir.join("active_low"),
+            if options.active_low { "1" } else { "0" },
+        )?;
+
+        log::info!("[GpioSinkWriter] Ready to drive {:?}", pin_dir);
+
+        Ok(Self {
+            pin_dir,
+            value_column: options.value_column,
+            level: None,
+        })
+    }
+
+    fn send_row(&mut self, row: SchemalessRow) -> Result<()> {
+        let level = match row.get_by_column_name(&self.value_column)? {
+            SqlValue::NotNull(nn_sql_value) => nn_sql_value.unpack::<bool>()?,
+            SqlValue::Null => {
+                return Err(SpringError::Sql(anyhow!(
+                    "cannot drive GPIO pin with NULL column: {}",
+                    self.value_column
+                )))
+            }
+        };
+
+        if self.level != Some(level) {
+            write_sysfs(&self.pin_dir.join("value"), if level { "1" } else { "0" })?;
+            self.level = Some(level);
+        }
+        Ok(())
+    }
+}
+
+/// # Failure
+///
+/// - `SpringError::ForeignIo` when:
+///   - failed to write into the sysfs file (e.g. the pin is not available or no permission).
+fn write_sysfs(path: &Path, value: &str) -> Result<()> {
+    fs::write(path, value)
+        .with_context(|| format!("failed to write `{}` into GPIO sysfs", value))
+        .map_err(|e| SpringError::ForeignIo {
+            source: e,
+            foreign_info: ForeignInfo::File(path.to_path_buf()),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{pipeline::OptionsBuilder, stream_engine::NnSqlValue};
+
+    use super::*;
+
+    fn row(on: Option<bool>) -> SchemalessRow {
+        let value = on.map_or(SqlValue::Null, |on| {
+            SqlValue::NotNull(NnSqlValue::Boolean(on))
+        });
+        let mut row = SchemalessRow::default();
+        row.insert(ColumnName::new("alert".to_string()), value)
+            .unwrap();
+        row
+    }
+
+    #[test]
+    fn test_gpio_sink_writer() {
+        let sysfs_root = tempfile::tempdir().unwrap();
+        let pin_dir = sysfs_root.path().join("gpio17");
+        // already exported
+        fs::create_dir(&pin_dir).unwrap();
+
+        let options = OptionsBuilder::default()
+            .add("PIN", "17")
+            .add("VALUE_COLUMN", "alert")
+            .add("SYSFS_ROOT", sysfs_root.path().to_str().unwrap())
+            .build();
+        let mut sink_writer =
+            GpioSinkWriter::start(&options, &SpringSinkWriterConfig::fx_default()).unwrap();
+        assert_eq!(
+            fs::read_to_string(pin_dir.join("direction")).unwrap(),
+            "out"
+        );
+        assert_eq!(fs::read_to_string(pin_dir.join("active_low")).unwrap(), "0");
+
+        sink_writer.send_row(row(Some(true))).unwrap();
+        assert_eq!(fs::read_to_string(pin_dir.join("value")).unwrap(), "1");
+
+        sink_writer.send_row(row(Some(false))).unwrap();
+        assert_eq!(fs::read_to_string(pin_dir.join("value")).unwrap(), "0");
+
+        assert!(sink_writer.send_row(row(None)).is_err());
+    }
+}
@@ -1,30 +1,63 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use std::{net::SocketAddr, time::Duration};
+use std::{
+    net::SocketAddr,
+    thread,
+    time::{Duration, Instant},
+};
+
+use reqwest::{
+    blocking::Client,
+    header::{HeaderMap, HeaderValue, CONTENT_TYPE},
+    Method, Url,
+};
 
-use reqwest::{header::HeaderMap, Method, Url};
+use anyhow::{anyhow, Context};
 
 use crate::{
     api::error::{foreign_info::ForeignInfo, Result, SpringError},
     api::SpringSinkWriterConfig,
-    pipeline::{ColumnName, Http1ClientOptions, HttpMethod, Options},
+    pipeline::{
+        ColumnName, ColumnTemplate, Http1ClientOptions, HttpMethod, JsonShapeOptions, Options,
+    },
     stream_engine::{
-        autonomous_executor::{task::sink_task::sink_writer::SinkWriter, SchemalessRow},
+        autonomous_executor::{
+            performance_metrics::SinkHealth, task::sink_task::sink_writer::SinkWriter,
+            SchemalessRow,
+        },
         SqlValue,
     },
 };
 
+/// Sends rows by HTTP requests.
+///
+/// - With `BLOB_BODY_COLUMN`, the BLOB column of a row is the body of a request.
+/// - Otherwise, a row is sent as a JSON object, or `BATCH_SIZE` rows are sent as a JSON array.
+///   Rows in an incomplete batch are sent when the sink writer is dropped.
+///
+/// Failed requests (connection errors and 5xx responses) are retried with exponential backoff.
+/// After consecutive failures, the circuit breaker opens and rows are dropped without requests for a while.
 #[derive(Debug)]
 pub struct HttpClientSinkWriter {
     foreign_addr: SocketAddr,
-
-    timeout: Duration,
-    connect_timeout: Duration,
+    client: Client,
 
     http_method: Method,
-    url: Url,
+    url: ColumnTemplate,
     http_headers: HeaderMap,
-    http_body_blob_column: ColumnName,
+    http_body_blob_column: Option<ColumnName>,
+
+    batch_size: Option<usize>,
+    /// URL and rows waiting for the batch to be filled.
+    batch: Option<(Url, Vec<serde_json::Value>)>,
+
+    retries: u32,
+    retry_backoff: Duration,
+    circuit_breaker: CircuitBreaker,
+
+    health: SinkHealth,
+
+    json_shape: JsonShapeOptions,
 }
 impl From<HttpMethod> for Method {
     fn from(m: HttpMethod) -> Self {
@@ -36,51 +69,166 @@ impl From<HttpMethod> for Method {
 
 impl SinkWriter for HttpClientSinkWriter {
     fn start(options: &Options, config: &SpringSinkWriterConfig) -> Result<Self> {
+        let json_shape = JsonShapeOptions::try_from(options)?;
         let options = Http1ClientOptions::try_from(options)?;
         let sock_addr = SocketAddr::new(options.remote_host, options.remote_port);
 
-        let timeout = Duration::from_millis(config.http_timeout_msec as u64);
-        let connect_timeout = Duration::from_millis(config.http_connect_timeout_msec as u64);
+        let client = Client::builder()
+            .connect_timeout(Duration::from_millis(
+                config.http_connect_timeout_msec as u64,
+            ))
+            .timeout(Duration::from_millis(config.http_timeout_msec as u64))
+            .build()
+            .context("failed to create HTTP client")
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::Http(sock_addr),
+            })?;
 
         let http_method = Method::from(options.method);
         let url = options.url.clone();
-        let http_headers =
+        let mut http_headers =
             HeaderMap::try_from(&options.headers).expect("don't know why this fails");
-        let http_body_blob_column = options.blob_body_column;
+        if options.blob_body_column.is_none() {
+            http_headers
+                .entry(CONTENT_TYPE)
+                .or_insert(HeaderValue::from_static("application/json"));
+        }
 
         log::info!("[HttpClientSinkWriter] Ready to connect {}", sock_addr);
 
         Ok(Self {
+            json_shape,
             foreign_addr: sock_addr,
-            timeout,
-            connect_timeout,
+            client,
             http_method,
             url,
             http_headers,
-            http_body_blob_column,
+            http_body_blob_column: options.blob_body_column,
+            batch_size: options.batch_size,
+            batch: None,
+            retries: config.http_retries,
+            retry_backoff: Duration::from_millis(config.http_retry_backoff_msec as u64),
+            circuit_breaker: CircuitBreaker::new(
+                config.http_circuit_breaker_failures,
+                Duration::from_millis(config.http_circuit_breaker_open_msec as u64),
+            ),
+            health: SinkHealth::default(),
         })
     }
 
     fn send_row(&mut self, row: SchemalessRow) -> Result<()> {
-        let blob_column = row.get_by_column_name(&self.http_body_blob_column)?;
-        if let SqlValue::NotNull(nn_sql_value) = blob_column {
-            let body = nn_sql_value.unpack::<Vec<u8>>()?;
-            self.send_request(body)
-        } else {
-            unimplemented!("NULL blob column is not supported yet");
+        let url = self.url(&row)?;
+
+        if let Some(blob_column) = &self.http_body_blob_column {
+            let i_col = row.column_index(blob_column)?;
+            let body = match row.get_by_index(i_col)? {
+                SqlValue::NotNull(nn_sql_value) => nn_sql_value.unpack::<Vec<u8>>()?,
+                SqlValue::Null => return Err(SpringError::Null { i_col }),
+            };
+            return self.send(url, body, 1);
+        }
+
+        let json = serde_json::Value::from(row.into_json(&self.json_shape)?);
+        match self.batch_size {
+            None => self.send(url, json.to_string().into_bytes(), 1),
+            Some(batch_size) => {
+                if matches!(&self.batch, Some((batch_url, _)) if batch_url != &url) {
+                    self.send_batch()?;
+                }
+                let (_, rows) = self.batch.get_or_insert_with(|| (url, vec![]));
+                rows.push(json);
+                if rows.len() >= batch_size {
+                    self.send_batch()
+                } else {
+                    Ok(())
+                }
+            }
         }
     }
+
+    fn health(&self) -> Option<SinkHealth> {
+        Some(SinkHealth {
+            circuit_open: self.circuit_breaker.is_open(),
+            ..self.health
+        })
+    }
 }
 
 impl HttpClientSinkWriter {
-    fn send_request(&mut self, body: Vec<u8>) -> Result<()> {
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Column in URL placeholders is not found or NULL.
+    ///   - Filled URL is invalid.
+    fn url(&self, row: &SchemalessRow) -> Result<Url> {
+        let url = row.render(&self.url)?;
+        Url::parse(&url)
+            .with_context(|| format!("invalid URL filled from row: {}", url))
+            .map_err(SpringError::Sql)
+    }
+
+    fn send_batch(&mut self) -> Result<()> {
+        match self.batch.take() {
+            Some((url, rows)) => {
+                let n_rows = rows.len() as u64;
+                let body = serde_json::Value::Array(rows).to_string().into_bytes();
+                self.send(url, body, n_rows)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Sends a request with retries, or drops `n_rows` rows in the body while the circuit breaker is open.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - the request has failed after retries.
+    fn send(&mut self, url: Url, body: Vec<u8>, n_rows: u64) -> Result<()> {
+        if self.circuit_breaker.is_open() {
+            self.health.dropped_rows += n_rows;
+            return Ok(());
+        }
+
+        let mut backoff = self.retry_backoff;
+        let mut n_retries = 0;
+        loop {
+            match self.request(url.clone(), body.clone()) {
+                Ok(()) => {
+                    self.circuit_breaker.record_success();
+                    return Ok(());
+                }
+                Err(failure) if failure.retryable && n_retries < self.retries => {
+                    log::debug!(
+                        "[HttpClientSinkWriter] retrying in {:?}: {:?}",
+                        backoff,
+                        failure.source
+                    );
+                    thread::sleep(backoff);
+                    backoff *= 2;
+                    n_retries += 1;
+                    self.health.retries += 1;
+                }
+                Err(failure) => {
+                    if failure.retryable && self.circuit_breaker.record_failure() {
+                        log::warn!(
+                            "[HttpClientSinkWriter] circuit breaker opened for {}",
+                            self.foreign_addr
+                        );
+                    }
+                    return Err(SpringError::ForeignIo {
+                        foreign_info: ForeignInfo::Http(self.foreign_addr),
+                        source: failure.source,
+                    });
+                }
+            }
+        }
+    }
+
+    fn request(&mut self, url: Url, body: Vec<u8>) -> std::result::Result<(), RequestFailure> {
         let req_builder = if self.http_method == Method::POST {
-            reqwest::blocking::Client::builder()
-                .connect_timeout(self.connect_timeout)
-                .timeout(self.timeout)
-                .build()
-                .expect("msg: failed to create reqwest client")
-                .post(self.url.to_string())
+            self.client.post(url)
         } else {
             unimplemented!("HTTP method {} is not supported yet", self.http_method);
         };
@@ -89,15 +237,211 @@ impl HttpClientSinkWriter {
         self.http_headers
             .insert("Content-Length", content_length.into());
 
-        let _resp = req_builder
+        let resp = req_builder
             .headers(self.http_headers.clone())
             .body(body)
             .send()
-            .map_err(|e| SpringError::ForeignIo {
-                foreign_info: ForeignInfo::Http(self.foreign_addr),
+            .map_err(|e| RequestFailure {
+                retryable: true,
                 source: e.into(),
             })?;
 
-        Ok(())
+        let status = resp.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            Err(RequestFailure {
+                retryable: status.is_server_error(),
+                source: anyhow!("HTTP request failed with status {}", status),
+            })
+        }
+    }
+}
+
+impl Drop for HttpClientSinkWriter {
+    fn drop(&mut self) {
+        if let Err(e) = self.send_batch() {
+            log::warn!(
+                "[HttpClientSinkWriter] failed to send the last batch: {:?}",
+                e
+            );
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RequestFailure {
+    /// Connection errors and 5xx responses are retryable (and counted by the circuit breaker).
+    retryable: bool,
+    source: anyhow::Error,
+}
+
+#[derive(Debug)]
+struct CircuitBreaker {
+    /// Consecutive failures to open the circuit. 0 disables the circuit breaker.
+    failures_to_open: u32,
+    open_duration: Duration,
+
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    fn new(failures_to_open: u32, open_duration: Duration) -> Self {
+        Self {
+            failures_to_open,
+            open_duration,
+            consecutive_failures: 0,
+            open_until: None,
+        }
+    }
+
+    /// After the open duration, the circuit is half-open: a request is tried and its result closes or reopens the circuit.
+    fn is_open(&self) -> bool {
+        self.open_until
+            .map_or(false, |open_until| Instant::now() < open_until)
+    }
+
+    fn record_success(&mut self) {
+        self.consecutive_failures = 0;
+        self.open_until = None;
+    }
+
+    /// # Returns
+    ///
+    /// true if the circuit is opened by this failure.
+    fn record_failure(&mut self) -> bool {
+        self.consecutive_failures += 1;
+        if self.failures_to_open > 0 && self.consecutive_failures >= self.failures_to_open {
+            self.open_until = Some(Instant::now() + self.open_duration);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+    };
+
+    use crate::{
+        pipeline::OptionsBuilder,
+        stream_engine::{NnSqlValue, SqlValue},
+    };
+
+    use super::*;
+
+    /// Responds with `statuses` in order and sends request bodies to the receiver.
+    fn serve(statuses: Vec<u16>) -> (SocketAddr, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for status in statuses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" {
+                        break;
+                    }
+                    if let Some(v) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = v.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                tx.send(String::from_utf8(body).unwrap()).unwrap();
+
+                write!(
+                    &stream,
+                    "HTTP/1.1 {} X\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                )
+                .unwrap();
+            }
+        });
+
+        (addr, rx)
+    }
+
+    fn start(addr: SocketAddr, options: &[(&str, &str)]) -> HttpClientSinkWriter {
+        let url = format!("http://{}/rows", addr);
+        let options = options.iter().fold(
+            OptionsBuilder::default()
+                .add("REMOTE_HOST", addr.ip().to_string())
+                .add("REMOTE_PORT", addr.port().to_string())
+                .add("METHOD", "POST")
+                .add("URL", url),
+            |builder, (k, v)| builder.add(*k, *v),
+        );
+        let options = options.build();
+        let config = SpringSinkWriterConfig::fx_default()
+            .overridden_by(&options)
+            .unwrap();
+        HttpClientSinkWriter::start(&options, &config).unwrap()
+    }
+
+    fn row(n: i32) -> SchemalessRow {
+        let mut row = SchemalessRow::default();
+        row.insert(
+            ColumnName::new("n".to_string()),
+            SqlValue::NotNull(NnSqlValue::Integer(n)),
+        )
+        .unwrap();
+        row
+    }
+
+    #[test]
+    fn test_batch_with_retry() {
+        let (addr, bodies) = serve(vec![503, 200, 200]);
+        let mut sink_writer = start(
+            addr,
+            &[
+                ("BATCH_SIZE", "2"),
+                ("HTTP_RETRIES", "1"),
+                ("HTTP_RETRY_BACKOFF_MSEC", "1"),
+            ],
+        );
+
+        for n in 1..=3 {
+            sink_writer.send_row(row(n)).unwrap();
+        }
+        assert_eq!(sink_writer.health().unwrap().retries, 1);
+        // sends the incomplete batch
+        drop(sink_writer);
+
+        assert_eq!(bodies.recv().unwrap(), r#"[{"n":1},{"n":2}]"#);
+        assert_eq!(bodies.recv().unwrap(), r#"[{"n":1},{"n":2}]"#);
+        assert_eq!(bodies.recv().unwrap(), r#"[{"n":3}]"#);
+    }
+
+    #[test]
+    fn test_circuit_breaker() {
+        let (addr, bodies) = serve(vec![500]);
+        let mut sink_writer = start(addr, &[("HTTP_CIRCUIT_BREAKER_FAILURES", "1")]);
+
+        assert!(sink_writer.send_row(row(1)).is_err());
+        assert_eq!(bodies.recv().unwrap(), r#"{"n":1}"#);
+
+        // dropped without request
+        sink_writer.send_row(row(2)).unwrap();
+        assert_eq!(
+            sink_writer.health().unwrap(),
+            SinkHealth {
+                retries: 0,
+                dropped_rows: 1,
+                circuit_open: true,
+            }
+        );
     }
 }
@@ -0,0 +1,339 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    fmt::Write,
+    net::{SocketAddr, UdpSocket},
+    time::Duration,
+};
+
+use anyhow::{anyhow, Context};
+use reqwest::{blocking::Client, header::AUTHORIZATION, Url};
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSinkWriterConfig,
+    pipeline::{InfluxDbOptions, InfluxDbProtocol, Options},
+    stream_engine::{
+        autonomous_executor::{task::sink_task::sink_writer::SinkWriter, SchemalessRow},
+        time::SpringTimestamp,
+        NnSqlValue, SqlValue,
+    },
+};
+
+#[derive(Debug)]
+enum Transport {
+    Http {
+        client: Client,
+        url: Url,
+        token: Option<String>,
+    },
+    Udp(UdpSocket),
+}
+
+/// Writes rows as InfluxDB line protocol over HTTP or UDP.
+///
+/// - `TAG_COLUMNS` are tags (NULL tags are omitted).
+/// - `FIELD_COLUMNS` (or the other columns) are fields: FLOAT as float, signed integers as integer (`i`), unsigned integers as unsigned (`u`), BOOLEAN as boolean and others as string. NULL fields are omitted.
+/// - `TIMESTAMP_COLUMN` is the timestamp in nanoseconds.
+///
+/// Over HTTP, lines are written `BATCH_SIZE` at a time. Lines in an incomplete batch are written when the sink writer is dropped.
+#[derive(Debug)]
+pub struct InfluxDbSinkWriter {
+    foreign_addr: SocketAddr,
+    options: InfluxDbOptions,
+    transport: Transport,
+
+    /// Lines waiting for the batch to be filled.
+    batch: String,
+    batch_len: usize,
+}
+
+impl SinkWriter for InfluxDbSinkWriter {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to create HTTP client or UDP socket.
+    /// - `SpringError::InvalidOption`
+    fn start(options: &Options, config: &SpringSinkWriterConfig) -> Result<Self> {
+        let options = InfluxDbOptions::try_from(options)?;
+        let foreign_addr = SocketAddr::new(options.remote_host, options.remote_port);
+
+        let transport = match &options.protocol {
+            InfluxDbProtocol::Udp => {
+                let bind_addr = if foreign_addr.is_ipv4() {
+                    "0.0.0.0:0"
+                } else {
+                    "[::]:0"
+                };
+                UdpSocket::bind(bind_addr)
+                    .and_then(|socket| {
+                        socket.connect(foreign_addr)?;
+                        socket.set_write_timeout(Some(Duration::from_millis(
+                            config.net_write_timeout_msec as u64,
+                        )))?;
+                        Ok(socket)
+                    })
+                    .context("failed to create UDP socket")
+                    .map(Transport::Udp)
+            }
+            protocol => Client::builder()
+                .connect_timeout(Duration::from_millis(
+                    config.http_connect_timeout_msec as u64,
+                ))
+                .timeout(Duration::from_millis(config.http_timeout_msec as u64))
+                .build()
+                .context("failed to create HTTP client")
+                .map(|client| Transport::Http {
+                    client,
+                    url: write_url(foreign_addr, protocol),
+                    token: match protocol {
+                        InfluxDbProtocol::HttpV2 { token, .. } => token.clone(),
+                        _ => None,
+                    },
+                }),
+        }
+        .map_err(|e| SpringError::ForeignIo {
+            source: e,
+            foreign_info: ForeignInfo::GenericTcp(foreign_addr),
+        })?;
+
+        log::info!("[InfluxDbSinkWriter] Ready to write into {}", foreign_addr);
+
+        Ok(Self {
+            foreign_addr,
+            options,
+            transport,
+            batch: String::new(),
+            batch_len: 0,
+        })
+    }
+
+    fn send_row(&mut self, row: SchemalessRow) -> Result<()> {
+        let line = line_protocol(&row, &self.options)?;
+        log::debug!("[InfluxDbSinkWriter] Writing line: {}", line);
+
+        if matches!(self.transport, Transport::Udp(_)) {
+            return self.write(line);
+        }
+
+        self.batch.push_str(&line);
+        self.batch.push('\n');
+        self.batch_len += 1;
+        if self.batch_len >= self.options.batch_size {
+            self.flush()
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl InfluxDbSinkWriter {
+    fn flush(&mut self) -> Result<()> {
+        if self.batch_len == 0 {
+            return Ok(());
+        }
+        let lines = std::mem::take(&mut self.batch);
+        self.batch_len = 0;
+        self.write(lines)
+    }
+
+    fn write(&self, lines: String) -> Result<()> {
+        match &self.transport {
+            Transport::Udp(socket) => socket
+                .send(lines.as_bytes())
+                .map(|_| ())
+                .context("failed to send datagram")
+                .map_err(|e| SpringError::ForeignIo {
+                    source: e,
+                    foreign_info: ForeignInfo::GenericTcp(self.foreign_addr),
+                }),
+            Transport::Http { client, url, token } => {
+                let mut request = client.post(url.clone()).body(lines);
+                if let Some(token) = token {
+                    request = request.header(AUTHORIZATION, format!("Token {}", token));
+                }
+                request
+                    .send()
+                    .and_then(|response| response.error_for_status())
+                    .map(|_| ())
+                    .context("failed to write lines")
+                    .map_err(|e| SpringError::ForeignIo {
+                        source: e,
+                        foreign_info: ForeignInfo::Http(self.foreign_addr),
+                    })
+            }
+        }
+    }
+}
+
+impl Drop for InfluxDbSinkWriter {
+    fn drop(&mut self) {
+        if let Err(e) = self.flush() {
+            log::warn!(
+                "[InfluxDbSinkWriter] Failed to write the last batch: {:?}",
+                e
+            );
+        }
+    }
+}
+
+fn write_url(foreign_addr: SocketAddr, protocol: &InfluxDbProtocol) -> Url {
+    let mut url = Url::parse(&format!("http://{}", foreign_addr)).expect("valid socket address");
+    match protocol {
+        InfluxDbProtocol::HttpV2 { org, bucket, .. } => {
+            url.set_path("/api/v2/write");
+            url.query_pairs_mut()
+                .append_pair("org", org)
+                .append_pair("bucket", bucket)
+                .append_pair("precision", "ns");
+        }
+        InfluxDbProtocol::HttpV1 { database } => {
+            url.set_path("/write");
+            url.query_pairs_mut()
+                .append_pair("db", database)
+                .append_pair("precision", "ns");
+        }
+        InfluxDbProtocol::Udp => unreachable!("UDP has no URL"),
+    }
+    url
+}
+
+/// `<measurement>[,<tag>=<value>...] <field>=<value>[,<field>=<value>...] [<timestamp>]`
+///
+/// # Failure
+///
+/// - `SpringError::Sql` when:
+///   - a column in options is not found.
+///   - all fields are NULL.
+fn line_protocol(row: &SchemalessRow, options: &InfluxDbOptions) -> Result<String> {
+    let mut line = escape(&row.render(&options.measurement)?, &[',', ' ']);
+
+    for tag_column in &options.tag_columns {
+        if let SqlValue::NotNull(value) = row.get_by_column_name(tag_column)? {
+            let value = match value {
+                NnSqlValue::Text(s) => s.clone(),
+                _ => value.to_string(),
+            };
+            let _ = write!(
+                line,
+                ",{}={}",
+                escape(tag_column.as_ref(), &[',', '=', ' ']),
+                escape(&value, &[',', '=', ' '])
+            );
+        }
+    }
+
+    let field_columns = match &options.field_columns {
+        Some(field_columns) => field_columns.clone(),
+        None => row
+            .clone()
+            .into_iter()
+            .map(|(column, _)| column)
+            .filter(|column| {
+                !options.tag_columns.contains(column)
+                    && options.timestamp_column.as_ref() != Some(column)
+            })
+            .collect(),
+    };
+    let mut n_fields = 0;
+    for field_column in &field_columns {
+        if let SqlValue::NotNull(value) = row.get_by_column_name(field_column)? {
+            let value = match value {
+                NnSqlValue::SmallInt(v) => format!("{}i", v),
+                NnSqlValue::Integer(v) => format!("{}i", v),
+                NnSqlValue::BigInt(v) => format!("{}i", v),
+                NnSqlValue::UnsignedInteger(v) => format!("{}u", v),
+                NnSqlValue::UnsignedBigInt(v) => format!("{}u", v),
+                NnSqlValue::Float(v) => v.to_string(),
+                NnSqlValue::Boolean(v) => v.to_string(),
+                NnSqlValue::Text(s) => format!("\"{}\"", escape(s, &['"', '\\'])),
+                _ => format!("\"{}\"", escape(&value.to_string(), &['"', '\\'])),
+            };
+            let separator = if n_fields == 0 { ' ' } else { ',' };
+            let _ = write!(
+                line,
+                "{}{}={}",
+                separator,
+                escape(field_column.as_ref(), &[',', '=', ' ']),
+                value
+            );
+            n_fields += 1;
+        }
+    }
+    if n_fields == 0 {
+        return Err(SpringError::Sql(anyhow!(
+            "no non-NULL field to write as InfluxDB line protocol: {:?}",
+            row
+        )));
+    }
+
+    if let Some(timestamp_column) = &options.timestamp_column {
+        if let SqlValue::NotNull(value) = row.get_by_column_name(timestamp_column)? {
+            let ts = value.unpack::<SpringTimestamp>()?;
+            let _ = write!(line, " {}", ts.unix_timestamp_nanos());
+        }
+    }
+
+    Ok(line)
+}
+
+fn escape(s: &str, special_chars: &[char]) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if special_chars.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::pipeline::{ColumnName, ColumnTemplate};
+
+    use super::*;
+
+    #[test]
+    fn test_line_protocol() {
+        let mut row = SchemalessRow::default();
+        let mut insert = |column: &str, value: SqlValue| {
+            row.insert(ColumnName::new(column.to_string()), value)
+                .unwrap()
+        };
+        insert(
+            "ts",
+            SqlValue::NotNull(NnSqlValue::Timestamp(
+                SpringTimestamp::from_str("1970-01-01 00:00:01.000000000").unwrap(),
+            )),
+        );
+        insert(
+            "site",
+            SqlValue::NotNull(NnSqlValue::Text("Tokyo 1".to_string())),
+        );
+        insert("count", SqlValue::NotNull(NnSqlValue::Integer(3)));
+        insert(
+            "note",
+            SqlValue::NotNull(NnSqlValue::Text("say \"hi\"".to_string())),
+        );
+        insert("missing", SqlValue::Null);
+
+        let options = InfluxDbOptions {
+            protocol: InfluxDbProtocol::Udp,
+            remote_host: "127.0.0.1".parse().unwrap(),
+            remote_port: 8089,
+            measurement: ColumnTemplate::from_str("weather").unwrap(),
+            tag_columns: vec![ColumnName::new("site".to_string())],
+            field_columns: None,
+            timestamp_column: Some(ColumnName::new("ts".to_string())),
+            batch_size: 1,
+        };
+
+        assert_eq!(
+            line_protocol(&row, &options).unwrap(),
+            r#"weather,site=Tokyo\ 1 count=3i,note="say \"hi\"" 1000000000"#
+        );
+    }
+}
@@ -0,0 +1,140 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{fmt::Debug, thread, time::Duration};
+
+use anyhow::Context;
+use rumqttc::{Client, Event, MqttOptions, Outgoing, Packet};
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSinkWriterConfig,
+    pipeline::{JsonShapeOptions, MqttSinkOptions, Options, SinkFormatOptions},
+    stream_engine::autonomous_executor::{
+        row::PayloadEncoder, task::sink_task::sink_writer::SinkWriter, SchemalessRow,
+    },
+};
+
+/// Publishes without waiting for the broker while this number of requests are in flight.
+const REQUEST_QUEUE_CAPACITY: usize = 100;
+const KEEP_ALIVE: Duration = Duration::from_secs(30);
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Publishes each row as a message in `FORMAT` (JSON by default) to the topic rendered from `TOPIC`.
+///
+/// A background thread drives the connection, which reconnects on errors.
+pub struct MqttSinkWriter {
+    options: MqttSinkOptions,
+    client: Client,
+
+    json_shape: JsonShapeOptions,
+    encoder: PayloadEncoder,
+}
+
+impl SinkWriter for MqttSinkWriter {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to register the Avro schema.
+    /// - `SpringError::InvalidOption`
+    fn start(options: &Options, _config: &SpringSinkWriterConfig) -> Result<Self> {
+        let json_shape = JsonShapeOptions::try_from(options)?;
+        let encoder = PayloadEncoder::new(&SinkFormatOptions::try_from(options)?)?;
+        let options = MqttSinkOptions::try_from(options)?;
+
+        let mut mqtt_options = MqttOptions::new(
+            &options.client_id,
+            &options.remote_host,
+            options.remote_port,
+        );
+        mqtt_options.set_keep_alive(KEEP_ALIVE);
+        if let Some((user_name, password)) = &options.user {
+            mqtt_options.set_credentials(user_name, password);
+        }
+
+        let (client, mut connection) = Client::new(mqtt_options, REQUEST_QUEUE_CAPACITY);
+
+        let broker = broker(&options);
+        let _ = thread::Builder::new()
+            .name("MqttSinkWriter".into())
+            .spawn(move || {
+                // ends when the client is dropped or disconnects
+                for notification in connection.iter() {
+                    match notification {
+                        Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                            log::info!("[MqttSinkWriter] Connected to {}", broker)
+                        }
+                        Ok(Event::Outgoing(Outgoing::Disconnect)) => break,
+                        Ok(_) => {}
+                        Err(e) => {
+                            log::warn!(
+                                "[MqttSinkWriter] Connection to {} failed (retrying in {:?}): {:?}",
+                                broker,
+                                RECONNECT_INTERVAL,
+                                e
+                            );
+                            thread::sleep(RECONNECT_INTERVAL);
+                        }
+                    }
+                }
+            });
+
+        log::info!("[MqttSinkWriter] Ready to publish to {}", broker(&options));
+
+        Ok(Self {
+            options,
+            client,
+            json_shape,
+            encoder,
+        })
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Column in topic placeholders is not found or NULL.
+    ///   - the row does not match the Avro schema.
+    /// - `SpringError::ForeignIo` when:
+    ///   - the request queue is full (e.g. disconnected for long).
+    fn send_row(&mut self, row: SchemalessRow) -> Result<()> {
+        let topic = row.render(&self.options.topic)?;
+        let payload = self.encoder.encode(row.into_json(&self.json_shape)?)?;
+
+        log::debug!(
+            "[MqttSinkWriter] Publishing to {}: {} bytes",
+            topic,
+            payload.len()
+        );
+
+        self.client
+            .try_publish(
+                topic.as_str(),
+                self.options.qos.into(),
+                self.options.retain,
+                payload,
+            )
+            .with_context(|| format!("failed to publish to {}", topic))
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::Mqtt(broker(&self.options)),
+            })
+    }
+}
+
+impl Debug for MqttSinkWriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MqttSinkWriter")
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+impl Drop for MqttSinkWriter {
+    fn drop(&mut self) {
+        // sent after queued publishes
+        let _ = self.client.try_disconnect();
+    }
+}
+
+fn broker(options: &MqttSinkOptions) -> String {
+    format!("{}:{}", options.remote_host, options.remote_port)
+}
@@ -0,0 +1,90 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use anyhow::Context;
+use nats::jetstream::{self, JetStream};
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSinkWriterConfig,
+    pipeline::{JsonShapeOptions, NatsMode, NatsSinkOptions, Options, SinkFormatOptions},
+    stream_engine::autonomous_executor::{
+        row::PayloadEncoder, task::sink_task::sink_writer::SinkWriter, SchemalessRow,
+    },
+};
+
+#[derive(Debug)]
+enum Publisher {
+    Core(nats::Connection),
+    /// Waits for the server's acknowledgement of each publication.
+    JetStream(JetStream),
+}
+
+/// Publishes each row as a message in `FORMAT` (JSON by default) to the subject rendered from `SUBJECT`.
+#[derive(Debug)]
+pub struct NatsSinkWriter {
+    options: NatsSinkOptions,
+    publisher: Publisher,
+
+    json_shape: JsonShapeOptions,
+    encoder: PayloadEncoder,
+}
+
+impl SinkWriter for NatsSinkWriter {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to connect to NATS servers.
+    ///   - failed to register the Avro schema.
+    /// - `SpringError::InvalidOption`
+    fn start(options: &Options, _config: &SpringSinkWriterConfig) -> Result<Self> {
+        let json_shape = JsonShapeOptions::try_from(options)?;
+        let encoder = PayloadEncoder::new(&SinkFormatOptions::try_from(options)?)?;
+        let options = NatsSinkOptions::try_from(options)?;
+
+        let connection = match &options.user {
+            Some((user_name, password)) => nats::Options::with_user_pass(user_name, password),
+            None => nats::Options::new(),
+        }
+        .with_name("springql")
+        .connect(options.url.as_str())
+        .context("failed to connect to NATS")
+        .map_err(|e| SpringError::ForeignIo {
+            source: e,
+            foreign_info: ForeignInfo::Nats(options.url.clone()),
+        })?;
+        let publisher = match options.mode {
+            NatsMode::Core => Publisher::Core(connection),
+            NatsMode::JetStream => Publisher::JetStream(jetstream::new(connection)),
+        };
+
+        log::info!("[NatsSinkWriter] Ready to write into {}", options.url);
+
+        Ok(Self {
+            options,
+            publisher,
+            json_shape,
+            encoder,
+        })
+    }
+
+    fn send_row(&mut self, row: SchemalessRow) -> Result<()> {
+        let subject = row.render(&self.options.subject)?;
+        let payload = self.encoder.encode(row.into_json(&self.json_shape)?)?;
+
+        log::debug!(
+            "[NatsSinkWriter] Publishing to {}: {} bytes",
+            subject,
+            payload.len()
+        );
+
+        match &self.publisher {
+            Publisher::Core(connection) => connection.publish(&subject, &payload),
+            Publisher::JetStream(jet_stream) => jet_stream.publish(&subject, &payload).map(|_| ()),
+        }
+        .with_context(|| format!("failed to publish to {}", subject))
+        .map_err(|e| SpringError::ForeignIo {
+            source: e,
+            foreign_info: ForeignInfo::Nats(self.options.url.clone()),
+        })
+    }
+}
@@ -11,20 +11,18 @@ use anyhow::Context;
 use crate::{
     api::error::{foreign_info::ForeignInfo, Result, SpringError},
     api::SpringSinkWriterConfig,
-    pipeline::{NetClientOptions, Options},
-    stream_engine::autonomous_executor::{
-        row::JsonObject, task::sink_task::sink_writer::SinkWriter, SchemalessRow,
-    },
+    pipeline::{JsonShapeOptions, NetClientOptions, Options},
+    stream_engine::autonomous_executor::{task::sink_task::sink_writer::SinkWriter, SchemalessRow},
 };
 
 #[derive(Debug)]
 pub struct NetSinkWriter {
-    foreign_addr: SocketAddr,
-    tcp_stream_writer: BufWriter<TcpStream>, // TODO UDP
+    line_writer: LineSinkWriter<TcpStream>, // TODO UDP
 }
 
 impl SinkWriter for NetSinkWriter {
     fn start(options: &Options, config: &SpringSinkWriterConfig) -> Result<Self> {
+        let json_shape = JsonShapeOptions::try_from(options)?;
         let options = NetClientOptions::try_from(options)?;
         let sock_addr = SocketAddr::new(options.remote_host, options.remote_port);
 
@@ -47,28 +45,53 @@ impl SinkWriter for NetSinkWriter {
                 foreign_info: ForeignInfo::GenericTcp(sock_addr),
             })?;
 
-        let tcp_stream_writer = BufWriter::new(tcp_stream);
-
         log::info!("[NetSinkWriter] Ready to write into {}", sock_addr);
 
         Ok(Self {
-            tcp_stream_writer,
-            foreign_addr: sock_addr,
+            line_writer: LineSinkWriter::new(
+                tcp_stream,
+                ForeignInfo::GenericTcp(sock_addr),
+                json_shape,
+            ),
         })
     }
 
     fn send_row(&mut self, row: SchemalessRow) -> Result<()> {
-        let mut json_s = JsonObject::from(row).to_string();
+        self.line_writer.send_row(row)
+    }
+}
+
+/// Writes a row per line as a JSON object into a byte stream.
+///
+/// Shared by sink writers connecting to a remote stream (`NET_CLIENT`, `UNIX_SOCKET`).
+#[derive(Debug)]
+pub(super) struct LineSinkWriter<S: Write> {
+    stream_writer: BufWriter<S>,
+    foreign_info: ForeignInfo,
+
+    json_shape: JsonShapeOptions,
+}
+
+impl<S: Write> LineSinkWriter<S> {
+    /// `stream` must have a write timeout not to block the sink task forever.
+    pub(super) fn new(stream: S, foreign_info: ForeignInfo, json_shape: JsonShapeOptions) -> Self {
+        Self {
+            stream_writer: BufWriter::new(stream),
+            foreign_info,
+            json_shape,
+        }
+    }
+
+    pub(super) fn send_row(&mut self, row: SchemalessRow) -> Result<()> {
+        let mut json_s = row.into_json(&self.json_shape)?.to_string();
         json_s.push('\n');
 
         log::debug!("[NetSinkWriter] Writing message to remote: {}", json_s);
         self.write_row(json_s.as_bytes())
     }
-}
 
-impl NetSinkWriter {
     fn write_row(&mut self, content: &[u8]) -> Result<()> {
-        self.tcp_stream_writer
+        self.stream_writer
             .write_all(content)
             .with_context(|| {
                 format!(
@@ -78,14 +101,14 @@ impl NetSinkWriter {
             })
             .map_err(|e| SpringError::ForeignIo {
                 source: e,
-                foreign_info: ForeignInfo::GenericTcp(self.foreign_addr),
+                foreign_info: self.foreign_info.clone(),
             })?;
-        self.tcp_stream_writer
+        self.stream_writer
             .flush()
             .with_context(|| "failed to flush row to remote sink")
             .map_err(|e| SpringError::ForeignIo {
                 source: e,
-                foreign_info: ForeignInfo::GenericTcp(self.foreign_addr),
+                foreign_info: self.foreign_info.clone(),
             })?;
 
         Ok(())
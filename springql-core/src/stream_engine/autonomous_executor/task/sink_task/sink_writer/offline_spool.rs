@@ -0,0 +1,623 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Store-and-forward layer for any sink writer (`OFFLINE_SPOOL_DIRECTORY` option).
+
+use std::{
+    collections::VecDeque,
+    fs::{self, File, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Context};
+use ordered_float::OrderedFloat;
+use serde_json::{json, Value};
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSinkWriterConfig,
+    pipeline::{ColumnName, OfflineSpoolOptions, Options},
+    stream_engine::{
+        autonomous_executor::{
+            performance_metrics::SinkHealth, task::sink_task::sink_writer::SinkWriter,
+            SchemalessRow,
+        },
+        time::{SpringDuration, SpringEventDuration, SpringTimestamp},
+        NnSqlValue, SqlValue,
+    },
+};
+
+const SEGMENT_SUFFIX: &str = ".spool";
+
+/// Wraps a sink writer `S` to spool rows while its remote endpoint is unreachable.
+///
+/// When `S::start()` or `S::send_row()` fails because the remote endpoint is unreachable (see `SpringError::is_foreign_unreachable()`),
+/// the row is appended to the spool and later rows are appended behind it.
+/// Every `OFFLINE_SPOOL_RETRY_INTERVAL_MSEC`, the sink writer is restarted and spooled rows are replayed in order
+/// before new rows are sent again.
+///
+/// Spooled rows survive restarts of the process. A row may be sent twice if the process stops while replaying.
+#[derive(Debug)]
+pub struct OfflineSpoolSinkWriter<S: SinkWriter> {
+    options: Options,
+    config: SpringSinkWriterConfig,
+    retry_interval: Duration,
+
+    /// `None` while the endpoint is unreachable.
+    inner: Option<S>,
+    spool: RowSpool,
+    /// Replays are not tried until this time after a failure.
+    next_retry_at: Option<Instant>,
+
+    /// `dropped_rows` counts rows dropped from the full spool (undecodable rows are counted by `spool`).
+    health: SinkHealth,
+}
+
+impl<S: SinkWriter> SinkWriter for OfflineSpoolSinkWriter<S> {
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidOption` when:
+    ///   - `OFFLINE_SPOOL_DIRECTORY` is not set.
+    /// - `SpringError::ForeignIo` when:
+    ///   - `OFFLINE_SPOOL_DIRECTORY` is not a directory.
+    /// - Raises Err from `S::start()` as-is unless the endpoint is unreachable.
+    fn start(options: &Options, config: &SpringSinkWriterConfig) -> Result<Self> {
+        let spool_options = OfflineSpoolOptions::from_options(options)?.ok_or_else(|| {
+            SpringError::InvalidOption {
+                key: "OFFLINE_SPOOL_DIRECTORY".to_string(),
+                value: "(not found)".to_string(),
+                source: anyhow!("key is not found in options"),
+            }
+        })?;
+        let spool = RowSpool::open(spool_options.directory, spool_options.max_bytes)?;
+
+        let mut sink_writer = Self {
+            options: options.clone(),
+            config: *config,
+            retry_interval: spool_options.retry_interval,
+            inner: None,
+            spool,
+            next_retry_at: None,
+            health: SinkHealth::default(),
+        };
+        sink_writer.restart_inner()?;
+        sink_writer.replay()?;
+        Ok(sink_writer)
+    }
+
+    fn send_row(&mut self, row: SchemalessRow) -> Result<()> {
+        if self.spool.is_empty() {
+            if let Some(inner) = self.inner.as_mut() {
+                return match inner.send_row(row.clone()) {
+                    Err(e) if e.is_foreign_unreachable() => {
+                        self.go_offline(e);
+                        self.push(row)
+                    }
+                    res => res,
+                };
+            }
+        }
+        self.push(row)?;
+        self.replay()
+    }
+
+    fn health(&self) -> Option<SinkHealth> {
+        let inner_health = self
+            .inner
+            .as_ref()
+            .and_then(|inner| inner.health())
+            .unwrap_or_default();
+        Some(SinkHealth {
+            retries: self.health.retries + inner_health.retries,
+            dropped_rows: self.health.dropped_rows
+                + self.spool.undecodable_rows
+                + inner_health.dropped_rows,
+            circuit_open: inner_health.circuit_open,
+        })
+    }
+}
+
+impl<S: SinkWriter> OfflineSpoolSinkWriter<S> {
+    fn push(&mut self, row: SchemalessRow) -> Result<()> {
+        self.health.dropped_rows += self.spool.push(&row)?;
+        Ok(())
+    }
+
+    fn go_offline(&mut self, e: SpringError) {
+        log::warn!(
+            "[OfflineSpoolSinkWriter] Spooling rows into {:?} until the endpoint comes back: {:?}",
+            self.spool.directory,
+            e
+        );
+        self.inner = None;
+        self.next_retry_at = Some(Instant::now() + self.retry_interval);
+    }
+
+    /// # Failure
+    ///
+    /// - Raises Err from `S::start()` as-is unless the endpoint is unreachable.
+    fn restart_inner(&mut self) -> Result<()> {
+        match S::start(&self.options, &self.config) {
+            Ok(inner) => {
+                self.inner = Some(inner);
+                Ok(())
+            }
+            Err(e) if e.is_foreign_unreachable() => {
+                self.go_offline(e);
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Reconnects and sends spooled rows in order, unless waiting for the next retry.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to read or delete spool files.
+    /// - Raises Err from `S::send_row()` as-is unless the endpoint is unreachable (the row is removed from the spool).
+    fn replay(&mut self) -> Result<()> {
+        if self.spool.is_empty()
+            || matches!(self.next_retry_at, Some(next_retry_at) if Instant::now() < next_retry_at)
+        {
+            return Ok(());
+        }
+        if self.inner.is_none() {
+            self.health.retries += 1;
+            self.restart_inner()?;
+        }
+        let inner = match self.inner.as_mut() {
+            Some(inner) => inner,
+            None => return Ok(()),
+        };
+
+        let mut n_replayed = 0;
+        while let Some(row) = self.spool.front()? {
+            match inner.send_row(row) {
+                Ok(()) => {
+                    self.spool.pop_front()?;
+                    n_replayed += 1;
+                }
+                Err(e) if e.is_foreign_unreachable() => {
+                    log::info!(
+                        "[OfflineSpoolSinkWriter] Replayed {} rows before going offline again",
+                        n_replayed
+                    );
+                    self.go_offline(e);
+                    return Ok(());
+                }
+                Err(e) => {
+                    self.spool.pop_front()?;
+                    return Err(e);
+                }
+            }
+        }
+
+        log::info!(
+            "[OfflineSpoolSinkWriter] Replayed {} spooled rows",
+            n_replayed
+        );
+        self.next_retry_at = None;
+        Ok(())
+    }
+}
+
+/// Append-only segment files of encoded rows (a row per line) in a directory.
+///
+/// The head segment is loaded into memory while replaying. Rows are appended to a new segment once a replay starts.
+#[derive(Debug)]
+struct RowSpool {
+    directory: PathBuf,
+    max_bytes: u64,
+    segment_bytes: u64,
+
+    /// Segment paths (and sizes) from the oldest.
+    segments: VecDeque<(PathBuf, u64)>,
+    next_segment_seq: u64,
+    /// Last segment open for appending.
+    tail: Option<File>,
+    /// Remaining rows of the head segment (loaded on replay).
+    head_rows: Option<VecDeque<String>>,
+    /// Rows skipped on replay since they cannot be decoded.
+    undecodable_rows: u64,
+}
+
+impl RowSpool {
+    fn io_error(path: PathBuf) -> impl FnOnce(anyhow::Error) -> SpringError {
+        move |e| SpringError::ForeignIo {
+            source: e,
+            foreign_info: ForeignInfo::File(path),
+        }
+    }
+
+    /// Opens a spool with segments left by the previous run.
+    fn open(directory: PathBuf, max_bytes: u64) -> Result<Self> {
+        if !directory.is_dir() {
+            return Err(SpringError::ForeignIo {
+                source: anyhow!("not a directory"),
+                foreign_info: ForeignInfo::File(directory),
+            });
+        }
+
+        let mut segments = fs::read_dir(&directory)
+            .and_then(|entries| {
+                entries
+                    .map(|entry| {
+                        let entry = entry?;
+                        Ok((entry.path(), entry.metadata()?.len()))
+                    })
+                    .collect::<std::io::Result<Vec<_>>>()
+            })
+            .context("failed to list spool segments")
+            .map_err(Self::io_error(directory.clone()))?;
+        segments.retain(|(path, _)| path.to_string_lossy().ends_with(SEGMENT_SUFFIX));
+        segments.sort();
+
+        let next_segment_seq = segments
+            .last()
+            .and_then(|(path, _)| path.file_stem()?.to_str()?.parse::<u64>().ok())
+            .map_or(0, |seq| seq + 1);
+        if !segments.is_empty() {
+            log::info!(
+                "[OfflineSpoolSinkWriter] Found {} spool segments in {:?}",
+                segments.len(),
+                directory
+            );
+        }
+
+        Ok(Self {
+            directory,
+            max_bytes,
+            // several segments so that the oldest ones can be dropped
+            segment_bytes: (max_bytes / 8).max(1),
+            segments: segments.into(),
+            next_segment_seq,
+            tail: None,
+            head_rows: None,
+            undecodable_rows: 0,
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    fn spool_bytes(&self) -> u64 {
+        self.segments.iter().map(|(_, len)| len).sum()
+    }
+
+    /// # Returns
+    ///
+    /// Number of rows dropped to make room for the row.
+    fn push(&mut self, row: &SchemalessRow) -> Result<u64> {
+        let line = encode_row(row) + "\n";
+        let len = line.len() as u64;
+
+        let mut n_dropped = 0;
+        while self.spool_bytes() + len > self.max_bytes && !self.segments.is_empty() {
+            n_dropped += self.drop_head()?;
+        }
+        if len > self.max_bytes {
+            log::warn!("[OfflineSpoolSinkWriter] Dropped a row larger than the spool");
+            return Ok(n_dropped + 1);
+        }
+
+        let tail_full = matches!(self.segments.back(), Some((_, tail_len)) if *tail_len + len > self.segment_bytes);
+        if self.tail.is_none() || tail_full {
+            let path = self
+                .directory
+                .join(format!("{:020}{}", self.next_segment_seq, SEGMENT_SUFFIX));
+            let file = OpenOptions::new()
+                .create_new(true)
+                .append(true)
+                .open(&path)
+                .context("failed to create spool segment")
+                .map_err(Self::io_error(path.clone()))?;
+            self.next_segment_seq += 1;
+            self.segments.push_back((path, 0));
+            self.tail = Some(file);
+        }
+
+        let (path, tail_len) = self.segments.back_mut().expect("opened above");
+        self.tail
+            .as_mut()
+            .expect("opened above")
+            .write_all(line.as_bytes())
+            .context("failed to append to spool segment")
+            .map_err(Self::io_error(path.clone()))?;
+        *tail_len += len;
+        Ok(n_dropped)
+    }
+
+    /// Deletes the head segment.
+    ///
+    /// # Returns
+    ///
+    /// Number of dropped rows.
+    fn drop_head(&mut self) -> Result<u64> {
+        let (path, _) = self.segments.pop_front().expect("checked by caller");
+        let n_rows = match self.head_rows.take() {
+            Some(rows) => rows.len(),
+            None => fs::read_to_string(&path).map_or(0, |s| s.lines().count()),
+        } as u64;
+        if self.segments.is_empty() {
+            self.tail = None;
+        }
+        log::warn!(
+            "[OfflineSpoolSinkWriter] Spool exceeds {} bytes. Dropped {} rows in {:?}",
+            self.max_bytes,
+            n_rows,
+            path
+        );
+        fs::remove_file(&path)
+            .context("failed to delete spool segment")
+            .map_err(Self::io_error(path))?;
+        Ok(n_rows)
+    }
+
+    /// The oldest row.
+    ///
+    /// Undecodable rows are skipped with a warning, like the last row partially appended when the process stopped.
+    /// Otherwise such a row would be at the front forever.
+    fn front(&mut self) -> Result<Option<SchemalessRow>> {
+        loop {
+            if let Some(line) = self.head_rows.as_ref().and_then(|rows| rows.front()) {
+                match decode_row(line) {
+                    Ok(row) => return Ok(Some(row)),
+                    Err(e) => {
+                        log::warn!(
+                            "[OfflineSpoolSinkWriter] Dropped an undecodable row in {:?}: {:?}",
+                            self.segments.front().map(|(path, _)| path),
+                            e
+                        );
+                        self.undecodable_rows += 1;
+                        self.pop_front()?;
+                        continue;
+                    }
+                }
+            }
+            if self.head_rows.is_some() {
+                self.delete_head()?;
+            }
+            let (path, _) = match self.segments.front() {
+                Some(head) => head.clone(),
+                None => return Ok(None),
+            };
+            if self.segments.len() == 1 {
+                // rows are appended to a new segment from now on
+                self.tail = None;
+            }
+            let content = fs::read_to_string(&path)
+                .context("failed to read spool segment")
+                .map_err(Self::io_error(path))?;
+            self.head_rows = Some(content.lines().map(|line| line.to_string()).collect());
+        }
+    }
+
+    fn pop_front(&mut self) -> Result<()> {
+        if let Some(rows) = self.head_rows.as_mut() {
+            rows.pop_front();
+            if rows.is_empty() {
+                self.delete_head()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Deletes the replayed head segment.
+    fn delete_head(&mut self) -> Result<()> {
+        self.head_rows = None;
+        if let Some((path, _)) = self.segments.pop_front() {
+            fs::remove_file(&path)
+                .context("failed to delete spool segment")
+                .map_err(Self::io_error(path))?;
+        }
+        Ok(())
+    }
+}
+
+/// Encodes a row into a JSON array of `[column, type, value]` so that types are restored from the spool.
+fn encode_row(row: &SchemalessRow) -> String {
+    let columns = row
+        .clone()
+        .into_iter()
+        .map(|(column, value)| {
+            let (typ, value) = match value {
+                SqlValue::Null => ("NULL", Value::Null),
+                SqlValue::NotNull(nn_sql_value) => match nn_sql_value {
+                    NnSqlValue::SmallInt(v) => ("SMALLINT", json!(v)),
+                    NnSqlValue::Integer(v) => ("INTEGER", json!(v)),
+                    NnSqlValue::BigInt(v) => ("BIGINT", json!(v)),
+                    NnSqlValue::UnsignedInteger(v) => ("UNSIGNED INTEGER", json!(v)),
+                    NnSqlValue::UnsignedBigInt(v) => ("UNSIGNED BIGINT", json!(v)),
+                    NnSqlValue::Float(v) => ("FLOAT", json!(v.into_inner())),
+                    NnSqlValue::Text(v) => ("TEXT", json!(v)),
+                    NnSqlValue::Blob(v) => ("BLOB", json!(v)),
+                    NnSqlValue::Boolean(v) => ("BOOLEAN", json!(v)),
+                    NnSqlValue::Timestamp(v) => ("TIMESTAMP", json!(v.to_string())),
+                    NnSqlValue::Duration(v) => ("DURATION", json!(v.as_std().as_nanos() as u64)),
+                },
+            };
+            json!([column.as_ref(), typ, value])
+        })
+        .collect::<Vec<_>>();
+    Value::Array(columns).to_string()
+}
+
+/// # Failure
+///
+/// - `SpringError::InvalidFormat` when:
+///   - `line` is not encoded by `encode_row()`.
+fn decode_row(line: &str) -> Result<SchemalessRow> {
+    let decode = || -> anyhow::Result<SchemalessRow> {
+        let columns: Vec<(String, String, Value)> = serde_json::from_str(line)?;
+        let mut row = SchemalessRow::with_capacity(columns.len());
+        for (column, typ, value) in columns {
+            let invalid = || anyhow!("invalid {} value: {}", typ, value);
+            let nn_sql_value = match typ.as_str() {
+                "NULL" => None,
+                "SMALLINT" => Some(NnSqlValue::SmallInt(serde_json::from_value(value.clone())?)),
+                "INTEGER" => Some(NnSqlValue::Integer(serde_json::from_value(value.clone())?)),
+                "BIGINT" => Some(NnSqlValue::BigInt(serde_json::from_value(value.clone())?)),
+                "UNSIGNED INTEGER" => Some(NnSqlValue::UnsignedInteger(serde_json::from_value(
+                    value.clone(),
+                )?)),
+                "UNSIGNED BIGINT" => Some(NnSqlValue::UnsignedBigInt(serde_json::from_value(
+                    value.clone(),
+                )?)),
+                "FLOAT" => Some(NnSqlValue::Float(OrderedFloat(serde_json::from_value(
+                    value.clone(),
+                )?))),
+                "TEXT" => Some(NnSqlValue::Text(serde_json::from_value(value.clone())?)),
+                "BLOB" => Some(NnSqlValue::Blob(serde_json::from_value(value.clone())?)),
+                "BOOLEAN" => Some(NnSqlValue::Boolean(serde_json::from_value(value.clone())?)),
+                "TIMESTAMP" => Some(NnSqlValue::Timestamp(
+                    value
+                        .as_str()
+                        .and_then(|s| SpringTimestamp::from_str(s).ok())
+                        .ok_or_else(invalid)?,
+                )),
+                "DURATION" => Some(NnSqlValue::Duration(SpringEventDuration::from_std(
+                    Duration::from_nanos(value.as_u64().ok_or_else(invalid)?),
+                ))),
+                _ => return Err(anyhow!("unknown type: {}", typ)),
+            };
+            let value = nn_sql_value.map_or(SqlValue::Null, SqlValue::NotNull);
+            row.insert(ColumnName::new(column), value)?;
+        }
+        Ok(row)
+    };
+    decode().map_err(|e| SpringError::InvalidFormat {
+        s: line.to_string(),
+        source: e,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{SocketAddr, TcpListener};
+
+    use springql_foreign_service::sink::ForeignSink;
+
+    use crate::{
+        pipeline::OptionsBuilder,
+        stream_engine::autonomous_executor::{
+            row::JsonObject, task::sink_task::sink_writer::NetSinkWriter,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_encode_row() {
+        let mut row = SchemalessRow::fx_city_temperature_tokyo();
+        row.insert(
+            ColumnName::new("raw".to_string()),
+            SqlValue::NotNull(NnSqlValue::Blob(vec![0, 255])),
+        )
+        .unwrap();
+        row.insert(ColumnName::new("note".to_string()), SqlValue::Null)
+            .unwrap();
+        assert_eq!(decode_row(&encode_row(&row)).unwrap(), row);
+    }
+
+    #[test]
+    fn test_skip_partially_appended_row() {
+        let directory = tempfile::tempdir().unwrap();
+
+        let mut spool = RowSpool::open(directory.path().to_path_buf(), 1024 * 1024).unwrap();
+        spool
+            .push(&SchemalessRow::fx_city_temperature_tokyo())
+            .unwrap();
+        // the process stopped while appending the next row
+        let (path, _) = spool.segments.back().unwrap().clone();
+        let line = encode_row(&SchemalessRow::fx_city_temperature_osaka());
+        spool
+            .tail
+            .as_mut()
+            .unwrap()
+            .write_all(line[..line.len() / 2].as_bytes())
+            .unwrap();
+        drop(spool);
+
+        let mut spool = RowSpool::open(directory.path().to_path_buf(), 1024 * 1024).unwrap();
+        spool
+            .push(&SchemalessRow::fx_city_temperature_london())
+            .unwrap();
+        assert_ne!(spool.segments.back().unwrap().0, path);
+
+        let mut rows = vec![];
+        while let Some(row) = spool.front().unwrap() {
+            rows.push(row);
+            spool.pop_front().unwrap();
+        }
+        assert_eq!(
+            rows,
+            vec![
+                SchemalessRow::fx_city_temperature_tokyo(),
+                SchemalessRow::fx_city_temperature_london()
+            ]
+        );
+        assert_eq!(spool.undecodable_rows, 1);
+        assert!(spool.is_empty());
+    }
+
+    #[test]
+    fn test_replay_after_reconnection() {
+        let spool = tempfile::tempdir().unwrap();
+        // reserve a port nobody listens to
+        let addr: SocketAddr = {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            listener.local_addr().unwrap()
+        };
+        let options = OptionsBuilder::default()
+            .add("PROTOCOL", "TCP")
+            .add("REMOTE_HOST", addr.ip().to_string())
+            .add("REMOTE_PORT", addr.port().to_string())
+            .add("OFFLINE_SPOOL_DIRECTORY", spool.path().to_string_lossy())
+            .add("OFFLINE_SPOOL_RETRY_INTERVAL_MSEC", "0")
+            .build();
+        let config = SpringSinkWriterConfig::fx_default();
+
+        let mut sink_writer =
+            OfflineSpoolSinkWriter::<NetSinkWriter>::start(&options, &config).unwrap();
+        sink_writer
+            .send_row(SchemalessRow::fx_city_temperature_tokyo())
+            .unwrap();
+        sink_writer
+            .send_row(SchemalessRow::fx_city_temperature_osaka())
+            .unwrap();
+        assert!(sink_writer.inner.is_none());
+        drop(sink_writer);
+
+        // restarted with the spool left by the previous run
+        let sink = ForeignSink::start().unwrap();
+        let options = OptionsBuilder::default()
+            .add("PROTOCOL", "TCP")
+            .add("REMOTE_HOST", sink.host_ip().to_string())
+            .add("REMOTE_PORT", sink.port().to_string())
+            .add("OFFLINE_SPOOL_DIRECTORY", spool.path().to_string_lossy())
+            .build();
+        let mut sink_writer =
+            OfflineSpoolSinkWriter::<NetSinkWriter>::start(&options, &config).unwrap();
+        sink_writer
+            .send_row(SchemalessRow::fx_city_temperature_london())
+            .unwrap();
+
+        const TIMEOUT: Duration = Duration::from_secs(1);
+        for expected in [
+            JsonObject::fx_city_temperature_tokyo(),
+            JsonObject::fx_city_temperature_osaka(),
+            JsonObject::fx_city_temperature_london(),
+        ] {
+            assert_eq!(
+                JsonObject::new(sink.try_receive(TIMEOUT).unwrap()),
+                expected
+            );
+        }
+        assert!(sink_writer.spool.is_empty());
+    }
+}
@@ -0,0 +1,454 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    fmt::Debug,
+    fs::{self, File},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context;
+use arrow::{
+    array::{
+        ArrayRef, BinaryArray, BooleanArray, DurationNanosecondArray, Float32Array, Int16Array,
+        Int32Array, Int64Array, StringArray, TimestampNanosecondArray, UInt32Array, UInt64Array,
+    },
+    datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit},
+    record_batch::RecordBatch,
+};
+use parquet::{arrow::ArrowWriter, basic::Compression, file::properties::WriterProperties};
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSinkWriterConfig,
+    pipeline::{
+        ColumnName, F32LooseType, I64LooseType, NumericComparableType, Options, ParquetCompression,
+        ParquetOptions, SqlType, U64LooseType,
+    },
+    stream_engine::{
+        autonomous_executor::{task::sink_task::sink_writer::SinkWriter, SchemalessRow},
+        time::{SpringDuration, SpringEventDuration, SpringTimestamp},
+        SpringValue, SqlValue,
+    },
+};
+
+/// A Parquet file being written.
+///
+/// Written as `<name>.parquet.tmp` and renamed to `<name>.parquet` when closed, so that uploaders only see complete files.
+struct OpenFile {
+    path: PathBuf,
+    tmp_path: PathBuf,
+    schema: SchemaRef,
+    writer: ArrowWriter<File>,
+}
+
+impl OpenFile {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to create the file.
+    fn create(options: &ParquetOptions, seq: u64, schema: SchemaRef) -> Result<Self> {
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let name = format!("{}-{}-{:06}", options.file_prefix, created_at, seq);
+        let path = options.directory.join(format!("{}.parquet", name));
+        let tmp_path = options.directory.join(format!("{}.parquet.tmp", name));
+
+        let props = writer_properties(options.compression, options.row_group_size);
+
+        let writer = File::create(&tmp_path)
+            .context("failed to create file")
+            .and_then(|file| {
+                ArrowWriter::try_new(file, schema.clone(), Some(props))
+                    .context("failed to write Parquet header")
+            })
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::File(tmp_path.clone()),
+            })?;
+
+        log::info!("[ParquetSinkWriter] Opened {:?}", tmp_path);
+        Ok(Self {
+            path,
+            tmp_path,
+            schema,
+            writer,
+        })
+    }
+
+    fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        self.writer
+            .write(batch)
+            .and_then(|_| self.writer.flush())
+            .context("failed to write row group")
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::File(self.tmp_path.clone()),
+            })
+    }
+
+    /// Writes the footer and renames the file.
+    fn close(self) -> Result<()> {
+        let Self {
+            path,
+            tmp_path,
+            writer,
+            ..
+        } = self;
+        writer
+            .close()
+            .context("failed to write Parquet footer")
+            .and_then(|_| fs::rename(&tmp_path, &path).context("failed to rename file"))
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::File(tmp_path),
+            })?;
+
+        log::info!("[ParquetSinkWriter] Closed {:?}", path);
+        Ok(())
+    }
+}
+
+/// Buffers rows and writes them into rolling Parquet files in `DIRECTORY`.
+///
+/// Columns are mapped to nullable Arrow types:
+///
+/// - SMALLINT, INTEGER, BIGINT: Int16, Int32, Int64
+/// - UNSIGNED INTEGER, UNSIGNED BIGINT: UInt32, UInt64
+/// - FLOAT: Float32
+/// - TEXT: Utf8
+/// - BLOB: Binary
+/// - BOOLEAN: Boolean
+/// - TIMESTAMP: Timestamp (nanoseconds, without time zone)
+/// - DURATION: Duration (nanoseconds)
+///
+/// The type of a column is taken from its first non-NULL value (Utf8 if all values in the first row group of a file are NULL).
+/// A new file is started when the column types change.
+///
+/// A file is closed when it reaches `MAX_FILE_SIZE_BYTES` or `ROLL_INTERVAL_SEC` has passed since its first row.
+/// Buffered rows and the current file are written when the sink writer is dropped.
+pub struct ParquetSinkWriter {
+    options: ParquetOptions,
+
+    /// Rows of the next row group.
+    rows: Vec<SchemalessRow>,
+    /// `ArrowWriter` is not Sync.
+    file: Mutex<Option<OpenFile>>,
+    /// When the first row of the current file arrived.
+    file_started_at: Option<Instant>,
+    n_files: u64,
+}
+
+impl SinkWriter for ParquetSinkWriter {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - `DIRECTORY` is not a directory.
+    /// - `SpringError::InvalidOption`
+    fn start(options: &Options, _config: &SpringSinkWriterConfig) -> Result<Self> {
+        let options = ParquetOptions::try_from(options)?;
+
+        if !options.directory.is_dir() {
+            return Err(SpringError::ForeignIo {
+                source: anyhow::anyhow!("not a directory"),
+                foreign_info: ForeignInfo::File(options.directory),
+            });
+        }
+
+        log::info!(
+            "[ParquetSinkWriter] Ready to write into {:?}",
+            options.directory
+        );
+
+        Ok(Self {
+            rows: Vec::with_capacity(options.row_group_size),
+            options,
+            file: Mutex::new(None),
+            file_started_at: None,
+            n_files: 0,
+        })
+    }
+
+    fn send_row(&mut self, row: SchemalessRow) -> Result<()> {
+        let started_at = *self.file_started_at.get_or_insert_with(Instant::now);
+        self.rows.push(row);
+
+        if self.rows.len() >= self.options.row_group_size {
+            self.write_row_group()?;
+        }
+        match self.options.roll_interval {
+            Some(roll_interval) if started_at.elapsed() >= roll_interval => self.close_file(),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl ParquetSinkWriter {
+    fn write_row_group(&mut self) -> Result<()> {
+        if self.rows.is_empty() {
+            return Ok(());
+        }
+        let rows = std::mem::take(&mut self.rows);
+
+        let file = self.file.get_mut().expect("Parquet writer mutex poisoned");
+        let schema = arrow_schema(&rows, file.as_ref().map(|file| file.schema.as_ref()));
+        if let Some(open_file) = file.take() {
+            if open_file.schema.as_ref() == &schema {
+                *file = Some(open_file);
+            } else {
+                log::info!("[ParquetSinkWriter] Column types changed. Starting a new file.");
+                open_file.close()?;
+            }
+        }
+        if file.is_none() {
+            self.n_files += 1;
+            *file = Some(OpenFile::create(
+                &self.options,
+                self.n_files,
+                Arc::new(schema),
+            )?);
+        }
+        let open_file = file.as_mut().expect("opened above");
+
+        let batch = record_batch(&rows, &open_file.schema)?;
+        open_file.write(&batch)?;
+
+        if open_file.writer.bytes_written() as u64 >= self.options.max_file_size_bytes {
+            file.take().expect("written above").close()?;
+            self.file_started_at = None;
+        }
+        Ok(())
+    }
+
+    fn close_file(&mut self) -> Result<()> {
+        self.write_row_group()?;
+        self.file_started_at = None;
+        match self
+            .file
+            .get_mut()
+            .expect("Parquet writer mutex poisoned")
+            .take()
+        {
+            Some(open_file) => open_file.close(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for ParquetSinkWriter {
+    fn drop(&mut self) {
+        if let Err(e) = self.close_file() {
+            log::warn!("[ParquetSinkWriter] Failed to write the last file: {:?}", e);
+        }
+    }
+}
+
+impl Debug for ParquetSinkWriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ParquetSinkWriter")
+            .field("options", &self.options)
+            .field("n_buffered_rows", &self.rows.len())
+            .field("n_files", &self.n_files)
+            .finish()
+    }
+}
+
+fn writer_properties(compression: ParquetCompression, row_group_size: usize) -> WriterProperties {
+    let compression = match compression {
+        ParquetCompression::Uncompressed => Compression::UNCOMPRESSED,
+        ParquetCompression::Snappy => Compression::SNAPPY,
+    };
+    WriterProperties::builder()
+        .set_compression(compression)
+        .set_max_row_group_size(row_group_size)
+        .build()
+}
+
+/// Encodes rows into a single-row-group Parquet file in memory (for sink writers uploading whole files).
+///
+/// # Failure
+///
+/// - `SpringError::Sql` when:
+///   - a value cannot be converted into the column type.
+///   - failed to encode the file.
+#[cfg(feature = "sink-s3")]
+pub(super) fn encode_parquet(
+    rows: &[SchemalessRow],
+    compression: ParquetCompression,
+) -> Result<Vec<u8>> {
+    let schema = Arc::new(arrow_schema(rows, None));
+    let batch = record_batch(rows, &schema)?;
+    let props = writer_properties(compression, rows.len().max(1));
+
+    ArrowWriter::try_new(Vec::new(), schema, Some(props))
+        .and_then(|mut writer| {
+            writer.write(&batch)?;
+            writer.into_inner()
+        })
+        .context("failed to encode Parquet file")
+        .map_err(SpringError::Sql)
+}
+
+fn arrow_data_type(sql_type: &SqlType) -> DataType {
+    match sql_type {
+        SqlType::NumericComparable(NumericComparableType::I64Loose(i)) => match i {
+            I64LooseType::SmallInt => DataType::Int16,
+            I64LooseType::Integer => DataType::Int32,
+            I64LooseType::BigInt => DataType::Int64,
+        },
+        SqlType::NumericComparable(NumericComparableType::U64Loose(u)) => match u {
+            U64LooseType::UnsignedInteger => DataType::UInt32,
+            U64LooseType::UnsignedBigInt => DataType::UInt64,
+        },
+        SqlType::NumericComparable(NumericComparableType::F32Loose(F32LooseType::Float)) => {
+            DataType::Float32
+        }
+        SqlType::StringComparableLoose(_) => DataType::Utf8,
+        SqlType::BinaryComparable => DataType::Binary,
+        SqlType::BooleanComparable => DataType::Boolean,
+        SqlType::TimestampComparable => DataType::Timestamp(TimeUnit::Nanosecond, None),
+        SqlType::DurationComparable => DataType::Duration(TimeUnit::Nanosecond),
+    }
+}
+
+/// Schema of a row group. Types of all-NULL columns are taken from `current` schema if possible.
+fn arrow_schema(rows: &[SchemalessRow], current: Option<&Schema>) -> Schema {
+    let columns = rows[0].clone().into_iter().map(|(column, _)| column);
+    let fields = columns
+        .map(|column| {
+            let sql_type = rows
+                .iter()
+                .find_map(|row| match row.get_by_column_name(&column) {
+                    Ok(SqlValue::NotNull(value)) => Some(value.sql_type()),
+                    _ => None,
+                });
+            let data_type = match sql_type {
+                Some(sql_type) => arrow_data_type(&sql_type),
+                None => current
+                    .and_then(|schema| schema.field_with_name(column.as_ref()).ok())
+                    .map(|field| field.data_type().clone())
+                    .unwrap_or(DataType::Utf8),
+            };
+            Field::new(column.as_ref(), data_type, true)
+        })
+        .collect::<Vec<_>>();
+    Schema::new(fields)
+}
+
+/// # Failure
+///
+/// - `SpringError::Sql` when:
+///   - a column in `schema` is not found in a row.
+///   - a value cannot be converted into the column type.
+fn record_batch(rows: &[SchemalessRow], schema: &SchemaRef) -> Result<RecordBatch> {
+    fn unpack<T: SpringValue>(values: &[&SqlValue]) -> Result<Vec<Option<T>>> {
+        values
+            .iter()
+            .map(|value| match value {
+                SqlValue::Null => Ok(None),
+                SqlValue::NotNull(value) => value.unpack::<T>().map(Some),
+            })
+            .collect()
+    }
+
+    let columns = schema
+        .fields()
+        .iter()
+        .map(|field| {
+            let column = ColumnName::new(field.name().clone());
+            let values = rows
+                .iter()
+                .map(|row| row.get_by_column_name(&column))
+                .collect::<Result<Vec<_>>>()?;
+            let values = values.as_slice();
+
+            let array: ArrayRef = match field.data_type() {
+                DataType::Int16 => Arc::new(Int16Array::from(unpack::<i16>(values)?)),
+                DataType::Int32 => Arc::new(Int32Array::from(unpack::<i32>(values)?)),
+                DataType::Int64 => Arc::new(Int64Array::from(unpack::<i64>(values)?)),
+                DataType::UInt32 => Arc::new(UInt32Array::from(unpack::<u32>(values)?)),
+                DataType::UInt64 => Arc::new(UInt64Array::from(unpack::<u64>(values)?)),
+                DataType::Float32 => Arc::new(Float32Array::from(unpack::<f32>(values)?)),
+                DataType::Utf8 => Arc::new(StringArray::from(unpack::<String>(values)?)),
+                DataType::Binary => Arc::new(
+                    unpack::<Vec<u8>>(values)?
+                        .into_iter()
+                        .collect::<BinaryArray>(),
+                ),
+                DataType::Boolean => Arc::new(BooleanArray::from(unpack::<bool>(values)?)),
+                DataType::Timestamp(_, _) => Arc::new(TimestampNanosecondArray::from(
+                    unpack::<SpringTimestamp>(values)?
+                        .into_iter()
+                        .map(|ts| ts.map(|ts| ts.unix_timestamp_nanos() as i64))
+                        .collect::<Vec<_>>(),
+                )),
+                DataType::Duration(_) => Arc::new(DurationNanosecondArray::from(
+                    unpack::<SpringEventDuration>(values)?
+                        .into_iter()
+                        .map(|d| d.map(|d| d.as_std().as_nanos() as i64))
+                        .collect::<Vec<_>>(),
+                )),
+                data_type => unreachable!("{:?} is not mapped from SQL types", data_type),
+            };
+            Ok(array)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    RecordBatch::try_new(schema.clone(), columns)
+        .context("failed to build Arrow record batch")
+        .map_err(SpringError::Sql)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::stream_engine::NnSqlValue;
+
+    use super::*;
+
+    fn row(temperature: SqlValue) -> SchemalessRow {
+        let mut row = SchemalessRow::default();
+        row.insert(
+            ColumnName::new("ts".to_string()),
+            SqlValue::NotNull(NnSqlValue::Timestamp(
+                SpringTimestamp::from_str("1970-01-01 00:00:01.000000000").unwrap(),
+            )),
+        )
+        .unwrap();
+        row.insert(ColumnName::new("temperature".to_string()), temperature)
+            .unwrap();
+        row
+    }
+
+    #[test]
+    fn test_arrow_schema() {
+        let rows = vec![
+            row(SqlValue::Null),
+            row(SqlValue::NotNull(NnSqlValue::Integer(21))),
+        ];
+        let schema = arrow_schema(&rows, None);
+        assert_eq!(
+            schema,
+            Schema::new(vec![
+                Field::new("ts", DataType::Timestamp(TimeUnit::Nanosecond, None), true),
+                Field::new("temperature", DataType::Int32, true),
+            ])
+        );
+
+        let batch = record_batch(&rows, &Arc::new(schema.clone())).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.column(1).null_count(), 1);
+
+        // all-NULL column keeps the type of the current file
+        let rows = vec![row(SqlValue::Null)];
+        assert_eq!(arrow_schema(&rows, Some(&schema)), schema);
+        assert_eq!(
+            arrow_schema(&rows, None).field(1).data_type(),
+            &DataType::Utf8
+        );
+    }
+}
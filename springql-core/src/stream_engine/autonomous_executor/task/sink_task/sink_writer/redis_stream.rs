@@ -0,0 +1,105 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{fmt::Debug, time::Duration};
+
+use anyhow::Context;
+use redis::{streams::StreamMaxlen, Client, Commands, Connection};
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSinkWriterConfig,
+    pipeline::{JsonShapeOptions, Options, RedisStreamSinkOptions},
+    stream_engine::autonomous_executor::{task::sink_task::sink_writer::SinkWriter, SchemalessRow},
+};
+
+/// Appends each row as a JSON value of `FIELD` in a new entry (`XADD <key> * <field> <json>`).
+pub struct RedisStreamSinkWriter {
+    options: RedisStreamSinkOptions,
+
+    addr: String,
+    connection: Connection,
+
+    json_shape: JsonShapeOptions,
+}
+
+impl SinkWriter for RedisStreamSinkWriter {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to connect to Redis.
+    /// - `SpringError::InvalidOption`
+    fn start(options: &Options, config: &SpringSinkWriterConfig) -> Result<Self> {
+        let json_shape = JsonShapeOptions::try_from(options)?;
+        let options = RedisStreamSinkOptions::try_from(options)?;
+
+        let client =
+            Client::open(options.url.as_str()).map_err(|e| SpringError::InvalidOption {
+                key: "URL".to_string(),
+                value: options.url.clone(),
+                source: e.into(),
+            })?;
+        let addr = client.get_connection_info().addr.to_string();
+        let foreign_io = |e: anyhow::Error| SpringError::ForeignIo {
+            source: e,
+            foreign_info: ForeignInfo::Redis(addr.clone()),
+        };
+
+        let connection = client
+            .get_connection_with_timeout(Duration::from_millis(
+                config.net_connect_timeout_msec as u64,
+            ))
+            .context("failed to connect to Redis")
+            .map_err(foreign_io)?;
+        connection
+            .set_write_timeout(Some(Duration::from_millis(
+                config.net_write_timeout_msec as u64,
+            )))
+            .context("failed to set write timeout")
+            .map_err(foreign_io)?;
+
+        log::info!("[RedisStreamSinkWriter] Ready to write into {}", addr);
+
+        Ok(Self {
+            json_shape,
+            options,
+            addr,
+            connection,
+        })
+    }
+
+    fn send_row(&mut self, row: SchemalessRow) -> Result<()> {
+        let stream_key = row.render(&self.options.stream_key)?;
+        let json_s = row.into_json(&self.json_shape)?.to_string();
+        let items = [(self.options.field.as_str(), json_s.as_str())];
+
+        log::debug!(
+            "[RedisStreamSinkWriter] Adding entry to {}: {}",
+            stream_key,
+            json_s
+        );
+
+        let added: redis::RedisResult<String> = match self.options.max_len {
+            Some(max_len) => {
+                self.connection
+                    .xadd_maxlen(&stream_key, StreamMaxlen::Approx(max_len), "*", &items)
+            }
+            None => self.connection.xadd(&stream_key, "*", &items),
+        };
+        added
+            .with_context(|| format!("failed to add entry to stream {}", stream_key))
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::Redis(self.addr.clone()),
+            })?;
+        Ok(())
+    }
+}
+
+impl Debug for RedisStreamSinkWriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisStreamSinkWriter")
+            .field("addr", &self.addr)
+            .field("options", &self.options)
+            .finish()
+    }
+}
@@ -0,0 +1,579 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Sink writer uploading batches of rows to S3-compatible object storage.
+//!
+//! Objects are spooled into a local directory first and deleted only after uploaded,
+//! so that rows survive while a vehicle is offline (and across restarts).
+
+use std::{
+    fs,
+    io::Write,
+    path::PathBuf,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, Context};
+use flate2::{write::GzEncoder, Compression};
+use hmac::{Hmac, Mac};
+use reqwest::{blocking::Client, Url};
+use sha2::{Digest, Sha256};
+use time::{macros::format_description, OffsetDateTime};
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSinkWriterConfig,
+    pipeline::{JsonShapeOptions, Options, S3ObjectFormat, S3Options},
+    stream_engine::autonomous_executor::{
+        performance_metrics::SinkHealth, task::sink_task::sink_writer::SinkWriter, SchemalessRow,
+    },
+};
+#[cfg(feature = "sink-parquet")]
+use crate::{
+    pipeline::ParquetCompression,
+    stream_engine::autonomous_executor::task::sink_task::sink_writer::parquet::encode_parquet,
+};
+
+/// Objects being written into the spool directory. Not uploaded.
+const TMP_SUFFIX: &str = ".tmp";
+
+/// Batches rows into objects (`BATCH_SIZE` rows or rows in `BATCH_INTERVAL_SEC`) and puts them to `BUCKET`.
+///
+/// An object is named `<KEY_PREFIX><unix time in millis>-<sequence>.<jsonl | jsonl.gz | parquet>`.
+/// Requests are signed by AWS Signature Version 4.
+///
+/// Failed uploads are retried from the oldest spooled object with exponential backoff
+/// (from `sink_writer.http_retry_backoff_msec` to `MAX_RETRY_INTERVAL_SEC`).
+/// When the spool exceeds `MAX_SPOOL_BYTES`, the oldest objects are deleted.
+/// Rows in an incomplete batch are spooled when the sink writer is dropped.
+#[derive(Debug)]
+pub struct S3SinkWriter {
+    options: S3Options,
+    client: Client,
+
+    /// Rows of the next object.
+    rows: Vec<SchemalessRow>,
+    /// When the first row of the next object arrived.
+    batch_started_at: Option<Instant>,
+    n_objects: u64,
+
+    /// Spooled objects may exist.
+    has_spooled: bool,
+    initial_backoff: Duration,
+    backoff: Duration,
+    /// Uploads are not tried until this time after a failure.
+    next_upload_at: Option<Instant>,
+
+    health: SinkHealth,
+
+    json_shape: JsonShapeOptions,
+}
+
+impl SinkWriter for S3SinkWriter {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - `SPOOL_DIRECTORY` is not a directory.
+    /// - `SpringError::InvalidOption`
+    /// - `SpringError::Unavailable` when:
+    ///   - `FORMAT 'PARQUET'` without `sink-parquet` feature.
+    fn start(options: &Options, config: &SpringSinkWriterConfig) -> Result<Self> {
+        let json_shape = JsonShapeOptions::try_from(options)?;
+        let options = S3Options::try_from(options)?;
+
+        #[cfg(not(feature = "sink-parquet"))]
+        if options.format == S3ObjectFormat::Parquet {
+            return Err(SpringError::Unavailable {
+                resource: "PARQUET format of S3 sink writer".to_string(),
+                source: anyhow!("springql-core is built without `sink-parquet` feature"),
+            });
+        }
+
+        if !options.spool_directory.is_dir() {
+            return Err(SpringError::ForeignIo {
+                source: anyhow!("not a directory"),
+                foreign_info: ForeignInfo::File(options.spool_directory),
+            });
+        }
+
+        let client = Client::builder()
+            .connect_timeout(Duration::from_millis(
+                config.http_connect_timeout_msec as u64,
+            ))
+            .timeout(options.upload_timeout)
+            .build()
+            .context("failed to create HTTP client")
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::ObjectStorage(options.endpoint.to_string()),
+            })?;
+
+        log::info!(
+            "[S3SinkWriter] Ready to upload to bucket {} at {} (spool: {:?})",
+            options.bucket,
+            options.endpoint,
+            options.spool_directory
+        );
+
+        let initial_backoff = Duration::from_millis(config.http_retry_backoff_msec as u64);
+        let mut sink_writer = Self {
+            json_shape,
+            rows: Vec::with_capacity(options.batch_size),
+            options,
+            client,
+            batch_started_at: None,
+            n_objects: 0,
+            // objects left by the previous run
+            has_spooled: true,
+            initial_backoff,
+            backoff: initial_backoff,
+            next_upload_at: None,
+            health: SinkHealth::default(),
+        };
+        sink_writer.upload_spooled()?;
+        Ok(sink_writer)
+    }
+
+    fn send_row(&mut self, row: SchemalessRow) -> Result<()> {
+        let started_at = *self.batch_started_at.get_or_insert_with(Instant::now);
+        self.rows.push(row);
+
+        let interval_passed = matches!(
+            self.options.batch_interval,
+            Some(batch_interval) if started_at.elapsed() >= batch_interval
+        );
+        if self.rows.len() >= self.options.batch_size || interval_passed {
+            self.spool_batch()?;
+        }
+        self.upload_spooled()
+    }
+
+    fn health(&self) -> Option<SinkHealth> {
+        Some(self.health)
+    }
+}
+
+impl S3SinkWriter {
+    fn foreign_info(&self) -> ForeignInfo {
+        ForeignInfo::ObjectStorage(format!(
+            "{} ({})",
+            self.options.endpoint, self.options.bucket
+        ))
+    }
+
+    fn spool_error(path: PathBuf) -> impl FnOnce(anyhow::Error) -> SpringError {
+        move |e| SpringError::ForeignIo {
+            source: e,
+            foreign_info: ForeignInfo::File(path),
+        }
+    }
+
+    /// Encodes buffered rows into an object in the spool directory.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to write the object into the spool directory.
+    /// - `SpringError::Sql` when:
+    ///   - failed to encode rows.
+    fn spool_batch(&mut self) -> Result<()> {
+        self.batch_started_at = None;
+        if self.rows.is_empty() {
+            return Ok(());
+        }
+        let rows = std::mem::take(&mut self.rows);
+
+        let (extension, body) = match self.options.format {
+            S3ObjectFormat::JsonLines => {
+                let lines = rows
+                    .into_iter()
+                    .map(|row| Ok(row.into_json(&self.json_shape)?.to_string() + "\n"))
+                    .collect::<Result<String>>()?;
+                if self.options.gzip {
+                    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                    let body = encoder
+                        .write_all(lines.as_bytes())
+                        .and_then(|_| encoder.finish())
+                        .context("failed to gzip JSON lines")
+                        .map_err(SpringError::Sql)?;
+                    ("jsonl.gz", body)
+                } else {
+                    ("jsonl", lines.into_bytes())
+                }
+            }
+            #[cfg(feature = "sink-parquet")]
+            S3ObjectFormat::Parquet => (
+                "parquet",
+                encode_parquet(&rows, ParquetCompression::Snappy)?,
+            ),
+            #[cfg(not(feature = "sink-parquet"))]
+            S3ObjectFormat::Parquet => unreachable!("rejected at start()"),
+        };
+
+        self.n_objects += 1;
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        // zero-padded to upload in creation order
+        let name = format!("{:013}-{:06}.{}", created_at, self.n_objects, extension);
+        let path = self.options.spool_directory.join(&name);
+        let tmp_path = self
+            .options
+            .spool_directory
+            .join(format!("{}{}", name, TMP_SUFFIX));
+
+        fs::write(&tmp_path, &body)
+            .and_then(|_| fs::rename(&tmp_path, &path))
+            .context("failed to spool object")
+            .map_err(Self::spool_error(tmp_path))?;
+        self.has_spooled = true;
+
+        self.trim_spool()
+    }
+
+    /// Spooled objects (excluding ones being written) from the oldest.
+    fn spooled_objects(&self) -> Result<Vec<(PathBuf, u64)>> {
+        let dir = &self.options.spool_directory;
+        let mut objects = fs::read_dir(dir)
+            .and_then(|entries| {
+                entries
+                    .map(|entry| {
+                        let entry = entry?;
+                        Ok((entry.path(), entry.metadata()?.len()))
+                    })
+                    .collect::<std::io::Result<Vec<_>>>()
+            })
+            .context("failed to list spooled objects")
+            .map_err(Self::spool_error(dir.clone()))?;
+        objects.retain(|(path, _)| path.is_file() && !path.to_string_lossy().ends_with(TMP_SUFFIX));
+        objects.sort();
+        Ok(objects)
+    }
+
+    /// Deletes the oldest objects while the spool exceeds `MAX_SPOOL_BYTES`.
+    fn trim_spool(&mut self) -> Result<()> {
+        let objects = self.spooled_objects()?;
+        let mut spool_bytes: u64 = objects.iter().map(|(_, len)| len).sum();
+        for (path, len) in objects {
+            if spool_bytes <= self.options.max_spool_bytes {
+                break;
+            }
+            log::warn!(
+                "[S3SinkWriter] Spool exceeds {} bytes. Deleting {:?} without upload.",
+                self.options.max_spool_bytes,
+                path
+            );
+            fs::remove_file(&path)
+                .context("failed to delete spooled object")
+                .map_err(Self::spool_error(path))?;
+            spool_bytes -= len;
+        }
+        Ok(())
+    }
+
+    /// Uploads spooled objects from the oldest, unless backing off from a failure.
+    ///
+    /// Connection errors and 5xx responses are not reported as errors (objects stay in the spool).
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to read or delete spooled objects.
+    ///   - the storage rejected an object with 4xx response (e.g. wrong credentials).
+    fn upload_spooled(&mut self) -> Result<()> {
+        if !self.has_spooled
+            || matches!(self.next_upload_at, Some(next_upload_at) if Instant::now() < next_upload_at)
+        {
+            return Ok(());
+        }
+
+        for (path, _) in self.spooled_objects()? {
+            let name = path
+                .file_name()
+                .expect("listed from directory")
+                .to_string_lossy()
+                .to_string();
+            let body = fs::read(&path)
+                .context("failed to read spooled object")
+                .map_err(Self::spool_error(path.clone()))?;
+
+            if let Err(failure) = self.put_object(&name, body) {
+                self.next_upload_at = Some(Instant::now() + self.backoff);
+                log::warn!(
+                    "[S3SinkWriter] Failed to upload {}. Retrying in {:?}: {:?}",
+                    name,
+                    self.backoff,
+                    failure.source
+                );
+                self.backoff = (self.backoff * 2).min(self.options.max_retry_interval);
+                self.health.retries += 1;
+
+                return if failure.retryable {
+                    Ok(())
+                } else {
+                    Err(SpringError::ForeignIo {
+                        source: failure.source,
+                        foreign_info: self.foreign_info(),
+                    })
+                };
+            }
+
+            fs::remove_file(&path)
+                .context("failed to delete uploaded object")
+                .map_err(Self::spool_error(path))?;
+            log::debug!("[S3SinkWriter] Uploaded {}", name);
+        }
+
+        self.has_spooled = false;
+        self.next_upload_at = None;
+        self.backoff = self.initial_backoff;
+        Ok(())
+    }
+
+    fn put_object(&self, name: &str, body: Vec<u8>) -> std::result::Result<(), UploadFailure> {
+        let endpoint = &self.options.endpoint;
+        let key = format!("{}{}", self.options.key_prefix, name);
+        let canonical_uri = format!(
+            "{}/{}/{}",
+            endpoint.path().trim_end_matches('/'),
+            uri_encode(&self.options.bucket),
+            key.split('/').map(uri_encode).collect::<Vec<_>>().join("/")
+        );
+        let mut url = endpoint.clone();
+        url.set_path(&canonical_uri);
+
+        let host = match endpoint.port() {
+            Some(port) => format!("{}:{}", endpoint.host_str().unwrap_or_default(), port),
+            None => endpoint.host_str().unwrap_or_default().to_string(),
+        };
+        let payload_sha256 = hex(&Sha256::digest(&body));
+        let amz_date = OffsetDateTime::now_utc()
+            .format(format_description!(
+                "[year][month][day]T[hour][minute][second]Z"
+            ))
+            .expect("valid format");
+        let authorization = authorization(
+            &self.options,
+            "PUT",
+            &canonical_uri,
+            &host,
+            &payload_sha256,
+            &amz_date,
+        );
+
+        let content_type = match self.options.format {
+            S3ObjectFormat::JsonLines if self.options.gzip => "application/gzip",
+            S3ObjectFormat::JsonLines => "application/x-ndjson",
+            S3ObjectFormat::Parquet => "application/octet-stream",
+        };
+
+        let resp = self
+            .client
+            .put(url)
+            .header("Host", host)
+            .header("Content-Type", content_type)
+            .header("x-amz-content-sha256", payload_sha256)
+            .header("x-amz-date", amz_date)
+            .header("Authorization", authorization)
+            .body(body)
+            .send()
+            .map_err(|e| UploadFailure {
+                retryable: true,
+                source: e.into(),
+            })?;
+
+        let status = resp.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            Err(UploadFailure {
+                retryable: !status.is_client_error(),
+                source: anyhow!("PUT {} failed with status {}", key, status),
+            })
+        }
+    }
+}
+
+impl Drop for S3SinkWriter {
+    fn drop(&mut self) {
+        // uploaded at next start if offline
+        if let Err(e) = self.spool_batch().and_then(|_| self.upload_spooled()) {
+            log::warn!("[S3SinkWriter] Failed to upload the last batch: {:?}", e);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct UploadFailure {
+    /// Connection errors and 5xx responses are retryable.
+    retryable: bool,
+    source: anyhow::Error,
+}
+
+/// `Authorization` header of AWS Signature Version 4 signing `host`, `x-amz-content-sha256` and `x-amz-date` headers.
+fn authorization(
+    options: &S3Options,
+    method: &str,
+    canonical_uri: &str,
+    host: &str,
+    payload_sha256: &str,
+    amz_date: &str,
+) -> String {
+    const SIGNED_HEADERS: &str = "host;x-amz-content-sha256;x-amz-date";
+
+    let date = &amz_date[..8];
+    let scope = format!("{}/{}/s3/aws4_request", date, options.region);
+    let canonical_request = format!(
+        "{}\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+        method, canonical_uri, host, payload_sha256, amz_date, SIGNED_HEADERS, payload_sha256
+    );
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        hex(&Sha256::digest(canonical_request.as_bytes()))
+    );
+
+    let signing_key = [date, options.region.as_str(), "s3", "aws4_request"]
+        .iter()
+        .fold(
+            format!("AWS4{}", options.secret_access_key).into_bytes(),
+            |key, data| hmac_sha256(&key, data.as_bytes()),
+        );
+    let signature = hex(&hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+    format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        options.access_key_id, scope, SIGNED_HEADERS, signature
+    )
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Percent-encodes all characters but unreserved ones (a path segment of SigV4 canonical URI).
+fn uri_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read},
+        net::{SocketAddr, TcpListener},
+        sync::mpsc,
+        thread,
+    };
+
+    use crate::{
+        pipeline::{ColumnName, OptionsBuilder},
+        stream_engine::{NnSqlValue, SqlValue},
+    };
+
+    use super::*;
+
+    /// Responds with `statuses` in order and sends request lines and bodies to the receiver.
+    fn serve(statuses: Vec<u16>) -> (SocketAddr, mpsc::Receiver<(String, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for status in statuses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" {
+                        break;
+                    }
+                    if let Some(v) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = v.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                tx.send((
+                    request_line.trim().to_string(),
+                    String::from_utf8(body).unwrap(),
+                ))
+                .unwrap();
+
+                write!(
+                    &stream,
+                    "HTTP/1.1 {} X\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                )
+                .unwrap();
+            }
+        });
+
+        (addr, rx)
+    }
+
+    fn row(n: i32) -> SchemalessRow {
+        let mut row = SchemalessRow::default();
+        row.insert(
+            ColumnName::new("n".to_string()),
+            SqlValue::NotNull(NnSqlValue::Integer(n)),
+        )
+        .unwrap();
+        row
+    }
+
+    #[test]
+    fn test_spool_while_offline() {
+        let spool = tempfile::tempdir().unwrap();
+        let (addr, requests) = serve(vec![503, 200, 200]);
+
+        let options = OptionsBuilder::default()
+            .add("ENDPOINT", format!("http://{}", addr))
+            .add("REGION", "us-east-1")
+            .add("BUCKET", "logs")
+            .add("KEY_PREFIX", "car-0001/")
+            .add("ACCESS_KEY_ID", "minio")
+            .add("SECRET_ACCESS_KEY", "minio123")
+            .add("GZIP", "FALSE")
+            .add("BATCH_SIZE", "1")
+            .add("SPOOL_DIRECTORY", spool.path().to_string_lossy())
+            .add("HTTP_RETRY_BACKOFF_MSEC", "1")
+            .build();
+        let config = SpringSinkWriterConfig::fx_default()
+            .overridden_by(&options)
+            .unwrap();
+        let mut sink_writer = S3SinkWriter::start(&options, &config).unwrap();
+
+        // 503: kept in the spool
+        sink_writer.send_row(row(1)).unwrap();
+        assert_eq!(requests.recv().unwrap().1, "{\"n\":1}\n");
+        assert_eq!(sink_writer.spooled_objects().unwrap().len(), 1);
+        assert_eq!(sink_writer.health().unwrap().retries, 1);
+
+        thread::sleep(Duration::from_millis(10));
+        sink_writer.send_row(row(2)).unwrap();
+        let (request_line, body) = requests.recv().unwrap();
+        assert!(request_line.starts_with("PUT /logs/car-0001/"));
+        assert_eq!(body, "{\"n\":1}\n");
+        assert_eq!(requests.recv().unwrap().1, "{\"n\":2}\n");
+        assert!(sink_writer.spooled_objects().unwrap().is_empty());
+    }
+}
@@ -1,35 +1,179 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+#[cfg(not(all(
+    feature = "sink-http",
+    feature = "mqtt",
+    feature = "sink-gpio",
+    feature = "sink-cloud-iot",
+    feature = "redis-stream",
+    feature = "nats-messaging",
+    feature = "zeromq",
+    feature = "sink-parquet",
+    feature = "sink-s3",
+    unix
+)))]
+use anyhow::anyhow;
+
+#[cfg(not(all(
+    feature = "sink-http",
+    feature = "mqtt",
+    feature = "sink-gpio",
+    feature = "sink-cloud-iot",
+    feature = "redis-stream",
+    feature = "nats-messaging",
+    feature = "zeromq",
+    feature = "sink-parquet",
+    feature = "sink-s3",
+    unix
+)))]
+use crate::api::error::SpringError;
+#[cfg(feature = "sink-cloud-iot")]
+use crate::stream_engine::autonomous_executor::task::sink_task::sink_writer::cloud_iot::{
+    AwsIotCoreSinkWriter, AzureIotHubSinkWriter,
+};
+#[cfg(feature = "sink-gpio")]
+use crate::stream_engine::autonomous_executor::task::sink_task::sink_writer::gpio::GpioSinkWriter;
+#[cfg(feature = "sink-http")]
+use crate::stream_engine::autonomous_executor::task::sink_task::sink_writer::http_client::HttpClientSinkWriter;
+#[cfg(feature = "mqtt")]
+use crate::stream_engine::autonomous_executor::task::sink_task::sink_writer::mqtt::MqttSinkWriter;
+#[cfg(feature = "nats-messaging")]
+use crate::stream_engine::autonomous_executor::task::sink_task::sink_writer::nats::NatsSinkWriter;
+#[cfg(feature = "sink-parquet")]
+use crate::stream_engine::autonomous_executor::task::sink_task::sink_writer::parquet::ParquetSinkWriter;
+#[cfg(feature = "redis-stream")]
+use crate::stream_engine::autonomous_executor::task::sink_task::sink_writer::redis_stream::RedisStreamSinkWriter;
+#[cfg(feature = "sink-s3")]
+use crate::stream_engine::autonomous_executor::task::sink_task::sink_writer::s3::S3SinkWriter;
+#[cfg(unix)]
+use crate::stream_engine::autonomous_executor::task::sink_task::sink_writer::unix_socket::UnixSocketSinkWriter;
+#[cfg(feature = "zeromq")]
+use crate::stream_engine::autonomous_executor::task::sink_task::sink_writer::zeromq::ZeroMqSinkWriter;
 use crate::{
     api::{error::Result, SpringSinkWriterConfig},
-    pipeline::{Options, SinkWriterType},
+    pipeline::{OfflineSpoolOptions, Options, SinkWriterType},
     stream_engine::autonomous_executor::task::sink_task::sink_writer::{
-        http_client::HttpClientSinkWriter, in_memory_queue::InMemoryQueueSinkWriter,
-        net::NetSinkWriter, SinkWriter,
+        command::CommandSinkWriter, file::FileSinkWriter, in_memory_queue::InMemoryQueueSinkWriter,
+        influxdb::InfluxDbSinkWriter, net::NetSinkWriter, offline_spool::OfflineSpoolSinkWriter,
+        springql_link::SpringqlLinkSinkWriter, SinkWriter,
     },
 };
 
 pub struct SinkWriterFactory;
 
+/// Starts `S`, wrapped by `OfflineSpoolSinkWriter` if `OFFLINE_SPOOL_DIRECTORY` option is set.
+fn start<S: SinkWriter>(
+    options: &Options,
+    config: &SpringSinkWriterConfig,
+) -> Result<Box<dyn SinkWriter>> {
+    if OfflineSpoolOptions::from_options(options)?.is_some() {
+        let sink_writer = OfflineSpoolSinkWriter::<S>::start(options, config)?;
+        Ok(Box::new(sink_writer) as Box<dyn SinkWriter>)
+    } else {
+        let sink_writer = S::start(options, config)?;
+        Ok(Box::new(sink_writer) as Box<dyn SinkWriter>)
+    }
+}
+
 impl SinkWriterFactory {
+    /// # Failure
+    ///
+    /// - `SpringError::Unavailable` when:
+    ///   - the cargo feature for `sink_writer_type` is disabled.
+    ///   - `sink_writer_type` is not supported on this platform.
+    /// - `SpringError::InvalidOption` when:
+    ///   - `OFFLINE_SPOOL_*` options are invalid.
+    /// - Raises Err from `SpringSinkWriterConfig::overridden_by()` and `SinkWriter::start()` as-is.
     pub fn sink(
         sink_writer_type: &SinkWriterType,
         options: &Options,
         config: &SpringSinkWriterConfig,
     ) -> Result<Box<dyn SinkWriter>> {
+        let config = &config.overridden_by(options)?;
         match sink_writer_type {
-            SinkWriterType::Net => {
-                let sink_writer = NetSinkWriter::start(options, config)?;
-                Ok(Box::new(sink_writer) as Box<dyn SinkWriter>)
-            }
-            SinkWriterType::Http1Client => {
-                let sink_writer = HttpClientSinkWriter::start(options, config)?;
-                Ok(Box::new(sink_writer) as Box<dyn SinkWriter>)
-            }
-            SinkWriterType::InMemoryQueue => {
-                let sink = InMemoryQueueSinkWriter::start(options, config)?;
-                Ok(Box::new(sink) as Box<dyn SinkWriter>)
-            }
+            SinkWriterType::Net => start::<NetSinkWriter>(options, config),
+            #[cfg(feature = "mqtt")]
+            SinkWriterType::NetMqtt => start::<MqttSinkWriter>(options, config),
+            #[cfg(not(feature = "mqtt"))]
+            SinkWriterType::NetMqtt => Err(SpringError::Unavailable {
+                resource: "NET_MQTT sink writer".to_string(),
+                source: anyhow!("springql-core is built without `mqtt` feature"),
+            }),
+            #[cfg(feature = "sink-http")]
+            SinkWriterType::Http1Client => start::<HttpClientSinkWriter>(options, config),
+            #[cfg(not(feature = "sink-http"))]
+            SinkWriterType::Http1Client => Err(SpringError::Unavailable {
+                resource: "HTTP1_CLIENT sink writer".to_string(),
+                source: anyhow!("springql-core is built without `sink-http` feature"),
+            }),
+            SinkWriterType::InMemoryQueue => start::<InMemoryQueueSinkWriter>(options, config),
+            SinkWriterType::Command => start::<CommandSinkWriter>(options, config),
+            SinkWriterType::InfluxDb => start::<InfluxDbSinkWriter>(options, config),
+            #[cfg(feature = "sink-gpio")]
+            SinkWriterType::Gpio => start::<GpioSinkWriter>(options, config),
+            #[cfg(not(feature = "sink-gpio"))]
+            SinkWriterType::Gpio => Err(SpringError::Unavailable {
+                resource: "GPIO sink writer".to_string(),
+                source: anyhow!("springql-core is built without `sink-gpio` feature"),
+            }),
+            #[cfg(feature = "sink-cloud-iot")]
+            SinkWriterType::AzureIotHub => start::<AzureIotHubSinkWriter>(options, config),
+            #[cfg(feature = "sink-cloud-iot")]
+            SinkWriterType::AwsIotCore => start::<AwsIotCoreSinkWriter>(options, config),
+            #[cfg(not(feature = "sink-cloud-iot"))]
+            SinkWriterType::AzureIotHub => Err(SpringError::Unavailable {
+                resource: "AZURE_IOT_HUB sink writer".to_string(),
+                source: anyhow!("springql-core is built without `sink-cloud-iot` feature"),
+            }),
+            #[cfg(not(feature = "sink-cloud-iot"))]
+            SinkWriterType::AwsIotCore => Err(SpringError::Unavailable {
+                resource: "AWS_IOT_CORE sink writer".to_string(),
+                source: anyhow!("springql-core is built without `sink-cloud-iot` feature"),
+            }),
+            #[cfg(feature = "redis-stream")]
+            SinkWriterType::RedisStream => start::<RedisStreamSinkWriter>(options, config),
+            #[cfg(not(feature = "redis-stream"))]
+            SinkWriterType::RedisStream => Err(SpringError::Unavailable {
+                resource: "REDIS_STREAM sink writer".to_string(),
+                source: anyhow!("springql-core is built without `redis-stream` feature"),
+            }),
+            #[cfg(feature = "nats-messaging")]
+            SinkWriterType::Nats => start::<NatsSinkWriter>(options, config),
+            #[cfg(not(feature = "nats-messaging"))]
+            SinkWriterType::Nats => Err(SpringError::Unavailable {
+                resource: "NATS sink writer".to_string(),
+                source: anyhow!("springql-core is built without `nats-messaging` feature"),
+            }),
+            #[cfg(feature = "zeromq")]
+            SinkWriterType::ZeroMq => start::<ZeroMqSinkWriter>(options, config),
+            #[cfg(not(feature = "zeromq"))]
+            SinkWriterType::ZeroMq => Err(SpringError::Unavailable {
+                resource: "ZEROMQ sink writer".to_string(),
+                source: anyhow!("springql-core is built without `zeromq` feature"),
+            }),
+            #[cfg(feature = "sink-parquet")]
+            SinkWriterType::Parquet => start::<ParquetSinkWriter>(options, config),
+            #[cfg(not(feature = "sink-parquet"))]
+            SinkWriterType::Parquet => Err(SpringError::Unavailable {
+                resource: "PARQUET sink writer".to_string(),
+                source: anyhow!("springql-core is built without `sink-parquet` feature"),
+            }),
+            #[cfg(feature = "sink-s3")]
+            SinkWriterType::S3 => start::<S3SinkWriter>(options, config),
+            #[cfg(not(feature = "sink-s3"))]
+            SinkWriterType::S3 => Err(SpringError::Unavailable {
+                resource: "S3 sink writer".to_string(),
+                source: anyhow!("springql-core is built without `sink-s3` feature"),
+            }),
+            SinkWriterType::SpringqlLink => start::<SpringqlLinkSinkWriter>(options, config),
+            SinkWriterType::File => start::<FileSinkWriter>(options, config),
+            #[cfg(unix)]
+            SinkWriterType::UnixSocket => start::<UnixSocketSinkWriter>(options, config),
+            #[cfg(not(unix))]
+            SinkWriterType::UnixSocket => Err(SpringError::Unavailable {
+                resource: "UNIX_SOCKET sink writer".to_string(),
+                source: anyhow!("UNIX domain sockets are not available on this platform"),
+            }),
         }
     }
 }
@@ -1,7 +1,7 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     sync::{Arc, Mutex},
 };
 
@@ -58,6 +58,17 @@ impl SinkWriterRepository {
         }
     }
 
+    /// Drops sink writers not in `names` (dropped from the pipeline), closing their connections.
+    pub fn retain(&self, names: &HashSet<&SinkWriterName>) {
+        self.sinks.write().retain(|name, _| {
+            let retained = names.contains(name);
+            if !retained {
+                log::debug!("[SinkWriterRepository] unregistered sink subtask: {}", name);
+            }
+            retained
+        });
+    }
+
     /// # Panics
     ///
     /// `name` is not registered yet
@@ -0,0 +1,85 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    io::{BufWriter, Write},
+    net::{SocketAddr, TcpStream},
+    time::Duration,
+};
+
+use anyhow::Context;
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSinkWriterConfig,
+    pipeline::{Options, SpringqlLinkSinkOptions},
+    stream_engine::autonomous_executor::{
+        row::LinkEncoder, task::sink_task::sink_writer::SinkWriter, SchemalessRow,
+    },
+};
+
+/// Sends rows in the binary encoding of `LinkEncoder` to a `SPRINGQL_LINK` source reader of another SpringQL instance.
+#[derive(Debug)]
+pub struct SpringqlLinkSinkWriter {
+    foreign_addr: SocketAddr,
+    tcp_stream_writer: BufWriter<TcpStream>,
+
+    encoder: LinkEncoder,
+    /// Reused for frames of each row.
+    buf: Vec<u8>,
+}
+
+impl SinkWriter for SpringqlLinkSinkWriter {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to connect to the remote host.
+    /// - `SpringError::InvalidOption`
+    fn start(options: &Options, config: &SpringSinkWriterConfig) -> Result<Self> {
+        let options = SpringqlLinkSinkOptions::try_from(options)?;
+        let sock_addr = SocketAddr::new(options.remote_host, options.remote_port);
+        let foreign_io = |e: anyhow::Error| SpringError::ForeignIo {
+            source: e,
+            foreign_info: ForeignInfo::GenericTcp(sock_addr),
+        };
+
+        let tcp_stream = TcpStream::connect_timeout(
+            &sock_addr,
+            Duration::from_millis(config.net_connect_timeout_msec as u64),
+        )
+        .context("failed to connect to remote host")
+        .map_err(foreign_io)?;
+        tcp_stream
+            .set_write_timeout(Some(Duration::from_millis(
+                config.net_write_timeout_msec as u64,
+            )))
+            .context("failed to set timeout to remote host")
+            .map_err(foreign_io)?;
+        tcp_stream
+            .set_nodelay(true)
+            .context("failed to set TCP_NODELAY")
+            .map_err(foreign_io)?;
+
+        log::info!("[SpringqlLinkSinkWriter] Ready to write into {}", sock_addr);
+
+        Ok(Self {
+            foreign_addr: sock_addr,
+            tcp_stream_writer: BufWriter::new(tcp_stream),
+            encoder: LinkEncoder::default(),
+            buf: Vec::new(),
+        })
+    }
+
+    fn send_row(&mut self, row: SchemalessRow) -> Result<()> {
+        self.buf.clear();
+        self.encoder.encode(row, &mut self.buf)?;
+
+        self.tcp_stream_writer
+            .write_all(&self.buf)
+            .and_then(|_| self.tcp_stream_writer.flush())
+            .context("failed to write row to remote SpringQL")
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::GenericTcp(self.foreign_addr),
+            })
+    }
+}
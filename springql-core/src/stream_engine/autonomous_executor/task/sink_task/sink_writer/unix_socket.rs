@@ -0,0 +1,108 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{os::unix::net::UnixStream, time::Duration};
+
+use anyhow::Context;
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSinkWriterConfig,
+    pipeline::{JsonShapeOptions, Options, UnixSocketOptions},
+    stream_engine::autonomous_executor::{
+        task::sink_task::sink_writer::{net::LineSinkWriter, SinkWriter},
+        SchemalessRow,
+    },
+};
+
+/// Connects to a UNIX domain socket at `PATH` and writes a JSON object per line, like `NET_CLIENT`.
+#[derive(Debug)]
+pub struct UnixSocketSinkWriter {
+    line_writer: LineSinkWriter<UnixStream>,
+}
+
+impl SinkWriter for UnixSocketSinkWriter {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to connect to the socket.
+    /// - `SpringError::InvalidOption`
+    fn start(options: &Options, config: &SpringSinkWriterConfig) -> Result<Self> {
+        let json_shape = JsonShapeOptions::try_from(options)?;
+        let options = UnixSocketOptions::try_from(options)?;
+
+        let foreign_io = |e: anyhow::Error| SpringError::ForeignIo {
+            source: e,
+            foreign_info: ForeignInfo::UnixSocket(options.path.clone()),
+        };
+        let stream = UnixStream::connect(&options.path)
+            .context("failed to connect to UNIX domain socket")
+            .map_err(foreign_io)?;
+        stream
+            .set_write_timeout(Some(Duration::from_millis(
+                config.net_write_timeout_msec as u64,
+            )))
+            .context("failed to set timeout to UNIX domain socket")
+            .map_err(foreign_io)?;
+
+        log::info!(
+            "[UnixSocketSinkWriter] Ready to write into {:?}",
+            options.path
+        );
+
+        Ok(Self {
+            line_writer: LineSinkWriter::new(
+                stream,
+                ForeignInfo::UnixSocket(options.path),
+                json_shape,
+            ),
+        })
+    }
+
+    fn send_row(&mut self, row: SchemalessRow) -> Result<()> {
+        self.line_writer.send_row(row)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader},
+        os::unix::net::UnixListener,
+    };
+
+    use super::*;
+    use crate::{pipeline::OptionsBuilder, stream_engine::autonomous_executor::row::JsonObject};
+
+    #[test]
+    fn test_sink_writer_unix_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sink.sock");
+        let listener = UnixListener::bind(&path).unwrap();
+
+        let options = OptionsBuilder::default()
+            .add("PATH", path.display().to_string())
+            .build();
+        let mut sink_writer =
+            UnixSocketSinkWriter::start(&options, &SpringSinkWriterConfig::fx_default()).unwrap();
+
+        sink_writer
+            .send_row(SchemalessRow::fx_city_temperature_tokyo())
+            .unwrap();
+        sink_writer
+            .send_row(SchemalessRow::fx_city_temperature_osaka())
+            .unwrap();
+
+        let (stream, _) = listener.accept().unwrap();
+        let mut lines = BufReader::new(stream).lines();
+        for expected in [
+            JsonObject::fx_city_temperature_tokyo(),
+            JsonObject::fx_city_temperature_osaka(),
+        ] {
+            let line = lines.next().unwrap().unwrap();
+            assert_eq!(
+                JsonObject::new(serde_json::from_str(&line).unwrap()),
+                expected
+            );
+        }
+    }
+}
@@ -0,0 +1,127 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{fmt::Debug, sync::Mutex};
+
+use anyhow::{anyhow, Context};
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSinkWriterConfig,
+    pipeline::{
+        JsonShapeOptions, Options, ZeroMqEndpoint, ZeroMqSinkOptions, ZeroMqSinkSocketType,
+    },
+    stream_engine::autonomous_executor::{task::sink_task::sink_writer::SinkWriter, SchemalessRow},
+};
+
+/// Sends each row as a JSON message with a PUB or PUSH socket, preceded by a topic frame if `TOPIC` is set.
+pub struct ZeroMqSinkWriter {
+    options: ZeroMqSinkOptions,
+
+    /// `zmq::Socket` is not Sync.
+    socket: Mutex<zmq::Socket>,
+
+    json_shape: JsonShapeOptions,
+}
+
+impl SinkWriter for ZeroMqSinkWriter {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to create, connect or bind the socket.
+    /// - `SpringError::InvalidOption`
+    fn start(options: &Options, config: &SpringSinkWriterConfig) -> Result<Self> {
+        let json_shape = JsonShapeOptions::try_from(options)?;
+        let options = ZeroMqSinkOptions::try_from(options)?;
+
+        let socket = open_socket(&options, config.net_write_timeout_msec as i32)
+            .with_context(|| format!("failed to open {:?} socket", options.socket_type))
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::ZeroMq(format!("{:?}", options.endpoint)),
+            })?;
+
+        log::info!(
+            "[ZeroMqSinkWriter] Ready to write into {:?}",
+            options.endpoint
+        );
+
+        Ok(Self {
+            json_shape,
+            options,
+            socket: Mutex::new(socket),
+        })
+    }
+
+    fn send_row(&mut self, row: SchemalessRow) -> Result<()> {
+        let topic = self
+            .options
+            .topic
+            .as_ref()
+            .map(|topic| row.render(topic))
+            .transpose()?;
+        let json_s = row.into_json(&self.json_shape)?.to_string();
+
+        log::debug!("[ZeroMqSinkWriter] Sending {:?}: {}", topic, json_s);
+
+        let socket = self.socket.lock().expect("ZeroMQ socket mutex poisoned");
+        match &topic {
+            Some(topic) => socket.send_multipart(vec![topic.as_bytes(), json_s.as_bytes()], 0),
+            None => socket.send(json_s.as_bytes(), 0),
+        }
+        .map_err(|e| match e {
+            zmq::Error::EAGAIN => SpringError::ForeignSourceTimeout {
+                source: anyhow!("no peer accepted the message within the write timeout"),
+                foreign_info: ForeignInfo::ZeroMq(format!("{:?}", self.options.endpoint)),
+            },
+            _ => SpringError::ForeignIo {
+                source: anyhow::Error::from(e).context("failed to send message"),
+                foreign_info: ForeignInfo::ZeroMq(format!("{:?}", self.options.endpoint)),
+            },
+        })
+    }
+}
+
+impl Debug for ZeroMqSinkWriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ZeroMqSinkWriter")
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+fn open_socket(
+    options: &ZeroMqSinkOptions,
+    write_timeout_msec: i32,
+) -> anyhow::Result<zmq::Socket> {
+    let socket_options = &options.socket_options;
+
+    let socket_type = match options.socket_type {
+        ZeroMqSinkSocketType::PUB => zmq::PUB,
+        ZeroMqSinkSocketType::PUSH => zmq::PUSH,
+    };
+    let socket = zmq::Context::new().socket(socket_type)?;
+    socket.set_sndtimeo(write_timeout_msec)?;
+    if let Some(hwm) = socket_options.hwm {
+        socket.set_sndhwm(hwm)?;
+    }
+    if let Some(reconnect_interval) = socket_options.reconnect_interval {
+        socket.set_reconnect_ivl(reconnect_interval.as_millis() as i32)?;
+    }
+    if let Some(linger) = socket_options.linger {
+        socket.set_linger(linger.as_millis() as i32)?;
+    }
+
+    match &options.endpoint {
+        ZeroMqEndpoint::Connect(endpoints) => {
+            for endpoint in endpoints {
+                socket
+                    .connect(endpoint)
+                    .with_context(|| format!("failed to connect to {}", endpoint))?;
+            }
+        }
+        ZeroMqEndpoint::Bind(endpoint) => socket
+            .bind(endpoint)
+            .with_context(|| format!("failed to bind to {}", endpoint))?,
+    }
+    Ok(socket)
+}
@@ -4,6 +4,7 @@ mod source_reader;
 
 pub use source_reader::{
     NetClientSourceReader, NetServerSourceReader, SourceReader, SourceReaderRepository,
+    SourceSampler,
 };
 
 use std::fmt::Debug;
@@ -19,7 +20,7 @@ use crate::{
                 MetricsUpdateByTaskExecution, OutQueueMetricsUpdateByTask, TaskMetricsUpdateByTask,
             },
             repositories::Repositories,
-            row::{SchemalessRow, StreamRow},
+            row::{RowTime, SchemalessRow, StreamRow},
             task::{task_context::TaskContext, ProcessedRows, TaskRunResult},
             task_graph::{QueueId, RowQueueId, TaskId, WindowQueueId},
             AutonomousExecutor,
@@ -54,11 +55,21 @@ impl SourceTask {
 
         let (processed_rows, out_queue_metrics_seq) = match self.collect_next(context) {
             Some(row) => {
+                context
+                    .pipeline_derivatives()
+                    .stream_tracers()
+                    .trace(&self.source_stream_name, std::iter::once(&row));
+                context
+                    .repos()
+                    .stream_tap_repository()
+                    .put(&self.source_stream_name, std::iter::once(&row));
+
                 let out_queue_metrics_seq = context
                     .output_queues()
                     .into_iter()
                     .map(|out_qid| self.put_row_into(out_qid, row.clone(), context)) // remove None metrics
                     .collect::<Vec<OutQueueMetricsUpdateByTask>>();
+                self.notify_enqueued(context);
                 (ProcessedRows::new(1), out_queue_metrics_seq)
             }
             None => (ProcessedRows::default(), vec![]),
@@ -96,7 +107,10 @@ impl SourceTask {
     ) -> OutQueueMetricsUpdateByTask {
         let row_q_repo = repos.row_queue_repository();
         let queue = row_q_repo.get(&queue_id);
-        let bytes_put = row.mem_size();
+        if queue.is_paused() || repos.chaos_injector().drop_row() {
+            return OutQueueMetricsUpdateByTask::new(queue_id.into(), 0, 0);
+        }
+        let bytes_put = row.stored_size();
 
         queue.put(row);
         OutQueueMetricsUpdateByTask::new(queue_id.into(), 1, bytes_put as u64)
@@ -109,12 +123,29 @@ impl SourceTask {
     ) -> OutQueueMetricsUpdateByTask {
         let window_q_repo = repos.window_queue_repository();
         let queue = window_q_repo.get(&queue_id);
-        let bytes_put = row.mem_size();
+        if queue.is_paused() {
+            return OutQueueMetricsUpdateByTask::new(queue_id.into(), 0, 0);
+        }
+        let bytes_put = row.stored_size();
 
         queue.put(row);
         OutQueueMetricsUpdateByTask::new(queue_id.into(), 1, bytes_put as u64)
     }
 
+    fn notify_enqueued(&self, context: &TaskContext) {
+        let source_reader = context
+            .repos()
+            .source_reader_repository()
+            .get_source_reader(&self.source_reader_name);
+
+        let mut source_reader = source_reader
+            .lock()
+            .expect("other worker threads sharing the same subtask must not get panic");
+        if let Err(e) = source_reader.row_enqueued() {
+            AutonomousExecutor::handle_error(e, &context.repos());
+        }
+    }
+
     fn collect_next(&self, context: &TaskContext) -> Option<StreamRow> {
         let source_reader = context
             .repos()
@@ -130,18 +161,25 @@ impl SourceTask {
         let mut source_reader = source_reader
             .lock()
             .expect("other worker threads sharing the same subtask must not get panic");
-        source_reader
-            .next_row()
+        context
+            .repos()
+            .chaos_injector()
+            .fail_source_read(&self.source_reader_name)
+            .and_then(|_| source_reader.next_row())
             .and_then(|source_row| {
                 let schemaless_row = SchemalessRow::try_from(source_row)?;
                 StreamRow::from_schemaless_row(schemaless_row, source_stream)
             })
             .map_or_else(
                 |e| {
-                    AutonomousExecutor::handle_error(e);
+                    AutonomousExecutor::handle_error(e, &context.repos());
                     None
                 },
                 Some,
             )
+            .filter(|row| match row.rowtime() {
+                RowTime::EventTime(rowtime) => source_reader.accept_rowtime(rowtime),
+                RowTime::ProcessingTime(_) => true,
+            })
     }
 }
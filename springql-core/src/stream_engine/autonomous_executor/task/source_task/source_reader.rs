@@ -1,22 +1,51 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+mod backfill;
+#[cfg(feature = "source-can")]
 mod can;
+mod file;
 mod in_memory_queue;
+#[cfg(feature = "source-kafka")]
+mod kafka;
+mod modbus;
+#[cfg(feature = "mqtt")]
+mod mqtt;
+#[cfg(feature = "nats-messaging")]
+mod nats;
 mod net_client;
+mod net_http;
 mod net_server;
+#[cfg(feature = "source-opc-ua")]
+mod opc_ua;
+#[cfg(feature = "redis-stream")]
+mod redis_stream;
+#[cfg(feature = "source-ros2")]
+mod ros2;
 mod source_reader_factory;
 mod source_reader_repository;
+mod source_sampler;
+mod springql_link;
+mod system;
+mod udp;
+#[cfg(unix)]
+mod unix_socket;
+#[cfg(feature = "zeromq")]
+mod zeromq;
 
 pub use in_memory_queue::InMemoryQueueSourceReader;
 pub use net_client::NetClientSourceReader;
 pub use net_server::NetServerSourceReader;
 pub use source_reader_repository::SourceReaderRepository;
+pub use source_sampler::SourceSampler;
+pub use system::SystemSourceReader;
 
 use std::fmt::Debug;
 
 use crate::{
-    api::error::Result, api::SpringSourceReaderConfig, pipeline::Options,
-    stream_engine::autonomous_executor::row::SourceRow,
+    api::error::Result,
+    api::SpringSourceReaderConfig,
+    pipeline::Options,
+    stream_engine::{autonomous_executor::row::SourceRow, time::SpringTimestamp},
 };
 
 /// Instance of SourceReaderModel.
@@ -38,4 +67,20 @@ pub trait SourceReader: Debug + Sync + Send + 'static {
     ///   - Failed to parse response from remote source.
     ///   - Unknown foreign error.
     fn next_row(&mut self) -> Result<SourceRow>;
+
+    /// Called with the event time (ROWTIME) of the row last returned from `next_row()`.
+    ///
+    /// # Returns
+    ///
+    /// `false` to drop the row.
+    fn accept_rowtime(&mut self, _rowtime: SpringTimestamp) -> bool {
+        true
+    }
+
+    /// Called after the row last returned from `next_row()` is put into the queues of the source stream.
+    ///
+    /// Source readers with acknowledgements (e.g. offset commits) should acknowledge rows here not to lose them on restarts.
+    fn row_enqueued(&mut self) -> Result<()> {
+        Ok(())
+    }
 }
@@ -0,0 +1,150 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{BufRead, BufReader},
+    path::PathBuf,
+};
+
+use anyhow::{anyhow, Context};
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSourceReaderConfig,
+    pipeline::Options,
+    stream_engine::{
+        autonomous_executor::{row::SourceRow, task::source_task::source_reader::SourceReader},
+        time::SpringTimestamp,
+    },
+};
+
+/// Replays rows in a historical file (`BACKFILL_FILE` option; JSON rows separated by newlines) before reading a live source reader.
+///
+/// Live rows whose ROWTIME is not later than the latest backfilled one are dropped,
+/// so that window state warmed by the backfill is continued without duplicates.
+#[derive(Debug)]
+pub struct BackfillSourceReader {
+    path: PathBuf,
+
+    /// `None` after the file is fully replayed.
+    file_reader: Option<BufReader<File>>,
+
+    /// Rows expanded from a line but not returned yet.
+    pending_rows: VecDeque<SourceRow>,
+
+    /// Latest ROWTIME of backfilled rows.
+    watermark: Option<SpringTimestamp>,
+
+    live: Box<dyn SourceReader>,
+}
+
+impl BackfillSourceReader {
+    /// Wraps `live` if `BACKFILL_FILE` option is set. Returns `live` as-is otherwise.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to open the backfill file.
+    pub fn wrap(live: Box<dyn SourceReader>, options: &Options) -> Result<Box<dyn SourceReader>> {
+        let path = options.get_optional("BACKFILL_FILE", |s| Ok(PathBuf::from(s)))?;
+
+        match path {
+            None => Ok(live),
+            Some(path) => {
+                let file = File::open(&path)
+                    .context("failed to open backfill file")
+                    .map_err(|e| SpringError::ForeignIo {
+                        source: e,
+                        foreign_info: ForeignInfo::File(path.clone()),
+                    })?;
+                log::info!("[BackfillSourceReader] Start replaying {:?}", path);
+
+                Ok(Box::new(Self {
+                    path,
+                    file_reader: Some(BufReader::new(file)),
+                    pending_rows: VecDeque::new(),
+                    watermark: None,
+                    live,
+                }))
+            }
+        }
+    }
+
+    /// # Returns
+    ///
+    /// `Ok(None)` when the file is fully replayed.
+    fn next_backfill_row(&mut self) -> Result<Option<SourceRow>> {
+        loop {
+            if let Some(row) = self.pending_rows.pop_front() {
+                return Ok(Some(row));
+            }
+
+            let file_reader = match &mut self.file_reader {
+                Some(file_reader) => file_reader,
+                None => return Ok(None),
+            };
+
+            let mut line = String::new();
+            let n_read = file_reader
+                .read_line(&mut line)
+                .context("failed to read backfill file")
+                .map_err(|e| SpringError::ForeignIo {
+                    source: e,
+                    foreign_info: ForeignInfo::File(self.path.clone()),
+                })?;
+
+            if n_read == 0 {
+                log::info!(
+                    "[BackfillSourceReader] Finished replaying {:?} (watermark: {:?}). Switching to live source",
+                    self.path,
+                    self.watermark
+                );
+                self.file_reader = None;
+            } else if !line.trim().is_empty() {
+                let rows = SourceRow::from_json_records(&line, None)?;
+                self.pending_rows.extend(rows);
+            }
+        }
+    }
+}
+
+impl SourceReader for BackfillSourceReader {
+    /// Backfill reader is created by wrapping a live source reader. See `BackfillSourceReader::wrap()`.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidOption` always.
+    fn start(_options: &Options, _config: &SpringSourceReaderConfig) -> Result<Self> {
+        Err(SpringError::InvalidOption {
+            key: "BACKFILL_FILE".to_string(),
+            value: "".to_string(),
+            source: anyhow!("backfill needs a live source reader to wrap"),
+        })
+    }
+
+    fn next_row(&mut self) -> Result<SourceRow> {
+        match self.next_backfill_row()? {
+            Some(row) => Ok(row),
+            None => self.live.next_row(),
+        }
+    }
+
+    fn accept_rowtime(&mut self, rowtime: SpringTimestamp) -> bool {
+        if self.file_reader.is_some() || !self.pending_rows.is_empty() {
+            self.watermark = Some(self.watermark.map_or(rowtime, |w| w.max(rowtime)));
+            true
+        } else {
+            self.watermark.map_or(true, |watermark| rowtime > watermark)
+        }
+    }
+
+    fn row_enqueued(&mut self) -> Result<()> {
+        // rows are returned from the live source reader after the file is fully replayed
+        if self.file_reader.is_none() {
+            self.live.row_enqueued()
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,385 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    collections::VecDeque,
+    fs::{self, File, Metadata},
+    io::{self, BufRead, BufReader, Read, Seek, SeekFrom},
+    mem, thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Context};
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSourceReaderConfig,
+    pipeline::{FileSourceOptions, FileStartPosition, Options, SourceFormatOptions},
+    stream_engine::autonomous_executor::{
+        row::{CsvParser, SourceRow},
+        task::source_task::source_reader::SourceReader,
+    },
+};
+
+/// Identity of a file to detect rotation: (device, inode). Not available on non-Unix platforms.
+type FileId = Option<(u64, u64)>;
+
+/// Tails a file like `tail -F`, whose lines are rows in `FORMAT` (JSON by default).
+///
+/// - Waits for the file to be created if it does not exist.
+/// - A line without the trailing newline is held until it is completed.
+/// - When the file is rotated (another file is created at `PATH`; detected on Unix), the rest of the old file is read
+///   and then the new file is read from the beginning.
+/// - When the file is truncated, it is read from the beginning again.
+#[derive(Debug)]
+pub struct FileSourceReader {
+    options: FileSourceOptions,
+    format_options: SourceFormatOptions,
+    read_timeout: Duration,
+
+    /// None while the file does not exist.
+    file: Option<TailedFile>,
+    /// Set for `FORMAT 'CSV'`. Keeps the header of the current file.
+    csv_parser: Option<CsvParser>,
+    /// Bytes of the line being written.
+    partial_line: Vec<u8>,
+
+    /// Rows expanded from a line but not returned yet.
+    pending_rows: VecDeque<SourceRow>,
+}
+
+#[derive(Debug)]
+struct TailedFile {
+    reader: BufReader<File>,
+    id: FileId,
+    /// Bytes read so far.
+    position: u64,
+}
+
+impl SourceReader for FileSourceReader {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to open the file (other than it does not exist).
+    /// - `SpringError::InvalidOption`
+    fn start(options: &Options, config: &SpringSourceReaderConfig) -> Result<Self> {
+        let format_options = SourceFormatOptions::try_from(options)?;
+        format_options.ensure_text("FILE")?;
+        let options = FileSourceOptions::try_from(options)?;
+
+        let mut reader = Self {
+            read_timeout: Duration::from_millis(config.net_read_timeout_msec as u64),
+            file: None,
+            csv_parser: None,
+            partial_line: Vec::new(),
+            pending_rows: VecDeque::new(),
+            options,
+            format_options,
+        };
+        if !reader.open(reader.options.start_position)? {
+            log::warn!(
+                "[FileSourceReader] {} does not exist yet",
+                reader.options.path.display()
+            );
+        }
+
+        log::info!(
+            "[FileSourceReader] Ready to tail {}",
+            reader.options.path.display()
+        );
+        Ok(reader)
+    }
+
+    fn next_row(&mut self) -> Result<SourceRow> {
+        let deadline = Instant::now() + self.read_timeout;
+        loop {
+            if let Some(row) = self.pending_rows.pop_front() {
+                return Ok(row);
+            }
+
+            if let Some(line) = self.read_line()? {
+                self.parse_line(&line)?;
+            } else if !self.follow()? {
+                let now = Instant::now();
+                if now >= deadline {
+                    return Err(SpringError::ForeignSourceTimeout {
+                        source: anyhow!("no line appended in {:?}", self.read_timeout),
+                        foreign_info: self.foreign_info(),
+                    });
+                }
+                thread::sleep(self.options.poll_interval.min(deadline - now));
+            }
+        }
+    }
+}
+
+impl FileSourceReader {
+    /// Opens the file at `PATH` from `start_position`, discarding the current file.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the file does not exist.
+    fn open(&mut self, start_position: FileStartPosition) -> Result<bool> {
+        let file = match File::open(&self.options.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+            Err(e) => return Err(self.io_error(e, "failed to open file")),
+        };
+        let metadata = file
+            .metadata()
+            .map_err(|e| self.io_error(e, "failed to get metadata of file"))?;
+
+        let mut reader = BufReader::new(file);
+        self.csv_parser = self.format_options.csv().map(CsvParser::new);
+        self.partial_line.clear();
+
+        let position = match start_position {
+            FileStartPosition::Beginning => 0,
+            FileStartPosition::End => {
+                if let Some(csv_parser) = &mut self.csv_parser {
+                    // header line is before the end
+                    let mut header = String::new();
+                    let _ = reader.read_line(&mut header);
+                    let _ = csv_parser.parse(&header);
+                }
+                reader
+                    .seek(SeekFrom::End(0))
+                    .map_err(|e| self.io_error(e, "failed to seek to the end of file"))?
+            }
+        };
+
+        self.file = Some(TailedFile {
+            reader,
+            id: file_id(&metadata),
+            position,
+        });
+        Ok(true)
+    }
+
+    /// # Returns
+    ///
+    /// A completed line, or `None` at the end of the file.
+    fn read_line(&mut self) -> Result<Option<Vec<u8>>> {
+        let file = match &mut self.file {
+            Some(file) => file,
+            None => return Ok(None),
+        };
+        match file.reader.read_until(b'\n', &mut self.partial_line) {
+            Ok(n) => file.position += n as u64,
+            Err(e) => return Err(self.io_error(e, "failed to read file")),
+        }
+
+        if self.partial_line.ends_with(b"\n") {
+            Ok(Some(mem::take(&mut self.partial_line)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Switches to the file newly created, rotated or truncated at `PATH`.
+    ///
+    /// # Returns
+    ///
+    /// `true` if switched.
+    fn follow(&mut self) -> Result<bool> {
+        let metadata = match fs::metadata(&self.options.path) {
+            Ok(metadata) => metadata,
+            // rotated but not created yet
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+            Err(e) => return Err(self.io_error(e, "failed to get metadata of file")),
+        };
+
+        match &mut self.file {
+            Some(file) if file.id == file_id(&metadata) => {
+                if metadata.len() >= file.position {
+                    return Ok(false);
+                }
+                log::info!(
+                    "[FileSourceReader] {} is truncated. Reading from the beginning",
+                    self.options.path.display()
+                );
+            }
+            Some(file) => {
+                log::info!(
+                    "[FileSourceReader] {} is rotated. Reading the new file from the beginning",
+                    self.options.path.display()
+                );
+                // lines written to the old file just before the rotation
+                let mut rest = mem::take(&mut self.partial_line);
+                let read = file.reader.read_to_end(&mut rest);
+                read.map_err(|e| self.io_error(e, "failed to read rotated file"))?;
+                for line in rest.split(|b| *b == b'\n') {
+                    if let Err(e) = self.parse_line(line) {
+                        log::warn!("[FileSourceReader] Skipped a malformed line: {:?}", e);
+                    }
+                }
+            }
+            None => {}
+        }
+
+        self.open(FileStartPosition::Beginning)
+    }
+
+    /// Puts rows in `line` into `pending_rows`. Blank lines are ignored.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidFormat` when:
+    ///   - `line` is not UTF-8.
+    ///   - `line` is malformed in `FORMAT`.
+    fn parse_line(&mut self, line: &[u8]) -> Result<()> {
+        let line = std::str::from_utf8(line)
+            .context("line is not UTF-8")
+            .map_err(|e| SpringError::InvalidFormat {
+                source: e,
+                s: String::from_utf8_lossy(line).to_string(),
+            })?;
+        if line.trim().is_empty() {
+            return Ok(());
+        }
+
+        let rows = match &mut self.csv_parser {
+            Some(csv_parser) => SourceRow::from_csv_records(csv_parser, line)?,
+            None => SourceRow::from_json_records(line, self.options.json_records_path.as_ref())?,
+        };
+        self.pending_rows.extend(rows);
+        Ok(())
+    }
+
+    fn io_error(&self, e: io::Error, context: &'static str) -> SpringError {
+        SpringError::ForeignIo {
+            source: anyhow::Error::from(e).context(context),
+            foreign_info: self.foreign_info(),
+        }
+    }
+
+    fn foreign_info(&self) -> ForeignInfo {
+        ForeignInfo::File(self.options.path.clone())
+    }
+}
+
+#[cfg(unix)]
+fn file_id(metadata: &Metadata) -> FileId {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_id(_metadata: &Metadata) -> FileId {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use crate::pipeline::OptionsBuilder;
+    use crate::stream_engine::autonomous_executor::row::{JsonObject, JsonSourceRow};
+
+    fn json_row(json: serde_json::Value) -> SourceRow {
+        SourceRow::Json(JsonSourceRow::from_json(JsonObject::new(json)))
+    }
+
+    fn append(path: &std::path::Path, s: &str) {
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        file.write_all(s.as_bytes()).unwrap();
+    }
+
+    fn start(path: &std::path::Path, start_position: &str, format: &str) -> FileSourceReader {
+        let options = OptionsBuilder::default()
+            .add("PATH", path.display().to_string())
+            .add("POLL_INTERVAL_MSEC", "10")
+            .add("START_POSITION", start_position)
+            .add("FORMAT", format)
+            .build();
+        FileSourceReader::start(&options, &SpringSourceReaderConfig::fx_default()).unwrap()
+    }
+
+    fn assert_timeout(reader: &mut FileSourceReader) {
+        assert!(matches!(
+            reader.next_row().unwrap_err(),
+            SpringError::ForeignSourceTimeout { .. }
+        ));
+    }
+
+    #[test]
+    fn test_tail_from_beginning_and_end() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sensor.log");
+        append(&path, "{\"v\": 1}\n");
+
+        let mut from_beginning = start(&path, "BEGINNING", "JSON");
+        let mut from_end = start(&path, "END", "JSON");
+        assert_eq!(
+            from_beginning.next_row().unwrap(),
+            json_row(serde_json::json!({"v": 1}))
+        );
+        assert_timeout(&mut from_beginning);
+        assert_timeout(&mut from_end);
+
+        // partial line is held until completed
+        append(&path, "\n{\"v\": ");
+        assert_timeout(&mut from_beginning);
+        append(&path, "2}\n");
+        for reader in [&mut from_beginning, &mut from_end] {
+            assert_eq!(
+                reader.next_row().unwrap(),
+                json_row(serde_json::json!({"v": 2}))
+            );
+            assert_timeout(reader);
+        }
+    }
+
+    #[test]
+    fn test_wait_for_creation_and_follow_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sensor.log");
+
+        let mut reader = start(&path, "END", "JSON");
+        assert_timeout(&mut reader);
+
+        append(&path, "{\"v\": 1}\n");
+        assert_eq!(
+            reader.next_row().unwrap(),
+            json_row(serde_json::json!({"v": 1}))
+        );
+
+        // written just before the rotation
+        append(&path, "{\"v\": 2}");
+        fs::rename(&path, dir.path().join("sensor.log.1")).unwrap();
+        append(&path, "{\"v\": 3}\n");
+        for v in [2, 3] {
+            assert_eq!(
+                reader.next_row().unwrap(),
+                json_row(serde_json::json!({ "v": v }))
+            );
+        }
+
+        // truncation
+        fs::write(&path, "").unwrap();
+        assert_timeout(&mut reader);
+        append(&path, "{\"v\": 4}\n");
+        assert_eq!(
+            reader.next_row().unwrap(),
+            json_row(serde_json::json!({"v": 4}))
+        );
+        assert_timeout(&mut reader);
+    }
+
+    #[test]
+    fn test_csv_header_from_end() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sensor.csv");
+        append(&path, "ticker,amount\nORCL,10\n");
+
+        let mut reader = start(&path, "END", "CSV");
+        assert_timeout(&mut reader);
+        append(&path, "IBM,20\n");
+        assert!(matches!(reader.next_row().unwrap(), SourceRow::Raw(_)));
+        assert_timeout(&mut reader);
+    }
+}
@@ -5,15 +5,22 @@ use anyhow::anyhow;
 use crate::{
     api::error::{foreign_info::ForeignInfo, Result, SpringError},
     api::SpringSourceReaderConfig,
-    pipeline::{InMemoryQueueOptions, Options, QueueName},
+    pipeline::{InMemoryQueueOptions, Options, QueueName, SourceMetadataOptions},
     stream_engine::{
-        autonomous_executor::{row::SourceRow, task::source_task::source_reader::SourceReader},
+        autonomous_executor::{
+            row::{SourceMetadata, SourceRow},
+            task::source_task::source_reader::SourceReader,
+        },
         in_memory_queue_repository::InMemoryQueueRepository,
     },
 };
 
+/// Queue name is provided as `topic` metadata.
 #[derive(Debug)]
-pub struct InMemoryQueueSourceReader(QueueName);
+pub struct InMemoryQueueSourceReader {
+    queue_name: QueueName,
+    metadata_options: SourceMetadataOptions,
+}
 
 impl SourceReader for InMemoryQueueSourceReader {
     /// # Failure
@@ -22,10 +29,14 @@ impl SourceReader for InMemoryQueueSourceReader {
     ///   - queue name provided from the option is invalid
     /// - `SpringError::InvalidOption`
     fn start(options: &Options, _config: &SpringSourceReaderConfig) -> Result<Self> {
+        let metadata_options = SourceMetadataOptions::try_from(options)?;
         let options = InMemoryQueueOptions::try_from(options)?;
         let queue_name = options.queue_name;
         InMemoryQueueRepository::instance().create(queue_name.clone())?;
-        Ok(Self(queue_name))
+        Ok(Self {
+            queue_name,
+            metadata_options,
+        })
     }
 
     /// # Failure
@@ -33,14 +44,18 @@ impl SourceReader for InMemoryQueueSourceReader {
     /// - `SpringError::ForeignSourceTimeout` when:
     ///   - queue does not have any row (does not wait a bit)
     fn next_row(&mut self) -> Result<SourceRow> {
-        let q = InMemoryQueueRepository::instance().get(&self.0)?;
+        let q = InMemoryQueueRepository::instance().get(&self.queue_name)?;
 
         if let Some(row) = q.pop_non_blocking() {
-            Ok(SourceRow::Raw(row))
+            let metadata = SourceMetadata {
+                topic: Some(self.queue_name.to_string()),
+                ..Default::default()
+            };
+            SourceRow::Raw(row).with_metadata(&metadata, &self.metadata_options)
         } else {
             Err(SpringError::ForeignSourceTimeout {
                 source: anyhow!("queue is empty"),
-                foreign_info: ForeignInfo::InMemoryQueue(self.0.clone()),
+                foreign_info: ForeignInfo::InMemoryQueue(self.queue_name.clone()),
             })
         }
     }
@@ -0,0 +1,230 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{collections::VecDeque, fmt::Debug, sync::Mutex, time::Duration};
+
+use anyhow::{anyhow, Context};
+use kafka::{
+    client::GroupOffsetStorage,
+    consumer::{Consumer, Message},
+};
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSourceReaderConfig,
+    pipeline::{KafkaSourceOptions, Options, SourceFormatOptions, SourceMetadataOptions},
+    stream_engine::autonomous_executor::{
+        row::{PayloadDecoder, SourceMetadata, SourceRow},
+        task::source_task::source_reader::SourceReader,
+    },
+};
+
+/// Position of a message: (partition, offset).
+type MessagePosition = (i32, i64);
+
+/// Consumes a Kafka topic whose messages are rows in `FORMAT` (JSON by default), as a member of a consumer group.
+///
+/// Topic, partition and key of each message are provided as `topic`, `partition` and `key` metadata.
+///
+/// A message is marked as consumed after its last row is put into the source stream,
+/// and consumed offsets are committed before fetching the next messages.
+/// Messages fetched but not enqueued are redelivered after restarts (at-least-once).
+/// Malformed messages, and Avro messages whose schema cannot be fetched from the registry, are skipped.
+pub struct KafkaSourceReader {
+    options: KafkaSourceOptions,
+    metadata_options: SourceMetadataOptions,
+    decoder: PayloadDecoder,
+
+    consumer: Mutex<Consumer>,
+    read_timeout: Duration,
+
+    /// Rows parsed but not returned yet. The last row of each message has the message position.
+    pending_rows: VecDeque<(SourceRow, Option<MessagePosition>)>,
+    /// Message whose last row is returned from `next_row()` and not enqueued yet.
+    returned_message: Option<MessagePosition>,
+    /// Whether some messages are consumed but their offsets are not committed.
+    uncommitted: bool,
+}
+
+impl SourceReader for KafkaSourceReader {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to connect to brokers or to fetch the consumer group's offsets.
+    ///   - failed to read `PROTO_DESCRIPTOR_FILE`.
+    /// - `SpringError::InvalidOption`
+    fn start(options: &Options, config: &SpringSourceReaderConfig) -> Result<Self> {
+        let metadata_options = SourceMetadataOptions::try_from(options)?;
+        let format_options = SourceFormatOptions::try_from(options)?;
+        let options = KafkaSourceOptions::try_from(options)?;
+        let decoder = PayloadDecoder::new(&format_options, options.json_records_path.as_ref())?;
+        let read_timeout = Duration::from_millis(config.net_read_timeout_msec as u64);
+
+        let consumer = Consumer::from_hosts(options.brokers.clone())
+            .with_topic(options.topic.clone())
+            .with_group(options.group_id.clone())
+            .with_fallback_offset(options.start_offset.into())
+            .with_offset_storage(Some(GroupOffsetStorage::Kafka))
+            .with_fetch_max_wait_time(read_timeout)
+            .with_client_id("springql".to_string())
+            .create()
+            .context("failed to create consumer")
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::Kafka(options.brokers.join(",")),
+            })?;
+
+        log::info!(
+            "[KafkaSourceReader] Ready to read topic {} from {} (group: {})",
+            options.topic,
+            options.brokers.join(","),
+            options.group_id
+        );
+
+        Ok(Self {
+            options,
+            metadata_options,
+            decoder,
+            consumer: Mutex::new(consumer),
+            read_timeout,
+            pending_rows: VecDeque::new(),
+            returned_message: None,
+            uncommitted: false,
+        })
+    }
+
+    fn next_row(&mut self) -> Result<SourceRow> {
+        loop {
+            if let Some((row, message)) = self.pending_rows.pop_front() {
+                self.returned_message = message;
+                return Ok(row);
+            }
+
+            // all rows fetched so far are enqueued (or dropped)
+            self.commit();
+            self.fetch()?;
+        }
+    }
+
+    /// Marks the message as consumed if the enqueued row is the last one of the message.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - the message is not of assigned partitions.
+    fn row_enqueued(&mut self) -> Result<()> {
+        if let Some((partition, offset)) = self.returned_message.take() {
+            self.consumer
+                .get_mut()
+                .expect("failed to lock mutex")
+                .consume_message(&self.options.topic, partition, offset)
+                .with_context(|| {
+                    format!(
+                        "failed to consume message (partition: {}, offset: {})",
+                        partition, offset
+                    )
+                })
+                .map_err(|e| SpringError::ForeignIo {
+                    source: e,
+                    foreign_info: ForeignInfo::Kafka(self.options.brokers.join(",")),
+                })?;
+            self.uncommitted = true;
+        }
+        Ok(())
+    }
+}
+
+impl KafkaSourceReader {
+    /// Commits consumed offsets. Failed commits are retried on the next call.
+    fn commit(&mut self) {
+        if self.uncommitted {
+            match self
+                .consumer
+                .get_mut()
+                .expect("failed to lock mutex")
+                .commit_consumed()
+            {
+                Ok(()) => self.uncommitted = false,
+                Err(e) => log::warn!("[KafkaSourceReader] Failed to commit offsets: {:?}", e),
+            }
+        }
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignSourceTimeout` when:
+    ///   - no message is fetched in `net_read_timeout_msec`.
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to fetch messages.
+    fn fetch(&mut self) -> Result<()> {
+        let message_sets = self
+            .consumer
+            .get_mut()
+            .expect("failed to lock mutex")
+            .poll()
+            .context("failed to fetch messages")
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::Kafka(self.options.brokers.join(",")),
+            })?;
+        if message_sets.is_empty() {
+            return Err(SpringError::ForeignSourceTimeout {
+                source: anyhow!("no message in {:?}", self.read_timeout),
+                foreign_info: ForeignInfo::Kafka(self.options.brokers.join(",")),
+            });
+        }
+
+        for message_set in message_sets.iter() {
+            let partition = message_set.partition();
+            for message in message_set.messages() {
+                let position = (partition, message.offset);
+                match self.message_into_rows(partition, message) {
+                    Ok(rows) => {
+                        let n_rows = rows.len();
+                        let rows = rows
+                            .into_iter()
+                            .enumerate()
+                            .map(|(i, row)| (row, (i + 1 == n_rows).then(|| position)));
+                        self.pending_rows.extend(rows);
+                    }
+                    Err(e) => log::warn!(
+                        "[KafkaSourceReader] Skipped malformed message at {:?}: {:?}",
+                        position,
+                        e
+                    ),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidFormat` when:
+    ///   - the message is malformed in `FORMAT`.
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to get the Avro schema of the message.
+    fn message_into_rows(&mut self, partition: i32, message: &Message) -> Result<Vec<SourceRow>> {
+        let metadata = SourceMetadata {
+            topic: Some(self.options.topic.clone()),
+            partition: Some(partition),
+            key: (!message.key.is_empty())
+                .then(|| String::from_utf8_lossy(message.key).to_string()),
+            ..Default::default()
+        };
+        self.decoder
+            .decode(message.value)?
+            .into_iter()
+            .map(|row| row.with_metadata(&metadata, &self.metadata_options))
+            .collect()
+    }
+}
+
+impl Debug for KafkaSourceReader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KafkaSourceReader")
+            .field("options", &self.options)
+            .field("pending_rows", &self.pending_rows.len())
+            .field("returned_message", &self.returned_message)
+            .finish()
+    }
+}
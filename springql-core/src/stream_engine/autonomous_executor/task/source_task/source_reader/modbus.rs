@@ -0,0 +1,315 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    io::{Read, Write},
+    net::{SocketAddr, TcpStream},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, bail, Context};
+use ordered_float::OrderedFloat;
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSourceReaderConfig,
+    pipeline::{
+        ModbusOptions, ModbusRegister, ModbusTable, ModbusValueType, ModbusWordOrder, Options,
+    },
+    stream_engine::{
+        autonomous_executor::{
+            row::{SchemalessRow, SourceRow},
+            task::source_task::source_reader::SourceReader,
+        },
+        time::SystemTimestamp,
+        NnSqlValue, SqlValue,
+    },
+};
+
+/// Polls registers of a Modbus TCP server every `POLL_INTERVAL_MSEC` and emits a row per poll.
+///
+/// # Data format
+///
+/// - `REGISTER_<column>` columns: BOOLEAN for coils and discrete inputs, SMALLINT / INTEGER / BIGINT / FLOAT for registers.
+/// - `TIMESTAMP_COLUMN` (optional): wall-clock time of the poll.
+///
+/// Each register is read by a request. The connection is re-established at the next poll after an IO error.
+#[derive(Debug)]
+pub struct ModbusSourceReader {
+    foreign_addr: SocketAddr,
+    options: ModbusOptions,
+
+    connect_timeout: Duration,
+    read_timeout: Duration,
+    tcp_stream: Option<TcpStream>,
+
+    transaction_id: u16,
+    next_poll_at: Instant,
+}
+
+impl SourceReader for ModbusSourceReader {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo`
+    /// - `SpringError::InvalidOption`
+    fn start(options: &Options, config: &SpringSourceReaderConfig) -> Result<Self> {
+        let options = ModbusOptions::try_from(options)?;
+        let foreign_addr = SocketAddr::new(options.remote_host, options.remote_port);
+
+        let mut reader = Self {
+            foreign_addr,
+            options,
+            connect_timeout: Duration::from_millis(config.net_connect_timeout_msec as u64),
+            read_timeout: Duration::from_millis(config.net_read_timeout_msec as u64),
+            tcp_stream: None,
+            transaction_id: 0,
+            next_poll_at: Instant::now(),
+        };
+        reader.connect()?;
+
+        log::info!(
+            "[ModbusSourceReader] Ready to poll {} registers from {}",
+            reader.options.registers.len(),
+            foreign_addr
+        );
+
+        Ok(reader)
+    }
+
+    fn next_row(&mut self) -> Result<SourceRow> {
+        let now = Instant::now();
+        if now < self.next_poll_at {
+            let wait = self.next_poll_at - now;
+            if wait > self.read_timeout {
+                thread::sleep(self.read_timeout);
+                return Err(SpringError::ForeignSourceTimeout {
+                    source: anyhow!("next poll in {:?}", wait - self.read_timeout),
+                    foreign_info: ForeignInfo::GenericTcp(self.foreign_addr),
+                });
+            }
+            thread::sleep(wait);
+        }
+        // does not catch up missed polls
+        self.next_poll_at = (self.next_poll_at + self.options.poll_interval)
+            .max(Instant::now() + self.options.poll_interval / 2);
+
+        let row = self.poll().map_err(|e| {
+            // reconnects at the next poll
+            self.tcp_stream = None;
+            SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::GenericTcp(self.foreign_addr),
+            }
+        })?;
+        Ok(SourceRow::Raw(row))
+    }
+}
+
+impl ModbusSourceReader {
+    fn connect(&mut self) -> Result<()> {
+        let tcp_stream = TcpStream::connect_timeout(&self.foreign_addr, self.connect_timeout)
+            .and_then(|tcp_stream| {
+                tcp_stream.set_read_timeout(Some(self.read_timeout))?;
+                Ok(tcp_stream)
+            })
+            .context("failed to connect to Modbus TCP server")
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::GenericTcp(self.foreign_addr),
+            })?;
+        self.tcp_stream = Some(tcp_stream);
+        Ok(())
+    }
+
+    fn poll(&mut self) -> anyhow::Result<SchemalessRow> {
+        if self.tcp_stream.is_none() {
+            self.connect()?;
+        }
+
+        let mut row = SchemalessRow::default();
+        for register in self.options.registers.clone() {
+            let data = self.read(&register)?;
+            let value = decode(&register, &data, self.options.word_order)?;
+            row.insert(register.column, SqlValue::NotNull(value))?;
+        }
+        if let Some(timestamp_column) = &self.options.timestamp_column {
+            row.insert(
+                timestamp_column.clone(),
+                SqlValue::NotNull(NnSqlValue::Timestamp(SystemTimestamp::now())),
+            )?;
+        }
+        Ok(row)
+    }
+
+    /// # Returns
+    ///
+    /// Data bytes in the response (without the byte count).
+    fn read(&mut self, register: &ModbusRegister) -> anyhow::Result<Vec<u8>> {
+        let function_code: u8 = match register.table {
+            ModbusTable::Coil => 0x01,
+            ModbusTable::DiscreteInput => 0x02,
+            ModbusTable::HoldingRegister => 0x03,
+            ModbusTable::InputRegister => 0x04,
+        };
+        self.transaction_id = self.transaction_id.wrapping_add(1);
+
+        // MBAP header (transaction ID, protocol ID, length, unit ID) + PDU
+        let mut request = Vec::with_capacity(12);
+        request.extend_from_slice(&self.transaction_id.to_be_bytes());
+        request.extend_from_slice(&0u16.to_be_bytes());
+        request.extend_from_slice(&6u16.to_be_bytes());
+        request.push(self.options.unit_id);
+        request.push(function_code);
+        request.extend_from_slice(&register.address.to_be_bytes());
+        request.extend_from_slice(&register.value_type.quantity().to_be_bytes());
+
+        let tcp_stream = self.tcp_stream.as_mut().expect("connected");
+        tcp_stream.write_all(&request)?;
+
+        let mut header = [0u8; 7];
+        tcp_stream.read_exact(&mut header)?;
+        let length = u16::from_be_bytes([header[4], header[5]]) as usize;
+        if length < 2 {
+            bail!("invalid Modbus response length: {}", length);
+        }
+        let mut pdu = vec![0u8; length - 1];
+        tcp_stream.read_exact(&mut pdu)?;
+
+        let transaction_id = u16::from_be_bytes([header[0], header[1]]);
+        if transaction_id != self.transaction_id {
+            bail!(
+                "unexpected Modbus transaction ID: {} (expected {})",
+                transaction_id,
+                self.transaction_id
+            );
+        }
+        if pdu[0] == function_code | 0x80 {
+            bail!(
+                "Modbus exception {} for {:?}",
+                pdu.get(1).copied().unwrap_or_default(),
+                register
+            );
+        }
+        if pdu[0] != function_code || pdu.len() < 2 || pdu.len() != 2 + pdu[1] as usize {
+            bail!("invalid Modbus response: {:?}", pdu);
+        }
+        Ok(pdu.split_off(2))
+    }
+}
+
+fn decode(
+    register: &ModbusRegister,
+    data: &[u8],
+    word_order: ModbusWordOrder,
+) -> anyhow::Result<NnSqlValue> {
+    let word = |i: usize| -> anyhow::Result<u16> {
+        let bytes = data
+            .get(i * 2..i * 2 + 2)
+            .with_context(|| format!("too short response for {:?}", register))?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    };
+    let double_word = || -> anyhow::Result<u32> {
+        let (high, low) = match word_order {
+            ModbusWordOrder::BigEndian => (word(0)?, word(1)?),
+            ModbusWordOrder::LittleEndian => (word(1)?, word(0)?),
+        };
+        Ok((high as u32) << 16 | low as u32)
+    };
+
+    let value = match register.value_type {
+        ModbusValueType::Bool => {
+            let byte = data
+                .first()
+                .with_context(|| format!("too short response for {:?}", register))?;
+            NnSqlValue::Boolean(byte & 1 == 1)
+        }
+        ModbusValueType::U16 => NnSqlValue::Integer(word(0)? as i32),
+        ModbusValueType::I16 => NnSqlValue::SmallInt(word(0)? as i16),
+        ModbusValueType::U32 => NnSqlValue::BigInt(double_word()? as i64),
+        ModbusValueType::I32 => NnSqlValue::Integer(double_word()? as i32),
+        ModbusValueType::F32 => NnSqlValue::Float(OrderedFloat(f32::from_bits(double_word()?))),
+    };
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+
+    use crate::pipeline::{ColumnName, OptionsBuilder};
+
+    use super::*;
+
+    /// Serves holding registers [0x4148, 0x0000] (12.5 as f32) and coils [true].
+    fn serve() -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 12];
+            while stream.read_exact(&mut request).is_ok() {
+                let data: &[u8] = match request[7] {
+                    0x01 => &[0x01],
+                    0x03 => &[0x41, 0x48, 0x00, 0x00],
+                    _ => unreachable!(),
+                };
+                let mut response = request[0..4].to_vec();
+                response.extend_from_slice(&(3 + data.len() as u16).to_be_bytes());
+                response.extend_from_slice(&[request[6], request[7], data.len() as u8]);
+                response.extend_from_slice(data);
+                stream.write_all(&response).unwrap();
+            }
+        });
+
+        addr
+    }
+
+    #[test]
+    fn test_modbus_source_reader() {
+        let addr = serve();
+        let options = OptionsBuilder::default()
+            .add("REMOTE_HOST", addr.ip().to_string())
+            .add("REMOTE_PORT", addr.port().to_string())
+            .add("POLL_INTERVAL_MSEC", "10")
+            .add("REGISTER_temperature", "holding:0:f32")
+            .add("REGISTER_running", "coil:0")
+            .build();
+        let mut reader =
+            ModbusSourceReader::start(&options, &SpringSourceReaderConfig::fx_default()).unwrap();
+
+        for _ in 0..2 {
+            let row = SchemalessRow::try_from(reader.next_row().unwrap()).unwrap();
+            assert_eq!(
+                row.get_by_column_name(&ColumnName::new("temperature".to_string()))
+                    .unwrap(),
+                &SqlValue::NotNull(NnSqlValue::Float(OrderedFloat(12.5)))
+            );
+            assert_eq!(
+                row.get_by_column_name(&ColumnName::new("running".to_string()))
+                    .unwrap(),
+                &SqlValue::NotNull(NnSqlValue::Boolean(true))
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_word_order() {
+        let register = ModbusRegister {
+            column: ColumnName::new("c".to_string()),
+            table: ModbusTable::HoldingRegister,
+            address: 0,
+            value_type: ModbusValueType::U32,
+        };
+        let data = [0x00, 0x01, 0x00, 0x02];
+
+        assert_eq!(
+            decode(&register, &data, ModbusWordOrder::BigEndian).unwrap(),
+            NnSqlValue::BigInt(0x0001_0002)
+        );
+        assert_eq!(
+            decode(&register, &data, ModbusWordOrder::LittleEndian).unwrap(),
+            NnSqlValue::BigInt(0x0002_0001)
+        );
+    }
+}
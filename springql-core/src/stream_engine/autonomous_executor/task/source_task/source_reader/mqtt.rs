@@ -0,0 +1,205 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    collections::VecDeque,
+    fmt::Debug,
+    sync::{mpsc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use anyhow::{anyhow, Context};
+use rumqttc::{Client, Event, MqttOptions, Outgoing, Packet, Publish, QoS};
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSourceReaderConfig,
+    pipeline::{MqttSourceOptions, Options, SourceFormatOptions, SourceMetadataOptions},
+    stream_engine::autonomous_executor::{
+        row::{PayloadDecoder, SourceMetadata, SourceRow},
+        task::source_task::source_reader::SourceReader,
+    },
+};
+
+/// Publications are not received while this number of them are not read.
+const REQUEST_QUEUE_CAPACITY: usize = 100;
+const KEEP_ALIVE: Duration = Duration::from_secs(30);
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Subscribes to an MQTT topic filter whose messages are rows in `FORMAT` (JSON by default).
+///
+/// Topic and QoS of each message are provided as `topic` and `qos` metadata.
+/// A background thread drives the connection, which reconnects on errors and renews the subscription.
+pub struct MqttSourceReader {
+    options: MqttSourceOptions,
+    metadata_options: SourceMetadataOptions,
+    decoder: PayloadDecoder,
+
+    /// Keeps the connection alive.
+    client: Client,
+    rx: Mutex<mpsc::Receiver<Publish>>,
+    read_timeout: Duration,
+
+    /// Rows parsed but not returned yet.
+    pending_rows: VecDeque<SourceRow>,
+}
+
+impl SourceReader for MqttSourceReader {
+    /// Blocks until the broker acknowledges the subscription.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to subscribe to the topic in `net_connect_timeout_msec`.
+    ///   - failed to read `PROTO_DESCRIPTOR_FILE`.
+    /// - `SpringError::InvalidOption`
+    fn start(options: &Options, config: &SpringSourceReaderConfig) -> Result<Self> {
+        let metadata_options = SourceMetadataOptions::try_from(options)?;
+        let format_options = SourceFormatOptions::try_from(options)?;
+        let options = MqttSourceOptions::try_from(options)?;
+        let decoder = PayloadDecoder::new(&format_options, options.json_records_path.as_ref())?;
+
+        let mut mqtt_options = MqttOptions::new(
+            &options.client_id,
+            &options.remote_host,
+            options.remote_port,
+        );
+        mqtt_options.set_keep_alive(KEEP_ALIVE);
+        if let Some((user_name, password)) = &options.user {
+            mqtt_options.set_credentials(user_name, password);
+        }
+
+        let (client, mut connection) = Client::new(mqtt_options, REQUEST_QUEUE_CAPACITY);
+        let (tx, rx) = mpsc::channel();
+        let (ready_tx, ready_rx) = mpsc::channel();
+
+        let thread_client = client.clone();
+        let topic = options.topic.clone();
+        let qos = QoS::from(options.qos);
+        let broker = broker(&options);
+        let _ = thread::Builder::new()
+            .name("MqttSourceReader".into())
+            .spawn(move || {
+                // ends when the client is dropped or disconnects
+                for notification in connection.iter() {
+                    match notification {
+                        Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                            log::info!("[MqttSourceReader] Connected to {}", broker);
+                            // a clean session does not keep the subscription
+                            if let Err(e) = thread_client.try_subscribe(&topic, qos) {
+                                log::warn!("[MqttSourceReader] Failed to subscribe: {:?}", e);
+                            }
+                        }
+                        Ok(Event::Incoming(Packet::SubAck(_))) => {
+                            let _ = ready_tx.send(());
+                        }
+                        Ok(Event::Incoming(Packet::Publish(publish))) => {
+                            if tx.send(publish).is_err() {
+                                break;
+                            }
+                        }
+                        Ok(Event::Outgoing(Outgoing::Disconnect)) => break,
+                        Ok(_) => {}
+                        Err(e) => {
+                            log::warn!(
+                                "[MqttSourceReader] Connection to {} failed (retrying in {:?}): {:?}",
+                                broker,
+                                RECONNECT_INTERVAL,
+                                e
+                            );
+                            thread::sleep(RECONNECT_INTERVAL);
+                        }
+                    }
+                }
+            });
+
+        let connect_timeout = Duration::from_millis(config.net_connect_timeout_msec as u64);
+        ready_rx
+            .recv_timeout(connect_timeout)
+            .with_context(|| format!("failed to subscribe in {:?}", connect_timeout))
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::Mqtt(broker(&options)),
+            })?;
+
+        log::info!(
+            "[MqttSourceReader] Ready to read topic {} from {}",
+            options.topic,
+            broker(&options)
+        );
+
+        Ok(Self {
+            options,
+            metadata_options,
+            decoder,
+            client,
+            rx: Mutex::new(rx),
+            read_timeout: Duration::from_millis(config.net_read_timeout_msec as u64),
+            pending_rows: VecDeque::new(),
+        })
+    }
+
+    fn next_row(&mut self) -> Result<SourceRow> {
+        loop {
+            if let Some(row) = self.pending_rows.pop_front() {
+                return Ok(row);
+            }
+
+            let publish = self
+                .rx
+                .lock()
+                .expect("failed to lock mutex")
+                .recv_timeout(self.read_timeout)
+                .map_err(|e| match e {
+                    mpsc::RecvTimeoutError::Timeout => SpringError::ForeignSourceTimeout {
+                        source: anyhow!("no message in {:?}", self.read_timeout),
+                        foreign_info: ForeignInfo::Mqtt(broker(&self.options)),
+                    },
+                    mpsc::RecvTimeoutError::Disconnected => SpringError::ForeignIo {
+                        source: anyhow!("disconnected from the broker"),
+                        foreign_info: ForeignInfo::Mqtt(broker(&self.options)),
+                    },
+                })?;
+            let rows = self.publish_into_rows(&publish)?;
+            self.pending_rows.extend(rows);
+        }
+    }
+}
+
+impl MqttSourceReader {
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidFormat` when:
+    ///   - the message is malformed in `FORMAT`.
+    fn publish_into_rows(&mut self, publish: &Publish) -> Result<Vec<SourceRow>> {
+        let rows = self.decoder.decode(&publish.payload)?;
+
+        let metadata = SourceMetadata {
+            topic: Some(publish.topic.clone()),
+            qos: Some(publish.qos as i16),
+            ..Default::default()
+        };
+        rows.into_iter()
+            .map(|row| row.with_metadata(&metadata, &self.metadata_options))
+            .collect()
+    }
+}
+
+impl Debug for MqttSourceReader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MqttSourceReader")
+            .field("options", &self.options)
+            .field("pending_rows", &self.pending_rows.len())
+            .finish()
+    }
+}
+
+impl Drop for MqttSourceReader {
+    fn drop(&mut self) {
+        let _ = self.client.try_disconnect();
+    }
+}
+
+fn broker(options: &MqttSourceOptions) -> String {
+    format!("{}:{}", options.remote_host, options.remote_port)
+}
@@ -0,0 +1,182 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{collections::VecDeque, io, time::Duration};
+
+use anyhow::{anyhow, Context};
+use nats::{
+    jetstream::{self, AckKind, PushSubscription, SubscribeOptions},
+    Message,
+};
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSourceReaderConfig,
+    pipeline::{NatsMode, NatsSourceOptions, Options, SourceMetadataOptions},
+    stream_engine::autonomous_executor::{
+        row::{SourceMetadata, SourceRow},
+        task::source_task::source_reader::SourceReader,
+    },
+};
+
+#[derive(Debug)]
+enum Subscription {
+    Core(nats::Subscription),
+    JetStream(PushSubscription),
+}
+
+impl Subscription {
+    fn next_timeout(&self, timeout: Duration) -> io::Result<Message> {
+        match self {
+            Self::Core(subscription) => subscription.next_timeout(timeout),
+            Self::JetStream(subscription) => subscription.next_timeout(timeout),
+        }
+    }
+}
+
+/// Subscribes to a NATS subject whose messages are JSON rows.
+///
+/// Subject of each message is provided as `topic` metadata.
+///
+/// In JETSTREAM mode, a message is acknowledged after its rows are parsed. Malformed messages are terminated (not redelivered).
+#[derive(Debug)]
+pub struct NatsSourceReader {
+    options: NatsSourceOptions,
+    metadata_options: SourceMetadataOptions,
+
+    subscription: Subscription,
+    read_timeout: Duration,
+
+    /// Rows parsed but not returned yet.
+    pending_rows: VecDeque<SourceRow>,
+}
+
+impl SourceReader for NatsSourceReader {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to connect to NATS servers.
+    ///   - failed to subscribe to the subject (e.g. no JetStream stream covers it).
+    /// - `SpringError::InvalidOption`
+    fn start(options: &Options, config: &SpringSourceReaderConfig) -> Result<Self> {
+        let metadata_options = SourceMetadataOptions::try_from(options)?;
+        let options = NatsSourceOptions::try_from(options)?;
+        let foreign_io = |e: io::Error, context: &'static str| SpringError::ForeignIo {
+            source: anyhow::Error::from(e).context(context),
+            foreign_info: ForeignInfo::Nats(options.url.clone()),
+        };
+
+        let connection = nats_options(options.user.as_ref())
+            .connect(options.url.as_str())
+            .map_err(|e| foreign_io(e, "failed to connect to NATS"))?;
+
+        let subscription = match options.mode {
+            NatsMode::Core => match &options.queue_group {
+                Some(queue_group) => connection.queue_subscribe(&options.subject, queue_group),
+                None => connection.subscribe(&options.subject),
+            }
+            .map(Subscription::Core),
+            NatsMode::JetStream => {
+                let jet_stream = jetstream::new(connection);
+                let mut subscribe_options = SubscribeOptions::new().ack_explicit();
+                if let Some(durable_name) = &options.durable_name {
+                    subscribe_options = subscribe_options.durable_name(durable_name.clone());
+                }
+                match &options.queue_group {
+                    Some(queue_group) => jet_stream.queue_subscribe_with_options(
+                        &options.subject,
+                        queue_group,
+                        &subscribe_options,
+                    ),
+                    None => jet_stream.subscribe_with_options(&options.subject, &subscribe_options),
+                }
+                .map(Subscription::JetStream)
+            }
+        }
+        .map_err(|e| foreign_io(e, "failed to subscribe"))?;
+
+        log::info!(
+            "[NatsSourceReader] Ready to read subject {} from {}",
+            options.subject,
+            options.url
+        );
+
+        Ok(Self {
+            options,
+            metadata_options,
+            subscription,
+            read_timeout: Duration::from_millis(config.net_read_timeout_msec as u64),
+            pending_rows: VecDeque::new(),
+        })
+    }
+
+    fn next_row(&mut self) -> Result<SourceRow> {
+        loop {
+            if let Some(row) = self.pending_rows.pop_front() {
+                return Ok(row);
+            }
+
+            let message = self
+                .subscription
+                .next_timeout(self.read_timeout)
+                .map_err(|e| match e.kind() {
+                    io::ErrorKind::TimedOut => SpringError::ForeignSourceTimeout {
+                        source: anyhow!("no message in {:?}", self.read_timeout),
+                        foreign_info: ForeignInfo::Nats(self.options.url.clone()),
+                    },
+                    _ => SpringError::ForeignIo {
+                        source: anyhow::Error::from(e).context("failed to receive message"),
+                        foreign_info: ForeignInfo::Nats(self.options.url.clone()),
+                    },
+                })?;
+            let rows = self.message_into_rows(&message);
+            self.acknowledge(&message, rows.is_ok())?;
+            self.pending_rows.extend(rows?);
+        }
+    }
+}
+
+impl NatsSourceReader {
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidFormat` when:
+    ///   - the message is not a JSON row.
+    fn message_into_rows(&self, message: &Message) -> Result<Vec<SourceRow>> {
+        let json = std::str::from_utf8(&message.data)
+            .context("message is not UTF-8")
+            .map_err(|e| SpringError::InvalidFormat {
+                source: e,
+                s: String::from_utf8_lossy(&message.data).to_string(),
+            })?;
+
+        let metadata = SourceMetadata {
+            topic: Some(message.subject.clone()),
+            ..Default::default()
+        };
+        SourceRow::from_json_records(json, self.options.json_records_path.as_ref())?
+            .into_iter()
+            .map(|row| row.with_metadata(&metadata, &self.metadata_options))
+            .collect()
+    }
+
+    fn acknowledge(&self, message: &Message, parsed: bool) -> Result<()> {
+        if self.options.mode == NatsMode::JetStream {
+            let ack_kind = if parsed { AckKind::Ack } else { AckKind::Term };
+            message
+                .ack_kind(ack_kind)
+                .context("failed to acknowledge message")
+                .map_err(|e| SpringError::ForeignIo {
+                    source: e,
+                    foreign_info: ForeignInfo::Nats(self.options.url.clone()),
+                })?;
+        }
+        Ok(())
+    }
+}
+
+fn nats_options(user: Option<&(String, String)>) -> nats::Options {
+    match user {
+        Some((user_name, password)) => nats::Options::with_user_pass(user_name, password),
+        None => nats::Options::new(),
+    }
+    .with_name("springql")
+}
@@ -1,7 +1,8 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 use std::{
-    io::{self, BufRead, BufReader},
+    collections::VecDeque,
+    io::{self, BufRead, BufReader, Read},
     net::{SocketAddr, TcpStream},
     time::Duration,
 };
@@ -11,16 +12,16 @@ use anyhow::Context;
 use crate::{
     api::error::{foreign_info::ForeignInfo, Result, SpringError},
     api::SpringSourceReaderConfig,
-    pipeline::{NetClientOptions, Options},
+    pipeline::{JsonPath, NetClientOptions, Options, SourceFormatOptions},
     stream_engine::autonomous_executor::{
-        row::SourceRow, task::source_task::source_reader::SourceReader,
+        row::{CsvParser, SourceRow},
+        task::source_task::source_reader::SourceReader,
     },
 };
 
 #[derive(Debug)]
 pub struct NetClientSourceReader {
-    foreign_addr: SocketAddr,
-    tcp_stream_reader: BufReader<TcpStream>, // TODO UDP
+    line_reader: LineSourceReader<TcpStream>, // TODO UDP
 }
 
 impl SourceReader for NetClientSourceReader {
@@ -29,6 +30,8 @@ impl SourceReader for NetClientSourceReader {
     /// - `SpringError::ForeignIo`
     /// - `SpringError::InvalidOption`
     fn start(options: &Options, config: &SpringSourceReaderConfig) -> Result<Self> {
+        let format_options = SourceFormatOptions::try_from(options)?;
+        format_options.ensure_text("NET_CLIENT")?;
         let options = NetClientOptions::try_from(options)?;
         let sock_addr = SocketAddr::new(options.remote_host, options.remote_port);
 
@@ -51,48 +54,106 @@ impl SourceReader for NetClientSourceReader {
                 foreign_info: ForeignInfo::GenericTcp(sock_addr),
             })?;
 
-        let tcp_stream_reader = BufReader::new(tcp_stream);
-
         log::info!("[NetSourceReader] Ready to read from {}", sock_addr);
 
         Ok(Self {
-            tcp_stream_reader,
-            foreign_addr: sock_addr,
+            line_reader: LineSourceReader::new(
+                tcp_stream,
+                ForeignInfo::GenericTcp(sock_addr),
+                &format_options,
+                options.json_records_path,
+            ),
         })
     }
 
     fn next_row(&mut self) -> Result<SourceRow> {
+        self.line_reader.next_row()
+    }
+}
+
+/// Reads rows from a byte stream with a row (or rows in a JSON array) per line.
+///
+/// Shared by source readers connecting to a remote stream (`NET_CLIENT`, `UNIX_SOCKET`).
+#[derive(Debug)]
+pub(super) struct LineSourceReader<S> {
+    stream_reader: BufReader<S>,
+    foreign_info: ForeignInfo,
+
+    json_records_path: Option<JsonPath>,
+    /// Set for `FORMAT 'CSV'`. Keeps the header from the first line.
+    csv_parser: Option<CsvParser>,
+
+    /// Rows expanded from a payload but not returned yet.
+    pending_rows: VecDeque<SourceRow>,
+}
+
+impl<S: Read> LineSourceReader<S> {
+    /// `stream` must have a read timeout not to block the source task forever.
+    pub(super) fn new(
+        stream: S,
+        foreign_info: ForeignInfo,
+        format_options: &SourceFormatOptions,
+        json_records_path: Option<JsonPath>,
+    ) -> Self {
+        Self {
+            stream_reader: BufReader::new(stream),
+            foreign_info,
+            json_records_path,
+            csv_parser: format_options.csv().map(CsvParser::new),
+            pending_rows: VecDeque::new(),
+        }
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignSourceTimeout` when:
+    ///   - no line is available within the read timeout.
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to read from the stream.
+    /// - `SpringError::InvalidFormat` when:
+    ///   - failed to parse a line.
+    pub(super) fn next_row(&mut self) -> Result<SourceRow> {
+        loop {
+            if let Some(row) = self.pending_rows.pop_front() {
+                return Ok(row);
+            }
+
+            let line = self.read_line()?;
+            let rows = self.parse_resp(&line)?;
+            self.pending_rows.extend(rows);
+        }
+    }
+
+    fn read_line(&mut self) -> Result<String> {
         let mut json_s = String::new();
 
-        self.tcp_stream_reader
+        self.stream_reader
             .read_line(&mut json_s)
             .map_err(|io_err| {
                 if let io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock = io_err.kind() {
                     SpringError::ForeignSourceTimeout {
                         source: anyhow::Error::from(io_err),
-                        foreign_info: ForeignInfo::GenericTcp(self.foreign_addr),
+                        foreign_info: self.foreign_info.clone(),
                     }
                 } else {
                     SpringError::ForeignIo {
                         source: anyhow::Error::from(io_err),
-                        foreign_info: ForeignInfo::GenericTcp(self.foreign_addr),
+                        foreign_info: self.foreign_info.clone(),
                     }
                 }
             })?;
 
-        self.parse_resp(&json_s)
+        Ok(json_s)
     }
-}
 
-impl NetClientSourceReader {
-    // TODO other formats than JSON
-    fn parse_resp(&self, json_s: &str) -> Result<SourceRow> {
-        SourceRow::from_json(json_s)
+    fn parse_resp(&mut self, line: &str) -> Result<Vec<SourceRow>> {
+        match &mut self.csv_parser {
+            Some(csv_parser) => SourceRow::from_csv_records(csv_parser, line),
+            None => SourceRow::from_json_records(line, self.json_records_path.as_ref()),
+        }
     }
 }
 
-impl NetClientSourceReader {}
-
 #[cfg(test)]
 mod tests {
     use springql_foreign_service::source::ForeignSource;
@@ -145,4 +206,53 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_source_tcp_records_envelope() -> crate::api::error::Result<()> {
+        let j1 = JsonObject::fx_city_temperature_tokyo();
+        let j2 = JsonObject::fx_city_temperature_osaka();
+        let j3 = JsonObject::fx_city_temperature_london();
+
+        let source = ForeignSource::new().unwrap();
+
+        let options = OptionsBuilder::default()
+            .add("PROTOCOL", "TCP")
+            .add("REMOTE_HOST", source.host_ip().to_string())
+            .add("REMOTE_PORT", source.port().to_string())
+            .add("JSON_RECORDS_PATH", "$.records")
+            .build();
+
+        source.start(ForeignSourceInput::new_fifo_batch(vec![
+            serde_json::json!({
+                "records": [
+                    serde_json::Value::from(j2.clone()),
+                    serde_json::Value::from(j3.clone()),
+                ]
+            }),
+            serde_json::json!({ "records": [] }),
+            serde_json::json!({ "records": [serde_json::Value::from(j1.clone())] }),
+        ]));
+
+        let mut subtask =
+            NetClientSourceReader::start(&options, &SpringSourceReaderConfig::fx_default())?;
+
+        assert_eq!(
+            subtask.next_row()?,
+            SourceRow::Json(JsonSourceRow::from_json(j2))
+        );
+        assert_eq!(
+            subtask.next_row()?,
+            SourceRow::Json(JsonSourceRow::from_json(j3))
+        );
+        assert_eq!(
+            subtask.next_row()?,
+            SourceRow::Json(JsonSourceRow::from_json(j1))
+        );
+        assert!(matches!(
+            subtask.next_row().unwrap_err(),
+            SpringError::ForeignSourceTimeout { .. }
+        ));
+
+        Ok(())
+    }
 }
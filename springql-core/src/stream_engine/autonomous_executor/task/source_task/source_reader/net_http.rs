@@ -0,0 +1,197 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    collections::VecDeque,
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Context};
+use reqwest::blocking::Client;
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSourceReaderConfig,
+    pipeline::{NetHttpOptions, Options, SourceFormatOptions},
+    stream_engine::autonomous_executor::{
+        row::{CsvParser, SourceRow},
+        task::source_task::source_reader::SourceReader,
+    },
+};
+
+/// Polls an HTTP endpoint with a GET request every `INTERVAL_MS` and emits rows in each JSON response body.
+///
+/// A response body is a JSON object (a row) or a JSON array of rows, selected by `JSON_RECORDS_PATH` if set.
+/// With `FORMAT 'CSV'`, a response body is CSV records and each response has its own header.
+/// A request times out in `INTERVAL_MS` (or `net_connect_timeout_msec` if longer) so that a slow endpoint does not pile up requests.
+#[derive(Debug)]
+pub struct NetHttpSourceReader {
+    client: Client,
+    options: NetHttpOptions,
+    format_options: SourceFormatOptions,
+
+    read_timeout: Duration,
+    next_poll_at: Instant,
+    pending_rows: VecDeque<SourceRow>,
+}
+
+impl SourceReader for NetHttpSourceReader {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to create HTTP client.
+    /// - `SpringError::InvalidOption`
+    fn start(options: &Options, config: &SpringSourceReaderConfig) -> Result<Self> {
+        let format_options = SourceFormatOptions::try_from(options)?;
+        format_options.ensure_text("NET_HTTP")?;
+        let options = NetHttpOptions::try_from(options)?;
+
+        let connect_timeout = Duration::from_millis(config.net_connect_timeout_msec as u64);
+        let client = Client::builder()
+            .connect_timeout(connect_timeout)
+            .timeout(options.interval.max(connect_timeout))
+            .build()
+            .context("failed to create HTTP client")
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::HttpEndpoint(options.url.to_string()),
+            })?;
+
+        log::info!(
+            "[NetHttpSourceReader] Ready to poll {} every {:?}",
+            options.url,
+            options.interval
+        );
+
+        Ok(Self {
+            client,
+            options,
+            format_options,
+            read_timeout: Duration::from_millis(config.net_read_timeout_msec as u64),
+            next_poll_at: Instant::now(),
+            pending_rows: VecDeque::new(),
+        })
+    }
+
+    fn next_row(&mut self) -> Result<SourceRow> {
+        loop {
+            if let Some(row) = self.pending_rows.pop_front() {
+                return Ok(row);
+            }
+
+            let now = Instant::now();
+            if now < self.next_poll_at {
+                let wait = self.next_poll_at - now;
+                if wait > self.read_timeout {
+                    thread::sleep(self.read_timeout);
+                    return Err(SpringError::ForeignSourceTimeout {
+                        source: anyhow!("next poll in {:?}", wait - self.read_timeout),
+                        foreign_info: self.foreign_info(),
+                    });
+                }
+                thread::sleep(wait);
+            }
+            // does not catch up missed polls
+            self.next_poll_at = (self.next_poll_at + self.options.interval)
+                .max(Instant::now() + self.options.interval / 2);
+
+            let body = self.poll().map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: self.foreign_info(),
+            })?;
+            let rows = match self.format_options.csv() {
+                Some(csv_options) => {
+                    SourceRow::from_csv_records(&mut CsvParser::new(csv_options), &body)?
+                }
+                None => {
+                    SourceRow::from_json_records(&body, self.options.json_records_path.as_ref())?
+                }
+            };
+            self.pending_rows.extend(rows);
+        }
+    }
+}
+
+impl NetHttpSourceReader {
+    fn foreign_info(&self) -> ForeignInfo {
+        ForeignInfo::HttpEndpoint(self.options.url.to_string())
+    }
+
+    fn poll(&self) -> anyhow::Result<String> {
+        let mut request = self.client.get(self.options.url.clone());
+        for (name, value) in &self.options.headers {
+            request = request.header(name, value);
+        }
+        let body = request
+            .send()
+            .and_then(|response| response.error_for_status())
+            .and_then(|response| response.text())
+            .context("failed to poll HTTP endpoint")?;
+        Ok(body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::{SocketAddr, TcpListener},
+    };
+
+    use crate::{
+        pipeline::{ColumnName, OptionsBuilder},
+        stream_engine::{autonomous_executor::row::SchemalessRow, NnSqlValue, SqlValue},
+    };
+
+    use super::*;
+
+    /// Responds to each request with `{"readings": [{"city": "Tokyo"}, {"city": "Osaka"}]}`.
+    fn serve() -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 0 && line != "\r\n" {
+                    line.clear();
+                }
+
+                let body = r#"{"readings": [{"city": "Tokyo"}, {"city": "Osaka"}]}"#;
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        addr
+    }
+
+    #[test]
+    fn test_net_http_source_reader() {
+        let addr = serve();
+        let options = OptionsBuilder::default()
+            .add("URL", format!("http://{}/readings", addr))
+            .add("INTERVAL_MS", "10")
+            .add("JSON_RECORDS_PATH", "$.readings")
+            .build();
+        let mut reader =
+            NetHttpSourceReader::start(&options, &SpringSourceReaderConfig::fx_default()).unwrap();
+
+        // 2 polls
+        for expected in ["Tokyo", "Osaka", "Tokyo", "Osaka"] {
+            let row = SchemalessRow::try_from(reader.next_row().unwrap()).unwrap();
+            assert_eq!(
+                row.get_by_column_name(&ColumnName::new("city".to_string()))
+                    .unwrap(),
+                &SqlValue::NotNull(NnSqlValue::Text(expected.to_string()))
+            );
+        }
+    }
+}
@@ -1,6 +1,7 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 use std::{
+    collections::VecDeque,
     io::{BufRead, BufReader},
     net::{Shutdown, SocketAddr, TcpListener, TcpStream},
     sync::{mpsc, Mutex, MutexGuard},
@@ -11,9 +12,9 @@ use std::{
 use crate::{
     api::error::{foreign_info::ForeignInfo, Result, SpringError},
     api::SpringSourceReaderConfig,
-    pipeline::{NetProtocol, NetServerOptions, Options},
+    pipeline::{JsonPath, NetProtocol, NetServerOptions, Options},
     stream_engine::autonomous_executor::{
-        row::{JsonObject, JsonSourceRow, SourceRow},
+        row::{JsonObject, SourceRow},
         task::source_task::source_reader::SourceReader,
     },
 };
@@ -26,6 +27,11 @@ pub struct NetServerSourceReader {
     rx: Mutex<mpsc::Receiver<serde_json::Value>>,
 
     timeout: Duration,
+
+    json_records_path: Option<JsonPath>,
+
+    /// Rows expanded from a payload but not returned yet.
+    pending_rows: VecDeque<SourceRow>,
 }
 
 impl SourceReader for NetServerSourceReader {
@@ -66,10 +72,33 @@ impl SourceReader for NetServerSourceReader {
             my_addr,
             rx: Mutex::new(rx),
             timeout,
+            json_records_path: options.json_records_path,
+            pending_rows: VecDeque::new(),
         })
     }
 
     fn next_row(&mut self) -> Result<SourceRow> {
+        loop {
+            if let Some(row) = self.pending_rows.pop_front() {
+                return Ok(row);
+            }
+
+            let json = self.recv()?;
+            let rows = SourceRow::from_json_object_records(
+                JsonObject::new(json),
+                self.json_records_path.as_ref(),
+            )?;
+            self.pending_rows.extend(rows);
+        }
+    }
+}
+
+impl NetServerSourceReader {
+    fn rx(&self) -> MutexGuard<mpsc::Receiver<serde_json::Value>> {
+        self.rx.lock().expect("failed to lock mutex")
+    }
+
+    fn recv(&self) -> Result<serde_json::Value> {
         let rx = self.rx();
 
         rx.try_recv()
@@ -77,21 +106,11 @@ impl SourceReader for NetServerSourceReader {
                 thread::sleep(self.timeout);
                 rx.try_recv()
             })
-            .map(|json| {
-                let json_obj = JsonObject::new(json);
-                SourceRow::Json(JsonSourceRow::from_json(json_obj))
-            })
             .map_err(|e| SpringError::ForeignSourceTimeout {
                 source: anyhow::Error::from(e),
                 foreign_info: ForeignInfo::GenericTcp(self.my_addr),
             })
     }
-}
-
-impl NetServerSourceReader {
-    fn rx(&self) -> MutexGuard<mpsc::Receiver<serde_json::Value>> {
-        self.rx.lock().expect("failed to lock mutex")
-    }
 
     fn stream_handler(stream: TcpStream, tx: mpsc::Sender<serde_json::Value>) {
         log::info!(
@@ -133,6 +152,7 @@ mod tests {
         api::SpringSinkWriterConfig,
         pipeline::OptionsBuilder,
         stream_engine::autonomous_executor::{
+            row::JsonSourceRow,
             task::sink_task::{NetSinkWriter, SinkWriter},
             SchemalessRow,
         },
@@ -0,0 +1,363 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    str::FromStr,
+    sync::{mpsc, Mutex},
+    time::Duration,
+};
+
+use anyhow::anyhow;
+use opcua::client::prelude::{
+    ClientBuilder, DataChangeCallback, DataValue, DateTime, IdentityToken, MessageSecurityMode,
+    MonitoredItemCreateRequest, NodeId, SecurityPolicy, Session, SessionCommand,
+    TimestampsToReturn, UserTokenPolicy, Variant,
+};
+use ordered_float::OrderedFloat;
+
+use crate::{
+    api::{
+        error::{foreign_info::ForeignInfo, Result},
+        SpringError, SpringSourceReaderConfig,
+    },
+    pipeline::{ColumnName, OpcUaOptions, Options},
+    stream_engine::{
+        autonomous_executor::{row::SchemalessRow, SourceReader, SourceRow},
+        time::{SpringTimestamp, SystemTimestamp},
+        NnSqlValue, SqlValue,
+    },
+    time::NaiveDateTime,
+};
+
+/// Subscribes to OPC UA nodes and emits a row with the latest values of all the nodes per data change notification.
+///
+/// # Data format
+///
+/// - `NODE_<column>` columns: value of each node. NULL until the first notification, or while the status is bad.
+///   - Boolean, integer, float, string, byte string and date-time values are converted into the corresponding SQL types.
+///   - Other values (e.g. arrays) are converted into TEXT in debug format.
+/// - `TIMESTAMP_COLUMN` (optional): the latest source timestamp of changed values (or wall-clock time if the server does not provide).
+pub(in crate::stream_engine) struct OpcUaSourceReader {
+    endpoint_url: String,
+    timeout: Duration,
+
+    rx: Mutex<mpsc::Receiver<SchemalessRow>>,
+    /// Called on drop.
+    stop_session: Option<Box<dyn FnOnce() + Send + Sync>>,
+}
+
+impl SourceReader for OpcUaSourceReader {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to connect to the server.
+    ///   - failed to subscribe to the nodes.
+    /// - `SpringError::InvalidOption`
+    fn start(options: &Options, config: &SpringSourceReaderConfig) -> Result<Self> {
+        let options = OpcUaOptions::try_from(options)?;
+        let foreign_io = |status| SpringError::ForeignIo {
+            source: anyhow!("OPC UA status: {}", status),
+            foreign_info: ForeignInfo::OpcUa(options.endpoint_url.clone()),
+        };
+
+        let node_ids = options
+            .nodes
+            .iter()
+            .map(|(_, node_id)| {
+                NodeId::from_str(node_id).map_err(|_| SpringError::InvalidOption {
+                    key: "NODE_<column>".to_string(),
+                    value: node_id.clone(),
+                    source: anyhow!("invalid OPC UA node ID"),
+                })
+            })
+            .collect::<Result<Vec<NodeId>>>()?;
+
+        let mut client = ClientBuilder::new()
+            .application_name("SpringQL")
+            .application_uri("urn:SpringQL")
+            .product_uri("urn:SpringQL")
+            .trust_server_certs(true)
+            .create_sample_keypair(true)
+            // reconnects forever
+            .session_retry_limit(-1)
+            .client()
+            .ok_or_else(|| SpringError::ForeignIo {
+                source: anyhow!("invalid OPC UA client configuration"),
+                foreign_info: ForeignInfo::OpcUa(options.endpoint_url.clone()),
+            })?;
+
+        let identity_token = match &options.user {
+            Some((user_name, password)) => {
+                IdentityToken::UserName(user_name.clone(), password.clone())
+            }
+            None => IdentityToken::Anonymous,
+        };
+        let session = client
+            .connect_to_endpoint(
+                (
+                    options.endpoint_url.as_str(),
+                    SecurityPolicy::None.to_str(),
+                    MessageSecurityMode::None,
+                    UserTokenPolicy::anonymous(),
+                ),
+                identity_token,
+            )
+            .map_err(foreign_io)?;
+
+        let (tx, rx) = mpsc::channel();
+        let handler = Mutex::new(DataChangeHandler {
+            node_index: node_ids
+                .iter()
+                .enumerate()
+                .map(|(i, node_id)| (node_id.clone(), i))
+                .collect(),
+            node_values: NodeValues::new(
+                options
+                    .nodes
+                    .iter()
+                    .map(|(column, _)| column.clone())
+                    .collect(),
+                options.timestamp_column.clone(),
+            ),
+            tx,
+        });
+
+        {
+            let mut session = session.write().expect("failed to lock OPC UA session");
+            let subscription_id = session
+                .create_subscription(
+                    options.publishing_interval.as_millis() as f64,
+                    10,
+                    30,
+                    0,
+                    0,
+                    true,
+                    DataChangeCallback::new(move |changed_monitored_items| {
+                        let mut handler = handler.lock().expect("failed to lock mutex");
+                        for item in changed_monitored_items {
+                            handler.update(&item.item_to_monitor().node_id, item.last_value());
+                        }
+                        handler.emit();
+                    }),
+                )
+                .map_err(foreign_io)?;
+
+            let items: Vec<MonitoredItemCreateRequest> =
+                node_ids.into_iter().map(|node_id| node_id.into()).collect();
+            session
+                .create_monitored_items(subscription_id, TimestampsToReturn::Both, &items)
+                .map_err(foreign_io)?;
+        }
+        let session_tx = Session::run_async(session);
+        let stop_session = move || {
+            let _ = session_tx.send(SessionCommand::Stop);
+        };
+
+        log::info!(
+            "[OpcUaSourceReader] Ready to read {} nodes from {}",
+            options.nodes.len(),
+            options.endpoint_url
+        );
+
+        Ok(Self {
+            endpoint_url: options.endpoint_url,
+            timeout: Duration::from_millis(config.net_read_timeout_msec as u64),
+            rx: Mutex::new(rx),
+            stop_session: Some(Box::new(stop_session)),
+        })
+    }
+
+    fn next_row(&mut self) -> Result<SourceRow> {
+        let rx = self.rx.lock().expect("failed to lock mutex");
+        let row = rx
+            .recv_timeout(self.timeout)
+            .map_err(|e| SpringError::ForeignSourceTimeout {
+                source: anyhow::Error::from(e),
+                foreign_info: ForeignInfo::OpcUa(self.endpoint_url.clone()),
+            })?;
+        Ok(SourceRow::Raw(row))
+    }
+}
+
+impl Debug for OpcUaSourceReader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OpcUaSourceReader")
+            .field("endpoint_url", &self.endpoint_url)
+            .finish()
+    }
+}
+
+impl Drop for OpcUaSourceReader {
+    fn drop(&mut self) {
+        if let Some(stop_session) = self.stop_session.take() {
+            stop_session();
+        }
+    }
+}
+
+#[derive(Debug)]
+struct DataChangeHandler {
+    node_index: HashMap<NodeId, usize>,
+    node_values: NodeValues,
+    tx: mpsc::Sender<SchemalessRow>,
+}
+
+impl DataChangeHandler {
+    fn update(&mut self, node_id: &NodeId, data_value: &DataValue) {
+        if let Some(&i) = self.node_index.get(node_id) {
+            let value = match (&data_value.value, data_value.status) {
+                (_, Some(status)) if status.is_bad() => SqlValue::Null,
+                (Some(variant), _) => variant_to_sql_value(variant),
+                (None, _) => SqlValue::Null,
+            };
+            let source_timestamp = data_value
+                .source_timestamp
+                .as_ref()
+                .and_then(to_spring_timestamp);
+            self.node_values.update(i, value, source_timestamp);
+        }
+    }
+
+    fn emit(&mut self) {
+        match self.node_values.to_row() {
+            // the receiver is dropped after the source reader
+            Ok(row) => {
+                let _ = self.tx.send(row);
+            }
+            Err(e) => log::warn!("[OpcUaSourceReader] failed to make a row: {:?}", e),
+        }
+    }
+}
+
+/// Latest values of the subscribed nodes.
+#[derive(Debug)]
+struct NodeValues {
+    columns: Vec<ColumnName>,
+    values: Vec<SqlValue>,
+
+    timestamp_column: Option<ColumnName>,
+    timestamp: Option<SpringTimestamp>,
+}
+
+impl NodeValues {
+    fn new(columns: Vec<ColumnName>, timestamp_column: Option<ColumnName>) -> Self {
+        let values = vec![SqlValue::Null; columns.len()];
+        Self {
+            columns,
+            values,
+            timestamp_column,
+            timestamp: None,
+        }
+    }
+
+    fn update(&mut self, i: usize, value: SqlValue, source_timestamp: Option<SpringTimestamp>) {
+        self.values[i] = value;
+        self.timestamp = match (self.timestamp, source_timestamp) {
+            (Some(t), Some(source_t)) => Some(t.max(source_t)),
+            (t, source_t) => source_t.or(t),
+        };
+    }
+
+    fn to_row(&self) -> Result<SchemalessRow> {
+        let mut row = SchemalessRow::default();
+        for (column, value) in self.columns.iter().zip(self.values.iter()) {
+            row.insert(column.clone(), value.clone())?;
+        }
+        if let Some(timestamp_column) = &self.timestamp_column {
+            let timestamp = self.timestamp.unwrap_or_else(SystemTimestamp::now);
+            row.insert(
+                timestamp_column.clone(),
+                SqlValue::NotNull(NnSqlValue::Timestamp(timestamp)),
+            )?;
+        }
+        Ok(row)
+    }
+}
+
+fn variant_to_sql_value(variant: &Variant) -> SqlValue {
+    let nn_sql_value = match variant {
+        Variant::Empty => return SqlValue::Null,
+        Variant::Boolean(v) => NnSqlValue::Boolean(*v),
+        Variant::SByte(v) => NnSqlValue::SmallInt(*v as i16),
+        Variant::Byte(v) => NnSqlValue::SmallInt(*v as i16),
+        Variant::Int16(v) => NnSqlValue::SmallInt(*v),
+        Variant::UInt16(v) => NnSqlValue::Integer(*v as i32),
+        Variant::Int32(v) => NnSqlValue::Integer(*v),
+        Variant::UInt32(v) => NnSqlValue::UnsignedInteger(*v),
+        Variant::Int64(v) => NnSqlValue::BigInt(*v),
+        Variant::UInt64(v) => NnSqlValue::UnsignedBigInt(*v),
+        Variant::Float(v) => NnSqlValue::Float(OrderedFloat(*v)),
+        Variant::Double(v) => NnSqlValue::Float(OrderedFloat(*v as f32)),
+        Variant::String(s) => match s.value() {
+            Some(s) => NnSqlValue::Text(s.clone()),
+            None => return SqlValue::Null,
+        },
+        Variant::ByteString(b) => match &b.value {
+            Some(b) => NnSqlValue::Blob(b.clone()),
+            None => return SqlValue::Null,
+        },
+        Variant::DateTime(dt) => match to_spring_timestamp(dt) {
+            Some(t) => NnSqlValue::Timestamp(t),
+            None => return SqlValue::Null,
+        },
+        other => NnSqlValue::Text(format!("{:?}", other)),
+    };
+    SqlValue::NotNull(nn_sql_value)
+}
+
+fn to_spring_timestamp(dt: &DateTime) -> Option<SpringTimestamp> {
+    let dt = dt.as_chrono();
+    NaiveDateTime::from_timestamp(dt.timestamp(), dt.timestamp_subsec_nanos())
+        .ok()
+        .map(SpringTimestamp::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use opcua::client::prelude::UAString;
+
+    use super::*;
+
+    #[test]
+    fn test_variant_to_sql_value() {
+        assert_eq!(
+            variant_to_sql_value(&Variant::Double(1.5)),
+            SqlValue::NotNull(NnSqlValue::Float(OrderedFloat(1.5)))
+        );
+        assert_eq!(
+            variant_to_sql_value(&Variant::String(UAString::from("running"))),
+            SqlValue::NotNull(NnSqlValue::Text("running".to_string()))
+        );
+        assert_eq!(variant_to_sql_value(&Variant::Empty), SqlValue::Null);
+    }
+
+    #[test]
+    fn test_node_values_keep_latest() {
+        let t1 = SpringTimestamp::from_str("2022-01-01 13:00:00.000000000").unwrap();
+        let t2 = SpringTimestamp::from_str("2022-01-01 13:00:01.000000000").unwrap();
+
+        let mut node_values = NodeValues::new(
+            vec![
+                ColumnName::new("a".to_string()),
+                ColumnName::new("b".to_string()),
+            ],
+            Some(ColumnName::new("ts".to_string())),
+        );
+
+        node_values.update(0, SqlValue::NotNull(NnSqlValue::Integer(1)), Some(t2));
+        node_values.update(1, SqlValue::NotNull(NnSqlValue::Integer(2)), Some(t1));
+        let row = node_values.to_row().unwrap();
+
+        assert_eq!(
+            row.get_by_column_name(&ColumnName::new("a".to_string()))
+                .unwrap(),
+            &SqlValue::NotNull(NnSqlValue::Integer(1))
+        );
+        assert_eq!(
+            row.get_by_column_name(&ColumnName::new("ts".to_string()))
+                .unwrap(),
+            &SqlValue::NotNull(NnSqlValue::Timestamp(t2))
+        );
+    }
+}
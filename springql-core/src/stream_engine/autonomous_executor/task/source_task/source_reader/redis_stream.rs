@@ -0,0 +1,234 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Debug,
+    time::Duration,
+};
+
+use anyhow::{anyhow, Context};
+use redis::{
+    streams::{StreamId, StreamReadOptions, StreamReadReply},
+    Client, Commands, Connection, Value,
+};
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSourceReaderConfig,
+    pipeline::{Options, RedisStreamSourceOptions, SourceMetadataOptions},
+    stream_engine::autonomous_executor::{
+        row::{SourceMetadata, SourceRow},
+        task::source_task::source_reader::SourceReader,
+    },
+};
+
+/// Reads entries of a Redis stream, each of which has a JSON row (or rows) in `FIELD`.
+///
+/// Stream key is provided as `topic` metadata, and entry ID as `key` metadata.
+///
+/// In a consumer group, entries are acknowledged as soon as they are read.
+pub struct RedisStreamSourceReader {
+    options: RedisStreamSourceOptions,
+    metadata_options: SourceMetadataOptions,
+
+    addr: String,
+    connection: Connection,
+    read_options: StreamReadOptions,
+    /// ID to read entries after (`>` in a consumer group).
+    last_id: String,
+
+    /// Rows read but not returned yet.
+    pending_rows: VecDeque<SourceRow>,
+}
+
+impl SourceReader for RedisStreamSourceReader {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to connect to Redis.
+    ///   - failed to create the consumer group.
+    /// - `SpringError::InvalidOption`
+    fn start(options: &Options, config: &SpringSourceReaderConfig) -> Result<Self> {
+        let metadata_options = SourceMetadataOptions::try_from(options)?;
+        let options = RedisStreamSourceOptions::try_from(options)?;
+
+        let client =
+            Client::open(options.url.as_str()).map_err(|e| SpringError::InvalidOption {
+                key: "URL".to_string(),
+                value: options.url.clone(),
+                source: e.into(),
+            })?;
+        let addr = client.get_connection_info().addr.to_string();
+        let foreign_io = |e: anyhow::Error| SpringError::ForeignIo {
+            source: e,
+            foreign_info: ForeignInfo::Redis(addr.clone()),
+        };
+
+        let block = Duration::from_millis(config.net_read_timeout_msec as u64);
+        let mut connection = client
+            .get_connection_with_timeout(Duration::from_millis(
+                config.net_connect_timeout_msec as u64,
+            ))
+            .context("failed to connect to Redis")
+            .map_err(foreign_io)?;
+        // XREAD blocks for `block` at most
+        connection
+            .set_read_timeout(Some(block * 2))
+            .context("failed to set read timeout")
+            .map_err(foreign_io)?;
+
+        let mut read_options = StreamReadOptions::default()
+            .count(options.count)
+            .block(block.as_millis() as usize);
+        let last_id = match &options.consumer_group {
+            Some(consumer_group) => {
+                let created: redis::RedisResult<()> = connection.xgroup_create_mkstream(
+                    &options.stream_key,
+                    &consumer_group.group,
+                    &options.start_id,
+                );
+                match created {
+                    Err(e) if e.code() != Some("BUSYGROUP") => {
+                        return Err(foreign_io(
+                            anyhow::Error::from(e).context("failed to create consumer group"),
+                        ))
+                    }
+                    _ => {}
+                }
+                read_options = read_options.group(&consumer_group.group, &consumer_group.consumer);
+                ">".to_string()
+            }
+            None => options.start_id.clone(),
+        };
+
+        log::info!(
+            "[RedisStreamSourceReader] Ready to read stream {} from {}",
+            options.stream_key,
+            addr
+        );
+
+        Ok(Self {
+            options,
+            metadata_options,
+            addr,
+            connection,
+            read_options,
+            last_id,
+            pending_rows: VecDeque::new(),
+        })
+    }
+
+    fn next_row(&mut self) -> Result<SourceRow> {
+        loop {
+            if let Some(row) = self.pending_rows.pop_front() {
+                return Ok(row);
+            }
+
+            let entries = self.read()?;
+            if entries.is_empty() {
+                return Err(SpringError::ForeignSourceTimeout {
+                    source: anyhow!("no new entry in stream {}", self.options.stream_key),
+                    foreign_info: ForeignInfo::Redis(self.addr.clone()),
+                });
+            }
+            for entry in entries {
+                let rows = self.entry_into_rows(entry)?;
+                self.pending_rows.extend(rows);
+            }
+        }
+    }
+}
+
+impl RedisStreamSourceReader {
+    fn read(&mut self) -> Result<Vec<StreamId>> {
+        let reply: StreamReadReply = self
+            .connection
+            .xread_options(
+                &[&self.options.stream_key],
+                &[&self.last_id],
+                &self.read_options,
+            )
+            .context("failed to read stream")
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::Redis(self.addr.clone()),
+            })?;
+        let entries: Vec<StreamId> = reply.keys.into_iter().flat_map(|key| key.ids).collect();
+
+        if let Some(consumer_group) = &self.options.consumer_group {
+            if !entries.is_empty() {
+                let ids: Vec<&str> = entries.iter().map(|entry| entry.id.as_str()).collect();
+                let _: i64 = self
+                    .connection
+                    .xack(&self.options.stream_key, &consumer_group.group, &ids)
+                    .context("failed to acknowledge entries")
+                    .map_err(|e| SpringError::ForeignIo {
+                        source: e,
+                        foreign_info: ForeignInfo::Redis(self.addr.clone()),
+                    })?;
+            }
+        } else if let Some(last) = entries.last() {
+            self.last_id = last.id.clone();
+        }
+
+        Ok(entries)
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidFormat` when:
+    ///   - the entry does not have `FIELD` or its value is not a JSON row.
+    fn entry_into_rows(&self, entry: StreamId) -> Result<Vec<SourceRow>> {
+        let json = field_value(&entry.map, &self.options.field)
+            .with_context(|| {
+                format!(
+                    "invalid field `{}` in entry {}",
+                    self.options.field, entry.id
+                )
+            })
+            .map_err(|e| SpringError::InvalidFormat {
+                source: e,
+                s: format!("{:?}", entry.map),
+            })?;
+
+        let metadata = SourceMetadata {
+            topic: Some(self.options.stream_key.clone()),
+            key: Some(entry.id),
+            ..Default::default()
+        };
+        SourceRow::from_json_records(&json, self.options.json_records_path.as_ref())?
+            .into_iter()
+            .map(|row| row.with_metadata(&metadata, &self.metadata_options))
+            .collect()
+    }
+}
+
+impl Debug for RedisStreamSourceReader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisStreamSourceReader")
+            .field("addr", &self.addr)
+            .field("stream_key", &self.options.stream_key)
+            .field("last_id", &self.last_id)
+            .finish()
+    }
+}
+
+fn field_value(fields: &HashMap<String, Value>, field: &str) -> anyhow::Result<String> {
+    let value = fields.get(field).context("field not found")?;
+    Ok(redis::from_redis_value(value)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_value() {
+        let fields = vec![("json".to_string(), Value::Data(br#"{"n": 1}"#.to_vec()))]
+            .into_iter()
+            .collect();
+
+        assert_eq!(field_value(&fields, "json").unwrap(), r#"{"n": 1}"#);
+        assert!(field_value(&fields, "payload").is_err());
+    }
+}
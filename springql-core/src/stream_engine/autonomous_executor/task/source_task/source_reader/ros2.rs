@@ -0,0 +1,316 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    fmt::Debug,
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, bail, Context};
+use ordered_float::OrderedFloat;
+use rustdds::{
+    no_key::{DataReader, DeserializerAdapter},
+    policy::Reliability,
+    serialization::error::Result as DeserializeResult,
+    DomainParticipant, QosPolicyBuilder, RepresentationIdentifier, TopicKind,
+};
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSourceReaderConfig,
+    pipeline::{Options, Ros2Field, Ros2FieldType, Ros2Options, Ros2Reliability},
+    stream_engine::{
+        autonomous_executor::{
+            row::{SchemalessRow, SourceRow},
+            task::source_task::source_reader::SourceReader,
+        },
+        NnSqlValue, SqlValue,
+    },
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Serialized message (without the encapsulation header).
+struct CdrPayload {
+    bytes: Vec<u8>,
+    little_endian: bool,
+}
+
+/// Passes serialized messages through to be decoded by `LAYOUT`.
+struct CdrPayloadAdapter;
+
+impl DeserializerAdapter<CdrPayload> for CdrPayloadAdapter {
+    fn supported_encodings() -> &'static [RepresentationIdentifier] {
+        &[
+            RepresentationIdentifier::CDR_LE,
+            RepresentationIdentifier::CDR_BE,
+        ]
+    }
+
+    fn from_bytes(
+        input_bytes: &[u8],
+        encoding: RepresentationIdentifier,
+    ) -> DeserializeResult<CdrPayload> {
+        Ok(CdrPayload {
+            bytes: input_bytes.to_vec(),
+            little_endian: encoding == RepresentationIdentifier::CDR_LE,
+        })
+    }
+}
+
+/// Subscribes to a ROS2 topic over DDS (without ROS2 installation) and emits a row per message.
+///
+/// Only the fields mapped by `FIELD_<column>` are put into a row:
+///
+/// - bool: BOOLEAN
+/// - byte, char, int8, uint8, int16: SMALLINT
+/// - uint16, int32: INTEGER
+/// - uint32: UNSIGNED INTEGER
+/// - int64: BIGINT
+/// - uint64: UNSIGNED BIGINT
+/// - float32, float64: FLOAT
+/// - string: TEXT
+pub struct Ros2SourceReader {
+    options: Ros2Options,
+
+    /// Holds the participant as long as the reader lives.
+    _participant: DomainParticipant,
+    /// DDS data readers are not Sync.
+    data_reader: Mutex<DataReader<CdrPayload, CdrPayloadAdapter>>,
+    read_timeout: Duration,
+}
+
+impl SourceReader for Ros2SourceReader {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to join the DDS domain or to create the topic reader.
+    /// - `SpringError::InvalidOption`
+    fn start(options: &Options, config: &SpringSourceReaderConfig) -> Result<Self> {
+        let options = Ros2Options::try_from(options)?;
+        let foreign_io = |e: anyhow::Error| SpringError::ForeignIo {
+            source: e,
+            foreign_info: ForeignInfo::Ros2(options.topic.clone()),
+        };
+
+        let reliability = match options.reliability {
+            Ros2Reliability::BestEffort => Reliability::BestEffort,
+            Ros2Reliability::Reliable => Reliability::Reliable {
+                max_blocking_time: rustdds::Duration::DURATION_ZERO,
+            },
+        };
+        let qos = QosPolicyBuilder::new().reliability(reliability).build();
+
+        let participant = DomainParticipant::new(options.domain_id)
+            .map_err(|e| anyhow!("{:?}", e).context("failed to join DDS domain"))
+            .map_err(foreign_io)?;
+        let data_reader = participant
+            .create_topic(
+                options.dds_topic_name(),
+                options.dds_type_name(),
+                &qos,
+                TopicKind::NoKey,
+            )
+            .and_then(|topic| {
+                participant
+                    .create_subscriber(&qos)?
+                    .create_datareader_no_key::<CdrPayload, CdrPayloadAdapter>(&topic, None)
+            })
+            .map_err(|e| anyhow!("{:?}", e).context("failed to create DDS data reader"))
+            .map_err(foreign_io)?;
+
+        log::info!(
+            "[Ros2SourceReader] Ready to read {} ({}) in domain {}",
+            options.topic,
+            options.message_type,
+            options.domain_id
+        );
+
+        Ok(Self {
+            options,
+            _participant: participant,
+            data_reader: Mutex::new(data_reader),
+            read_timeout: Duration::from_millis(config.net_read_timeout_msec as u64),
+        })
+    }
+
+    fn next_row(&mut self) -> Result<SourceRow> {
+        let deadline = Instant::now() + self.read_timeout;
+        loop {
+            let sample = self
+                .data_reader
+                .lock()
+                .expect("DDS data reader mutex poisoned")
+                .take_next_sample()
+                .map_err(|e| SpringError::ForeignIo {
+                    source: anyhow!("{:?}", e).context("failed to take DDS sample"),
+                    foreign_info: ForeignInfo::Ros2(self.options.topic.clone()),
+                })?;
+
+            match sample {
+                Some(sample) => {
+                    let payload = sample.into_value();
+                    let row = self.payload_into_row(&payload)?;
+                    return Ok(SourceRow::Raw(row));
+                }
+                None if Instant::now() < deadline => thread::sleep(POLL_INTERVAL),
+                None => {
+                    return Err(SpringError::ForeignSourceTimeout {
+                        source: anyhow!("no message in {:?}", self.read_timeout),
+                        foreign_info: ForeignInfo::Ros2(self.options.topic.clone()),
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl Ros2SourceReader {
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidFormat` when:
+    ///   - the message does not match `LAYOUT`.
+    fn payload_into_row(&self, payload: &CdrPayload) -> Result<SchemalessRow> {
+        let values = decode_cdr(&self.options.layout, &payload.bytes, payload.little_endian)
+            .with_context(|| format!("message does not match LAYOUT of {}", self.options.topic))
+            .map_err(|e| SpringError::InvalidFormat {
+                source: e,
+                s: format!("{:?}", payload.bytes),
+            })?;
+
+        let mut row = SchemalessRow::default();
+        for (column, path) in &self.options.fields {
+            let (_, value) = values
+                .iter()
+                .find(|(field, _)| &field.path == path)
+                .expect("FIELD_<column> is validated against LAYOUT");
+            row.insert(column.clone(), SqlValue::NotNull(value.clone()))?;
+        }
+        Ok(row)
+    }
+}
+
+impl Debug for Ros2SourceReader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Ros2SourceReader")
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+struct CdrCursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> CdrCursor<'a> {
+    /// Takes `size` bytes after aligning the position to `alignment`.
+    fn take(&mut self, size: usize, alignment: usize) -> anyhow::Result<&'a [u8]> {
+        let pos = (self.pos + alignment - 1) / alignment * alignment;
+        let slice = self
+            .bytes
+            .get(pos..pos + size)
+            .with_context(|| format!("message too short at offset {}", pos))?;
+        self.pos = pos + size;
+        Ok(slice)
+    }
+}
+
+/// Decodes fields of a CDR-serialized message in order.
+///
+/// Primitives are aligned to their size from the beginning of `bytes`; strings are a 4-byte length (including the NUL terminator) followed by bytes.
+fn decode_cdr<'a>(
+    layout: &'a [Ros2Field],
+    bytes: &[u8],
+    little_endian: bool,
+) -> anyhow::Result<Vec<(&'a Ros2Field, NnSqlValue)>> {
+    let mut cursor = CdrCursor { bytes, pos: 0 };
+    macro_rules! primitive {
+        ($ty:ty) => {{
+            let mut buf = [0u8; std::mem::size_of::<$ty>()];
+            buf.copy_from_slice(cursor.take(buf.len(), buf.len())?);
+            if little_endian {
+                <$ty>::from_le_bytes(buf)
+            } else {
+                <$ty>::from_be_bytes(buf)
+            }
+        }};
+    }
+
+    layout
+        .iter()
+        .map(|field| {
+            let value = match field.field_type {
+                Ros2FieldType::Bool => NnSqlValue::Boolean(primitive!(u8) != 0),
+                Ros2FieldType::Byte | Ros2FieldType::Uint8 => {
+                    NnSqlValue::SmallInt(primitive!(u8) as i16)
+                }
+                Ros2FieldType::Int8 => NnSqlValue::SmallInt(primitive!(i8) as i16),
+                Ros2FieldType::Int16 => NnSqlValue::SmallInt(primitive!(i16)),
+                Ros2FieldType::Uint16 => NnSqlValue::Integer(primitive!(u16) as i32),
+                Ros2FieldType::Int32 => NnSqlValue::Integer(primitive!(i32)),
+                Ros2FieldType::Uint32 => NnSqlValue::UnsignedInteger(primitive!(u32)),
+                Ros2FieldType::Int64 => NnSqlValue::BigInt(primitive!(i64)),
+                Ros2FieldType::Uint64 => NnSqlValue::UnsignedBigInt(primitive!(u64)),
+                Ros2FieldType::Float32 => NnSqlValue::Float(OrderedFloat(primitive!(f32))),
+                Ros2FieldType::Float64 => NnSqlValue::Float(OrderedFloat(primitive!(f64) as f32)),
+                Ros2FieldType::String => {
+                    let len = primitive!(u32) as usize;
+                    if len == 0 {
+                        bail!("invalid string length 0 for {}", field.path);
+                    }
+                    let mut s = cursor.take(len, 1)?.to_vec();
+                    s.pop(); // NUL terminator
+                    NnSqlValue::Text(
+                        String::from_utf8(s)
+                            .with_context(|| format!("{} is not UTF-8", field.path))?,
+                    )
+                }
+            };
+            Ok((field, value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(path: &str, field_type: Ros2FieldType) -> Ros2Field {
+        Ros2Field {
+            path: path.to_string(),
+            field_type,
+        }
+    }
+
+    #[test]
+    fn test_decode_cdr_alignment() {
+        let layout = vec![
+            field("charging", Ros2FieldType::Bool),
+            field("frame_id", Ros2FieldType::String),
+            field("voltage", Ros2FieldType::Float64),
+        ];
+        let mut bytes = vec![1, 0, 0, 0]; // bool + padding to 4
+        bytes.extend_from_slice(&3u32.to_le_bytes());
+        bytes.extend_from_slice(b"ab\0");
+        bytes.extend_from_slice(&[0; 5]); // padding to 16
+        bytes.extend_from_slice(&12.5f64.to_le_bytes());
+
+        let values: Vec<NnSqlValue> = decode_cdr(&layout, &bytes, true)
+            .unwrap()
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                NnSqlValue::Boolean(true),
+                NnSqlValue::Text("ab".to_string()),
+                NnSqlValue::Float(OrderedFloat(12.5)),
+            ]
+        );
+
+        assert!(decode_cdr(&layout, &bytes[..16], true).is_err());
+    }
+}
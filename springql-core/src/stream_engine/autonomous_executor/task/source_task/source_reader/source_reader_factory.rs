@@ -1,33 +1,158 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use anyhow::anyhow;
+
+use crate::api::error::SpringError;
+#[cfg(feature = "source-can")]
+use crate::stream_engine::autonomous_executor::task::source_task::source_reader::can::CANSourceReader;
+#[cfg(feature = "source-kafka")]
+use crate::stream_engine::autonomous_executor::task::source_task::source_reader::kafka::KafkaSourceReader;
+#[cfg(feature = "mqtt")]
+use crate::stream_engine::autonomous_executor::task::source_task::source_reader::mqtt::MqttSourceReader;
+#[cfg(feature = "nats-messaging")]
+use crate::stream_engine::autonomous_executor::task::source_task::source_reader::nats::NatsSourceReader;
+#[cfg(feature = "source-opc-ua")]
+use crate::stream_engine::autonomous_executor::task::source_task::source_reader::opc_ua::OpcUaSourceReader;
+#[cfg(feature = "redis-stream")]
+use crate::stream_engine::autonomous_executor::task::source_task::source_reader::redis_stream::RedisStreamSourceReader;
+#[cfg(feature = "source-ros2")]
+use crate::stream_engine::autonomous_executor::task::source_task::source_reader::ros2::Ros2SourceReader;
+#[cfg(unix)]
+use crate::stream_engine::autonomous_executor::task::source_task::source_reader::unix_socket::UnixSocketSourceReader;
+#[cfg(feature = "zeromq")]
+use crate::stream_engine::autonomous_executor::task::source_task::source_reader::zeromq::ZeroMqSourceReader;
 use crate::{
     api::{error::Result, SpringSourceReaderConfig},
     pipeline::{Options, SourceReaderType},
     stream_engine::autonomous_executor::task::source_task::source_reader::{
-        can::CANSourceReader, net_client::NetClientSourceReader, net_server::NetServerSourceReader,
-        InMemoryQueueSourceReader, SourceReader,
+        backfill::BackfillSourceReader, file::FileSourceReader, modbus::ModbusSourceReader,
+        net_client::NetClientSourceReader, net_http::NetHttpSourceReader,
+        net_server::NetServerSourceReader, springql_link::SpringqlLinkSourceReader,
+        udp::UdpSourceReader, InMemoryQueueSourceReader, SourceReader,
     },
 };
 
 pub struct SourceReaderFactory;
 
 impl SourceReaderFactory {
+    /// # Failure
+    ///
+    /// - `SpringError::Unavailable` when:
+    ///   - the cargo feature for `source_reader_type` is disabled.
+    ///   - `source_reader_type` is not supported on this platform.
+    ///   - `source_reader_type` is `SourceReaderType::System`.
+    /// - Raises Err from `SpringSourceReaderConfig::overridden_by()`, `SourceReader::start()` and `BackfillSourceReader::wrap()` as-is.
     pub fn source(
         source_reader_type: &SourceReaderType,
         options: &Options,
         config: &SpringSourceReaderConfig,
     ) -> Result<Box<dyn SourceReader>> {
-        match source_reader_type {
-            SourceReaderType::NetClient => {
-                Ok(Box::new(NetClientSourceReader::start(options, config)?))
+        let config = &config.overridden_by(options)?;
+        let live: Box<dyn SourceReader> = match source_reader_type {
+            SourceReaderType::NetClient => Box::new(NetClientSourceReader::start(options, config)?),
+            SourceReaderType::NetServer => Box::new(NetServerSourceReader::start(options, config)?),
+            SourceReaderType::NetHttp => Box::new(NetHttpSourceReader::start(options, config)?),
+            #[cfg(feature = "mqtt")]
+            SourceReaderType::NetMqtt => Box::new(MqttSourceReader::start(options, config)?),
+            #[cfg(not(feature = "mqtt"))]
+            SourceReaderType::NetMqtt => {
+                return Err(SpringError::Unavailable {
+                    resource: "NET_MQTT source reader".to_string(),
+                    source: anyhow!("springql-core is built without `mqtt` feature"),
+                })
             }
-            SourceReaderType::NetServer => {
-                Ok(Box::new(NetServerSourceReader::start(options, config)?))
+            #[cfg(feature = "source-can")]
+            SourceReaderType::CAN => Box::new(CANSourceReader::start(options, config)?),
+            #[cfg(not(feature = "source-can"))]
+            SourceReaderType::CAN => {
+                return Err(SpringError::Unavailable {
+                    resource: "CAN source reader".to_string(),
+                    source: anyhow!("springql-core is built without `source-can` feature"),
+                })
             }
-            SourceReaderType::CAN => Ok(Box::new(CANSourceReader::start(options, config)?)),
             SourceReaderType::InMemoryQueue => {
-                Ok(Box::new(InMemoryQueueSourceReader::start(options, config)?))
+                Box::new(InMemoryQueueSourceReader::start(options, config)?)
+            }
+            SourceReaderType::ModbusTcp => Box::new(ModbusSourceReader::start(options, config)?),
+            #[cfg(feature = "source-opc-ua")]
+            SourceReaderType::OpcUa => Box::new(OpcUaSourceReader::start(options, config)?),
+            #[cfg(not(feature = "source-opc-ua"))]
+            SourceReaderType::OpcUa => {
+                return Err(SpringError::Unavailable {
+                    resource: "OPC_UA source reader".to_string(),
+                    source: anyhow!("springql-core is built without `source-opc-ua` feature"),
+                })
+            }
+            #[cfg(feature = "redis-stream")]
+            SourceReaderType::RedisStream => {
+                Box::new(RedisStreamSourceReader::start(options, config)?)
+            }
+            #[cfg(not(feature = "redis-stream"))]
+            SourceReaderType::RedisStream => {
+                return Err(SpringError::Unavailable {
+                    resource: "REDIS_STREAM source reader".to_string(),
+                    source: anyhow!("springql-core is built without `redis-stream` feature"),
+                })
+            }
+            #[cfg(feature = "nats-messaging")]
+            SourceReaderType::Nats => Box::new(NatsSourceReader::start(options, config)?),
+            #[cfg(not(feature = "nats-messaging"))]
+            SourceReaderType::Nats => {
+                return Err(SpringError::Unavailable {
+                    resource: "NATS source reader".to_string(),
+                    source: anyhow!("springql-core is built without `nats-messaging` feature"),
+                })
+            }
+            #[cfg(feature = "source-kafka")]
+            SourceReaderType::Kafka => Box::new(KafkaSourceReader::start(options, config)?),
+            #[cfg(not(feature = "source-kafka"))]
+            SourceReaderType::Kafka => {
+                return Err(SpringError::Unavailable {
+                    resource: "KAFKA source reader".to_string(),
+                    source: anyhow!("springql-core is built without `source-kafka` feature"),
+                })
+            }
+            #[cfg(feature = "zeromq")]
+            SourceReaderType::ZeroMq => Box::new(ZeroMqSourceReader::start(options, config)?),
+            #[cfg(not(feature = "zeromq"))]
+            SourceReaderType::ZeroMq => {
+                return Err(SpringError::Unavailable {
+                    resource: "ZEROMQ source reader".to_string(),
+                    source: anyhow!("springql-core is built without `zeromq` feature"),
+                })
+            }
+            #[cfg(feature = "source-ros2")]
+            SourceReaderType::Ros2 => Box::new(Ros2SourceReader::start(options, config)?),
+            #[cfg(not(feature = "source-ros2"))]
+            SourceReaderType::Ros2 => {
+                return Err(SpringError::Unavailable {
+                    resource: "ROS2 source reader".to_string(),
+                    source: anyhow!("springql-core is built without `source-ros2` feature"),
+                })
+            }
+            SourceReaderType::SpringqlLink => {
+                Box::new(SpringqlLinkSourceReader::start(options, config)?)
+            }
+            SourceReaderType::File => Box::new(FileSourceReader::start(options, config)?),
+            #[cfg(unix)]
+            SourceReaderType::UnixSocket => {
+                Box::new(UnixSocketSourceReader::start(options, config)?)
+            }
+            #[cfg(not(unix))]
+            SourceReaderType::UnixSocket => {
+                return Err(SpringError::Unavailable {
+                    resource: "UNIX_SOCKET source reader".to_string(),
+                    source: anyhow!("UNIX domain sockets are not available on this platform"),
+                })
+            }
+            SourceReaderType::Udp => Box::new(UdpSourceReader::start(options, config)?),
+            SourceReaderType::System(_) => {
+                return Err(SpringError::Unavailable {
+                    resource: "system source reader".to_string(),
+                    source: anyhow!("system source reader is created by SourceReaderRepository"),
+                })
             }
-        }
+        };
+        BackfillSourceReader::wrap(live, options)
     }
 }
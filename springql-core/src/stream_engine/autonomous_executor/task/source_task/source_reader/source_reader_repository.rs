@@ -1,7 +1,7 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     sync::{Arc, Mutex},
 };
 
@@ -9,9 +9,12 @@ use parking_lot::RwLock;
 
 use crate::{
     api::{error::Result, SpringSourceReaderConfig},
-    pipeline::{SourceReaderModel, SourceReaderName},
-    stream_engine::autonomous_executor::task::source_task::source_reader::{
-        source_reader_factory::SourceReaderFactory, SourceReader,
+    pipeline::{SourceReaderModel, SourceReaderName, SourceReaderType},
+    stream_engine::autonomous_executor::{
+        system_stream_repository::SystemStreamRepository,
+        task::source_task::source_reader::{
+            source_reader_factory::SourceReaderFactory, SourceReader, SystemSourceReader,
+        },
     },
 };
 
@@ -19,14 +22,19 @@ use crate::{
 #[derive(Debug)]
 pub struct SourceReaderRepository {
     config: SpringSourceReaderConfig,
+    system_stream_repo: Arc<SystemStreamRepository>,
 
     sources: RwLock<HashMap<SourceReaderName, Arc<Mutex<Box<dyn SourceReader>>>>>,
 }
 
 impl SourceReaderRepository {
-    pub fn new(config: SpringSourceReaderConfig) -> Self {
+    pub fn new(
+        config: SpringSourceReaderConfig,
+        system_stream_repo: Arc<SystemStreamRepository>,
+    ) -> Self {
         Self {
             config,
+            system_stream_repo,
             sources: RwLock::default(),
         }
     }
@@ -43,12 +51,18 @@ impl SourceReaderRepository {
         if sources.get(source_reader.name()).is_some() {
             Ok(())
         } else {
-            let subtask = SourceReaderFactory::source(
-                source_reader.source_reader_type(),
-                source_reader.options(),
-                &self.config,
-            )?;
-            let subtask = Arc::new(Mutex::new(subtask as Box<dyn SourceReader>));
+            let subtask: Box<dyn SourceReader> = match source_reader.source_reader_type() {
+                SourceReaderType::System(system_stream) => {
+                    let queue = self.system_stream_repo.open(*system_stream);
+                    Box::new(SystemSourceReader::new(*system_stream, queue))
+                }
+                source_reader_type => SourceReaderFactory::source(
+                    source_reader_type,
+                    source_reader.options(),
+                    &self.config,
+                )?,
+            };
+            let subtask = Arc::new(Mutex::new(subtask));
             let _ = sources.insert(source_reader.name().clone(), subtask);
             log::debug!(
                 "[SourceReaderRepository] registered source subtask: {}",
@@ -58,6 +72,20 @@ impl SourceReaderRepository {
         }
     }
 
+    /// Drops source readers not in `names` (dropped from the pipeline), closing their connections.
+    pub fn retain(&self, names: &HashSet<&SourceReaderName>) {
+        self.sources.write().retain(|name, _| {
+            let retained = names.contains(name);
+            if !retained {
+                log::debug!(
+                    "[SourceReaderRepository] unregistered source subtask: {}",
+                    name
+                );
+            }
+            retained
+        });
+    }
+
     /// # Panics
     ///
     /// `name` is not registered yet
@@ -0,0 +1,51 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use crate::{
+    api::{
+        error::{Result, SpringError},
+        SpringSourceReaderConfig,
+    },
+    pipeline::{Options, SourceReaderType},
+    stream_engine::autonomous_executor::{
+        task::source_task::source_reader::source_reader_factory::SourceReaderFactory, SourceRow,
+    },
+};
+
+/// Takes sample rows from a foreign source without making a source task.
+#[derive(Debug)]
+pub struct SourceSampler;
+
+impl SourceSampler {
+    /// Starts a source reader and reads rows until `n_samples` rows are collected or the source stops providing rows.
+    ///
+    /// Rows are returned as read (e.g. with raw JSON numbers), for schema inference.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to start a source reader.
+    /// - `SpringError::InvalidOption` when:
+    ///   - `options` are invalid for `source_reader_type`.
+    /// - `SpringError::ForeignSourceTimeout` when:
+    ///   - the source does not provide any row within timeout.
+    pub fn sample(
+        source_reader_type: &SourceReaderType,
+        options: &Options,
+        config: &SpringSourceReaderConfig,
+        n_samples: usize,
+    ) -> Result<Vec<SourceRow>> {
+        let mut source_reader = SourceReaderFactory::source(source_reader_type, options, config)?;
+
+        let mut rows = Vec::with_capacity(n_samples);
+        while rows.len() < n_samples {
+            match source_reader.next_row() {
+                Ok(source_row) => rows.push(source_row),
+                Err(SpringError::ForeignSourceTimeout { .. }) if !rows.is_empty() => break,
+                Err(e) => return Err(e),
+            }
+        }
+
+        log::info!("[SourceSampler] sampled {} rows", rows.len());
+        Ok(rows)
+    }
+}
@@ -0,0 +1,195 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    io::BufReader,
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{mpsc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use anyhow::Context;
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSourceReaderConfig,
+    pipeline::{Options, SpringqlLinkSourceOptions},
+    stream_engine::autonomous_executor::{
+        row::{LinkDecoder, SchemalessRow, SourceRow},
+        task::source_task::source_reader::SourceReader,
+    },
+};
+
+/// Accepts connections from `SPRINGQL_LINK` sink writers of other SpringQL instances and
+/// reads rows in the binary encoding of `LinkDecoder`.
+///
+/// A connection sending a broken frame is closed.
+#[derive(Debug)]
+pub struct SpringqlLinkSourceReader {
+    my_addr: SocketAddr,
+    timeout: Duration,
+
+    rx: Mutex<mpsc::Receiver<SchemalessRow>>,
+}
+
+impl SourceReader for SpringqlLinkSourceReader {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to listen on `LISTEN_HOST` and `PORT`.
+    /// - `SpringError::InvalidOption`
+    fn start(options: &Options, config: &SpringSourceReaderConfig) -> Result<Self> {
+        let options = SpringqlLinkSourceOptions::try_from(options)?;
+        let listen_addr = SocketAddr::new(options.listen_host, options.port);
+
+        let listener = TcpListener::bind(listen_addr)
+            .context("failed to listen")
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::GenericTcp(listen_addr),
+            })?;
+        let my_addr = listener.local_addr().unwrap_or(listen_addr);
+
+        let (tx, rx) = mpsc::channel();
+        let _ = thread::Builder::new()
+            .name("SpringqlLinkSourceReader".into())
+            .spawn(move || {
+                for stream in listener.incoming() {
+                    match stream {
+                        Ok(stream) => {
+                            let tx = tx.clone();
+                            let _ = thread::Builder::new()
+                                .name("SpringqlLinkConnection".into())
+                                .spawn(move || Self::stream_handler(stream, tx));
+                        }
+                        Err(e) => log::warn!(
+                            "[SpringqlLinkSourceReader] Failed to accept a connection: {:?}",
+                            e
+                        ),
+                    }
+                }
+            });
+
+        log::info!(
+            "[SpringqlLinkSourceReader] Ready to accept rows at {}",
+            my_addr
+        );
+
+        Ok(Self {
+            my_addr,
+            timeout: Duration::from_millis(config.net_read_timeout_msec as u64),
+            rx: Mutex::new(rx),
+        })
+    }
+
+    fn next_row(&mut self) -> Result<SourceRow> {
+        let rx = self.rx.lock().expect("failed to lock mutex");
+        let row = rx
+            .recv_timeout(self.timeout)
+            .map_err(|e| SpringError::ForeignSourceTimeout {
+                source: anyhow::Error::from(e),
+                foreign_info: ForeignInfo::GenericTcp(self.my_addr),
+            })?;
+        Ok(SourceRow::Raw(row))
+    }
+}
+
+impl SpringqlLinkSourceReader {
+    fn stream_handler(stream: TcpStream, tx: mpsc::Sender<SchemalessRow>) {
+        let peer_addr = stream.peer_addr().ok();
+        log::info!("[SpringqlLinkSourceReader] Connection from {:?}", peer_addr);
+
+        let mut reader = BufReader::new(stream);
+        let mut decoder = LinkDecoder::default();
+        loop {
+            let frame = match LinkDecoder::read_frame(&mut reader) {
+                Ok(Some(frame)) => frame,
+                Ok(None) => {
+                    log::info!("[SpringqlLinkSourceReader] {:?} closed", peer_addr);
+                    return;
+                }
+                Err(e) => {
+                    log::warn!(
+                        "[SpringqlLinkSourceReader] Failed to read from {:?}: {:?}",
+                        peer_addr,
+                        e
+                    );
+                    return;
+                }
+            };
+
+            match decoder.decode(&frame) {
+                Ok(Some(row)) => {
+                    if tx.send(row).is_err() {
+                        // source reader is dropped
+                        return;
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    log::warn!(
+                        "[SpringqlLinkSourceReader] Closing {:?} sending a broken frame: {:?}",
+                        peer_addr,
+                        e
+                    );
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::SpringSinkWriterConfig,
+        pipeline::OptionsBuilder,
+        stream_engine::autonomous_executor::task::sink_task::{SinkWriter, SpringqlLinkSinkWriter},
+    };
+
+    #[test]
+    fn test_springql_link() -> crate::api::error::Result<()> {
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let options = OptionsBuilder::default()
+            .add("LISTEN_HOST", "127.0.0.1")
+            .add("PORT", port.to_string())
+            .build();
+        let mut reader =
+            SpringqlLinkSourceReader::start(&options, &SpringSourceReaderConfig::fx_default())?;
+
+        let options = OptionsBuilder::default()
+            .add("REMOTE_HOST", "127.0.0.1")
+            .add("REMOTE_PORT", port.to_string())
+            .build();
+        let mut writer =
+            SpringqlLinkSinkWriter::start(&options, &SpringSinkWriterConfig::fx_default())?;
+
+        writer.send_row(SchemalessRow::fx_city_temperature_tokyo())?;
+        writer.send_row(SchemalessRow::fx_city_temperature_osaka())?;
+        writer.send_row(SchemalessRow::fx_trade_oracle())?;
+
+        assert_eq!(
+            reader.next_row()?,
+            SourceRow::Raw(SchemalessRow::fx_city_temperature_tokyo())
+        );
+        assert_eq!(
+            reader.next_row()?,
+            SourceRow::Raw(SchemalessRow::fx_city_temperature_osaka())
+        );
+        assert_eq!(
+            reader.next_row()?,
+            SourceRow::Raw(SchemalessRow::fx_trade_oracle())
+        );
+        assert!(matches!(
+            reader.next_row().unwrap_err(),
+            SpringError::ForeignSourceTimeout { .. }
+        ));
+
+        Ok(())
+    }
+}
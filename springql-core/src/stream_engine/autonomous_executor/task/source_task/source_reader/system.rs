@@ -0,0 +1,60 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSourceReaderConfig,
+    pipeline::{Options, SystemStream},
+    stream_engine::autonomous_executor::{
+        row::SourceRow, system_stream_repository::SystemStreamQueue,
+        task::source_task::source_reader::SourceReader,
+    },
+};
+
+/// Reads rows the stream engine puts into a system stream.
+#[derive(Debug)]
+pub struct SystemSourceReader {
+    system_stream: SystemStream,
+    queue: Arc<SystemStreamQueue>,
+}
+
+impl SystemSourceReader {
+    /// `queue` is opened by `SystemStreamRepository::open()`.
+    pub fn new(system_stream: SystemStream, queue: Arc<SystemStreamQueue>) -> Self {
+        Self {
+            system_stream,
+            queue,
+        }
+    }
+}
+
+impl SourceReader for SystemSourceReader {
+    /// System source reader is created by `SourceReaderRepository` when a system stream is created.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Unavailable` always.
+    fn start(_options: &Options, _config: &SpringSourceReaderConfig) -> Result<Self> {
+        Err(SpringError::Unavailable {
+            resource: "system source reader".to_string(),
+            source: anyhow!("system source reader cannot be created by DDL"),
+        })
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignSourceTimeout` when:
+    ///   - system stream does not have any row (does not wait a bit)
+    fn next_row(&mut self) -> Result<SourceRow> {
+        self.queue
+            .pop_non_blocking()
+            .map(SourceRow::Raw)
+            .ok_or_else(|| SpringError::ForeignSourceTimeout {
+                source: anyhow!("system stream is empty"),
+                foreign_info: ForeignInfo::SystemStream(self.system_stream.name()),
+            })
+    }
+}
@@ -0,0 +1,221 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    collections::VecDeque,
+    io,
+    net::{SocketAddr, UdpSocket},
+    time::Duration,
+};
+
+use anyhow::{anyhow, Context};
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSourceReaderConfig,
+    pipeline::{Options, SourceFormatOptions, UdpSourceOptions},
+    stream_engine::autonomous_executor::{
+        row::{PayloadDecoder, SourceRow},
+        task::source_task::source_reader::SourceReader,
+    },
+};
+
+/// Receives a row (or rows in a JSON array) per UDP datagram.
+///
+/// UDP is lossy: datagrams lost on the network or dropped by a full socket buffer are not detected.
+/// In `FORMAT 'CSV'`, each datagram has its own header unless `CSV_HEADER 'false'`.
+#[derive(Debug)]
+pub struct UdpSourceReader {
+    socket: UdpSocket,
+    local_addr: SocketAddr,
+    max_datagram_size: usize,
+
+    decoder: PayloadDecoder,
+
+    /// One byte longer than `max_datagram_size` to detect truncated datagrams.
+    buf: Vec<u8>,
+    /// Rows expanded from a datagram but not returned yet.
+    pending_rows: VecDeque<SourceRow>,
+}
+
+impl SourceReader for UdpSourceReader {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to bind the socket.
+    /// - `SpringError::InvalidOption`
+    fn start(options: &Options, config: &SpringSourceReaderConfig) -> Result<Self> {
+        let format_options = SourceFormatOptions::try_from(options)?;
+        let options = UdpSourceOptions::try_from(options)?;
+        let decoder = PayloadDecoder::new(&format_options, options.json_records_path.as_ref())?;
+
+        let bind_addr = SocketAddr::new(options.bind_host, options.port);
+        let socket = UdpSocket::bind(bind_addr)
+            .and_then(|socket| {
+                socket.set_read_timeout(Some(Duration::from_millis(
+                    config.net_read_timeout_msec as u64,
+                )))?;
+                Ok(socket)
+            })
+            .context("failed to bind UDP socket")
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::GenericUdp(bind_addr),
+            })?;
+        let local_addr = socket.local_addr().unwrap_or(bind_addr);
+
+        log::info!(
+            "[UdpSourceReader] Ready to receive datagrams at {}",
+            local_addr
+        );
+
+        Ok(Self {
+            socket,
+            local_addr,
+            max_datagram_size: options.max_datagram_size,
+            decoder,
+            buf: vec![0; options.max_datagram_size + 1],
+            pending_rows: VecDeque::new(),
+        })
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignSourceTimeout` when:
+    ///   - no datagram is received within the read timeout.
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to receive from the socket.
+    /// - `SpringError::InvalidFormat` when:
+    ///   - a datagram is larger than `MAX_DATAGRAM_SIZE` or malformed in the format. The datagram is dropped.
+    fn next_row(&mut self) -> Result<SourceRow> {
+        loop {
+            if let Some(row) = self.pending_rows.pop_front() {
+                return Ok(row);
+            }
+
+            let n = self.recv()?;
+            if n > self.max_datagram_size {
+                return Err(SpringError::InvalidFormat {
+                    source: anyhow!(
+                        "datagram larger than MAX_DATAGRAM_SIZE ({} bytes)",
+                        self.max_datagram_size
+                    ),
+                    s: String::from_utf8_lossy(&self.buf[..self.max_datagram_size]).to_string(),
+                });
+            }
+            let rows = self.decoder.decode(&self.buf[..n])?;
+            self.pending_rows.extend(rows);
+        }
+    }
+}
+
+impl UdpSourceReader {
+    /// # Returns
+    ///
+    /// Size of the received datagram, truncated to `self.buf.len()`.
+    fn recv(&mut self) -> Result<usize> {
+        self.socket
+            .recv_from(&mut self.buf)
+            .map(|(n, _)| n)
+            .map_err(|io_err| {
+                if let io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock = io_err.kind() {
+                    SpringError::ForeignSourceTimeout {
+                        source: anyhow::Error::from(io_err),
+                        foreign_info: ForeignInfo::GenericUdp(self.local_addr),
+                    }
+                } else {
+                    SpringError::ForeignIo {
+                        source: anyhow::Error::from(io_err),
+                        foreign_info: ForeignInfo::GenericUdp(self.local_addr),
+                    }
+                }
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pipeline::OptionsBuilder,
+        stream_engine::autonomous_executor::row::{JsonObject, JsonSourceRow},
+    };
+
+    fn start(options: OptionsBuilder) -> (UdpSourceReader, UdpSocket) {
+        let options = options
+            .add("BIND_HOST", "127.0.0.1")
+            .add("PORT", "0")
+            .build();
+        let reader =
+            UdpSourceReader::start(&options, &SpringSourceReaderConfig::fx_default()).unwrap();
+
+        let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+        sender.connect(reader.local_addr).unwrap();
+        (reader, sender)
+    }
+
+    #[test]
+    fn test_source_udp() -> Result<()> {
+        let j1 = JsonObject::fx_city_temperature_tokyo();
+        let j2 = JsonObject::fx_city_temperature_osaka();
+        let j3 = JsonObject::fx_city_temperature_london();
+
+        let options = OptionsBuilder::default().add("MAX_DATAGRAM_SIZE", "256");
+        let (mut reader, sender) = start(options);
+
+        sender.send(j1.to_string().as_bytes()).unwrap();
+        sender
+            .send(format!("[{}, {}]", j2.to_string(), j3.to_string()).as_bytes())
+            .unwrap();
+        sender.send(&[b' '; 257]).unwrap();
+        sender.send(j1.to_string().as_bytes()).unwrap();
+
+        assert_eq!(
+            reader.next_row()?,
+            SourceRow::Json(JsonSourceRow::from_json(j1.clone()))
+        );
+        assert_eq!(
+            reader.next_row()?,
+            SourceRow::Json(JsonSourceRow::from_json(j2))
+        );
+        assert_eq!(
+            reader.next_row()?,
+            SourceRow::Json(JsonSourceRow::from_json(j3))
+        );
+        assert!(matches!(
+            reader.next_row().unwrap_err(),
+            SpringError::InvalidFormat { .. }
+        ));
+        assert_eq!(
+            reader.next_row()?,
+            SourceRow::Json(JsonSourceRow::from_json(j1))
+        );
+        assert!(matches!(
+            reader.next_row().unwrap_err(),
+            SpringError::ForeignSourceTimeout { .. }
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_source_udp_csv() -> Result<()> {
+        let (mut reader, sender) = start(
+            OptionsBuilder::default()
+                .add("FORMAT", "CSV")
+                .add("CSV_COLUMNS", "ts,city,temperature")
+                .add("CSV_HEADER", "false"),
+        );
+
+        sender
+            .send(b"2020-01-01 00:00:00.000000000,Tokyo,21")
+            .unwrap();
+
+        assert!(matches!(reader.next_row()?, SourceRow::Raw(_)));
+        assert!(matches!(
+            reader.next_row().unwrap_err(),
+            SpringError::ForeignSourceTimeout { .. }
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{os::unix::net::UnixStream, time::Duration};
+
+use anyhow::Context;
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSourceReaderConfig,
+    pipeline::{Options, SourceFormatOptions, UnixSocketOptions},
+    stream_engine::autonomous_executor::{
+        row::SourceRow,
+        task::source_task::source_reader::{net_client::LineSourceReader, SourceReader},
+    },
+};
+
+/// Connects to a UNIX domain socket at `PATH` and reads a row (or rows in a JSON array) per line, like `NET_CLIENT`.
+#[derive(Debug)]
+pub struct UnixSocketSourceReader {
+    line_reader: LineSourceReader<UnixStream>,
+}
+
+impl SourceReader for UnixSocketSourceReader {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to connect to the socket.
+    /// - `SpringError::InvalidOption`
+    fn start(options: &Options, config: &SpringSourceReaderConfig) -> Result<Self> {
+        let format_options = SourceFormatOptions::try_from(options)?;
+        format_options.ensure_text("UNIX_SOCKET")?;
+        let options = UnixSocketOptions::try_from(options)?;
+
+        let foreign_io = |e: anyhow::Error| SpringError::ForeignIo {
+            source: e,
+            foreign_info: ForeignInfo::UnixSocket(options.path.clone()),
+        };
+        let stream = UnixStream::connect(&options.path)
+            .context("failed to connect to UNIX domain socket")
+            .map_err(foreign_io)?;
+        stream
+            .set_read_timeout(Some(Duration::from_millis(
+                config.net_read_timeout_msec as u64,
+            )))
+            .context("failed to set timeout to UNIX domain socket")
+            .map_err(foreign_io)?;
+
+        log::info!(
+            "[UnixSocketSourceReader] Ready to read from {:?}",
+            options.path
+        );
+
+        Ok(Self {
+            line_reader: LineSourceReader::new(
+                stream,
+                ForeignInfo::UnixSocket(options.path),
+                &format_options,
+                options.json_records_path,
+            ),
+        })
+    }
+
+    fn next_row(&mut self) -> Result<SourceRow> {
+        self.line_reader.next_row()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Write, os::unix::net::UnixListener, thread};
+
+    use super::*;
+    use crate::{
+        pipeline::OptionsBuilder,
+        stream_engine::autonomous_executor::row::{JsonObject, JsonSourceRow},
+    };
+
+    #[test]
+    fn test_source_unix_socket() -> Result<()> {
+        let j1 = JsonObject::fx_city_temperature_tokyo();
+        let j2 = JsonObject::fx_city_temperature_osaka();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("source.sock");
+        let listener = UnixListener::bind(&path).unwrap();
+
+        let payload = format!("{}\n{}\n", j1.to_string(), j2.to_string());
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            stream.write_all(payload.as_bytes()).unwrap();
+            stream
+        });
+
+        let options = OptionsBuilder::default()
+            .add("PATH", path.display().to_string())
+            .build();
+        let mut subtask =
+            UnixSocketSourceReader::start(&options, &SpringSourceReaderConfig::fx_default())?;
+        let _stream = server.join().unwrap();
+
+        assert_eq!(
+            subtask.next_row()?,
+            SourceRow::Json(JsonSourceRow::from_json(j1))
+        );
+        assert_eq!(
+            subtask.next_row()?,
+            SourceRow::Json(JsonSourceRow::from_json(j2))
+        );
+        assert!(matches!(
+            subtask.next_row().unwrap_err(),
+            SpringError::ForeignSourceTimeout { .. }
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_source_unix_socket_not_listening() {
+        let dir = tempfile::tempdir().unwrap();
+        let options = OptionsBuilder::default()
+            .add(
+                "PATH",
+                dir.path().join("missing.sock").display().to_string(),
+            )
+            .build();
+
+        assert!(matches!(
+            UnixSocketSourceReader::start(&options, &SpringSourceReaderConfig::fx_default())
+                .unwrap_err(),
+            SpringError::ForeignIo { .. }
+        ));
+    }
+}
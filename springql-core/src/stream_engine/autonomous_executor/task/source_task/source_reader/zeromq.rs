@@ -0,0 +1,164 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{collections::VecDeque, fmt::Debug, sync::Mutex};
+
+use anyhow::{anyhow, Context};
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSourceReaderConfig,
+    pipeline::{Options, SourceMetadataOptions, ZeroMqEndpoint, ZeroMqSourceOptions},
+    stream_engine::autonomous_executor::{
+        row::{SourceMetadata, SourceRow},
+        task::source_task::source_reader::SourceReader,
+    },
+};
+
+/// Receives messages with a SUB socket.
+///
+/// A message is either a single JSON frame or multipart `[topic, JSON]`. The topic frame is provided as `topic` metadata.
+pub struct ZeroMqSourceReader {
+    options: ZeroMqSourceOptions,
+    metadata_options: SourceMetadataOptions,
+
+    /// `zmq::Socket` is not Sync.
+    socket: Mutex<zmq::Socket>,
+
+    /// Rows parsed but not returned yet.
+    pending_rows: VecDeque<SourceRow>,
+}
+
+impl SourceReader for ZeroMqSourceReader {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to create, connect or bind the socket.
+    /// - `SpringError::InvalidOption`
+    fn start(options: &Options, config: &SpringSourceReaderConfig) -> Result<Self> {
+        let metadata_options = SourceMetadataOptions::try_from(options)?;
+        let options = ZeroMqSourceOptions::try_from(options)?;
+
+        let socket = open_socket(&options, config.net_read_timeout_msec as i32)
+            .context("failed to open SUB socket")
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::ZeroMq(format!("{:?}", options.endpoint)),
+            })?;
+
+        log::info!(
+            "[ZeroMqSourceReader] Ready to read topics {:?} from {:?}",
+            options.topics,
+            options.endpoint
+        );
+
+        Ok(Self {
+            options,
+            metadata_options,
+            socket: Mutex::new(socket),
+            pending_rows: VecDeque::new(),
+        })
+    }
+
+    fn next_row(&mut self) -> Result<SourceRow> {
+        loop {
+            if let Some(row) = self.pending_rows.pop_front() {
+                return Ok(row);
+            }
+
+            let frames = self
+                .socket
+                .lock()
+                .expect("ZeroMQ socket mutex poisoned")
+                .recv_multipart(0)
+                .map_err(|e| match e {
+                    zmq::Error::EAGAIN => SpringError::ForeignSourceTimeout {
+                        source: anyhow!("no message within the read timeout"),
+                        foreign_info: self.foreign_info(),
+                    },
+                    _ => SpringError::ForeignIo {
+                        source: anyhow::Error::from(e).context("failed to receive message"),
+                        foreign_info: self.foreign_info(),
+                    },
+                })?;
+            let rows = self.frames_into_rows(frames)?;
+            self.pending_rows.extend(rows);
+        }
+    }
+}
+
+impl ZeroMqSourceReader {
+    fn foreign_info(&self) -> ForeignInfo {
+        ForeignInfo::ZeroMq(format!("{:?}", self.options.endpoint))
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidFormat` when:
+    ///   - the payload frame is not a JSON row.
+    fn frames_into_rows(&self, mut frames: Vec<Vec<u8>>) -> Result<Vec<SourceRow>> {
+        let payload = frames.pop().unwrap_or_default();
+        let topic = frames
+            .first()
+            .map(|topic| String::from_utf8_lossy(topic).to_string());
+
+        let json = std::str::from_utf8(&payload)
+            .context("message is not UTF-8")
+            .map_err(|e| SpringError::InvalidFormat {
+                source: e,
+                s: String::from_utf8_lossy(&payload).to_string(),
+            })?;
+
+        let metadata = SourceMetadata {
+            topic,
+            ..Default::default()
+        };
+        SourceRow::from_json_records(json, self.options.json_records_path.as_ref())?
+            .into_iter()
+            .map(|row| row.with_metadata(&metadata, &self.metadata_options))
+            .collect()
+    }
+}
+
+impl Debug for ZeroMqSourceReader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ZeroMqSourceReader")
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+fn open_socket(
+    options: &ZeroMqSourceOptions,
+    read_timeout_msec: i32,
+) -> anyhow::Result<zmq::Socket> {
+    let socket_options = &options.socket_options;
+
+    let socket = zmq::Context::new().socket(zmq::SUB)?;
+    socket.set_rcvtimeo(read_timeout_msec)?;
+    if let Some(hwm) = socket_options.hwm {
+        socket.set_rcvhwm(hwm)?;
+    }
+    if let Some(reconnect_interval) = socket_options.reconnect_interval {
+        socket.set_reconnect_ivl(reconnect_interval.as_millis() as i32)?;
+    }
+    if let Some(linger) = socket_options.linger {
+        socket.set_linger(linger.as_millis() as i32)?;
+    }
+    for topic in &options.topics {
+        socket.set_subscribe(topic.as_bytes())?;
+    }
+
+    match &options.endpoint {
+        ZeroMqEndpoint::Connect(endpoints) => {
+            for endpoint in endpoints {
+                socket
+                    .connect(endpoint)
+                    .with_context(|| format!("failed to connect to {}", endpoint))?;
+            }
+        }
+        ZeroMqEndpoint::Bind(endpoint) => socket
+            .bind(endpoint)
+            .with_context(|| format!("failed to bind to {}", endpoint))?,
+    }
+    Ok(socket)
+}
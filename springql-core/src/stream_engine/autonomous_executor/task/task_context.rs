@@ -2,10 +2,13 @@
 
 use std::sync::Arc;
 
-use crate::stream_engine::autonomous_executor::{
-    pipeline_derivatives::PipelineDerivatives,
-    repositories::Repositories,
-    task_graph::{QueueId, TaskId},
+use crate::{
+    pipeline::StreamName,
+    stream_engine::autonomous_executor::{
+        pipeline_derivatives::PipelineDerivatives,
+        repositories::Repositories,
+        task_graph::{QueueId, TaskId},
+    },
 };
 
 /// Holds everything needed for a task execution.
@@ -45,6 +48,12 @@ impl TaskContext {
         task_graph.output_queues(&self.task)
     }
 
+    /// Output queues carrying rows of `stream`.
+    pub fn output_queues_of(&self, stream: &StreamName) -> Vec<QueueId> {
+        let task_graph = self.pipeline_derivatives.task_graph();
+        task_graph.output_queues_of(&self.task, stream)
+    }
+
     pub fn repos(&self) -> Arc<Repositories> {
         self.repos.clone()
     }
@@ -2,10 +2,11 @@
 
 use crate::{
     api::error::{Result, SpringError},
-    mem_size::MemSize,
+    mem_size::{vec_mem_size, MemSize},
     pipeline::{ColumnReference, Field},
     stream_engine::{
         autonomous_executor::row::{RowTime, StreamRow},
+        time::MIN_TIMESTAMP,
         NnSqlValue, SqlValue,
     },
 };
@@ -27,9 +28,7 @@ pub struct Tuple {
 
 impl MemSize for Tuple {
     fn mem_size(&self) -> usize {
-        let rowtime_size = self.rowtime.mem_size();
-        let fields_size: usize = self.fields.iter().map(|f| f.mem_size()).sum();
-        rowtime_size + fields_size
+        self.rowtime.mem_size() + vec_mem_size(self.fields.capacity(), self.fields.iter())
     }
 }
 
@@ -52,6 +51,19 @@ impl Tuple {
         Self { rowtime, fields }
     }
 
+    /// Tuple with NULL for each of `colrefs` (the null-extended side of an outer join).
+    pub fn new_nulls(colrefs: &[ColumnReference]) -> Self {
+        // unused
+        let rowtime = RowTime::EventTime(MIN_TIMESTAMP);
+
+        let fields = colrefs
+            .iter()
+            .map(|colref| Field::new(colref.clone(), SqlValue::Null))
+            .collect();
+
+        Self { rowtime, fields }
+    }
+
     pub fn rowtime(&self) -> RowTime {
         self.rowtime
     }
@@ -149,7 +161,7 @@ mod tests {
             TestDatum::new(
                 ValueExpr::factory_eq(ValueExpr::factory_null(), ValueExpr::factory_null()),
                 Tuple::fx_trade_oracle(),
-                SqlValue::factory_bool(false),
+                SqlValue::Null,
             ),
             TestDatum::new(
                 ValueExpr::factory_eq(
@@ -200,7 +212,11 @@ mod tests {
         for t in test_data {
             let expr_ph2 = t.in_expr.resolve_colref(&t.tuple).unwrap();
             let sql_value = expr_ph2.eval().unwrap();
-            assert_eq!(sql_value, t.expected_sql_value);
+            match t.expected_sql_value {
+                // NULL is not equal to NULL in `SqlValue::eq()`
+                SqlValue::Null => assert!(matches!(sql_value, SqlValue::Null)),
+                expected => assert_eq!(sql_value, expected),
+            }
         }
     }
 }
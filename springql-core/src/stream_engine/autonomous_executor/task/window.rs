@@ -1,14 +1,19 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 mod aggregate;
+mod interval_join;
 mod join_window;
 mod panes;
+mod slices;
 
 mod watermark;
 
 pub use aggregate::{AggrWindow, AggregatedAndGroupingValues};
+pub use interval_join::IntervalJoin;
 pub use join_window::JoinWindow;
-pub use panes::{AggrPane, GroupByValues, JoinDir, JoinPane, Pane, Panes};
+pub use panes::{
+    AggrPane, AggregateState, GroupByValues, JoinDir, JoinKeyValues, JoinPane, Pane, Panes,
+};
 
 use crate::{
     api::SpringError,
@@ -17,6 +22,7 @@ use crate::{
         autonomous_executor::{
             performance_metrics::WindowInFlowByWindowTask, task::window::watermark::Watermark,
         },
+        time::SpringTimestamp,
         Tuple,
     },
 };
@@ -34,7 +40,21 @@ pub trait Window {
 
     fn purge(&mut self);
 
+    /// Updates the watermark with a tuple dispatched with `arg`.
+    fn update_watermark(
+        &mut self,
+        rowtime: SpringTimestamp,
+        _arg: &<<Self as Window>::Pane as Pane>::DispatchArg,
+    ) {
+        self.watermark_mut().update(rowtime);
+    }
+
     /// A task dispatches a tuple from waiting queue.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - failed to evaluate expressions for `tuple`. Panes the watermark has passed are closed on the next dispatch.
     fn dispatch(
         &mut self,
         expr_resolver: &ExprResolver,
@@ -43,35 +63,81 @@ pub trait Window {
     ) -> Result<Success<<Self::Pane as Pane>::CloseOut>, SpringError> {
         let rowtime = tuple.rowtime().as_timestamp();
 
-        if rowtime < self.watermark().as_timestamp() {
+        let lateness = WindowInFlowByWindowTask {
+            late_rows: (rowtime < self.watermark().as_timestamp()) as u64,
+            max_disorder: self.watermark().disorder(rowtime),
+            ..WindowInFlowByWindowTask::zero()
+        };
+
+        if rowtime < self.watermark().lateness_limit() {
             // too late tuple does not have any chance to be dispatched nor to close a pane.
-            Ok((Vec::new(), WindowInFlowByWindowTask::zero()))
+            Ok((
+                Vec::new(),
+                WindowInFlowByWindowTask {
+                    late_rows: 0,
+                    dropped_late_rows: 1,
+                    ..lateness
+                },
+            ))
         } else {
-            self.watermark_mut().update(rowtime);
-            let wm = *self.watermark();
-
-            let window_in_flow_dispatch = self
-                .panes_mut()
-                .panes_to_dispatch(rowtime)?
-                .map(|pane| pane.dispatch(expr_resolver, &tuple, arg.clone()))
-                .fold(WindowInFlowByWindowTask::zero(), |acc, window_in_flow| {
-                    acc + window_in_flow
-                });
-
-            let (out, window_in_flow_close) = self
-                .panes_mut()
-                .remove_panes_to_close(&wm)
-                .into_iter()
-                .fold(
-                    (Vec::new(), WindowInFlowByWindowTask::zero()),
-                    |(mut out_acc, window_in_flow_acc), pane| {
-                        let (mut out_seq, window_in_flow) = pane.close(expr_resolver);
-                        out_acc.append(&mut out_seq);
-                        (out_acc, window_in_flow_acc + window_in_flow)
-                    },
-                );
-
-            Ok((out, window_in_flow_dispatch + window_in_flow_close))
+            self.update_watermark(rowtime, &arg);
+            let (out, window_in_flow) = self.dispatch_in_time(expr_resolver, tuple, arg)?;
+            Ok((out, window_in_flow + lateness))
         }
     }
+
+    /// Dispatches a tuple not too late (the watermark is already updated by it) into panes, and closes panes the watermark has passed.
+    ///
+    /// With `EMIT ON UPDATE`, current results updated by the tuple are output before results of closed panes (if any).
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - failed to evaluate expressions for `tuple`.
+    fn dispatch_in_time(
+        &mut self,
+        expr_resolver: &ExprResolver,
+        tuple: Tuple,
+        arg: <<Self as Window>::Pane as Pane>::DispatchArg,
+    ) -> Result<Success<<Self::Pane as Pane>::CloseOut>, SpringError> {
+        dispatch_to_panes(self, expr_resolver, tuple, arg)
+    }
+}
+
+/// Default implementation of `Window::dispatch_in_time()` with `Window::panes_mut()`.
+fn dispatch_to_panes<W>(
+    window: &mut W,
+    expr_resolver: &ExprResolver,
+    tuple: Tuple,
+    arg: <<W as Window>::Pane as Pane>::DispatchArg,
+) -> Result<Success<<W::Pane as Pane>::CloseOut>, SpringError>
+where
+    W: Window + ?Sized,
+{
+    let rowtime = tuple.rowtime().as_timestamp();
+    let wm = *window.watermark();
+
+    let emit_on_update = window.panes().window_param().emit_on_update();
+    let mut out = vec![];
+
+    let (window_in_flow_open, mut panes) = window.panes_mut().panes_to_dispatch(rowtime)?;
+    let window_in_flow_dispatch = panes.try_fold(window_in_flow_open, |acc, pane| {
+        let window_in_flow = pane.dispatch(expr_resolver, &tuple, arg.clone())?;
+        if emit_on_update {
+            out.append(&mut pane.updated(expr_resolver, &tuple)?);
+        }
+        Ok::<_, SpringError>(acc + window_in_flow)
+    })?;
+    let window_in_flow_update = WindowInFlowByWindowTask {
+        emitted_rows: out.len() as u64,
+        ..WindowInFlowByWindowTask::zero()
+    };
+
+    let (mut out_close, window_in_flow_close) = window.panes_mut().close_panes(&wm, expr_resolver);
+    out.append(&mut out_close);
+
+    Ok((
+        out,
+        window_in_flow_dispatch + window_in_flow_update + window_in_flow_close,
+    ))
 }
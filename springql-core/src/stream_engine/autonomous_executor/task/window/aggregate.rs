@@ -6,35 +6,44 @@ use anyhow::anyhow;
 
 use crate::{
     api::error::{Result, SpringError},
-    expr_resolver::{AggrExprLabel, ValueExprLabel},
+    expr_resolver::{AggrExprLabel, ExprResolver, ValueExprLabel},
     pipeline::{WindowOperationParameter, WindowParameter},
     stream_engine::{
-        autonomous_executor::task::window::{
-            panes::{AggrPane, Panes},
-            watermark::Watermark,
-            Window,
+        autonomous_executor::{
+            snapshot::{AggrPaneSnapshot, AggrWindowSnapshot},
+            task::window::{
+                dispatch_to_panes,
+                panes::{AggrPane, Pane, Panes},
+                slices::AggrSlices,
+                watermark::Watermark,
+                Success, Window,
+            },
         },
-        SqlValue,
+        SqlValue, Tuple,
     },
 };
 
 /// for aggregate expressions: AggrExprLabel -> SqlValue,
-/// for GROUP BY expressions: ValueExprLabel -> SqlValue
+/// for GROUP BY expressions: ValueExprLabel -> SqlValue,
+/// for window metadata: ValueExprLabel -> SqlValue
 ///
 /// Projection operation for SELECT with aggregate completes with this instance (without Tuple).
 #[derive(Clone, PartialEq, Debug, Default)]
 pub struct AggregatedAndGroupingValues {
     aggr: HashMap<AggrExprLabel, SqlValue>,
     group_by: HashMap<ValueExprLabel, SqlValue>,
+    window_metadata: HashMap<ValueExprLabel, SqlValue>,
 }
 impl AggregatedAndGroupingValues {
     pub fn new(
         aggregates: Vec<(AggrExprLabel, SqlValue)>,
         group_bys: Vec<(ValueExprLabel, SqlValue)>,
+        window_metadata: Vec<(ValueExprLabel, SqlValue)>,
     ) -> Self {
         Self {
             aggr: aggregates.into_iter().collect(),
             group_by: group_bys.into_iter().collect(),
+            window_metadata: window_metadata.into_iter().collect(),
         }
     }
 
@@ -57,12 +66,20 @@ impl AggregatedAndGroupingValues {
             .get(label)
             .ok_or_else(|| SpringError::Sql(anyhow!("GROUP BY label not found: {:?}", label)))
     }
+
+    /// `None` if `label` is not window metadata.
+    pub fn get_window_metadata_value(&self, label: &ValueExprLabel) -> Option<&SqlValue> {
+        self.window_metadata.get(label)
+    }
 }
 
 #[derive(Debug)]
 pub struct AggrWindow {
     watermark: Watermark,
     panes: Panes<AggrPane>,
+
+    /// Used instead of `panes` for sliding windows with overlapping panes.
+    slices: Option<AggrSlices>,
 }
 
 impl Window for AggrWindow {
@@ -85,27 +102,100 @@ impl Window for AggrWindow {
     }
 
     fn purge(&mut self) {
-        self.panes.purge()
+        self.panes.purge();
+        if let Some(slices) = &mut self.slices {
+            slices.purge();
+        }
+    }
+
+    fn dispatch_in_time(
+        &mut self,
+        expr_resolver: &ExprResolver,
+        tuple: Tuple,
+        arg: (),
+    ) -> Result<Success<AggregatedAndGroupingValues>> {
+        if let Some(slices) = &mut self.slices {
+            slices.dispatch(expr_resolver, &tuple, &self.watermark)
+        } else {
+            dispatch_to_panes(self, expr_resolver, tuple, arg)
+        }
     }
 }
 
 impl AggrWindow {
     pub fn new(window_param: WindowParameter, op_param: WindowOperationParameter) -> Self {
-        let watermark = Watermark::new(window_param.allowed_delay());
+        let watermark = Watermark::new(
+            window_param.allowed_delay(),
+            window_param.allowed_lateness(),
+        );
         Self {
             watermark,
+            slices: AggrSlices::new(window_param.clone(), op_param.clone()),
             panes: Panes::new(window_param, op_param),
         }
     }
+
+    /// Slices are taken as panes never closed.
+    pub fn snapshot(&self, pump: String) -> AggrWindowSnapshot {
+        let panes: Vec<(&AggrPane, Option<bool>)> = match &self.slices {
+            Some(slices) => slices.iter().map(|slice| (slice, None)).collect(),
+            None => self.panes.iter_with_fired().collect(),
+        };
+
+        AggrWindowSnapshot {
+            pump,
+            max_rowtime: self.watermark.max_rowtime(),
+            panes: panes
+                .into_iter()
+                .map(|(pane, fired)| AggrPaneSnapshot {
+                    open_at: pane.open_at(),
+                    close_at: pane.close_at(),
+                    fired,
+                    groups: pane.snapshot_groups(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Replaces the watermark and panes (or slices).
+    ///
+    /// # Failure
+    ///
+    /// - Raises Err from `AggrPane::restore_groups()` and `AggrSlices::restore()` as-is.
+    pub fn restore(&mut self, snapshot: AggrWindowSnapshot) -> Result<()> {
+        self.watermark.update(snapshot.max_rowtime);
+
+        let panes = snapshot
+            .panes
+            .into_iter()
+            .map(|pane_snapshot| {
+                let mut pane = self
+                    .panes
+                    .new_pane(pane_snapshot.open_at, pane_snapshot.close_at);
+                pane.restore_groups(pane_snapshot.groups)?;
+                Ok((pane, pane_snapshot.fired))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        match &mut self.slices {
+            Some(slices) => slices.restore(
+                panes.into_iter().map(|(pane, _)| pane).collect(),
+                &self.watermark,
+            )?,
+            None => self.panes.restore(panes),
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
     use springql_test_logger::setup_test_logger;
 
     use super::*;
 
-    use std::str::FromStr;
+    use std::{str::FromStr, time::Duration};
 
     use crate::{
         expr_resolver::{ExprLabel, ExprResolver},
@@ -116,7 +206,7 @@ mod tests {
         },
         sql_processor::SelectFieldSyntax,
         stream_engine::{
-            autonomous_executor::task::tuple::Tuple,
+            autonomous_executor::{task::tuple::Tuple, test_support::strategy::trade_tuples},
             time::{SpringDuration, SpringEventDuration, SpringTimestamp},
         },
     };
@@ -202,11 +292,16 @@ mod tests {
                         length: SpringEventDuration::from_secs(10),
                         period: SpringEventDuration::from_secs(5),
                         allowed_delay: SpringEventDuration::from_secs(1),
+                        allowed_lateness: SpringEventDuration::from_secs(0),
+                        min_watermark: false,
+                        input_idle_timeout: None,
+                        emit_on_update: false,
+                        trace_triggers: false,
                     },
                     WindowOperationParameter::Aggregate(AggregateParameter {
-                        aggr_func: AggregateFunctionParameter::Avg,
-                        aggr_expr: aggr_label,
+                        aggr_exprs: vec![(AggregateFunctionParameter::Avg, aggr_label)],
                         group_by: GroupByLabels::new(vec![group_by_label]),
+                        window_metadata: vec![],
                     }),
                 );
 
@@ -449,11 +544,17 @@ mod tests {
                     WindowParameter::TimedFixedWindow {
                         length: SpringEventDuration::from_secs(10),
                         allowed_delay: SpringEventDuration::from_secs(1),
+                        allowed_lateness: SpringEventDuration::from_secs(0),
+                        min_watermark: false,
+                        input_idle_timeout: None,
+                        offset: SpringEventDuration::from_secs(0),
+                        emit_on_update: false,
+                        trace_triggers: false,
                     },
                     WindowOperationParameter::Aggregate(AggregateParameter {
-                        aggr_func: AggregateFunctionParameter::Avg,
-                        aggr_expr: aggr_label,
+                        aggr_exprs: vec![(AggregateFunctionParameter::Avg, aggr_label)],
                         group_by: GroupByLabels::new(vec![group_by_label]),
+                        window_metadata: vec![],
                     }),
                 );
 
@@ -621,4 +722,374 @@ mod tests {
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn test_timed_fixed_window_aggregation_with_allowed_lateness() {
+        setup_test_logger();
+
+        // SELECT ticker, AVG(amount) AS avg_amount
+        //   FROM trade
+        //   FIXED WINDOW duration_secs(10), duration_secs(0) ALLOWED LATENESS duration_secs(5)
+        //   GROUP BY ticker;
+
+        let ticker_expr = ValueExpr::factory_colref(
+            StreamName::fx_trade().as_ref(),
+            ColumnName::fx_ticker().as_ref(),
+        );
+        let avg_amount_expr = AggrExpr {
+            func: AggregateFunctionParameter::Avg,
+            aggregated: ValueExpr::factory_colref(
+                StreamName::fx_trade().as_ref(),
+                ColumnName::fx_amount().as_ref(),
+            ),
+        };
+
+        let select_list = vec![
+            SelectFieldSyntax::ValueExpr {
+                value_expr: ticker_expr,
+                alias: None,
+            },
+            SelectFieldSyntax::AggrExpr {
+                aggr_expr: avg_amount_expr,
+                alias: Some(AggrAlias::new("avg_amount".to_string())),
+            },
+        ];
+
+        let (expr_resolver, labels) = ExprResolver::new(select_list);
+        match &labels[..] {
+            &[ExprLabel::Value(group_by_label), ExprLabel::Aggr(aggr_label)] => {
+                let mut window = AggrWindow::new(
+                    WindowParameter::TimedFixedWindow {
+                        length: SpringEventDuration::from_secs(10),
+                        allowed_delay: SpringEventDuration::from_secs(0),
+                        allowed_lateness: SpringEventDuration::from_secs(5),
+                        min_watermark: false,
+                        input_idle_timeout: None,
+                        offset: SpringEventDuration::from_secs(0),
+                        emit_on_update: false,
+                        trace_triggers: false,
+                    },
+                    WindowOperationParameter::Aggregate(AggregateParameter {
+                        aggr_exprs: vec![(AggregateFunctionParameter::Avg, aggr_label)],
+                        group_by: GroupByLabels::new(vec![group_by_label]),
+                        window_metadata: vec![],
+                    }),
+                );
+
+                let mut dispatch = |ts: &str, amount: i16| {
+                    window
+                        .dispatch(
+                            &expr_resolver,
+                            Tuple::factory_trade(
+                                SpringTimestamp::from_str(ts).unwrap(),
+                                "GOOGL",
+                                amount,
+                            ),
+                            (),
+                        )
+                        .unwrap()
+                };
+
+                // [:00, :10): ("GOOGL", 100)
+                assert!(dispatch("2020-01-01 00:00:01.000000000", 100).0.is_empty());
+
+                // [:00, :10): -> "GOOGL" AVG = 100 (kept until :15)
+                // [:10, :20): ("GOOGL", 300)
+                let (out, _) = dispatch("2020-01-01 00:00:11.000000000", 300);
+                assert_eq!(out.len(), 1);
+                t_expect(aggr_label, group_by_label, out[0].clone(), "GOOGL", 100);
+
+                // [:00, :10): ("GOOGL", 100), ("GOOGL", 200) <-- !!LATE DATA!! -> "GOOGL" AVG = 150
+                let (out, window_in_flow) = dispatch("2020-01-01 00:00:07.000000000", 200);
+                assert_eq!(window_in_flow.late_rows, 1);
+                assert_eq!(
+                    window_in_flow.max_disorder,
+                    Some(SpringEventDuration::from_secs(4))
+                );
+                assert_eq!(out.len(), 1);
+                t_expect(aggr_label, group_by_label, out[0].clone(), "GOOGL", 150);
+
+                // no late data to [:00, :10) since the last output
+                assert!(dispatch("2020-01-01 00:00:12.000000000", 0).0.is_empty());
+
+                // too late data to be ignored (watermark - allowed lateness = :07)
+                let (out, window_in_flow) = dispatch("2020-01-01 00:00:03.000000000", 1000);
+                assert!(out.is_empty());
+                assert_eq!(window_in_flow.late_rows, 0);
+                assert_eq!(window_in_flow.dropped_late_rows, 1);
+
+                // [:00, :10) is removed without output
+                assert!(dispatch("2020-01-01 00:00:16.000000000", 0).0.is_empty());
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// `SELECT ticker, COUNT(amount) FROM trade <window> GROUP BY ticker;`
+    fn count_by_ticker_window(
+        window_param: WindowParameter,
+    ) -> (AggrWindow, ExprResolver, AggrExprLabel) {
+        let select_list = vec![
+            SelectFieldSyntax::ValueExpr {
+                value_expr: ValueExpr::factory_colref(
+                    StreamName::fx_trade().as_ref(),
+                    ColumnName::fx_ticker().as_ref(),
+                ),
+                alias: None,
+            },
+            SelectFieldSyntax::AggrExpr {
+                aggr_expr: AggrExpr {
+                    func: AggregateFunctionParameter::Count,
+                    aggregated: ValueExpr::factory_colref(
+                        StreamName::fx_trade().as_ref(),
+                        ColumnName::fx_amount().as_ref(),
+                    ),
+                },
+                alias: Some(AggrAlias::new("n".to_string())),
+            },
+        ];
+
+        let (expr_resolver, labels) = ExprResolver::new(select_list);
+        match &labels[..] {
+            &[ExprLabel::Value(group_by_label), ExprLabel::Aggr(aggr_label)] => {
+                let window = AggrWindow::new(
+                    window_param,
+                    WindowOperationParameter::Aggregate(AggregateParameter {
+                        aggr_exprs: vec![(AggregateFunctionParameter::Count, aggr_label)],
+                        group_by: GroupByLabels::new(vec![group_by_label]),
+                        window_metadata: vec![],
+                    }),
+                );
+                (window, expr_resolver, aggr_label)
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_timed_fixed_window_aggregation_emit_on_update() {
+        setup_test_logger();
+
+        // SELECT ticker, COUNT(amount) AS n
+        //   FROM trade
+        //   FIXED WINDOW duration_secs(10), duration_secs(0) ALLOWED LATENESS duration_secs(5) EMIT ON UPDATE
+        //   GROUP BY ticker;
+        let (mut window, expr_resolver, aggr_label) =
+            count_by_ticker_window(WindowParameter::TimedFixedWindow {
+                length: SpringEventDuration::from_secs(10),
+                allowed_delay: SpringEventDuration::from_secs(0),
+                allowed_lateness: SpringEventDuration::from_secs(5),
+                min_watermark: false,
+                input_idle_timeout: None,
+                offset: SpringEventDuration::from_secs(0),
+                emit_on_update: true,
+                trace_triggers: false,
+            });
+
+        // counts of updated groups
+        let mut dispatch = |ts: &str, ticker: &str| {
+            let (out, window_in_flow) = window
+                .dispatch(
+                    &expr_resolver,
+                    Tuple::factory_trade(SpringTimestamp::from_str(ts).unwrap(), ticker, 0),
+                    (),
+                )
+                .unwrap();
+            assert_eq!(window_in_flow.emitted_rows, out.len() as u64);
+            out.into_iter()
+                .map(|aggregated_and_grouping_values| {
+                    aggregated_and_grouping_values
+                        .get_aggregated_value(&aggr_label)
+                        .unwrap()
+                        .to_i64()
+                        .unwrap()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // [:00, :10): "GOOGL" -> 1
+        assert_eq!(dispatch("2020-01-01 00:00:01.000000000", "GOOGL"), vec![1]);
+        // [:00, :10): "ORCL" -> 1 (other groups are not output)
+        assert_eq!(dispatch("2020-01-01 00:00:02.000000000", "ORCL"), vec![1]);
+        // [:00, :10): "GOOGL" -> 2
+        assert_eq!(dispatch("2020-01-01 00:00:03.000000000", "GOOGL"), vec![2]);
+
+        // [:10, :20): "GOOGL" -> 1
+        // [:00, :10) is closed without output
+        assert_eq!(dispatch("2020-01-01 00:00:11.000000000", "GOOGL"), vec![1]);
+
+        // [:00, :10): "GOOGL" -> 3 <-- !!LATE DATA!!
+        assert_eq!(dispatch("2020-01-01 00:00:07.000000000", "GOOGL"), vec![3]);
+
+        // [:10, :20): "GOOGL" -> 2
+        // [:00, :10) is removed without output
+        assert_eq!(dispatch("2020-01-01 00:00:16.000000000", "GOOGL"), vec![2]);
+    }
+
+    /// Dispatches `tuples` and then a tuple far in the future to close all panes with them.
+    ///
+    /// # Returns
+    ///
+    /// (sum of counts of closed panes, dropped late rows)
+    fn dispatch_counting(
+        window_param: WindowParameter,
+        tuples: Vec<Tuple>,
+    ) -> std::result::Result<(i64, u64), TestCaseError> {
+        let (mut window, expr_resolver, aggr_label) = count_by_ticker_window(window_param);
+
+        let flush_at = tuples
+            .iter()
+            .map(|tuple| tuple.rowtime().as_timestamp())
+            .max()
+            .map(|max_rowtime| max_rowtime + SpringEventDuration::from_secs(3600).to_duration());
+
+        let mut watermark = window.watermark().as_timestamp();
+        let (mut count, mut dropped) = (0, 0);
+        for tuple in tuples
+            .into_iter()
+            .chain(flush_at.map(|flush_at| Tuple::factory_trade(flush_at, "GOOGL", 0)))
+        {
+            let (out, window_in_flow) = window.dispatch(&expr_resolver, tuple, ()).unwrap();
+
+            // watermark monotonicity
+            prop_assert!(window.watermark().as_timestamp() >= watermark);
+            watermark = window.watermark().as_timestamp();
+
+            for aggregated_and_grouping_values in out {
+                count += aggregated_and_grouping_values
+                    .get_aggregated_value(&aggr_label)
+                    .unwrap()
+                    .to_i64()
+                    .unwrap();
+            }
+            dropped += window_in_flow.dropped_late_rows;
+        }
+        Ok((count, dropped))
+    }
+
+    proptest! {
+        #[test]
+        fn test_fixed_window_counts_all_rows(
+            (length_secs, allowed_delay_msec, max_disorder_msec, tuples) in
+                (1..=10_u64, 0..=2_000_u64, 0..=2_000_u64).prop_flat_map(
+                    |(length_secs, allowed_delay_msec, max_disorder_msec)| (
+                        Just(length_secs),
+                        Just(allowed_delay_msec),
+                        Just(max_disorder_msec),
+                        trade_tuples(0..50, Duration::from_millis(max_disorder_msec)),
+                    )
+                )
+        ) {
+            let n = tuples.len() as i64;
+            let (count, dropped) = dispatch_counting(
+                WindowParameter::TimedFixedWindow {
+                    length: SpringEventDuration::from_secs(length_secs),
+                    allowed_delay: SpringEventDuration::from_millis(allowed_delay_msec),
+                    allowed_lateness: SpringEventDuration::from_secs(0),
+                    min_watermark: false,
+                    input_idle_timeout: None,
+                    offset: SpringEventDuration::from_secs(0),
+                    emit_on_update: false,
+                    trace_triggers: false,
+                },
+                tuples,
+            )?;
+
+            // each row is in a pane unless it is late
+            prop_assert_eq!(count + dropped as i64, n);
+            if allowed_delay_msec >= max_disorder_msec {
+                prop_assert_eq!(dropped, 0);
+            }
+        }
+
+        #[test]
+        fn test_sliding_window_counts_all_rows(
+            (period_secs, panes_per_row, allowed_delay_msec, tuples) in
+                (1..=5_u64, 1..=4_u64, 0..=2_000_u64).prop_flat_map(
+                    |(period_secs, panes_per_row, allowed_delay_msec)| (
+                        Just(period_secs),
+                        Just(panes_per_row),
+                        Just(allowed_delay_msec),
+                        trade_tuples(0..50, Duration::from_millis(2_000)),
+                    )
+                )
+        ) {
+            let n = tuples.len() as i64;
+            let (count, dropped) = dispatch_counting(
+                WindowParameter::TimedSlidingWindow {
+                    length: SpringEventDuration::from_secs(period_secs * panes_per_row),
+                    period: SpringEventDuration::from_secs(period_secs),
+                    allowed_delay: SpringEventDuration::from_millis(allowed_delay_msec),
+                    allowed_lateness: SpringEventDuration::from_secs(0),
+                    min_watermark: false,
+                    input_idle_timeout: None,
+                    emit_on_update: false,
+                    trace_triggers: false,
+                },
+                tuples,
+            )?;
+
+            // each row is in `length / period` panes unless it is late
+            prop_assert_eq!(count, (n - dropped as i64) * panes_per_row as i64);
+        }
+
+        #[test]
+        fn test_sliding_window_slices_output_as_panes(
+            (period_100msec, extra_length_100msec, allowed_lateness_msec, tuples) in
+                (1..=30_u64, 1..=90_u64, 0..=2_000_u64).prop_flat_map(
+                    |(period_100msec, extra_length_100msec, allowed_lateness_msec)| (
+                        Just(period_100msec),
+                        Just(extra_length_100msec),
+                        Just(allowed_lateness_msec),
+                        trade_tuples(0..50, Duration::from_millis(2_000)),
+                    )
+                )
+        ) {
+            // period does not always divide length
+            let period_msec = period_100msec * 100;
+            let length_msec = period_msec + extra_length_100msec * 100;
+
+            let window_param = WindowParameter::TimedSlidingWindow {
+                length: SpringEventDuration::from_millis(length_msec),
+                period: SpringEventDuration::from_millis(period_msec),
+                allowed_delay: SpringEventDuration::from_millis(500),
+                allowed_lateness: SpringEventDuration::from_millis(allowed_lateness_msec),
+                min_watermark: false,
+                input_idle_timeout: None,
+                emit_on_update: false,
+                trace_triggers: false,
+            };
+            let (mut sliced, sliced_expr_resolver, aggr_label) =
+                count_by_ticker_window(window_param.clone());
+            let (mut paned, paned_expr_resolver, _) = count_by_ticker_window(window_param);
+            prop_assert!(sliced.slices.is_some());
+            paned.slices = None;
+
+            let counts = |out: Vec<AggregatedAndGroupingValues>| {
+                let mut counts = out
+                    .into_iter()
+                    .map(|aggregated_and_grouping_values| {
+                        aggregated_and_grouping_values
+                            .get_aggregated_value(&aggr_label)
+                            .unwrap()
+                            .to_i64()
+                            .unwrap()
+                    })
+                    .collect::<Vec<_>>();
+                counts.sort_unstable();
+                counts
+            };
+
+            for tuple in tuples {
+                let (sliced_out, sliced_window_in_flow) = sliced
+                    .dispatch(&sliced_expr_resolver, tuple.clone(), ())
+                    .unwrap();
+                let (paned_out, paned_window_in_flow) =
+                    paned.dispatch(&paned_expr_resolver, tuple, ()).unwrap();
+
+                prop_assert_eq!(counts(sliced_out), counts(paned_out));
+                prop_assert_eq!(sliced_window_in_flow, paned_window_in_flow);
+            }
+        }
+    }
 }
@@ -0,0 +1,228 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{cmp::min, collections::VecDeque};
+
+use crate::{
+    api::error::Result,
+    expr_resolver::{ExprResolver, ValueExprLabel},
+    mem_size::MemSize,
+    pipeline::{IntervalJoinBound, JoinParameter, JoinType},
+    stream_engine::{
+        autonomous_executor::{
+            performance_metrics::WindowInFlowByWindowTask,
+            task::window::{
+                panes::{JoinDir, JoinKeyValues},
+                watermark::Watermark,
+            },
+        },
+        time::{SpringDuration, SpringEventDuration, SpringTimestamp},
+        Tuple,
+    },
+};
+
+/// Interval join without windows.
+///
+/// Rows from each input are buffered in ROWTIME order. A left row at `t` joins right rows whose ROWTIME is in
+/// `[t + bound.lower, t + bound.upper]`, so a new row only probes that range of the other buffer.
+///
+/// Rows are evicted when the minimum watermark of the inputs passes the range they can join to.
+/// Left rows without any match are emitted with NULL right columns on eviction (LEFT OUTER JOIN).
+#[derive(Debug)]
+pub struct IntervalJoin {
+    join_param: JoinParameter,
+    bound: IntervalJoinBound,
+
+    left_watermark: Watermark,
+    right_watermark: Watermark,
+
+    /// Sorted by ROWTIME.
+    left_rows: VecDeque<BufferedRow>,
+    /// Sorted by ROWTIME. Rows with NULL key values are not kept since they never match.
+    right_rows: VecDeque<BufferedRow>,
+}
+
+impl IntervalJoin {
+    pub fn new(bound: IntervalJoinBound, join_param: JoinParameter) -> Self {
+        let watermark = Watermark::new(
+            SpringEventDuration::from_secs(0),
+            SpringEventDuration::from_secs(0),
+        );
+        Self {
+            join_param,
+            bound,
+            left_watermark: watermark,
+            right_watermark: watermark,
+            left_rows: VecDeque::new(),
+            right_rows: VecDeque::new(),
+        }
+    }
+
+    /// A task dispatches a tuple from left or right input.
+    ///
+    /// # Returns
+    ///
+    /// Joined tuples (including null-extended left tuples evicted) and window in-flow.
+    pub fn dispatch(
+        &mut self,
+        expr_resolver: &ExprResolver,
+        tuple: Tuple,
+        dir: JoinDir,
+    ) -> Result<(Vec<Tuple>, WindowInFlowByWindowTask)> {
+        let rowtime = tuple.rowtime().as_timestamp();
+
+        let input_watermark = match dir {
+            JoinDir::Left => &mut self.left_watermark,
+            JoinDir::Right => &mut self.right_watermark,
+        };
+        let max_disorder = input_watermark.disorder(rowtime);
+        input_watermark.update(rowtime);
+        let late_rows = (rowtime < self.watermark()) as u64;
+
+        let key_labels = self.join_param.keys.iter().map(|key| match dir {
+            JoinDir::Left => key.left,
+            JoinDir::Right => key.right,
+        });
+        let key = JoinKeyValues::from_key_labels(key_labels, expr_resolver, &tuple)?;
+        let mut row = BufferedRow::new(rowtime, key, tuple);
+
+        let on_expr = self.join_param.on_expr;
+        let mut joined = Vec::new();
+        let mut gain_rows = 0;
+        match dir {
+            JoinDir::Left => {
+                let (from, to) = (rowtime + self.bound.lower, rowtime + self.bound.upper);
+                for right in rows_in(&mut self.right_rows, from, to) {
+                    if let Some(tuple) = join_pair(expr_resolver, on_expr, &row, right)? {
+                        joined.push(tuple);
+                        row.matched = true;
+                    }
+                }
+                gain_rows += row.tuple.stored_size() as i64;
+                insert_row(&mut self.left_rows, row);
+            }
+            JoinDir::Right => {
+                let (from, to) = (rowtime - self.bound.upper, rowtime - self.bound.lower);
+                for left in rows_in(&mut self.left_rows, from, to) {
+                    if let Some(tuple) = join_pair(expr_resolver, on_expr, left, &row)? {
+                        joined.push(tuple);
+                        left.matched = true;
+                    }
+                }
+                if row.key.is_some() {
+                    gain_rows += row.tuple.stored_size() as i64;
+                    insert_row(&mut self.right_rows, row);
+                }
+            }
+        }
+
+        let (evicted, evicted_size) = self.evict();
+        joined.extend(evicted);
+
+        let window_in_flow = WindowInFlowByWindowTask {
+            late_rows,
+            max_disorder,
+            ..WindowInFlowByWindowTask::new(0, gain_rows - evicted_size)
+        };
+        Ok((joined, window_in_flow))
+    }
+
+    pub fn purge(&mut self) {
+        self.left_rows.clear();
+        self.right_rows.clear();
+    }
+
+    /// Minimum watermark of inputs.
+    fn watermark(&self) -> SpringTimestamp {
+        min(
+            self.left_watermark.as_timestamp(),
+            self.right_watermark.as_timestamp(),
+        )
+    }
+
+    /// Evicts rows no future row from the other input can join to.
+    ///
+    /// # Returns
+    ///
+    /// (null-extended tuples of left rows without any match, evicted bytes)
+    fn evict(&mut self) -> (Vec<Tuple>, i64) {
+        let wm = self.watermark();
+        let mut evicted_size = 0;
+
+        let mut null_extended = Vec::new();
+        while let Some(left) = self.left_rows.front() {
+            // future right rows come at wm or later
+            if left.rowtime + self.bound.upper >= wm {
+                break;
+            }
+            let left = self.left_rows.pop_front().expect("front exists");
+            evicted_size += left.tuple.stored_size() as i64;
+            if !left.matched {
+                match self.join_param.join_type {
+                    JoinType::LeftOuter => null_extended.push(
+                        left.tuple
+                            .join(Tuple::new_nulls(&self.join_param.right_colrefs)),
+                    ),
+                }
+            }
+        }
+
+        while let Some(right) = self.right_rows.front() {
+            // future left rows come at wm or later
+            if right.rowtime - self.bound.lower >= wm {
+                break;
+            }
+            let right = self.right_rows.pop_front().expect("front exists");
+            evicted_size += right.tuple.stored_size() as i64;
+        }
+
+        (null_extended, evicted_size)
+    }
+}
+
+#[derive(Debug, new)]
+struct BufferedRow {
+    rowtime: SpringTimestamp,
+    /// `None` if any of key values is NULL.
+    key: Option<JoinKeyValues>,
+    tuple: Tuple,
+    #[new(default)]
+    matched: bool,
+}
+
+/// Rows with ROWTIME in `[from, to]`.
+fn rows_in(
+    rows: &mut VecDeque<BufferedRow>,
+    from: SpringTimestamp,
+    to: SpringTimestamp,
+) -> impl Iterator<Item = &mut BufferedRow> {
+    let start = rows.partition_point(|row| row.rowtime < from);
+    let end = rows.partition_point(|row| row.rowtime <= to).max(start);
+    rows.range_mut(start..end)
+}
+
+/// Inserts a row after rows with the same or older ROWTIME.
+fn insert_row(rows: &mut VecDeque<BufferedRow>, row: BufferedRow) {
+    let at = rows.partition_point(|r| r.rowtime <= row.rowtime);
+    rows.insert(at, row);
+}
+
+fn join_pair(
+    expr_resolver: &ExprResolver,
+    on_expr: Option<ValueExprLabel>,
+    left: &BufferedRow,
+    right: &BufferedRow,
+) -> Result<Option<Tuple>> {
+    if left.key.is_none() || left.key != right.key {
+        return Ok(None);
+    }
+
+    // TODO less clone. ExprResolver takes two tuples to resolve ColumnReference?
+    let joined_tuple = left.tuple.clone().join(right.tuple.clone());
+    let on_bool = match on_expr {
+        Some(on_expr) => expr_resolver
+            .eval_value_expr(on_expr, &joined_tuple)?
+            .to_bool()?,
+        None => true,
+    };
+    Ok(on_bool.then(|| joined_tuple))
+}
@@ -1,11 +1,16 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use std::cmp::{max, min};
+
 use crate::{
     pipeline::{JoinParameter, WindowOperationParameter, WindowParameter},
-    stream_engine::autonomous_executor::task::window::{
-        panes::{JoinPane, Panes},
-        watermark::Watermark,
-        Window,
+    stream_engine::{
+        autonomous_executor::task::window::{
+            panes::{JoinDir, JoinPane, Panes},
+            watermark::Watermark,
+            Window,
+        },
+        time::{SpringTimestamp, WallClockDuration, WallClockStopwatch},
     },
 };
 
@@ -13,6 +18,9 @@ use crate::{
 pub struct JoinWindow {
     watermark: Watermark,
     panes: Panes<JoinPane>,
+
+    /// `Some` if the watermark is the minimum of watermarks of each input.
+    input_watermarks: Option<InputWatermarks>,
 }
 
 impl Window for JoinWindow {
@@ -37,32 +45,130 @@ impl Window for JoinWindow {
     fn purge(&mut self) {
         self.panes.purge()
     }
+
+    fn update_watermark(&mut self, rowtime: SpringTimestamp, dir: &JoinDir) {
+        match &mut self.input_watermarks {
+            Some(input_watermarks) => {
+                input_watermarks.update(rowtime, *dir);
+                // an input resumed from idle must not move the watermark backward
+                self.watermark = max(self.watermark, input_watermarks.min_watermark());
+            }
+            None => self.watermark.update(rowtime),
+        }
+    }
 }
 
 impl JoinWindow {
     pub fn new(window_param: WindowParameter, join_param: JoinParameter) -> Self {
-        let watermark = Watermark::new(window_param.allowed_delay());
+        let watermark = Watermark::new(
+            window_param.allowed_delay(),
+            window_param.allowed_lateness(),
+        );
+        let input_watermarks = window_param
+            .min_watermark()
+            .then(|| InputWatermarks::new(watermark, window_param.input_idle_timeout()));
         Self {
             watermark,
             panes: Panes::new(window_param, WindowOperationParameter::Join(join_param)),
+            input_watermarks,
         }
     }
 }
 
+/// Watermarks of left and right inputs.
+#[derive(Debug)]
+struct InputWatermarks {
+    left: InputWatermark,
+    right: InputWatermark,
+    idle_timeout: Option<WallClockDuration>,
+}
+
+impl InputWatermarks {
+    fn new(watermark: Watermark, idle_timeout: Option<WallClockDuration>) -> Self {
+        Self {
+            left: InputWatermark::new(watermark),
+            right: InputWatermark::new(watermark),
+            idle_timeout,
+        }
+    }
+
+    fn update(&mut self, rowtime: SpringTimestamp, dir: JoinDir) {
+        let input = match dir {
+            JoinDir::Left => &mut self.left,
+            JoinDir::Right => &mut self.right,
+        };
+
+        if input.idle {
+            log::info!("[JoinWindow] {:?} input resumed from idle", dir);
+            input.idle = false;
+        }
+        input.watermark.update(rowtime);
+        input.last_row_stopwatch = WallClockStopwatch::start();
+    }
+
+    /// Minimum watermark of inputs not idle.
+    fn min_watermark(&mut self) -> Watermark {
+        let idle_timeout = self.idle_timeout;
+        let left_active = self.left.check_active(JoinDir::Left, idle_timeout);
+        let right_active = self.right.check_active(JoinDir::Right, idle_timeout);
+
+        match (left_active, right_active) {
+            (true, false) => self.left.watermark,
+            (false, true) => self.right.watermark,
+            _ => min(self.left.watermark, self.right.watermark),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct InputWatermark {
+    watermark: Watermark,
+    last_row_stopwatch: WallClockStopwatch,
+    idle: bool,
+}
+
+impl InputWatermark {
+    fn new(watermark: Watermark) -> Self {
+        Self {
+            watermark,
+            last_row_stopwatch: WallClockStopwatch::start(),
+            idle: false,
+        }
+    }
+
+    /// Marks this input idle if no row came for `idle_timeout`. Returns whether this input is active.
+    fn check_active(&mut self, dir: JoinDir, idle_timeout: Option<WallClockDuration>) -> bool {
+        if let Some(idle_timeout) = idle_timeout {
+            if !self.idle && self.last_row_stopwatch.stop() >= idle_timeout {
+                log::info!(
+                    "[JoinWindow] {:?} input is marked idle (no rows for {:?})",
+                    dir,
+                    idle_timeout
+                );
+                self.idle = true;
+            }
+        }
+        !self.idle
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use std::str::FromStr;
+    use std::{str::FromStr, thread, time::Duration};
 
     use springql_test_logger::setup_test_logger;
 
     use crate::{
         expr_resolver::ExprResolver,
         expression::ValueExpr,
-        pipeline::{ColumnName, ColumnReference, JoinParameter, JoinType, StreamName},
+        pipeline::{
+            ColumnName, ColumnReference, JoinBuildSide, JoinKey, JoinParameter, JoinType,
+            StreamName,
+        },
         sql_processor::SelectFieldSyntax,
         stream_engine::{
             autonomous_executor::task::window::panes::JoinDir,
-            time::{SpringDuration, SpringEventDuration, SpringTimestamp},
+            time::{SpringDuration, SpringEventDuration, SpringTimestamp, WallClockDuration},
             SqlValue, Tuple,
         },
     };
@@ -102,16 +208,29 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_timed_fixed_window_left_out_join() {
-        setup_test_logger();
-
-        // SELECT trade.timestamp, trade.amount, city_temperature.temperature
-        //   FROM trade
-        //   LEFT OUTER JOIN city_temperature
-        //   ON trade.timestamp = city_temperature.timestamp
-        //   FIXED WINDOW duration_secs(10), duration_secs(1);
+    /// ```sql
+    /// SELECT trade.timestamp, trade.amount, city_temperature.temperature
+    ///   FROM trade
+    ///   LEFT OUTER JOIN city_temperature
+    ///   ON trade.timestamp = city_temperature.timestamp
+    ///   FIXED WINDOW duration_secs(10), duration_secs(1);
+    /// ```
+    fn fx_left_outer_join_window(
+        min_watermark: bool,
+        input_idle_timeout: Option<WallClockDuration>,
+    ) -> (JoinWindow, ExprResolver) {
+        fx_left_outer_join_window_with_build_side(
+            min_watermark,
+            input_idle_timeout,
+            JoinBuildSide::Auto,
+        )
+    }
 
+    fn fx_left_outer_join_window_with_build_side(
+        min_watermark: bool,
+        input_idle_timeout: Option<WallClockDuration>,
+        build_side: JoinBuildSide,
+    ) -> (JoinWindow, ExprResolver) {
         let trade_timestamp_expr = ValueExpr::factory_colref(
             StreamName::fx_trade().as_ref(),
             ColumnName::fx_timestamp().as_ref(),
@@ -129,14 +248,10 @@ mod tests {
             StreamName::fx_city_temperature().as_ref(),
             ColumnName::fx_timestamp().as_ref(),
         );
-        let on_expr = ValueExpr::factory_eq(
-            trade_timestamp_expr.clone(),
-            city_temperature_timestamp_expr,
-        );
 
         let select_list = vec![
             SelectFieldSyntax::ValueExpr {
-                value_expr: trade_timestamp_expr,
+                value_expr: trade_timestamp_expr.clone(),
                 alias: None,
             },
             SelectFieldSyntax::ValueExpr {
@@ -151,12 +266,21 @@ mod tests {
 
         let (mut expr_resolver, _) = ExprResolver::new(select_list);
 
-        let on_expr_label = expr_resolver.register_value_expr(on_expr);
+        let join_key = JoinKey::new(
+            expr_resolver.register_value_expr(trade_timestamp_expr),
+            expr_resolver.register_value_expr(city_temperature_timestamp_expr),
+        );
 
-        let mut window = JoinWindow::new(
+        let window = JoinWindow::new(
             WindowParameter::TimedFixedWindow {
                 length: SpringEventDuration::from_secs(10),
                 allowed_delay: SpringEventDuration::from_secs(1),
+                allowed_lateness: SpringEventDuration::from_secs(0),
+                min_watermark,
+                input_idle_timeout,
+                offset: SpringEventDuration::from_secs(0),
+                emit_on_update: false,
+                trace_triggers: false,
             },
             JoinParameter {
                 join_type: JoinType::LeftOuter,
@@ -170,10 +294,27 @@ mod tests {
                     ColumnReference::fx_city_temperature_city(),
                     ColumnReference::fx_city_temperature_temperature(),
                 ],
-                on_expr: on_expr_label,
+                keys: vec![join_key],
+                on_expr: None,
+                build_side,
             },
         );
 
+        (window, expr_resolver)
+    }
+
+    #[test]
+    fn test_timed_fixed_window_left_out_join() {
+        setup_test_logger();
+
+        // SELECT trade.timestamp, trade.amount, city_temperature.temperature
+        //   FROM trade
+        //   LEFT OUTER JOIN city_temperature
+        //   ON trade.timestamp = city_temperature.timestamp
+        //   FIXED WINDOW duration_secs(10), duration_secs(1);
+
+        let (mut window, expr_resolver) = fx_left_outer_join_window(false, None);
+
         // [:00, :10): t(:00, 100)
         let (out, window_in_flow) = window
             .dispatch(
@@ -311,4 +452,169 @@ mod tests {
         assert!(window_in_flow.window_gain_bytes_rows < 0);
         assert_eq!(window_in_flow.window_gain_bytes_states, 0);
     }
+
+    #[test]
+    fn test_timed_fixed_window_left_out_join_build_side() {
+        setup_test_logger();
+
+        let ts = |s: &str| SpringTimestamp::from_str(&format!("2020-01-01 00:00:{}", s)).unwrap();
+
+        for build_side in [
+            JoinBuildSide::Auto,
+            JoinBuildSide::Left,
+            JoinBuildSide::Right,
+        ] {
+            let (mut window, expr_resolver) =
+                fx_left_outer_join_window_with_build_side(false, None, build_side);
+
+            // [:00, :10): t(:00, 100), t(:05, 200), c(:00, 10), c(:00, 20), c(:05, 30), c(:07, 40)
+            // (fewer left tuples, so that AUTO builds a hash table from left)
+            for (timestamp, amount) in [("00.000000000", 100), ("05.000000000", 200)] {
+                window
+                    .dispatch(
+                        &expr_resolver,
+                        Tuple::factory_trade(ts(timestamp), "", amount),
+                        JoinDir::Left,
+                    )
+                    .unwrap();
+            }
+            for (timestamp, temperature) in [
+                ("00.000000000", 10),
+                ("00.000000000", 20),
+                ("05.000000000", 30),
+                ("07.000000000", 40),
+            ] {
+                window
+                    .dispatch(
+                        &expr_resolver,
+                        Tuple::factory_city_temperature(ts(timestamp), "", temperature),
+                        JoinDir::Right,
+                    )
+                    .unwrap();
+            }
+
+            // [:00, :10): -> tc(:00, 100, 10), tc(:00, 100, 20), tc(:05, 200, 30)
+            let (out, _) = window
+                .dispatch(
+                    &expr_resolver,
+                    Tuple::factory_trade(ts("11.000000000"), "", 300),
+                    JoinDir::Left,
+                )
+                .unwrap();
+            assert_eq!(out.len(), 3, "{:?}", build_side);
+            t_expect(out[0].clone(), ts("00.000000000"), 100, Some(10));
+            t_expect(out[1].clone(), ts("00.000000000"), 100, Some(20));
+            t_expect(out[2].clone(), ts("05.000000000"), 200, Some(30));
+        }
+    }
+
+    #[test]
+    fn test_timed_fixed_window_left_out_join_min_watermark() {
+        setup_test_logger();
+
+        let (mut window, expr_resolver) = fx_left_outer_join_window(true, None);
+
+        let (out, _) = window
+            .dispatch(
+                &expr_resolver,
+                Tuple::factory_trade(
+                    SpringTimestamp::from_str("2020-01-01 00:00:00.000000000").unwrap(),
+                    "",
+                    100,
+                ),
+                JoinDir::Left,
+            )
+            .unwrap();
+        assert!(out.is_empty());
+
+        // right input has not caught up yet
+        let (out, _) = window
+            .dispatch(
+                &expr_resolver,
+                Tuple::factory_trade(
+                    SpringTimestamp::from_str("2020-01-01 00:00:11.000000000").unwrap(),
+                    "",
+                    200,
+                ),
+                JoinDir::Left,
+            )
+            .unwrap();
+        assert!(out.is_empty());
+
+        // delayed right input is still joined
+        let (out, _) = window
+            .dispatch(
+                &expr_resolver,
+                Tuple::factory_city_temperature(
+                    SpringTimestamp::from_str("2020-01-01 00:00:00.000000000").unwrap(),
+                    "",
+                    10,
+                ),
+                JoinDir::Right,
+            )
+            .unwrap();
+        assert!(out.is_empty());
+
+        // both inputs caught up
+        let (out, _) = window
+            .dispatch(
+                &expr_resolver,
+                Tuple::factory_city_temperature(
+                    SpringTimestamp::from_str("2020-01-01 00:00:11.000000000").unwrap(),
+                    "",
+                    20,
+                ),
+                JoinDir::Right,
+            )
+            .unwrap();
+        assert_eq!(out.len(), 1);
+        t_expect(
+            out.get(0).cloned().unwrap(),
+            SpringTimestamp::from_str("2020-01-01 00:00:00.000000000").unwrap(),
+            100,
+            Some(10),
+        );
+    }
+
+    #[test]
+    fn test_timed_fixed_window_left_out_join_idle_input() {
+        setup_test_logger();
+
+        let (mut window, expr_resolver) =
+            fx_left_outer_join_window(true, Some(WallClockDuration::from_millis(100)));
+
+        let (out, _) = window
+            .dispatch(
+                &expr_resolver,
+                Tuple::factory_trade(
+                    SpringTimestamp::from_str("2020-01-01 00:00:00.000000000").unwrap(),
+                    "",
+                    100,
+                ),
+                JoinDir::Left,
+            )
+            .unwrap();
+        assert!(out.is_empty());
+
+        // right input is marked idle
+        thread::sleep(Duration::from_millis(200));
+        let (out, _) = window
+            .dispatch(
+                &expr_resolver,
+                Tuple::factory_trade(
+                    SpringTimestamp::from_str("2020-01-01 00:00:11.000000000").unwrap(),
+                    "",
+                    200,
+                ),
+                JoinDir::Left,
+            )
+            .unwrap();
+        assert_eq!(out.len(), 1);
+        t_expect(
+            out.get(0).cloned().unwrap(),
+            SpringTimestamp::from_str("2020-01-01 00:00:00.000000000").unwrap(),
+            100,
+            None,
+        );
+    }
 }
@@ -2,15 +2,18 @@
 
 mod pane;
 
-pub use pane::{AggrPane, AggrPaneInner, GroupByValues, JoinDir, JoinPane, Pane};
+pub use pane::{AggrPane, AggregateState, GroupByValues, JoinDir, JoinKeyValues, JoinPane, Pane};
 
-use std::cmp::Ordering;
+use std::{cmp::Ordering, collections::HashMap};
 
 use crate::{
     api::SpringError,
+    expr_resolver::ExprResolver,
     pipeline::{WindowOperationParameter, WindowParameter},
     stream_engine::{
-        autonomous_executor::task::window::watermark::Watermark,
+        autonomous_executor::{
+            performance_metrics::WindowInFlowByWindowTask, task::window::watermark::Watermark,
+        },
         time::{SpringDuration, SpringTimestamp},
     },
 };
@@ -25,6 +28,10 @@ where
     /// Sorted by `Pane::open_at`.
     panes: Vec<P>,
 
+    /// Panes already closed by the watermark but kept for allowed lateness.
+    /// `Pane::open_at` -> whether late rows are dispatched after the last close.
+    fired: HashMap<SpringTimestamp, bool>,
+
     window_param: WindowParameter,
     op_param: WindowOperationParameter,
 }
@@ -36,6 +43,7 @@ where
     pub fn new(window_param: WindowParameter, op_param: WindowOperationParameter) -> Self {
         Self {
             panes: vec![],
+            fired: HashMap::new(),
             window_param,
             op_param,
         }
@@ -44,49 +52,172 @@ where
     /// Generate new panes if not exists.
     /// Then, return all panes to get a tuple with the `rowtime`.
     ///
-    /// Caller must assure rowtime is not smaller than watermark.
+    /// Caller must assure rowtime is not smaller than `Watermark::lateness_limit()`.
+    ///
+    /// # Returns
+    ///
+    /// (window in-flow counting generated panes, panes to dispatch)
     pub fn panes_to_dispatch(
         &mut self,
         rowtime: SpringTimestamp,
-    ) -> Result<impl Iterator<Item = &mut P>, SpringError> {
-        self.generate_panes_if_not_exist(rowtime)?;
+    ) -> Result<(WindowInFlowByWindowTask, impl Iterator<Item = &mut P>), SpringError> {
+        let panes_opened = self.generate_panes_if_not_exist(rowtime)?;
+        let window_in_flow = WindowInFlowByWindowTask {
+            panes_opened,
+            ..WindowInFlowByWindowTask::zero()
+        };
 
-        Ok(self
+        for pane in self
             .panes
-            .iter_mut()
-            .filter(move |pane| pane.is_acceptable(&rowtime)))
+            .iter()
+            .filter(|pane| pane.is_acceptable(&rowtime))
+        {
+            if let Some(updated) = self.fired.get_mut(&pane.open_at()) {
+                *updated = true;
+            }
+        }
+
+        Ok((
+            window_in_flow,
+            self.panes
+                .iter_mut()
+                .filter(move |pane| pane.is_acceptable(&rowtime)),
+        ))
     }
 
-    pub fn remove_panes_to_close(&mut self, watermark: &Watermark) -> Vec<P> {
-        let mut panes_to_close = vec![];
+    pub fn window_param(&self) -> &WindowParameter {
+        &self.window_param
+    }
+
+    /// Closes panes the watermark has passed.
+    ///
+    /// A pane is removed after the watermark passes `close_at + allowed_lateness`.
+    /// Until then, the pane is kept and closed again whenever late rows are dispatched to it,
+    /// so that its (whole) updated result is output again.
+    ///
+    /// The first close of a pane is counted as `panes_closed` and the following ones as `late_merges` in the window in-flow.
+    ///
+    /// Nothing is output with `EMIT ON UPDATE`, where `Window::dispatch()` outputs results instead.
+    pub fn close_panes(
+        &mut self,
+        watermark: &Watermark,
+        expr_resolver: &ExprResolver,
+    ) -> (Vec<P::CloseOut>, WindowInFlowByWindowTask) {
+        let allowed_lateness = self.window_param.allowed_lateness().to_duration();
+        let emit_on_close = !self.window_param.emit_on_update();
+
+        let mut out = vec![];
+        let mut window_in_flow = WindowInFlowByWindowTask::zero();
 
         let mut idx = 0;
         while idx < self.panes.len() {
-            let pane = &mut self.panes[idx];
+            let pane = &self.panes[idx];
+            if !pane.should_close(watermark) {
+                idx += 1;
+                continue;
+            }
 
-            if pane.should_close(watermark) {
+            let (open_at, close_at) = (pane.open_at(), pane.close_at());
+            let already_fired = self.fired.contains_key(&open_at);
+            let has_new_rows = self.fired.get(&open_at).copied().unwrap_or(true);
+
+            let out_seq = if close_at + allowed_lateness <= watermark.as_timestamp() {
                 let pane = self.panes.remove(idx);
-                panes_to_close.push(pane);
+                self.fired.remove(&open_at);
+                self.trace(|| format!("pane [{:?}, {:?}) removed", open_at, close_at));
+
+                let (out_seq, window_in_flow_close) = pane.close(expr_resolver);
+                window_in_flow = window_in_flow + window_in_flow_close;
+                has_new_rows.then(|| out_seq)
             } else {
+                self.fired.insert(open_at, false);
+                // window in-flow is released when the pane is removed
+                let out_seq = has_new_rows.then(|| {
+                    if emit_on_close {
+                        pane.clone().close(expr_resolver).0
+                    } else {
+                        vec![]
+                    }
+                });
                 idx += 1;
+                out_seq
+            };
+
+            if let Some(mut out_seq) = out_seq {
+                if already_fired {
+                    window_in_flow.late_merges += 1;
+                    self.trace(|| format!("pane [{:?}, {:?}) merged late rows", open_at, close_at));
+                } else {
+                    window_in_flow.panes_closed += 1;
+                    self.trace(|| format!("pane [{:?}, {:?}) closed", open_at, close_at));
+                }
+                if emit_on_close {
+                    for close_out in &out_seq {
+                        self.trace(|| {
+                            format!(
+                                "pane [{:?}, {:?}) emitted {:?}",
+                                open_at, close_at, close_out
+                            )
+                        });
+                    }
+                    window_in_flow.emitted_rows += out_seq.len() as u64;
+                    out.append(&mut out_seq);
+                }
             }
         }
 
-        panes_to_close
+        (out, window_in_flow)
     }
 
     pub fn purge(&mut self) {
-        self.panes.clear()
+        self.panes.clear();
+        self.fired.clear();
+    }
+
+    /// Panes sorted by `Pane::open_at` with their `fired` flags (`None` if a pane is not closed yet).
+    pub fn iter_with_fired(&self) -> impl Iterator<Item = (&P, Option<bool>)> {
+        self.panes
+            .iter()
+            .map(|pane| (pane, self.fired.get(&pane.open_at()).copied()))
+    }
+
+    /// An empty pane for `[open_at, close_at)` to restore.
+    pub fn new_pane(&self, open_at: SpringTimestamp, close_at: SpringTimestamp) -> P {
+        P::new(open_at, close_at, self.op_param.clone())
+    }
+
+    /// Replaces all panes with restored ones and their `fired` flags.
+    pub fn restore(&mut self, panes: Vec<(P, Option<bool>)>) {
+        self.purge();
+        for (pane, fired) in panes {
+            if let Some(fired) = fired {
+                self.fired.insert(pane.open_at(), fired);
+            }
+            self.panes.push(pane);
+        }
+        self.panes.sort_by_key(|pane| pane.open_at());
     }
 
-    fn generate_panes_if_not_exist(&mut self, rowtime: SpringTimestamp) -> Result<(), SpringError> {
+    /// # Returns
+    ///
+    /// Number of generated panes.
+    fn generate_panes_if_not_exist(
+        &mut self,
+        rowtime: SpringTimestamp,
+    ) -> Result<u64, SpringError> {
         // Sort-Merge Join like algorithm
+        let mut generated = 0;
         let mut pane_idx = 0;
         for open_at in self.valid_open_at_s(rowtime)? {
             loop {
                 if pane_idx < self.panes.len() {
                     match open_at.cmp(&self.panes[pane_idx].open_at()) {
-                        Ordering::Less => unreachable!("watermark must kick this rowtime"),
+                        Ordering::Less => {
+                            // late row (within allowed delay or lateness) for a pane not generated yet
+                            self.panes.insert(pane_idx, self.generate_pane(open_at));
+                            generated += 1;
+                            break; // next open_at
+                        }
                         Ordering::Equal => {
                             // Pane already exists.
                             break; // next open_at
@@ -99,11 +230,12 @@ where
                 } else {
                     // no pane has the open_at
                     self.panes.push(self.generate_pane(open_at));
+                    generated += 1;
                     break; // next open_at
                 }
             }
         }
-        Ok(())
+        Ok(generated)
     }
 
     fn valid_open_at_s(
@@ -112,6 +244,10 @@ where
     ) -> Result<Vec<SpringTimestamp>, SpringError> {
         let mut ret = vec![];
 
+        // shifted so that panes open at multiples of period
+        let offset = self.window_param.offset().to_duration();
+        let rowtime = rowtime - offset;
+
         let leftmost_open_at = {
             let l = (rowtime - self.window_param.length().to_duration())
                 .ceil(self.window_param.period().to_duration())?;
@@ -127,7 +263,7 @@ where
 
         let mut open_at = leftmost_open_at;
         while open_at <= rightmost_open_at {
-            ret.push(open_at);
+            ret.push(open_at + offset);
             open_at = open_at + self.window_param.period().to_duration();
         }
 
@@ -136,8 +272,19 @@ where
 
     fn generate_pane(&self, open_at: SpringTimestamp) -> P {
         let close_at = open_at + self.window_param.length().to_duration();
+        self.trace(|| format!("pane [{:?}, {:?}) opened", open_at, close_at));
         P::new(open_at, close_at, self.op_param.clone())
     }
+
+    /// Logs a pane trigger if `TRACE TRIGGERS` is set to the window.
+    fn trace<F>(&self, message: F)
+    where
+        F: FnOnce() -> String,
+    {
+        if self.window_param.trace_triggers() {
+            log::info!("[Panes] {}", message());
+        }
+    }
 }
 
 #[cfg(test)]
@@ -175,13 +322,16 @@ mod tests {
             GroupByLabels::new(vec![expr_resolver.register_value_expr(group_by_expr)]);
 
         WindowOperationParameter::Aggregate(AggregateParameter {
-            aggr_func: AggregateFunctionParameter::Avg,
-            aggr_expr: if let ExprLabel::Aggr(l) = labels[0] {
-                l
-            } else {
-                unreachable!()
-            },
+            aggr_exprs: vec![(
+                AggregateFunctionParameter::Avg,
+                if let ExprLabel::Aggr(l) = labels[0] {
+                    l
+                } else {
+                    unreachable!()
+                },
+            )],
             group_by: group_by_labels,
+            window_metadata: vec![],
         })
     }
 
@@ -196,6 +346,11 @@ mod tests {
                     length,
                     period,
                     allowed_delay: SpringEventDuration::from_secs(0),
+                    allowed_lateness: SpringEventDuration::from_secs(0),
+                    min_watermark: false,
+                    input_idle_timeout: None,
+                    emit_on_update: false,
+                    trace_triggers: false,
                 },
                 dont_care_window_operation_parameter(),
             )
@@ -249,4 +404,38 @@ mod tests {
             vec![SpringTimestamp::from_str("2020-01-01 00:00:00.000000000").unwrap(),]
         );
     }
+
+    #[test]
+    fn test_valid_open_at_s_with_offset() {
+        let panes: Panes<AggrPane> = Panes::new(
+            WindowParameter::TimedFixedWindow {
+                length: SpringEventDuration::from_secs(10),
+                allowed_delay: SpringEventDuration::from_secs(0),
+                allowed_lateness: SpringEventDuration::from_secs(0),
+                min_watermark: false,
+                input_idle_timeout: None,
+                offset: SpringEventDuration::from_secs(3),
+                emit_on_update: false,
+                trace_triggers: false,
+            },
+            dont_care_window_operation_parameter(),
+        );
+
+        assert_eq!(
+            panes
+                .valid_open_at_s(
+                    SpringTimestamp::from_str("2020-01-01 00:00:02.999999999").unwrap()
+                )
+                .unwrap(),
+            vec![SpringTimestamp::from_str("2019-12-31 23:59:53.000000000").unwrap(),]
+        );
+        assert_eq!(
+            panes
+                .valid_open_at_s(
+                    SpringTimestamp::from_str("2020-01-01 00:00:03.000000000").unwrap()
+                )
+                .unwrap(),
+            vec![SpringTimestamp::from_str("2020-01-01 00:00:03.000000000").unwrap(),]
+        );
+    }
 }
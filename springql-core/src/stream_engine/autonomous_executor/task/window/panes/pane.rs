@@ -3,10 +3,13 @@
 mod aggregate_pane;
 mod join_pane;
 
-pub use aggregate_pane::{AggrPane, AggrPaneInner, GroupByValues};
-pub use join_pane::{JoinDir, JoinPane};
+pub use aggregate_pane::{AggrPane, AggregateState, GroupByValues};
+pub use join_pane::{JoinDir, JoinKeyValues, JoinPane};
+
+use std::fmt::Debug;
 
 use crate::{
+    api::error::Result,
     expr_resolver::ExprResolver,
     pipeline::WindowOperationParameter,
     stream_engine::{
@@ -18,8 +21,8 @@ use crate::{
     },
 };
 
-pub trait Pane {
-    type CloseOut;
+pub trait Pane: Clone {
+    type CloseOut: Debug;
     type DispatchArg: Clone;
 
     fn new(
@@ -39,13 +42,29 @@ pub trait Pane {
         self.close_at() <= watermark.as_timestamp()
     }
 
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - failed to evaluate expressions for `tuple`. The pane is kept as if `tuple` were not dispatched.
     fn dispatch(
         &mut self,
         expr_resolver: &ExprResolver,
         tuple: &Tuple,
         arg: Self::DispatchArg,
-    ) -> WindowInFlowByWindowTask;
+    ) -> Result<WindowInFlowByWindowTask>;
 
     fn close(self, expr_resolver: &ExprResolver)
         -> (Vec<Self::CloseOut>, WindowInFlowByWindowTask);
+
+    /// Current results updated by `tuple` just dispatched to the pane (`EMIT ON UPDATE`).
+    ///
+    /// Whole results of the pane by default.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - failed to evaluate expressions for `tuple`.
+    fn updated(&self, expr_resolver: &ExprResolver, _tuple: &Tuple) -> Result<Vec<Self::CloseOut>> {
+        Ok(self.clone().close(expr_resolver).0)
+    }
 }
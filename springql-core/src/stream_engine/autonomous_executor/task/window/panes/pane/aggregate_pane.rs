@@ -2,16 +2,16 @@
 
 mod aggregate_state;
 
+pub use aggregate_state::AggregateState;
+
 use std::collections::HashMap;
 
-use ordered_float::OrderedFloat;
+use anyhow::anyhow;
 
 use crate::{
-    api::error::Result,
+    api::error::{Result, SpringError},
     expr_resolver::ExprResolver,
-    pipeline::{
-        AggregateFunctionParameter, AggregateParameter, GroupByLabels, WindowOperationParameter,
-    },
+    pipeline::{AggregateParameter, GroupByLabels, WindowMetadata, WindowOperationParameter},
     stream_engine::{
         autonomous_executor::{
             performance_metrics::WindowInFlowByWindowTask,
@@ -19,7 +19,7 @@ use crate::{
                 tuple::Tuple,
                 window::{
                     aggregate::AggregatedAndGroupingValues,
-                    panes::pane::{aggregate_pane::aggregate_state::AvgState, Pane},
+                    panes::pane::{aggregate_pane::aggregate_state::AggregateState, Pane},
                 },
             },
         },
@@ -28,14 +28,15 @@ use crate::{
     },
 };
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct AggrPane {
     open_at: SpringTimestamp,
     close_at: SpringTimestamp,
 
     aggregate_parameter: AggregateParameter,
 
-    inner: AggrPaneInner,
+    /// States in the order of `aggregate_parameter.aggr_exprs` per group.
+    states: HashMap<GroupByValues, Vec<AggregateState>>,
 }
 
 impl Pane for AggrPane {
@@ -51,17 +52,11 @@ impl Pane for AggrPane {
         op_param: WindowOperationParameter,
     ) -> Self {
         if let WindowOperationParameter::Aggregate(aggregate_parameter) = op_param {
-            let inner = match aggregate_parameter.aggr_func {
-                AggregateFunctionParameter::Avg => AggrPaneInner::Avg {
-                    states: HashMap::new(),
-                },
-            };
-
             Self {
                 open_at,
                 close_at,
                 aggregate_parameter,
-                inner,
+                states: HashMap::new(),
             }
         } else {
             panic!("op_param {:?} is not GroupAggregateParameter", op_param)
@@ -76,85 +71,196 @@ impl Pane for AggrPane {
         self.close_at
     }
 
+    /// A new group is not created if `tuple` fails.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - failed to evaluate GROUP BY or aggregated expressions for `tuple`.
+    ///   - a GROUP BY expression is evaluated as NULL.
+    ///   - an aggregated value cannot be aggregated (e.g. SUM of TEXT).
     fn dispatch(
         &mut self,
         expr_resolver: &ExprResolver,
         tuple: &Tuple,
         _arg: (),
-    ) -> WindowInFlowByWindowTask {
+    ) -> Result<WindowInFlowByWindowTask> {
         let group_by_values = GroupByValues::from_group_by_labels(
             self.aggregate_parameter.group_by.clone(),
             expr_resolver,
             tuple,
-        )
-        .expect("TODO handle Result");
+        )?;
 
-        let aggregated_value = expr_resolver
-            .eval_aggr_expr_inner(self.aggregate_parameter.aggr_expr, tuple)
-            .expect("TODO Result");
-        let aggregated_value = if let SqlValue::NotNull(v) = aggregated_value {
-            v
-        } else {
-            unimplemented!("aggregation with NULL value is not supported")
-        };
-
-        match &mut self.inner {
-            AggrPaneInner::Avg { states } => {
-                let state = states
-                    .entry(group_by_values)
-                    .or_insert_with(AvgState::default);
-
-                state.next(
-                    aggregated_value
-                        .unpack::<f32>()
-                        .expect("only f32 is supported currently"),
-                );
-
-                WindowInFlowByWindowTask::zero() // state in AVG is constant
+        let aggr_exprs = &self.aggregate_parameter.aggr_exprs;
+        let aggregated_values = aggr_exprs
+            .iter()
+            .map(|(_, aggr_label)| expr_resolver.eval_aggr_expr_inner(*aggr_label, tuple))
+            .collect::<Result<Vec<_>>>()?;
+
+        match self.states.get_mut(&group_by_values) {
+            Some(states) => next_states(states, aggregated_values)?,
+            None => {
+                let mut states: Vec<AggregateState> = aggr_exprs
+                    .iter()
+                    .map(|(aggr_func, _)| AggregateState::new(*aggr_func))
+                    .collect();
+                next_states(&mut states, aggregated_values)?;
+                self.states.insert(group_by_values, states);
             }
         }
+
+        Ok(WindowInFlowByWindowTask::zero()) // state of an aggregate function is constant
     }
 
     fn close(
-        self,
+        mut self,
         _expr_resolver: &ExprResolver,
     ) -> (Vec<Self::CloseOut>, WindowInFlowByWindowTask) {
-        let aggr_label = self.aggregate_parameter.aggr_expr;
-        let group_by_labels = self.aggregate_parameter.group_by;
-
-        match self.inner {
-            AggrPaneInner::Avg { states } => {
-                let aggregated_and_grouping_values_seq = states
-                    .into_iter()
-                    .map(|(group_by_values, state)| {
-                        let aggr_value =
-                            SqlValue::NotNull(NnSqlValue::Float(OrderedFloat(state.finalize())));
-
-                        let group_bys = group_by_labels
-                            .as_labels()
-                            .iter()
-                            .cloned()
-                            .zip(group_by_values.into_sql_values())
-                            .collect();
-
-                        AggregatedAndGroupingValues::new(vec![(aggr_label, aggr_value)], group_bys)
-                    })
-                    .collect();
+        let aggregated_and_grouping_values_seq = std::mem::take(&mut self.states)
+            .into_iter()
+            .map(|(group_by_values, states)| {
+                self.aggregated_and_grouping_values(group_by_values, states)
+            })
+            .collect();
+
+        (
+            aggregated_and_grouping_values_seq,
+            WindowInFlowByWindowTask::zero(),
+        )
+    }
+
+    /// Current result of the group `tuple` belongs to.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - failed to evaluate GROUP BY expressions for `tuple`.
+    ///   - a GROUP BY expression is evaluated as NULL.
+    fn updated(&self, expr_resolver: &ExprResolver, tuple: &Tuple) -> Result<Vec<Self::CloseOut>> {
+        let group_by_values = GroupByValues::from_group_by_labels(
+            self.aggregate_parameter.group_by.clone(),
+            expr_resolver,
+            tuple,
+        )?;
+        Ok(self
+            .states
+            .get(&group_by_values)
+            .map(|states| self.aggregated_and_grouping_values(group_by_values, states.clone()))
+            .into_iter()
+            .collect())
+    }
+}
 
-                (
-                    aggregated_and_grouping_values_seq,
-                    WindowInFlowByWindowTask::zero(),
-                )
+impl AggrPane {
+    fn aggregated_and_grouping_values(
+        &self,
+        group_by_values: GroupByValues,
+        states: Vec<AggregateState>,
+    ) -> AggregatedAndGroupingValues {
+        // every state has the same number of rows
+        let n_rows = states.first().map_or(0, AggregateState::n_rows);
+
+        let window_metadata_values = self
+            .aggregate_parameter
+            .window_metadata
+            .iter()
+            .map(|(label, window_metadata)| {
+                let value = match window_metadata {
+                    WindowMetadata::WindowStart => NnSqlValue::Timestamp(self.open_at),
+                    WindowMetadata::WindowEnd => NnSqlValue::Timestamp(self.close_at),
+                    WindowMetadata::PaneRowCount => NnSqlValue::BigInt(n_rows as i64),
+                };
+                (*label, SqlValue::NotNull(value))
+            })
+            .collect();
+
+        let aggr_values = self
+            .aggregate_parameter
+            .aggr_exprs
+            .iter()
+            .map(|(_, aggr_label)| *aggr_label)
+            .zip(states.into_iter().map(AggregateState::finalize))
+            .collect();
+
+        let group_bys = self
+            .aggregate_parameter
+            .group_by
+            .as_labels()
+            .iter()
+            .cloned()
+            .zip(group_by_values.into_sql_values())
+            .collect();
+
+        AggregatedAndGroupingValues::new(aggr_values, group_bys, window_metadata_values)
+    }
+
+    /// Merges groups of `other` (e.g. a slice of this pane) into this pane.
+    /// A group is not changed if it fails to merge.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - states of a group fail to merge. Other groups are merged.
+    pub fn merge(&mut self, other: &AggrPane) -> Result<()> {
+        let mut result = Ok(());
+        for (group_by_values, other_states) in &other.states {
+            match self.states.get_mut(group_by_values) {
+                Some(states) => {
+                    let mut merged = states.clone();
+                    let merge_result = merged
+                        .iter_mut()
+                        .zip(other_states)
+                        .try_for_each(|(state, other_state)| state.merge(other_state));
+                    match merge_result {
+                        Ok(()) => *states = merged,
+                        Err(e) => result = Err(e),
+                    }
+                }
+                None => {
+                    self.states
+                        .insert(group_by_values.clone(), other_states.clone());
+                }
             }
         }
+        result
+    }
+
+    /// (GROUP BY values, states in the order of aggregate expressions) per group.
+    pub fn snapshot_groups(&self) -> Vec<(Vec<NnSqlValue>, Vec<AggregateState>)> {
+        self.states
+            .iter()
+            .map(|(group_by_values, states)| (group_by_values.0.clone(), states.clone()))
+            .collect()
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - the number of states in a group differs from the number of aggregate expressions.
+    pub fn restore_groups(
+        &mut self,
+        groups: Vec<(Vec<NnSqlValue>, Vec<AggregateState>)>,
+    ) -> Result<()> {
+        let n_aggr_exprs = self.aggregate_parameter.aggr_exprs.len();
+        for (group_by_values, states) in groups {
+            if states.len() != n_aggr_exprs {
+                return Err(SpringError::Sql(anyhow!(
+                    "{} aggregate states in the snapshot for {} aggregate expressions",
+                    states.len(),
+                    n_aggr_exprs
+                )));
+            }
+            self.states.insert(GroupByValues(group_by_values), states);
+        }
+        Ok(())
     }
 }
 
-#[derive(Debug)]
-pub enum AggrPaneInner {
-    Avg {
-        states: HashMap<GroupByValues, AvgState>,
-    },
+fn next_states(states: &mut [AggregateState], aggregated_values: Vec<SqlValue>) -> Result<()> {
+    states
+        .iter_mut()
+        .zip(aggregated_values)
+        .try_for_each(|(state, value)| state.next(value))
 }
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
@@ -165,6 +271,12 @@ pub struct GroupByValues(
 
 impl GroupByValues {
     /// Order of elements in GROUP BY clause is preserved.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - failed to evaluate a GROUP BY expression for `tuple`.
+    ///   - a GROUP BY expression is evaluated as NULL.
     fn from_group_by_labels(
         group_by_labels: GroupByLabels,
         expr_resolver: &ExprResolver,
@@ -173,13 +285,17 @@ impl GroupByValues {
         let values = group_by_labels
             .as_labels()
             .iter()
-            .map(|group_by_label| {
+            .enumerate()
+            .map(|(i, group_by_label)| {
                 let group_by_value = expr_resolver.eval_value_expr(*group_by_label, tuple)?;
 
                 if let SqlValue::NotNull(v) = group_by_value {
                     Ok(v)
                 } else {
-                    unimplemented!("group by NULL is not supported ")
+                    Err(SpringError::Sql(anyhow!(
+                        "GROUP BY #{} is NULL, which is not supported yet",
+                        i + 1
+                    )))
                 }
             })
             .collect::<Result<Vec<_>>>()?;
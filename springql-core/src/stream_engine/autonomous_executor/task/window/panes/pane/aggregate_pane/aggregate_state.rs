@@ -1,7 +1,158 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use anyhow::anyhow;
+use ordered_float::OrderedFloat;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{AggregateFunctionParameter, NumericComparableType, SqlType},
+    stream_engine::{NnSqlValue, SqlCompareResult, SqlValue},
+};
+
+/// State of an aggregate function for a group in a pane.
+///
+/// NULLs are not aggregated but counted in `n_rows()`.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct AggregateState {
+    n_rows: u64,
+    func_state: FuncState,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+enum FuncState {
+    Avg(AvgState),
+    Count(u64),
+    Sum(SumState),
+    Min(Option<NnSqlValue>),
+    Max(Option<NnSqlValue>),
+}
+
+impl AggregateState {
+    pub fn new(func: AggregateFunctionParameter) -> Self {
+        let func_state = match func {
+            AggregateFunctionParameter::Avg => FuncState::Avg(AvgState::default()),
+            AggregateFunctionParameter::Count => FuncState::Count(0),
+            AggregateFunctionParameter::Sum => FuncState::Sum(SumState::default()),
+            AggregateFunctionParameter::Min => FuncState::Min(None),
+            AggregateFunctionParameter::Max => FuncState::Max(None),
+        };
+        Self {
+            n_rows: 0,
+            func_state,
+        }
+    }
+
+    /// The state is not changed on failure.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `value` is not numeric for AVG and SUM, or not comparable with previous values for MIN and MAX.
+    pub fn next(&mut self, value: SqlValue) -> Result<()> {
+        let value = match value {
+            SqlValue::Null => {
+                self.n_rows += 1;
+                return Ok(());
+            }
+            SqlValue::NotNull(v) => v,
+        };
+        match &mut self.func_state {
+            FuncState::Avg(state) => state.next(value.unpack::<f32>()?),
+            FuncState::Count(n) => *n += 1,
+            FuncState::Sum(state) => state.next(value)?,
+            FuncState::Min(min) => Self::replace_if(min, value, SqlCompareResult::LessThan)?,
+            FuncState::Max(max) => Self::replace_if(max, value, SqlCompareResult::GreaterThan)?,
+        }
+        self.n_rows += 1;
+        Ok(())
+    }
+
+    /// Merges a state of the same aggregate function for other rows (e.g. rows in another slice of a pane).
+    /// The state is not changed on failure.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `other` is a state of another aggregate function.
+    ///   - SUM of values in another type, or MIN and MAX of values not comparable with each other.
+    pub fn merge(&mut self, other: &AggregateState) -> Result<()> {
+        let func_state = match (&self.func_state, &other.func_state) {
+            (FuncState::Avg(state), FuncState::Avg(other)) => FuncState::Avg(state.merge(other)),
+            (FuncState::Count(n), FuncState::Count(other)) => FuncState::Count(n + other),
+            (FuncState::Sum(state), FuncState::Sum(other)) => FuncState::Sum(state.merge(other)?),
+            (FuncState::Min(min), FuncState::Min(other)) => {
+                FuncState::Min(Self::merge_if(min, other, SqlCompareResult::LessThan)?)
+            }
+            (FuncState::Max(max), FuncState::Max(other)) => {
+                FuncState::Max(Self::merge_if(max, other, SqlCompareResult::GreaterThan)?)
+            }
+            (state, other) => {
+                return Err(SpringError::Sql(anyhow!(
+                    "cannot merge aggregate states {:?} and {:?}",
+                    state,
+                    other
+                )))
+            }
+        };
+        self.func_state = func_state;
+        self.n_rows += other.n_rows;
+        Ok(())
+    }
+
+    fn merge_if(
+        current: &Option<NnSqlValue>,
+        other: &Option<NnSqlValue>,
+        cond: SqlCompareResult,
+    ) -> Result<Option<NnSqlValue>> {
+        let mut merged = current.clone();
+        if let Some(other) = other {
+            Self::replace_if(&mut merged, other.clone(), cond)?;
+        }
+        Ok(merged)
+    }
+
+    fn replace_if(
+        current: &mut Option<NnSqlValue>,
+        value: NnSqlValue,
+        cond: SqlCompareResult,
+    ) -> Result<()> {
+        let replace = match current {
+            None => true,
+            Some(current) => value.sql_compare(current)? == cond,
+        };
+        if replace {
+            *current = Some(value);
+        }
+        Ok(())
+    }
+
+    /// Number of rows dispatched, including NULLs.
+    pub fn n_rows(&self) -> u64 {
+        self.n_rows
+    }
+
+    /// - AVG: FLOAT
+    /// - COUNT: BIGINT
+    /// - SUM: BIGINT for signed integers, UNSIGNED BIGINT for unsigned integers, FLOAT for FLOAT
+    /// - MIN, MAX: type of aggregated values
+    ///
+    /// NULL if no value but NULL is aggregated, except for COUNT.
+    pub fn finalize(self) -> SqlValue {
+        let value = match self.func_state {
+            FuncState::Avg(state) => {
+                (state.n() > 0).then(|| NnSqlValue::Float(OrderedFloat(state.finalize())))
+            }
+            FuncState::Count(n) => Some(NnSqlValue::BigInt(n as i64)),
+            FuncState::Sum(state) => state.finalize(),
+            FuncState::Min(v) | FuncState::Max(v) => v,
+        };
+        value.map_or(SqlValue::Null, SqlValue::NotNull)
+    }
+}
+
 // TODO more generic avg
-#[derive(Debug, Default)]
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
 pub struct AvgState {
     current_avg: f32,
     current_n: u64,
@@ -20,11 +171,119 @@ impl AvgState {
         self.current_n = next_n;
     }
 
+    pub fn merge(&self, other: &AvgState) -> AvgState {
+        let n = self.current_n + other.current_n;
+        if n == 0 {
+            AvgState::default()
+        } else {
+            let avg = (self.current_avg * self.current_n as f32
+                + other.current_avg * other.current_n as f32)
+                / n as f32;
+            AvgState {
+                current_avg: avg,
+                current_n: n,
+            }
+        }
+    }
+
+    /// Number of values aggregated.
+    pub fn n(&self) -> u64 {
+        self.current_n
+    }
+
     pub fn finalize(self) -> f32 {
         self.current_avg.round()
     }
 }
 
+/// Integers saturate instead of overflowing.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub enum SumState {
+    #[default]
+    Empty,
+    I64(i64),
+    U64(u64),
+    F32(f32),
+}
+
+impl SumState {
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `value` is not numeric or its type differs from previous values.
+    pub fn next(&mut self, value: NnSqlValue) -> Result<()> {
+        let n = match value.sql_type() {
+            SqlType::NumericComparable(n) => n,
+            _ => {
+                return Err(SpringError::Sql(anyhow!(
+                    "SUM only supports numeric values: {:?}",
+                    value
+                )))
+            }
+        };
+
+        *self = match (&*self, n) {
+            (SumState::Empty, NumericComparableType::I64Loose(_)) => {
+                SumState::I64(value.unpack::<i64>()?)
+            }
+            (SumState::Empty, NumericComparableType::U64Loose(_)) => {
+                SumState::U64(value.unpack::<u64>()?)
+            }
+            (SumState::Empty, NumericComparableType::F32Loose(_)) => {
+                SumState::F32(value.unpack::<f32>()?)
+            }
+            (SumState::I64(sum), NumericComparableType::I64Loose(_)) => {
+                SumState::I64(sum.saturating_add(value.unpack::<i64>()?))
+            }
+            (SumState::U64(sum), NumericComparableType::U64Loose(_)) => {
+                SumState::U64(sum.saturating_add(value.unpack::<u64>()?))
+            }
+            (SumState::F32(sum), NumericComparableType::F32Loose(_)) => {
+                SumState::F32(sum + value.unpack::<f32>()?)
+            }
+            (state, n) => {
+                return Err(SpringError::Sql(anyhow!(
+                    "SUM of {:?} cannot add {:?}",
+                    state,
+                    n
+                )))
+            }
+        };
+        Ok(())
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `other` is a sum of values in another type.
+    pub fn merge(&self, other: &SumState) -> Result<SumState> {
+        match (self, other) {
+            (SumState::Empty, state) | (state, SumState::Empty) => Ok(state.clone()),
+            (SumState::I64(sum), SumState::I64(other)) => {
+                Ok(SumState::I64(sum.saturating_add(*other)))
+            }
+            (SumState::U64(sum), SumState::U64(other)) => {
+                Ok(SumState::U64(sum.saturating_add(*other)))
+            }
+            (SumState::F32(sum), SumState::F32(other)) => Ok(SumState::F32(sum + other)),
+            (state, other) => Err(SpringError::Sql(anyhow!(
+                "SUM of {:?} cannot add {:?}",
+                state,
+                other
+            ))),
+        }
+    }
+
+    pub fn finalize(self) -> Option<NnSqlValue> {
+        match self {
+            SumState::Empty => None,
+            SumState::I64(sum) => Some(NnSqlValue::BigInt(sum)),
+            SumState::U64(sum) => Some(NnSqlValue::UnsignedBigInt(sum)),
+            SumState::F32(sum) => Some(NnSqlValue::Float(OrderedFloat(sum))),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -37,4 +296,124 @@ mod tests {
         state.next(100.);
         assert_eq!(state.finalize().round() as i32, 200);
     }
+
+    fn aggregate(func: AggregateFunctionParameter, values: Vec<SqlValue>) -> (SqlValue, u64) {
+        let mut state = AggregateState::new(func);
+        for v in values {
+            state.next(v).unwrap();
+        }
+        let n_rows = state.n_rows();
+        (state.finalize(), n_rows)
+    }
+
+    #[test]
+    fn test_aggregate_state() {
+        let values = || {
+            vec![
+                SqlValue::NotNull(NnSqlValue::Integer(3)),
+                SqlValue::Null,
+                SqlValue::NotNull(NnSqlValue::Integer(-5)),
+                SqlValue::NotNull(NnSqlValue::Integer(10)),
+            ]
+        };
+
+        let (count, n_rows) = aggregate(AggregateFunctionParameter::Count, values());
+        assert_eq!(count, SqlValue::NotNull(NnSqlValue::BigInt(3)));
+        assert_eq!(n_rows, 4);
+
+        assert_eq!(
+            aggregate(AggregateFunctionParameter::Sum, values()).0,
+            SqlValue::NotNull(NnSqlValue::BigInt(8))
+        );
+        assert_eq!(
+            aggregate(AggregateFunctionParameter::Min, values()).0,
+            SqlValue::NotNull(NnSqlValue::Integer(-5))
+        );
+        assert_eq!(
+            aggregate(AggregateFunctionParameter::Max, values()).0,
+            SqlValue::NotNull(NnSqlValue::Integer(10))
+        );
+
+        let (sum, n_rows) = aggregate(AggregateFunctionParameter::Sum, vec![SqlValue::Null]);
+        assert!(matches!(sum, SqlValue::Null));
+        assert_eq!(n_rows, 1);
+        assert_eq!(
+            aggregate(AggregateFunctionParameter::Count, vec![SqlValue::Null]).0,
+            SqlValue::NotNull(NnSqlValue::BigInt(0))
+        );
+    }
+
+    #[test]
+    fn test_aggregate_state_merge() {
+        let values = || {
+            vec![
+                SqlValue::NotNull(NnSqlValue::Integer(3)),
+                SqlValue::Null,
+                SqlValue::NotNull(NnSqlValue::Integer(-5)),
+                SqlValue::NotNull(NnSqlValue::Integer(10)),
+                SqlValue::NotNull(NnSqlValue::Integer(4)),
+            ]
+        };
+
+        for func in [
+            AggregateFunctionParameter::Avg,
+            AggregateFunctionParameter::Count,
+            AggregateFunctionParameter::Sum,
+            AggregateFunctionParameter::Min,
+            AggregateFunctionParameter::Max,
+        ] {
+            // split into [3, NULL] and [-5, 10, 4]
+            let mut left = AggregateState::new(func);
+            let mut right = AggregateState::new(func);
+            for (i, v) in values().into_iter().enumerate() {
+                if i < 2 {
+                    left.next(v).unwrap()
+                } else {
+                    right.next(v).unwrap()
+                }
+            }
+            left.merge(&right).unwrap();
+
+            let (expected, n_rows) = aggregate(func, values());
+            assert_eq!(left.n_rows(), n_rows);
+            assert_eq!(left.finalize(), expected, "{:?}", func);
+        }
+
+        // merging an empty state
+        let mut state = AggregateState::new(AggregateFunctionParameter::Min);
+        state
+            .merge(&AggregateState::new(AggregateFunctionParameter::Min))
+            .unwrap();
+        assert_eq!(state.finalize(), SqlValue::Null);
+
+        let mut sum = AggregateState::new(AggregateFunctionParameter::Sum);
+        sum.next(SqlValue::NotNull(NnSqlValue::Integer(3))).unwrap();
+        let mut float_sum = AggregateState::new(AggregateFunctionParameter::Sum);
+        float_sum
+            .next(SqlValue::NotNull(NnSqlValue::Float(OrderedFloat(1.5))))
+            .unwrap();
+        assert!(sum.merge(&float_sum).is_err());
+        assert!(sum
+            .merge(&AggregateState::new(AggregateFunctionParameter::Count))
+            .is_err());
+
+        // failed merge does not change the state
+        assert_eq!(sum.n_rows(), 1);
+        assert_eq!(sum.finalize(), SqlValue::NotNull(NnSqlValue::BigInt(3)));
+    }
+
+    #[test]
+    fn test_aggregate_state_invalid_value() {
+        let mut state = AggregateState::new(AggregateFunctionParameter::Sum);
+        state
+            .next(SqlValue::NotNull(NnSqlValue::Integer(3)))
+            .unwrap();
+        assert!(state
+            .next(SqlValue::NotNull(NnSqlValue::Text("x".to_string())))
+            .is_err());
+
+        // failed value is not counted
+        assert_eq!(state.n_rows(), 1);
+        assert_eq!(state.finalize(), SqlValue::NotNull(NnSqlValue::BigInt(3)));
+    }
 }
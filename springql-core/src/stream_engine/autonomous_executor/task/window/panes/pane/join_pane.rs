@@ -1,18 +1,19 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use std::str::FromStr;
+use std::collections::HashMap;
 
 use crate::{
-    expr_resolver::ExprResolver,
+    api::error::Result,
+    expr_resolver::{ExprResolver, ValueExprLabel},
     mem_size::MemSize,
-    pipeline::{Field, JoinParameter, JoinType, WindowOperationParameter},
+    pipeline::{JoinBuildSide, JoinParameter, JoinType, WindowOperationParameter},
     stream_engine::{
         autonomous_executor::{
             performance_metrics::WindowInFlowByWindowTask,
             task::{tuple::Tuple, window::panes::pane::Pane},
         },
         time::SpringTimestamp,
-        RowTime, SqlValue,
+        NnSqlValue, SqlValue,
     },
 };
 
@@ -22,15 +23,17 @@ pub enum JoinDir {
     Right,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct JoinPane {
     open_at: SpringTimestamp,
     close_at: SpringTimestamp,
 
     join_parameter: JoinParameter,
 
-    left_tuples: Vec<Tuple>,
-    right_tuples: Vec<Tuple>,
+    /// `None` key if any of key values is NULL.
+    left_tuples: Vec<(Option<JoinKeyValues>, Tuple)>,
+    /// Tuples with NULL key values are not kept since they never match.
+    right_tuples: Vec<(JoinKeyValues, Tuple)>,
 }
 
 impl Pane for JoinPane {
@@ -68,18 +71,32 @@ impl Pane for JoinPane {
         self.close_at
     }
 
-    /// Dispatch to left_tuples
+    /// Dispatch to left_tuples or right_tuples by join key
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - failed to evaluate join keys for `tuple`.
     fn dispatch(
         &mut self,
-        _expr_resolver: &ExprResolver,
+        expr_resolver: &ExprResolver,
         tuple: &Tuple,
         dir: JoinDir,
-    ) -> WindowInFlowByWindowTask {
-        match dir {
-            JoinDir::Left => self.left_tuples.push(tuple.clone()),
-            JoinDir::Right => self.right_tuples.push(tuple.clone()),
+    ) -> Result<WindowInFlowByWindowTask> {
+        let key_labels = self.join_parameter.keys.iter().map(|key| match dir {
+            JoinDir::Left => key.left,
+            JoinDir::Right => key.right,
+        });
+        let key_values = JoinKeyValues::from_key_labels(key_labels, expr_resolver, tuple)?;
+
+        match (dir, key_values) {
+            (JoinDir::Left, key_values) => self.left_tuples.push((key_values, tuple.clone())),
+            (JoinDir::Right, Some(key_values)) => {
+                self.right_tuples.push((key_values, tuple.clone()))
+            }
+            (JoinDir::Right, None) => return Ok(WindowInFlowByWindowTask::zero()),
         }
-        WindowInFlowByWindowTask::new(0, tuple.mem_size() as i64)
+        Ok(WindowInFlowByWindowTask::new(0, tuple.stored_size() as i64))
     }
 
     fn close(
@@ -99,22 +116,33 @@ impl JoinPane {
     ) -> (Vec<Tuple>, WindowInFlowByWindowTask) {
         let window_in_flow = self.calc_window_in_flow_on_close();
 
-        let null_right = self.null_right_tuple();
+        let null_right = Tuple::new_nulls(&self.join_parameter.right_colrefs);
+
+        // using Hash Join by join key (Nested Loop Join if no key).
+        let right_tuples_by_left = self.right_tuples_by_left();
 
-        // using Nested Loop Join.
         let mut res_tuples = Vec::new();
-        for left_tuple in self.left_tuples {
+        for ((_, left_tuple), right_tuples) in self.left_tuples.iter().zip(right_tuples_by_left) {
             let mut joined_to_the_left = vec![];
 
-            for right_tuple in &self.right_tuples {
+            for right_tuple in right_tuples {
                 // TODO less clone. ExprResolver takes two tuples to resolve ColumnReference?
                 let joined_tuple = left_tuple.clone().join(right_tuple.clone());
 
-                let on_bool = expr_resolver
-                    .eval_value_expr(self.join_parameter.on_expr, &joined_tuple)
-                    .expect("TODO Result")
-                    .to_bool()
-                    .expect("TODO Result");
+                let on_bool = match self.join_parameter.on_expr {
+                    Some(on_expr) => expr_resolver
+                        .eval_value_expr(on_expr, &joined_tuple)
+                        .and_then(|on_value| on_value.to_bool())
+                        .unwrap_or_else(|e| {
+                            // the pane is closed at once; a failed pair should not discard the others
+                            log::warn!(
+                                "[JoinPane] failed to evaluate ON clause, treated as FALSE: {:?}",
+                                e
+                            );
+                            false
+                        }),
+                    None => true,
+                };
 
                 if on_bool {
                     joined_to_the_left.push(joined_tuple);
@@ -122,7 +150,7 @@ impl JoinPane {
             }
 
             if joined_to_the_left.is_empty() {
-                let joined_tuple = left_tuple.join(null_right.clone());
+                let joined_tuple = left_tuple.clone().join(null_right.clone());
                 joined_to_the_left.push(joined_tuple);
             }
 
@@ -132,29 +160,94 @@ impl JoinPane {
         (res_tuples, window_in_flow)
     }
 
+    /// Build side of the hash join: `JOIN_BUILD_SIDE` option of the pump if set.
+    /// Otherwise the side with fewer tuples in this pane, so that the hash table is small.
+    fn build_side(&self) -> JoinDir {
+        match self.join_parameter.build_side {
+            JoinBuildSide::Left => JoinDir::Left,
+            JoinBuildSide::Right => JoinDir::Right,
+            JoinBuildSide::Auto if self.left_tuples.len() < self.right_tuples.len() => {
+                JoinDir::Left
+            }
+            JoinBuildSide::Auto => JoinDir::Right,
+        }
+    }
+
+    /// Right tuples with the same key as each left tuple (in the order of `left_tuples`), in arrival order.
+    fn right_tuples_by_left(&self) -> Vec<Vec<&Tuple>> {
+        match self.build_side() {
+            JoinDir::Right => {
+                let mut hash_table: HashMap<&JoinKeyValues, Vec<&Tuple>> = HashMap::new();
+                for (key_values, right_tuple) in &self.right_tuples {
+                    hash_table.entry(key_values).or_default().push(right_tuple);
+                }
+
+                self.left_tuples
+                    .iter()
+                    .map(|(key_values, _)| {
+                        key_values
+                            .as_ref()
+                            .and_then(|key_values| hash_table.get(key_values))
+                            .cloned()
+                            .unwrap_or_default()
+                    })
+                    .collect()
+            }
+            JoinDir::Left => {
+                let mut hash_table: HashMap<&JoinKeyValues, Vec<usize>> = HashMap::new();
+                for (i, (key_values, _)) in self.left_tuples.iter().enumerate() {
+                    if let Some(key_values) = key_values {
+                        hash_table.entry(key_values).or_default().push(i);
+                    }
+                }
+
+                let mut right_tuples_by_left = vec![vec![]; self.left_tuples.len()];
+                for (key_values, right_tuple) in &self.right_tuples {
+                    for i in hash_table.get(key_values).into_iter().flatten() {
+                        right_tuples_by_left[*i].push(right_tuple);
+                    }
+                }
+                right_tuples_by_left
+            }
+        }
+    }
+
     fn calc_window_in_flow_on_close(&self) -> WindowInFlowByWindowTask {
-        let left_size = self.left_tuples.iter().map(|t| t.mem_size()).sum::<usize>();
+        let left_size = self
+            .left_tuples
+            .iter()
+            .map(|(_, t)| t.stored_size())
+            .sum::<usize>();
         let right_size = self
             .right_tuples
             .iter()
-            .map(|t| t.mem_size())
+            .map(|(_, t)| t.stored_size())
             .sum::<usize>();
 
         WindowInFlowByWindowTask::new(0, -((left_size + right_size) as i64))
     }
+}
 
-    fn null_right_tuple(&self) -> Tuple {
-        // unused
-        let rowtime =
-            RowTime::EventTime(SpringTimestamp::from_str("1970-01-01 00:00:00.000000000").unwrap());
-
-        let fields = self
-            .join_parameter
-            .right_colrefs
-            .iter()
-            .map(|colref| Field::new(colref.clone(), SqlValue::Null))
-            .collect();
+/// Values of join keys of a tuple. Empty if the join has no key.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct JoinKeyValues(Vec<NnSqlValue>);
 
-        Tuple::new(rowtime, fields)
+impl JoinKeyValues {
+    /// # Returns
+    ///
+    /// `None` if any of key values is NULL (NULL never equals to anything).
+    pub fn from_key_labels(
+        key_labels: impl Iterator<Item = ValueExprLabel>,
+        expr_resolver: &ExprResolver,
+        tuple: &Tuple,
+    ) -> Result<Option<Self>> {
+        let mut values = Vec::new();
+        for key_label in key_labels {
+            match expr_resolver.eval_value_expr(key_label, tuple)? {
+                SqlValue::NotNull(v) => values.push(v),
+                SqlValue::Null => return Ok(None),
+            }
+        }
+        Ok(Some(Self(values)))
     }
 }
@@ -0,0 +1,278 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{collections::BTreeMap, time::Duration};
+
+use crate::{
+    api::error::Result,
+    expr_resolver::ExprResolver,
+    pipeline::{WindowOperationParameter, WindowParameter},
+    stream_engine::{
+        autonomous_executor::{
+            performance_metrics::WindowInFlowByWindowTask,
+            task::window::{
+                aggregate::AggregatedAndGroupingValues,
+                panes::{AggrPane, Pane},
+                watermark::Watermark,
+            },
+        },
+        time::{SpringDuration, SpringEventDuration, SpringTimestamp},
+        Tuple,
+    },
+};
+
+/// Slices shared among overlapping panes of a sliding window aggregation.
+///
+/// ```text
+/// length = 10sec, period = 4sec -> slice = 2sec;
+///
+/// pane1 |                   |
+/// pane2         |                   |
+/// pane3                 |                   |
+/// slices|   |   |   |   |   |   |   |   |   |
+///
+/// -----------------------------------------------> t
+///      :00     :04     :08     :12     :16
+/// ```
+///
+/// A row is aggregated only into its slice of `gcd(length, period)`, instead of into `length / period` panes,
+/// and a pane merges aggregate states of its slices when it closes.
+/// So the cost of a row and the number of aggregate states do not grow with the overlap of panes.
+///
+/// Closed panes are kept for allowed lateness and output again on late rows, as `Panes` does.
+#[derive(Debug)]
+pub struct AggrSlices {
+    slice_length: SpringEventDuration,
+
+    /// `Pane::open_at` -> slice as a pane for `[open_at, open_at + slice_length)`.
+    slices: BTreeMap<SpringTimestamp, AggrPane>,
+
+    /// `open_at` of panes with any row -> `None` until the pane is closed,
+    /// and then whether late rows are dispatched after the last close.
+    panes: BTreeMap<SpringTimestamp, Option<bool>>,
+
+    window_param: WindowParameter,
+    op_param: WindowOperationParameter,
+}
+
+impl AggrSlices {
+    /// `None` unless `window_param` is a sliding window with overlapping panes (`length > period`).
+    ///
+    /// `EMIT ON UPDATE` windows use panes instead, since each row outputs results of all panes it belongs to.
+    pub fn new(window_param: WindowParameter, op_param: WindowOperationParameter) -> Option<Self> {
+        let (length, period) = match &window_param {
+            WindowParameter::TimedSlidingWindow {
+                length,
+                period,
+                emit_on_update: false,
+                ..
+            } if length > period => (*length, *period),
+            _ => return None,
+        };
+
+        let slice_nanos = gcd(length.as_std().as_nanos(), period.as_std().as_nanos());
+        Some(Self {
+            slice_length: SpringEventDuration::from_std(Duration::from_nanos(slice_nanos as u64)),
+            slices: BTreeMap::new(),
+            panes: BTreeMap::new(),
+            window_param,
+            op_param,
+        })
+    }
+
+    /// Dispatches a tuple not too late into its slice, and closes panes the watermark has passed.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - failed to evaluate expressions for `tuple`.
+    pub fn dispatch(
+        &mut self,
+        expr_resolver: &ExprResolver,
+        tuple: &Tuple,
+        watermark: &Watermark,
+    ) -> Result<(Vec<AggregatedAndGroupingValues>, WindowInFlowByWindowTask)> {
+        let rowtime = tuple.rowtime().as_timestamp();
+
+        let slice_length = self.slice_length.to_duration();
+        let slice_open_at = rowtime.floor(slice_length)?;
+        let op_param = &self.op_param;
+        self.slices
+            .entry(slice_open_at)
+            .or_insert_with(|| {
+                AggrPane::new(
+                    slice_open_at,
+                    slice_open_at + slice_length,
+                    op_param.clone(),
+                )
+            })
+            .dispatch(expr_resolver, tuple, ())?;
+
+        let mut window_in_flow = WindowInFlowByWindowTask::zero();
+        for open_at in self.pane_open_at_s(rowtime)? {
+            match self.panes.get_mut(&open_at) {
+                Some(Some(updated)) => *updated = true,
+                Some(None) => {}
+                None => {
+                    self.panes.insert(open_at, None);
+                    window_in_flow.panes_opened += 1;
+                    self.trace(|| format!("pane [{:?}, ...) opened", open_at));
+                }
+            }
+        }
+
+        let (out, window_in_flow_close) = self.close_panes(watermark, expr_resolver);
+        Ok((out, window_in_flow + window_in_flow_close))
+    }
+
+    /// Slices sorted by `Pane::open_at`.
+    pub fn iter(&self) -> impl Iterator<Item = &AggrPane> {
+        self.slices.values()
+    }
+
+    /// Replaces all slices with restored ones.
+    /// Panes the watermark has passed are regarded as closed without late rows.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Time` when:
+    ///   - `Pane::open_at` of a slice is out of range.
+    pub fn restore(&mut self, slices: Vec<AggrPane>, watermark: &Watermark) -> Result<()> {
+        self.purge();
+
+        let length = self.window_param.length().to_duration();
+        for slice in slices {
+            for open_at in self.pane_open_at_s(slice.open_at())? {
+                let closed = open_at + length <= watermark.as_timestamp();
+                self.panes
+                    .entry(open_at)
+                    .or_insert(if closed { Some(false) } else { None });
+            }
+            self.slices.insert(slice.open_at(), slice);
+        }
+        Ok(())
+    }
+
+    pub fn purge(&mut self) {
+        self.slices.clear();
+        self.panes.clear();
+    }
+
+    /// Closes panes the watermark has passed, merging their slices. Then removes slices no pane needs.
+    ///
+    /// Window in-flow is counted as `Panes::close_panes()` does.
+    fn close_panes(
+        &mut self,
+        watermark: &Watermark,
+        expr_resolver: &ExprResolver,
+    ) -> (Vec<AggregatedAndGroupingValues>, WindowInFlowByWindowTask) {
+        let length = self.window_param.length().to_duration();
+        let allowed_lateness = self.window_param.allowed_lateness().to_duration();
+
+        let mut out = vec![];
+        let mut window_in_flow = WindowInFlowByWindowTask::zero();
+
+        let closing = self
+            .panes
+            .range(..=watermark.as_timestamp() - length)
+            .map(|(open_at, fired)| (*open_at, *fired))
+            .collect::<Vec<_>>();
+
+        for (open_at, fired) in closing {
+            let close_at = open_at + length;
+
+            if fired != Some(false) {
+                let mut out_seq = self.merge_slices(open_at, expr_resolver);
+                if fired.is_some() {
+                    window_in_flow.late_merges += 1;
+                    self.trace(|| format!("pane [{:?}, {:?}) merged late rows", open_at, close_at));
+                } else {
+                    window_in_flow.panes_closed += 1;
+                    self.trace(|| format!("pane [{:?}, {:?}) closed", open_at, close_at));
+                }
+                for close_out in &out_seq {
+                    self.trace(|| {
+                        format!(
+                            "pane [{:?}, {:?}) emitted {:?}",
+                            open_at, close_at, close_out
+                        )
+                    });
+                }
+                window_in_flow.emitted_rows += out_seq.len() as u64;
+                out.append(&mut out_seq);
+            }
+
+            if close_at + allowed_lateness <= watermark.as_timestamp() {
+                self.panes.remove(&open_at);
+                self.trace(|| format!("pane [{:?}, {:?}) removed", open_at, close_at));
+            } else {
+                self.panes.insert(open_at, Some(false));
+            }
+        }
+
+        // slices are aligned to panes
+        match self.panes.keys().next() {
+            Some(leftmost_open_at) => self.slices = self.slices.split_off(leftmost_open_at),
+            None => self.slices.clear(),
+        }
+
+        (out, window_in_flow)
+    }
+
+    /// Results of the pane opening at `open_at`, from its slices.
+    ///
+    /// A group failed to merge a slice (e.g. SUM of INTEGER and FLOAT) is output without the slice.
+    fn merge_slices(
+        &self,
+        open_at: SpringTimestamp,
+        expr_resolver: &ExprResolver,
+    ) -> Vec<AggregatedAndGroupingValues> {
+        let close_at = open_at + self.window_param.length().to_duration();
+
+        let mut pane = AggrPane::new(open_at, close_at, self.op_param.clone());
+        for slice in self.slices.range(open_at..close_at).map(|(_, slice)| slice) {
+            if let Err(e) = pane.merge(slice) {
+                log::warn!(
+                    "[AggrSlices] pane [{:?}, {:?}) failed to merge slice [{:?}, {:?}): {:?}",
+                    open_at,
+                    close_at,
+                    slice.open_at(),
+                    slice.close_at(),
+                    e
+                );
+            }
+        }
+        pane.close(expr_resolver).0
+    }
+
+    /// `open_at` of panes `rowtime` belongs to.
+    fn pane_open_at_s(&self, rowtime: SpringTimestamp) -> Result<Vec<SpringTimestamp>> {
+        let length = self.window_param.length().to_duration();
+        let period = self.window_param.period().to_duration();
+
+        let mut ret = vec![];
+        let mut open_at = rowtime.floor(period)?;
+        while rowtime < open_at + length {
+            ret.push(open_at);
+            open_at = open_at - period;
+        }
+        Ok(ret)
+    }
+
+    /// Logs a pane trigger if `TRACE TRIGGERS` is set to the window.
+    fn trace<F>(&self, message: F)
+    where
+        F: FnOnce() -> String,
+    {
+        if self.window_param.trace_triggers() {
+            log::info!("[AggrSlices] {}", message());
+        }
+    }
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
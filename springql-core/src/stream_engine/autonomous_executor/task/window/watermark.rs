@@ -15,20 +15,47 @@ use crate::stream_engine::time::{
 pub struct Watermark {
     max_rowtime: SpringTimestamp,
     allowed_delay: SpringEventDuration,
+    allowed_lateness: SpringEventDuration,
 }
 
 impl Watermark {
-    pub fn new(allowed_delay: SpringEventDuration) -> Self {
+    pub fn new(allowed_delay: SpringEventDuration, allowed_lateness: SpringEventDuration) -> Self {
         Self {
-            max_rowtime: MIN_TIMESTAMP + allowed_delay.to_duration(), // to avoid overflow
+            // to avoid overflow
+            max_rowtime: MIN_TIMESTAMP + (allowed_delay + allowed_lateness).to_duration(),
             allowed_delay,
+            allowed_lateness,
         }
     }
 
+    pub fn max_rowtime(&self) -> SpringTimestamp {
+        self.max_rowtime
+    }
+
     pub fn as_timestamp(&self) -> SpringTimestamp {
         self.max_rowtime - self.allowed_delay.to_duration()
     }
 
+    /// Rows older than this timestamp are dropped.
+    ///
+    /// ```text
+    /// lateness_limit = watermark - allowed_lateness
+    /// ```
+    pub fn lateness_limit(&self) -> SpringTimestamp {
+        self.as_timestamp() - self.allowed_lateness.to_duration()
+    }
+
+    /// How much `rowtime` is behind the latest ROWTIME.
+    pub fn disorder(&self, rowtime: SpringTimestamp) -> Option<SpringEventDuration> {
+        (rowtime < self.max_rowtime).then(|| {
+            SpringEventDuration::from_std(
+                (self.max_rowtime - rowtime)
+                    .to_std()
+                    .expect("max_rowtime > rowtime"),
+            )
+        })
+    }
+
     pub fn update(&mut self, rowtime: SpringTimestamp) {
         self.max_rowtime = max(rowtime, self.max_rowtime);
     }
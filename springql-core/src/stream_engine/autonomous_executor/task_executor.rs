@@ -1,9 +1,12 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 mod generic_worker_pool;
+mod micro_batch;
 mod scheduler;
 mod source_worker_pool;
 mod task_executor_lock;
+mod task_partition;
+mod task_placement;
 mod task_worker_thread_handler;
 
 pub use task_executor_lock::{
@@ -22,12 +25,13 @@ use crate::{
         task_executor::{
             generic_worker_pool::GenericWorkerPool, source_worker_pool::SourceWorkerPool,
         },
-        task_graph::TaskGraph,
+        task_graph::{QueueId, TaskGraph},
     },
 };
 
 /// Task executor executes task graph's dataflow by internal worker threads.
 /// Source tasks are scheduled by SourceScheduler and other tasks are scheduled by FlowEfficientScheduler (in Moderate state) or MemoryReducingScheduler (in Severe state).
+/// Light pump tasks fed by source tasks may be executed by source workers instead (see `task_placement`).
 ///
 /// All interface methods are called from main thread, while `new()` spawns worker threads.
 #[derive(Debug)]
@@ -67,12 +71,29 @@ impl TaskExecutor {
     }
 
     /// Update workers' internal current pipeline.
+    ///
+    /// Source readers and sink writers dropped from the pipeline are closed.
     pub fn update_pipeline(
         &self,
         _lock_guard: &MainJobBarrierGuard,
         pipeline_derivatives: Arc<PipelineDerivatives>,
     ) -> Result<()> {
         let pipeline = pipeline_derivatives.pipeline();
+        self.repos.source_reader_repository().retain(
+            &pipeline
+                .all_sources()
+                .into_iter()
+                .map(|source_reader| source_reader.name())
+                .collect(),
+        );
+        self.repos.sink_writer_repository().retain(
+            &pipeline
+                .all_sinks()
+                .into_iter()
+                .map(|sink_writer| sink_writer.name())
+                .collect(),
+        );
+
         pipeline
             .all_sources()
             .into_iter()
@@ -92,6 +113,8 @@ impl TaskExecutor {
     }
 
     /// Stop all source tasks and executes pump tasks and sink tasks to finish all rows remaining in queues.
+    ///
+    /// Input queues of stopped pumps are paused so that rows do not pile up in them.
     pub fn cleanup(&self, _lock_guard: &MainJobBarrierGuard, task_graph: &TaskGraph) {
         // TODO do not just remove rows in queues. Do the things in doc comment.
 
@@ -101,5 +124,14 @@ impl TaskExecutor {
         self.repos
             .window_queue_repository()
             .reset(task_graph.window_queues().into_iter().collect());
+
+        for task_id in task_graph.stopped_tasks() {
+            for queue_id in task_graph.input_queues(&task_id) {
+                match queue_id {
+                    QueueId::Row(id) => self.repos.row_queue_repository().get(&id).pause(),
+                    QueueId::Window(id) => self.repos.window_queue_repository().get(&id).pause(),
+                }
+            }
+        }
     }
 }
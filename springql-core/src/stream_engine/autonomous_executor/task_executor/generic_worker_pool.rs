@@ -11,6 +11,9 @@ use crate::{
         repositories::Repositories,
         task_executor::{
             generic_worker_pool::generic_worker::GenericWorker,
+            micro_batch::MicroBatch,
+            task_partition::TaskPartition,
+            task_placement::TaskPlacement,
             task_worker_thread_handler::{TaskWorkerId, TaskWorkerThreadArg},
         },
     },
@@ -37,11 +40,15 @@ impl GenericWorkerPool {
     ) -> Self {
         let workers = (0..config.n_generic_worker_threads)
             .map(|id| {
+                let worker_id = TaskWorkerId::new(id as u16);
                 let arg = TaskWorkerThreadArg::new(
-                    TaskWorkerId::new(id as u16),
+                    worker_id,
                     locks.task_executor_lock.clone(),
                     repos.clone(),
                     config.sleep_msec_no_row,
+                    TaskPlacement::new(config, false),
+                    TaskPartition::new(config, false, worker_id),
+                    MicroBatch::new(config),
                 );
                 GenericWorker::new(
                     locks.main_job_lock.clone(),
@@ -0,0 +1,265 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Micro-batch execution of tasks (`worker.micro_batch` config).
+//!
+//! A worker executes a scheduled task with input queues repeatedly, up to the batch size of the task, before moving on to the next task in the series.
+//! A batch ends early when an execution processes no row.
+//!
+//! After each batch, the batch size of the task is tuned by:
+//!
+//! - halving it when the batch took longer than `worker.micro_batch_latency_target_usec`, since rows of other tasks wait for the batch,
+//! - doubling it (up to `worker.micro_batch_max_size`) when the batch is full and input queues of the task still have more rows than the batch size,
+//! - keeping it otherwise.
+//!
+//! Batch sizes start from 1, and are reported as `batch_size` of task metrics.
+
+use std::{cmp::min, collections::HashMap};
+
+use crate::{
+    api::SpringWorkerConfig,
+    stream_engine::{
+        autonomous_executor::{
+            performance_metrics::PerformanceMetrics,
+            task_graph::{TaskGraph, TaskId},
+        },
+        time::{SpringDuration, WallClockDuration},
+    },
+};
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum MicroBatch {
+    Disabled,
+    Enabled {
+        max_size: u64,
+        latency_target: WallClockDuration,
+    },
+}
+
+impl MicroBatch {
+    pub fn new(config: &SpringWorkerConfig) -> Self {
+        if config.micro_batch {
+            Self::Enabled {
+                max_size: config.micro_batch_max_size,
+                latency_target: WallClockDuration::from_micros(
+                    config.micro_batch_latency_target_usec as u64,
+                ),
+            }
+        } else {
+            Self::Disabled
+        }
+    }
+
+    /// Batch size of `task_id` for the next batch. Always 1 for tasks without input queues (source tasks).
+    pub fn size(&self, batch_sizes: &BatchSizes, task_id: &TaskId) -> u64 {
+        match (self, task_id) {
+            (Self::Disabled, _) | (_, TaskId::Source { .. }) => 1,
+            (Self::Enabled { .. }, _) => batch_sizes.get(task_id),
+        }
+    }
+
+    /// Tunes the batch size of `task_id` after its batch.
+    ///
+    /// # Returns
+    ///
+    /// The batch size for the next batch. `None` if micro-batch is disabled or `task_id` is a source task.
+    pub fn tune(
+        &self,
+        batch_sizes: &mut BatchSizes,
+        task_id: &TaskId,
+        batch: &Batch,
+        graph: &TaskGraph,
+        metrics: &PerformanceMetrics,
+    ) -> Option<u64> {
+        match (self, task_id) {
+            (Self::Disabled, _) | (_, TaskId::Source { .. }) => None,
+            (
+                Self::Enabled {
+                    max_size,
+                    latency_target,
+                },
+                _,
+            ) => {
+                let queued_rows = graph
+                    .input_queues(task_id)
+                    .iter()
+                    .map(|queue_id| metrics.rows_for_task_input(queue_id))
+                    .sum();
+                let size = Self::next_size(
+                    batch_sizes.get(task_id),
+                    batch,
+                    queued_rows,
+                    *max_size,
+                    *latency_target,
+                );
+                batch_sizes.set(task_id.clone(), size);
+                Some(size)
+            }
+        }
+    }
+
+    fn next_size(
+        size: u64,
+        batch: &Batch,
+        queued_rows: u64,
+        max_size: u64,
+        latency_target: WallClockDuration,
+    ) -> u64 {
+        if batch.execution_time > latency_target {
+            (size / 2).max(1)
+        } else if batch.executions >= size && !batch.drained && queued_rows > size {
+            min(size * 2, max_size)
+        } else {
+            size
+        }
+    }
+}
+
+/// Executions of a task in a batch.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Batch {
+    pub executions: u64,
+    pub execution_time: WallClockDuration,
+    /// Whether the last execution processed no row.
+    pub drained: bool,
+}
+
+impl Default for Batch {
+    fn default() -> Self {
+        Self {
+            executions: 0,
+            execution_time: WallClockDuration::from_micros(0),
+            drained: false,
+        }
+    }
+}
+
+/// Batch size per task, kept by each worker thread.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct BatchSizes(HashMap<TaskId, u64>);
+
+impl BatchSizes {
+    fn get(&self, task_id: &TaskId) -> u64 {
+        self.0.get(task_id).copied().unwrap_or(1)
+    }
+
+    fn set(&mut self, task_id: TaskId, size: u64) {
+        self.0.insert(task_id, size);
+    }
+
+    /// Forgets batch sizes of tasks not in `graph` (e.g. dropped pumps).
+    pub fn retain_tasks(&mut self, graph: &TaskGraph) {
+        let tasks = graph.tasks();
+        self.0.retain(|task_id, _| tasks.contains(task_id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::stream_engine::autonomous_executor::performance_metrics::MetricsUpdateByTaskExecution;
+
+    fn batch(executions: u64, execution_usec: u64, drained: bool) -> Batch {
+        Batch {
+            executions,
+            execution_time: WallClockDuration::from_micros(execution_usec),
+            drained,
+        }
+    }
+
+    #[test]
+    fn test_next_size() {
+        let target = WallClockDuration::from_micros(1000);
+        let next_size = |size, batch: &Batch, queued_rows| {
+            MicroBatch::next_size(size, batch, queued_rows, 64, target)
+        };
+
+        // backlog grows the batch up to the max size
+        assert_eq!(next_size(1, &batch(1, 10, false), 100), 2);
+        assert_eq!(next_size(32, &batch(32, 320, false), 100), 64);
+        assert_eq!(next_size(64, &batch(64, 640, false), 100), 64);
+
+        // no backlog
+        assert_eq!(next_size(8, &batch(8, 80, false), 8), 8);
+        assert_eq!(next_size(8, &batch(3, 30, true), 100), 8);
+
+        // over the latency target
+        assert_eq!(next_size(8, &batch(8, 1500, false), 100), 4);
+        assert_eq!(next_size(1, &batch(1, 1500, false), 100), 1);
+    }
+
+    #[test]
+    fn test_micro_batch_tune() {
+        let micro_batch = MicroBatch::Enabled {
+            max_size: 64,
+            latency_target: WallClockDuration::from_micros(1000),
+        };
+        let mut batch_sizes = BatchSizes::default();
+        // 5 rows are left in the input queue of pump_task2
+        let graph = TaskGraph::fx_split_join();
+        let metrics = PerformanceMetrics::fx_split_join();
+        let task_id = TaskId::fx_split_join_t2();
+
+        let mut tuned = vec![];
+        for _ in 0..4 {
+            let size = micro_batch.size(&batch_sizes, &task_id);
+            let tuned_size = micro_batch
+                .tune(
+                    &mut batch_sizes,
+                    &task_id,
+                    &batch(size, 10 * size, false),
+                    &graph,
+                    &metrics,
+                )
+                .unwrap();
+            tuned.push(tuned_size);
+        }
+        assert_eq!(tuned, vec![2, 4, 8, 8]);
+
+        // decisions are in task metrics
+        metrics.update_by_task_execution(
+            &MetricsUpdateByTaskExecution::fx_split_join_t2().with_batch_size(8),
+        );
+        let (_, task_metrics) = metrics
+            .get_tasks()
+            .into_iter()
+            .find(|(id, _)| *id == &task_id)
+            .unwrap();
+        assert_eq!(task_metrics.batch_size(), 8);
+
+        // source tasks are not batched
+        let source_task_id = TaskId::fx_split_join_t1();
+        assert_eq!(
+            micro_batch.tune(
+                &mut batch_sizes,
+                &source_task_id,
+                &batch(1, 10, false),
+                &graph,
+                &metrics
+            ),
+            None
+        );
+        assert_eq!(micro_batch.size(&batch_sizes, &source_task_id), 1);
+    }
+
+    #[test]
+    fn test_micro_batch_disabled() {
+        let micro_batch = MicroBatch::Disabled;
+        let mut batch_sizes = BatchSizes::default();
+        let graph = TaskGraph::fx_split_join();
+        let metrics = PerformanceMetrics::from_task_graph(&graph);
+        let task_id = TaskId::fx_split_join_t2();
+
+        assert_eq!(
+            micro_batch.tune(
+                &mut batch_sizes,
+                &task_id,
+                &batch(1, 10, false),
+                &graph,
+                &metrics
+            ),
+            None
+        );
+        assert_eq!(micro_batch.size(&batch_sizes, &task_id), 1);
+    }
+}
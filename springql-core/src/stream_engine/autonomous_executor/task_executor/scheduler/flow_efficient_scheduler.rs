@@ -31,6 +31,28 @@
 //! Each collector has fair chance to be scheduled. Fairness is defined as such here:
 //!
 //! Each row created by the generator waits, on average, the same number of times until it is collected by the collector.
+//!
+//! - **Rule4: urgent first**
+//!
+//! While any collector is urgent, only urgent collectors are scheduled. A collector is urgent when rows are waiting in queues from it to stoppers and:
+//!
+//! - any of the queues is fed by a `PRIORITY WHEN` pump, or
+//! - it reaches a sink whose latency SLO (`LATENCY_SLO_MSEC`) is at risk.
+//!
+//! Then high-priority rows and rows for deadline-aware sinks flow to stoppers ahead of bulk rows from the other collectors.
+//!
+//! - **Rule5: quotas**
+//!
+//! Tasks of namespaces and pumps paused over their quotas are never scheduled.
+//! Collectors deprioritized over their quotas are scheduled only while no other collector has incoming rows.
+//!
+//! - **Rule6: congestion-aware**
+//!
+//! A queue is congested when `CONGESTED_QUEUE_ROWS` or more rows are waiting in it (e.g. a sink writer cannot keep up).
+//! While any queue is congested, the task consuming it inherits the priority of a collector, taking precedence over all the other rules:
+//! the series from it to stoppers is scheduled instead of the series from collectors.
+//! Tasks upstream of congested queues, which only produce more rows into them, are not scheduled meanwhile.
+//! If many queues are congested, the consumer is picked with probability proportional to the rows waiting.
 
 use std::{cell::RefCell, collections::HashSet};
 
@@ -39,12 +61,21 @@ use rand::{
     prelude::{Distribution, ThreadRng},
 };
 
-use crate::stream_engine::autonomous_executor::{
-    performance_metrics::PerformanceMetrics,
-    task_executor::scheduler::Scheduler,
-    task_graph::{TaskGraph, TaskId},
+use crate::stream_engine::{
+    autonomous_executor::{
+        performance_metrics::{PerformanceMetrics, Throttle},
+        task_executor::scheduler::Scheduler,
+        task_graph::{TaskGraph, TaskId},
+    },
+    time::SpringDuration,
 };
 
+/// Sinks are treated as SLO-at-risk before their latency actually violates SLO.
+const LATENCY_SLO_AT_RISK_RATIO: f32 = 0.8;
+
+/// A queue with this number of rows or more is congested (see **Rule6: congestion-aware**).
+const CONGESTED_QUEUE_ROWS: u64 = 1_000;
+
 /// A generator task is one of:
 ///
 /// - source tasks
@@ -88,8 +119,14 @@ pub struct FlowEfficientScheduler {
 
 impl Scheduler for FlowEfficientScheduler {
     fn next_task_series(&self, graph: &TaskGraph, metrics: &PerformanceMetrics) -> Vec<TaskId> {
-        self.decide_collector(graph, metrics)
-            .map(|collector| self.collector_to_stoppers_dfs(&collector, graph))
+        self.decide_congested_consumer(graph, metrics)
+            .or_else(|| self.decide_collector(graph, metrics))
+            .map(|collector| {
+                // Rule5: quotas
+                let mut series = self.collector_to_stoppers_dfs(&collector, graph);
+                series.retain(|task_id| metrics.throttle(task_id) != Some(Throttle::Pause));
+                series
+            })
             .unwrap_or_else(Vec::new)
     }
 }
@@ -113,7 +150,38 @@ impl FlowEfficientScheduler {
         graph: &TaskGraph,
         metrics: &PerformanceMetrics,
     ) -> Option<Collector> {
-        let collectors = self.collectors(graph).into_iter().collect::<Vec<_>>();
+        let mut collectors = self
+            .collectors(graph)
+            .into_iter()
+            .filter(|c| metrics.throttle(&c.task_id) != Some(Throttle::Pause))
+            .collect::<Vec<_>>();
+
+        // Rule5: quotas
+        let (deprioritized, prioritized): (Vec<_>, Vec<_>) = collectors
+            .iter()
+            .cloned()
+            .partition(|c| metrics.throttle(&c.task_id) == Some(Throttle::Deprioritize));
+        if !deprioritized.is_empty()
+            && prioritized
+                .iter()
+                .any(|c| self.incoming_rows(c, graph, metrics) > 0)
+        {
+            collectors = prioritized;
+        }
+
+        let urgent = collectors
+            .iter()
+            .map(|c| self.is_urgent(c, graph, metrics))
+            .collect::<Vec<_>>();
+        if urgent.contains(&true) {
+            // Rule4: urgent first
+            collectors = collectors
+                .into_iter()
+                .zip(urgent)
+                .filter_map(|(c, urgent)| urgent.then(|| c))
+                .collect();
+        }
+
         if collectors.is_empty() {
             None
         } else {
@@ -139,6 +207,58 @@ impl FlowEfficientScheduler {
             Some(picked_collector.clone())
         }
     }
+
+    /// Picks a task consuming a congested queue to meet the **Rule6: congestion-aware**.
+    /// The task is treated as a collector.
+    ///
+    /// # Returns
+    ///
+    /// `None` if no queue is congested.
+    fn decide_congested_consumer(
+        &self,
+        graph: &TaskGraph,
+        metrics: &PerformanceMetrics,
+    ) -> Option<Collector> {
+        let mut consumers = graph
+            .tasks()
+            .into_iter()
+            .filter(|task_id| {
+                metrics.throttle(task_id) != Some(Throttle::Pause) && !graph.is_stopped(task_id)
+            })
+            .filter_map(|task_id| {
+                let rows = graph
+                    .input_queues(&task_id)
+                    .iter()
+                    .map(|q| metrics.rows_for_task_input(q))
+                    .max()?;
+                Some((task_id, rows))
+            })
+            .filter(|(_, rows)| *rows >= CONGESTED_QUEUE_ROWS)
+            .collect::<Vec<_>>();
+        // duplicate task id on JOIN and ROUTER pump task
+        consumers.sort();
+        consumers.dedup_by(|(a, _), (b, _)| a == b);
+
+        if consumers.is_empty() {
+            None
+        } else {
+            let distribution = WeightedIndex::new(consumers.iter().map(|(_, rows)| *rows))
+                .expect("at least 1 congested queue");
+            let (task_id, rows) = consumers
+                .get(distribution.sample(&mut *self.rng.borrow_mut()))
+                .expect("safe index");
+
+            log::trace!(
+                "[FlowEfficientScheduler::decide_congested_consumer()] {} ({} rows waiting)",
+                task_id,
+                rows
+            );
+            Some(Collector {
+                task_id: task_id.clone(),
+            })
+        }
+    }
+
     fn incoming_rows(
         &self,
         collector: &Collector,
@@ -153,6 +273,43 @@ impl FlowEfficientScheduler {
             .sum()
     }
 
+    /// See **Rule4: urgent first**.
+    fn is_urgent(
+        &self,
+        collector: &Collector,
+        graph: &TaskGraph,
+        metrics: &PerformanceMetrics,
+    ) -> bool {
+        let series = self.collector_to_stoppers_dfs(collector, graph);
+        let waiting_queues = series
+            .iter()
+            .flat_map(|task_id| graph.input_queues(task_id))
+            .filter(|q| metrics.rows_for_task_input(q) > 0)
+            .collect::<Vec<_>>();
+
+        let has_priority_rows = waiting_queues.iter().any(|q| graph.is_priority_queue(q));
+        let reaches_slo_at_risk_sink = !waiting_queues.is_empty()
+            && series
+                .iter()
+                .any(|task_id| Self::is_latency_slo_at_risk(task_id, graph, metrics));
+
+        has_priority_rows || reaches_slo_at_risk_sink
+    }
+
+    /// A sink's latency SLO is at risk when the last latency exceeds `LATENCY_SLO_AT_RISK_RATIO` of the SLO.
+    fn is_latency_slo_at_risk(
+        task_id: &TaskId,
+        graph: &TaskGraph,
+        metrics: &PerformanceMetrics,
+    ) -> bool {
+        graph
+            .sink_latency_slo(task_id)
+            .zip(metrics.last_sink_latency(task_id))
+            .map_or(false, |(latency_slo, latency)| {
+                latency.as_secs_f32() > latency_slo.as_secs_f32() * LATENCY_SLO_AT_RISK_RATIO
+            })
+    }
+
     fn collector_to_stoppers_dfs(&self, collector: &Collector, graph: &TaskGraph) -> Vec<TaskId> {
         fn to_stoppers_dfs(current_task: &TaskId, graph: &TaskGraph) -> Vec<TaskId> {
             if current_task.is_window_task() {
@@ -199,6 +356,7 @@ mod tests {
     use springql_test_logger::setup_test_logger;
 
     use super::*;
+    use crate::stream_engine::autonomous_executor::performance_metrics::MetricsUpdateByTaskExecution;
 
     #[ignore]
     #[test]
@@ -219,4 +377,30 @@ mod tests {
                 .join(", ")
         );
     }
+
+    #[test]
+    fn test_congested_queue() {
+        let graph = TaskGraph::fx_split_join();
+        let metrics = PerformanceMetrics::fx_split_join();
+        let sched = FlowEfficientScheduler::default();
+        assert_eq!(sched.decide_congested_consumer(&graph, &metrics), None);
+
+        // source_task1 -> (q2) -> pump_task2 -> (q3_1) -> pump_task3 (window)
+        for _ in 0..CONGESTED_QUEUE_ROWS {
+            metrics.update_by_task_execution(&MetricsUpdateByTaskExecution::fx_split_join_t1());
+        }
+        for _ in 0..10 {
+            assert_eq!(
+                sched.next_task_series(&graph, &metrics),
+                vec![TaskId::fx_split_join_t2(), TaskId::fx_split_join_t3()]
+            );
+        }
+
+        metrics.set_throttles(
+            [(TaskId::fx_split_join_t2(), Throttle::Pause)]
+                .into_iter()
+                .collect(),
+        );
+        assert_eq!(sched.decide_congested_consumer(&graph, &metrics), None);
+    }
 }
@@ -28,11 +28,15 @@
 //!
 //! Unlike Flow-Efficient Scheduler, Memory-Reducing Scheduler does not have fairness.
 //! Some rows may get large delay until they get to sink, or even lose chance to participate in time-based window.
+//!
+//! `BEST_EFFORT` pumps are never scheduled. They are paused while Memory-Reducing Scheduler is used (Severe and Critical state).
+//!
+//! Tasks paused over their quotas are never scheduled, and tasks deprioritized over their quotas are scheduled after the others.
 
 use std::{cmp::min, collections::HashSet, iter};
 
 use crate::stream_engine::autonomous_executor::{
-    performance_metrics::PerformanceMetrics,
+    performance_metrics::{PerformanceMetrics, Throttle},
     task_executor::scheduler::{Scheduler, MAX_TASK_SERIES},
     task_graph::{TaskGraph, TaskId},
 };
@@ -79,9 +83,17 @@ impl MemoryReducingScheduler {
         let tasks = tasks.difference(&source_tasks); // source tasks are scheduled by SourceScheduler
 
         let mut profiles = tasks
+            .filter(|task| !graph.is_best_effort(task))
+            .filter(|task| metrics.throttle(task) != Some(Throttle::Pause))
             .map(|task| self.task_profile(task, graph, metrics))
             .collect::<Vec<_>>();
-        profiles.sort_by(|a, b| a.loss.partial_cmp(&b.loss).expect("loss cannot be NaN"));
+        profiles.sort_by(|a, b| {
+            let a_deprioritized = metrics.throttle(&a.task_id).is_some();
+            let b_deprioritized = metrics.throttle(&b.task_id).is_some();
+            a_deprioritized
+                .cmp(&b_deprioritized)
+                .then(a.loss.partial_cmp(&b.loss).expect("loss cannot be NaN"))
+        });
         profiles
     }
 
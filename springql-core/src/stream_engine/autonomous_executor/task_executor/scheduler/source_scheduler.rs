@@ -1,11 +1,13 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 //! Source Scheduler dedicating to schedule source tasks eagerly at Moderate and Severe state.
+//!
+//! Source tasks paused over their quotas are not scheduled, and ones deprioritized over their quotas are scheduled after the others.
 
 use std::collections::HashSet;
 
 use crate::stream_engine::autonomous_executor::{
-    performance_metrics::PerformanceMetrics,
+    performance_metrics::{PerformanceMetrics, Throttle},
     task_executor::scheduler::Scheduler,
     task_graph::{TaskGraph, TaskId},
 };
@@ -20,11 +22,15 @@ pub struct SourceScheduler {}
 
 impl Scheduler for SourceScheduler {
     /// TODO [prioritize source with lower source-miss rate](https://gh01.base.toyota-tokyo.tech/SpringQL-internal/SpringQL/issues/122)
-    fn next_task_series(&self, graph: &TaskGraph, _metrics: &PerformanceMetrics) -> Vec<TaskId> {
-        self.source_tasks(graph)
+    fn next_task_series(&self, graph: &TaskGraph, metrics: &PerformanceMetrics) -> Vec<TaskId> {
+        let mut series = self
+            .source_tasks(graph)
             .into_iter()
             .map(|s| s.task_id)
-            .collect()
+            .filter(|task_id| metrics.throttle(task_id) != Some(Throttle::Pause))
+            .collect::<Vec<_>>();
+        series.sort_by_key(|task_id| metrics.throttle(task_id).is_some());
+        series
     }
 }
 
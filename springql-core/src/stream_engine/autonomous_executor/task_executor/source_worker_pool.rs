@@ -10,7 +10,10 @@ use crate::{
         args::{Coordinators, EventQueues, Locks},
         repositories::Repositories,
         task_executor::{
+            micro_batch::MicroBatch,
             source_worker_pool::source_worker::SourceWorker,
+            task_partition::TaskPartition,
+            task_placement::TaskPlacement,
             task_worker_thread_handler::{TaskWorkerId, TaskWorkerThreadArg},
         },
     },
@@ -37,11 +40,15 @@ impl SourceWorkerPool {
     ) -> Self {
         let workers = (0..config.n_source_worker_threads)
             .map(|id| {
+                let worker_id = TaskWorkerId::new(id as u16);
                 let arg = TaskWorkerThreadArg::new(
-                    TaskWorkerId::new(id as u16),
+                    worker_id,
                     locks.task_executor_lock.clone(),
                     repos.clone(),
                     config.sleep_msec_no_row,
+                    TaskPlacement::new(config, true),
+                    TaskPartition::new(config, true, worker_id),
+                    MicroBatch::new(config),
                 );
                 SourceWorker::new(
                     locks.main_job_lock.clone(),
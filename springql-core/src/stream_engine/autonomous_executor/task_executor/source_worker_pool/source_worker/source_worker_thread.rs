@@ -64,6 +64,9 @@ impl WorkerThread for SourceWorkerThread {
         );
 
         let mut state = current_state;
+        state
+            .batch_sizes
+            .retain_tasks(pipeline_derivatives.task_graph());
         state.pipeline_derivatives = Some(pipeline_derivatives);
         state
     }
@@ -0,0 +1,187 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Dedication of subgraphs of a task graph to worker threads (`worker.thread_per_core` config).
+//!
+//! Subgraph `i` (see `TaskGraph::subgraph_index()`) is executed only by generic worker `i % n_generic_worker_threads`
+//! and source worker `i % n_source_worker_threads`. Workers never take over tasks of other workers' subgraphs,
+//! so that each queue has only 1 writer thread and 1 reader thread.
+//!
+//! Each worker thread is pinned to a CPU core: generic workers to core 0, 1, ..., and source workers to the following cores.
+//! Cores are reused round-robin when worker threads outnumber them.
+
+use crate::{
+    api::SpringWorkerConfig,
+    stream_engine::autonomous_executor::{
+        task_executor::task_worker_thread_handler::TaskWorkerId,
+        task_graph::{TaskGraph, TaskId},
+    },
+};
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum TaskPartition {
+    /// Every worker executes any task its scheduler generates.
+    Shared,
+    Dedicated {
+        worker_index: usize,
+        n_workers: usize,
+        core_index: usize,
+    },
+}
+
+impl TaskPartition {
+    pub fn new(
+        config: &SpringWorkerConfig,
+        on_source_worker: bool,
+        worker_id: TaskWorkerId,
+    ) -> Self {
+        if !config.thread_per_core {
+            return Self::Shared;
+        }
+
+        let worker_index = worker_id.as_u16() as usize;
+        let (n_workers, core_index) = if on_source_worker {
+            (
+                config.n_source_worker_threads as usize,
+                config.n_generic_worker_threads as usize + worker_index,
+            )
+        } else {
+            (config.n_generic_worker_threads as usize, worker_index)
+        };
+        Self::Dedicated {
+            worker_index,
+            n_workers,
+            core_index,
+        }
+    }
+
+    /// Removes tasks of subgraphs dedicated to other workers from a task series.
+    pub fn apply(&self, mut task_series: Vec<TaskId>, graph: &TaskGraph) -> Vec<TaskId> {
+        match self {
+            Self::Shared => task_series,
+            Self::Dedicated {
+                worker_index,
+                n_workers,
+                ..
+            } => {
+                task_series.retain(|task_id| {
+                    graph
+                        .subgraph_index(task_id)
+                        .map_or(false, |i| i % n_workers == *worker_index)
+                });
+                task_series
+            }
+        }
+    }
+
+    /// Pins the calling worker thread to its CPU core. Does nothing in `Shared` partition.
+    pub fn pin_current_thread(&self) {
+        if let Self::Dedicated { core_index, .. } = self {
+            let pinned = core_affinity::get_core_ids()
+                .filter(|core_ids| !core_ids.is_empty())
+                .map_or(false, |core_ids| {
+                    core_affinity::set_for_current(core_ids[core_index % core_ids.len()])
+                });
+            if !pinned {
+                log::warn!(
+                    "[TaskPartition] failed to pin a worker thread to CPU core {}",
+                    core_index
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        pipeline::PipelineVersion,
+        stream_engine::autonomous_executor::task_graph::QueueIdWithUpstream,
+    };
+
+    fn config(thread_per_core: bool, n_generic_worker_threads: u16) -> SpringWorkerConfig {
+        SpringWorkerConfig {
+            n_generic_worker_threads,
+            n_source_worker_threads: 1,
+            sleep_msec_no_row: 100,
+            adaptive_placement: false,
+            adaptive_placement_max_task_usec: 1000,
+            thread_per_core,
+            micro_batch: false,
+            micro_batch_max_size: 256,
+            micro_batch_latency_target_usec: 1000,
+        }
+    }
+
+    /// source_task1 -> pump_task2, and source_task6 -> pump_task7
+    fn two_subgraphs() -> TaskGraph {
+        let mut g = TaskGraph::new(PipelineVersion::new());
+        g.add_task(TaskId::fx_split_join_t1());
+        g.add_task(TaskId::fx_split_join_t2());
+        g.add_task(TaskId::fx_split_join_t6());
+        g.add_task(TaskId::fx_split_join_t7());
+        g.add_queue(
+            QueueIdWithUpstream::fx_split_join_q2(),
+            TaskId::fx_split_join_t1(),
+            TaskId::fx_split_join_t2(),
+        );
+        g.add_queue(
+            QueueIdWithUpstream::fx_split_join_q7(),
+            TaskId::fx_split_join_t6(),
+            TaskId::fx_split_join_t7(),
+        );
+        g
+    }
+
+    #[test]
+    fn test_subgraph_index() {
+        let graph = TaskGraph::fx_split_join();
+        assert!(graph
+            .tasks()
+            .iter()
+            .all(|task_id| graph.subgraph_index(task_id) == Some(0)));
+
+        let graph = two_subgraphs();
+        assert_eq!(graph.subgraph_index(&TaskId::fx_split_join_t1()), Some(0));
+        assert_eq!(graph.subgraph_index(&TaskId::fx_split_join_t2()), Some(0));
+        assert_eq!(graph.subgraph_index(&TaskId::fx_split_join_t6()), Some(1));
+        assert_eq!(graph.subgraph_index(&TaskId::fx_split_join_t7()), Some(1));
+        assert_eq!(graph.subgraph_index(&TaskId::fx_split_join_t3()), None);
+    }
+
+    #[test]
+    fn test_task_partition() {
+        let graph = two_subgraphs();
+        let series = vec![TaskId::fx_split_join_t2(), TaskId::fx_split_join_t7()];
+
+        let partition = TaskPartition::new(&config(false, 2), false, TaskWorkerId::new(1));
+        assert_eq!(partition.apply(series.clone(), &graph), series);
+
+        let partition = TaskPartition::new(&config(true, 2), false, TaskWorkerId::new(0));
+        assert_eq!(
+            partition.apply(series.clone(), &graph),
+            vec![TaskId::fx_split_join_t2()]
+        );
+        let partition = TaskPartition::new(&config(true, 2), false, TaskWorkerId::new(1));
+        assert_eq!(
+            partition.apply(series.clone(), &graph),
+            vec![TaskId::fx_split_join_t7()]
+        );
+
+        // a single worker executes all subgraphs
+        let partition = TaskPartition::new(&config(true, 1), false, TaskWorkerId::new(0));
+        assert_eq!(partition.apply(series.clone(), &graph), series);
+
+        // source workers are pinned after generic workers
+        let partition = TaskPartition::new(&config(true, 2), true, TaskWorkerId::new(0));
+        assert_eq!(
+            partition,
+            TaskPartition::Dedicated {
+                worker_index: 0,
+                n_workers: 1,
+                core_index: 2
+            }
+        );
+    }
+}
@@ -0,0 +1,175 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Adaptive placement of light pump tasks onto source workers (`worker.adaptive_placement` config).
+//!
+//! A pump task is placed on source workers when:
+//!
+//! - all of its input queues are written by source tasks,
+//! - it is not throttled by quotas, and
+//! - its observed average execution time is up to `worker.adaptive_placement_max_task_usec`.
+//!
+//! Placement is decided only from a task graph and performance metrics, so that source workers and generic workers
+//! agree on it without any coordination: source workers execute placed pumps just after source tasks, and generic workers skip them.
+//! Pump tasks not executed yet are on generic workers, as their execution time is unknown.
+
+use std::collections::HashSet;
+
+use crate::{
+    api::SpringWorkerConfig,
+    stream_engine::autonomous_executor::{
+        performance_metrics::PerformanceMetrics,
+        task_graph::{TaskGraph, TaskId},
+    },
+};
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum TaskPlacement {
+    Disabled,
+    SourceWorker { max_task_secs: f64 },
+    GenericWorker { max_task_secs: f64 },
+}
+
+impl TaskPlacement {
+    pub fn new(config: &SpringWorkerConfig, on_source_worker: bool) -> Self {
+        let max_task_secs = config.adaptive_placement_max_task_usec as f64 / 1_000_000.0;
+        // subgraphs are dedicated to workers in thread-per-core mode
+        let enabled = config.adaptive_placement && !config.thread_per_core;
+        match (enabled, on_source_worker) {
+            (false, _) => Self::Disabled,
+            (true, true) => Self::SourceWorker { max_task_secs },
+            (true, false) => Self::GenericWorker { max_task_secs },
+        }
+    }
+
+    /// Adds placed pump tasks to a source worker's task series, or removes them from a generic worker's one.
+    pub fn apply(
+        &self,
+        mut task_series: Vec<TaskId>,
+        graph: &TaskGraph,
+        metrics: &PerformanceMetrics,
+    ) -> Vec<TaskId> {
+        match self {
+            Self::Disabled => task_series,
+            Self::SourceWorker { max_task_secs } => {
+                for task_id in Self::placed_pumps(*max_task_secs, graph, metrics) {
+                    if !task_series.contains(&task_id) {
+                        task_series.push(task_id);
+                    }
+                }
+                task_series
+            }
+            Self::GenericWorker { max_task_secs } => {
+                let placed = Self::placed_pumps(*max_task_secs, graph, metrics)
+                    .into_iter()
+                    .collect::<HashSet<_>>();
+                task_series.retain(|task_id| !placed.contains(task_id));
+                task_series
+            }
+        }
+    }
+
+    /// Placed pump tasks, sorted to make task series stable.
+    fn placed_pumps(
+        max_task_secs: f64,
+        graph: &TaskGraph,
+        metrics: &PerformanceMetrics,
+    ) -> Vec<TaskId> {
+        let mut placed = graph
+            .source_tasks()
+            .iter()
+            .flat_map(|source_task| graph.downstream_tasks(source_task))
+            .filter(|task_id| matches!(task_id, TaskId::Pump { .. }))
+            .filter(|task_id| {
+                graph
+                    .input_queues(task_id)
+                    .iter()
+                    .all(|queue_id| matches!(graph.upstream_task(queue_id), TaskId::Source { .. }))
+            })
+            .filter(|task_id| metrics.throttle(task_id).is_none())
+            .filter(|task_id| {
+                metrics
+                    .avg_execution_secs(task_id)
+                    .map_or(false, |secs| secs <= max_task_secs)
+            })
+            .collect::<Vec<_>>();
+        placed.sort();
+        placed.dedup();
+        placed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(
+        adaptive_placement: bool,
+        adaptive_placement_max_task_usec: u32,
+    ) -> SpringWorkerConfig {
+        SpringWorkerConfig {
+            n_generic_worker_threads: 1,
+            n_source_worker_threads: 1,
+            sleep_msec_no_row: 100,
+            adaptive_placement,
+            adaptive_placement_max_task_usec,
+            thread_per_core: false,
+            micro_batch: false,
+            micro_batch_max_size: 256,
+            micro_batch_latency_target_usec: 1000,
+        }
+    }
+
+    #[test]
+    fn test_task_placement() {
+        // pump_task2 and pump_task7 are fed only by source tasks, and both run 50 us on average
+        let graph = TaskGraph::fx_split_join();
+        let metrics = PerformanceMetrics::fx_split_join();
+
+        let source_series = vec![TaskId::fx_split_join_t1(), TaskId::fx_split_join_t6()];
+        let generic_series = vec![
+            TaskId::fx_split_join_t2(),
+            TaskId::fx_split_join_t3(),
+            TaskId::fx_split_join_t7(),
+        ];
+
+        let placement = TaskPlacement::new(&config(false, 100), true);
+        assert_eq!(
+            placement.apply(source_series.clone(), &graph, &metrics),
+            source_series
+        );
+
+        let placement = TaskPlacement::new(&config(true, 40), true);
+        assert_eq!(
+            placement.apply(source_series.clone(), &graph, &metrics),
+            source_series
+        );
+
+        let placement = TaskPlacement::new(&config(true, 100), true);
+        assert_eq!(
+            placement.apply(source_series.clone(), &graph, &metrics),
+            vec![
+                TaskId::fx_split_join_t1(),
+                TaskId::fx_split_join_t6(),
+                TaskId::fx_split_join_t2(),
+                TaskId::fx_split_join_t7(),
+            ]
+        );
+        let placement = TaskPlacement::new(&config(true, 100), false);
+        assert_eq!(
+            placement.apply(generic_series, &graph, &metrics),
+            vec![TaskId::fx_split_join_t3()]
+        );
+    }
+
+    #[test]
+    fn test_task_placement_not_executed_yet() {
+        let graph = TaskGraph::fx_split_join();
+        let metrics = PerformanceMetrics::from_task_graph(&graph);
+
+        let placement = TaskPlacement::new(&config(true, 100), true);
+        assert_eq!(
+            placement.apply(vec![TaskId::fx_split_join_t1()], &graph, &metrics),
+            vec![TaskId::fx_split_join_t1()]
+        );
+    }
+}
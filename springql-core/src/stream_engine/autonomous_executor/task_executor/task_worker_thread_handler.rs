@@ -6,11 +6,19 @@ use std::{fmt::Display, sync::Arc, thread, time::Duration};
 
 use crate::stream_engine::autonomous_executor::{
     event_queue::{Event, NonBlockingEventQueue},
-    performance_metrics::{MetricsUpdateByTaskExecutionOrPurge, PerformanceMetrics},
+    performance_metrics::{
+        MetricsUpdateByTaskExecution, MetricsUpdateByTaskExecutionOrPurge, PerformanceMetrics,
+    },
     pipeline_derivatives::PipelineDerivatives,
     repositories::Repositories,
     task::{ProcessedRows, TaskContext},
-    task_executor::{scheduler::Scheduler, task_executor_lock::TaskExecutorLock},
+    task_executor::{
+        micro_batch::{Batch, BatchSizes, MicroBatch},
+        scheduler::Scheduler,
+        task_executor_lock::TaskExecutorLock,
+        task_partition::TaskPartition,
+        task_placement::TaskPlacement,
+    },
     task_graph::TaskId,
     worker::WorkerThreadLoopState,
     AutonomousExecutor,
@@ -24,6 +32,11 @@ pub struct TaskWorkerThreadHandler;
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug, new)]
 pub struct TaskWorkerId(u16);
+impl TaskWorkerId {
+    pub fn as_u16(&self) -> u16 {
+        self.0
+    }
+}
 impl Display for TaskWorkerId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
@@ -36,6 +49,9 @@ pub struct TaskWorkerThreadArg {
     task_executor_lock: Arc<TaskExecutorLock>,
     repos: Arc<Repositories>,
     sleep_msec_no_row: u64,
+    task_placement: TaskPlacement,
+    task_partition: TaskPartition,
+    micro_batch: MicroBatch,
 }
 
 #[derive(Debug)]
@@ -43,19 +59,27 @@ pub struct TaskWorkerLoopState<S: Scheduler> {
     pub pipeline_derivatives: Option<Arc<PipelineDerivatives>>,
     pub metrics: Option<Arc<PerformanceMetrics>>,
     pub scheduler: S,
+    /// Batch sizes of tasks this worker has executed in micro-batch mode.
+    pub batch_sizes: BatchSizes,
+    /// Killed by chaos mode. A killed worker executes no more tasks.
+    pub killed: bool,
 }
 
 impl<S: Scheduler> WorkerThreadLoopState for TaskWorkerLoopState<S> {
     type ThreadArg = TaskWorkerThreadArg;
 
-    fn new(_thread_arg: &Self::ThreadArg) -> Self
+    /// Called in the worker thread.
+    fn new(thread_arg: &Self::ThreadArg) -> Self
     where
         Self: Sized,
     {
+        thread_arg.task_partition.pin_current_thread();
         Self {
             pipeline_derivatives: None,
             metrics: None,
             scheduler: S::default(),
+            batch_sizes: BatchSizes::default(),
+            killed: false,
         }
     }
 
@@ -69,7 +93,7 @@ impl<S: Scheduler> WorkerThreadLoopState for TaskWorkerLoopState<S> {
 
 impl TaskWorkerThreadHandler {
     pub fn main_loop_cycle<S>(
-        current_state: TaskWorkerLoopState<S>,
+        mut current_state: TaskWorkerLoopState<S>,
         thread_arg: &TaskWorkerThreadArg,
         event_queue: &NonBlockingEventQueue,
     ) -> TaskWorkerLoopState<S>
@@ -79,20 +103,37 @@ impl TaskWorkerThreadHandler {
         if let (Some(pipeline_derivatives), Some(metrics)) =
             (&current_state.pipeline_derivatives, &current_state.metrics)
         {
+            if current_state.killed {
+                thread::sleep(Duration::from_millis(TASK_WAIT_MSEC));
+                return current_state;
+            }
+
             let task_executor_lock = &thread_arg.task_executor_lock;
 
             if let Ok(_lock) = task_executor_lock.try_task_execution() {
+                let task_graph = pipeline_derivatives.task_graph();
                 let task_series = current_state
                     .scheduler
-                    .next_task_series(pipeline_derivatives.task_graph(), metrics.as_ref());
+                    .next_task_series(task_graph, metrics.as_ref());
+                let task_series =
+                    thread_arg
+                        .task_placement
+                        .apply(task_series, task_graph, metrics.as_ref());
+                let mut task_series = thread_arg.task_partition.apply(task_series, task_graph);
+                task_series.retain(|task_id| !task_graph.is_stopped(task_id));
                 if !task_series.is_empty() {
                     let processed_rows = Self::execute_task_series::<S>(
                         &task_series,
                         pipeline_derivatives.clone(),
+                        metrics.as_ref(),
+                        &mut current_state.batch_sizes,
                         thread_arg,
                         event_queue,
                     );
-                    if processed_rows.is_empty() {
+                    if thread_arg.repos.chaos_injector().kill_worker() {
+                        log::error!("[TaskWorker#{}] killed by chaos mode", thread_arg.worker_id);
+                        current_state.killed = true;
+                    } else if processed_rows.is_empty() {
                         // Wait for rows to process
                         thread::sleep(Duration::from_millis(thread_arg.sleep_msec_no_row));
                     }
@@ -111,6 +152,8 @@ impl TaskWorkerThreadHandler {
     fn execute_task_series<S>(
         task_series: &[TaskId],
         pipeline_derivatives: Arc<PipelineDerivatives>,
+        metrics: &PerformanceMetrics,
+        batch_sizes: &mut BatchSizes,
         thread_arg: &TaskWorkerThreadArg,
         event_queue: &NonBlockingEventQueue,
     ) -> ProcessedRows
@@ -130,24 +173,77 @@ impl TaskWorkerThreadHandler {
                     .get_task(task_id)
                     .expect("task id got from scheduler");
 
-                let processed_rows = task
-                    .run(&context)
-                    .map(|run_result| {
-                        event_queue.publish(Event::IncrementalUpdateMetrics {
-                            metrics_update_by_task_execution_or_purge: Arc::new(
-                                MetricsUpdateByTaskExecutionOrPurge::TaskExecution(
-                                    run_result.metrics,
-                                ),
-                            ),
+                let batch_size = thread_arg.micro_batch.size(batch_sizes, task_id);
+                let mut batch = Batch::default();
+                let mut batch_processed_rows = ProcessedRows::default();
+                // published after the batch with the tuned batch size
+                let mut last_metrics = None;
+
+                while batch.executions < batch_size && !batch.drained {
+                    thread_arg.repos.chaos_injector().delay_task();
+
+                    let processed_rows = thread_arg
+                        .repos
+                        .task_hook_repository()
+                        .around(task_id, thread_arg.worker_id.as_u16(), || {
+                            task.run(&context)
+                        })
+                        .map(|run_result| {
+                            batch.execution_time =
+                                batch.execution_time + run_result.metrics.task_execution_time();
+                            if let Some(metrics_update) = last_metrics.replace(run_result.metrics) {
+                                Self::publish_metrics(metrics_update, event_queue);
+                            }
+                            run_result.processed_rows
+                        })
+                        .unwrap_or_else(|e| {
+                            AutonomousExecutor::handle_error(e, &thread_arg.repos);
+                            ProcessedRows::default()
                         });
-                        run_result.processed_rows
-                    })
-                    .unwrap_or_else(|e| {
-                        AutonomousExecutor::handle_error(e);
-                        ProcessedRows::default()
-                    });
-
-                acc_processed_rows + processed_rows
+
+                    batch.executions += 1;
+                    batch.drained = processed_rows.is_empty();
+                    batch_processed_rows = batch_processed_rows + processed_rows;
+                }
+
+                let tuned_batch_size = thread_arg.micro_batch.tune(
+                    batch_sizes,
+                    task_id,
+                    &batch,
+                    pipeline_derivatives.task_graph(),
+                    metrics,
+                );
+                if let Some(metrics_update) = last_metrics {
+                    let metrics_update = match tuned_batch_size {
+                        Some(tuned_batch_size) => {
+                            if tuned_batch_size != batch_size {
+                                log::debug!(
+                                    "[TaskWorker#{}] batch size of {}: {} -> {}",
+                                    thread_arg.worker_id,
+                                    task_id,
+                                    batch_size,
+                                    tuned_batch_size
+                                );
+                            }
+                            metrics_update.with_batch_size(tuned_batch_size)
+                        }
+                        None => metrics_update,
+                    };
+                    Self::publish_metrics(metrics_update, event_queue);
+                }
+
+                acc_processed_rows + batch_processed_rows
             })
     }
+
+    fn publish_metrics(
+        metrics_update: MetricsUpdateByTaskExecution,
+        event_queue: &NonBlockingEventQueue,
+    ) {
+        event_queue.publish(Event::IncrementalUpdateMetrics {
+            metrics_update_by_task_execution_or_purge: Arc::new(
+                MetricsUpdateByTaskExecutionOrPurge::TaskExecution(metrics_update),
+            ),
+        });
+    }
 }
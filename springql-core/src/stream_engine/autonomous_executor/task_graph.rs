@@ -17,13 +17,21 @@ mod edge_ref;
 pub use queue_id::{QueueId, RowQueueId, WindowQueueId};
 pub use task_id::TaskId;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use petgraph::graph::{DiGraph, NodeIndex};
+use once_cell::sync::OnceCell;
+use petgraph::{
+    algo::toposort,
+    graph::{DiGraph, NodeIndex},
+    unionfind::UnionFind,
+    visit::EdgeRef,
+};
 
 use crate::{
-    pipeline::{Edge, Pipeline, PipelineVersion, StreamName},
-    stream_engine::autonomous_executor::task_graph::edge_ref::MyEdgeRef,
+    pipeline::{Edge, Pipeline, PipelineVersion, StreamName, Tags},
+    stream_engine::{
+        autonomous_executor::task_graph::edge_ref::MyEdgeRef, time::WallClockDuration,
+    },
 };
 
 #[derive(Clone, Debug, new)]
@@ -40,6 +48,24 @@ pub struct TaskGraph {
     g: DiGraph<TaskId, QueueIdWithUpstream>,
     task_id_node_map: HashMap<TaskId, NodeIndex>,
     queue_id_edge_map: HashMap<QueueId, MyEdgeRef>,
+
+    /// Queues which may have high-priority rows from `PRIORITY WHEN` pumps.
+    priority_queues: HashSet<QueueId>,
+
+    /// Sink tasks with `LATENCY_SLO_MSEC` option.
+    sink_latency_slos: HashMap<TaskId, WallClockDuration>,
+
+    /// Tasks of `BEST_EFFORT` pumps.
+    best_effort_tasks: HashSet<TaskId>,
+
+    /// Tasks of pumps stopped by `ALTER PUMP p STOP`.
+    stopped_tasks: HashSet<TaskId>,
+
+    /// Tags of pumps (for pump tasks) and streams (for source and sink tasks).
+    task_tags: HashMap<TaskId, Tags>,
+
+    /// Index of the connected subgraph each task belongs to. Calculated on first use.
+    subgraphs: OnceCell<HashMap<TaskId, usize>>,
 }
 
 impl TaskGraph {
@@ -49,6 +75,12 @@ impl TaskGraph {
             g: DiGraph::default(),
             task_id_node_map: HashMap::default(),
             queue_id_edge_map: HashMap::default(),
+            priority_queues: HashSet::default(),
+            sink_latency_slos: HashMap::default(),
+            best_effort_tasks: HashSet::default(),
+            stopped_tasks: HashSet::default(),
+            task_tags: HashMap::default(),
+            subgraphs: OnceCell::default(),
         }
     }
 
@@ -93,6 +125,22 @@ impl TaskGraph {
             .collect()
     }
 
+    /// Output queues carrying rows of `stream`.
+    ///
+    /// A router task has output queues for each of its downstream streams.
+    pub fn output_queues_of(&self, task_id: &TaskId, stream: &StreamName) -> Vec<QueueId> {
+        let i = self.find_node(task_id);
+        self.g
+            .edges_directed(i, petgraph::EdgeDirection::Outgoing)
+            .into_iter()
+            .filter_map(|e| {
+                let queue_id_with_upstream = e.weight();
+                (&queue_id_with_upstream.upstream == stream)
+                    .then(|| queue_id_with_upstream.queue_id.clone())
+            })
+            .collect()
+    }
+
     /// # Returns
     ///
     /// `None` if `task_id` does not have incoming edge (queue) from `upstream`.
@@ -109,6 +157,103 @@ impl TaskGraph {
             })
     }
 
+    pub fn is_priority_queue(&self, queue_id: &QueueId) -> bool {
+        self.priority_queues.contains(queue_id)
+    }
+
+    /// # Returns
+    ///
+    /// `None` if `task_id` is not a sink task with latency SLO.
+    pub fn sink_latency_slo(&self, task_id: &TaskId) -> Option<WallClockDuration> {
+        self.sink_latency_slos.get(task_id).copied()
+    }
+
+    pub fn is_best_effort(&self, task_id: &TaskId) -> bool {
+        self.best_effort_tasks.contains(task_id)
+    }
+
+    pub fn is_stopped(&self, task_id: &TaskId) -> bool {
+        self.stopped_tasks.contains(task_id)
+    }
+
+    /// # Returns
+    ///
+    /// `None` if `task_id` has no tag.
+    pub fn task_tags(&self, task_id: &TaskId) -> Option<&Tags> {
+        self.task_tags.get(task_id)
+    }
+
+    /// Tags of the task consuming `queue_id`.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the task has no tag or `queue_id` is not in this graph.
+    pub fn queue_tags(&self, queue_id: &QueueId) -> Option<&Tags> {
+        let edge = self.queue_id_edge_map.get(queue_id)?;
+        let task_id = self.g.node_weight(edge.target())?;
+        self.task_tags(task_id)
+    }
+
+    /// Namespace of the stream whose rows flow through `queue_id`.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the stream has no namespace or `queue_id` is not in this graph.
+    pub fn queue_namespace(&self, queue_id: &QueueId) -> Option<&str> {
+        self.g
+            .edge_weights()
+            .find(|queue_id_with_upstream| &queue_id_with_upstream.queue_id == queue_id)
+            .and_then(|queue_id_with_upstream| queue_id_with_upstream.upstream.namespace())
+    }
+
+    /// Namespace of the streams `task_id` reads (or writes, for source tasks).
+    ///
+    /// # Returns
+    ///
+    /// `None` if the streams have no namespace.
+    pub fn task_namespace(&self, task_id: &TaskId) -> Option<&str> {
+        let i = self.find_node(task_id);
+        self.g
+            .edges_directed(i, petgraph::EdgeDirection::Incoming)
+            .chain(self.g.edges_directed(i, petgraph::EdgeDirection::Outgoing))
+            .find_map(|e| e.weight().upstream.namespace())
+    }
+
+    /// Queues carrying rows of streams in `namespace`.
+    pub fn namespace_queues(&self, namespace: &str) -> Vec<QueueId> {
+        self.g
+            .edge_weights()
+            .filter(|queue_id_with_upstream| {
+                queue_id_with_upstream.upstream.namespace() == Some(namespace)
+            })
+            .map(|queue_id_with_upstream| queue_id_with_upstream.queue_id.clone())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    pub fn best_effort_tasks(&self) -> Vec<TaskId> {
+        self.best_effort_tasks.iter().cloned().collect()
+    }
+
+    pub fn stopped_tasks(&self) -> Vec<TaskId> {
+        self.stopped_tasks.iter().cloned().collect()
+    }
+
+    /// Index of the (weakly) connected subgraph `task_id` belongs to.
+    ///
+    /// Subgraphs share no queue with each other. They are indexed from 0, in order of their smallest task id.
+    ///
+    /// # Returns
+    ///
+    /// `None` if `task_id` is not in this graph.
+    pub fn subgraph_index(&self, task_id: &TaskId) -> Option<usize> {
+        self.subgraphs
+            .get_or_init(|| self.calc_subgraphs())
+            .get(task_id)
+            .copied()
+    }
+
     pub fn downstream_tasks(&self, task_id: &TaskId) -> Vec<TaskId> {
         self.output_queues(task_id)
             .iter()
@@ -120,6 +265,14 @@ impl TaskGraph {
         self.g.node_weights().cloned().collect()
     }
 
+    /// Tasks in topological order (upstream tasks first), or in arbitrary order if the graph has a cycle.
+    pub fn tasks_in_topological_order(&self) -> Vec<TaskId> {
+        toposort(&self.g, None).map_or_else(
+            |_| self.tasks(),
+            |nodes| nodes.into_iter().map(|i| self.g[i].clone()).collect(),
+        )
+    }
+
     pub fn source_tasks(&self) -> Vec<TaskId> {
         self.tasks()
             .iter()
@@ -165,6 +318,7 @@ impl TaskGraph {
     pub fn add_task(&mut self, task_id: TaskId) {
         let i = self.g.add_node(task_id.clone());
         let _ = self.task_id_node_map.insert(task_id, i);
+        self.subgraphs = OnceCell::default();
     }
 
     /// # Panics
@@ -177,6 +331,60 @@ impl TaskGraph {
 
         let edge_ref = MyEdgeRef::new(source, target);
         let _ = self.queue_id_edge_map.insert(queue_id.queue_id, edge_ref);
+        self.subgraphs = OnceCell::default();
+    }
+
+    /// Marks `queue_id` to have high-priority rows.
+    pub fn add_priority_queue(&mut self, queue_id: QueueId) {
+        let _ = self.priority_queues.insert(queue_id);
+    }
+
+    pub fn add_sink_latency_slo(&mut self, task_id: TaskId, latency_slo: WallClockDuration) {
+        let _ = self.sink_latency_slos.insert(task_id, latency_slo);
+    }
+
+    pub fn add_best_effort_task(&mut self, task_id: TaskId) {
+        let _ = self.best_effort_tasks.insert(task_id);
+    }
+
+    pub fn add_stopped_task(&mut self, task_id: TaskId) {
+        let _ = self.stopped_tasks.insert(task_id);
+    }
+
+    pub fn add_task_tags(&mut self, task_id: TaskId, tags: Tags) {
+        if !tags.is_empty() {
+            let _ = self.task_tags.insert(task_id, tags);
+        }
+    }
+
+    fn calc_subgraphs(&self) -> HashMap<TaskId, usize> {
+        let mut union_find = UnionFind::new(self.g.node_count());
+        for edge in self.g.edge_references() {
+            let _ = union_find.union(edge.source().index(), edge.target().index());
+        }
+
+        // smallest task id in each subgraph, keyed by representative node
+        let mut representatives = HashMap::<usize, &TaskId>::new();
+        for (task_id, i) in &self.task_id_node_map {
+            let representative = union_find.find(i.index());
+            let smallest = representatives.entry(representative).or_insert(task_id);
+            if task_id < *smallest {
+                *smallest = task_id;
+            }
+        }
+        let mut smallest_task_ids = representatives.values().copied().collect::<Vec<_>>();
+        smallest_task_ids.sort();
+
+        self.task_id_node_map
+            .iter()
+            .map(|(task_id, i)| {
+                let smallest = representatives[&union_find.find(i.index())];
+                let index = smallest_task_ids
+                    .binary_search(&smallest)
+                    .expect("every subgraph has its smallest task id");
+                (task_id.clone(), index)
+            })
+            .collect()
     }
 
     /// # Panics
@@ -209,11 +417,23 @@ impl From<&Pipeline> for TaskGraph {
         // add all task ids
         pipeline_petgraph.edge_weights().for_each(|edge| {
             let task_id = TaskId::from(edge);
-            // duplicate task id on JOIN pump task (but it's ok)
+            if matches!(edge, Edge::Pump { pump_model, .. } if pump_model.best_effort()) {
+                task_graph.add_best_effort_task(task_id.clone());
+            }
+            if let Edge::Pump { pump_model, .. } = edge {
+                if pipeline_graph.is_pump_stopped(pump_model.name()) {
+                    task_graph.add_stopped_task(task_id.clone());
+                }
+            }
+            if let Some(tags) = task_tags(pipeline, edge) {
+                task_graph.add_task_tags(task_id.clone(), tags);
+            }
+            // duplicate task id on JOIN and ROUTER pump task (but it's ok)
             task_graph.add_task(task_id);
         });
 
         // Add all queues.
+        let mut pump_queue_ids = HashSet::new();
         for edge_ref in pipeline_petgraph.edge_references() {
             match edge_ref.weight() {
                 Edge::Pump {
@@ -221,12 +441,19 @@ impl From<&Pipeline> for TaskGraph {
                     upstream,
                 } => {
                     let queue_id = QueueId::from_pump(pump_model, upstream);
+                    if !pump_queue_ids.insert(queue_id.clone()) {
+                        // a router pump has an edge to each downstream but only 1 input queue from an upstream
+                        continue;
+                    }
                     let target = TaskId::from_pump(pump_model);
                     pipeline_graph
                         .upstream_edges(&edge_ref)
                         .iter()
                         .for_each(|source_edge_ref| {
                             let source_edge = source_edge_ref.weight();
+                            if is_prioritized_pump(source_edge) {
+                                task_graph.add_priority_queue(queue_id.clone());
+                            }
                             let source = TaskId::from(source_edge);
                             task_graph.add_queue(
                                 QueueIdWithUpstream::new(queue_id.clone(), upstream.clone()),
@@ -238,11 +465,20 @@ impl From<&Pipeline> for TaskGraph {
                 Edge::Sink(sink) => {
                     let queue_id = QueueId::from_sink(sink);
                     let target = TaskId::from_sink(sink);
+                    if let Some(latency_slo) = sink
+                        .latency_slo()
+                        .expect("LATENCY_SLO_MSEC must be validated on CREATE SINK WRITER")
+                    {
+                        task_graph.add_sink_latency_slo(target.clone(), latency_slo);
+                    }
                     let source_edge = pipeline_graph
                         .upstream_edges(&edge_ref)
                         .first()
                         .expect("sink writer must have 1 upstream pump")
                         .weight();
+                    if is_prioritized_pump(source_edge) {
+                        task_graph.add_priority_queue(queue_id.clone());
+                    }
                     let source = TaskId::from(source_edge);
                     task_graph.add_queue(
                         QueueIdWithUpstream::new(queue_id, sink.sink_upstream().clone()),
@@ -256,3 +492,20 @@ impl From<&Pipeline> for TaskGraph {
         task_graph
     }
 }
+
+/// Pump tasks have tags of the pump, and source and sink tasks have tags of their streams.
+fn task_tags(pipeline: &Pipeline, edge: &Edge) -> Option<Tags> {
+    let stream = match edge {
+        Edge::Pump { pump_model, .. } => return Some(pump_model.tags().clone()),
+        Edge::Source(source) => source.dest_source_stream(),
+        Edge::Sink(sink) => sink.sink_upstream(),
+    };
+    pipeline
+        .get_stream(stream)
+        .ok()
+        .map(|stream| stream.tags().clone())
+}
+
+fn is_prioritized_pump(edge: &Edge) -> bool {
+    matches!(edge, Edge::Pump { pump_model, .. } if pump_model.prioritized())
+}
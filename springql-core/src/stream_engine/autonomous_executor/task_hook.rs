@@ -0,0 +1,148 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{fmt::Debug, sync::Arc, time::Instant};
+
+use parking_lot::RwLock;
+
+use crate::{
+    api::{error::Result, SpringTaskExecution, SpringTaskHook, SpringTaskInfo, SpringTaskKind},
+    stream_engine::autonomous_executor::{task::TaskRunResult, task_graph::TaskId},
+};
+
+/// Task hooks registered by embedders.
+#[derive(Default)]
+pub struct TaskHookRepository {
+    hooks: RwLock<Vec<Arc<dyn SpringTaskHook>>>,
+}
+
+impl TaskHookRepository {
+    pub fn register(&self, hook: Arc<dyn SpringTaskHook>) {
+        self.hooks.write().push(hook);
+    }
+
+    /// Runs a task execution `run` between `before_task()` and `after_task()` of hooks.
+    pub fn around<F>(&self, task_id: &TaskId, worker_id: u16, run: F) -> Result<TaskRunResult>
+    where
+        F: FnOnce() -> Result<TaskRunResult>,
+    {
+        let hooks = {
+            let hooks = self.hooks.read();
+            if hooks.is_empty() {
+                return run();
+            }
+            // hooks may take long; do not block registration
+            hooks.clone()
+        };
+
+        let task = task_info(task_id, worker_id);
+        hooks.iter().for_each(|hook| hook.before_task(&task));
+
+        let started_at = Instant::now();
+        let result = run();
+        let elapsed = started_at.elapsed();
+
+        let execution = match &result {
+            Ok(run_result) => SpringTaskExecution {
+                processed_rows: run_result.processed_rows.as_u64(),
+                rows_put: run_result.metrics.rows_put(),
+                elapsed,
+                error: None,
+            },
+            Err(e) => SpringTaskExecution {
+                processed_rows: 0,
+                rows_put: 0,
+                elapsed,
+                error: Some(e.to_string()),
+            },
+        };
+        hooks
+            .iter()
+            .for_each(|hook| hook.after_task(&task, &execution));
+
+        result
+    }
+}
+
+impl Debug for TaskHookRepository {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskHookRepository")
+            .field("hooks", &self.hooks.read().len())
+            .finish()
+    }
+}
+
+fn task_info(task_id: &TaskId, worker_id: u16) -> SpringTaskInfo {
+    let (kind, name) = match task_id {
+        TaskId::Source { id } => (SpringTaskKind::Source, id),
+        TaskId::Pump { id, .. } => (SpringTaskKind::Pump, id),
+        TaskId::Sink { id } => (SpringTaskKind::Sink, id),
+    };
+    SpringTaskInfo {
+        kind,
+        name: name.clone(),
+        worker_id,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parking_lot::Mutex;
+
+    use crate::{
+        api::error::SpringError,
+        stream_engine::autonomous_executor::{
+            performance_metrics::MetricsUpdateByTaskExecution, task::ProcessedRows,
+        },
+    };
+
+    use super::*;
+
+    #[derive(Default)]
+    struct RecordingHook {
+        calls: Mutex<Vec<String>>,
+    }
+
+    impl SpringTaskHook for RecordingHook {
+        fn before_task(&self, task: &SpringTaskInfo) {
+            self.calls.lock().push(format!("before {}", task.name));
+        }
+
+        fn after_task(&self, task: &SpringTaskInfo, execution: &SpringTaskExecution) {
+            self.calls.lock().push(format!(
+                "after {} ({} -> {} rows, error: {})",
+                task.name,
+                execution.processed_rows,
+                execution.rows_put,
+                execution.error.is_some()
+            ));
+        }
+    }
+
+    #[test]
+    fn test_task_hook_repository() {
+        let repo = TaskHookRepository::default();
+        let hook = Arc::new(RecordingHook::default());
+        repo.register(hook.clone());
+
+        let task_id = TaskId::fx_split_join_t2();
+        let _ = repo.around(&task_id, 1, || {
+            Ok(TaskRunResult {
+                processed_rows: ProcessedRows::new(1),
+                metrics: MetricsUpdateByTaskExecution::fx_split_join_t2(),
+            })
+        });
+        let _ = repo.around(&task_id, 1, || {
+            Err(SpringError::Sql(anyhow::anyhow!("failed")))
+        });
+
+        assert_eq!(
+            *hook.calls.lock(),
+            vec![
+                "before pump_task2".to_string(),
+                "after pump_task2 (1 -> 1 rows, error: false)".to_string(),
+                "before pump_task2".to_string(),
+                "after pump_task2 (0 -> 0 rows, error: true)".to_string(),
+            ]
+        );
+    }
+}
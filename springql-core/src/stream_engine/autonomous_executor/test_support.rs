@@ -4,3 +4,4 @@
 
 pub mod factory;
 pub mod fixture;
+pub mod strategy;
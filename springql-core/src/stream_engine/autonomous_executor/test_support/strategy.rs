@@ -0,0 +1,72 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! proptest strategies for rows and values. Public ones for `SpringSourceRow` are in `api::testing`.
+
+use std::time::Duration;
+
+use ordered_float::OrderedFloat;
+use proptest::{collection::SizeRange, prelude::*};
+
+use crate::{
+    api::testing::{rowtimes, spring_timestamp},
+    stream_engine::{
+        autonomous_executor::{
+            row::{NnSqlValue, SqlValue},
+            task::Tuple,
+        },
+        time::{SpringDuration, SpringEventDuration},
+    },
+};
+
+const TICKERS: &[&str] = &["GOOGL", "ORCL", "IBM"];
+
+/// Any type of value. FLOAT is finite (NaN is not comparable).
+pub fn nn_sql_value() -> impl Strategy<Value = NnSqlValue> {
+    prop_oneof![
+        any::<i16>().prop_map(NnSqlValue::SmallInt),
+        any::<i32>().prop_map(NnSqlValue::Integer),
+        any::<i64>().prop_map(NnSqlValue::BigInt),
+        any::<u32>().prop_map(NnSqlValue::UnsignedInteger),
+        any::<u64>().prop_map(NnSqlValue::UnsignedBigInt),
+        (prop::num::f32::NORMAL | prop::num::f32::ZERO)
+            .prop_map(|f| NnSqlValue::Float(OrderedFloat(f))),
+        ".{0,16}".prop_map(NnSqlValue::Text),
+        prop::collection::vec(any::<u8>(), 0..16).prop_map(NnSqlValue::Blob),
+        any::<bool>().prop_map(NnSqlValue::Boolean),
+        spring_timestamp().prop_map(NnSqlValue::Timestamp),
+        any::<u32>().prop_map(
+            |millis| NnSqlValue::Duration(SpringEventDuration::from_millis(millis as u64))
+        ),
+    ]
+}
+
+/// NULL in 1/8.
+pub fn sql_value() -> impl Strategy<Value = SqlValue> {
+    prop_oneof![
+        1 => Just(SqlValue::Null),
+        7 => nn_sql_value().prop_map(SqlValue::NotNull),
+    ]
+}
+
+/// Tuples of `Tuple::factory_trade()` with `api::testing::rowtimes()`.
+pub fn trade_tuples(
+    len: impl Into<SizeRange>,
+    max_disorder: Duration,
+) -> impl Strategy<Value = Vec<Tuple>> {
+    rowtimes(len, max_disorder).prop_flat_map(|rowtimes| {
+        let n = rowtimes.len();
+        (
+            Just(rowtimes),
+            prop::collection::vec((prop::sample::select(TICKERS), 0..10_000_i16), n),
+        )
+            .prop_map(|(rowtimes, values)| {
+                rowtimes
+                    .into_iter()
+                    .zip(values)
+                    .map(|(rowtime, (ticker, amount))| {
+                        Tuple::factory_trade(rowtime, ticker, amount)
+                    })
+                    .collect()
+            })
+    })
+}
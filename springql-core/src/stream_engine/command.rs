@@ -1,17 +1,95 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 mod alter_pipeline_command;
+mod infer_schema_command;
 mod insert_plan;
+mod pump_output_plan;
 mod query_plan;
+mod replicate_plan;
+mod route_plan;
+mod show_objects_command;
 
 pub use crate::stream_engine::command::alter_pipeline_command::AlterPipelineCommand;
+pub use infer_schema_command::InferSchemaCommand;
 pub use insert_plan::InsertPlan;
+pub use pump_output_plan::PumpOutputPlan;
 pub use query_plan::{
-    CollectOp, GroupAggregateWindowOp, JoinOp, JoinWindowOp, LowerOps, ProjectionOp, QueryPlan,
-    UpperOps,
+    CollectOp, GroupAggregateWindowOp, IntervalJoinOp, JoinOp, JoinWindowOp, LowerOps,
+    ProjectionOp, QueryPlan, SelectionOp, UpperOps,
 };
+pub use replicate_plan::ReplicatePlan;
+pub use route_plan::RoutePlan;
+pub use show_objects_command::ShowObjectsCommand;
 
 #[derive(Clone, PartialEq, Debug)]
 pub enum Command {
     AlterPipeline(AlterPipelineCommand),
+
+    /// Does not alter pipeline but returns a suggested DDL.
+    InferSchema(InferSchemaCommand),
+
+    /// Does not alter pipeline but returns statements applied so far.
+    ShowHistory,
+
+    /// Does not alter pipeline but returns unused streams, pumps, and columns.
+    ShowWarnings,
+
+    /// Does not alter pipeline but returns objects in the pipeline as rows.
+    ShowObjects(ShowObjectsCommand),
+
+    /// Does not alter pipeline but returns the query plan of a pump or a `SELECT STREAM`.
+    Explain(Box<QueryPlan>),
+}
+
+impl Command {
+    /// Statement names a command is compiled from, used to restrict commands by `permission.role`.
+    pub const STATEMENTS: [&'static str; 21] = [
+        "CREATE SOURCE STREAM",
+        "CREATE SOURCE READER",
+        "CREATE STREAM",
+        "CREATE SINK STREAM",
+        "CREATE SINK WRITER",
+        "CREATE PUMP",
+        "SWITCH PUMP",
+        "DROP PUMP",
+        "DROP STREAM",
+        "DROP SOURCE READER",
+        "DROP SINK WRITER",
+        "ALTER PUMP",
+        "ALTER STREAM",
+        "INFER SCHEMA",
+        "SHOW HISTORY",
+        "SHOW WARNINGS",
+        "SHOW STREAMS",
+        "SHOW PUMPS",
+        "SHOW SOURCE READERS",
+        "SHOW SINK WRITERS",
+        "EXPLAIN",
+    ];
+
+    /// One of `Command::STATEMENTS`.
+    pub fn statement(&self) -> &'static str {
+        match self {
+            Command::AlterPipeline(c) => match c {
+                AlterPipelineCommand::CreateSourceStream(_) => "CREATE SOURCE STREAM",
+                AlterPipelineCommand::CreateSourceReader(_) => "CREATE SOURCE READER",
+                AlterPipelineCommand::CreateStream(_) => "CREATE STREAM",
+                AlterPipelineCommand::CreateSinkStream(_) => "CREATE SINK STREAM",
+                AlterPipelineCommand::CreateSinkWriter(_) => "CREATE SINK WRITER",
+                AlterPipelineCommand::CreatePump(_) => "CREATE PUMP",
+                AlterPipelineCommand::SwitchPump { .. } => "SWITCH PUMP",
+                AlterPipelineCommand::DropPump(_) => "DROP PUMP",
+                AlterPipelineCommand::DropStream(_) => "DROP STREAM",
+                AlterPipelineCommand::DropSourceReader(_) => "DROP SOURCE READER",
+                AlterPipelineCommand::DropSinkWriter(_) => "DROP SINK WRITER",
+                AlterPipelineCommand::AlterPumpState { .. } => "ALTER PUMP",
+                AlterPipelineCommand::AlterStreamTrace { .. } => "ALTER STREAM",
+            },
+            Command::InferSchema(_) => "INFER SCHEMA",
+            Command::ShowHistory => "SHOW HISTORY",
+            Command::ShowWarnings => "SHOW WARNINGS",
+            Command::ShowObjects(c) => c.statement(),
+            Command::Explain(_) => "EXPLAIN",
+        }
+    }
 }
@@ -1,6 +1,9 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use crate::pipeline::{PumpModel, SinkWriterModel, SourceReaderModel, StreamModel};
+use crate::pipeline::{
+    PumpModel, PumpName, SinkWriterModel, SinkWriterName, SourceReaderModel, SourceReaderName,
+    StreamModel, StreamName,
+};
 
 #[allow(clippy::enum_variant_names)]
 #[derive(Clone, PartialEq, Debug)]
@@ -11,4 +14,28 @@ pub enum AlterPipelineCommand {
     CreateSinkStream(StreamModel),
     CreateSinkWriter(SinkWriterModel),
     CreatePump(Box<PumpModel>),
+
+    /// Drops `from_pump` so that only `to_pump` feeds their downstream streams.
+    SwitchPump {
+        from_pump: PumpName,
+        to_pump: PumpName,
+    },
+
+    DropPump(PumpName),
+    /// Only a stream without any pump, source reader, or sink writer can be dropped.
+    DropStream(StreamName),
+    DropSourceReader(SourceReaderName),
+    DropSinkWriter(SinkWriterName),
+
+    /// Stops (`stopped: true`) or restarts (`stopped: false`) a pump without dropping it.
+    AlterPumpState {
+        pump_name: PumpName,
+        stopped: bool,
+    },
+
+    /// Starts (`Some(sample_rate)`) or stops (`None`) logging sampled rows of a stream.
+    AlterStreamTrace {
+        stream_name: StreamName,
+        trace_sample_rate: Option<f32>,
+    },
 }
@@ -0,0 +1,36 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use crate::pipeline::{Options, SourceReaderType, StreamName};
+
+/// `INFER SCHEMA` samples rows from a source reader which is not a part of the pipeline.
+///
+/// `INFER SCHEMA FROM SOURCE READER source_reader_name` starts another source reader with the type and options of the named one.
+#[derive(Clone, PartialEq, Eq, Debug, new)]
+pub struct InferSchemaCommand {
+    /// Name of the stream in the suggested DDL.
+    stream_name: StreamName,
+
+    source_reader_type: SourceReaderType,
+    options: Options,
+
+    /// Max number of rows to sample.
+    n_samples: usize,
+}
+
+impl InferSchemaCommand {
+    pub fn stream_name(&self) -> &StreamName {
+        &self.stream_name
+    }
+
+    pub fn source_reader_type(&self) -> &SourceReaderType {
+        &self.source_reader_type
+    }
+
+    pub fn options(&self) -> &Options {
+        &self.options
+    }
+
+    pub fn n_samples(&self) -> usize {
+        self.n_samples
+    }
+}
@@ -0,0 +1,66 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use crate::{
+    pipeline::{ColumnName, StreamName},
+    stream_engine::command::{InsertPlan, ReplicatePlan, RoutePlan},
+};
+
+/// Where a pump puts rows from its query plan.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum PumpOutputPlan {
+    /// `CREATE PUMP ... INSERT INTO`
+    Insert(InsertPlan),
+
+    /// `CREATE ROUTER`
+    Route(RoutePlan),
+
+    /// `CREATE REPLICATOR`
+    Replicate(ReplicatePlan),
+}
+
+impl PumpOutputPlan {
+    pub fn column_order(&self) -> &[ColumnName] {
+        match self {
+            Self::Insert(insert_plan) => insert_plan.column_order(),
+            Self::Route(route_plan) => route_plan.column_order(),
+            Self::Replicate(replicate_plan) => replicate_plan.column_order(),
+        }
+    }
+
+    /// Streams which have rows matching the i-th condition.
+    pub fn when_streams(&self) -> &[StreamName] {
+        match self {
+            Self::Route(route_plan) => route_plan.when_streams(),
+            Self::Insert(_) | Self::Replicate(_) => &[],
+        }
+    }
+
+    /// Stream which has rows matching no condition.
+    pub fn else_stream(&self) -> Option<&StreamName> {
+        match self {
+            Self::Route(route_plan) => route_plan.else_stream(),
+            Self::Insert(_) | Self::Replicate(_) => None,
+        }
+    }
+
+    /// Streams which have all rows.
+    pub fn replica_streams(&self) -> &[StreamName] {
+        match self {
+            Self::Insert(insert_plan) => std::slice::from_ref(insert_plan.stream()),
+            Self::Replicate(replicate_plan) => replicate_plan.streams(),
+            Self::Route(_) => &[],
+        }
+    }
+
+    pub fn downstreams(&self) -> Vec<&StreamName> {
+        let mut downstreams = self
+            .when_streams()
+            .iter()
+            .chain(self.else_stream())
+            .chain(self.replica_streams())
+            .collect::<Vec<_>>();
+        downstreams.sort();
+        downstreams.dedup();
+        downstreams
+    }
+}
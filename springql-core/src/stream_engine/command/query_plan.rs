@@ -3,11 +3,14 @@
 mod query_plan_operation;
 
 pub use query_plan_operation::{
-    CollectOp, GroupAggregateWindowOp, JoinOp, JoinWindowOp, LowerOps, ProjectionOp, UpperOps,
+    CollectOp, GroupAggregateWindowOp, IntervalJoinOp, JoinOp, JoinWindowOp, LowerOps,
+    ProjectionOp, SelectionOp, UpperOps,
 };
 
+use serde_json::json;
+
 use crate::{
-    expr_resolver::ExprResolver,
+    expr_resolver::{ExprLabel, ExprResolver},
     pipeline::{PumpInputType, StreamName},
 };
 
@@ -33,9 +36,94 @@ impl QueryPlan {
     pub fn upstreams(&self) -> Vec<&StreamName> {
         match &self.lower_ops.join {
             JoinOp::Collect(collect) => vec![&collect.stream],
-            JoinOp::JoinWindow(JoinWindowOp { left, right, .. }) => {
+            JoinOp::JoinWindow(JoinWindowOp { left, right, .. })
+            | JoinOp::IntervalJoin(IntervalJoinOp { left, right, .. }) => {
                 vec![&left.stream, &right.stream]
             }
         }
     }
+
+    /// Operator tree for `EXPLAIN`: upper ops (projection and aggregation) over lower ops (join and selection),
+    /// down to collecting rows from upstreams.
+    ///
+    /// Expressions and operation parameters are in their debug representation.
+    /// Expression labels in parameters refer to `label`s in `projection`.
+    pub fn explain(&self) -> serde_json::Value {
+        let projection = self
+            .upper_ops
+            .projection
+            .expr_labels
+            .iter()
+            .map(|label| {
+                json!({
+                    "label": format!("{:?}", label),
+                    "expr": self.explain_expr(*label),
+                })
+            })
+            .collect::<Vec<_>>();
+        let group_aggr_window = self.upper_ops.group_aggr_window.as_ref().map(|op| {
+            json!({
+                "window": format!("{:?}", op.window_param),
+                "operation": format!("{:?}", op.op_param),
+            })
+        });
+
+        let join = match &self.lower_ops.join {
+            JoinOp::Collect(collect) => json!({ "collect": collect.stream.to_string() }),
+            JoinOp::JoinWindow(JoinWindowOp {
+                left,
+                right,
+                window_param,
+                join_param,
+            }) => json!({
+                "join_window": {
+                    "left": left.stream.to_string(),
+                    "right": right.stream.to_string(),
+                    "window": format!("{:?}", window_param),
+                    "join": format!("{:?}", join_param),
+                    "on": join_param.on_expr.map(|l| self.explain_expr(ExprLabel::Value(l))),
+                }
+            }),
+            JoinOp::IntervalJoin(IntervalJoinOp {
+                left,
+                right,
+                bound,
+                join_param,
+            }) => json!({
+                "interval_join": {
+                    "left": left.stream.to_string(),
+                    "right": right.stream.to_string(),
+                    "bound": format!("{:?}", bound),
+                    "join": format!("{:?}", join_param),
+                    "on": join_param.on_expr.map(|l| self.explain_expr(ExprLabel::Value(l))),
+                }
+            }),
+        };
+        let selection = self
+            .lower_ops
+            .selection
+            .as_ref()
+            .map(|selection| self.explain_expr(ExprLabel::Value(selection.condition)));
+
+        json!({
+            "input_type": format!("{:?}", self.input_type()),
+            "upper_ops": {
+                "projection": projection,
+                "group_aggr_window": group_aggr_window,
+            },
+            "lower_ops": {
+                "join": join,
+                "selection": selection,
+            },
+        })
+    }
+
+    fn explain_expr(&self, label: ExprLabel) -> String {
+        match label {
+            ExprLabel::Value(label) => {
+                format!("{:?}", self.expr_resolver.resolve_value_expr(label))
+            }
+            ExprLabel::Aggr(label) => format!("{:?}", self.expr_resolver.resolve_aggr_expr(label)),
+        }
+    }
 }
@@ -1,8 +1,10 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 use crate::{
-    expr_resolver::ExprLabel,
-    pipeline::{JoinParameter, StreamName, WindowOperationParameter, WindowParameter},
+    expr_resolver::{ExprLabel, ValueExprLabel},
+    pipeline::{
+        IntervalJoinBound, JoinParameter, StreamName, WindowOperationParameter, WindowParameter,
+    },
 };
 
 #[derive(Clone, PartialEq, Debug)]
@@ -19,10 +21,13 @@ impl UpperOps {
 #[derive(Clone, PartialEq, Debug)]
 pub struct LowerOps {
     pub join: JoinOp,
+
+    /// Multi stream selection (WHERE clause) applied to joined tuples.
+    pub selection: Option<SelectionOp>,
 }
 impl LowerOps {
     pub fn has_window(&self) -> bool {
-        matches!(self.join, JoinOp::JoinWindow(_))
+        matches!(self.join, JoinOp::JoinWindow(_) | JoinOp::IntervalJoin(_))
     }
 }
 
@@ -31,6 +36,12 @@ pub struct ProjectionOp {
     pub expr_labels: Vec<ExprLabel>,
 }
 
+/// Drops tuples whose condition is not TRUE (FALSE or NULL).
+#[derive(Clone, PartialEq, Debug)]
+pub struct SelectionOp {
+    pub condition: ValueExprLabel,
+}
+
 #[derive(Clone, PartialEq, Debug)]
 pub struct GroupAggregateWindowOp {
     pub window_param: WindowParameter,
@@ -47,6 +58,7 @@ pub struct CollectOp {
 pub enum JoinOp {
     Collect(CollectOp),
     JoinWindow(JoinWindowOp),
+    IntervalJoin(IntervalJoinOp),
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -57,3 +69,13 @@ pub struct JoinWindowOp {
     pub window_param: WindowParameter,
     pub join_param: JoinParameter,
 }
+
+/// Join without windows, whose ON clause bounds ROWTIME of right by ROWTIME of left.
+#[derive(Clone, PartialEq, Debug)]
+pub struct IntervalJoinOp {
+    pub left: CollectOp,
+    pub right: CollectOp,
+
+    pub bound: IntervalJoinBound,
+    pub join_param: JoinParameter,
+}
@@ -0,0 +1,22 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use crate::pipeline::{ColumnName, StreamName};
+
+/// `CREATE REPLICATOR ... FROM s TO s1, s2` inserts every row into all of the streams.
+///
+/// Each stream has its own output queues so a slow consumer of a stream does not block consumers of the others.
+#[derive(Clone, Eq, PartialEq, Debug, new)]
+pub struct ReplicatePlan {
+    column_order: Vec<ColumnName>,
+    streams: Vec<StreamName>,
+}
+
+impl ReplicatePlan {
+    pub fn column_order(&self) -> &[ColumnName] {
+        &self.column_order
+    }
+
+    pub fn streams(&self) -> &[StreamName] {
+        &self.streams
+    }
+}
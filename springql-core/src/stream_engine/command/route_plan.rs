@@ -0,0 +1,31 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use crate::pipeline::{ColumnName, StreamName};
+
+/// `CREATE ROUTER ... ROUTE WHEN cond1 TO s1 WHEN cond2 TO s2 ELSE TO s3` inserts each row into the first stream whose condition is TRUE.
+///
+/// Rows from the query plan have `column_order` values followed by a boolean value for each condition.
+#[derive(Clone, Eq, PartialEq, Debug, new)]
+pub struct RoutePlan {
+    column_order: Vec<ColumnName>,
+
+    /// i-th stream is for i-th condition.
+    when_streams: Vec<StreamName>,
+
+    /// Rows matching no condition are dropped if None.
+    else_stream: Option<StreamName>,
+}
+
+impl RoutePlan {
+    pub fn column_order(&self) -> &[ColumnName] {
+        &self.column_order
+    }
+
+    pub fn when_streams(&self) -> &[StreamName] {
+        &self.when_streams
+    }
+
+    pub fn else_stream(&self) -> Option<&StreamName> {
+        self.else_stream.as_ref()
+    }
+}
@@ -0,0 +1,22 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+/// `SHOW STREAMS`, `SHOW PUMPS`, `SHOW SOURCE READERS`, or `SHOW SINK WRITERS` lists objects in the current pipeline as rows.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ShowObjectsCommand {
+    Streams,
+    Pumps,
+    SourceReaders,
+    SinkWriters,
+}
+
+impl ShowObjectsCommand {
+    /// One of `Command::STATEMENTS`.
+    pub fn statement(&self) -> &'static str {
+        match self {
+            ShowObjectsCommand::Streams => "SHOW STREAMS",
+            ShowObjectsCommand::Pumps => "SHOW PUMPS",
+            ShowObjectsCommand::SourceReaders => "SHOW SOURCE READERS",
+            ShowObjectsCommand::SinkWriters => "SHOW SINK WRITERS",
+        }
+    }
+}
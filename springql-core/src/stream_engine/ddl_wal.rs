@@ -0,0 +1,242 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Write-ahead log of statements altering the pipeline (`recovery.ddl_wal_path` config).
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Context};
+use serde_json::json;
+
+use crate::{
+    api::{
+        error::{foreign_info::ForeignInfo, Result, SpringError},
+        SpringRecoveryConfig,
+    },
+    pipeline::PipelineVersion,
+};
+
+/// When appended statements are flushed to storage.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum FsyncPolicy {
+    Always,
+    Interval(Duration),
+    Never,
+}
+
+/// A record per line: `{"version": <pipeline version after the statement>, "statement": "<SQL>"}`.
+///
+/// A record is appended after a statement is validated against the pipeline and before the pipeline is altered by it,
+/// so that every statement in effect is in the log.
+/// A broken last line, written partially by a crash, is discarded on open.
+#[derive(Debug)]
+pub struct DdlWal {
+    path: PathBuf,
+    file: File,
+    fsync: FsyncPolicy,
+    last_synced_at: Instant,
+}
+
+impl DdlWal {
+    fn io_error(path: PathBuf) -> impl FnOnce(anyhow::Error) -> SpringError {
+        move |e| SpringError::ForeignIo {
+            source: e,
+            foreign_info: ForeignInfo::File(path),
+        }
+    }
+
+    /// Opens (or creates) the log.
+    ///
+    /// # Returns
+    ///
+    /// `None` if `recovery.ddl_wal_path` is not set. Otherwise, the log and statements in it in the appended order.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to open, read, or truncate the log.
+    /// - `SpringError::InvalidFormat` when:
+    ///   - a line other than the last one is broken.
+    pub fn open(config: &SpringRecoveryConfig) -> Result<Option<(Self, Vec<String>)>> {
+        let path = match &config.ddl_wal_path {
+            Some(path) => PathBuf::from(path),
+            None => return Ok(None),
+        };
+        let fsync = match config.ddl_wal_fsync.as_str() {
+            "always" => FsyncPolicy::Always,
+            "interval" => FsyncPolicy::Interval(Duration::from_millis(
+                config.ddl_wal_fsync_interval_msec as u64,
+            )),
+            _ => FsyncPolicy::Never,
+        };
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&path)
+            .context("failed to open DDL WAL")
+            .map_err(Self::io_error(path.clone()))?;
+
+        let (statements, valid_len) = Self::read_statements(&mut file, &path)?;
+        let len = file
+            .metadata()
+            .context("failed to get the size of DDL WAL")
+            .map_err(Self::io_error(path.clone()))?
+            .len();
+        if valid_len < len {
+            log::warn!(
+                "[DdlWal] Discarding a partially written record at the end of {:?}",
+                path
+            );
+            file.set_len(valid_len)
+                .and_then(|_| file.sync_data())
+                .context("failed to truncate DDL WAL")
+                .map_err(Self::io_error(path.clone()))?;
+        }
+
+        log::info!(
+            "[DdlWal] Opened {:?} with {} statements",
+            path,
+            statements.len()
+        );
+        Ok(Some((
+            Self {
+                path,
+                file,
+                fsync,
+                last_synced_at: Instant::now(),
+            },
+            statements,
+        )))
+    }
+
+    /// # Returns
+    ///
+    /// Statements and the length of the log up to the last valid record.
+    fn read_statements(file: &mut File, path: &Path) -> Result<(Vec<String>, u64)> {
+        file.seek(SeekFrom::Start(0))
+            .context("failed to seek DDL WAL")
+            .map_err(Self::io_error(path.to_path_buf()))?;
+
+        let mut reader = BufReader::new(file);
+        let mut statements = Vec::new();
+        let mut valid_len = 0;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let n = reader
+                .read_line(&mut line)
+                .context("failed to read DDL WAL")
+                .map_err(Self::io_error(path.to_path_buf()))?;
+            if n == 0 {
+                break;
+            }
+
+            let statement = serde_json::from_str::<serde_json::Value>(&line)
+                .ok()
+                .and_then(|record| record["statement"].as_str().map(|s| s.to_string()));
+            match statement {
+                // partially written by a crash
+                _ if !line.ends_with('\n') => break,
+                Some(statement) => {
+                    statements.push(statement);
+                    valid_len += n as u64;
+                }
+                None => {
+                    return Err(SpringError::InvalidFormat {
+                        s: line.trim_end().to_string(),
+                        source: anyhow!("broken record in DDL WAL {:?}", path),
+                    })
+                }
+            }
+        }
+        Ok((statements, valid_len))
+    }
+
+    /// Appends a statement and flushes it to storage along with `FsyncPolicy`.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to write or flush the log.
+    pub fn append(&mut self, version: PipelineVersion, statement: &str) -> Result<()> {
+        let line =
+            json!({ "version": version.to_string(), "statement": statement }).to_string() + "\n";
+
+        self.file
+            .write_all(line.as_bytes())
+            .context("failed to append to DDL WAL")
+            .map_err(Self::io_error(self.path.clone()))?;
+
+        let sync = match self.fsync {
+            FsyncPolicy::Always => true,
+            FsyncPolicy::Interval(interval) => self.last_synced_at.elapsed() >= interval,
+            FsyncPolicy::Never => false,
+        };
+        if sync {
+            self.file
+                .sync_data()
+                .context("failed to fsync DDL WAL")
+                .map_err(Self::io_error(self.path.clone()))?;
+            self.last_synced_at = Instant::now();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fx_config(path: &Path) -> SpringRecoveryConfig {
+        SpringRecoveryConfig {
+            ddl_wal_path: Some(path.to_string_lossy().to_string()),
+            ddl_wal_fsync: "always".to_string(),
+            ddl_wal_fsync_interval_msec: 1_000,
+        }
+    }
+
+    #[test]
+    fn test_ddl_wal_recovers_statements() -> Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ddl.wal");
+
+        let (mut wal, statements) = DdlWal::open(&fx_config(&path))?.unwrap();
+        assert!(statements.is_empty());
+
+        let mut version = PipelineVersion::new();
+        version.up();
+        wal.append(version, "CREATE STREAM s1 (c INTEGER NOT NULL);")?;
+        version.up();
+        wal.append(version, "CREATE STREAM s2 (\n  c TEXT NOT NULL\n);")?;
+        drop(wal);
+
+        // a crash while appending
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(br#"{"version":"4","statem"#).unwrap();
+        drop(file);
+
+        let (mut wal, statements) = DdlWal::open(&fx_config(&path))?.unwrap();
+        assert_eq!(
+            statements,
+            vec![
+                "CREATE STREAM s1 (c INTEGER NOT NULL);".to_string(),
+                "CREATE STREAM s2 (\n  c TEXT NOT NULL\n);".to_string(),
+            ]
+        );
+
+        version.up();
+        wal.append(version, "CREATE STREAM s3 (c INTEGER NOT NULL);")?;
+        drop(wal);
+
+        let (_, statements) = DdlWal::open(&fx_config(&path))?.unwrap();
+        assert_eq!(statements.len(), 3);
+
+        Ok(())
+    }
+}
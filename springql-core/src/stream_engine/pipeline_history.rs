@@ -0,0 +1,84 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::collections::VecDeque;
+
+use crate::{
+    pipeline::PipelineVersion,
+    stream_engine::time::{SpringTimestamp, SystemTimestamp},
+};
+
+/// Keeps long-lived processes from growing the history unboundedly.
+const MAX_ENTRIES: usize = 1_000;
+
+#[derive(Clone, PartialEq, Debug)]
+struct PipelineHistoryEntry {
+    applied_at: SpringTimestamp,
+    version: PipelineVersion,
+    /// Whitespaces are collapsed so that an entry fits in a line.
+    statement: String,
+}
+
+/// Statements which altered the pipeline, for `SHOW HISTORY`.
+///
+/// Only the latest `MAX_ENTRIES` statements are kept.
+#[derive(Debug, Default)]
+pub struct PipelineHistory {
+    entries: VecDeque<PipelineHistoryEntry>,
+}
+
+impl PipelineHistory {
+    /// `version` is the pipeline version right after `statement` is applied.
+    pub fn record(&mut self, version: PipelineVersion, statement: &str) {
+        if self.entries.len() == MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(PipelineHistoryEntry {
+            applied_at: SystemTimestamp::now(),
+            version,
+            statement: statement.split_whitespace().collect::<Vec<_>>().join(" "),
+        });
+    }
+
+    /// A line per statement in the applied order: `<applied_at (UTC)>\tversion <version>\t<statement>`
+    pub fn show(&self) -> String {
+        self.entries
+            .iter()
+            .map(|entry| {
+                format!(
+                    "{}\tversion {}\t{}",
+                    entry.applied_at.to_string(),
+                    entry.version,
+                    entry.statement
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pipeline_history() {
+        let mut history = PipelineHistory::default();
+        assert_eq!(history.show(), "");
+
+        let mut version = PipelineVersion::new();
+        for i in 0..MAX_ENTRIES + 1 {
+            version.up();
+            history.record(version, &format!("CREATE STREAM s{}\n  (c INTEGER);", i));
+        }
+
+        let show = history.show();
+        let lines = show.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), MAX_ENTRIES);
+        assert!(lines[0].ends_with("\tversion 3\tCREATE STREAM s1 (c INTEGER);"));
+        assert!(lines[MAX_ENTRIES - 1].ends_with(&format!(
+            "\tversion {}\tCREATE STREAM s{} (c INTEGER);",
+            MAX_ENTRIES + 2,
+            MAX_ENTRIES
+        )));
+    }
+}
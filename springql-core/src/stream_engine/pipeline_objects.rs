@@ -0,0 +1,107 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Rows listing objects in a pipeline, for `SHOW STREAMS`, `SHOW PUMPS`, `SHOW SOURCE READERS`, and `SHOW SINK WRITERS`.
+//!
+//! Columns are:
+//!
+//! - `SHOW STREAMS`: `name`, `columns` (comma-separated column names)
+//! - `SHOW PUMPS`: `name`, `upstreams`, `downstreams` (comma-separated stream names), `stopped`
+//! - `SHOW SOURCE READERS` and `SHOW SINK WRITERS`: `name`, `type`, `stream`
+//!
+//! Rows are sorted by `name`.
+
+use crate::{
+    pipeline::{ColumnName, Pipeline, StreamName},
+    stream_engine::{
+        autonomous_executor::SchemalessRow, command::ShowObjectsCommand, NnSqlValue, SqlValue,
+    },
+};
+
+pub fn object_rows(pipeline: &Pipeline, command: ShowObjectsCommand) -> Vec<SchemalessRow> {
+    let mut rows = match command {
+        ShowObjectsCommand::Streams => pipeline
+            .all_streams()
+            .into_iter()
+            .map(|stream| {
+                let columns = stream
+                    .shape()
+                    .column_names()
+                    .iter()
+                    .map(|column| column.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                (stream.name().to_string(), vec![("columns", text(columns))])
+            })
+            .collect::<Vec<_>>(),
+        ShowObjectsCommand::Pumps => pipeline
+            .all_pumps()
+            .into_iter()
+            .map(|pump| {
+                let stopped = pipeline.as_graph().is_pump_stopped(pump.name());
+                (
+                    pump.name().to_string(),
+                    vec![
+                        ("upstreams", text(join_streams(pump.upstreams()))),
+                        ("downstreams", text(join_streams(pump.downstreams()))),
+                        ("stopped", SqlValue::NotNull(NnSqlValue::Boolean(stopped))),
+                    ],
+                )
+            })
+            .collect(),
+        ShowObjectsCommand::SourceReaders => pipeline
+            .all_sources()
+            .into_iter()
+            .map(|source_reader| {
+                (
+                    source_reader.name().to_string(),
+                    vec![
+                        ("type", text(source_reader.source_reader_type().to_string())),
+                        (
+                            "stream",
+                            text(source_reader.dest_source_stream().to_string()),
+                        ),
+                    ],
+                )
+            })
+            .collect(),
+        ShowObjectsCommand::SinkWriters => pipeline
+            .all_sinks()
+            .into_iter()
+            .map(|sink_writer| {
+                (
+                    sink_writer.name().to_string(),
+                    vec![
+                        ("type", text(sink_writer.sink_writer_type().to_string())),
+                        ("stream", text(sink_writer.sink_upstream().to_string())),
+                    ],
+                )
+            })
+            .collect(),
+    };
+    rows.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    rows.into_iter()
+        .map(|(name, columns)| {
+            let mut row = SchemalessRow::with_capacity(1 + columns.len());
+            row.insert(ColumnName::new("name".to_string()), text(name))
+                .expect("column names are unique");
+            for (column, value) in columns {
+                row.insert(ColumnName::new(column.to_string()), value)
+                    .expect("column names are unique");
+            }
+            row
+        })
+        .collect()
+}
+
+fn text(s: String) -> SqlValue {
+    SqlValue::NotNull(NnSqlValue::Text(s))
+}
+
+fn join_streams(streams: Vec<&StreamName>) -> String {
+    streams
+        .iter()
+        .map(|stream| stream.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,102 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::error::{Result, SpringError},
+    stream_engine::autonomous_executor::ExecutorSnapshot,
+};
+
+/// Incremented on incompatible changes of the archive.
+const FORMAT_VERSION: u32 = 1;
+
+/// Pipeline definition (statements altered the pipeline) and state of queues and windows.
+///
+/// Encoded into a JSON document to be restored in another process, possibly on another device.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct PipelineSnapshot {
+    format_version: u32,
+    statements: Vec<String>,
+    state: ExecutorSnapshot,
+}
+
+impl PipelineSnapshot {
+    pub fn new(statements: Vec<String>, state: ExecutorSnapshot) -> Self {
+        Self {
+            format_version: FORMAT_VERSION,
+            statements,
+            state,
+        }
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidFormat` when:
+    ///   - failed to serialize.
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec(self).map_err(|e| SpringError::InvalidFormat {
+            s: "pipeline snapshot".to_string(),
+            source: e.into(),
+        })
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidFormat` when:
+    ///   - `archive` is not an encoded snapshot.
+    ///   - `archive` is encoded in an unsupported format version.
+    pub fn decode(archive: &[u8]) -> Result<Self> {
+        let snapshot: Self =
+            serde_json::from_slice(archive).map_err(|e| SpringError::InvalidFormat {
+                s: String::from_utf8_lossy(&archive[..archive.len().min(64)]).to_string(),
+                source: e.into(),
+            })?;
+        if snapshot.format_version != FORMAT_VERSION {
+            return Err(SpringError::InvalidFormat {
+                s: format!("format_version {}", snapshot.format_version),
+                source: anyhow!(
+                    "only format_version {} is supported for pipeline snapshots",
+                    FORMAT_VERSION
+                ),
+            });
+        }
+        Ok(snapshot)
+    }
+
+    pub fn statements(&self) -> &[String] {
+        &self.statements
+    }
+
+    pub fn into_state(self) -> ExecutorSnapshot {
+        self.state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pipeline_snapshot_encode_decode() {
+        let snapshot = PipelineSnapshot::new(
+            vec!["CREATE STREAM s1 (c INTEGER NOT NULL);".to_string()],
+            ExecutorSnapshot::default(),
+        );
+        let archive = snapshot.encode().unwrap();
+        assert_eq!(PipelineSnapshot::decode(&archive).unwrap(), snapshot);
+
+        assert!(matches!(
+            PipelineSnapshot::decode(b"{}"),
+            Err(SpringError::InvalidFormat { .. })
+        ));
+
+        let archive = String::from_utf8(archive)
+            .unwrap()
+            .replace(r#""format_version":1"#, r#""format_version":999"#);
+        assert!(matches!(
+            PipelineSnapshot::decode(archive.as_bytes()),
+            Err(SpringError::InvalidFormat { .. })
+        ));
+    }
+}
@@ -0,0 +1,347 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{cmp::max, collections::HashMap, str::FromStr};
+
+use anyhow::anyhow;
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{ColumnName, StreamName},
+    sql_processor::PestParserImpl,
+    stream_engine::{
+        autonomous_executor::{SchemalessRow, SourceRow},
+        time::SpringTimestamp,
+        NnSqlValue, SqlValue,
+    },
+};
+
+/// Column type inferred from sampled values.
+///
+/// Numeric variants are ordered from the narrowest to the widest.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+enum InferredType {
+    Integer,
+    BigInt,
+    Float,
+
+    Boolean,
+    Timestamp,
+    Text,
+    Blob,
+}
+
+impl InferredType {
+    fn from_nn_sql_value(value: &NnSqlValue) -> Self {
+        match value {
+            NnSqlValue::SmallInt(_) | NnSqlValue::Integer(_) => Self::Integer,
+            NnSqlValue::BigInt(i) => Self::from_i64(*i),
+            NnSqlValue::UnsignedInteger(u) => Self::from_i64(*u as i64),
+            NnSqlValue::UnsignedBigInt(_) => Self::BigInt,
+            NnSqlValue::Float(_) => Self::Float,
+            NnSqlValue::Text(s) => {
+                if SpringTimestamp::from_str(s).is_ok() {
+                    Self::Timestamp
+                } else {
+                    Self::Text
+                }
+            }
+            NnSqlValue::Blob(_) => Self::Blob,
+            NnSqlValue::Boolean(_) => Self::Boolean,
+            NnSqlValue::Timestamp(_) => Self::Timestamp,
+            NnSqlValue::Duration(_) => Self::BigInt,
+        }
+    }
+
+    /// From a number as it is in JSON, before it is converted into FLOAT (and loses precision).
+    fn from_json_number(n: &serde_json::Number) -> Self {
+        if let Some(i) = n.as_i64() {
+            Self::from_i64(i)
+        } else if n.is_u64() {
+            Self::BigInt
+        } else {
+            Self::Float
+        }
+    }
+
+    fn from_i64(i: i64) -> Self {
+        if i32::try_from(i).is_ok() {
+            Self::Integer
+        } else {
+            Self::BigInt
+        }
+    }
+
+    fn is_numeric(&self) -> bool {
+        matches!(self, Self::Integer | Self::BigInt | Self::Float)
+    }
+
+    /// # Returns
+    ///
+    /// None if `self` and `other` cannot share a column.
+    fn merge(self, other: Self) -> Option<Self> {
+        if self == other {
+            Some(self)
+        } else if self.is_numeric() && other.is_numeric() {
+            Some(max(self, other))
+        } else {
+            match (self, other) {
+                (Self::Timestamp, Self::Text) | (Self::Text, Self::Timestamp) => Some(Self::Text),
+                _ => None,
+            }
+        }
+    }
+
+    fn as_ddl(&self) -> &'static str {
+        match self {
+            Self::Integer => "INTEGER",
+            Self::BigInt => "BIGINT",
+            Self::Float => "FLOAT",
+            Self::Boolean => "BOOLEAN",
+            Self::Timestamp => "TIMESTAMP",
+            Self::Text => "TEXT",
+            Self::Blob => "BLOB",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct InferredColumn {
+    name: ColumnName,
+
+    /// None if only NULLs are sampled so far.
+    typ: Option<InferredType>,
+    nullable: bool,
+}
+
+/// Infers a stream shape from sampled rows and suggests a `CREATE SOURCE STREAM` statement.
+///
+/// - Columns are ordered as they first appear in samples.
+/// - A column is `NOT NULL` only when it has non-NULL value in all the samples.
+/// - The first `TIMESTAMP NOT NULL` column is suggested as `ROWTIME`.
+#[derive(Debug)]
+pub struct SchemaInferrer {
+    stream_name: StreamName,
+    columns: Vec<InferredColumn>,
+    n_rows: usize,
+}
+
+impl SchemaInferrer {
+    pub fn new(stream_name: StreamName) -> Self {
+        Self {
+            stream_name,
+            columns: vec![],
+            n_rows: 0,
+        }
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidFormat` when:
+    ///   - `row` cannot be converted into columns.
+    ///   - a column has values in incompatible types among samples.
+    pub fn feed(&mut self, row: SourceRow) -> Result<()> {
+        let json_numbers = match &row {
+            SourceRow::Json(json_row) => json_row.numbers(),
+            _ => HashMap::new(),
+        };
+        let row = SchemalessRow::try_from(row)?;
+
+        let mut seen = vec![false; self.columns.len()];
+
+        for (column_name, sql_value) in row {
+            let idx = match self.columns.iter().position(|c| c.name == column_name) {
+                Some(idx) => idx,
+                None => {
+                    self.columns.push(InferredColumn {
+                        name: column_name,
+                        typ: None,
+                        // absent in the previous samples
+                        nullable: self.n_rows > 0,
+                    });
+                    seen.push(false);
+                    self.columns.len() - 1
+                }
+            };
+            seen[idx] = true;
+
+            let column = &mut self.columns[idx];
+            match sql_value {
+                SqlValue::Null => column.nullable = true,
+                SqlValue::NotNull(nn_sql_value) => {
+                    let typ = match json_numbers.get(column.name.as_ref()) {
+                        Some(n) => InferredType::from_json_number(n),
+                        None => InferredType::from_nn_sql_value(&nn_sql_value),
+                    };
+                    column.typ =
+                        match column.typ {
+                            None => Some(typ),
+                            Some(current) => Some(current.merge(typ).ok_or_else(|| {
+                                SpringError::InvalidFormat {
+                                    s: nn_sql_value.to_string(),
+                                    source: anyhow!(
+                                        r#"column "{}" has both {} and {} values in samples"#,
+                                        column.name,
+                                        current.as_ddl(),
+                                        typ.as_ddl()
+                                    ),
+                                }
+                            })?),
+                        };
+                }
+            }
+        }
+
+        for (column, seen) in self.columns.iter_mut().zip(seen) {
+            if !seen {
+                column.nullable = true;
+            }
+        }
+
+        self.n_rows += 1;
+        Ok(())
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - no column is sampled.
+    pub fn suggest_ddl(&self) -> Result<String> {
+        if self.columns.is_empty() {
+            return Err(SpringError::Sql(anyhow!(
+                "cannot infer schema: no column found in {} sampled rows",
+                self.n_rows
+            )));
+        }
+
+        let rowtime_idx = self
+            .columns
+            .iter()
+            .position(|c| c.typ == Some(InferredType::Timestamp) && !c.nullable);
+
+        let column_definitions = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(idx, column)| {
+                // only-NULL column does not have any hint
+                let typ = column.typ.unwrap_or(InferredType::Text);
+
+                let mut def = format!(
+                    "  {} {}",
+                    PestParserImpl::to_identifier(column.name.as_ref()),
+                    typ.as_ddl()
+                );
+                if !column.nullable {
+                    def.push_str(" NOT NULL");
+                }
+                if rowtime_idx == Some(idx) {
+                    def.push_str(" ROWTIME");
+                }
+                def
+            })
+            .collect::<Vec<_>>();
+
+        Ok(format!(
+            "CREATE SOURCE STREAM {} (\n{}\n);",
+            PestParserImpl::to_identifier(self.stream_name.as_ref()),
+            column_definitions.join(",\n")
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn row(json: serde_json::Value) -> SourceRow {
+        SourceRow::from_json(&json.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_suggest_ddl() {
+        let mut inferrer = SchemaInferrer::new(StreamName::new("source_trade".to_string()));
+
+        inferrer
+            .feed(row(json!({
+                "ts": "2020-01-01 00:00:00.000000000",
+                "ticker": "ORCL",
+                "amount": 10,
+                "price": 1,
+            })))
+            .unwrap();
+        inferrer
+            .feed(row(json!({
+                "ts": "2020-01-01 00:00:01.000000000",
+                "ticker": "GOOGL",
+                "amount": 3000000000_i64,
+                "price": 1.5,
+                "comment": "new column",
+            })))
+            .unwrap();
+        inferrer
+            .feed(row(json!({
+                "ts": "2020-01-01 00:00:02.000000000",
+                "ticker": null,
+                "amount": 20,
+                "price": 2,
+            })))
+            .unwrap();
+
+        assert_eq!(
+            inferrer.suggest_ddl().unwrap(),
+            "CREATE SOURCE STREAM source_trade (
+  amount BIGINT NOT NULL,
+  price FLOAT NOT NULL,
+  ticker TEXT,
+  ts TIMESTAMP NOT NULL ROWTIME,
+  comment TEXT
+);"
+        );
+    }
+
+    #[test]
+    fn test_json_numbers() {
+        let mut inferrer = SchemaInferrer::new(StreamName::new("s".to_string()));
+
+        inferrer
+            .feed(row(json!({
+                "c_int": 16777217, // 2^24 + 1, not exact in f32
+                "c_bigint": i64::MAX,
+                "c_ubigint": u64::MAX,
+                "c_float": 1.0,
+                "c_huge_float": 9.3e18,
+            })))
+            .unwrap();
+
+        assert_eq!(
+            inferrer.suggest_ddl().unwrap(),
+            "CREATE SOURCE STREAM s (
+  c_bigint BIGINT NOT NULL,
+  c_float FLOAT NOT NULL,
+  c_huge_float FLOAT NOT NULL,
+  c_int INTEGER NOT NULL,
+  c_ubigint BIGINT NOT NULL
+);"
+        );
+    }
+
+    #[test]
+    fn test_incompatible_types() {
+        let mut inferrer = SchemaInferrer::new(StreamName::new("s".to_string()));
+
+        inferrer.feed(row(json!({"c": true}))).unwrap();
+        assert!(matches!(
+            inferrer.feed(row(json!({"c": 1}))).unwrap_err(),
+            SpringError::InvalidFormat { .. }
+        ));
+    }
+
+    #[test]
+    fn test_no_column() {
+        let inferrer = SchemaInferrer::new(StreamName::new("s".to_string()));
+        assert!(inferrer.suggest_ddl().is_err());
+    }
+}
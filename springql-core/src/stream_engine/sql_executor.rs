@@ -5,7 +5,8 @@ use std::sync::Arc;
 use crate::{
     api::error::Result,
     pipeline::{
-        Pipeline, PipelineVersion, PumpModel, SinkWriterModel, SourceReaderModel, StreamModel,
+        Pipeline, PipelineVersion, PumpModel, PumpName, SinkWriterModel, SinkWriterName,
+        SourceReaderModel, SourceReaderName, StreamModel, StreamName,
     },
     stream_engine::command::AlterPipelineCommand,
 };
@@ -31,8 +32,17 @@ impl SqlExecutor {
         &self.pipeline
     }
 
-    pub fn alter_pipeline(&mut self, command: AlterPipelineCommand) -> Result<Pipeline> {
+    /// `before_commit` is called with the new pipeline before it replaces the current one. Err from it aborts the alteration.
+    pub fn alter_pipeline<F>(
+        &mut self,
+        command: AlterPipelineCommand,
+        before_commit: F,
+    ) -> Result<Pipeline>
+    where
+        F: FnOnce(&Pipeline) -> Result<()>,
+    {
         let new_pipeline = Self::new_pipeline(self.pipeline.clone(), command)?;
+        before_commit(&new_pipeline)?;
         self.pipeline = new_pipeline;
         Ok(self.pipeline.clone())
     }
@@ -53,6 +63,26 @@ impl SqlExecutor {
                 Self::create_sink_writer(pipeline, sink_writer)
             }
             AlterPipelineCommand::CreatePump(pump) => Self::create_pump(pipeline, *pump),
+            AlterPipelineCommand::SwitchPump { from_pump, to_pump } => {
+                Self::switch_pump(pipeline, &from_pump, &to_pump)
+            }
+            AlterPipelineCommand::DropPump(pump_name) => Self::drop_pump(pipeline, &pump_name),
+            AlterPipelineCommand::DropStream(stream_name) => {
+                Self::drop_stream(pipeline, &stream_name)
+            }
+            AlterPipelineCommand::DropSourceReader(source_reader_name) => {
+                Self::drop_source_reader(pipeline, &source_reader_name)
+            }
+            AlterPipelineCommand::DropSinkWriter(sink_writer_name) => {
+                Self::drop_sink_writer(pipeline, &sink_writer_name)
+            }
+            AlterPipelineCommand::AlterPumpState { pump_name, stopped } => {
+                Self::alter_pump_state(pipeline, &pump_name, stopped)
+            }
+            AlterPipelineCommand::AlterStreamTrace {
+                stream_name,
+                trace_sample_rate,
+            } => Self::alter_stream_trace(pipeline, stream_name, trace_sample_rate),
         }
     }
 
@@ -92,4 +122,58 @@ impl SqlExecutor {
         pipeline.add_pump(pump)?;
         Ok(pipeline)
     }
+
+    fn switch_pump(
+        mut pipeline: Pipeline,
+        from_pump: &PumpName,
+        to_pump: &PumpName,
+    ) -> Result<Pipeline> {
+        let _ = pipeline.get_pump(to_pump)?;
+        pipeline.remove_pump(from_pump)?;
+        Ok(pipeline)
+    }
+
+    fn drop_pump(mut pipeline: Pipeline, pump_name: &PumpName) -> Result<Pipeline> {
+        pipeline.remove_pump(pump_name)?;
+        Ok(pipeline)
+    }
+
+    fn drop_stream(mut pipeline: Pipeline, stream_name: &StreamName) -> Result<Pipeline> {
+        pipeline.remove_stream(stream_name)?;
+        Ok(pipeline)
+    }
+
+    fn drop_source_reader(
+        mut pipeline: Pipeline,
+        source_reader_name: &SourceReaderName,
+    ) -> Result<Pipeline> {
+        pipeline.remove_source_reader(source_reader_name)?;
+        Ok(pipeline)
+    }
+
+    fn drop_sink_writer(
+        mut pipeline: Pipeline,
+        sink_writer_name: &SinkWriterName,
+    ) -> Result<Pipeline> {
+        pipeline.remove_sink_writer(sink_writer_name)?;
+        Ok(pipeline)
+    }
+
+    fn alter_pump_state(
+        mut pipeline: Pipeline,
+        pump_name: &PumpName,
+        stopped: bool,
+    ) -> Result<Pipeline> {
+        pipeline.set_pump_stopped(pump_name, stopped)?;
+        Ok(pipeline)
+    }
+
+    fn alter_stream_trace(
+        mut pipeline: Pipeline,
+        stream_name: StreamName,
+        trace_sample_rate: Option<f32>,
+    ) -> Result<Pipeline> {
+        pipeline.set_stream_trace(stream_name, trace_sample_rate)?;
+        Ok(pipeline)
+    }
 }
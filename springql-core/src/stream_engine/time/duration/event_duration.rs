@@ -2,20 +2,21 @@
 
 use std::{
     fmt::Display,
-    mem::size_of,
     ops::{Add, Div, Mul, Sub},
     time::Duration,
 };
 
+use serde::{Deserialize, Serialize};
+
 use crate::{mem_size::MemSize, stream_engine::time::duration::SpringDuration};
 
 /// Event-time duration.
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
 pub struct SpringEventDuration(Duration);
 
 impl MemSize for SpringEventDuration {
     fn mem_size(&self) -> usize {
-        size_of::<u64>() + size_of::<u32>()
+        0
     }
 }
 
@@ -15,7 +15,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     api::error::{Result, SpringError},
-    mem_size::{chrono_naive_date_time_overhead_size, MemSize},
+    mem_size::MemSize,
     time::{DateTime, Duration, NaiveDateTime, MIN_DATETIME},
 };
 
@@ -28,7 +28,7 @@ pub struct SpringTimestamp(NaiveDateTime);
 
 impl MemSize for SpringTimestamp {
     fn mem_size(&self) -> usize {
-        chrono_naive_date_time_overhead_size()
+        0
     }
 }
 
@@ -61,6 +61,23 @@ impl SpringTimestamp {
         }
     }
 
+    /// Nanoseconds since the UNIX epoch.
+    pub fn unix_timestamp_nanos(&self) -> i128 {
+        self.0.timestamp_nanos()
+    }
+
+    /// Inverse of `unix_timestamp_nanos()`.
+    pub fn from_unix_timestamp_nanos(nanos: i128) -> Result<Self> {
+        let secs = nanos.div_euclid(1_000_000_000);
+        let subsec_nanos = nanos.rem_euclid(1_000_000_000);
+        let secs = i64::try_from(secs)
+            .context("timestamp out of range")
+            .map_err(SpringError::Sql)?;
+        let ndt =
+            NaiveDateTime::from_timestamp(secs, subsec_nanos as u32).map_err(SpringError::Time)?;
+        Ok(SpringTimestamp(ndt))
+    }
+
     fn try_parse_original(s: &str) -> Result<Self> {
         let ndt = NaiveDateTime::parse_from_str(s)
             .with_context(|| format!("failed to parse timestamp: {}", s))
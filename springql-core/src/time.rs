@@ -1,7 +1,7 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 use serde::{Deserialize, Serialize};
-use std::ops::{Add, Sub};
+use std::ops::{Add, Neg, Sub};
 use time::{macros::format_description, UtcOffset};
 
 #[derive(Debug, thiserror::Error)]
@@ -18,7 +18,7 @@ pub enum TimeError {
     ComponentRange(#[from] time::error::ComponentRange),
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Duration(time::Duration);
 
 impl Duration {
@@ -71,6 +71,14 @@ impl Add for Duration {
     }
 }
 
+impl Neg for Duration {
+    type Output = Duration;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
 pub struct DateTime(time::OffsetDateTime);
 
 impl DateTime {
@@ -1,8 +1,8 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use std::cmp::Ordering;
+use core::cmp::Ordering;
 
-/// Comparison result of two `SqlValue`s.
+/// Comparison result of two SQL values.
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub enum SqlCompareResult {
     /// v1 = v2
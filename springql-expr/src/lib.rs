@@ -0,0 +1,23 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! SQL semantics of SpringQL shared by `springql-core`, for `no_std` + `alloc` targets.
+//!
+//! Firmware-side preprocessors can compare values, match `LIKE` patterns and combine conditions in three-valued logic
+//! exactly as SpringQL instances they feed, since `springql-core` evaluates expressions with this crate:
+//!
+//! - [SqlCompareResult](crate::SqlCompareResult): result of comparing two values, which is NULL if either is NULL.
+//! - [like()](crate::like): `LIKE` pattern matching.
+//! - [logic](crate::logic): `AND`, `IN` and `BETWEEN` in three-valued logic.
+//!
+//! Value types and the evaluator of operators and scalar functions are in `springql-core`, which requires `std`.
+
+#![no_std]
+
+extern crate alloc;
+
+mod compare;
+mod like;
+pub mod logic;
+
+pub use compare::SqlCompareResult;
+pub use like::like;
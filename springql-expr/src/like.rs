@@ -0,0 +1,58 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use alloc::vec::Vec;
+
+/// `LIKE` matching: `%` matches any sequence of characters (including empty) and `_` matches any single character.
+pub fn like(s: &str, pattern: &str) -> bool {
+    let s = s.chars().collect::<Vec<_>>();
+    let pattern = pattern.chars().collect::<Vec<_>>();
+
+    let (mut si, mut pi) = (0, 0);
+    // (pattern index after the last `%`, string index the `%` currently matches up to)
+    let mut last_percent: Option<(usize, usize)> = None;
+
+    while si < s.len() {
+        match pattern.get(pi) {
+            Some('%') => {
+                last_percent = Some((pi + 1, si));
+                pi += 1;
+            }
+            Some(c) if *c == '_' || *c == s[si] => {
+                si += 1;
+                pi += 1;
+            }
+            _ => match last_percent {
+                Some((after_percent, matched_to)) => {
+                    // let the last `%` match one more character
+                    last_percent = Some((after_percent, matched_to + 1));
+                    pi = after_percent;
+                    si = matched_to + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[pi..].iter().all(|c| *c == '%')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_like() {
+        assert!(like("WBA12345", "WBA%"));
+        assert!(like("WBA12345", "%345"));
+        assert!(like("WBA12345", "W_A%4_"));
+        assert!(like("WBA12345", "%%"));
+        assert!(like("", "%"));
+        assert!(like("E-0x1F", "E-%1F"));
+        assert!(like("aXbXc", "a%b%c"));
+        assert!(like("ababc", "%abc"));
+
+        assert!(!like("WBA12345", "WBS%"));
+        assert!(!like("WBA12345", "WBA"));
+        assert!(!like("WBA12345", "_BA1234"));
+        assert!(!like("", "_"));
+    }
+}
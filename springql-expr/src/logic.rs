@@ -0,0 +1,113 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Three-valued logic. `None` is NULL (UNKNOWN).
+
+use crate::SqlCompareResult;
+
+/// `left AND right`: FALSE on either side decides the result even with NULL.
+pub fn and(left: Option<bool>, right: Option<bool>) -> Option<bool> {
+    match (left, right) {
+        (Some(false), _) | (_, Some(false)) => Some(false),
+        (Some(true), Some(true)) => Some(true),
+        _ => None,
+    }
+}
+
+/// `target IN (list)` from comparison results of `target` to each of the list.
+///
+/// TRUE if target equals to any of the list. Otherwise, NULL if target or any of the list is NULL, FALSE if not.
+/// Results after the first `Eq` are not consumed, so that the rest of the list need not be evaluated.
+///
+/// # Failure
+///
+/// - Raises the first Err in `results` as-is.
+pub fn in_list<E>(
+    results: impl IntoIterator<Item = Result<SqlCompareResult, E>>,
+) -> Result<Option<bool>, E> {
+    let mut found = Some(false);
+    for result in results {
+        match result? {
+            SqlCompareResult::Eq => return Ok(Some(true)),
+            SqlCompareResult::Null => found = None,
+            SqlCompareResult::LessThan | SqlCompareResult::GreaterThan => {}
+        }
+    }
+    Ok(found)
+}
+
+/// `low <= target AND target <= high` from comparison results of `target` to `low` and to `high`.
+pub fn between(target_to_low: SqlCompareResult, target_to_high: SqlCompareResult) -> Option<bool> {
+    let ge_low = match target_to_low {
+        SqlCompareResult::Null => None,
+        SqlCompareResult::LessThan => Some(false),
+        SqlCompareResult::Eq | SqlCompareResult::GreaterThan => Some(true),
+    };
+    let le_high = match target_to_high {
+        SqlCompareResult::Null => None,
+        SqlCompareResult::GreaterThan => Some(false),
+        SqlCompareResult::Eq | SqlCompareResult::LessThan => Some(true),
+    };
+    and(ge_low, le_high)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_and() {
+        assert_eq!(and(Some(true), Some(true)), Some(true));
+        assert_eq!(and(Some(true), Some(false)), Some(false));
+        assert_eq!(and(None, Some(false)), Some(false));
+        assert_eq!(and(Some(true), None), None);
+        assert_eq!(and(None, None), None);
+    }
+
+    #[test]
+    fn test_in_list() {
+        let in_list = |results: &[SqlCompareResult]| {
+            super::in_list(results.iter().cloned().map(Ok::<_, ()>)).unwrap()
+        };
+
+        assert_eq!(
+            in_list(&[SqlCompareResult::LessThan, SqlCompareResult::Eq]),
+            Some(true)
+        );
+        assert_eq!(
+            in_list(&[SqlCompareResult::Null, SqlCompareResult::Eq]),
+            Some(true)
+        );
+        assert_eq!(
+            in_list(&[SqlCompareResult::Null, SqlCompareResult::GreaterThan]),
+            None
+        );
+        assert_eq!(in_list(&[SqlCompareResult::GreaterThan]), Some(false));
+        assert_eq!(in_list(&[]), Some(false));
+
+        // stops at the first match
+        assert_eq!(
+            super::in_list([Ok(SqlCompareResult::Eq), Err(())]),
+            Ok(Some(true))
+        );
+    }
+
+    #[test]
+    fn test_between() {
+        assert_eq!(
+            between(SqlCompareResult::Eq, SqlCompareResult::LessThan),
+            Some(true)
+        );
+        assert_eq!(
+            between(SqlCompareResult::GreaterThan, SqlCompareResult::GreaterThan),
+            Some(false)
+        );
+        assert_eq!(
+            between(SqlCompareResult::LessThan, SqlCompareResult::Null),
+            Some(false)
+        );
+        assert_eq!(
+            between(SqlCompareResult::GreaterThan, SqlCompareResult::Null),
+            None
+        );
+    }
+}
@@ -242,3 +242,178 @@ fn test_feat_aggregation_with_2_group_bys() -> Result<()> {
 
     Ok(())
 }
+
+fn run_aggregation_without_group_by(aggregation: &str, result_type: &str) -> Vec<i64> {
+    let source_input = gen_source_input();
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,    
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK STREAM sink_aggregated (
+          aggregated {result_type} NOT NULL
+        );
+        ",
+            result_type = result_type
+        ),
+        format!(
+            "
+        CREATE PUMP aggregate_all AS
+        INSERT INTO sink_aggregated (aggregated)
+        SELECT STREAM
+            {aggregation}(source_trade.amount) AS aggregated
+        FROM source_trade
+        FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        ",
+            aggregation = aggregation
+        ),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_trade FOR sink_aggregated
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_trade FOR source_trade
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ];
+
+    run_and_drain(
+        &ddls,
+        ForeignSourceInput::new_fifo_batch(source_input),
+        test_source,
+        &test_sink,
+    )
+    .into_iter()
+    .map(|r| r["aggregated"].as_i64().unwrap())
+    .collect()
+}
+
+#[test]
+fn test_feat_aggregation_count_sum_min_max() {
+    setup_test_logger();
+
+    assert_eq!(
+        run_aggregation_without_group_by("COUNT", "BIGINT"),
+        vec![2, 1]
+    );
+    assert_eq!(
+        run_aggregation_without_group_by("SUM", "BIGINT"),
+        vec![40, 50]
+    );
+    assert_eq!(
+        run_aggregation_without_group_by("MIN", "INTEGER"),
+        vec![10, 50]
+    );
+    assert_eq!(
+        run_aggregation_without_group_by("MAX", "INTEGER"),
+        vec![30, 50]
+    );
+}
+
+#[test]
+fn test_feat_aggregation_multiple_aggregates() {
+    setup_test_logger();
+
+    let source_input = gen_source_input();
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,    
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_aggregated (
+          avg_amount FLOAT NOT NULL,
+          sum_amount BIGINT NOT NULL,
+          n_trades BIGINT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP aggregate_all AS
+        INSERT INTO sink_aggregated (avg_amount, sum_amount, n_trades)
+        SELECT STREAM
+            AVG(source_trade.amount) AS avg_amount,
+            SUM(source_trade.amount) AS sum_amount,
+            COUNT(*) AS n_trades
+        FROM source_trade
+        FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_trade FOR sink_aggregated
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_trade FOR source_trade
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ];
+
+    let sink_received = run_and_drain(
+        &ddls,
+        ForeignSourceInput::new_fifo_batch(source_input),
+        test_source,
+        &test_sink,
+    )
+    .into_iter()
+    .map(|r| {
+        (
+            r["avg_amount"].as_f64().unwrap().round() as i64,
+            r["sum_amount"].as_i64().unwrap(),
+            r["n_trades"].as_i64().unwrap(),
+        )
+    })
+    .collect::<Vec<_>>();
+
+    assert_eq!(sink_received, vec![(20, 40, 2), (50, 50, 1)]);
+}
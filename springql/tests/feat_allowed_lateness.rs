@@ -0,0 +1,98 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use springql::{SpringConfig, SpringPipeline, SpringSourceRow};
+
+use crate::test_support::*;
+
+/// `emit_policy` is appended to the window clause.
+fn ddls(emit_policy: &str, source_queue: &str, sink_queue: &str) -> Vec<String> {
+    vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          avg_n FLOAT NOT NULL
+        );
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE PUMP pump_1 AS
+          INSERT INTO sink_1 (avg_n)
+          SELECT STREAM AVG(source_1.n) AS avg_n FROM source_1
+          FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0) ALLOWED LATENESS DURATION_SECS(5) {};
+        ",
+            emit_policy
+        ),
+        format!(
+            "
+        CREATE SINK WRITER q_sink_1 FOR sink_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME '{}'
+        );
+        ",
+            sink_queue
+        ),
+        format!(
+            "
+        CREATE SOURCE READER q_source_1 FOR source_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME '{}'
+          );
+        ",
+            source_queue
+        ),
+    ]
+}
+
+fn push_rows(pipeline: &SpringPipeline, queue: &str) {
+    for (ts, n) in [
+        ("2022-01-01 13:00:01.000000000", 100),
+        ("2022-01-01 13:00:11.000000000", 300),
+        // late but within allowed lateness: updates [:00, :10)
+        ("2022-01-01 13:00:07.000000000", 200),
+        // too late
+        ("2022-01-01 13:00:03.000000000", 1000),
+        ("2022-01-01 13:00:21.000000000", 0),
+    ] {
+        let row =
+            SpringSourceRow::from_json(&format!(r#"{{"ts": "{}", "n": {}}}"#, ts, n)).unwrap();
+        pipeline.push(queue, row).unwrap();
+    }
+}
+
+#[test]
+fn test_allowed_lateness() {
+    let ddls = ddls("", "q_allowed_lateness_source", "q_allowed_lateness_sink");
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+    push_rows(&pipeline, "q_allowed_lateness_source");
+
+    for expected in [100.0, 150.0, 300.0] {
+        let row = pipeline.pop("q_allowed_lateness_sink").unwrap();
+        assert_eq!(row.get_not_null_by_index::<f32>(0).unwrap(), expected);
+    }
+}
+
+#[test]
+fn test_allowed_lateness_emit_on_update() {
+    let ddls = ddls(
+        "EMIT ON UPDATE",
+        "q_emit_on_update_source",
+        "q_emit_on_update_sink",
+    );
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+    push_rows(&pipeline, "q_emit_on_update_source");
+
+    // each row (except the too late one) outputs the current result of its pane
+    for expected in [100.0, 300.0, 150.0, 0.0] {
+        let row = pipeline.pop("q_emit_on_update_sink").unwrap();
+        assert_eq!(row.get_not_null_by_index::<f32>(0).unwrap(), expected);
+    }
+}
@@ -0,0 +1,79 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use std::{thread, time::Duration};
+
+use springql::{SpringConfig, SpringSourceRow};
+
+use crate::test_support::*;
+
+fn push(pipeline: &springql::SpringPipeline, n: i32) {
+    let row = SpringSourceRow::from_json(&format!(
+        r#"{{"ts": "2022-01-01 13:00:00.000000000", "n": {}}}"#,
+        n
+    ))
+    .unwrap();
+    pipeline.push("q_alter_pump_source", row).unwrap();
+}
+
+#[test]
+fn test_alter_pump_stop_start() {
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pump_1 AS
+          INSERT INTO sink_1 (ts, n)
+          SELECT STREAM source_1.ts, source_1.n FROM source_1;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_1 FOR sink_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_alter_pump_sink'
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_1 FOR source_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_alter_pump_source'
+          );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    push(&pipeline, 1);
+    let row = pipeline.pop("q_alter_pump_sink").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 1);
+
+    // rows arriving at a stopped pump are discarded
+    pipeline.command("ALTER PUMP pump_1 STOP;").unwrap();
+    push(&pipeline, 2);
+    thread::sleep(Duration::from_millis(500));
+    assert!(pipeline
+        .pop_non_blocking("q_alter_pump_sink")
+        .unwrap()
+        .is_none());
+
+    pipeline.command("ALTER PUMP pump_1 START;").unwrap();
+    push(&pipeline, 3);
+    let row = pipeline.pop("q_alter_pump_sink").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 3);
+
+    assert!(pipeline.command("ALTER PUMP undefined_pump STOP;").is_err());
+}
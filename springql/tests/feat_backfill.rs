@@ -0,0 +1,94 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use std::io::Write;
+
+use springql::{SpringConfig, SpringSourceRow};
+use tempfile::NamedTempFile;
+
+use crate::test_support::*;
+
+fn ddls(backfill_file: &str) -> Vec<String> {
+    vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pump_1 AS
+          INSERT INTO sink_1 (ts, n)
+          SELECT STREAM source_1.ts, source_1.n FROM source_1;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_1 FOR sink_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_backfill_sink'
+        );
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SOURCE READER q_source_1 FOR source_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_backfill_source',
+            BACKFILL_FILE '{}'
+          );
+        ",
+            backfill_file
+        ),
+    ]
+}
+
+#[test]
+fn test_backfill() {
+    let mut backfill_file = NamedTempFile::new().unwrap();
+    writeln!(
+        backfill_file,
+        r#"{{"ts": "2022-01-01 13:00:00.000000000", "n": 1}}"#
+    )
+    .unwrap();
+    writeln!(
+        backfill_file,
+        r#"[{{"ts": "2022-01-01 13:00:01.000000000", "n": 2}}]"#
+    )
+    .unwrap();
+
+    let pipeline = apply_ddls(
+        &ddls(backfill_file.path().to_str().unwrap()),
+        SpringConfig::default(),
+    );
+
+    // older than the backfilled rows
+    let row =
+        SpringSourceRow::from_json(r#"{"ts": "2022-01-01 13:00:00.500000000", "n": 3}"#).unwrap();
+    pipeline.push("q_backfill_source", row).unwrap();
+    let row =
+        SpringSourceRow::from_json(r#"{"ts": "2022-01-01 13:00:02.000000000", "n": 4}"#).unwrap();
+    pipeline.push("q_backfill_source", row).unwrap();
+
+    for expected in [1, 2, 4] {
+        let row = pipeline.pop("q_backfill_sink").unwrap();
+        assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), expected);
+    }
+}
+
+#[test]
+fn test_backfill_file_not_found() {
+    let mut ddls = ddls("/nonexistent/springql_backfill.json");
+    let source_reader_ddl = ddls.pop().unwrap();
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    assert!(pipeline.command(source_reader_ddl).is_err());
+}
@@ -0,0 +1,56 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use springql::{SpringConfig, SpringSourceRow};
+
+use crate::test_support::*;
+
+#[test]
+fn test_best_effort_pump_in_moderate_state() {
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pump_1 BEST_EFFORT AS
+          INSERT INTO sink_1 (ts, n)
+          SELECT STREAM source_1.ts, source_1.n FROM source_1;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_1 FOR sink_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_best_effort_sink'
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_1 FOR source_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_best_effort_source'
+          );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    // best-effort pumps run as usual unless memory state is Severe or Critical
+    let row =
+        SpringSourceRow::from_json(r#"{"ts": "2022-01-01 13:00:00.000000000", "n": 1}"#).unwrap();
+    pipeline.push("q_best_effort_source", row).unwrap();
+
+    let row = pipeline.pop("q_best_effort_sink").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 1);
+}
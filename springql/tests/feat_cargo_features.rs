@@ -0,0 +1,73 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use springql::SpringConfig;
+
+use crate::test_support::*;
+
+fn ddls() -> Vec<String> {
+    vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          http_body BLOB NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          http_body BLOB NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pump_1 AS
+          INSERT INTO sink_1 (ts, http_body)
+          SELECT STREAM source_1.ts, source_1.http_body FROM source_1;
+        "
+        .to_string(),
+    ]
+}
+
+const HTTP_SINK_WRITER_DDL: &str = "
+    CREATE SINK WRITER http_sink_1 FOR sink_1
+      TYPE HTTP1_CLIENT OPTIONS (
+        REMOTE_HOST '127.0.0.1',
+        REMOTE_PORT '18080',
+        METHOD 'POST',
+        URL 'http://127.0.0.1:18080/test',
+        BLOB_BODY_COLUMN 'http_body'
+    );
+    ";
+
+#[cfg(feature = "sink-http")]
+#[test]
+fn test_sink_http_enabled() {
+    let pipeline = apply_ddls(&ddls(), SpringConfig::default());
+    pipeline.command(HTTP_SINK_WRITER_DDL).unwrap();
+}
+
+#[cfg(not(feature = "sink-http"))]
+#[test]
+fn test_sink_http_disabled() {
+    let pipeline = apply_ddls(&ddls(), SpringConfig::default());
+    assert!(pipeline.command(HTTP_SINK_WRITER_DDL).is_err());
+}
+
+#[cfg(not(feature = "source-can"))]
+#[test]
+fn test_source_can_disabled() {
+    let pipeline = apply_ddls(&ddls(), SpringConfig::default());
+    assert!(pipeline
+        .command(
+            "
+        CREATE SOURCE READER can_source_1 FOR source_1
+          TYPE CAN OPTIONS (
+            INTERFACE 'vcan0'
+        );
+        "
+        )
+        .is_err());
+}
@@ -0,0 +1,105 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+#![cfg(feature = "chaos")]
+
+mod test_support;
+
+use std::{thread, time::Duration};
+
+use springql::{SpringConfig, SpringSourceRow};
+
+use crate::test_support::*;
+
+fn ddls(source_queue: &str, sink_queue: &str) -> Vec<String> {
+    vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pump_1 AS
+          INSERT INTO sink_1 (ts, n)
+          SELECT STREAM source_1.ts, source_1.n FROM source_1;
+        "
+        .to_string(),
+        format!(
+            "
+            CREATE SINK WRITER q_sink_1 FOR sink_1
+              TYPE IN_MEMORY_QUEUE OPTIONS (
+                NAME '{}'
+              );
+            ",
+            sink_queue
+        ),
+        format!(
+            "
+            CREATE SOURCE READER q_source_1 FOR source_1
+              TYPE IN_MEMORY_QUEUE OPTIONS (
+                NAME '{}'
+              );
+            ",
+            source_queue
+        ),
+    ]
+}
+
+fn push_rows(pipeline: &springql::SpringPipeline, queue: &str, n_rows: i32) {
+    for n in 0..n_rows {
+        let row = SpringSourceRow::from_json(&format!(
+            r#"{{"ts": "2022-01-01 13:00:00.000000000", "n": {}}}"#,
+            n
+        ))
+        .unwrap();
+        pipeline.push(queue, row).unwrap();
+    }
+}
+
+#[test]
+fn test_chaos_row_drop() {
+    let config = SpringConfig::new(
+        "
+        [chaos]
+        row_drop_ppm = 1_000_000
+        ",
+    )
+    .unwrap();
+    let pipeline = apply_ddls(&ddls("q_chaos_drop_source", "q_chaos_drop_sink"), config);
+
+    push_rows(&pipeline, "q_chaos_drop_source", 3);
+    thread::sleep(Duration::from_millis(500));
+
+    assert!(pipeline
+        .pop_non_blocking("q_chaos_drop_sink")
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn test_chaos_task_delay() {
+    let config = SpringConfig::new(
+        "
+        [chaos]
+        task_delay_ppm = 1_000_000
+        task_delay_msec = 1
+        ",
+    )
+    .unwrap();
+    let pipeline = apply_ddls(&ddls("q_chaos_delay_source", "q_chaos_delay_sink"), config);
+
+    // delayed rows are still delivered
+    push_rows(&pipeline, "q_chaos_delay_source", 3);
+    for n in 0..3 {
+        let row = pipeline.pop("q_chaos_delay_sink").unwrap();
+        assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), n);
+    }
+}
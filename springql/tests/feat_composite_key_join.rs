@@ -0,0 +1,113 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use springql::{SpringConfig, SpringSourceRow};
+
+use crate::test_support::*;
+
+fn ddls() -> Vec<String> {
+    vec![
+        "
+        CREATE SOURCE STREAM source_trip (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          vehicle_id INTEGER NOT NULL,
+          distance INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE STREAM source_speed (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          vehicle_id INTEGER NOT NULL,
+          speed INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_joined (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          distance INTEGER NOT NULL,
+          speed INTEGER
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_join AS
+          INSERT INTO sink_joined (ts, distance, speed)
+          SELECT STREAM
+            source_trip.ts,
+            source_trip.distance,
+            source_speed.speed
+          FROM source_trip
+          LEFT OUTER JOIN source_speed
+            ON source_trip.vehicle_id = source_speed.vehicle_id
+              AND FLOOR_TIME(source_speed.ts, DURATION_SECS(1)) = FLOOR_TIME(source_trip.ts, DURATION_SECS(1))
+              AND source_speed.speed = source_speed.speed -- not a join key (evaluated per pair)
+          FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0) MIN WATERMARK;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_joined FOR sink_joined
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_composite_key_join_sink'
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_trip FOR source_trip
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_composite_key_join_trip'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_speed FOR source_speed
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_composite_key_join_speed'
+          );
+        "
+        .to_string(),
+    ]
+}
+
+fn push(pipeline: &springql::SpringPipeline, queue: &str, json: &str) {
+    let row = SpringSourceRow::from_json(json).unwrap();
+    pipeline.push(queue, row).unwrap();
+}
+
+#[test]
+fn test_composite_key_join() {
+    let pipeline = apply_ddls(&ddls(), SpringConfig::default());
+
+    for json in [
+        r#"{"ts": "2020-01-01 00:00:00.500000000", "vehicle_id": 1, "distance": 100}"#,
+        r#"{"ts": "2020-01-01 00:00:03.200000000", "vehicle_id": 2, "distance": 200}"#,
+        r#"{"ts": "2020-01-01 00:00:05.000000000", "vehicle_id": 3, "distance": 300}"#,
+        r#"{"ts": "2020-01-01 00:00:11.000000000", "vehicle_id": 1, "distance": 0}"#,
+    ] {
+        push(&pipeline, "q_composite_key_join_trip", json);
+    }
+    for json in [
+        r#"{"ts": "2020-01-01 00:00:00.900000000", "vehicle_id": 1, "speed": 11}"#,
+        r#"{"ts": "2020-01-01 00:00:03.700000000", "vehicle_id": 1, "speed": 12}"#,
+        r#"{"ts": "2020-01-01 00:00:03.100000000", "vehicle_id": 2, "speed": 22}"#,
+        r#"{"ts": "2020-01-01 00:00:06.000000000", "vehicle_id": 3, "speed": 33}"#,
+        r#"{"ts": "2020-01-01 00:00:11.000000000", "vehicle_id": 1, "speed": 0}"#,
+    ] {
+        push(&pipeline, "q_composite_key_join_speed", json);
+    }
+
+    let row = pipeline.pop("q_composite_key_join_sink").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 100);
+    assert_eq!(row.get_not_null_by_index::<i32>(2).unwrap(), 11);
+
+    let row = pipeline.pop("q_composite_key_join_sink").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 200);
+    assert_eq!(row.get_not_null_by_index::<i32>(2).unwrap(), 22);
+
+    // same vehicle but in another second
+    let row = pipeline.pop("q_composite_key_join_sink").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 300);
+    assert!(row.get_not_null_by_index::<i32>(2).is_err());
+}
@@ -0,0 +1,91 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use std::{io::Write, net::TcpListener, thread};
+
+use springql::SpringConfig;
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::*;
+
+/// Serves `csv` to the first client and returns the port.
+fn serve_csv(csv: &'static str) -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let _ = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        stream.write_all(csv.as_bytes()).unwrap();
+        // keep the connection until the pipeline is dropped
+        thread::park();
+    });
+    port
+}
+
+#[test]
+fn test_feat_csv_source() {
+    setup_test_logger();
+
+    let port = serve_csv(
+        "ts,city,temperature\n\
+         2020-01-01 00:00:00.000000000,Tokyo,21\n\
+         2020-01-01 00:00:01.000000000,\"Osaka, Japan\",-3\n",
+    );
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_temperature (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          city TEXT NOT NULL,
+          temperature INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_temperature (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          city TEXT NOT NULL,
+          temperature INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pump_temperature AS
+          INSERT INTO sink_temperature (ts, city, temperature)
+          SELECT STREAM source_temperature.ts, source_temperature.city, source_temperature.temperature
+          FROM source_temperature;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER queue_temperature FOR sink_temperature
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_csv_source'
+          );
+        "
+        .to_string(),
+        format!(
+            "
+            CREATE SOURCE READER tcp_temperature FOR source_temperature
+              TYPE NET_CLIENT OPTIONS (
+                PROTOCOL 'TCP',
+                REMOTE_HOST '127.0.0.1',
+                REMOTE_PORT '{}',
+                FORMAT 'CSV'
+              );
+            ",
+            port
+        ),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    let row = pipeline.pop("q_csv_source").unwrap();
+    assert_eq!(row.get_not_null_by_index::<String>(1).unwrap(), "Tokyo");
+    assert_eq!(row.get_not_null_by_index::<i32>(2).unwrap(), 21);
+
+    let row = pipeline.pop("q_csv_source").unwrap();
+    assert_eq!(
+        row.get_not_null_by_index::<String>(1).unwrap(),
+        "Osaka, Japan"
+    );
+    assert_eq!(row.get_not_null_by_index::<i32>(2).unwrap(), -3);
+}
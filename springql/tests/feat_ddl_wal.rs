@@ -0,0 +1,52 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use springql::{SpringConfig, SpringPipeline, SpringSourceRow};
+
+#[test]
+fn test_ddl_wal_reconstructs_pipeline() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = SpringConfig::from_toml(&format!(
+        r#"
+[recovery]
+ddl_wal_path = '{wal_path}'
+
+[pipeline]
+ddl = [
+  "CREATE SOURCE STREAM source_1 (ts TIMESTAMP NOT NULL ROWTIME, n INTEGER NOT NULL);",
+]
+"#,
+        wal_path = dir.path().join("ddl.wal").display()
+    ))
+    .unwrap();
+
+    {
+        let pipeline = SpringPipeline::new(&config).unwrap();
+        pipeline
+            .command(
+                "CREATE SINK STREAM sink_1 (ts TIMESTAMP NOT NULL ROWTIME, n INTEGER NOT NULL);",
+            )
+            .unwrap();
+        pipeline
+            .command("CREATE PUMP pump_1 AS INSERT INTO sink_1 (ts, n) SELECT STREAM source_1.ts, source_1.n FROM source_1;")
+            .unwrap();
+    }
+
+    // `pipeline.ddl` would fail with the duplicate `source_1` if it were executed again.
+    let pipeline = SpringPipeline::new(&config).unwrap();
+    assert_eq!(pipeline.query("SHOW HISTORY").unwrap().lines().count(), 3);
+
+    // in-memory queues live as long as the process, so that they are created after the recovery
+    pipeline
+        .command("CREATE SINK WRITER q_sink_1 FOR sink_1 TYPE IN_MEMORY_QUEUE OPTIONS (NAME 'q_ddl_wal_sink');")
+        .unwrap();
+    pipeline
+        .command("CREATE SOURCE READER q_source_1 FOR source_1 TYPE IN_MEMORY_QUEUE OPTIONS (NAME 'q_ddl_wal_source');")
+        .unwrap();
+
+    let row =
+        SpringSourceRow::from_json(r#"{"ts": "2022-01-01 13:00:00.000000000", "n": 42}"#).unwrap();
+    pipeline.push("q_ddl_wal_source", row).unwrap();
+
+    let row = pipeline.pop("q_ddl_wal_sink").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 42);
+}
@@ -0,0 +1,83 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use springql::{SpringConfig, SpringSourceRow};
+
+use crate::test_support::*;
+
+fn ddls() -> Vec<String> {
+    vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pump_1 AS
+          INSERT INTO sink_1 (ts, n)
+          SELECT STREAM source_1.ts, source_1.n FROM source_1;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_1 FOR sink_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_drop_sink'
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_1 FOR source_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_drop_source'
+          );
+        "
+        .to_string(),
+    ]
+}
+
+#[test]
+fn test_drop() {
+    let pipeline = apply_ddls(&ddls(), SpringConfig::default());
+
+    // streams in use cannot be dropped
+    assert!(pipeline.command("DROP STREAM source_1;").is_err());
+    assert!(pipeline.command("DROP STREAM sink_1;").is_err());
+
+    let row =
+        SpringSourceRow::from_json(r#"{"ts": "2022-01-01 13:00:00.000000000", "n": 1}"#).unwrap();
+    pipeline.push("q_drop_source", row).unwrap();
+    let row = pipeline.pop("q_drop_sink").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 1);
+
+    pipeline.command("DROP PUMP pump_1;").unwrap();
+    pipeline.command("DROP SINK WRITER q_sink_1;").unwrap();
+    pipeline.command("DROP STREAM sink_1;").unwrap();
+    assert!(pipeline.command("DROP STREAM source_1;").is_err());
+    pipeline.command("DROP SOURCE READER q_source_1;").unwrap();
+    pipeline.command("DROP STREAM source_1;").unwrap();
+
+    for dropped in [
+        "DROP PUMP pump_1;",
+        "DROP SINK WRITER q_sink_1;",
+        "DROP SOURCE READER q_source_1;",
+        "DROP STREAM sink_1;",
+    ] {
+        assert!(pipeline.command(dropped).is_err());
+    }
+    assert_eq!(pipeline.query("SHOW WARNINGS").unwrap(), "");
+
+    // names are free again
+    for ddl in &ddls()[..3] {
+        pipeline.command(ddl).unwrap();
+    }
+}
@@ -0,0 +1,76 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use springql::{SpringConfig, SpringPipeline};
+
+use crate::test_support::*;
+
+fn explain(pipeline: &SpringPipeline, sql: &str) -> serde_json::Value {
+    serde_json::from_str(&pipeline.query(sql).unwrap()).unwrap()
+}
+
+#[test]
+fn test_explain() {
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_avg_amount (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          amount FLOAT NOT NULL
+        );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    let plan = explain(
+        &pipeline,
+        "
+        EXPLAIN SELECT STREAM source_trade.ts, source_trade.amount
+          FROM source_trade
+          WHERE source_trade.amount > 10;
+        ",
+    );
+    assert_eq!(plan["input_type"], json!("Row"));
+    assert_eq!(
+        plan["lower_ops"]["join"],
+        json!({"collect": "source_trade"})
+    );
+    assert!(plan["lower_ops"]["selection"].is_string());
+    assert_eq!(plan["upper_ops"]["projection"].as_array().unwrap().len(), 2);
+    assert!(plan["upper_ops"]["group_aggr_window"].is_null());
+
+    let plan = explain(
+        &pipeline,
+        "
+        EXPLAIN CREATE PUMP pu_avg AS
+          INSERT INTO sink_avg_amount (ts, amount)
+          SELECT STREAM
+            FLOOR_TIME(source_trade.ts, DURATION_SECS(10)) AS sampled_ts,
+            AVG(source_trade.amount) AS avg_amount
+          FROM source_trade
+          GROUP BY sampled_ts
+          FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        ",
+    );
+    assert_eq!(plan["input_type"], json!("Window"));
+    assert!(plan["upper_ops"]["group_aggr_window"]["window"].is_string());
+    assert!(plan["lower_ops"]["selection"].is_null());
+
+    // EXPLAIN does not create the pump
+    assert_eq!(pipeline.query("SHOW PUMPS").unwrap(), "");
+
+    assert!(pipeline
+        .query("EXPLAIN SELECT STREAM undefined.ts FROM undefined")
+        .is_err());
+}
@@ -0,0 +1,94 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use std::{
+    fs, thread,
+    time::{Duration, Instant},
+};
+
+use springql::SpringConfig;
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::*;
+
+#[test]
+fn test_feat_file_sink() {
+    setup_test_logger();
+
+    let dir = tempfile::tempdir().unwrap();
+    let source_path = dir.path().join("temperature.log");
+    let sink_path = dir.path().join("alert.csv");
+    fs::write(
+        &source_path,
+        "{\"ts\": \"2020-01-01 00:00:00.000000000\", \"city\": \"Tokyo\", \"temperature\": 21}\n\
+         {\"ts\": \"2020-01-01 00:00:01.000000000\", \"city\": \"Osaka\", \"temperature\": 35}\n\
+         {\"ts\": \"2020-01-01 00:00:02.000000000\", \"city\": \"Nagoya\", \"temperature\": 36}\n",
+    )
+    .unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_temperature (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          city TEXT NOT NULL,
+          temperature INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_alert (
+          city TEXT NOT NULL,
+          temperature INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pump_alert AS
+          INSERT INTO sink_alert (city, temperature)
+          SELECT STREAM source_temperature.city, source_temperature.temperature
+          FROM source_temperature
+          WHERE source_temperature.temperature > 30;
+        "
+        .to_string(),
+        format!(
+            "
+            CREATE SINK WRITER file_alert FOR sink_alert
+              TYPE FILE OPTIONS (
+                PATH '{}',
+                FORMAT 'CSV',
+                MAX_FILE_SIZE_BYTES '20'
+              );
+            ",
+            sink_path.display()
+        ),
+        format!(
+            "
+            CREATE SOURCE READER file_temperature FOR source_temperature
+              TYPE FILE OPTIONS (
+                PATH '{}',
+                POLL_INTERVAL_MSEC '10',
+                START_POSITION 'BEGINNING'
+              );
+            ",
+            source_path.display()
+        ),
+    ];
+    let _pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    // the first file is rotated after a row is written next to the header
+    let rotated_path = dir.path().join("alert.csv.1");
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while !(rotated_path.exists() && fs::metadata(&sink_path).map_or(false, |m| m.len() > 0)) {
+        assert!(Instant::now() < deadline, "no rows written");
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(
+        fs::read_to_string(&rotated_path).unwrap(),
+        "city,temperature\n\"Osaka\",35\n"
+    );
+    assert_eq!(
+        fs::read_to_string(&sink_path).unwrap(),
+        "city,temperature\n\"Nagoya\",36\n"
+    );
+}
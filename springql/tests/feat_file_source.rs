@@ -0,0 +1,82 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use std::{fs, io::Write};
+
+use springql::SpringConfig;
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::*;
+
+#[test]
+fn test_feat_file_source() {
+    setup_test_logger();
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("temperature.log");
+    fs::write(
+        &path,
+        "{\"ts\": \"2020-01-01 00:00:00.000000000\", \"city\": \"Tokyo\", \"temperature\": 21}\n",
+    )
+    .unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_temperature (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          city TEXT NOT NULL,
+          temperature INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_temperature (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          city TEXT NOT NULL,
+          temperature INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pump_temperature AS
+          INSERT INTO sink_temperature (ts, city, temperature)
+          SELECT STREAM source_temperature.ts, source_temperature.city, source_temperature.temperature
+          FROM source_temperature;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER queue_temperature FOR sink_temperature
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_file_source'
+          );
+        "
+        .to_string(),
+        format!(
+            "
+            CREATE SOURCE READER file_temperature FOR source_temperature
+              TYPE FILE OPTIONS (
+                PATH '{}',
+                POLL_INTERVAL_MSEC '10',
+                START_POSITION 'BEGINNING'
+              );
+            ",
+            path.display()
+        ),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    let row = pipeline.pop("q_file_source").unwrap();
+    assert_eq!(row.get_not_null_by_index::<String>(1).unwrap(), "Tokyo");
+
+    // appended after the pipeline starts
+    let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+    file.write_all(
+        b"{\"ts\": \"2020-01-01 00:00:01.000000000\", \"city\": \"Osaka\", \"temperature\": -3}\n",
+    )
+    .unwrap();
+
+    let row = pipeline.pop("q_file_source").unwrap();
+    assert_eq!(row.get_not_null_by_index::<String>(1).unwrap(), "Osaka");
+    assert_eq!(row.get_not_null_by_index::<i32>(2).unwrap(), -3);
+}
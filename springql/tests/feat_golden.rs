@@ -0,0 +1,15 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Runs golden cases in `tests/golden/`. See `springql::testing::golden` to add cases.
+
+#![cfg(feature = "testing")]
+
+use springql::testing::golden::assert_golden_dir;
+use springql_test_logger::setup_test_logger;
+
+#[test]
+fn test_feat_golden() {
+    setup_test_logger();
+
+    assert_golden_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden"));
+}
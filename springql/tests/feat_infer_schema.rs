@@ -0,0 +1,70 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use springql::*;
+use springql_foreign_service::source::{ForeignSource, ForeignSourceInput};
+use springql_test_logger::setup_test_logger;
+
+#[test]
+fn test_feat_infer_schema() {
+    setup_test_logger();
+
+    let json1 = json!({
+        "ts": "2020-01-01 00:00:00.000000000",
+        "ticker": "ORCL",
+        "amount": 20,
+    });
+    let json2 = json!({
+        "ts": "2020-01-01 00:00:01.000000000",
+        "ticker": "GOOGL",
+        "amount": 100,
+    });
+    let source_input = vec![json1, json2];
+
+    let test_source = ForeignSource::new().unwrap();
+    let (remote_host, remote_port) = (test_source.host_ip(), test_source.port());
+    test_source.start(ForeignSourceInput::new_fifo_batch(source_input));
+
+    let pipeline = SpringPipeline::new(&SpringConfig::default()).unwrap();
+    let ddl = pipeline
+        .query(format!(
+            "
+            INFER SCHEMA FOR source_trade
+              FROM SOURCE READER TYPE NET_CLIENT OPTIONS (
+                PROTOCOL 'TCP',
+                REMOTE_HOST '{remote_host}',
+                REMOTE_PORT '{remote_port}'
+              )
+              SAMPLE 100;
+            ",
+            remote_host = remote_host,
+            remote_port = remote_port,
+        ))
+        .unwrap();
+
+    assert_eq!(
+        ddl,
+        "CREATE SOURCE STREAM source_trade (
+  amount INTEGER NOT NULL,
+  ticker TEXT NOT NULL,
+  ts TIMESTAMP NOT NULL ROWTIME
+);"
+    );
+
+    // suggested DDL is valid
+    pipeline.command(ddl).unwrap();
+}
+
+#[test]
+fn test_feat_query_without_text() {
+    setup_test_logger();
+
+    let pipeline = SpringPipeline::new(&SpringConfig::default()).unwrap();
+    assert!(matches!(
+        pipeline
+            .query("CREATE SOURCE STREAM s (c INTEGER NOT NULL);")
+            .unwrap_err(),
+        SpringError::Sql(_)
+    ));
+}
@@ -0,0 +1,104 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use springql::{SpringConfig, SpringSourceRow};
+
+use crate::test_support::*;
+
+fn ddls() -> Vec<String> {
+    vec![
+        "
+        CREATE SOURCE STREAM source_trip (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          vehicle_id INTEGER NOT NULL,
+          distance INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE STREAM source_speed (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          vehicle_id INTEGER NOT NULL,
+          speed INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_joined (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          distance INTEGER NOT NULL,
+          speed INTEGER
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_join AS
+          INSERT INTO sink_joined (ts, distance, speed)
+          SELECT STREAM
+            source_trip.ts,
+            source_trip.distance,
+            source_speed.speed
+          FROM source_trip
+          LEFT OUTER JOIN source_speed
+            ON source_trip.vehicle_id = source_speed.vehicle_id
+              AND source_speed.ts BETWEEN source_trip.ts AND source_trip.ts + INTERVAL '5' SECOND;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_joined FOR sink_joined
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_interval_join_sink'
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_trip FOR source_trip
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_interval_join_trip'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_speed FOR source_speed
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_interval_join_speed'
+          );
+        "
+        .to_string(),
+    ]
+}
+
+fn push(pipeline: &springql::SpringPipeline, queue: &str, json: &str) {
+    let row = SpringSourceRow::from_json(json).unwrap();
+    pipeline.push(queue, row).unwrap();
+}
+
+#[test]
+fn test_interval_join() {
+    let pipeline = apply_ddls(&ddls(), SpringConfig::default());
+
+    for json in [
+        r#"{"ts": "2020-01-01 00:00:00.000000000", "vehicle_id": 1, "distance": 100}"#,
+        r#"{"ts": "2020-01-01 00:00:10.000000000", "vehicle_id": 2, "distance": 200}"#,
+        r#"{"ts": "2020-01-01 00:00:30.000000000", "vehicle_id": 1, "distance": 0}"#,
+    ] {
+        push(&pipeline, "q_interval_join_trip", json);
+    }
+    for json in [
+        r#"{"ts": "2020-01-01 00:00:03.000000000", "vehicle_id": 1, "speed": 11}"#,
+        r#"{"ts": "2020-01-01 00:00:12.000000000", "vehicle_id": 1, "speed": 12}"#,
+        r#"{"ts": "2020-01-01 00:00:30.000000000", "vehicle_id": 2, "speed": 0}"#,
+    ] {
+        push(&pipeline, "q_interval_join_speed", json);
+    }
+
+    let row = pipeline.pop("q_interval_join_sink").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 100);
+    assert_eq!(row.get_not_null_by_index::<i32>(2).unwrap(), 11);
+
+    // no speed of the vehicle within 5 seconds
+    let row = pipeline.pop("q_interval_join_sink").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 200);
+    assert!(row.get_not_null_by_index::<i32>(2).is_err());
+}
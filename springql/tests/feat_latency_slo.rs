@@ -0,0 +1,72 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use springql::{SpringConfig, SpringSourceRow};
+
+use crate::test_support::*;
+
+fn ddls(latency_slo_msec: &str) -> Vec<String> {
+    vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pump_1 AS
+          INSERT INTO sink_1 (ts, n)
+          SELECT STREAM source_1.ts, source_1.n FROM source_1;
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER q_sink_1 FOR sink_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_latency_slo_sink',
+            LATENCY_SLO_MSEC '{}'
+        );
+        ",
+            latency_slo_msec
+        ),
+        "
+        CREATE SOURCE READER q_source_1 FOR source_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_latency_slo_source'
+          );
+        "
+        .to_string(),
+    ]
+}
+
+#[test]
+fn test_latency_slo() {
+    let pipeline = apply_ddls(&ddls("10"), SpringConfig::default());
+
+    // event time far in the past violates the SLO but the row is still written
+    let row =
+        SpringSourceRow::from_json(r#"{"ts": "2022-01-01 13:00:00.000000000", "n": 1}"#).unwrap();
+    pipeline.push("q_latency_slo_source", row).unwrap();
+
+    let row = pipeline.pop("q_latency_slo_sink").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 1);
+}
+
+#[test]
+fn test_invalid_latency_slo() {
+    let mut ddls = ddls("10ms");
+    let sink_writer_ddl = ddls.remove(3);
+    let _source_reader_ddl = ddls.pop();
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    assert!(pipeline.command(sink_writer_ddl).is_err());
+}
@@ -0,0 +1,108 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use std::{thread, time::Duration};
+
+use springql::{SpringConfig, SpringSourceRow};
+
+use crate::test_support::*;
+
+fn ddls() -> Vec<String> {
+    vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE STREAM source_city_temperature (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          temperature INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_joined (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          amount INTEGER NOT NULL,
+          temperature INTEGER
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_join AS
+          INSERT INTO sink_joined (ts, amount, temperature)
+          SELECT STREAM
+            source_trade.ts,
+            source_trade.amount,
+            source_city_temperature.temperature
+          FROM source_trade
+          LEFT OUTER JOIN source_city_temperature
+            ON source_trade.ts = source_city_temperature.ts
+          FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0) MIN WATERMARK;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_joined FOR sink_joined
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_min_watermark_sink'
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_trade FOR source_trade
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_min_watermark_trade'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_city_temperature FOR source_city_temperature
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_min_watermark_city_temperature'
+          );
+        "
+        .to_string(),
+    ]
+}
+
+fn push(pipeline: &springql::SpringPipeline, queue: &str, json: &str) {
+    let row = SpringSourceRow::from_json(json).unwrap();
+    pipeline.push(queue, row).unwrap();
+}
+
+#[test]
+fn test_min_watermark() {
+    let pipeline = apply_ddls(&ddls(), SpringConfig::default());
+
+    push(
+        &pipeline,
+        "q_min_watermark_trade",
+        r#"{"ts": "2020-01-01 00:00:00.000000000", "amount": 100}"#,
+    );
+    push(
+        &pipeline,
+        "q_min_watermark_trade",
+        r#"{"ts": "2020-01-01 00:00:11.000000000", "amount": 200}"#,
+    );
+
+    // city_temperature is delayed
+    thread::sleep(Duration::from_millis(500));
+    push(
+        &pipeline,
+        "q_min_watermark_city_temperature",
+        r#"{"ts": "2020-01-01 00:00:00.000000000", "temperature": 10}"#,
+    );
+    push(
+        &pipeline,
+        "q_min_watermark_city_temperature",
+        r#"{"ts": "2020-01-01 00:00:11.000000000", "temperature": 20}"#,
+    );
+
+    let row = pipeline.pop("q_min_watermark_sink").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 100);
+    assert_eq!(row.get_not_null_by_index::<i32>(2).unwrap(), 10);
+}
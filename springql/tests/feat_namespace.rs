@@ -0,0 +1,90 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use springql::{SpringConfig, SpringPipeline, SpringSourceRow};
+use springql_test_logger::setup_test_logger;
+
+fn create_tenant(pipeline: &SpringPipeline, tenant: &str) {
+    pipeline
+        .command(format!(
+            "CREATE SOURCE STREAM {tenant}.source_trade (ts TIMESTAMP NOT NULL ROWTIME, amount INTEGER NOT NULL);",
+            tenant = tenant
+        ))
+        .unwrap();
+    pipeline
+        .command(format!(
+            "CREATE SINK STREAM {tenant}.sink_trade (ts TIMESTAMP NOT NULL ROWTIME, amount INTEGER NOT NULL);",
+            tenant = tenant
+        ))
+        .unwrap();
+    pipeline
+        .command(format!(
+            "
+            CREATE PUMP {tenant}_pump AS
+              INSERT INTO {tenant}.sink_trade (ts, amount)
+              SELECT STREAM {tenant}.source_trade.ts, {tenant}.source_trade.amount * 10
+              FROM {tenant}.source_trade
+              WHERE {tenant}.source_trade.amount > 0;
+            ",
+            tenant = tenant
+        ))
+        .unwrap();
+    pipeline
+        .command(format!(
+            "CREATE SINK WRITER {tenant}_sink FOR {tenant}.sink_trade TYPE IN_MEMORY_QUEUE OPTIONS (NAME 'q_namespace_{tenant}_sink');",
+            tenant = tenant
+        ))
+        .unwrap();
+    pipeline
+        .command(format!(
+            "CREATE SOURCE READER {tenant}_source FOR {tenant}.source_trade TYPE IN_MEMORY_QUEUE OPTIONS (NAME 'q_namespace_{tenant}_source');",
+            tenant = tenant
+        ))
+        .unwrap();
+}
+
+fn push_trade(pipeline: &SpringPipeline, tenant: &str, amount: i32) {
+    let row = SpringSourceRow::from_json(&format!(
+        r#"{{"ts": "2022-01-01 13:00:00.000000000", "amount": {}}}"#,
+        amount
+    ))
+    .unwrap();
+    pipeline
+        .push(&format!("q_namespace_{}_source", tenant), row)
+        .unwrap();
+}
+
+#[test]
+fn test_feat_namespaces() {
+    setup_test_logger();
+
+    let config = SpringConfig::from_toml(
+        "
+        [namespaces.tenant_a]
+        memory_upper_limit_bytes = 1_000_000
+        ",
+    )
+    .unwrap();
+    let pipeline = SpringPipeline::new(&config).unwrap();
+
+    // the same stream names in different namespaces
+    create_tenant(&pipeline, "tenant_a");
+    create_tenant(&pipeline, "tenant_b");
+
+    push_trade(&pipeline, "tenant_a", 1);
+    push_trade(&pipeline, "tenant_b", 2);
+
+    let row = pipeline.pop("q_namespace_tenant_a_sink").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 10);
+    let row = pipeline.pop("q_namespace_tenant_b_sink").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 20);
+}
+
+#[test]
+fn test_feat_namespace_spring_is_reserved() {
+    setup_test_logger();
+
+    let pipeline = SpringPipeline::new(&SpringConfig::default()).unwrap();
+    assert!(pipeline
+        .command("CREATE STREAM spring.s1 (ts TIMESTAMP NOT NULL ROWTIME, n INTEGER NOT NULL);")
+        .is_err());
+}
@@ -0,0 +1,66 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use springql::{SpringConfig, SpringSourceRow};
+
+use crate::test_support::*;
+
+#[test]
+fn test_numeric_literals() {
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL,
+          f FLOAT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n_hex INTEGER NOT NULL,
+          n_separated INTEGER NOT NULL,
+          f_scientific FLOAT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pump_1 AS
+          INSERT INTO sink_1 (ts, n_hex, n_separated, f_scientific)
+          SELECT STREAM
+            source_1.ts,
+            source_1.n + 0xFF,
+            source_1.n + 1_000_000,
+            source_1.f * 1.5e3
+          FROM source_1;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_1 FOR sink_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_numeric_literals_sink'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_1 FOR source_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_numeric_literals_source'
+          );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    let row =
+        SpringSourceRow::from_json(r#"{"ts": "2022-01-01 13:00:00.000000000", "n": 1, "f": 2.0}"#)
+            .unwrap();
+    pipeline.push("q_numeric_literals_source", row).unwrap();
+
+    let row = pipeline.pop("q_numeric_literals_sink").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 256);
+    assert_eq!(row.get_not_null_by_index::<i32>(2).unwrap(), 1_000_001);
+    assert_eq!(row.get_not_null_by_index::<f32>(3).unwrap(), 3000.0);
+}
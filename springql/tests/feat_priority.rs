@@ -0,0 +1,102 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use springql::{SpringConfig, SpringSourceRow};
+
+use crate::test_support::*;
+
+fn source_and_sink_ddls() -> Vec<String> {
+    vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          alarm BOOLEAN NOT NULL,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          alarm BOOLEAN NOT NULL,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+    ]
+}
+
+#[test]
+fn test_priority_when() {
+    let mut ddls = source_and_sink_ddls();
+    ddls.append(&mut vec![
+        "
+        CREATE PUMP pump_1 AS
+          INSERT INTO sink_1 (ts, alarm, n)
+          SELECT STREAM source_1.ts, source_1.alarm, source_1.n FROM source_1
+          PRIORITY WHEN source_1.alarm = TRUE;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_1 FOR sink_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_priority_sink'
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_1 FOR source_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_priority_source'
+          );
+        "
+        .to_string(),
+    ]);
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    for (alarm, n) in [(false, 1), (true, 2), (false, 3)] {
+        let row = SpringSourceRow::from_json(&format!(
+            r#"{{"ts": "2022-01-01 13:00:00.000000000", "alarm": {}, "n": {}}}"#,
+            alarm, n
+        ))
+        .unwrap();
+        pipeline.push("q_priority_source", row).unwrap();
+    }
+
+    // high-priority rows may overtake others but no row is lost
+    let mut ns = (0..3)
+        .map(|_| {
+            let row = pipeline.pop("q_priority_sink").unwrap();
+            row.get_not_null_by_index::<i32>(2).unwrap()
+        })
+        .collect::<Vec<_>>();
+    ns.sort_unstable();
+    assert_eq!(ns, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_priority_when_with_window() {
+    let mut ddls = source_and_sink_ddls();
+    ddls.push(
+        "
+        CREATE STREAM s_avg (
+          avg_n FLOAT NOT NULL
+        );
+        "
+        .to_string(),
+    );
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    assert!(pipeline
+        .command(
+            "
+        CREATE PUMP pump_1 AS
+          INSERT INTO s_avg (avg_n)
+          SELECT STREAM AVG(source_1.n) AS avg_n FROM source_1
+          FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0)
+          PRIORITY WHEN source_1.alarm = TRUE;
+        "
+        )
+        .is_err());
+}
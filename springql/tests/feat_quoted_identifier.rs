@@ -0,0 +1,57 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use springql::{SpringConfig, SpringSourceRow};
+
+use crate::test_support::*;
+
+#[test]
+fn test_quoted_identifier() {
+    let ddls = vec![
+        r#"
+        CREATE SOURCE STREAM "can0.speed-raw" (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          "wheel-speed" INTEGER NOT NULL
+        );
+        "#
+        .to_string(),
+        r#"
+        CREATE SINK STREAM "can0.speed" (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          "select" INTEGER NOT NULL
+        );
+        "#
+        .to_string(),
+        r#"
+        CREATE PUMP "pump-speed" AS
+          INSERT INTO "can0.speed" (ts, "select")
+          SELECT STREAM "can0.speed-raw".ts, "can0.speed-raw"."wheel-speed"
+          FROM "can0.speed-raw";
+        "#
+        .to_string(),
+        r#"
+        CREATE SINK WRITER "q-sink" FOR "can0.speed"
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_quoted_identifier_sink'
+          );
+        "#
+        .to_string(),
+        r#"
+        CREATE SOURCE READER "q-source" FOR "can0.speed-raw"
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_quoted_identifier_source'
+          );
+        "#
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    let row =
+        SpringSourceRow::from_json(r#"{"ts": "2022-01-01 13:00:00.000000000", "wheel-speed": 42}"#)
+            .unwrap();
+    pipeline.push("q_quoted_identifier_source", row).unwrap();
+
+    let row = pipeline.pop("q_quoted_identifier_sink").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 42);
+}
@@ -0,0 +1,106 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use springql::{SpringConfig, SpringPipeline, SpringSourceRow};
+
+use crate::test_support::*;
+
+fn pipeline(sink_queues: &[&str], source_queue: &str) -> SpringPipeline {
+    let mut ddls = vec!["
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+    .to_string()];
+
+    let sink_streams = (0..sink_queues.len())
+        .map(|i| format!("sink_{}", i))
+        .collect::<Vec<_>>();
+    for (sink_stream, sink_queue) in sink_streams.iter().zip(sink_queues) {
+        ddls.push(format!(
+            "
+        CREATE SINK STREAM {} (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        ",
+            sink_stream
+        ));
+        ddls.push(format!(
+            "
+        CREATE SINK WRITER q_{} FOR {}
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME '{}'
+        );
+        ",
+            sink_stream, sink_stream, sink_queue
+        ));
+    }
+
+    ddls.push(format!(
+        "
+        CREATE REPLICATOR replicator_1 FROM source_1 TO {};
+        ",
+        sink_streams.join(", ")
+    ));
+    ddls.push(format!(
+        "
+        CREATE SOURCE READER q_source_1 FOR source_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME '{}'
+          );
+        ",
+        source_queue
+    ));
+
+    apply_ddls(&ddls, SpringConfig::default())
+}
+
+#[test]
+fn test_replicator() {
+    let sink_queues = ["q_replicator_sink_0", "q_replicator_sink_1"];
+    let pipeline = pipeline(&sink_queues, "q_replicator_source");
+
+    for n in [1, 2] {
+        let row = SpringSourceRow::from_json(&format!(
+            r#"{{"ts": "2022-01-01 13:00:00.000000000", "n": {}}}"#,
+            n
+        ))
+        .unwrap();
+        pipeline.push("q_replicator_source", row).unwrap();
+    }
+
+    for sink_queue in sink_queues {
+        let row = pipeline.pop(sink_queue).unwrap();
+        assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 1);
+        let row = pipeline.pop(sink_queue).unwrap();
+        assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 2);
+    }
+}
+
+#[test]
+fn test_replicator_duplicate_destination() {
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    assert!(pipeline
+        .command("CREATE REPLICATOR replicator_1 FROM source_1 TO sink_1, sink_1;")
+        .is_err());
+}
@@ -0,0 +1,160 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use springql::{SpringConfig, SpringPipeline, SpringSourceRow};
+
+use crate::test_support::*;
+
+fn sink_ddls(sink_stream: &str, sink_queue: &str) -> Vec<String> {
+    vec![
+        format!(
+            "
+        CREATE SINK STREAM {} (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          kind TEXT NOT NULL,
+          n INTEGER NOT NULL
+        );
+        ",
+            sink_stream
+        ),
+        format!(
+            "
+        CREATE SINK WRITER q_{} FOR {}
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME '{}'
+        );
+        ",
+            sink_stream, sink_stream, sink_queue
+        ),
+    ]
+}
+
+fn pipeline(router_ddl: &str, sinks: &[(&str, &str)], source_queue: &str) -> SpringPipeline {
+    let mut ddls = vec!["
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          kind TEXT NOT NULL,
+          n INTEGER NOT NULL
+        );
+        "
+    .to_string()];
+    for (sink_stream, sink_queue) in sinks {
+        ddls.append(&mut sink_ddls(sink_stream, sink_queue));
+    }
+    ddls.push(router_ddl.to_string());
+    ddls.push(format!(
+        "
+        CREATE SOURCE READER q_source_1 FOR source_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME '{}'
+          );
+        ",
+        source_queue
+    ));
+
+    apply_ddls(&ddls, SpringConfig::default())
+}
+
+fn push(pipeline: &SpringPipeline, queue: &str, kind: &str, n: i32) {
+    let row = SpringSourceRow::from_json(&format!(
+        r#"{{"ts": "2022-01-01 13:00:00.000000000", "kind": "{}", "n": {}}}"#,
+        kind, n
+    ))
+    .unwrap();
+    pipeline.push(queue, row).unwrap();
+}
+
+#[test]
+fn test_router_with_else() {
+    let pipeline = pipeline(
+        "
+        CREATE ROUTER router_1 FROM source_1
+          ROUTE
+            WHEN source_1.kind = 'a' TO sink_a
+            WHEN source_1.n = 2 TO sink_b
+            ELSE TO sink_other;
+        ",
+        &[
+            ("sink_a", "q_router_else_a"),
+            ("sink_b", "q_router_else_b"),
+            ("sink_other", "q_router_else_other"),
+        ],
+        "q_router_else_source",
+    );
+
+    push(&pipeline, "q_router_else_source", "a", 1);
+    push(&pipeline, "q_router_else_source", "b", 2);
+    push(&pipeline, "q_router_else_source", "c", 3);
+    // matches both conditions but only goes to the first one
+    push(&pipeline, "q_router_else_source", "a", 2);
+
+    let row = pipeline.pop("q_router_else_a").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(2).unwrap(), 1);
+    let row = pipeline.pop("q_router_else_a").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(2).unwrap(), 2);
+
+    let row = pipeline.pop("q_router_else_b").unwrap();
+    assert_eq!(row.get_not_null_by_index::<String>(1).unwrap(), "b");
+
+    let row = pipeline.pop("q_router_else_other").unwrap();
+    assert_eq!(row.get_not_null_by_index::<String>(1).unwrap(), "c");
+
+    assert!(pipeline
+        .pop_non_blocking("q_router_else_b")
+        .unwrap()
+        .is_none());
+    assert!(pipeline
+        .pop_non_blocking("q_router_else_other")
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn test_router_without_else() {
+    let pipeline = pipeline(
+        "
+        CREATE ROUTER router_1 FROM source_1
+          ROUTE WHEN source_1.kind = 'a' TO sink_a;
+        ",
+        &[("sink_a", "q_router_no_else_a")],
+        "q_router_no_else_source",
+    );
+
+    push(&pipeline, "q_router_no_else_source", "b", 1);
+    push(&pipeline, "q_router_no_else_source", "a", 2);
+
+    let row = pipeline.pop("q_router_no_else_a").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(2).unwrap(), 2);
+}
+
+#[test]
+fn test_router_destination_with_different_columns() {
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          kind TEXT NOT NULL,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_a (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    assert!(pipeline
+        .command(
+            "
+        CREATE ROUTER router_1 FROM source_1
+          ROUTE WHEN source_1.kind = 'a' TO sink_a;
+        "
+        )
+        .is_err());
+}
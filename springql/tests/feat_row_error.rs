@@ -0,0 +1,311 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use springql::{SpringConfig, SpringSourceRow};
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::*;
+
+#[test]
+fn test_row_error_skips_row() {
+    setup_test_logger();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          code TEXT NOT NULL,
+          pattern TEXT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          code TEXT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pump_1 AS
+          INSERT INTO sink_1 (ts, code)
+          SELECT STREAM source_1.ts, source_1.code FROM source_1
+          WHERE REGEXP_MATCH(source_1.code, source_1.pattern);
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_1 FOR sink_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_row_error_sink'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_1 FOR source_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_row_error_source'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_task_metrics (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          task_id TEXT NOT NULL,
+          row_errors BIGINT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_task_metrics AS
+          INSERT INTO sink_task_metrics (ts, task_id, row_errors)
+          SELECT STREAM
+            spring._task_metrics.ts,
+            spring._task_metrics.task_id,
+            spring._task_metrics.row_errors
+          FROM spring._task_metrics;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_task_metrics FOR sink_task_metrics
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_row_error_task_metrics'
+          );
+        "
+        .to_string(),
+    ];
+
+    let config = SpringConfig::from_toml(
+        "
+        [system_stream]
+        task_metrics_interval_msec = 100
+        ",
+    )
+    .unwrap();
+    let pipeline = apply_ddls(&ddls, config);
+
+    // the second row has an invalid regular expression
+    for (code, pattern) in [("E042", "^E"), ("E043", "("), ("E044", "^E")] {
+        let row = SpringSourceRow::from_json(&format!(
+            r#"{{"ts": "2022-01-01 13:00:00.000000000", "code": "{}", "pattern": "{}"}}"#,
+            code, pattern
+        ))
+        .unwrap();
+        pipeline.push("q_row_error_source", row).unwrap();
+    }
+
+    for code in ["E042", "E044"] {
+        let row = pipeline.pop("q_row_error_sink").unwrap();
+        assert_eq!(row.get_not_null_by_index::<String>(1).unwrap(), code);
+    }
+
+    loop {
+        let row = pipeline.pop("q_row_error_task_metrics").unwrap();
+        if row.get_not_null_by_index::<String>(1).unwrap() == "pump_1"
+            && row.get_not_null_by_index::<i64>(2).unwrap() == 1
+        {
+            break;
+        }
+    }
+}
+
+#[test]
+fn test_row_error_routed_to_row_errors_stream() {
+    setup_test_logger();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          code TEXT
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          code TEXT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pump_1 AS
+          INSERT INTO sink_1 (ts, code)
+          SELECT STREAM source_1.ts, source_1.code FROM source_1;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_1 FOR sink_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_row_errors_sink'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_1 FOR source_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_row_errors_source'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_row_errors (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          pump TEXT NOT NULL,
+          stream TEXT NOT NULL,
+          code TEXT NOT NULL,
+          field TEXT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_row_errors AS
+          INSERT INTO sink_row_errors (ts, pump, stream, code, field)
+          SELECT STREAM
+            spring._row_errors.ts,
+            spring._row_errors.pump,
+            spring._row_errors.stream,
+            spring._row_errors.code,
+            spring._row_errors.field
+          FROM spring._row_errors;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_row_errors FOR sink_row_errors
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_row_errors'
+          );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    // the second row has NULL for a NOT NULL column in sink_1
+    for code in [r#""E042""#, "null", r#""E044""#] {
+        let row = SpringSourceRow::from_json(&format!(
+            r#"{{"ts": "2022-01-01 13:00:00.000000000", "code": {}}}"#,
+            code
+        ))
+        .unwrap();
+        pipeline.push("q_row_errors_source", row).unwrap();
+    }
+
+    for code in ["E042", "E044"] {
+        let row = pipeline.pop("q_row_errors_sink").unwrap();
+        assert_eq!(row.get_not_null_by_index::<String>(1).unwrap(), code);
+    }
+
+    let row = pipeline.pop("q_row_errors").unwrap();
+    assert_eq!(row.get_not_null_by_index::<String>(1).unwrap(), "pump_1");
+    assert_eq!(row.get_not_null_by_index::<String>(2).unwrap(), "source_1");
+    assert_eq!(row.get_not_null_by_index::<String>(3).unwrap(), "SQL");
+    assert_eq!(
+        row.get_not_null_by_index::<String>(4).unwrap(),
+        "INSERT INTO sink_1"
+    );
+}
+
+#[test]
+fn test_row_error_group_by_null() {
+    setup_test_logger();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_avg_by_ticker (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          avg_amount FLOAT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP avg_by_ticker AS
+          INSERT INTO sink_avg_by_ticker (ts, ticker, avg_amount)
+          SELECT STREAM
+            FLOOR_TIME(source_trade.ts, DURATION_SECS(10)) AS min_ts,
+            source_trade.ticker AS ticker,
+            AVG(source_trade.amount) AS avg_amount
+          FROM source_trade
+          GROUP BY min_ts, ticker
+          FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_avg_by_ticker FOR sink_avg_by_ticker
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_group_by_null_sink'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_trade FOR source_trade
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_group_by_null_source'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_row_errors (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          pump TEXT NOT NULL,
+          code TEXT NOT NULL,
+          field TEXT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_row_errors AS
+          INSERT INTO sink_row_errors (ts, pump, code, field)
+          SELECT STREAM
+            spring._row_errors.ts,
+            spring._row_errors.pump,
+            spring._row_errors.code,
+            spring._row_errors.field
+          FROM spring._row_errors;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_row_errors FOR sink_row_errors
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_group_by_null_row_errors'
+          );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    // the second row has NULL for a GROUP BY column, and the last row closes the window
+    for (ts, ticker) in [
+        ("2022-01-01 13:00:00.000000000", r#""ORCL""#),
+        ("2022-01-01 13:00:01.000000000", "null"),
+        ("2022-01-01 13:00:02.000000000", r#""ORCL""#),
+        ("2022-01-01 13:00:10.000000000", r#""GOOGL""#),
+    ] {
+        let row = SpringSourceRow::from_json(&format!(
+            r#"{{"ts": "{}", "ticker": {}, "amount": 10}}"#,
+            ts, ticker
+        ))
+        .unwrap();
+        pipeline.push("q_group_by_null_source", row).unwrap();
+    }
+
+    let row = pipeline.pop("q_group_by_null_sink").unwrap();
+    assert_eq!(row.get_not_null_by_index::<String>(1).unwrap(), "ORCL");
+    assert_eq!(row.get_not_null_by_index::<f32>(2).unwrap(), 10.0);
+
+    let row = pipeline.pop("q_group_by_null_row_errors").unwrap();
+    assert_eq!(
+        row.get_not_null_by_index::<String>(1).unwrap(),
+        "avg_by_ticker"
+    );
+    assert_eq!(row.get_not_null_by_index::<String>(2).unwrap(), "SQL");
+    assert_eq!(row.get_not_null_by_index::<String>(3).unwrap(), "AGGREGATE");
+}
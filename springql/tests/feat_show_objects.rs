@@ -0,0 +1,109 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use pretty_assertions::assert_eq;
+use springql::{SpringConfig, SpringPipeline};
+
+use crate::test_support::*;
+
+fn names(pipeline: &SpringPipeline, sql: &str) -> Vec<String> {
+    pipeline
+        .command_query(sql)
+        .unwrap()
+        .iter()
+        .map(|row| row.get_not_null_by_column_name::<String>("name").unwrap())
+        .collect()
+}
+
+#[test]
+fn test_show_objects() {
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pump_1 AS
+          INSERT INTO sink_1 (ts, n)
+          SELECT STREAM source_1.ts, source_1.n FROM source_1;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_1 FOR sink_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_show_objects_sink'
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_1 FOR source_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_show_objects_source'
+          );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    assert_eq!(names(&pipeline, "SHOW STREAMS"), vec!["sink_1", "source_1"]);
+    assert_eq!(names(&pipeline, "SHOW PUMPS"), vec!["pump_1"]);
+    assert_eq!(names(&pipeline, "SHOW SOURCE READERS"), vec!["q_source_1"]);
+    assert_eq!(names(&pipeline, "SHOW SINK WRITERS"), vec!["q_sink_1"]);
+
+    let streams = pipeline.command_query("SHOW STREAMS").unwrap();
+    assert_eq!(
+        streams[1]
+            .get_not_null_by_column_name::<String>("columns")
+            .unwrap(),
+        "ts, n"
+    );
+
+    pipeline.command("ALTER PUMP pump_1 STOP").unwrap();
+    let pumps = pipeline.command_query("SHOW PUMPS").unwrap();
+    assert_eq!(
+        pumps[0]
+            .get_not_null_by_column_name::<String>("upstreams")
+            .unwrap(),
+        "source_1"
+    );
+    assert_eq!(
+        pumps[0]
+            .get_not_null_by_column_name::<String>("downstreams")
+            .unwrap(),
+        "sink_1"
+    );
+    assert!(pumps[0]
+        .get_not_null_by_column_name::<bool>("stopped")
+        .unwrap());
+
+    let sink_writers = pipeline.command_query("SHOW SINK WRITERS").unwrap();
+    assert_eq!(
+        sink_writers[0]
+            .get_not_null_by_column_name::<String>("type")
+            .unwrap(),
+        "IN_MEMORY_QUEUE"
+    );
+    assert_eq!(
+        sink_writers[0]
+            .get_not_null_by_column_name::<String>("stream")
+            .unwrap(),
+        "sink_1"
+    );
+
+    assert_eq!(
+        pipeline.query("SHOW SOURCE READERS").unwrap(),
+        r#"{"name":"q_source_1","stream":"source_1","type":"IN_MEMORY_QUEUE"}"#
+    );
+    assert!(pipeline.command_query("SHOW HISTORY").is_err());
+}
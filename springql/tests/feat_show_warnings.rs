@@ -0,0 +1,71 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use pretty_assertions::assert_eq;
+use springql_core::api::*;
+use springql_test_logger::setup_test_logger;
+
+#[test]
+fn test_show_warnings() {
+    setup_test_logger();
+
+    let pipeline = SpringPipeline::new(&SpringConfig::default()).unwrap();
+    assert_eq!(pipeline.query("SHOW WARNINGS").unwrap(), "");
+
+    for ddl in [
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        ",
+        "
+        CREATE SINK STREAM sink_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          amount INTEGER NOT NULL
+        );
+        ",
+        "
+        CREATE STREAM stream_dead (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          amount INTEGER NOT NULL
+        );
+        ",
+    ] {
+        pipeline.command(ddl).unwrap();
+    }
+    assert_eq!(
+        pipeline.query("SHOW WARNINGS").unwrap(),
+        r#"stream "sink_trade" is not read by any pump or sink writer
+stream "source_trade" is not read by any pump or sink writer
+stream "stream_dead" is not read by any pump or sink writer"#
+    );
+
+    for ddl in [
+        "
+        CREATE PUMP pu_sink AS
+          INSERT INTO sink_trade (ts, amount)
+          SELECT STREAM source_trade.ts, source_trade.amount FROM source_trade;
+        ",
+        "
+        CREATE PUMP pu_dead AS
+          INSERT INTO stream_dead (ts, amount)
+          SELECT STREAM source_trade.ts, source_trade.amount FROM source_trade
+          WHERE source_trade.amount > 100;
+        ",
+        "
+        CREATE SINK WRITER q_sink_trade FOR sink_trade
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_show_warnings'
+          );
+        ",
+    ] {
+        pipeline.command(ddl).unwrap();
+    }
+    assert_eq!(
+        pipeline.query("SHOW WARNINGS").unwrap(),
+        r#"stream "stream_dead" is not read by any pump or sink writer
+pump "pu_dead" inserts into streams which do not reach any sink writer
+column "ticker" of stream "source_trade" is not used downstream"#
+    );
+}
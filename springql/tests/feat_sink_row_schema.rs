@@ -0,0 +1,93 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use pretty_assertions::assert_eq;
+use springql::{SpringColumn, SpringConfig, SpringSourceRow};
+
+use crate::test_support::*;
+
+fn column(name: &str, sql_type: &str, nullable: bool) -> SpringColumn {
+    SpringColumn {
+        name: name.to_string(),
+        sql_type: sql_type.to_string(),
+        nullable,
+    }
+}
+
+#[test]
+fn test_sink_row_schema() {
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_passthrough AS
+          INSERT INTO sink_trade (ts, ticker, amount)
+          SELECT STREAM source_trade.ts, source_trade.ticker, source_trade.amount FROM source_trade;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_all FOR sink_trade
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_schema_sink_all'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_projected FOR sink_trade
+          PROJECT (amount, ts)
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_schema_sink_projected'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_trade FOR source_trade
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_schema_source'
+          );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    let row = SpringSourceRow::from_json(
+        r#"{"ts": "2022-01-01 13:00:00.000000000", "ticker": "ORCL", "amount": 10}"#,
+    )
+    .unwrap();
+    pipeline.push("q_schema_source", row).unwrap();
+
+    let row = pipeline.pop("q_schema_sink_all").unwrap();
+    assert_eq!(
+        row.schema().unwrap(),
+        &[
+            column("ts", "TIMESTAMP", false),
+            column("ticker", "TEXT", false),
+            column("amount", "INTEGER", true),
+        ]
+    );
+
+    let row = pipeline.pop("q_schema_sink_projected").unwrap();
+    assert_eq!(
+        row.schema().unwrap(),
+        &[
+            column("amount", "INTEGER", true),
+            column("ts", "TIMESTAMP", false),
+        ]
+    );
+    assert_eq!(row.get_not_null_by_index::<i32>(0).unwrap(), 10);
+}
@@ -0,0 +1,97 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    io::Write,
+    net::{TcpListener, TcpStream},
+    thread,
+    time::Duration,
+};
+
+use serde_json::json;
+use springql::{SpringConfig, SpringPipeline};
+use springql_test_logger::setup_test_logger;
+
+fn send_trade(conn: &mut TcpStream, ts: &str, amount: i32) {
+    let row = json!({ "ts": ts, "amount": amount });
+    writeln!(conn, "{}", row).unwrap();
+}
+
+/// Rows aggregated into an open pane before the snapshot are output by the restored pipeline.
+#[test]
+fn test_feat_snapshot_restores_aggregation() {
+    setup_test_logger();
+
+    // serves both of the original and the restored pipelines as a source
+    let source = TcpListener::bind("127.0.0.1:0").unwrap();
+    let source_addr = source.local_addr().unwrap();
+
+    let config = SpringConfig::default();
+
+    let archive = {
+        let pipeline = SpringPipeline::new(&config).unwrap();
+        pipeline
+            .command(
+                "CREATE SOURCE STREAM source_trade (ts TIMESTAMP NOT NULL ROWTIME, amount INTEGER NOT NULL);",
+            )
+            .unwrap();
+        pipeline
+            .command("CREATE SINK STREAM sink_total (total BIGINT NOT NULL, n BIGINT NOT NULL);")
+            .unwrap();
+        pipeline
+            .command(
+                "
+                CREATE PUMP total_amount AS
+                INSERT INTO sink_total (total, n)
+                SELECT STREAM
+                    SUM(source_trade.amount) AS total,
+                    COUNT(*) AS n
+                FROM source_trade
+                FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+                ",
+            )
+            .unwrap();
+        pipeline
+            .command(format!(
+                "
+                CREATE SOURCE READER tcp_trade FOR source_trade
+                  TYPE NET_CLIENT OPTIONS (
+                    PROTOCOL 'TCP',
+                    REMOTE_HOST '{}',
+                    REMOTE_PORT '{}'
+                  );
+                ",
+                source_addr.ip(),
+                source_addr.port()
+            ))
+            .unwrap();
+
+        let (mut conn, _) = source.accept().unwrap();
+        send_trade(&mut conn, "2020-01-01 00:00:00.000000000", 10);
+        send_trade(&mut conn, "2020-01-01 00:00:05.000000000", 20);
+        thread::sleep(Duration::from_secs(1));
+
+        pipeline.snapshot().unwrap()
+    };
+
+    let pipeline = SpringPipeline::restore(&config, &archive).unwrap();
+    let (mut conn, _) = source.accept().unwrap();
+    pipeline
+        .command(
+            "CREATE SINK WRITER q_sink_total FOR sink_total TYPE IN_MEMORY_QUEUE OPTIONS (NAME 'q_snapshot_sink');",
+        )
+        .unwrap();
+
+    // closes the pane
+    send_trade(&mut conn, "2020-01-01 00:00:10.000000000", 50);
+
+    let row = pipeline.pop("q_snapshot_sink").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i64>(0).unwrap(), 30);
+    assert_eq!(row.get_not_null_by_index::<i64>(1).unwrap(), 2);
+}
+
+#[test]
+fn test_feat_snapshot_invalid_archive() {
+    setup_test_logger();
+
+    assert!(SpringPipeline::restore(&SpringConfig::default(), b"not a snapshot").is_err());
+}
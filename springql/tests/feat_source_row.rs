@@ -128,3 +128,62 @@ fn test_source_row_from_builder() -> Result<(), SpringError> {
 
     Ok(())
 }
+
+#[test]
+fn test_source_row_with_topic_metadata() {
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL,
+          topic TEXT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL,
+          topic TEXT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_1 AS
+          INSERT INTO sink_1 (ts, n, topic)
+          SELECT STREAM source_1.ts, source_1.n, source_1.topic FROM source_1;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_1 FOR sink_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_sink_topic_metadata'
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_1 FOR source_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_source_topic_metadata',
+            MAP_TOPIC_TO 'topic'
+          );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    pipeline
+        .push(
+            "q_source_topic_metadata",
+            SpringSourceRow::from_json(r#"{"ts": "2022-01-01 13:00:00.000000000", "n": 42}"#)
+                .unwrap(),
+        )
+        .unwrap();
+
+    let sink_row = pipeline.pop("q_sink_topic_metadata").unwrap();
+    assert_eq!(sink_row.get_not_null_by_index::<i32>(1).unwrap(), 42);
+    assert_eq!(
+        sink_row.get_not_null_by_index::<String>(2).unwrap(),
+        "q_source_topic_metadata"
+    );
+}
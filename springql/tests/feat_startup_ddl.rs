@@ -0,0 +1,40 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use springql::{SpringConfig, SpringPipeline, SpringSourceRow};
+
+#[test]
+fn test_startup_ddl() {
+    let config = SpringConfig::from_toml(
+        r#"
+[pipeline]
+ddl = [
+  "CREATE SOURCE STREAM source_1 (ts TIMESTAMP NOT NULL ROWTIME, n INTEGER NOT NULL);",
+  "CREATE SINK STREAM sink_1 (ts TIMESTAMP NOT NULL ROWTIME, n INTEGER NOT NULL);",
+  "CREATE PUMP pump_1 AS INSERT INTO sink_1 (ts, n) SELECT STREAM source_1.ts, source_1.n FROM source_1;",
+  "CREATE SINK WRITER q_sink_1 FOR sink_1 TYPE IN_MEMORY_QUEUE OPTIONS (NAME 'q_startup_ddl_sink');",
+  "CREATE SOURCE READER q_source_1 FOR source_1 TYPE IN_MEMORY_QUEUE OPTIONS (NAME 'q_startup_ddl_source');",
+]
+"#,
+    )
+    .unwrap();
+    let pipeline = SpringPipeline::new(&config).unwrap();
+
+    let row =
+        SpringSourceRow::from_json(r#"{"ts": "2022-01-01 13:00:00.000000000", "n": 42}"#).unwrap();
+    pipeline.push("q_startup_ddl_source", row).unwrap();
+
+    let row = pipeline.pop("q_startup_ddl_sink").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 42);
+}
+
+#[test]
+fn test_startup_ddl_invalid() {
+    let config = SpringConfig::from_toml(
+        r#"
+[pipeline]
+ddl = ["CREATE PUMP pump_1 AS INSERT INTO undefined_sink (n) SELECT STREAM undefined_source.n FROM undefined_source;"]
+"#,
+    )
+    .unwrap();
+    assert!(SpringPipeline::new(&config).is_err());
+}
@@ -0,0 +1,124 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use std::time::Duration;
+
+use springql::{SpringConfig, SpringSourceRow};
+
+use crate::test_support::*;
+
+fn ddls() -> Vec<String> {
+    vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE STREAM s_mid (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pump_1 AS
+          INSERT INTO s_mid (ts, n)
+          SELECT STREAM source_1.ts, source_1.n FROM source_1;
+        "
+        .to_string(),
+        "
+        CREATE PUMP pump_2 AS
+          INSERT INTO sink_1 (ts, n)
+          SELECT STREAM s_mid.ts, s_mid.n FROM s_mid;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_1 FOR sink_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_stream_tap_sink'
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_1 FOR source_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_stream_tap_source'
+          );
+        "
+        .to_string(),
+    ]
+}
+
+fn push(pipeline: &springql::SpringPipeline, n: i32) {
+    let row = SpringSourceRow::from_json(&format!(
+        r#"{{"ts": "2022-01-01 13:00:00.000000000", "n": {}}}"#,
+        n
+    ))
+    .unwrap();
+    pipeline.push("q_stream_tap_source", row).unwrap();
+}
+
+#[test]
+fn test_stream_tap() {
+    let pipeline = apply_ddls(&ddls(), SpringConfig::default());
+
+    let tap = pipeline.tap("s_mid", 2, Duration::from_secs(60)).unwrap();
+
+    for n in 0..3 {
+        push(&pipeline, n);
+    }
+    for n in 0..3 {
+        let row = pipeline.pop("q_stream_tap_sink").unwrap();
+        assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), n);
+    }
+
+    // tap is bounded to 2 rows
+    assert_eq!(
+        tap.pop_non_blocking()
+            .unwrap()
+            .get_not_null_by_index::<i32>(1)
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        tap.pop_non_blocking()
+            .unwrap()
+            .get_not_null_by_index::<i32>(1)
+            .unwrap(),
+        1
+    );
+    assert!(tap.pop_non_blocking().is_none());
+    assert!(!tap.is_expired());
+}
+
+#[test]
+fn test_stream_tap_expired() {
+    let pipeline = apply_ddls(&ddls(), SpringConfig::default());
+
+    let tap = pipeline.tap("s_mid", 10, Duration::from_secs(0)).unwrap();
+    assert!(tap.is_expired());
+
+    push(&pipeline, 1);
+    let _ = pipeline.pop("q_stream_tap_sink").unwrap();
+
+    assert!(tap.pop_non_blocking().is_none());
+}
+
+#[test]
+fn test_stream_tap_undefined_stream() {
+    let pipeline = apply_ddls(&ddls(), SpringConfig::default());
+    assert!(pipeline
+        .tap("undefined_stream", 10, Duration::from_secs(60))
+        .is_err());
+}
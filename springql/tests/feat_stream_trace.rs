@@ -0,0 +1,91 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use springql::{SpringConfig, SpringSourceRow};
+
+use crate::test_support::*;
+
+fn ddls() -> Vec<String> {
+    vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pump_1 AS
+          INSERT INTO sink_1 (ts, n)
+          SELECT STREAM source_1.ts, source_1.n FROM source_1;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_1 FOR sink_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_stream_trace_sink'
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_1 FOR source_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_stream_trace_source'
+          );
+        "
+        .to_string(),
+    ]
+}
+
+#[test]
+fn test_stream_trace() {
+    let pipeline = apply_ddls(&ddls(), SpringConfig::default());
+
+    pipeline
+        .command("ALTER STREAM source_1 SET TRACE SAMPLE 1;")
+        .unwrap();
+    pipeline
+        .command("ALTER STREAM sink_1 SET TRACE SAMPLE 0.5;")
+        .unwrap();
+
+    // tracing does not affect rows
+    for n in 0..3 {
+        let row = SpringSourceRow::from_json(&format!(
+            r#"{{"ts": "2022-01-01 13:00:00.000000000", "n": {}}}"#,
+            n
+        ))
+        .unwrap();
+        pipeline.push("q_stream_trace_source", row).unwrap();
+    }
+    for n in 0..3 {
+        let row = pipeline.pop("q_stream_trace_sink").unwrap();
+        assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), n);
+    }
+
+    pipeline
+        .command("ALTER STREAM source_1 SET TRACE OFF;")
+        .unwrap();
+}
+
+#[test]
+fn test_stream_trace_invalid() {
+    let pipeline = apply_ddls(&ddls(), SpringConfig::default());
+
+    assert!(pipeline
+        .command("ALTER STREAM source_1 SET TRACE SAMPLE 1.5;")
+        .is_err());
+    assert!(pipeline
+        .command("ALTER STREAM source_1 SET TRACE SAMPLE 0;")
+        .is_err());
+    assert!(pipeline
+        .command("ALTER STREAM undefined_stream SET TRACE SAMPLE 0.1;")
+        .is_err());
+}
@@ -0,0 +1,108 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use springql::{SpringConfig, SpringSourceRow};
+
+use crate::test_support::*;
+
+fn ddls() -> Vec<String> {
+    vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_2 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pump_v1 AS
+          INSERT INTO sink_1 (ts, n)
+          SELECT STREAM source_1.ts, source_1.n FROM source_1;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_1 FOR sink_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_switch_pump_sink'
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_1 FOR source_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_switch_pump_source'
+          );
+        "
+        .to_string(),
+    ]
+}
+
+#[test]
+fn test_switch_pump() {
+    let pipeline = apply_ddls(&ddls(), SpringConfig::default());
+
+    pipeline
+        .command(
+            "
+        CREATE PUMP pump_v2 AS
+          INSERT INTO sink_1 (ts, n)
+          SELECT STREAM source_1.ts, source_1.n + source_1.n FROM source_1;
+        ",
+        )
+        .unwrap();
+    pipeline.command("SWITCH PUMP pump_v1 TO pump_v2;").unwrap();
+
+    let row =
+        SpringSourceRow::from_json(r#"{"ts": "2022-01-01 13:00:00.000000000", "n": 1}"#).unwrap();
+    pipeline.push("q_switch_pump_source", row).unwrap();
+
+    let row = pipeline.pop("q_switch_pump_sink").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 2);
+    assert!(pipeline
+        .pop_non_blocking("q_switch_pump_sink")
+        .unwrap()
+        .is_none());
+
+    // old pump is dropped and its name is free again
+    assert!(pipeline.command("SWITCH PUMP pump_v1 TO pump_v2;").is_err());
+    pipeline
+        .command(
+            "
+        CREATE PUMP pump_v1 AS
+          INSERT INTO sink_2 (ts, n)
+          SELECT STREAM source_1.ts, source_1.n FROM source_1;
+        ",
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_switch_pump_to_different_stream() {
+    let pipeline = apply_ddls(&ddls(), SpringConfig::default());
+
+    pipeline
+        .command(
+            "
+        CREATE PUMP pump_v2 AS
+          INSERT INTO sink_2 (ts, n)
+          SELECT STREAM source_1.ts, source_1.n FROM source_1;
+        ",
+        )
+        .unwrap();
+    assert!(pipeline.command("SWITCH PUMP pump_v1 TO pump_v2;").is_err());
+}
@@ -0,0 +1,67 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use springql::SpringConfig;
+
+use crate::test_support::*;
+
+#[test]
+fn test_task_metrics_system_stream() {
+    let ddls = vec![
+        "
+        CREATE SINK STREAM sink_task_metrics (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          task_id TEXT NOT NULL,
+          num_input_rows BIGINT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_task_metrics AS
+          INSERT INTO sink_task_metrics (ts, task_id, num_input_rows)
+          SELECT STREAM
+            spring._task_metrics.ts,
+            spring._task_metrics.task_id,
+            spring._task_metrics.num_input_rows
+          FROM spring._task_metrics;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_task_metrics FOR sink_task_metrics
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_task_metrics'
+          );
+        "
+        .to_string(),
+    ];
+
+    let config = SpringConfig::from_toml(
+        "
+        [system_stream]
+        task_metrics_interval_msec = 100
+        ",
+    )
+    .unwrap();
+    let pipeline = apply_ddls(&ddls, config);
+
+    // the pipeline monitors its own tasks
+    let row = pipeline.pop("q_task_metrics").unwrap();
+    assert!(!row.get_not_null_by_index::<String>(1).unwrap().is_empty());
+    assert!(row.get_not_null_by_index::<i64>(2).unwrap() >= 0);
+}
+
+#[test]
+fn test_system_stream_name_reserved() {
+    let pipeline = apply_ddls(&[], SpringConfig::default());
+
+    assert!(pipeline
+        .command(
+            "
+            CREATE STREAM spring._my_stream (
+              ts TIMESTAMP NOT NULL ROWTIME
+            );
+            ",
+        )
+        .is_err());
+}
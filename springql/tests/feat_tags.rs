@@ -0,0 +1,75 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use springql::SpringConfig;
+
+use crate::test_support::*;
+
+#[test]
+fn test_tags_in_task_metrics() {
+    let ddls = vec![
+        "
+        CREATE SINK STREAM sink_task_metrics (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          task_id TEXT NOT NULL,
+          tags TEXT NOT NULL
+        ) WITH TAGS ('subsystem' = 'monitoring');
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_tagged AS
+          INSERT INTO sink_task_metrics (ts, task_id, tags)
+          SELECT STREAM
+            spring._task_metrics.ts,
+            spring._task_metrics.task_id,
+            spring._task_metrics.tags
+          FROM spring._task_metrics
+          WITH TAGS ('subsystem' = 'brakes', 'owner' = 'chassis');
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_task_metrics FOR sink_task_metrics
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_tags'
+          );
+        "
+        .to_string(),
+    ];
+
+    let config = SpringConfig::from_toml(
+        "
+        [system_stream]
+        task_metrics_interval_msec = 100
+        ",
+    )
+    .unwrap();
+    let pipeline = apply_ddls(&ddls, config);
+
+    let mut tags_by_task = std::collections::HashMap::new();
+    while !(tags_by_task.contains_key("pu_tagged")
+        && tags_by_task.contains_key("q_sink_task_metrics"))
+    {
+        let row = pipeline.pop("q_tags").unwrap();
+        let task_id = row.get_not_null_by_index::<String>(1).unwrap();
+        let tags = row.get_not_null_by_index::<String>(2).unwrap();
+        tags_by_task.insert(task_id, tags);
+    }
+
+    assert_eq!(tags_by_task["pu_tagged"], "owner=chassis,subsystem=brakes");
+    assert_eq!(tags_by_task["q_sink_task_metrics"], "subsystem=monitoring");
+}
+
+#[test]
+fn test_duplicate_tag() {
+    let pipeline = apply_ddls(&[], SpringConfig::default());
+    assert!(pipeline
+        .command(
+            "
+            CREATE STREAM s (
+              ts TIMESTAMP NOT NULL ROWTIME
+            ) WITH TAGS ('subsystem' = 'brakes', 'subsystem' = 'steering');
+            "
+        )
+        .is_err());
+}
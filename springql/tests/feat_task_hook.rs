@@ -0,0 +1,88 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use springql::{
+    SpringConfig, SpringSourceRow, SpringTaskExecution, SpringTaskHook, SpringTaskInfo,
+    SpringTaskKind,
+};
+
+use crate::test_support::*;
+
+/// Rows put by each task.
+#[derive(Clone, Default)]
+struct RowCounter(Arc<Mutex<HashMap<String, u64>>>);
+
+impl SpringTaskHook for RowCounter {
+    fn after_task(&self, task: &SpringTaskInfo, execution: &SpringTaskExecution) {
+        if task.kind == SpringTaskKind::Pump {
+            *self.0.lock().unwrap().entry(task.name.clone()).or_default() += execution.rows_put;
+        }
+    }
+}
+
+fn ddls() -> Vec<String> {
+    vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pump_1 AS
+          INSERT INTO sink_1 (ts, n)
+          SELECT STREAM source_1.ts, source_1.n FROM source_1;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_1 FOR sink_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_task_hook_sink'
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_1 FOR source_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_task_hook_source'
+          );
+        "
+        .to_string(),
+    ]
+}
+
+#[test]
+fn test_task_hook() {
+    let pipeline = apply_ddls(&ddls(), SpringConfig::default());
+
+    let counter = RowCounter::default();
+    pipeline.add_task_hook(counter.clone()).unwrap();
+
+    for n in 0..3 {
+        let row = SpringSourceRow::from_json(&format!(
+            r#"{{"ts": "2022-01-01 13:00:00.000000000", "n": {}}}"#,
+            n
+        ))
+        .unwrap();
+        pipeline.push("q_task_hook_source", row).unwrap();
+    }
+    for _ in 0..3 {
+        let _ = pipeline.pop("q_task_hook_sink").unwrap();
+    }
+
+    assert_eq!(counter.0.lock().unwrap().get("pump_1"), Some(&3));
+}
@@ -0,0 +1,104 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+#![cfg(feature = "testing")]
+
+mod test_support;
+
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use proptest::prelude::*;
+use springql::{testing::trade_source_rows, SpringConfig, SpringSourceRow};
+
+use crate::test_support::*;
+
+/// In-memory queues are named uniquely in the process.
+static CASE_ID: AtomicUsize = AtomicUsize::new(0);
+
+fn ddls(source_queue: &str, sink_queue: &str) -> Vec<String> {
+    vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_count (
+          ticker TEXT NOT NULL,
+          n BIGINT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP count_by_ticker AS
+          INSERT INTO sink_count (ticker, n)
+          SELECT STREAM source_trade.ticker, COUNT(source_trade.amount) AS n
+          FROM source_trade
+          GROUP BY source_trade.ticker
+          FIXED WINDOW DURATION_SECS(10), DURATION_MILLIS(500);
+        "
+        .to_string(),
+        format!(
+            "
+            CREATE SINK WRITER q_sink_count FOR sink_count
+              TYPE IN_MEMORY_QUEUE OPTIONS (
+                NAME '{}'
+              );
+            ",
+            sink_queue
+        ),
+        format!(
+            "
+            CREATE SOURCE READER q_source_trade FOR source_trade
+              TYPE IN_MEMORY_QUEUE OPTIONS (
+                NAME '{}'
+              );
+            ",
+            source_queue
+        ),
+    ]
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(8))]
+
+    /// Disorder within `ALLOWED DELAY` loses no rows.
+    #[test]
+    fn test_fixed_window_counts_all_rows(
+        rows in trade_source_rows(1..30, Duration::from_millis(500))
+    ) {
+        let case_id = CASE_ID.fetch_add(1, Ordering::SeqCst);
+        let (source_queue, sink_queue) = (
+            format!("q_testing_source_{}", case_id),
+            format!("q_testing_sink_{}", case_id),
+        );
+        let pipeline = apply_ddls(&ddls(&source_queue, &sink_queue), SpringConfig::default());
+
+        let n = rows.len() as i64;
+        for row in rows {
+            pipeline.push(&source_queue, row).unwrap();
+        }
+        // closes all panes with the rows above
+        let flush_row = SpringSourceRow::from_json(
+            r#"{"ts": "2099-01-01 00:00:00.000000000", "ticker": "IBM", "amount": 0}"#,
+        )
+        .unwrap();
+        pipeline.push(&source_queue, flush_row).unwrap();
+
+        let mut count = 0;
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while count < n && Instant::now() < deadline {
+            match pipeline.pop_non_blocking(&sink_queue).unwrap() {
+                Some(row) => count += row.get_not_null_by_index::<i64>(1).unwrap(),
+                None => thread::sleep(Duration::from_millis(10)),
+            }
+        }
+        prop_assert_eq!(count, n);
+    }
+}
@@ -0,0 +1,243 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use springql::{SpringConfig, SpringSourceRow};
+
+use crate::test_support::*;
+
+#[test]
+fn test_where_like_regexp_match() {
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          vin TEXT NOT NULL,
+          code TEXT NOT NULL,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_like (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_regexp (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pump_like AS
+          INSERT INTO sink_like (ts, n)
+          SELECT STREAM source_1.ts, source_1.n * 2 + 3
+          FROM source_1
+          WHERE source_1.vin LIKE 'WBA%' AND source_1.code LIKE 'E_4_';
+        "
+        .to_string(),
+        "
+        CREATE PUMP pump_regexp AS
+          INSERT INTO sink_regexp (ts, n)
+          SELECT STREAM source_1.ts, source_1.n
+          FROM source_1
+          WHERE REGEXP_MATCH(source_1.code, '^E0[0-9]{2}$');
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_like FOR sink_like
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_where_like'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_regexp FOR sink_regexp
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_where_regexp'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_1 FOR source_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_where_source'
+          );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    for (vin, code, n) in [
+        ("WBA1", "E042", 10),
+        ("WBS1", "E042", 20),
+        ("WBA2", "W100", 30),
+    ] {
+        let row = SpringSourceRow::from_json(&format!(
+            r#"{{"ts": "2022-01-01 13:00:00.000000000", "vin": "{}", "code": "{}", "n": {}}}"#,
+            vin, code, n
+        ))
+        .unwrap();
+        pipeline.push("q_where_source", row).unwrap();
+    }
+
+    let row = pipeline.pop("q_where_like").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 10 * 2 + 3);
+
+    let row = pipeline.pop("q_where_regexp").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 10);
+    let row = pipeline.pop("q_where_regexp").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 20);
+}
+
+#[test]
+fn test_invalid_regexp() {
+    let pipeline = apply_ddls(
+        &[
+            "
+            CREATE STREAM source_1 (
+              ts TIMESTAMP NOT NULL ROWTIME,
+              code TEXT NOT NULL
+            );
+            "
+            .to_string(),
+            "
+            CREATE STREAM sink_1 (
+              ts TIMESTAMP NOT NULL ROWTIME,
+              code TEXT NOT NULL
+            );
+            "
+            .to_string(),
+        ],
+        SpringConfig::default(),
+    );
+    assert!(pipeline
+        .command(
+            "
+            CREATE PUMP pump_1 AS
+              INSERT INTO sink_1 (ts, code)
+              SELECT STREAM source_1.ts, source_1.code
+              FROM source_1
+              WHERE REGEXP_MATCH(source_1.code, '(');
+            "
+        )
+        .is_err());
+}
+
+#[test]
+fn test_where_in_between() {
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          code INTEGER NOT NULL,
+          speed FLOAT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          code INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pump_1 AS
+          INSERT INTO sink_1 (ts, code)
+          SELECT STREAM source_1.ts, source_1.code
+          FROM source_1
+          WHERE source_1.code IN (0x10, 0x20, 0x30) AND source_1.speed NOT BETWEEN 0.0 AND 10.0;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_1 FOR sink_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_where_in_between'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_1 FOR source_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_where_in_between_source'
+          );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    for (code, speed) in [(0x10, 5.0), (0x11, 50.0), (0x20, 50.0)] {
+        let row = SpringSourceRow::from_json(&format!(
+            r#"{{"ts": "2022-01-01 13:00:00.000000000", "code": {}, "speed": {:.1}}}"#,
+            code, speed
+        ))
+        .unwrap();
+        pipeline.push("q_where_in_between_source", row).unwrap();
+    }
+
+    let row = pipeline.pop("q_where_in_between").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 0x20);
+}
+
+#[test]
+fn test_null_semantics() {
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          code INTEGER,
+          fallback INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          code INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pump_1 AS
+          INSERT INTO sink_1 (ts, code)
+          SELECT STREAM source_1.ts, COALESCE(NULLIF(source_1.code, -1), source_1.fallback)
+          FROM source_1
+          WHERE source_1.code IS DISTINCT FROM 0;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_1 FOR sink_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_null_semantics'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_1 FOR source_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_null_semantics_source'
+          );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    for (code, fallback) in [("null", 1), ("0", 2), ("-1", 3), ("4", 5)] {
+        let row = SpringSourceRow::from_json(&format!(
+            r#"{{"ts": "2022-01-01 13:00:00.000000000", "code": {}, "fallback": {}}}"#,
+            code, fallback
+        ))
+        .unwrap();
+        pipeline.push("q_null_semantics_source", row).unwrap();
+    }
+
+    for expected in [1, 3, 4] {
+        let row = pipeline.pop("q_null_semantics").unwrap();
+        assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), expected);
+    }
+}
@@ -0,0 +1,98 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use springql::{SpringConfig, SpringSourceRow};
+
+use crate::test_support::*;
+
+fn ddls(alignment: &str) -> Vec<String> {
+    vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          avg_n FLOAT NOT NULL
+        );
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE PUMP pump_1 AS
+          INSERT INTO sink_1 (avg_n)
+          SELECT STREAM AVG(source_1.n) AS avg_n FROM source_1
+          FIXED WINDOW DURATION_SECS(3600), DURATION_SECS(0) {};
+        ",
+            alignment
+        ),
+        "
+        CREATE SINK WRITER q_sink_1 FOR sink_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_window_alignment_sink'
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_1 FOR source_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_window_alignment_source'
+          );
+        "
+        .to_string(),
+    ]
+}
+
+#[test]
+fn test_window_aligned_to_wall_clock() {
+    // panes open at xx:30 in UTC
+    let pipeline = apply_ddls(&ddls("ALIGNED TO '00:30 +09:00'"), SpringConfig::default());
+
+    for (ts, n) in [
+        ("2022-01-01 13:10:00.000000000", 10),
+        ("2022-01-01 13:20:00.000000000", 20),
+        ("2022-01-01 13:40:00.000000000", 100),
+        ("2022-01-01 14:40:00.000000000", 0),
+    ] {
+        let row =
+            SpringSourceRow::from_json(&format!(r#"{{"ts": "{}", "n": {}}}"#, ts, n)).unwrap();
+        pipeline.push("q_window_alignment_source", row).unwrap();
+    }
+
+    for expected in [15.0, 100.0] {
+        let row = pipeline.pop("q_window_alignment_sink").unwrap();
+        assert_eq!(row.get_not_null_by_index::<f32>(0).unwrap(), expected);
+    }
+}
+
+#[test]
+fn test_window_aligned_to_unit() {
+    let pipeline = apply_ddls(&ddls("ALIGNED TO HOUR"), SpringConfig::default());
+
+    for (ts, n) in [
+        ("2022-01-01 13:10:00.000000000", 10),
+        ("2022-01-01 13:40:00.000000000", 20),
+        ("2022-01-01 14:10:00.000000000", 0),
+    ] {
+        let row =
+            SpringSourceRow::from_json(&format!(r#"{{"ts": "{}", "n": {}}}"#, ts, n)).unwrap();
+        pipeline.push("q_window_alignment_source", row).unwrap();
+    }
+
+    let row = pipeline.pop("q_window_alignment_sink").unwrap();
+    assert_eq!(row.get_not_null_by_index::<f32>(0).unwrap(), 15.0);
+}
+
+#[test]
+fn test_window_not_aligned_to_unit() {
+    let mut ddls = ddls("ALIGNED TO DAY");
+    ddls[2] = ddls[2].replace("DURATION_SECS(3600)", "DURATION_SECS(7000)");
+    let pump_ddl = ddls.remove(2);
+    let pipeline = apply_ddls(&ddls[..2], SpringConfig::default());
+
+    assert!(pipeline.command(pump_ddl).is_err());
+}
@@ -0,0 +1,100 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use springql::{SpringConfig, SpringSourceRow};
+
+use crate::test_support::*;
+
+#[test]
+fn test_window_metadata() {
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_avg_by_ticker (
+          window_start TIMESTAMP NOT NULL ROWTIME,
+          window_end TIMESTAMP NOT NULL,
+          n_rows BIGINT NOT NULL,
+          ticker TEXT NOT NULL,
+          avg_amount FLOAT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP avg_by_ticker AS
+          INSERT INTO sink_avg_by_ticker (window_start, window_end, n_rows, ticker, avg_amount)
+          SELECT STREAM
+            WINDOW_START AS window_start,
+            WINDOW_END AS window_end,
+            PANE_ROW_COUNT AS n_rows,
+            source_trade.ticker AS ticker,
+            AVG(source_trade.amount) AS avg_amount
+          FROM source_trade
+          GROUP BY ticker
+          FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_avg_by_ticker FOR sink_avg_by_ticker
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_window_metadata_sink'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_trade FOR source_trade
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_window_metadata_source'
+          );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    for json in [
+        r#"{"ts": "2020-01-01 00:00:00.000000000", "ticker": "ORCL", "amount": 10}"#,
+        r#"{"ts": "2020-01-01 00:00:01.000000000", "ticker": "ORCL", "amount": 20}"#,
+        r#"{"ts": "2020-01-01 00:00:09.000000000", "ticker": "GOOGL", "amount": 30}"#,
+        r#"{"ts": "2020-01-01 00:00:10.000000000", "ticker": "IBM", "amount": 50}"#, // closes [:00, :10)
+    ] {
+        let row = SpringSourceRow::from_json(json).unwrap();
+        pipeline.push("q_window_metadata_source", row).unwrap();
+    }
+
+    let mut rows = (0..2)
+        .map(|_| {
+            let row = pipeline.pop("q_window_metadata_sink").unwrap();
+            (
+                row.get_not_null_by_index::<String>(3).unwrap(),
+                row.get_not_null_by_index::<i64>(2).unwrap(),
+                row.get_not_null_by_index::<String>(0).unwrap(),
+                row.get_not_null_by_index::<String>(1).unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+    rows.sort();
+
+    let (window_start, window_end) = (
+        "2020-01-01 00:00:00.000000000".to_string(),
+        "2020-01-01 00:00:10.000000000".to_string(),
+    );
+    assert_eq!(
+        rows,
+        vec![
+            (
+                "GOOGL".to_string(),
+                1,
+                window_start.clone(),
+                window_end.clone()
+            ),
+            ("ORCL".to_string(), 2, window_start, window_end),
+        ]
+    );
+}
@@ -0,0 +1,182 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use springql::{SpringConfig, SpringPipeline, SpringSourceRow};
+
+use crate::test_support::*;
+
+/// (panes_opened, panes_closed, late_merges, emitted_rows)
+type Triggers = (i64, i64, i64, i64);
+
+fn setup(name: &str, window_clause: &str) -> SpringPipeline {
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          avg_n FLOAT NOT NULL
+        );
+        "
+        .to_string(),
+        format!(
+            "
+            CREATE PUMP pump_1 AS
+              INSERT INTO sink_1 (avg_n)
+              SELECT STREAM AVG(source_1.n) AS avg_n FROM source_1
+              {};
+            ",
+            window_clause
+        ),
+        format!(
+            "
+            CREATE SINK WRITER q_sink_1 FOR sink_1
+              TYPE IN_MEMORY_QUEUE OPTIONS (
+                NAME 'q_{}_sink'
+            );
+            ",
+            name
+        ),
+        format!(
+            "
+            CREATE SOURCE READER q_source_1 FOR source_1
+              TYPE IN_MEMORY_QUEUE OPTIONS (
+                NAME 'q_{}_source'
+              );
+            ",
+            name
+        ),
+        "
+        CREATE SINK STREAM sink_triggers (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          task_id TEXT NOT NULL,
+          panes_opened BIGINT NOT NULL,
+          panes_closed BIGINT NOT NULL,
+          late_merges BIGINT NOT NULL,
+          emitted_rows BIGINT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_triggers AS
+          INSERT INTO sink_triggers (ts, task_id, panes_opened, panes_closed, late_merges, emitted_rows)
+          SELECT STREAM
+            spring._task_metrics.ts,
+            spring._task_metrics.task_id,
+            spring._task_metrics.panes_opened,
+            spring._task_metrics.panes_closed,
+            spring._task_metrics.late_merges,
+            spring._task_metrics.emitted_rows
+          FROM spring._task_metrics;
+        "
+        .to_string(),
+        format!(
+            "
+            CREATE SINK WRITER q_sink_triggers FOR sink_triggers
+              TYPE IN_MEMORY_QUEUE OPTIONS (
+                NAME 'q_{}_triggers'
+              );
+            ",
+            name
+        ),
+    ];
+
+    let config = SpringConfig::from_toml(
+        "
+        [system_stream]
+        task_metrics_interval_msec = 100
+        ",
+    )
+    .unwrap();
+    apply_ddls(&ddls, config)
+}
+
+fn push(pipeline: &SpringPipeline, name: &str, rows: &[(&str, i32)]) {
+    for (ts, n) in rows {
+        let row =
+            SpringSourceRow::from_json(&format!(r#"{{"ts": "{}", "n": {}}}"#, ts, n)).unwrap();
+        pipeline.push(&format!("q_{}_source", name), row).unwrap();
+    }
+}
+
+/// Pops `spring._task_metrics` rows until `pump_1` has emitted `emitted_rows` rows.
+fn wait_triggers(pipeline: &SpringPipeline, name: &str, emitted_rows: i64) -> Triggers {
+    loop {
+        let row = pipeline.pop(&format!("q_{}_triggers", name)).unwrap();
+        if row.get_not_null_by_index::<String>(1).unwrap() != "pump_1" {
+            continue;
+        }
+        let triggers = (
+            row.get_not_null_by_index::<i64>(2).unwrap(),
+            row.get_not_null_by_index::<i64>(3).unwrap(),
+            row.get_not_null_by_index::<i64>(4).unwrap(),
+            row.get_not_null_by_index::<i64>(5).unwrap(),
+        );
+        if triggers.3 >= emitted_rows {
+            return triggers;
+        }
+    }
+}
+
+#[test]
+fn test_window_triggers_at_pane_boundary() {
+    let name = "window_triggers_boundary";
+    let pipeline = setup(
+        name,
+        "FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0) TRACE TRIGGERS",
+    );
+
+    push(
+        &pipeline,
+        name,
+        &[
+            ("2022-01-01 13:00:00.000000000", 100),
+            // belongs to [:10, :20) and closes [:00, :10)
+            ("2022-01-01 13:00:10.000000000", 200),
+            ("2022-01-01 13:00:20.000000000", 300),
+        ],
+    );
+
+    for expected in [100.0, 200.0] {
+        let row = pipeline.pop(&format!("q_{}_sink", name)).unwrap();
+        assert_eq!(row.get_not_null_by_index::<f32>(0).unwrap(), expected);
+    }
+
+    assert_eq!(wait_triggers(&pipeline, name, 2), (3, 2, 0, 2));
+}
+
+#[test]
+fn test_window_triggers_with_late_merge() {
+    let name = "window_triggers_late_merge";
+    let pipeline = setup(
+        name,
+        "FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0) ALLOWED LATENESS DURATION_SECS(5) TRACE TRIGGERS",
+    );
+
+    push(
+        &pipeline,
+        name,
+        &[
+            ("2022-01-01 13:00:01.000000000", 100),
+            ("2022-01-01 13:00:11.000000000", 300),
+            // late but within allowed lateness: merged into [:00, :10)
+            ("2022-01-01 13:00:07.000000000", 200),
+            // too late: dropped without any trigger
+            ("2022-01-01 13:00:03.000000000", 1000),
+            // removes [:00, :10) without emission and closes [:10, :20)
+            ("2022-01-01 13:00:21.000000000", 0),
+        ],
+    );
+
+    for expected in [100.0, 150.0, 300.0] {
+        let row = pipeline.pop(&format!("q_{}_sink", name)).unwrap();
+        assert_eq!(row.get_not_null_by_index::<f32>(0).unwrap(), expected);
+    }
+
+    assert_eq!(wait_triggers(&pipeline, name, 3), (3, 2, 1, 3));
+}
@@ -11,13 +11,25 @@ use springql_foreign_service::{
 };
 use springql_test_logger::setup_test_logger;
 
-fn t(n_generic_worker_threads: u16, n_source_worker_threads: u16) {
+fn t(
+    n_generic_worker_threads: u16,
+    n_source_worker_threads: u16,
+    adaptive_placement: bool,
+    thread_per_core: bool,
+    micro_batch: bool,
+) {
     setup_test_logger();
 
     let worker_config = SpringWorkerConfig {
         n_generic_worker_threads,
         n_source_worker_threads,
         sleep_msec_no_row: 100,
+        adaptive_placement,
+        adaptive_placement_max_task_usec: 1000,
+        thread_per_core,
+        micro_batch,
+        micro_batch_max_size: 256,
+        micro_batch_latency_target_usec: 1000,
     };
 
     let json_oracle = json!({
@@ -106,20 +118,35 @@ fn t(n_generic_worker_threads: u16, n_source_worker_threads: u16) {
 
 #[test]
 fn test_feat_1generic_1source() {
-    t(1, 1)
+    t(1, 1, false, false, false)
 }
 
 #[test]
 fn test_feat_5generic_1source() {
-    t(5, 1)
+    t(5, 1, false, false, false)
 }
 
 #[test]
 fn test_feat_1generic_5source() {
-    t(1, 5)
+    t(1, 5, false, false, false)
 }
 
 #[test]
 fn test_feat_5generic_5source() {
-    t(5, 5)
+    t(5, 5, false, false, false)
+}
+
+#[test]
+fn test_feat_1generic_1source_adaptive_placement() {
+    t(1, 1, true, false, false)
+}
+
+#[test]
+fn test_feat_2generic_2source_thread_per_core() {
+    t(2, 2, false, true, false)
+}
+
+#[test]
+fn test_feat_2generic_2source_micro_batch() {
+    t(2, 2, false, false, true)
 }
@@ -0,0 +1,33 @@
+-- Average amount by ticker in 10-second fixed windows.
+
+CREATE SOURCE STREAM source_trade (
+  ts TIMESTAMP NOT NULL ROWTIME,
+  ticker TEXT NOT NULL,
+  amount INTEGER NOT NULL
+);
+
+CREATE SINK STREAM sink_avg_by_ticker (
+  ts TIMESTAMP NOT NULL ROWTIME,
+  ticker TEXT NOT NULL,
+  avg_amount FLOAT NOT NULL
+);
+
+CREATE PUMP avg_by_ticker AS
+  INSERT INTO sink_avg_by_ticker (ts, ticker, avg_amount)
+  SELECT STREAM
+    FLOOR_TIME(source_trade.ts, DURATION_SECS(10)) AS min_ts,
+    source_trade.ticker AS ticker,
+    AVG(source_trade.amount) AS avg_amount
+  FROM source_trade
+  GROUP BY min_ts, ticker
+  FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+
+CREATE SINK WRITER queue_avg_by_ticker FOR sink_avg_by_ticker
+  TYPE IN_MEMORY_QUEUE OPTIONS (
+    NAME 'fixed_window_avg_by_ticker_sink'
+  );
+
+CREATE SOURCE READER queue_trade FOR source_trade
+  TYPE IN_MEMORY_QUEUE OPTIONS (
+    NAME 'fixed_window_avg_by_ticker_source'
+  );
@@ -0,0 +1,29 @@
+-- Trades with amount over 20.
+
+CREATE SOURCE STREAM source_trade (
+  ts TIMESTAMP NOT NULL ROWTIME,
+  ticker TEXT NOT NULL,
+  amount INTEGER NOT NULL
+);
+
+CREATE SINK STREAM sink_large_trade (
+  ts TIMESTAMP NOT NULL ROWTIME,
+  ticker TEXT NOT NULL,
+  amount INTEGER NOT NULL
+);
+
+CREATE PUMP large_trade AS
+  INSERT INTO sink_large_trade (ts, ticker, amount)
+  SELECT STREAM source_trade.ts, source_trade.ticker, source_trade.amount
+  FROM source_trade
+  WHERE source_trade.amount > 20;
+
+CREATE SINK WRITER queue_large_trade FOR sink_large_trade
+  TYPE IN_MEMORY_QUEUE OPTIONS (
+    NAME 'where_filter_sink'
+  );
+
+CREATE SOURCE READER queue_trade FOR source_trade
+  TYPE IN_MEMORY_QUEUE OPTIONS (
+    NAME 'where_filter_source'
+  );